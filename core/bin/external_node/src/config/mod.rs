@@ -4,14 +4,16 @@ use anyhow::Context;
 use serde::Deserialize;
 use url::Url;
 use zksync_basic_types::{Address, L1ChainId, L2ChainId};
-use zksync_config::ObjectStoreConfig;
+use zksync_config::{configs::database::MerkleTreeMode, ObjectStoreConfig};
 use zksync_core::{
     api_server::{
         tx_sender::TxSenderConfig,
         web3::{state::InternalApiConfig, Namespace},
     },
     consensus,
+    metadata_calculator::MetadataCalculatorConfig,
     temp_config_store::decode_yaml,
+    tx_admission::TxAdmissionRules,
 };
 use zksync_types::{api::BridgeAddresses, fee_model::FeeParams};
 use zksync_web3_decl::{
@@ -129,6 +131,17 @@ pub struct OptionalENConfig {
     /// Maximum response body size in MiBs. Default is 10 MiB.
     #[serde(default = "OptionalENConfig::default_max_response_body_size_mb")]
     pub max_response_body_size_mb: usize,
+    /// Accounts that are not allowed to submit transactions via the API of this node. Unlike on the main node,
+    /// admission rules only restrict transaction submission; transactions synced from the main node are never filtered.
+    #[serde(default)]
+    pub tx_admission_denied_senders: Vec<Address>,
+    /// If non-empty, only these accounts are allowed to deploy contracts via the API of this node.
+    #[serde(default)]
+    pub tx_admission_allowed_deployers: Vec<Address>,
+    /// If non-empty, only these contracts are allowed to be called via the API of this node. Transactions
+    /// without calldata (i.e., plain transfers) are not restricted.
+    #[serde(default)]
+    pub tx_admission_allowed_contracts: Vec<Address>,
 
     // Other API config settings
     /// Interval between polling DB for pubsub (in ms).
@@ -348,6 +361,22 @@ impl OptionalENConfig {
         10
     }
 
+    pub fn tx_admission_rules(&self) -> TxAdmissionRules {
+        TxAdmissionRules {
+            denied_senders: self.tx_admission_denied_senders.iter().copied().collect(),
+            allowed_deployers: self
+                .tx_admission_allowed_deployers
+                .iter()
+                .copied()
+                .collect(),
+            allowed_contracts: self
+                .tx_admission_allowed_contracts
+                .iter()
+                .copied()
+                .collect(),
+        }
+    }
+
     pub fn polling_interval(&self) -> Duration {
         Duration::from_millis(self.polling_interval)
     }
@@ -584,6 +613,38 @@ impl ExternalNodeConfig {
             consensus: read_consensus_config().context("read_consensus_config()")?,
         })
     }
+
+    /// Returns the diamond proxy address, checking that it matches the one returned by the main node
+    /// if it is specified in the config.
+    pub fn diamond_proxy_addr(&self) -> anyhow::Result<Address> {
+        let remote_diamond_proxy_addr = self.remote.diamond_proxy_addr;
+        if let Some(addr) = self.optional.contracts_diamond_proxy_addr {
+            anyhow::ensure!(
+                addr == remote_diamond_proxy_addr,
+                "Diamond proxy address {addr:?} specified in config doesn't match one returned by main node \
+                 ({remote_diamond_proxy_addr:?})"
+            );
+            Ok(addr)
+        } else {
+            tracing::info!(
+                "Diamond proxy address is not specified in config; will use address returned by main node: {remote_diamond_proxy_addr:?}"
+            );
+            Ok(remote_diamond_proxy_addr)
+        }
+    }
+
+    pub fn metadata_calculator_config(&self) -> MetadataCalculatorConfig {
+        MetadataCalculatorConfig {
+            db_path: self.required.merkle_tree_path.clone(),
+            mode: MerkleTreeMode::Lightweight,
+            delay_interval: self.optional.metadata_calculator_delay(),
+            max_l1_batches_per_iter: self.optional.max_l1_batches_per_tree_iter,
+            multi_get_chunk_size: self.optional.merkle_tree_multi_get_chunk_size,
+            block_cache_capacity: self.optional.merkle_tree_block_cache_size(),
+            memtable_capacity: self.optional.merkle_tree_memtable_capacity(),
+            stalled_writes_timeout: self.optional.merkle_tree_stalled_writes_timeout(),
+        }
+    }
 }
 
 fn env_var<T>(name: &str) -> T
@@ -1,5 +1,7 @@
 //! Tests for EN configuration.
 
+use std::collections::HashSet;
+
 use super::*;
 
 #[test]
@@ -25,6 +27,7 @@ fn parsing_optional_config_from_empty_env() {
         128 * BYTES_IN_MEGABYTE
     );
     assert_eq!(config.max_response_body_size(), 10 * BYTES_IN_MEGABYTE);
+    assert!(config.tx_admission_rules().is_empty());
 }
 
 #[test]
@@ -45,6 +48,10 @@ fn parsing_optional_config_from_env() {
         ("EN_MERKLE_TREE_MULTI_GET_CHUNK_SIZE", "1000"),
         ("EN_MERKLE_TREE_BLOCK_CACHE_SIZE_MB", "32"),
         ("EN_MAX_RESPONSE_BODY_SIZE_MB", "1"),
+        (
+            "EN_TX_ADMISSION_DENIED_SENDERS",
+            "0x0000000000000000000000000000000000000bad,0x0000000000000000000000000000000000001bad",
+        ),
     ];
     let env_vars = env_vars
         .into_iter()
@@ -72,4 +79,13 @@ fn parsing_optional_config_from_env() {
         32 * BYTES_IN_MEGABYTE
     );
     assert_eq!(config.max_response_body_size(), BYTES_IN_MEGABYTE);
+    let admission_rules = config.tx_admission_rules();
+    assert_eq!(
+        admission_rules.denied_senders,
+        HashSet::from([
+            Address::from_low_u64_be(0xbad),
+            Address::from_low_u64_be(0x1bad)
+        ])
+    );
+    assert!(admission_rules.allowed_deployers.is_empty());
 }
@@ -2,14 +2,18 @@
 
 use anyhow::Context as _;
 use zksync_basic_types::{L1BatchNumber, L2ChainId};
-use zksync_core::sync_layer::genesis::perform_genesis_if_needed;
+use zksync_core::{
+    block_reverter::{BlockReverter, BlockReverterFlags, L1ExecutedBatchesRevert, NodeRole},
+    reorg_detector::{self, ReorgDetector},
+    sync_layer::genesis::perform_genesis_if_needed,
+};
 use zksync_dal::ConnectionPool;
 use zksync_health_check::AppHealthCheck;
 use zksync_object_store::ObjectStoreFactory;
 use zksync_snapshots_applier::SnapshotsApplierConfig;
 use zksync_web3_decl::jsonrpsee::http_client::HttpClient;
 
-use crate::config::read_snapshots_recovery_config;
+use crate::config::{read_snapshots_recovery_config, ExternalNodeConfig};
 
 #[derive(Debug)]
 enum InitDecision {
@@ -97,3 +101,57 @@ pub(crate) async fn ensure_storage_initialized(
     }
     Ok(())
 }
+
+/// Reverts the node storage if a reorg is detected, or if reverting the pending L1 batch is requested.
+pub(crate) async fn revert_storage_if_needed(
+    config: &ExternalNodeConfig,
+    pool: &ConnectionPool,
+    main_node_client: &HttpClient,
+    revert_pending_l1_batch: bool,
+) -> anyhow::Result<()> {
+    let reverter = BlockReverter::new(
+        NodeRole::External,
+        config.required.state_cache_path.clone(),
+        config.required.merkle_tree_path.clone(),
+        None,
+        pool.clone(),
+        L1ExecutedBatchesRevert::Allowed,
+    );
+
+    let mut reorg_detector = ReorgDetector::new(main_node_client.clone(), pool.clone());
+    // We're checking for the reorg in the beginning because we expect that if reorg is detected during
+    // the node lifecycle, the node will exit the same way as it does with any other critical error,
+    // and would restart. Then, on the 2nd launch reorg would be detected here, then processed and the node
+    // will be able to operate normally afterwards.
+    match reorg_detector.check_consistency().await {
+        Ok(()) => {}
+        Err(reorg_detector::Error::ReorgDetected(last_correct_l1_batch)) => {
+            tracing::info!("Rolling back to l1 batch number {last_correct_l1_batch}");
+            reverter
+                .rollback_db(last_correct_l1_batch, BlockReverterFlags::all())
+                .await;
+            tracing::info!("Rollback successfully completed");
+        }
+        Err(err) => return Err(err).context("reorg_detector.check_consistency()"),
+    }
+    if revert_pending_l1_batch {
+        tracing::info!("Rolling pending L1 batch back..");
+        let mut connection = pool.access_storage().await?;
+        let sealed_l1_batch_number = connection
+            .blocks_dal()
+            .get_sealed_l1_batch_number()
+            .await
+            .context("Failed getting sealed L1 batch number")?
+            .context(
+                "Cannot roll back pending L1 batch since there are no L1 batches in Postgres",
+            )?;
+        drop(connection);
+
+        tracing::info!("Rolling back to l1 batch number {sealed_l1_batch_number}");
+        reverter
+            .rollback_db(sealed_l1_batch_number, BlockReverterFlags::all())
+            .await;
+        tracing::info!("Rollback successfully completed");
+    }
+    Ok(())
+}
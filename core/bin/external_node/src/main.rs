@@ -2,12 +2,10 @@ use std::{sync::Arc, time::Duration};
 
 use anyhow::Context as _;
 use clap::Parser;
-use metrics::EN_METRICS;
 use prometheus_exporter::PrometheusExporterConfig;
 use tokio::{sync::watch, task, time::sleep};
 use zksync_basic_types::{Address, L2ChainId};
 use zksync_concurrency::{ctx, limiter, scope, time};
-use zksync_config::configs::database::MerkleTreeMode;
 use zksync_core::{
     api_server::{
         execution_sandbox::VmConcurrencyLimiter,
@@ -15,13 +13,11 @@ use zksync_core::{
         tx_sender::{proxy::TxProxy, ApiContracts, TxSenderBuilder},
         web3::{ApiBuilder, Namespace},
     },
-    block_reverter::{BlockReverter, BlockReverterFlags, L1ExecutedBatchesRevert, NodeRole},
     commitment_generator::CommitmentGenerator,
     consensus,
     consistency_checker::ConsistencyChecker,
     l1_gas_price::MainNodeFeeParamsFetcher,
-    metadata_calculator::{MetadataCalculator, MetadataCalculatorConfig},
-    reorg_detector,
+    metadata_calculator::MetadataCalculator,
     reorg_detector::ReorgDetector,
     setup_sigint_handler,
     state_keeper::{
@@ -30,7 +26,7 @@ use zksync_core::{
     },
     sync_layer::{
         batch_status_updater::BatchStatusUpdater, external_io::ExternalIO, ActionQueue,
-        MainNodeClient, SyncState,
+        MainNodeClient, MainNodeHealthCheck, SyncState,
     },
 };
 use zksync_dal::{healthcheck::ConnectionPoolHealthCheck, ConnectionPool};
@@ -42,17 +38,29 @@ use zksync_web3_decl::jsonrpsee::http_client::HttpClient;
 
 use crate::{
     config::{observability::observability_config_from_env, ExternalNodeConfig},
-    helpers::MainNodeHealthCheck,
-    init::ensure_storage_initialized,
+    init::{ensure_storage_initialized, revert_storage_if_needed},
+    metrics::run_version_metrics_reporting,
+    node_builder::ExternalNodeBuilder,
 };
 
 mod config;
-mod helpers;
 mod init;
 mod metrics;
+mod node_builder;
 
 const RELEASE_MANIFEST: &str = include_str!("../../../../.github/release-please/manifest.json");
 
+/// Returns the server version specified in the release manifest.
+fn server_version() -> semver::Version {
+    let release_manifest: serde_json::Value = serde_json::from_str(RELEASE_MANIFEST)
+        .expect("release manifest is a valid json document; qed");
+    let release_manifest_version = release_manifest["core"].as_str().expect(
+        "a release-please manifest with \"core\" version field was specified at build time; qed.",
+    );
+    semver::Version::parse(release_manifest_version)
+        .expect("version in manifest is a correct semver format; qed")
+}
+
 /// Creates the state keeper configured to work in the external node mode.
 #[allow(clippy::too_many_arguments)]
 async fn build_state_keeper(
@@ -117,14 +125,6 @@ async fn init_tasks(
     app_health: &AppHealthCheck,
     stop_receiver: watch::Receiver<bool>,
 ) -> anyhow::Result<()> {
-    let release_manifest: serde_json::Value = serde_json::from_str(RELEASE_MANIFEST)
-        .expect("release manifest is a valid json document; qed");
-    let release_manifest_version = release_manifest["core"].as_str().expect(
-        "a release-please manifest with \"core\" version field was specified at build time; qed.",
-    );
-
-    let version = semver::Version::parse(release_manifest_version)
-        .expect("version in manifest is a correct semver format; qed");
     // Create components.
     let fee_params_fetcher = Arc::new(MainNodeFeeParamsFetcher::new(main_node_client.clone()));
 
@@ -137,23 +137,11 @@ async fn init_tasks(
         config.optional.miniblock_seal_queue_capacity,
     );
     task_handles.push(tokio::spawn(miniblock_sealer.run()));
-    let pool = connection_pool.clone();
-    task_handles.push(tokio::spawn(async move {
-        loop {
-            let protocol_version = pool
-                .access_storage()
-                .await
-                .unwrap()
-                .protocol_versions_dal()
-                .last_used_version_id()
-                .await
-                .map(|version| version as u16);
-
-            EN_METRICS.version[&(format!("{}", version), protocol_version)].set(1);
-
-            tokio::time::sleep(Duration::from_secs(10)).await;
-        }
-    }));
+    task_handles.push(tokio::spawn(run_version_metrics_reporting(
+        connection_pool.clone(),
+        server_version(),
+        stop_receiver.clone(),
+    )));
 
     let state_keeper = build_state_keeper(
         action_queue,
@@ -222,35 +210,12 @@ async fn init_tasks(
 
     let singleton_pool_builder = ConnectionPool::singleton(&config.postgres.database_url);
 
-    let metadata_calculator_config = MetadataCalculatorConfig {
-        db_path: config.required.merkle_tree_path.clone(),
-        mode: MerkleTreeMode::Lightweight,
-        delay_interval: config.optional.metadata_calculator_delay(),
-        max_l1_batches_per_iter: config.optional.max_l1_batches_per_tree_iter,
-        multi_get_chunk_size: config.optional.merkle_tree_multi_get_chunk_size,
-        block_cache_capacity: config.optional.merkle_tree_block_cache_size(),
-        memtable_capacity: config.optional.merkle_tree_memtable_capacity(),
-        stalled_writes_timeout: config.optional.merkle_tree_stalled_writes_timeout(),
-    };
-    let metadata_calculator = MetadataCalculator::new(metadata_calculator_config, None)
+    let metadata_calculator = MetadataCalculator::new(config.metadata_calculator_config(), None)
         .await
         .context("failed initializing metadata calculator")?;
     app_health.insert_component(metadata_calculator.tree_health_check());
 
-    let remote_diamond_proxy_addr = config.remote.diamond_proxy_addr;
-    let diamond_proxy_addr = if let Some(addr) = config.optional.contracts_diamond_proxy_addr {
-        anyhow::ensure!(
-            addr == remote_diamond_proxy_addr,
-            "Diamond proxy address {addr:?} specified in config doesn't match one returned by main node \
-             ({remote_diamond_proxy_addr:?})"
-        );
-        addr
-    } else {
-        tracing::info!(
-            "Diamond proxy address is not specified in config; will use address returned by main node: {remote_diamond_proxy_addr:?}"
-        );
-        remote_diamond_proxy_addr
-    };
+    let diamond_proxy_addr = config.diamond_proxy_addr()?;
 
     let consistency_checker = ConsistencyChecker::new(
         &config
@@ -298,6 +263,8 @@ async fn init_tasks(
     let updater_handle = task::spawn(batch_status_updater.run(stop_receiver.clone()));
     let fee_address_migration_handle =
         task::spawn(state_keeper.run_fee_address_migration(connection_pool.clone()));
+    let logs_bloom_backfill_handle =
+        task::spawn(state_keeper.run_logs_bloom_backfill(connection_pool.clone()));
     let sk_handle = task::spawn(state_keeper.run());
     let fee_params_fetcher_handle =
         tokio::spawn(fee_params_fetcher.clone().run(stop_receiver.clone()));
@@ -313,11 +280,15 @@ async fn init_tasks(
                 .run_account_nonce_sweeper(proxy_cache_updater_pool.clone(), stop_receiver.clone()),
         );
 
-        let tx_sender_builder = TxSenderBuilder::new(
+        let mut tx_sender_builder = TxSenderBuilder::new(
             config.clone().into(),
             connection_pool.clone(),
             Arc::new(tx_proxy),
         );
+        let admission_rules = config.optional.tx_admission_rules();
+        if !admission_rules.is_empty() {
+            tx_sender_builder = tx_sender_builder.with_admission_policy(Arc::new(admission_rules));
+        }
 
         if config.optional.transactions_per_sec_limit.is_some() {
             tracing::warn!("`transactions_per_sec_limit` option is deprecated and ignored");
@@ -414,6 +385,7 @@ async fn init_tasks(
     task_handles.extend([
         sk_handle,
         fee_address_migration_handle,
+        logs_bloom_backfill_handle,
         updater_handle,
         tree_handle,
         consistency_checker_handle,
@@ -455,10 +427,14 @@ struct Cli {
     /// This is an experimental and incomplete feature; do not use unless you know what you're doing.
     #[arg(long, conflicts_with = "enable_consensus")]
     enable_snapshots_recovery: bool,
+    /// Runs the node using the node framework instead of the legacy task wiring.
+    ///
+    /// This is an experimental feature; do not use unless you know what you're doing.
+    #[arg(long)]
+    use_node_framework: bool,
 }
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
+fn main() -> anyhow::Result<()> {
     // Initial setup.
     let opt = Cli::parse();
 
@@ -485,8 +461,12 @@ async fn main() -> anyhow::Result<()> {
         tracing::info!("No sentry URL was provided");
     }
 
-    let mut config = ExternalNodeConfig::collect()
-        .await
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .context("failed creating Tokio runtime")?;
+    let mut config = runtime
+        .block_on(ExternalNodeConfig::collect())
         .context("Failed to load external node config")?;
     if opt.enable_consensus {
         // This is more of a sanity check; the mutual exclusion of `enable_consensus` and `enable_snapshots_recovery`
@@ -506,6 +486,15 @@ async fn main() -> anyhow::Result<()> {
         ConnectionPool::global_config().set_long_connection_threshold(threshold)?;
     }
 
+    if opt.use_node_framework {
+        // The node framework manages its own runtime.
+        drop(runtime);
+        return ExternalNodeBuilder::new(config, &opt)?.build()?.run();
+    }
+    runtime.block_on(run_node(opt, config))
+}
+
+async fn run_node(opt: Cli, config: ExternalNodeConfig) -> anyhow::Result<()> {
     let connection_pool = ConnectionPool::builder(
         &config.postgres.database_url,
         config.postgres.max_connections,
@@ -562,50 +551,13 @@ async fn main() -> anyhow::Result<()> {
     .await?;
 
     // Revert the storage if needed.
-    let reverter = BlockReverter::new(
-        NodeRole::External,
-        config.required.state_cache_path.clone(),
-        config.required.merkle_tree_path.clone(),
-        None,
-        connection_pool.clone(),
-        L1ExecutedBatchesRevert::Allowed,
-    );
-
-    let mut reorg_detector = ReorgDetector::new(main_node_client.clone(), connection_pool.clone());
-    // We're checking for the reorg in the beginning because we expect that if reorg is detected during
-    // the node lifecycle, the node will exit the same way as it does with any other critical error,
-    // and would restart. Then, on the 2nd launch reorg would be detected here, then processed and the node
-    // will be able to operate normally afterwards.
-    match reorg_detector.check_consistency().await {
-        Ok(()) => {}
-        Err(reorg_detector::Error::ReorgDetected(last_correct_l1_batch)) => {
-            tracing::info!("Rolling back to l1 batch number {last_correct_l1_batch}");
-            reverter
-                .rollback_db(last_correct_l1_batch, BlockReverterFlags::all())
-                .await;
-            tracing::info!("Rollback successfully completed");
-        }
-        Err(err) => return Err(err).context("reorg_detector.check_consistency()"),
-    }
-    if opt.revert_pending_l1_batch {
-        tracing::info!("Rolling pending L1 batch back..");
-        let mut connection = connection_pool.access_storage().await?;
-        let sealed_l1_batch_number = connection
-            .blocks_dal()
-            .get_sealed_l1_batch_number()
-            .await
-            .context("Failed getting sealed L1 batch number")?
-            .context(
-                "Cannot roll back pending L1 batch since there are no L1 batches in Postgres",
-            )?;
-        drop(connection);
-
-        tracing::info!("Rolling back to l1 batch number {sealed_l1_batch_number}");
-        reverter
-            .rollback_db(sealed_l1_batch_number, BlockReverterFlags::all())
-            .await;
-        tracing::info!("Rollback successfully completed");
-    }
+    revert_storage_if_needed(
+        &config,
+        &connection_pool,
+        &main_node_client,
+        opt.revert_pending_l1_batch,
+    )
+    .await?;
 
     let (stop_sender, stop_receiver) = watch::channel(false);
     init_tasks(
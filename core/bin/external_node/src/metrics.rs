@@ -1,4 +1,8 @@
+use std::time::Duration;
+
+use tokio::sync::watch;
 use vise::{Gauge, LabeledFamily, Metrics};
+use zksync_dal::ConnectionPool;
 
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "external_node")]
@@ -9,3 +13,29 @@ pub(crate) struct EnMetrics {
 
 #[vise::register]
 pub(crate) static EN_METRICS: vise::Global<EnMetrics> = vise::Global::new();
+
+/// Periodically reports the server version and the last used protocol version of the node.
+pub(crate) async fn run_version_metrics_reporting(
+    pool: ConnectionPool,
+    server_version: semver::Version,
+    mut stop_receiver: watch::Receiver<bool>,
+) -> anyhow::Result<()> {
+    const UPDATE_INTERVAL: Duration = Duration::from_secs(10);
+
+    while !*stop_receiver.borrow() {
+        let protocol_version = pool
+            .access_storage()
+            .await?
+            .protocol_versions_dal()
+            .last_used_version_id()
+            .await
+            .map(|version| version as u16);
+        EN_METRICS.version[&(server_version.to_string(), protocol_version)].set(1);
+
+        // Error here corresponds to a timeout w/o `stop_receiver` changed; we're OK with this.
+        tokio::time::timeout(UPDATE_INTERVAL, stop_receiver.changed())
+            .await
+            .ok();
+    }
+    Ok(())
+}
@@ -0,0 +1,417 @@
+//! Wiring of the external node using the node framework.
+
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use prometheus_exporter::PrometheusExporterConfig;
+use zksync_config::{configs::api::HealthCheckConfig, PostgresConfig};
+use zksync_core::api_server::{tx_sender::ApiContracts, web3::Namespace};
+use zksync_dal::{healthcheck::ConnectionPoolHealthCheck, ConnectionPool};
+use zksync_node_framework::{
+    implementations::{
+        layers::{
+            batch_status_updater::BatchStatusUpdaterLayer,
+            commitment_generator::CommitmentGeneratorLayer,
+            consensus::ConsensusLayer,
+            consistency_checker::ConsistencyCheckerLayer,
+            fee_input::MainNodeFeeParamsFetcherLayer,
+            healtcheck_server::HealthCheckLayer,
+            main_node_client::MainNodeClientLayer,
+            metadata_calculator::MetadataCalculatorLayer,
+            pools_layer::PoolsLayerBuilder,
+            postgres_metrics::PostgresMetricsLayer,
+            prometheus_exporter::PrometheusExporterLayer,
+            reorg_detector::ReorgDetectorLayer,
+            sigint::SigintHandlerLayer,
+            state_keeper::{
+                external_io::ExternalIOLayer, main_batch_executor::MainBatchExecutorLayer,
+                StateKeeperLayer,
+            },
+            web3_api::{
+                server::{Web3ServerLayer, Web3ServerOptionalConfig},
+                tx_sender::{PostgresStorageCachesConfig, TxSenderLayer},
+                tx_sink::TxSinkLayer,
+            },
+        },
+        resources::{
+            healthcheck::AppHealthCheckResource, main_node_client::MainNodeClientResource,
+            pools::MasterPoolResource,
+        },
+    },
+    service::{ServiceContext, StopReceiver, ZkStackService},
+    task::Task,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+use crate::{
+    config::{self, ExternalNodeConfig},
+    init::{ensure_storage_initialized, revert_storage_if_needed},
+    metrics::run_version_metrics_reporting,
+    server_version, Cli,
+};
+
+/// Builder of the external node based on the node framework.
+///
+/// Each `add_*` method adds layers for a single node component, so custom node flavors (e.g., an API-only replica
+/// without a Merkle tree) can be assembled by skipping some of them.
+pub(crate) struct ExternalNodeBuilder {
+    node: ZkStackService,
+    config: ExternalNodeConfig,
+    enable_snapshots_recovery: bool,
+    revert_pending_l1_batch: bool,
+}
+
+impl ExternalNodeBuilder {
+    pub fn new(config: ExternalNodeConfig, opt: &Cli) -> anyhow::Result<Self> {
+        Ok(Self {
+            node: ZkStackService::new()?,
+            config,
+            enable_snapshots_recovery: opt.enable_snapshots_recovery,
+            revert_pending_l1_batch: opt.revert_pending_l1_batch,
+        })
+    }
+
+    fn add_sigint_handler_layer(mut self) -> anyhow::Result<Self> {
+        self.node.add_layer(SigintHandlerLayer);
+        Ok(self)
+    }
+
+    fn add_healthcheck_layer(mut self) -> anyhow::Result<Self> {
+        let optional = &self.config.optional;
+        let healthcheck_config = HealthCheckConfig {
+            port: self.config.required.healthcheck_port,
+            slow_time_limit_ms: optional
+                .healthcheck_slow_time_limit()
+                .map(|limit| limit.as_millis() as u64),
+            hard_time_limit_ms: optional
+                .healthcheck_hard_time_limit()
+                .map(|limit| limit.as_millis() as u64),
+        };
+        self.node.add_layer(HealthCheckLayer(healthcheck_config));
+        Ok(self)
+    }
+
+    fn add_prometheus_exporter_layer(mut self) -> anyhow::Result<Self> {
+        if let Some(port) = self.config.optional.prometheus_port {
+            let prometheus_config = PrometheusExporterConfig::pull(port);
+            self.node
+                .add_layer(PrometheusExporterLayer(prometheus_config));
+        }
+        Ok(self)
+    }
+
+    fn add_pools_layer(mut self) -> anyhow::Result<Self> {
+        // The external node uses a single database, so both pools point to it.
+        let database_url = self.config.postgres.database_url.clone();
+        let config = PostgresConfig {
+            master_url: Some(database_url.clone()),
+            replica_url: Some(database_url),
+            prover_url: None,
+            max_connections: Some(self.config.postgres.max_connections),
+            acquire_timeout_sec: None,
+            statement_timeout_sec: None,
+            long_connection_threshold_ms: None,
+            slow_query_threshold_ms: None,
+        };
+        let pools_layer = PoolsLayerBuilder::empty(config)
+            .with_master(true)
+            .with_replica(true)
+            .build();
+        self.node.add_layer(pools_layer);
+        Ok(self)
+    }
+
+    fn add_main_node_client_layer(mut self) -> anyhow::Result<Self> {
+        let main_node_url = self.config.required.main_node_url()?;
+        tracing::info!("Main node URL is: {main_node_url}");
+        self.node.add_layer(MainNodeClientLayer::new(main_node_url));
+        Ok(self)
+    }
+
+    fn add_postgres_metrics_layer(mut self) -> anyhow::Result<Self> {
+        self.node.add_layer(PostgresMetricsLayer);
+        Ok(self)
+    }
+
+    fn add_storage_initialization_layer(mut self) -> anyhow::Result<Self> {
+        self.node.add_layer(StorageInitializationLayer {
+            config: self.config.clone(),
+            enable_snapshots_recovery: self.enable_snapshots_recovery,
+            revert_pending_l1_batch: self.revert_pending_l1_batch,
+        });
+        Ok(self)
+    }
+
+    fn add_version_metrics_layer(mut self) -> anyhow::Result<Self> {
+        self.node.add_layer(VersionMetricsLayer);
+        Ok(self)
+    }
+
+    fn add_state_keeper_layer(mut self) -> anyhow::Result<Self> {
+        // We only need call traces on the external node if the `debug_` namespace is enabled.
+        let save_call_traces = self
+            .config
+            .optional
+            .api_namespaces()
+            .contains(&Namespace::Debug);
+        let external_io_layer = ExternalIOLayer::new(
+            self.config.remote.l2_chain_id,
+            self.config.remote.l2_erc20_bridge_addr,
+            self.config.optional.miniblock_seal_queue_capacity,
+        );
+        let batch_executor_layer = MainBatchExecutorLayer::for_external_node(
+            self.config.required.state_cache_path.clone(),
+            save_call_traces,
+            self.config.optional.enum_index_migration_chunk_size,
+        );
+        self.node
+            .add_layer(external_io_layer)
+            .add_layer(batch_executor_layer)
+            .add_layer(StateKeeperLayer);
+        Ok(self)
+    }
+
+    fn add_consensus_layer(mut self) -> anyhow::Result<Self> {
+        let consensus = match self.config.consensus.clone() {
+            Some(consensus_config) => {
+                let secrets = config::read_consensus_secrets()
+                    .context("config::read_consensus_secrets()")?
+                    .context("consensus secrets missing")?;
+                Some((consensus_config, secrets))
+            }
+            None => None,
+        };
+        self.node
+            .add_layer(ConsensusLayer::external_node(consensus));
+        Ok(self)
+    }
+
+    fn add_reorg_detector_layer(mut self) -> anyhow::Result<Self> {
+        self.node.add_layer(ReorgDetectorLayer);
+        Ok(self)
+    }
+
+    fn add_metadata_calculator_layer(mut self) -> anyhow::Result<Self> {
+        let metadata_calculator_config = self.config.metadata_calculator_config();
+        self.node.add_layer(
+            MetadataCalculatorLayer::new(metadata_calculator_config).with_tree_api_client(),
+        );
+        Ok(self)
+    }
+
+    fn add_consistency_checker_layer(mut self) -> anyhow::Result<Self> {
+        let l1_client_url = self
+            .config
+            .required
+            .eth_client_url()
+            .context("L1 client URL is incorrect")?;
+        self.node.add_layer(ConsistencyCheckerLayer::new(
+            l1_client_url,
+            self.config.diamond_proxy_addr()?,
+            10, // TODO (BFT-97): Make it a part of a proper EN config
+        ));
+        Ok(self)
+    }
+
+    fn add_commitment_generator_layer(mut self) -> anyhow::Result<Self> {
+        self.node.add_layer(CommitmentGeneratorLayer);
+        Ok(self)
+    }
+
+    fn add_batch_status_updater_layer(mut self) -> anyhow::Result<Self> {
+        self.node.add_layer(BatchStatusUpdaterLayer);
+        Ok(self)
+    }
+
+    fn add_tx_sender_layer(mut self) -> anyhow::Result<Self> {
+        let optional = &self.config.optional;
+        if optional.transactions_per_sec_limit.is_some() {
+            tracing::warn!("`transactions_per_sec_limit` option is deprecated and ignored");
+        };
+
+        let postgres_storage_caches_config = PostgresStorageCachesConfig {
+            factory_deps_cache_size: optional.factory_deps_cache_size() as u64,
+            initial_writes_cache_size: optional.initial_writes_cache_size() as u64,
+            latest_values_cache_size: optional.latest_values_cache_size() as u64,
+        };
+        let mut tx_sender_layer = TxSenderLayer::new(
+            self.config.clone().into(),
+            postgres_storage_caches_config,
+            optional.vm_concurrency_limit,
+            ApiContracts::load_from_disk(), // TODO (BFT-138): Allow to dynamically reload API contracts
+        );
+        // Admission rules only restrict transaction submission; they are not shared with the state keeper
+        // (i.e., not provided as a resource), since it replays transactions already accepted by the main node.
+        let admission_rules = optional.tx_admission_rules();
+        if !admission_rules.is_empty() {
+            tx_sender_layer = tx_sender_layer.with_admission_policy(Arc::new(admission_rules));
+        }
+
+        // Fee params are fetched from the main node, and transactions are proxied to it.
+        self.node
+            .add_layer(MainNodeFeeParamsFetcherLayer)
+            .add_layer(TxSinkLayer::ProxySink)
+            .add_layer(tx_sender_layer);
+        Ok(self)
+    }
+
+    fn add_http_web3_api_layer(mut self) -> anyhow::Result<Self> {
+        let optional = &self.config.optional;
+        let optional_config = Web3ServerOptionalConfig {
+            namespaces: Some(optional.api_namespaces()),
+            filters_limit: Some(optional.filters_limit),
+            batch_request_size_limit: Some(optional.max_batch_request_size),
+            response_body_size_limit: Some(optional.max_response_body_size()),
+            ..Default::default()
+        };
+        self.node.add_layer(Web3ServerLayer::http(
+            self.config.required.http_port,
+            self.config.clone().into(),
+            optional_config,
+        ));
+        Ok(self)
+    }
+
+    fn add_ws_web3_api_layer(mut self) -> anyhow::Result<Self> {
+        let optional = &self.config.optional;
+        let optional_config = Web3ServerOptionalConfig {
+            namespaces: Some(optional.api_namespaces()),
+            filters_limit: Some(optional.filters_limit),
+            subscriptions_limit: Some(optional.subscriptions_limit),
+            batch_request_size_limit: Some(optional.max_batch_request_size),
+            response_body_size_limit: Some(optional.max_response_body_size()),
+            polling_interval: Some(optional.polling_interval()),
+            ..Default::default()
+        };
+        self.node.add_layer(Web3ServerLayer::ws(
+            self.config.required.ws_port,
+            self.config.clone().into(),
+            optional_config,
+        ));
+        Ok(self)
+    }
+
+    /// Adds layers for all external node components.
+    pub fn build(self) -> anyhow::Result<ZkStackService> {
+        tracing::warn!("The external node is in the alpha phase, and should be used with caution.");
+        tracing::info!("Starting the external node using the node framework");
+
+        // The health check layer goes first, so that the health check time limits are applied.
+        // Storage initialization is performed during wiring, so it must precede the layers that access the storage.
+        Ok(self
+            .add_healthcheck_layer()?
+            .add_sigint_handler_layer()?
+            .add_prometheus_exporter_layer()?
+            .add_pools_layer()?
+            .add_main_node_client_layer()?
+            .add_storage_initialization_layer()?
+            .add_postgres_metrics_layer()?
+            .add_version_metrics_layer()?
+            .add_state_keeper_layer()?
+            .add_consensus_layer()?
+            .add_reorg_detector_layer()?
+            .add_metadata_calculator_layer()?
+            .add_consistency_checker_layer()?
+            .add_commitment_generator_layer()?
+            .add_batch_status_updater_layer()?
+            .add_tx_sender_layer()?
+            .add_http_web3_api_layer()?
+            .add_ws_web3_api_layer()?
+            .node)
+    }
+}
+
+/// Makes sure that the node storage is initialized either via genesis or snapshot recovery, and reverts it
+/// if necessary.
+///
+/// Unlike other layers, this one does its work during wiring, so that the storage is ready by the time
+/// any of the node tasks start.
+///
+/// ## Effects
+///
+/// - Resolves `MasterPoolResource`.
+/// - Resolves `MainNodeClientResource`.
+/// - Adds connection pool health check to the `AppHealthCheckResource`.
+#[derive(Debug)]
+struct StorageInitializationLayer {
+    config: ExternalNodeConfig,
+    enable_snapshots_recovery: bool,
+    revert_pending_l1_batch: bool,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for StorageInitializationLayer {
+    fn layer_name(&self) -> &'static str {
+        "external_node_storage_initialization_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let master_pool = context.get_resource::<MasterPoolResource>().await?;
+        let pool = master_pool.get().await?;
+        let MainNodeClientResource(main_node_client) = context.get_resource().await?;
+        let AppHealthCheckResource(app_health) = context.get_resource_or_default().await;
+
+        let health_check_pool = master_pool.get_singleton().await?;
+        app_health
+            .insert_custom_component(Arc::new(ConnectionPoolHealthCheck::new(health_check_pool)));
+
+        ensure_storage_initialized(
+            &pool,
+            &main_node_client,
+            &app_health,
+            self.config.remote.l2_chain_id,
+            self.enable_snapshots_recovery,
+        )
+        .await?;
+        revert_storage_if_needed(
+            &self.config,
+            &pool,
+            &main_node_client,
+            self.revert_pending_l1_batch,
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+/// Reports the server and protocol versions of the node as metrics.
+///
+/// ## Effects
+///
+/// - Resolves `MasterPoolResource`.
+/// - Adds `version_metrics` to the node.
+#[derive(Debug)]
+struct VersionMetricsLayer;
+
+#[async_trait::async_trait]
+impl WiringLayer for VersionMetricsLayer {
+    fn layer_name(&self) -> &'static str {
+        "external_node_version_metrics_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let pool = context
+            .get_resource::<MasterPoolResource>()
+            .await?
+            .get_singleton()
+            .await?;
+        context.add_task(Box::new(VersionMetricsTask { pool }));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct VersionMetricsTask {
+    pool: ConnectionPool,
+}
+
+#[async_trait::async_trait]
+impl Task for VersionMetricsTask {
+    fn name(&self) -> &'static str {
+        "version_metrics"
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        run_version_metrics_reporting(self.pool, server_version(), stop_receiver.0).await
+    }
+}
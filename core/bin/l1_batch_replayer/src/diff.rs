@@ -0,0 +1,482 @@
+//! Structured diff between stored and re-executed L1 batch data.
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::Context as _;
+use serde::Serialize;
+use zksync_dal::StorageProcessor;
+use zksync_l1_contract_interface::i_executor::commit::kzg::pubdata_to_blob_commitments;
+use zksync_types::{
+    commitment::{
+        AuxCommitments, CommitmentCommonInput, CommitmentInput, L1BatchCommitment,
+        L1BatchWithMetadata,
+    },
+    l2_to_l1_log::{SystemL2ToL1Log, UserL2ToL1Log},
+    writes::StateDiffRecord,
+    AccountTreeId, Address, Bytes, L1BatchNumber, ProtocolVersionId, StorageKey, VmEvent, H256,
+    U256,
+};
+use zksync_utils::{h256_to_u256, u256_to_h256};
+
+use crate::replay::ReplayedL1Batch;
+
+/// Difference between the stored and re-executed values. `None` means that the value is missing.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct ValueDiff<T> {
+    pub expected: Option<T>,
+    pub actual: Option<T>,
+}
+
+/// Difference for an item in a sequence (e.g., events or L2-to-L1 logs).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct SequenceItemDiff<T> {
+    pub index: usize,
+    #[serde(flatten)]
+    pub diff: ValueDiff<T>,
+}
+
+/// Difference for the final value of a storage slot written in the batch.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct StorageWriteDiff {
+    pub address: Address,
+    pub key: H256,
+    #[serde(flatten)]
+    pub diff: ValueDiff<H256>,
+}
+
+/// Event data compared during the diff. Event locations are ignored.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct EventData {
+    pub address: Address,
+    pub topics: Vec<H256>,
+    pub value: Bytes,
+}
+
+impl From<VmEvent> for EventData {
+    fn from(event: VmEvent) -> Self {
+        Self {
+            address: event.address,
+            topics: event.indexed_topics,
+            value: Bytes(event.value),
+        }
+    }
+}
+
+/// Execution outcome of a transaction compared during the diff.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct TransactionOutcome {
+    pub hash: H256,
+    pub gas_used: U256,
+    pub is_failed: bool,
+}
+
+/// Result of the commitment check.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "status")]
+pub(crate) enum CommitmentCheck {
+    /// Commitment recomputed from the re-executed data matches the stored one.
+    Matches { commitment: H256 },
+    /// Commitment recomputed from the re-executed data differs from the stored one.
+    Differs {
+        #[serde(flatten)]
+        diff: ValueDiff<H256>,
+    },
+    /// Commitment cannot be checked (e.g., because it is not computed for the batch yet).
+    Skipped { reason: String },
+}
+
+/// Structured diff between the stored and re-executed L1 batch.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct L1BatchDiff {
+    pub l1_batch_number: L1BatchNumber,
+    pub vm_version: String,
+    pub storage_writes: Vec<StorageWriteDiff>,
+    pub events: Vec<SequenceItemDiff<EventData>>,
+    pub user_l2_to_l1_logs: Vec<SequenceItemDiff<UserL2ToL1Log>>,
+    pub system_logs: Vec<SequenceItemDiff<SystemL2ToL1Log>>,
+    pub transactions: Vec<SequenceItemDiff<TransactionOutcome>>,
+    pub pubdata_input: Option<ValueDiff<Bytes>>,
+    pub commitment: CommitmentCheck,
+}
+
+impl L1BatchDiff {
+    /// Checks whether the re-executed batch fully matches the stored one.
+    pub fn is_empty(&self) -> bool {
+        self.storage_writes.is_empty()
+            && self.events.is_empty()
+            && self.user_l2_to_l1_logs.is_empty()
+            && self.system_logs.is_empty()
+            && self.transactions.is_empty()
+            && self.pubdata_input.is_none()
+            && !matches!(self.commitment, CommitmentCheck::Differs { .. })
+    }
+}
+
+/// Compares two sequences positionally.
+fn diff_sequences<T: Clone + PartialEq>(expected: &[T], actual: &[T]) -> Vec<SequenceItemDiff<T>> {
+    let len = expected.len().max(actual.len());
+    (0..len)
+        .filter_map(|index| {
+            let expected = expected.get(index);
+            let actual = actual.get(index);
+            (expected != actual).then(|| SequenceItemDiff {
+                index,
+                diff: ValueDiff {
+                    expected: expected.cloned(),
+                    actual: actual.cloned(),
+                },
+            })
+        })
+        .collect()
+}
+
+/// Compares final values of storage slots written in the batch. The output is ordered by address and key.
+fn diff_storage_writes(
+    expected: &HashMap<StorageKey, H256>,
+    actual: &HashMap<StorageKey, H256>,
+) -> Vec<StorageWriteDiff> {
+    let mut all_keys: BTreeMap<_, _> = BTreeMap::new();
+    for key in expected.keys().chain(actual.keys()) {
+        all_keys.insert((*key.address(), *key.key()), *key);
+    }
+    all_keys
+        .into_iter()
+        .filter_map(|((address, slot), key)| {
+            let expected = expected.get(&key).copied();
+            let actual = actual.get(&key).copied();
+            (expected != actual).then_some(StorageWriteDiff {
+                address,
+                key: slot,
+                diff: ValueDiff { expected, actual },
+            })
+        })
+        .collect()
+}
+
+/// Returns final values of storage slots written in the re-executed batch.
+fn replayed_storage_writes(replayed: &ReplayedL1Batch) -> HashMap<StorageKey, H256> {
+    let state = &replayed.finished_batch.final_execution_state;
+    state
+        .deduplicated_storage_log_queries
+        .iter()
+        .filter(|query| query.rw_flag)
+        .map(|query| {
+            let key = StorageKey::new(AccountTreeId::new(query.address), u256_to_h256(query.key));
+            (key, u256_to_h256(query.written_value))
+        })
+        .collect()
+}
+
+/// Loads the stored data for the replayed L1 batch and compares it with the re-executed data.
+pub(crate) async fn diff_l1_batch(
+    storage: &mut StorageProcessor<'_>,
+    replayed: &ReplayedL1Batch,
+) -> anyhow::Result<L1BatchDiff> {
+    let l1_batch_number = replayed.l1_batch_number;
+    let header = storage
+        .blocks_dal()
+        .get_l1_batch_header(l1_batch_number)
+        .await
+        .context("failed loading L1 batch header")?
+        .with_context(|| format!("L1 batch #{l1_batch_number} is not sealed"))?;
+    let state = &replayed.finished_batch.final_execution_state;
+
+    let stored_writes = storage
+        .storage_logs_dal()
+        .get_touched_slots_for_l1_batch(l1_batch_number)
+        .await
+        .context("failed loading storage writes")?;
+    let replayed_writes = replayed_storage_writes(replayed);
+    let storage_writes = diff_storage_writes(&stored_writes, &replayed_writes);
+
+    let stored_events = storage
+        .events_dal()
+        .get_vm_events_for_l1_batch(l1_batch_number)
+        .await
+        .context("failed loading events")?
+        .unwrap_or_default();
+    let stored_events: Vec<_> = stored_events.into_iter().map(EventData::from).collect();
+    let replayed_events: Vec<_> = state.events.iter().cloned().map(EventData::from).collect();
+    let events = diff_sequences(&stored_events, &replayed_events);
+
+    let user_l2_to_l1_logs = diff_sequences(&header.l2_to_l1_logs, &state.user_l2_to_l1_logs);
+    let system_logs = diff_sequences(&header.system_logs, &state.system_logs);
+
+    let tx_hashes: Vec<_> = replayed.transactions.iter().map(|tx| tx.hash).collect();
+    let receipts = storage
+        .transactions_web3_dal()
+        .get_transaction_receipts(&tx_hashes)
+        .await
+        .context("failed loading transaction receipts")?;
+    let receipts: HashMap<_, _> = receipts
+        .into_iter()
+        .map(|receipt| (receipt.transaction_hash, receipt))
+        .collect();
+    let stored_outcomes: Vec<_> = tx_hashes
+        .iter()
+        .filter_map(|hash| {
+            let receipt = receipts.get(hash)?;
+            Some(TransactionOutcome {
+                hash: *hash,
+                gas_used: receipt.gas_used.unwrap_or_default(),
+                is_failed: receipt.status.is_zero(),
+            })
+        })
+        .collect();
+    let replayed_outcomes: Vec<_> = replayed
+        .transactions
+        .iter()
+        .map(|tx| TransactionOutcome {
+            hash: tx.hash,
+            gas_used: tx.gas_used,
+            is_failed: tx.is_failed,
+        })
+        .collect();
+    let transactions = diff_sequences(&stored_outcomes, &replayed_outcomes);
+
+    let replayed_pubdata = replayed.finished_batch.pubdata_input.clone();
+    let pubdata_input = (header.pubdata_input != replayed_pubdata).then(|| ValueDiff {
+        expected: header.pubdata_input.map(Bytes),
+        actual: replayed_pubdata.map(Bytes),
+    });
+
+    let commitment = check_commitment(storage, replayed, &replayed_writes).await?;
+
+    Ok(L1BatchDiff {
+        l1_batch_number,
+        vm_version: format!("{:?}", replayed.vm_version),
+        storage_writes,
+        events,
+        user_l2_to_l1_logs,
+        system_logs,
+        transactions,
+        pubdata_input,
+        commitment,
+    })
+}
+
+/// Recomputes the batch commitment using re-executed data (L2-to-L1 logs, state diffs and pubdata) and compares it
+/// with the stored one. Data not produced by the VM (the Merkle tree root and auxiliary commitments) is taken
+/// from the storage.
+async fn check_commitment(
+    storage: &mut StorageProcessor<'_>,
+    replayed: &ReplayedL1Batch,
+    replayed_writes: &HashMap<StorageKey, H256>,
+) -> anyhow::Result<CommitmentCheck> {
+    let l1_batch_number = replayed.l1_batch_number;
+    let Some(L1BatchWithMetadata {
+        header, metadata, ..
+    }) = storage
+        .blocks_dal()
+        .get_l1_batch_metadata(l1_batch_number)
+        .await
+        .context("failed loading L1 batch metadata")?
+    else {
+        return Ok(CommitmentCheck::Skipped {
+            reason: "L1 batch metadata is not computed yet".to_owned(),
+        });
+    };
+
+    let protocol_version = header
+        .protocol_version
+        .unwrap_or_else(ProtocolVersionId::last_potentially_undefined);
+    if protocol_version.is_pre_boojum() {
+        return Ok(CommitmentCheck::Skipped {
+            reason: format!(
+                "commitments for protocol version {protocol_version:?} are not supported"
+            ),
+        });
+    }
+    let (Some(events_queue_commitment), Some(bootloader_initial_content_commitment)) = (
+        metadata.events_queue_commitment,
+        metadata.bootloader_initial_content_commitment,
+    ) else {
+        return Ok(CommitmentCheck::Skipped {
+            reason: "auxiliary commitments are missing".to_owned(),
+        });
+    };
+
+    let hashed_keys: Vec<_> = replayed_writes.keys().map(StorageKey::hashed_key).collect();
+    let previous_values = storage
+        .storage_logs_dal()
+        .get_previous_storage_values(&hashed_keys, l1_batch_number)
+        .await
+        .context("failed loading previous storage values")?;
+    let initial_writes = storage
+        .storage_logs_dal()
+        .get_l1_batches_and_indices_for_initial_writes(&hashed_keys)
+        .await
+        .context("failed loading initial writes")?;
+
+    let mut state_diffs = vec![];
+    for (key, &value) in replayed_writes {
+        let hashed_key = key.hashed_key();
+        let prev_value = previous_values
+            .get(&hashed_key)
+            .copied()
+            .flatten()
+            .unwrap_or_default();
+        if prev_value == value {
+            continue;
+        }
+        // Slots not written in the stored batch are treated as initial writes.
+        let (enumeration_index, initial_value) = match initial_writes.get(&hashed_key) {
+            Some(&(initial_write_l1_batch, index)) if initial_write_l1_batch < l1_batch_number => {
+                (index, h256_to_u256(prev_value))
+            }
+            _ => (0, U256::zero()),
+        };
+        state_diffs.push(StateDiffRecord {
+            address: *key.address(),
+            key: h256_to_u256(*key.key()),
+            derived_key: StorageKey::raw_hashed_key(key.address(), key.key()),
+            enumeration_index,
+            initial_value,
+            final_value: h256_to_u256(value),
+        });
+    }
+    state_diffs.sort_unstable_by_key(|record| (record.address, record.key));
+
+    let blob_commitments = if protocol_version.is_post_1_4_2() {
+        let pubdata_input = replayed
+            .finished_batch
+            .pubdata_input
+            .as_deref()
+            .context("re-executed batch has no pubdata input")?;
+        pubdata_to_blob_commitments(pubdata_input)
+    } else {
+        [H256::zero(); 2]
+    };
+
+    let state = &replayed.finished_batch.final_execution_state;
+    let input = CommitmentInput::PostBoojum {
+        common: CommitmentCommonInput {
+            l2_to_l1_logs: state.user_l2_to_l1_logs.clone(),
+            rollup_last_leaf_index: metadata.rollup_last_leaf_index,
+            rollup_root_hash: metadata.root_hash,
+            bootloader_code_hash: header.base_system_contracts_hashes.bootloader,
+            default_aa_code_hash: header.base_system_contracts_hashes.default_aa,
+            protocol_version,
+        },
+        system_logs: state.system_logs.clone(),
+        state_diffs,
+        aux_commitments: AuxCommitments {
+            events_queue_commitment,
+            bootloader_initial_content_commitment,
+        },
+        blob_commitments,
+    };
+    let commitment = L1BatchCommitment::new(input).hash().commitment;
+
+    Ok(if commitment == metadata.commitment {
+        CommitmentCheck::Matches { commitment }
+    } else {
+        CommitmentCheck::Differs {
+            diff: ValueDiff {
+                expected: Some(metadata.commitment),
+                actual: Some(commitment),
+            },
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diffing_sequences() {
+        let diff = diff_sequences(&[1, 2, 3], &[1, 2, 3]);
+        assert!(diff.is_empty());
+
+        let diff = diff_sequences(&[1, 2, 3], &[1, 5]);
+        assert_eq!(
+            diff,
+            [
+                SequenceItemDiff {
+                    index: 1,
+                    diff: ValueDiff {
+                        expected: Some(2),
+                        actual: Some(5),
+                    },
+                },
+                SequenceItemDiff {
+                    index: 2,
+                    diff: ValueDiff {
+                        expected: Some(3),
+                        actual: None,
+                    },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn diffing_storage_writes() {
+        let address = Address::repeat_byte(1);
+        let key = |slot: u8| StorageKey::new(AccountTreeId::new(address), H256::repeat_byte(slot));
+        let expected = HashMap::from([
+            (key(1), H256::repeat_byte(0xaa)),
+            (key(2), H256::repeat_byte(0xbb)),
+        ]);
+        let actual = HashMap::from([
+            (key(1), H256::repeat_byte(0xaa)),
+            (key(2), H256::repeat_byte(0xcc)),
+            (key(3), H256::repeat_byte(0xdd)),
+        ]);
+
+        let diff = diff_storage_writes(&expected, &actual);
+        assert_eq!(
+            diff,
+            [
+                StorageWriteDiff {
+                    address,
+                    key: H256::repeat_byte(2),
+                    diff: ValueDiff {
+                        expected: Some(H256::repeat_byte(0xbb)),
+                        actual: Some(H256::repeat_byte(0xcc)),
+                    },
+                },
+                StorageWriteDiff {
+                    address,
+                    key: H256::repeat_byte(3),
+                    diff: ValueDiff {
+                        expected: None,
+                        actual: Some(H256::repeat_byte(0xdd)),
+                    },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn serializing_diff() {
+        let diff = StorageWriteDiff {
+            address: Address::repeat_byte(1),
+            key: H256::repeat_byte(2),
+            diff: ValueDiff {
+                expected: None,
+                actual: Some(H256::repeat_byte(3)),
+            },
+        };
+        let json = serde_json::to_value(diff).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "address": Address::repeat_byte(1),
+                "key": H256::repeat_byte(2),
+                "expected": null,
+                "actual": H256::repeat_byte(3),
+            })
+        );
+
+        let check = CommitmentCheck::Skipped {
+            reason: "test".to_owned(),
+        };
+        let json = serde_json::to_value(check).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "status": "skipped", "reason": "test" })
+        );
+    }
+}
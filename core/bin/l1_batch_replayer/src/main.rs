@@ -0,0 +1,159 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::Context as _;
+use clap::{Parser, ValueEnum};
+use tokio::runtime::Handle;
+use zksync_config::{
+    configs::{chain::NetworkConfig, ObservabilityConfig},
+    ObjectStoreConfig, PostgresConfig,
+};
+use zksync_dal::ConnectionPool;
+use zksync_env_config::FromEnv;
+use zksync_object_store::ObjectStoreFactory;
+use zksync_types::{vm_version::VmVersion, witness_block_state::WitnessBlockState, L1BatchNumber};
+
+use crate::{diff::diff_l1_batch, replay::replay_l1_batch};
+
+mod diff;
+mod replay;
+
+/// VM version used to re-execute the batch.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum VmVersionArg {
+    M5WithoutRefunds,
+    M5WithRefunds,
+    M6Initial,
+    M6BugWithCompressionFixed,
+    Vm1_3_2,
+    VmVirtualBlocks,
+    VmVirtualBlocksRefundsEnhancement,
+    VmBoojumIntegration,
+    Vm1_4_1,
+    Vm1_4_2,
+}
+
+impl From<VmVersionArg> for VmVersion {
+    fn from(arg: VmVersionArg) -> Self {
+        match arg {
+            VmVersionArg::M5WithoutRefunds => Self::M5WithoutRefunds,
+            VmVersionArg::M5WithRefunds => Self::M5WithRefunds,
+            VmVersionArg::M6Initial => Self::M6Initial,
+            VmVersionArg::M6BugWithCompressionFixed => Self::M6BugWithCompressionFixed,
+            VmVersionArg::Vm1_3_2 => Self::Vm1_3_2,
+            VmVersionArg::VmVirtualBlocks => Self::VmVirtualBlocks,
+            VmVersionArg::VmVirtualBlocksRefundsEnhancement => {
+                Self::VmVirtualBlocksRefundsEnhancement
+            }
+            VmVersionArg::VmBoojumIntegration => Self::VmBoojumIntegration,
+            VmVersionArg::Vm1_4_1 => Self::Vm1_4_1,
+            VmVersionArg::Vm1_4_2 => Self::Vm1_4_2,
+        }
+    }
+}
+
+#[derive(Debug, Parser)]
+#[command(
+    author = "Matter Labs",
+    version,
+    about = "Re-executes a sealed L1 batch and diffs the results against stored data",
+    long_about = None
+)]
+struct Cli {
+    /// Number of the L1 batch to replay.
+    #[arg(long = "l1-batch")]
+    l1_batch: u32,
+    /// VM version to use for re-execution. If not specified, the VM version corresponding
+    /// to the batch protocol version is used.
+    #[arg(long, value_enum)]
+    vm_version: Option<VmVersionArg>,
+    /// Reads storage slots from the witness input persisted in the object store by the basic witness input producer
+    /// instead of Postgres.
+    #[arg(long)]
+    witness_input: bool,
+    /// Path to write the JSON diff to. If not specified, the diff is written to stdout.
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let observability_config =
+        ObservabilityConfig::from_env().context("ObservabilityConfig::from_env()")?;
+    let log_format: vlog::LogFormat = observability_config
+        .log_format
+        .parse()
+        .context("Invalid log format")?;
+    let mut builder = vlog::ObservabilityBuilder::new().with_log_format(log_format);
+    if let Some(sentry_url) = observability_config.sentry_url {
+        builder = builder
+            .with_sentry_url(&sentry_url)
+            .context("Invalid Sentry URL")?
+            .with_sentry_environment(observability_config.sentry_environment);
+    }
+    let _guard = builder.build();
+
+    let opts = Cli::parse();
+    let l1_batch_number = L1BatchNumber(opts.l1_batch);
+    let postgres_config = PostgresConfig::from_env().context("PostgresConfig::from_env()")?;
+    let network_config = NetworkConfig::from_env().context("NetworkConfig::from_env()")?;
+
+    let witness_input = if opts.witness_input {
+        let object_store_config =
+            ObjectStoreConfig::from_env().context("ObjectStoreConfig::from_env()")?;
+        let object_store = ObjectStoreFactory::new(object_store_config)
+            .create_store()
+            .await;
+        let witness_input: WitnessBlockState =
+            object_store.get(l1_batch_number).await.with_context(|| {
+                format!("failed loading witness input for L1 batch #{l1_batch_number}")
+            })?;
+        Some(witness_input)
+    } else {
+        None
+    };
+
+    let pool = ConnectionPool::singleton(postgres_config.replica_url()?)
+        .build()
+        .await
+        .context("failed to build a connection pool")?;
+
+    let replayed = tokio::task::spawn_blocking({
+        let pool = pool.clone();
+        let vm_version = opts.vm_version.map(VmVersion::from);
+        move || {
+            replay_l1_batch(
+                Handle::current(),
+                &pool,
+                l1_batch_number,
+                network_config.zksync_network_id,
+                vm_version,
+                witness_input,
+            )
+        }
+    })
+    .await
+    .context("replay task panicked")??;
+
+    let mut storage = pool
+        .access_storage_tagged("l1_batch_replayer")
+        .await
+        .context("failed getting Postgres connection")?;
+    let diff = diff_l1_batch(&mut storage, &replayed).await?;
+    drop(storage);
+
+    let diff_json = serde_json::to_string_pretty(&diff).context("failed serializing diff")?;
+    if let Some(output) = &opts.output {
+        fs::write(output, &diff_json)
+            .with_context(|| format!("failed writing diff to {}", output.display()))?;
+        tracing::info!("Diff written to {}", output.display());
+    } else {
+        println!("{diff_json}");
+    }
+
+    anyhow::ensure!(
+        diff.is_empty(),
+        "re-executed L1 batch #{l1_batch_number} differs from the stored data"
+    );
+    tracing::info!("Re-executed L1 batch #{l1_batch_number} matches the stored data");
+    Ok(())
+}
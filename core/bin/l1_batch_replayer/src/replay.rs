@@ -0,0 +1,165 @@
+//! Re-execution of sealed L1 batches in the VM.
+
+use anyhow::Context as _;
+use multivm::{
+    interface::{FinishedL1Batch, L2BlockEnv, VmInterface},
+    vm_latest::HistoryEnabled,
+    VmInstance,
+};
+use tokio::runtime::Handle;
+use vm_utils::{execute_tx, storage::L1BatchParamsProvider};
+use zksync_dal::ConnectionPool;
+use zksync_state::{PostgresStorage, ReadStorage, StorageView, WitnessStorage};
+use zksync_types::{
+    vm_version::VmVersion, witness_block_state::WitnessBlockState, L1BatchNumber, L2ChainId,
+    StorageKey, StorageValue, H256, U256,
+};
+
+/// Result of re-executing a single transaction.
+#[derive(Debug, Clone)]
+pub(crate) struct ReplayedTransaction {
+    pub hash: H256,
+    pub gas_used: U256,
+    pub is_failed: bool,
+}
+
+/// Result of re-executing an L1 batch.
+#[derive(Debug)]
+pub(crate) struct ReplayedL1Batch {
+    pub l1_batch_number: L1BatchNumber,
+    pub vm_version: VmVersion,
+    pub transactions: Vec<ReplayedTransaction>,
+    pub finished_batch: FinishedL1Batch,
+}
+
+/// [`ReadStorage`] reading storage slots from a witness input produced by `BasicWitnessInputProducer`.
+/// Witness inputs don't contain bytecodes, so they are loaded from Postgres.
+#[derive(Debug)]
+struct WitnessInputStorage<'a> {
+    witness: WitnessStorage<'a>,
+    postgres: PostgresStorage<'a>,
+}
+
+impl ReadStorage for WitnessInputStorage<'_> {
+    fn read_value(&mut self, key: &StorageKey) -> StorageValue {
+        self.witness.read_value(key)
+    }
+
+    fn is_write_initial(&mut self, key: &StorageKey) -> bool {
+        self.witness.is_write_initial(key)
+    }
+
+    fn load_factory_dep(&mut self, hash: H256) -> Option<Vec<u8>> {
+        self.postgres.load_factory_dep(hash)
+    }
+
+    fn get_enumeration_index(&mut self, key: &StorageKey) -> Option<u64> {
+        self.postgres.get_enumeration_index(key)
+    }
+}
+
+/// Re-executes the specified L1 batch. If `vm_version` is not specified, the VM version corresponding to
+/// the batch protocol version is used. If `witness_input` is specified, storage slots are read from it
+/// rather than from Postgres.
+///
+/// This method is blocking and must be called from a blocking thread.
+pub(crate) fn replay_l1_batch(
+    rt_handle: Handle,
+    pool: &ConnectionPool,
+    l1_batch_number: L1BatchNumber,
+    l2_chain_id: L2ChainId,
+    vm_version: Option<VmVersion>,
+    witness_input: Option<WitnessBlockState>,
+) -> anyhow::Result<ReplayedL1Batch> {
+    let mut connection = rt_handle
+        .block_on(pool.access_storage_tagged("l1_batch_replayer"))
+        .context("failed getting Postgres connection")?;
+    let miniblocks = rt_handle
+        .block_on(
+            connection
+                .transactions_dal()
+                .get_miniblocks_to_execute_for_l1_batch(l1_batch_number),
+        )
+        .with_context(|| format!("failed loading miniblocks for L1 batch #{l1_batch_number}"))?;
+    anyhow::ensure!(
+        !miniblocks.is_empty(),
+        "no miniblocks persisted for L1 batch #{l1_batch_number}"
+    );
+
+    let l1_batch_params_provider = rt_handle
+        .block_on(L1BatchParamsProvider::new(&mut connection))
+        .context("failed initializing L1 batch params provider")?;
+    let first_miniblock_in_batch = rt_handle
+        .block_on(
+            l1_batch_params_provider
+                .load_first_miniblock_in_batch(&mut connection, l1_batch_number),
+        )
+        .with_context(|| format!("failed loading first miniblock in L1 batch #{l1_batch_number}"))?
+        .with_context(|| format!("no miniblocks persisted for L1 batch #{l1_batch_number}"))?;
+    // The batch was already accepted by the state keeper, so we don't want to reject it on validation.
+    let validation_computational_gas_limit = u32::MAX;
+    let (system_env, l1_batch_env) = rt_handle
+        .block_on(l1_batch_params_provider.load_l1_batch_params(
+            &mut connection,
+            &first_miniblock_in_batch,
+            validation_computational_gas_limit,
+            l2_chain_id,
+        ))
+        .with_context(|| format!("failed loading params for L1 batch #{l1_batch_number}"))?;
+    let vm_version = vm_version.unwrap_or_else(|| system_env.version.into());
+    tracing::info!(
+        "Replaying L1 batch #{l1_batch_number} (protocol version {:?}) with VM version {vm_version:?}",
+        system_env.version
+    );
+
+    let postgres = PostgresStorage::new(
+        rt_handle.clone(),
+        connection,
+        first_miniblock_in_batch.number() - 1,
+        true,
+    );
+    let storage: Box<dyn ReadStorage + '_> = match witness_input {
+        Some(witness_input) => Box::new(WitnessInputStorage {
+            witness: WitnessStorage::new(witness_input),
+            postgres,
+        }),
+        None => Box::new(postgres),
+    };
+    let storage_view = StorageView::new(storage).to_rc_ptr();
+    let mut vm: VmInstance<_, HistoryEnabled> =
+        VmInstance::new_with_specific_version(l1_batch_env, system_env, storage_view, vm_version);
+
+    let mut transactions = vec![];
+    let next_miniblocks = miniblocks.iter().skip(1).map(Some).chain([None]);
+    for (miniblock, next_miniblock) in miniblocks.iter().zip(next_miniblocks) {
+        tracing::debug!(
+            "Replaying miniblock #{} with {} transactions",
+            miniblock.number,
+            miniblock.txs.len()
+        );
+        for tx in &miniblock.txs {
+            let result = execute_tx(tx, &mut vm)
+                .with_context(|| format!("failed executing transaction {:?}", tx.hash()))?;
+            transactions.push(ReplayedTransaction {
+                hash: tx.hash(),
+                gas_used: tx.gas_limit() - U256::from(result.refunds.gas_refunded),
+                is_failed: result.result.is_failed(),
+            });
+        }
+        if let Some(next_miniblock) = next_miniblock {
+            vm.start_new_l2_block(L2BlockEnv::from_miniblock_data(next_miniblock));
+        }
+    }
+    let finished_batch = vm.finish_batch();
+    tracing::info!(
+        "Finished replaying L1 batch #{l1_batch_number} with {} transactions",
+        transactions.len()
+    );
+
+    Ok(ReplayedL1Batch {
+        l1_batch_number,
+        vm_version,
+        transactions,
+        finished_batch,
+    })
+}
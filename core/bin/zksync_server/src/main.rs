@@ -6,8 +6,8 @@ use zksync_config::{
     configs::{
         api::{HealthCheckConfig, MerkleTreeApiConfig, Web3JsonRpcConfig},
         chain::{
-            CircuitBreakerConfig, MempoolConfig, NetworkConfig, OperationsManagerConfig,
-            StateKeeperConfig,
+            CircuitBreakerConfig, L2CongestionConfig, MempoolConfig, NetworkConfig,
+            OperationsManagerConfig, StateKeeperConfig,
         },
         fri_prover_group::FriProverGroupConfig,
         house_keeper::HouseKeeperConfig,
@@ -140,6 +140,7 @@ async fn main() -> anyhow::Result<()> {
             gas_adjuster_config: GasAdjusterConfig::from_env().ok(),
             object_store_config: ObjectStoreConfig::from_env().ok(),
             consensus_config: config::read_consensus_config().context("read_consensus_config()")?,
+            l2_congestion_config: L2CongestionConfig::from_env().ok(),
         },
     };
     let secrets: Secrets = match opt.secrets_path {
@@ -165,6 +166,7 @@ async fn main() -> anyhow::Result<()> {
             &eth_sender,
             &network,
             &contracts,
+            configs.object_store_config.as_ref(),
             &eth_client.web3_url,
             opt.set_chain_id,
         )
@@ -7,6 +7,8 @@ use tokio::sync::watch;
 use zksync_config::configs::chain::CircuitBreakerConfig;
 
 pub mod l1_txs;
+mod metrics;
+pub mod priority_ops;
 pub mod replication_lag;
 pub mod utils;
 
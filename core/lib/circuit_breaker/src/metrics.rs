@@ -0,0 +1,20 @@
+//! Metrics for circuit breakers.
+
+use std::time::Duration;
+
+use vise::{Gauge, Global, Metrics, Unit};
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "circuit_breaker")]
+pub(crate) struct PriorityOpsMetrics {
+    /// Number of priority operations not included into miniblocks yet.
+    pub pending_priority_ops: Gauge<u64>,
+    /// Time the oldest pending priority operation is waiting to be included into a miniblock.
+    #[metrics(unit = Unit::Seconds)]
+    pub oldest_pending_priority_op: Gauge<Duration>,
+    /// Set to 1 if the oldest pending priority operation is overdue, and to 0 otherwise.
+    pub overdue_priority_ops: Gauge<u64>,
+}
+
+#[vise::register]
+pub(crate) static PRIORITY_OPS_METRICS: Global<PriorityOpsMetrics> = Global::new();
@@ -0,0 +1,173 @@
+use std::time::Duration;
+
+use anyhow::Context as _;
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use zksync_config::configs::chain::CircuitBreakerConfig;
+use zksync_dal::{transactions_dal::PendingPriorityOps, ConnectionPool};
+
+use crate::{metrics::PRIORITY_OPS_METRICS, CircuitBreaker, CircuitBreakerError};
+
+#[derive(Debug, Error)]
+enum OverduePriorityOp {
+    #[error("Priority operation #{0} is pending for {1:?}, above the deadline ({2:?})")]
+    DeadlineExceeded(u64, Duration, Duration),
+    #[error("Priority operation #{0} is pending past its L1 expiration timestamp ({1})")]
+    Expired(u64, u64),
+}
+
+/// Monitors that priority operations are included into miniblocks in time. A priority operation pending
+/// for too long may indicate that the sequencer censors L1 transactions.
+///
+/// Unlike other circuit breakers, the monitor never trips: stopping the state keeper would only make
+/// pending operations wait longer. Overdue operations are reported via logs and the
+/// `circuit_breaker_overdue_priority_ops` metric instead, which alerts should be set up on.
+#[derive(Debug)]
+pub struct PendingPriorityOpsMonitor {
+    pool: ConnectionPool,
+    /// If not set, pending operations are never considered overdue.
+    deadline: Option<Duration>,
+}
+
+impl PendingPriorityOpsMonitor {
+    pub fn new(pool: ConnectionPool, config: &CircuitBreakerConfig) -> Self {
+        Self {
+            pool,
+            deadline: config.priority_op_deadline(),
+        }
+    }
+
+    fn check_pending_ops(
+        pending_ops: &PendingPriorityOps,
+        deadline: Duration,
+        now: DateTime<Utc>,
+    ) -> Result<(), OverduePriorityOp> {
+        let serial_id = pending_ops.oldest_serial_id.0;
+        let pending_time = (now - pending_ops.oldest_received_at)
+            .to_std()
+            .unwrap_or_default();
+        if pending_time > deadline {
+            return Err(OverduePriorityOp::DeadlineExceeded(
+                serial_id,
+                pending_time,
+                deadline,
+            ));
+        }
+
+        // L1 contracts may set the expiration to the request timestamp (i.e., provide no expiration window);
+        // such expirations are ignored.
+        if let Some(expiration_timestamp) = pending_ops.oldest_expiration_timestamp {
+            let received_at = pending_ops.oldest_received_at.timestamp();
+            let expiration = expiration_timestamp as i64;
+            if expiration > received_at && now.timestamp() > expiration {
+                return Err(OverduePriorityOp::Expired(serial_id, expiration_timestamp));
+            }
+        }
+        Ok(())
+    }
+
+    async fn check_inner(&self) -> anyhow::Result<()> {
+        let pending_ops = self
+            .pool
+            .access_storage_tagged("circuit_breaker")
+            .await?
+            .transactions_dal()
+            .get_pending_priority_ops()
+            .await
+            .context("get_pending_priority_ops()")?;
+        let now = Utc::now();
+
+        let (count, oldest_pending_time) =
+            pending_ops.as_ref().map_or((0, Duration::ZERO), |ops| {
+                let pending_time = (now - ops.oldest_received_at).to_std().unwrap_or_default();
+                (ops.count, pending_time)
+            });
+        PRIORITY_OPS_METRICS.pending_priority_ops.set(count as u64);
+        PRIORITY_OPS_METRICS
+            .oldest_pending_priority_op
+            .set(oldest_pending_time);
+
+        let overdue = match (&pending_ops, self.deadline) {
+            (Some(pending_ops), Some(deadline)) => {
+                Self::check_pending_ops(pending_ops, deadline, now).err()
+            }
+            _ => None,
+        };
+        if let Some(overdue) = &overdue {
+            tracing::error!("{overdue}");
+        }
+        PRIORITY_OPS_METRICS
+            .overdue_priority_ops
+            .set(overdue.is_some().into());
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl CircuitBreaker for PendingPriorityOpsMonitor {
+    async fn check(&self) -> Result<(), CircuitBreakerError> {
+        if let Err(err) = self.check_inner().await {
+            tracing::warn!("Cannot check pending priority operations: {err:#}");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use zksync_types::PriorityOpId;
+
+    use super::*;
+
+    const DEADLINE: Duration = Duration::from_secs(3_600);
+
+    fn pending_ops(received_at: DateTime<Utc>, expiration: Option<u64>) -> PendingPriorityOps {
+        PendingPriorityOps {
+            count: 3,
+            oldest_serial_id: PriorityOpId(5),
+            oldest_received_at: received_at,
+            oldest_expiration_timestamp: expiration,
+        }
+    }
+
+    #[test]
+    fn checking_pending_priority_op_deadline() {
+        let now = Utc::now();
+        let received_at = now - chrono::Duration::seconds(60);
+        let ops = pending_ops(received_at, None);
+        PendingPriorityOpsMonitor::check_pending_ops(&ops, DEADLINE, now).unwrap();
+
+        let received_at = now - chrono::Duration::seconds(7_200);
+        let ops = pending_ops(received_at, None);
+        let err = PendingPriorityOpsMonitor::check_pending_ops(&ops, DEADLINE, now).unwrap_err();
+        assert_matches!(
+            err,
+            OverduePriorityOp::DeadlineExceeded(5, pending_time, deadline)
+                if pending_time >= Duration::from_secs(7_200) && deadline == DEADLINE
+        );
+    }
+
+    #[test]
+    fn checking_pending_priority_op_expiration() {
+        let now = Utc::now();
+        let received_at = now - chrono::Duration::seconds(60);
+
+        // Expiration equal to the request timestamp is ignored.
+        let expiration = received_at.timestamp() as u64;
+        let ops = pending_ops(received_at, Some(expiration));
+        PendingPriorityOpsMonitor::check_pending_ops(&ops, DEADLINE, now).unwrap();
+
+        let expiration = received_at.timestamp() as u64 + 120;
+        let ops = pending_ops(received_at, Some(expiration));
+        PendingPriorityOpsMonitor::check_pending_ops(&ops, DEADLINE, now).unwrap();
+
+        let expiration = received_at.timestamp() as u64 + 30;
+        let ops = pending_ops(received_at, Some(expiration));
+        let err = PendingPriorityOpsMonitor::check_pending_ops(&ops, DEADLINE, now).unwrap_err();
+        assert_matches!(
+            err,
+            OverduePriorityOp::Expired(5, ts) if ts == expiration
+        );
+    }
+}
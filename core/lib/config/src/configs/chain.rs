@@ -45,6 +45,35 @@ impl Default for FeeModelVersion {
     }
 }
 
+/// Names of the conditional seal criteria that can be enabled for the sequencer.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum SealCriterionName {
+    /// Seals the batch once all transaction slots are used.
+    Slots,
+    /// Seals the batch based on the L1 gas required to commit / prove / execute it.
+    Gas,
+    /// Seals the batch based on the amount of pubdata it produces.
+    PubdataBytes,
+    /// Seals the batch based on the number of circuits required to prove it.
+    Circuits,
+    /// Seals the batch based on the bootloader memory occupied by encoded transactions.
+    TxEncodingSize,
+    /// Seals the batch if there's not enough gas left to process the batch tip.
+    GasForBatchTip,
+    /// Seals the batch once the cost of its pubdata exceeds `target_batch_pubdata_cost_wei`.
+    PubdataCost,
+}
+
+/// VM version used by the shadow VM in the state keeper. Only post-Boojum VM versions are supported.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ShadowVmVersion {
+    VmBoojumIntegration,
+    Vm1_4_1,
+    Vm1_4_2,
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq, Default)]
 pub struct StateKeeperConfig {
     /// The max number of slots for txs in a block before it should be sealed by the slots sealer.
@@ -116,9 +145,58 @@ pub struct StateKeeperConfig {
 
     /// Number of keys that is processed by enum_index migration in State Keeper each L1 batch.
     pub enum_index_migration_chunk_size: Option<usize>,
+
+    /// Conditional seal criteria used by the sequencer. If not specified, all criteria except for
+    /// `pubdata_cost` are used; `pubdata_cost` is added to them if `target_batch_pubdata_cost_wei` is set.
+    pub seal_criteria: Option<Vec<SealCriterionName>>,
+    /// Target cost (in wei) of pubdata published by a single L1 batch. Used by the `pubdata_cost` seal criterion.
+    pub target_batch_pubdata_cost_wei: Option<u64>,
+    /// L1 gas price (in wei) starting from which L1 batches are sealed by timeout using
+    /// `high_l1_gas_price_block_commit_deadline_ms` instead of `block_commit_deadline_ms`.
+    pub high_l1_gas_price_threshold_wei: Option<u64>,
+    /// Number of ms after which an L1 batch opened with a high L1 gas price is going to be unconditionally sealed.
+    pub high_l1_gas_price_block_commit_deadline_ms: Option<u64>,
+
+    /// If set, every transaction is additionally executed by a shadow VM of the specified version. Execution results
+    /// of the shadow VM are compared with the primary ones; mismatches are reported via metrics and persisted
+    /// to Postgres, but they never influence the primary execution.
+    /// NOTE: This roughly doubles the execution time of transactions in the state keeper.
+    pub shadow_vm_version: Option<ShadowVmVersion>,
+
+    /// Whether the transaction admission policy configured by the `tx_admission_*` params below is enforced.
+    /// If disabled, all transactions are admitted.
+    #[serde(default)]
+    pub tx_admission_enabled: bool,
+    /// Accounts that are not allowed to initiate transactions. Applies both to L2 transactions submitted via the API
+    /// and to L1 priority transactions processed by the state keeper. Since priority transactions cannot be skipped,
+    /// a denied priority transaction stops the state keeper until the policy is updated.
+    #[serde(default)]
+    pub tx_admission_denied_senders: Vec<Address>,
+    /// If non-empty, only these accounts are allowed to deploy contracts.
+    #[serde(default)]
+    pub tx_admission_allowed_deployers: Vec<Address>,
+    /// If non-empty, only these contracts are allowed to be called. Transactions without calldata (i.e., plain
+    /// transfers) are not restricted.
+    #[serde(default)]
+    pub tx_admission_allowed_contracts: Vec<Address>,
+    /// If set, transaction admission rules are additionally loaded from the `tx_admission_rules` Postgres table,
+    /// and are reloaded with this interval.
+    pub tx_admission_reload_interval_ms: Option<u64>,
 }
 
 impl StateKeeperConfig {
+    /// Checks that the configured seal criteria are consistent with the other config values.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let has_pubdata_cost_criterion = self.seal_criteria.as_ref().map_or(false, |names| {
+            names.contains(&SealCriterionName::PubdataCost)
+        });
+        anyhow::ensure!(
+            !has_pubdata_cost_criterion || self.target_batch_pubdata_cost_wei.is_some(),
+            "`pubdata_cost` seal criterion requires `target_batch_pubdata_cost_wei` to be set"
+        );
+        Ok(())
+    }
+
     /// Creates a config object suitable for use in unit tests.
     /// Values mostly repeat the values used in the localhost environment.
     pub fn for_tests() -> Self {
@@ -150,12 +228,43 @@ impl StateKeeperConfig {
             virtual_blocks_per_miniblock: 1,
             upload_witness_inputs_to_gcs: false,
             enum_index_migration_chunk_size: None,
+            seal_criteria: None,
+            target_batch_pubdata_cost_wei: None,
+            high_l1_gas_price_threshold_wei: None,
+            high_l1_gas_price_block_commit_deadline_ms: None,
+            shadow_vm_version: None,
+            tx_admission_enabled: false,
+            tx_admission_denied_senders: vec![],
+            tx_admission_allowed_deployers: vec![],
+            tx_admission_allowed_contracts: vec![],
+            tx_admission_reload_interval_ms: None,
         }
     }
 
     pub fn enum_index_migration_chunk_size(&self) -> usize {
         self.enum_index_migration_chunk_size.unwrap_or(1_000)
     }
+
+    pub fn tx_admission_reload_interval(&self) -> Option<Duration> {
+        self.tx_admission_reload_interval_ms
+            .map(Duration::from_millis)
+    }
+}
+
+/// Configuration of the L2 congestion component of the `V2` fee model. If set, the fair L2 gas price is multiplied
+/// by a congestion multiplier that grows when recent L1 batches are utilized (by gas / transaction slots) above the target,
+/// and decreases when they are utilized below it, similarly to the EIP-1559 base fee.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct L2CongestionConfig {
+    /// Target utilization of L1 batches. Must be in `(0, 1]`.
+    pub target_utilization: f64,
+    /// Bounds the relative change of the congestion multiplier per L1 batch: the multiplier can change by at most
+    /// `1 / max_change_denominator` of its value. Defaults to 8 (as in EIP-1559).
+    pub max_change_denominator: Option<u64>,
+    /// Maximum value of the congestion multiplier. Must be at least 1; defaults to 10.
+    pub max_multiplier: Option<f64>,
+    /// Number of the latest L1 batches used to compute the congestion multiplier. Defaults to 100.
+    pub lookback_batches: Option<u32>,
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -176,6 +285,10 @@ pub struct CircuitBreakerConfig {
     pub http_req_max_retry_number: usize,
     pub http_req_retry_interval_sec: u8,
     pub replication_lag_limit_sec: Option<u32>,
+    /// Maximum time a priority operation may stay pending before it's included into a miniblock. Overdue
+    /// operations are reported via logs and metrics; they never stop the node. If not set, only the number
+    /// and age of pending priority operations are reported.
+    pub priority_op_deadline_sec: Option<u32>,
 }
 
 impl CircuitBreakerConfig {
@@ -186,6 +299,11 @@ impl CircuitBreakerConfig {
     pub fn http_req_retry_interval(&self) -> Duration {
         Duration::from_secs(self.http_req_retry_interval_sec as u64)
     }
+
+    pub fn priority_op_deadline(&self) -> Option<Duration> {
+        self.priority_op_deadline_sec
+            .map(|deadline| Duration::from_secs(deadline.into()))
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -47,6 +47,17 @@ pub struct ContractsConfig {
     pub state_transition_proxy_addr: Option<Address>,
     pub state_transition_impl_addr: Option<Address>,
     pub transparent_proxy_admin_addr: Option<Address>,
+
+    /// Directory with custom base system contract bytecodes (`bootloader.zbin` and `default_aa.zbin`).
+    /// If set, these bytecodes are used instead of the ones read from `ZKSYNC_HOME`.
+    pub base_system_contracts_dir: Option<String>,
+    /// Key of the custom base system contract bytecodes in the object store. Mutually exclusive
+    /// with `base_system_contracts_dir`.
+    pub base_system_contracts_object_key: Option<String>,
+    /// Expected hash of the bootloader bytecode. Custom bytecodes not matching this hash are rejected.
+    pub bootloader_hash: Option<H256>,
+    /// Expected hash of the default account bytecode. Custom bytecodes not matching this hash are rejected.
+    pub default_aa_hash: Option<H256>,
 }
 
 impl ContractsConfig {
@@ -89,6 +100,10 @@ impl ContractsConfig {
             bridgehub_impl_addr: Some(Address::repeat_byte(0x15)),
             state_transition_proxy_addr: Some(Address::repeat_byte(0x16)),
             state_transition_impl_addr: Some(Address::repeat_byte(0x17)),
+            base_system_contracts_dir: None,
+            base_system_contracts_object_key: None,
+            bootloader_hash: None,
+            default_aa_hash: None,
         }
     }
 }
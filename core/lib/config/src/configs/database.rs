@@ -112,12 +112,24 @@ pub struct DBConfig {
     // ^ Filled in separately in `Self::from_env()`. We cannot use `serde(flatten)` because it
     // doesn't work with 'envy`.
     pub merkle_tree: MerkleTreeConfig,
+    /// Interval between online backups of RocksDB instances (the state keeper cache and the Merkle tree)
+    /// uploaded to the object store. If not specified, backups are disabled.
+    pub rocksdb_backup_interval_sec: Option<u64>,
+    /// Whether to restore empty RocksDB instances from the latest backup in the object store on node startup.
+    /// Restored instances are caught up with Postgres afterwards.
+    #[serde(default)]
+    pub restore_rocksdb_from_backup: bool,
 }
 
 impl DBConfig {
     fn default_state_keeper_db_path() -> String {
         "./db/state_keeper".to_owned()
     }
+
+    /// Returns the interval between RocksDB backups, or `None` if backups are disabled.
+    pub fn rocksdb_backup_interval(&self) -> Option<Duration> {
+        self.rocksdb_backup_interval_sec.map(Duration::from_secs)
+    }
 }
 
 /// Collection of different database URLs and general PostgreSQL options.
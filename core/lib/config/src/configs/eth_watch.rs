@@ -2,12 +2,34 @@ use std::time::Duration;
 
 use serde::Deserialize;
 
+/// L1 block tag that can be followed by the Ethereum watcher.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum L1BlockTag {
+    /// Follow the `finalized` block. Blocks are not expected to be reorged.
+    #[default]
+    Finalized,
+    /// Follow the `safe` block. Blocks are processed sooner, but can be reorged in rare cases.
+    Safe,
+}
+
+/// Rule to select the latest L1 block processed by the Ethereum watcher.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum L1BlockSelection {
+    /// Process blocks having the specified number of confirmations.
+    Confirmations(u64),
+    /// Process blocks up to the block with the specified tag.
+    Tag(L1BlockTag),
+}
+
 /// Configuration for the Ethereum sender crate.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct ETHWatchConfig {
     /// Amount of confirmations for the priority operation to be processed.
     /// If not specified operation will be processed once its block is finalized.
     pub confirmations_for_eth_event: Option<u64>,
+    /// L1 block tag to follow. If specified, takes precedence over `confirmations_for_eth_event`.
+    pub block_tag: Option<L1BlockTag>,
     /// How often we want to poll the Ethereum node.
     /// Value in milliseconds.
     pub eth_node_poll_interval: u64,
@@ -18,4 +40,13 @@ impl ETHWatchConfig {
     pub fn poll_interval(&self) -> Duration {
         Duration::from_millis(self.eth_node_poll_interval)
     }
+
+    /// Returns the rule to select the latest L1 block processed by the watcher.
+    pub fn l1_block_selection(&self) -> L1BlockSelection {
+        match (self.block_tag, self.confirmations_for_eth_event) {
+            (Some(tag), _) => L1BlockSelection::Tag(tag),
+            (None, Some(confirmations)) => L1BlockSelection::Confirmations(confirmations),
+            (None, None) => L1BlockSelection::Tag(L1BlockTag::Finalized),
+        }
+    }
 }
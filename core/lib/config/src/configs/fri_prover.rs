@@ -8,6 +8,17 @@ pub enum SetupLoadMode {
     FromMemory,
 }
 
+/// Source of prover keys (verification keys, finalization hints and setup data).
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq)]
+pub enum KeystoreSource {
+    /// Keys are expected to be present on the local disk.
+    #[default]
+    LocalDisk,
+    /// Keys are downloaded from the prover object store and cached on the local disk
+    /// (in the same locations that are used for `LocalDisk`).
+    ObjectStore,
+}
+
 /// Configuration for the fri prover application
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct FriProverConfig {
@@ -18,6 +29,8 @@ pub struct FriProverConfig {
     pub base_layer_circuit_ids_to_be_verified: Vec<u8>,
     pub recursive_layer_circuit_ids_to_be_verified: Vec<u8>,
     pub setup_load_mode: SetupLoadMode,
+    #[serde(default)]
+    pub keystore_source: KeystoreSource,
     pub specialized_group_id: u8,
     pub witness_vector_generator_thread_count: Option<usize>,
     pub queue_capacity: usize,
@@ -1,7 +1,63 @@
 use std::collections::HashSet;
 
 use serde::Deserialize;
-use zksync_basic_types::basic_fri_types::CircuitIdRoundTuple;
+use zksync_basic_types::basic_fri_types::{AggregationRound, CircuitIdRoundTuple};
+
+/// Policy used by provers to choose the next job from the queue.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FriProverSchedulingPolicy {
+    /// Generic provers pick jobs from later aggregation rounds first, while specialized provers pick jobs
+    /// for the oldest L1 batch first. This is the ordering used before the policy became configurable.
+    #[default]
+    Default,
+    /// Jobs from later aggregation rounds are always picked first; within a round, jobs for the oldest L1 batch
+    /// are picked first.
+    AggregationRoundFirst,
+    /// Jobs for the oldest L1 batch are picked first; within a batch, jobs from later aggregation rounds
+    /// are picked first. Guarantees that batches are proven in order.
+    OldestBatchFirst,
+    /// Jobs are ordered by their L1 batch number reduced by the weight of their aggregation round,
+    /// i.e. a job from a round with weight `w` can overtake jobs for up to `w` older L1 batches.
+    RoundWeights,
+}
+
+/// Configuration of prover job scheduling.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct FriProverSchedulingConfig {
+    #[serde(default)]
+    pub policy: FriProverSchedulingPolicy,
+    /// Weight of basic circuit jobs for the `round_weights` policy, measured in L1 batches.
+    #[serde(default)]
+    pub basic_circuits_weight: u32,
+    /// Weight of leaf aggregation jobs for the `round_weights` policy, measured in L1 batches.
+    #[serde(default)]
+    pub leaf_aggregation_weight: u32,
+    /// Weight of node aggregation jobs for the `round_weights` policy, measured in L1 batches.
+    #[serde(default)]
+    pub node_aggregation_weight: u32,
+    /// Weight of scheduler jobs for the `round_weights` policy, measured in L1 batches.
+    #[serde(default)]
+    pub scheduler_weight: u32,
+    /// Share of prover capacity (in percent) dedicated to node aggregation and scheduler jobs. Capacity is measured
+    /// in busy prover slots, i.e. in-progress prover jobs across all provers. While aggregation jobs occupy
+    /// less than this share of slots, generic (i.e., not specialized) provers pick queued aggregation jobs before
+    /// any other jobs. Idle dedicated slots are lent to other jobs and are reclaimed as soon as these jobs complete.
+    #[serde(default)]
+    pub reserved_aggregation_capacity_percent: u8,
+}
+
+impl FriProverSchedulingConfig {
+    /// Returns the weight of the specified aggregation round for the `round_weights` policy.
+    pub fn round_weight(&self, round: AggregationRound) -> u32 {
+        match round {
+            AggregationRound::BasicCircuits => self.basic_circuits_weight,
+            AggregationRound::LeafAggregation => self.leaf_aggregation_weight,
+            AggregationRound::NodeAggregation => self.node_aggregation_weight,
+            AggregationRound::Scheduler => self.scheduler_weight,
+        }
+    }
+}
 
 /// Configuration for the grouping of specialized provers.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -19,7 +75,10 @@ pub struct FriProverGroupConfig {
     pub group_10: HashSet<CircuitIdRoundTuple>,
     pub group_11: HashSet<CircuitIdRoundTuple>,
     pub group_12: HashSet<CircuitIdRoundTuple>,
+    #[serde(default)]
+    pub scheduling: FriProverSchedulingConfig,
 }
+
 impl FriProverGroupConfig {
     pub fn get_circuit_ids_for_group_id(&self, group_id: u8) -> Option<Vec<CircuitIdRoundTuple>> {
         match group_id {
@@ -82,6 +141,12 @@ impl FriProverGroupConfig {
     /// In aggregation round 2, the circuit ids should be 2.
     /// In aggregation round 3, the circuit ids should be 1.
     pub fn validate(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.scheduling.reserved_aggregation_capacity_percent <= 100,
+            "Reserved aggregation capacity should be a percentage, got {}",
+            self.scheduling.reserved_aggregation_capacity_percent
+        );
+
         let mut rounds: Vec<Vec<CircuitIdRoundTuple>> = vec![Vec::new(); 4];
         let groups = [
             &self.group_0,
@@ -160,6 +160,30 @@ impl RandomConfig for configs::chain::FeeModelVersion {
     }
 }
 
+impl RandomConfig for configs::chain::SealCriterionName {
+    fn sample(g: &mut Gen<impl Rng>) -> Self {
+        match g.rng.gen_range(0..7) {
+            0 => Self::Slots,
+            1 => Self::Gas,
+            2 => Self::PubdataBytes,
+            3 => Self::Circuits,
+            4 => Self::TxEncodingSize,
+            5 => Self::GasForBatchTip,
+            _ => Self::PubdataCost,
+        }
+    }
+}
+
+impl RandomConfig for configs::chain::ShadowVmVersion {
+    fn sample(g: &mut Gen<impl Rng>) -> Self {
+        match g.rng.gen_range(0..3) {
+            0 => Self::VmBoojumIntegration,
+            1 => Self::Vm1_4_1,
+            _ => Self::Vm1_4_2,
+        }
+    }
+}
+
 impl RandomConfig for configs::AlertsConfig {
     fn sample(g: &mut Gen<impl Rng>) -> Self {
         Self {
@@ -288,6 +312,16 @@ impl RandomConfig for configs::chain::StateKeeperConfig {
             virtual_blocks_per_miniblock: g.gen(),
             upload_witness_inputs_to_gcs: g.gen(),
             enum_index_migration_chunk_size: g.gen(),
+            seal_criteria: g.gen(),
+            target_batch_pubdata_cost_wei: g.gen(),
+            high_l1_gas_price_threshold_wei: g.gen(),
+            high_l1_gas_price_block_commit_deadline_ms: g.gen(),
+            shadow_vm_version: g.gen(),
+            tx_admission_enabled: g.gen(),
+            tx_admission_denied_senders: g.gen(),
+            tx_admission_allowed_deployers: g.gen(),
+            tx_admission_allowed_contracts: g.gen(),
+            tx_admission_reload_interval_ms: g.gen(),
         }
     }
 }
@@ -307,6 +341,18 @@ impl RandomConfig for configs::chain::CircuitBreakerConfig {
             http_req_max_retry_number: g.gen(),
             http_req_retry_interval_sec: g.gen(),
             replication_lag_limit_sec: g.gen(),
+            priority_op_deadline_sec: g.gen(),
+        }
+    }
+}
+
+impl RandomConfig for configs::chain::L2CongestionConfig {
+    fn sample(g: &mut Gen<impl Rng>) -> Self {
+        Self {
+            target_utilization: g.gen(),
+            max_change_denominator: g.gen(),
+            max_multiplier: g.gen(),
+            lookback_batches: g.gen(),
         }
     }
 }
@@ -379,6 +425,10 @@ impl RandomConfig for configs::ContractsConfig {
             state_transition_proxy_addr: g.gen(),
             state_transition_impl_addr: g.gen(),
             transparent_proxy_admin_addr: g.gen(),
+            base_system_contracts_dir: g.gen(),
+            base_system_contracts_object_key: g.gen(),
+            bootloader_hash: g.gen(),
+            default_aa_hash: g.gen(),
         }
     }
 }
@@ -411,6 +461,8 @@ impl RandomConfig for configs::database::DBConfig {
         Self {
             state_keeper_db_path: g.gen(),
             merkle_tree: g.gen(),
+            rocksdb_backup_interval_sec: g.gen(),
+            restore_rocksdb_from_backup: g.gen(),
         }
     }
 }
@@ -523,11 +575,21 @@ impl RandomConfig for configs::ETHWatchConfig {
     fn sample(g: &mut Gen<impl Rng>) -> Self {
         Self {
             confirmations_for_eth_event: g.gen(),
+            block_tag: g.gen(),
             eth_node_poll_interval: g.gen(),
         }
     }
 }
 
+impl RandomConfig for configs::eth_watch::L1BlockTag {
+    fn sample(g: &mut Gen<impl Rng>) -> Self {
+        match g.rng.gen_range(0..2) {
+            0 => Self::Finalized,
+            _ => Self::Safe,
+        }
+    }
+}
+
 impl RandomConfig for configs::FriProofCompressorConfig {
     fn sample(g: &mut Gen<impl Rng>) -> Self {
         Self {
@@ -553,6 +615,15 @@ impl RandomConfig for configs::fri_prover::SetupLoadMode {
     }
 }
 
+impl RandomConfig for configs::fri_prover::KeystoreSource {
+    fn sample(g: &mut Gen<impl Rng>) -> Self {
+        match g.rng.gen_range(0..2) {
+            0 => Self::LocalDisk,
+            _ => Self::ObjectStore,
+        }
+    }
+}
+
 impl RandomConfig for configs::FriProverConfig {
     fn sample(g: &mut Gen<impl Rng>) -> Self {
         Self {
@@ -563,6 +634,7 @@ impl RandomConfig for configs::FriProverConfig {
             base_layer_circuit_ids_to_be_verified: g.gen(),
             recursive_layer_circuit_ids_to_be_verified: g.gen(),
             setup_load_mode: g.gen(),
+            keystore_source: g.gen(),
             specialized_group_id: g.gen(),
             witness_vector_generator_thread_count: g.gen(),
             queue_capacity: g.gen(),
@@ -610,6 +682,31 @@ impl RandomConfig for configs::fri_prover_group::FriProverGroupConfig {
             group_10: g.gen(),
             group_11: g.gen(),
             group_12: g.gen(),
+            scheduling: g.gen(),
+        }
+    }
+}
+
+impl RandomConfig for configs::fri_prover_group::FriProverSchedulingPolicy {
+    fn sample(g: &mut Gen<impl Rng>) -> Self {
+        match g.rng.gen_range(0..4) {
+            0 => Self::Default,
+            1 => Self::AggregationRoundFirst,
+            2 => Self::OldestBatchFirst,
+            _ => Self::RoundWeights,
+        }
+    }
+}
+
+impl RandomConfig for configs::fri_prover_group::FriProverSchedulingConfig {
+    fn sample(g: &mut Gen<impl Rng>) -> Self {
+        Self {
+            policy: g.gen(),
+            basic_circuits_weight: g.gen(),
+            leaf_aggregation_weight: g.gen(),
+            node_aggregation_weight: g.gen(),
+            scheduler_weight: g.gen(),
+            reserved_aggregation_capacity_percent: g.rng.gen_range(0..=100),
         }
     }
 }
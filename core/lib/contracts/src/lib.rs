@@ -245,19 +245,21 @@ impl PartialEq for BaseSystemContracts {
 }
 
 impl BaseSystemContracts {
-    fn load_with_bootloader(bootloader_bytecode: Vec<u8>) -> Self {
+    /// Creates base system contracts from the raw bytecodes of the bootloader and the default account.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the bytecodes is malformed, i.e., has a length not divisible by 32.
+    pub fn from_bytecodes(bootloader_bytecode: Vec<u8>, default_aa_bytecode: Vec<u8>) -> Self {
         let hash = hash_bytecode(&bootloader_bytecode);
-
         let bootloader = SystemContractCode {
             code: bytes_to_be_words(bootloader_bytecode),
             hash,
         };
 
-        let bytecode = read_sys_contract_bytecode("", "DefaultAccount", ContractLanguage::Sol);
-        let hash = hash_bytecode(&bytecode);
-
+        let hash = hash_bytecode(&default_aa_bytecode);
         let default_aa = SystemContractCode {
-            code: bytes_to_be_words(bytecode),
+            code: bytes_to_be_words(default_aa_bytecode),
             hash,
         };
 
@@ -266,6 +268,11 @@ impl BaseSystemContracts {
             default_aa,
         }
     }
+
+    fn load_with_bootloader(bootloader_bytecode: Vec<u8>) -> Self {
+        let bytecode = read_sys_contract_bytecode("", "DefaultAccount", ContractLanguage::Sol);
+        Self::from_bytecodes(bootloader_bytecode, bytecode)
+    }
     // BaseSystemContracts with proved bootloader - for handling transactions.
     pub fn load_from_disk() -> Self {
         let bootloader_bytecode = read_proved_batch_bootloader_bytecode();
@@ -0,0 +1 @@
+ALTER TABLE miniblocks ADD COLUMN IF NOT EXISTS logs_bloom BYTEA;
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS shadow_vm_mismatches;
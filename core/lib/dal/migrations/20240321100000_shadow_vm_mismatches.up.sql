@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS shadow_vm_mismatches (
+    id BIGSERIAL PRIMARY KEY,
+    l1_batch_number BIGINT NOT NULL,
+    tx_hash BYTEA,
+    kind TEXT NOT NULL,
+    shadow_vm_version TEXT NOT NULL,
+    details JSONB NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS shadow_vm_mismatches_l1_batch_number_idx ON shadow_vm_mismatches (l1_batch_number);
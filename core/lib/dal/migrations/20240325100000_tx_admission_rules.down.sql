@@ -0,0 +1 @@
+DROP TABLE IF EXISTS tx_admission_rules;
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS tx_admission_rules (
+    address BYTEA NOT NULL,
+    kind TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (address, kind)
+);
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS priority_op_l1_info (
+    priority_op_id BIGINT PRIMARY KEY,
+    l1_tx_hash BYTEA NOT NULL,
+    expiration_timestamp BIGINT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS priority_op_l1_info_l1_tx_hash_idx ON priority_op_l1_info (l1_tx_hash);
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS eth_watch_cursors (
+    processor_name TEXT NOT NULL PRIMARY KEY,
+    last_processed_l1_block BIGINT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
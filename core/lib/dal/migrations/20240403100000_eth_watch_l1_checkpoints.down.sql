@@ -0,0 +1 @@
+DROP TABLE IF EXISTS eth_watch_l1_checkpoints;
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS eth_watch_l1_checkpoints (
+    l1_block_number BIGINT NOT NULL PRIMARY KEY,
+    l1_block_hash BYTEA NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
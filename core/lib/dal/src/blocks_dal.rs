@@ -12,7 +12,7 @@ use zksync_types::{
     circuit::CircuitStatistic,
     commitment::{L1BatchCommitmentArtifacts, L1BatchWithMetadata},
     zk_evm_types::LogQuery,
-    Address, L1BatchNumber, MiniblockNumber, ProtocolVersionId, H256, U256,
+    Address, L1BatchNumber, MiniblockNumber, ProtocolVersionId, H2048, H256, U256,
 };
 
 use crate::{
@@ -610,11 +610,31 @@ impl BlocksDal<'_, '_> {
                     protocol_version,
                     virtual_blocks,
                     fair_pubdata_price,
+                    logs_bloom,
                     created_at,
                     updated_at
                 )
             VALUES
-                ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, NOW(), NOW())
+                (
+                    $1,
+                    $2,
+                    $3,
+                    $4,
+                    $5,
+                    $6,
+                    $7,
+                    $8,
+                    $9,
+                    $10,
+                    $11,
+                    $12,
+                    $13,
+                    $14,
+                    $15,
+                    $16,
+                    NOW(),
+                    NOW()
+                )
             "#,
             i64::from(miniblock_header.number.0),
             miniblock_header.timestamp as i64,
@@ -637,6 +657,7 @@ impl BlocksDal<'_, '_> {
             miniblock_header.protocol_version.map(|v| v as i32),
             i64::from(miniblock_header.virtual_blocks),
             miniblock_header.batch_fee_input.fair_pubdata_price() as i64,
+            miniblock_header.logs_bloom.as_bytes(),
         )
         .execute(self.storage.conn())
         .await?;
@@ -664,7 +685,8 @@ impl BlocksDal<'_, '_> {
                 default_aa_code_hash,
                 protocol_version,
                 virtual_blocks,
-                fair_pubdata_price
+                fair_pubdata_price,
+                logs_bloom
             FROM
                 miniblocks
             ORDER BY
@@ -710,7 +732,8 @@ impl BlocksDal<'_, '_> {
                 default_aa_code_hash,
                 protocol_version,
                 virtual_blocks,
-                fair_pubdata_price
+                fair_pubdata_price,
+                logs_bloom
             FROM
                 miniblocks
             WHERE
@@ -1890,6 +1913,48 @@ impl BlocksDal<'_, '_> {
             .context("Sum of predicted gas costs should fit into u32")
     }
 
+    /// Returns the number of transactions and predicted L1 gas costs for up to `limit` latest sealed L1 batches
+    /// (excluding the genesis batch), in the ascending order of L1 batch numbers.
+    pub async fn get_recent_l1_batches_utilization(
+        &mut self,
+        limit: usize,
+    ) -> sqlx::Result<Vec<(usize, BlockGasCount)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                l1_tx_count,
+                l2_tx_count,
+                predicted_commit_gas_cost,
+                predicted_prove_gas_cost,
+                predicted_execute_gas_cost
+            FROM
+                l1_batches
+            WHERE
+                number > 0
+            ORDER BY
+                number DESC
+            LIMIT
+                $1
+            "#,
+            limit as i64
+        )
+        .instrument("get_recent_l1_batches_utilization")
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+
+        let utilization = rows.into_iter().rev().map(|row| {
+            let tx_count = (row.l1_tx_count + row.l2_tx_count) as usize;
+            let gas_count = BlockGasCount {
+                commit: row.predicted_commit_gas_cost as u32,
+                prove: row.predicted_prove_gas_cost as u32,
+                execute: row.predicted_execute_gas_cost as u32,
+            };
+            (tx_count, gas_count)
+        });
+        Ok(utilization.collect())
+    }
+
     pub async fn get_miniblock_range_of_l1_batch(
         &mut self,
         l1_batch_number: L1BatchNumber,
@@ -2265,6 +2330,68 @@ impl BlocksDal<'_, '_> {
         Ok(execution_result.rows_affected())
     }
 
+    /// Returns the number of the latest miniblock without a logs bloom, i.e., one sealed before logs blooms
+    /// were introduced and not backfilled yet.
+    pub async fn get_max_miniblock_without_logs_bloom(
+        &mut self,
+    ) -> sqlx::Result<Option<MiniblockNumber>> {
+        let max_number = sqlx::query!(
+            r#"
+            SELECT
+                MAX(number) AS "max?"
+            FROM
+                miniblocks
+            WHERE
+                logs_bloom IS NULL
+            "#
+        )
+        .instrument("get_max_miniblock_without_logs_bloom")
+        .fetch_one(self.storage)
+        .await?
+        .max;
+
+        Ok(max_number.map(|number| MiniblockNumber(number as u32)))
+    }
+
+    /// Sets logs blooms for consecutive miniblocks starting from `from_miniblock`. Blooms are not overwritten
+    /// for miniblocks that already have them. Returns the number of affected miniblocks.
+    pub async fn range_update_logs_bloom(
+        &mut self,
+        from_miniblock: MiniblockNumber,
+        blooms: &[H2048],
+    ) -> sqlx::Result<u64> {
+        let numbers: Vec<_> = (0..blooms.len() as i64)
+            .map(|offset| i64::from(from_miniblock.0) + offset)
+            .collect();
+        let blooms: Vec<_> = blooms.iter().map(H2048::as_bytes).collect();
+
+        let execution_result = sqlx::query!(
+            r#"
+            UPDATE miniblocks
+            SET
+                logs_bloom = data.logs_bloom
+            FROM
+                (
+                    SELECT
+                        UNNEST($1::BIGINT[]) AS number,
+                        UNNEST($2::BYTEA[]) AS logs_bloom
+                ) AS data
+            WHERE
+                miniblocks.number = data.number
+                AND miniblocks.logs_bloom IS NULL
+            "#,
+            &numbers,
+            &blooms as &[&[u8]],
+        )
+        .instrument("range_update_logs_bloom")
+        .with_arg("from_miniblock", &from_miniblock)
+        .with_arg("blooms.len", &blooms.len())
+        .execute(self.storage)
+        .await?;
+
+        Ok(execution_result.rows_affected())
+    }
+
     /// Sets `fee_account_address` for an L1 batch. Should only be used in tests.
     pub async fn set_l1_batch_fee_address(
         &mut self,
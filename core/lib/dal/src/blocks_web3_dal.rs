@@ -1,4 +1,6 @@
-use zksync_system_constants::EMPTY_UNCLES_HASH;
+use std::{ops, str::FromStr};
+
+use zksync_system_constants::{EMPTY_UNCLES_HASH, L1_GAS_PER_PUBDATA_BYTE};
 use zksync_types::{
     api,
     l2_to_l1_log::L2ToL1Log,
@@ -18,6 +20,29 @@ use crate::{
 };
 
 const BLOCK_GAS_LIMIT: u32 = u32::MAX;
+/// Logs bloom returned for miniblocks without a stored bloom (i.e., sealed before logs blooms were introduced
+/// and not backfilled yet). All bits are set so that clients filtering blocks by their blooms don't skip
+/// these miniblocks.
+const MATCH_ALL_LOGS_BLOOM: H2048 = H2048([u8::MAX; 256]);
+
+/// Fee-related information about a miniblock used in `eth_feeHistory`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MiniblockFeeData {
+    pub number: MiniblockNumber,
+    pub hash: H256,
+    pub base_fee_per_gas: U256,
+    /// Price of publishing a single pubdata byte. For miniblocks without a stored pubdata price,
+    /// it is derived from the L1 gas price.
+    pub fair_pubdata_price: u64,
+}
+
+/// Gas usage and price of a transaction used in `eth_feeHistory`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionFeeData {
+    pub miniblock_number: MiniblockNumber,
+    pub effective_gas_price: U256,
+    pub gas_used: U256,
+}
 
 #[derive(Debug)]
 pub struct BlocksWeb3Dal<'a, 'c> {
@@ -37,6 +62,7 @@ impl BlocksWeb3Dal<'_, '_> {
                 miniblocks.l1_batch_number,
                 miniblocks.timestamp,
                 miniblocks.base_fee_per_gas,
+                miniblocks.logs_bloom,
                 prev_miniblock.hash AS "parent_hash?",
                 l1_batches.timestamp AS "l1_batch_timestamp?",
                 transactions.gas_limit AS "gas_limit?",
@@ -74,7 +100,10 @@ impl BlocksWeb3Dal<'_, '_> {
                     base_fee_per_gas: bigdecimal_to_u256(row.base_fee_per_gas),
                     timestamp: (row.timestamp as u64).into(),
                     l1_batch_timestamp: row.l1_batch_timestamp.map(U256::from),
-                    // TODO: include logs
+                    logs_bloom: row
+                        .logs_bloom
+                        .as_deref()
+                        .map_or(MATCH_ALL_LOGS_BLOOM, H2048::from_slice),
                     ..api::Block::default()
                 }
             });
@@ -91,6 +120,41 @@ impl BlocksWeb3Dal<'_, '_> {
         Ok(block)
     }
 
+    /// Returns logs blooms for miniblocks in the specified range, ordered by miniblock number. Blooms are `None`
+    /// for miniblocks sealed before logs blooms were introduced and not backfilled yet.
+    pub async fn get_logs_blooms(
+        &mut self,
+        miniblock_range: ops::RangeInclusive<MiniblockNumber>,
+    ) -> sqlx::Result<Vec<(MiniblockNumber, Option<H2048>)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                number,
+                logs_bloom
+            FROM
+                miniblocks
+            WHERE
+                number BETWEEN $1 AND $2
+            ORDER BY
+                number ASC
+            "#,
+            i64::from(miniblock_range.start().0),
+            i64::from(miniblock_range.end().0)
+        )
+        .instrument("get_logs_blooms")
+        .with_arg("miniblock_range", &miniblock_range)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let bloom = row.logs_bloom.as_deref().map(H2048::from_slice);
+                (MiniblockNumber(row.number as u32), bloom)
+            })
+            .collect())
+    }
+
     pub async fn get_block_tx_count(
         &mut self,
         block_number: MiniblockNumber,
@@ -150,7 +214,8 @@ impl BlocksWeb3Dal<'_, '_> {
             SELECT
                 hash,
                 number,
-                timestamp
+                timestamp,
+                logs_bloom
             FROM
                 miniblocks
             WHERE
@@ -176,8 +241,10 @@ impl BlocksWeb3Dal<'_, '_> {
             gas_limit: U256::zero(),
             base_fee_per_gas: None,
             extra_data: Bytes::default(),
-            // TODO: include logs
-            logs_bloom: H2048::default(),
+            logs_bloom: row
+                .logs_bloom
+                .as_deref()
+                .map_or(MATCH_ALL_LOGS_BLOOM, H2048::from_slice),
             timestamp: U256::from(row.timestamp),
             difficulty: U256::zero(),
             mix_hash: None,
@@ -469,17 +536,21 @@ impl BlocksWeb3Dal<'_, '_> {
         .collect())
     }
 
-    /// Returns `base_fee_per_gas` for miniblock range [min(newest_block - block_count + 1, 0), newest_block]
+    /// Returns fee-related data for miniblock range [min(newest_block - block_count + 1, 0), newest_block]
     /// in descending order of miniblock numbers.
-    pub async fn get_fee_history(
+    pub async fn get_miniblocks_fee_data(
         &mut self,
         newest_block: MiniblockNumber,
         block_count: u64,
-    ) -> sqlx::Result<Vec<U256>> {
-        let result: Vec<_> = sqlx::query!(
+    ) -> sqlx::Result<Vec<MiniblockFeeData>> {
+        let rows = sqlx::query!(
             r#"
             SELECT
-                base_fee_per_gas
+                number,
+                hash,
+                base_fee_per_gas,
+                l1_gas_price,
+                fair_pubdata_price
             FROM
                 miniblocks
             WHERE
@@ -492,13 +563,69 @@ impl BlocksWeb3Dal<'_, '_> {
             i64::from(newest_block.0),
             block_count as i64
         )
-        .fetch_all(self.storage.conn())
-        .await?
-        .into_iter()
-        .map(|row| bigdecimal_to_u256(row.base_fee_per_gas))
-        .collect();
+        .instrument("get_miniblocks_fee_data")
+        .with_arg("newest_block", &newest_block)
+        .with_arg("block_count", &block_count)
+        .fetch_all(self.storage)
+        .await?;
 
-        Ok(result)
+        Ok(rows
+            .into_iter()
+            .map(|row| MiniblockFeeData {
+                number: MiniblockNumber(row.number as u32),
+                hash: H256::from_slice(&row.hash),
+                base_fee_per_gas: bigdecimal_to_u256(row.base_fee_per_gas),
+                fair_pubdata_price: row.fair_pubdata_price.map_or(
+                    row.l1_gas_price as u64 * u64::from(L1_GAS_PER_PUBDATA_BYTE),
+                    |price| price as u64,
+                ),
+            })
+            .collect())
+    }
+
+    /// Returns gas usage and effective gas prices of all transactions in the specified miniblock range,
+    /// ordered by miniblock number and the index of the transaction in the miniblock.
+    pub async fn get_transactions_fee_data(
+        &mut self,
+        miniblocks: ops::RangeInclusive<MiniblockNumber>,
+    ) -> sqlx::Result<Vec<TransactionFeeData>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                miniblock_number AS "miniblock_number!",
+                effective_gas_price,
+                gas_limit,
+                refunded_gas
+            FROM
+                transactions
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+            ORDER BY
+                miniblock_number,
+                index_in_block
+            "#,
+            i64::from(miniblocks.start().0),
+            i64::from(miniblocks.end().0)
+        )
+        .instrument("get_transactions_fee_data")
+        .with_arg("miniblocks", &miniblocks)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let gas_limit = row.gas_limit.map(bigdecimal_to_u256).unwrap_or_default();
+                TransactionFeeData {
+                    miniblock_number: MiniblockNumber(row.miniblock_number as u32),
+                    effective_gas_price: row
+                        .effective_gas_price
+                        .map(bigdecimal_to_u256)
+                        .unwrap_or_default(),
+                    gas_used: gas_limit.saturating_sub(U256::from(row.refunded_gas as u64)),
+                }
+            })
+            .collect())
     }
 
     pub async fn get_block_details(
@@ -638,6 +765,67 @@ impl BlocksWeb3Dal<'_, '_> {
 
         Ok(l1_batch_details.map(Into::into))
     }
+
+    /// Returns updates for L1 batches in the specified range that have reached the specified lifecycle stage,
+    /// ordered by L1 batch number. Only confirmed L1 transactions are taken into account.
+    pub async fn get_l1_batch_status_updates(
+        &mut self,
+        l1_batch_range: ops::RangeInclusive<L1BatchNumber>,
+        stage: api::L1BatchStage,
+    ) -> sqlx::Result<Vec<api::L1BatchStatusUpdate>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                l1_batches.number,
+                commit_tx.tx_hash AS "commit_tx_hash?",
+                prove_tx.tx_hash AS "prove_tx_hash?",
+                execute_tx.tx_hash AS "execute_tx_hash?"
+            FROM
+                l1_batches
+                LEFT JOIN eth_txs_history AS commit_tx ON (
+                    l1_batches.eth_commit_tx_id = commit_tx.eth_tx_id
+                    AND commit_tx.confirmed_at IS NOT NULL
+                )
+                LEFT JOIN eth_txs_history AS prove_tx ON (
+                    l1_batches.eth_prove_tx_id = prove_tx.eth_tx_id
+                    AND prove_tx.confirmed_at IS NOT NULL
+                )
+                LEFT JOIN eth_txs_history AS execute_tx ON (
+                    l1_batches.eth_execute_tx_id = execute_tx.eth_tx_id
+                    AND execute_tx.confirmed_at IS NOT NULL
+                )
+            WHERE
+                l1_batches.number BETWEEN $1 AND $2
+            ORDER BY
+                l1_batches.number
+            "#,
+            i64::from(l1_batch_range.start().0),
+            i64::from(l1_batch_range.end().0)
+        )
+        .instrument("get_l1_batch_status_updates")
+        .with_arg("l1_batch_range", &l1_batch_range)
+        .with_arg("stage", &stage)
+        .report_latency()
+        .fetch_all(self.storage)
+        .await?;
+
+        let updates = rows.into_iter().filter_map(|row| {
+            let l1_tx_hash = match stage {
+                api::L1BatchStage::Sealed => None,
+                api::L1BatchStage::Committed => Some(row.commit_tx_hash?),
+                api::L1BatchStage::Proven => Some(row.prove_tx_hash?),
+                api::L1BatchStage::Executed => Some(row.execute_tx_hash?),
+            };
+            let l1_tx_hash = l1_tx_hash
+                .map(|hash| H256::from_str(&hash).expect("Incorrect L1 transaction hash"));
+            Some(api::L1BatchStatusUpdate {
+                l1_batch_number: L1BatchNumber(row.number as u32),
+                stage,
+                l1_tx_hash,
+            })
+        });
+        Ok(updates.collect())
+    }
 }
 
 #[cfg(test)]
@@ -705,6 +893,38 @@ mod tests {
         assert_eq!(tx_count.unwrap(), None);
     }
 
+    #[tokio::test]
+    async fn missing_logs_bloom_matches_all_logs() {
+        let connection_pool = ConnectionPool::test_pool().await;
+        let mut conn = connection_pool.access_storage().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion::default())
+            .await;
+        conn.blocks_dal()
+            .insert_miniblock(&create_miniblock_header(0))
+            .await
+            .unwrap();
+
+        let block = conn
+            .blocks_web3_dal()
+            .get_api_block(MiniblockNumber(0))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(block.logs_bloom, H2048::zero());
+
+        crate::testonly::clear_logs_blooms(&mut conn, MiniblockNumber(0)..=MiniblockNumber(0))
+            .await
+            .unwrap();
+        let block = conn
+            .blocks_web3_dal()
+            .get_api_block(MiniblockNumber(0))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(block.logs_bloom, MATCH_ALL_LOGS_BLOOM);
+    }
+
     #[tokio::test]
     async fn resolving_earliest_block_id() {
         let connection_pool = ConnectionPool::test_pool().await;
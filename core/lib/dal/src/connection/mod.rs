@@ -16,9 +16,9 @@ use sqlx::{
     postgres::{PgConnectOptions, PgPool, PgPoolOptions, Postgres},
 };
 
-pub use self::processor::StorageProcessor;
 pub(crate) use self::processor::StorageProcessorTags;
 use self::processor::TracedConnections;
+pub use self::processor::{StorageProcessor, StorageProcessorWrapper};
 use crate::metrics::{PostgresMetrics, CONNECTION_METRICS};
 
 mod processor;
@@ -134,10 +134,22 @@ impl TestTemplate {
 
     /// Obtains the test database URL from the environment variable.
     pub fn empty() -> anyhow::Result<Self> {
-        let db_url = env::var("TEST_DATABASE_URL").context(
-            "TEST_DATABASE_URL must be set. Normally, this is done by the 'zk' tool. \
-            Make sure that you are running the tests with 'zk test rust' command or equivalent.",
-        )?;
+        Self::from_env_var("TEST_DATABASE_URL")
+    }
+
+    /// Obtains the test prover database URL from the environment variable. The template is expected to have
+    /// all prover DAL migrations applied.
+    pub fn prover_empty() -> anyhow::Result<Self> {
+        Self::from_env_var("TEST_DATABASE_PROVER_URL")
+    }
+
+    fn from_env_var(var_name: &str) -> anyhow::Result<Self> {
+        let db_url = env::var(var_name).with_context(|| {
+            format!(
+                "{var_name} must be set. Normally, this is done by the 'zk' tool. \
+                 Make sure that you are running the tests with 'zk test rust' command or equivalent."
+            )
+        })?;
         Ok(Self(db_url.parse()?))
     }
 
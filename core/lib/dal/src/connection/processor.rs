@@ -205,6 +205,20 @@ impl<'a> StorageProcessor<'a> {
     }
 }
 
+/// Extension point for DALs defined in other crates (e.g., the prover DAL). Such crates wrap a [`StorageProcessor`]
+/// into their own type exposing a separate set of DALs, and implement this trait for the wrapper. This provides
+/// access to the underlying connection and allows running [instrumented](crate::instrument) queries,
+/// without exposing the connection of `StorageProcessor` itself.
+pub trait StorageProcessorWrapper<'a> {
+    /// Returns the wrapped storage processor.
+    fn storage_processor(&mut self) -> &mut StorageProcessor<'a>;
+
+    /// Returns the underlying Postgres connection.
+    fn conn(&mut self) -> &mut PgConnection {
+        self.storage_processor().conn()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::ConnectionPool;
@@ -0,0 +1,234 @@
+use zksync_types::H256;
+
+use crate::{instrument::InstrumentExt, StorageProcessor};
+
+/// Hash of an L1 block up to which the Ethereum watcher has processed events. Used to detect L1 reorgs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct L1Checkpoint {
+    pub l1_block_number: u64,
+    pub l1_block_hash: H256,
+}
+
+/// Persists progress of L1 event processors used by the Ethereum watcher.
+#[derive(Debug)]
+pub struct EthWatchDal<'a, 'c> {
+    pub(crate) storage: &'a mut StorageProcessor<'c>,
+}
+
+impl EthWatchDal<'_, '_> {
+    /// Returns the last L1 block fully processed by the specified event processor, or `None`
+    /// if the processor hasn't processed any blocks yet.
+    pub async fn get_processor_cursor(
+        &mut self,
+        processor_name: &str,
+    ) -> sqlx::Result<Option<u64>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                last_processed_l1_block
+            FROM
+                eth_watch_cursors
+            WHERE
+                processor_name = $1
+            "#,
+            processor_name
+        )
+        .instrument("get_eth_watch_processor_cursor")
+        .with_arg("processor_name", &processor_name)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| row.last_processed_l1_block as u64))
+    }
+
+    /// Sets the last L1 block fully processed by the specified event processor.
+    pub async fn set_processor_cursor(
+        &mut self,
+        processor_name: &str,
+        last_processed_l1_block: u64,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                eth_watch_cursors (processor_name, last_processed_l1_block, created_at, updated_at)
+            VALUES
+                ($1, $2, NOW(), NOW())
+            ON CONFLICT (processor_name) DO
+            UPDATE
+            SET
+                last_processed_l1_block = excluded.last_processed_l1_block,
+                updated_at = NOW()
+            "#,
+            processor_name,
+            last_processed_l1_block as i64
+        )
+        .instrument("set_eth_watch_processor_cursor")
+        .with_arg("processor_name", &processor_name)
+        .with_arg("last_processed_l1_block", &last_processed_l1_block)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Saves an L1 checkpoint, overwriting the existing checkpoint for the same block if any.
+    pub async fn insert_l1_checkpoint(&mut self, checkpoint: L1Checkpoint) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                eth_watch_l1_checkpoints (l1_block_number, l1_block_hash, created_at)
+            VALUES
+                ($1, $2, NOW())
+            ON CONFLICT (l1_block_number) DO
+            UPDATE
+            SET
+                l1_block_hash = excluded.l1_block_hash,
+                created_at = NOW()
+            "#,
+            checkpoint.l1_block_number as i64,
+            checkpoint.l1_block_hash.as_bytes()
+        )
+        .instrument("insert_eth_watch_l1_checkpoint")
+        .with_arg("checkpoint", &checkpoint)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns up to `limit` latest L1 checkpoints ordered by descending L1 block number.
+    pub async fn get_latest_l1_checkpoints(
+        &mut self,
+        limit: usize,
+    ) -> sqlx::Result<Vec<L1Checkpoint>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                l1_block_number,
+                l1_block_hash
+            FROM
+                eth_watch_l1_checkpoints
+            ORDER BY
+                l1_block_number DESC
+            LIMIT
+                $1
+            "#,
+            limit as i64
+        )
+        .instrument("get_latest_eth_watch_l1_checkpoints")
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| L1Checkpoint {
+                l1_block_number: row.l1_block_number as u64,
+                l1_block_hash: H256::from_slice(&row.l1_block_hash),
+            })
+            .collect())
+    }
+
+    /// Removes L1 checkpoints after the specified L1 block (e.g., because they were reorged).
+    pub async fn remove_l1_checkpoints_after(&mut self, l1_block_number: u64) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM eth_watch_l1_checkpoints
+            WHERE
+                l1_block_number > $1
+            "#,
+            l1_block_number as i64
+        )
+        .instrument("remove_eth_watch_l1_checkpoints_after")
+        .with_arg("l1_block_number", &l1_block_number)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Removes all L1 checkpoints except for `retained_count` latest ones.
+    pub async fn prune_l1_checkpoints(&mut self, retained_count: usize) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM eth_watch_l1_checkpoints
+            WHERE
+                l1_block_number NOT IN (
+                    SELECT
+                        l1_block_number
+                    FROM
+                        eth_watch_l1_checkpoints
+                    ORDER BY
+                        l1_block_number DESC
+                    LIMIT
+                        $1
+                )
+            "#,
+            retained_count as i64
+        )
+        .instrument("prune_eth_watch_l1_checkpoints")
+        .with_arg("retained_count", &retained_count)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConnectionPool;
+
+    #[tokio::test]
+    async fn persisting_processor_cursors() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        let mut dal = conn.eth_watch_dal();
+
+        assert_eq!(
+            dal.get_processor_cursor("priority_ops").await.unwrap(),
+            None
+        );
+        dal.set_processor_cursor("priority_ops", 10).await.unwrap();
+        dal.set_processor_cursor("upgrades", 5).await.unwrap();
+        assert_eq!(
+            dal.get_processor_cursor("priority_ops").await.unwrap(),
+            Some(10)
+        );
+
+        dal.set_processor_cursor("priority_ops", 20).await.unwrap();
+        assert_eq!(
+            dal.get_processor_cursor("priority_ops").await.unwrap(),
+            Some(20)
+        );
+        assert_eq!(dal.get_processor_cursor("upgrades").await.unwrap(), Some(5));
+    }
+
+    fn checkpoint(l1_block_number: u64) -> L1Checkpoint {
+        L1Checkpoint {
+            l1_block_number,
+            l1_block_hash: H256::from_low_u64_be(l1_block_number),
+        }
+    }
+
+    #[tokio::test]
+    async fn managing_l1_checkpoints() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        let mut dal = conn.eth_watch_dal();
+
+        for number in [10, 20, 30, 40] {
+            dal.insert_l1_checkpoint(checkpoint(number)).await.unwrap();
+        }
+        let checkpoints = dal.get_latest_l1_checkpoints(3).await.unwrap();
+        assert_eq!(
+            checkpoints,
+            [checkpoint(40), checkpoint(30), checkpoint(20)]
+        );
+
+        dal.remove_l1_checkpoints_after(25).await.unwrap();
+        let checkpoints = dal.get_latest_l1_checkpoints(10).await.unwrap();
+        assert_eq!(checkpoints, [checkpoint(20), checkpoint(10)]);
+
+        dal.prune_l1_checkpoints(1).await.unwrap();
+        let checkpoints = dal.get_latest_l1_checkpoints(10).await.unwrap();
+        assert_eq!(checkpoints, [checkpoint(20)]);
+    }
+}
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, fmt};
+use std::{collections::HashMap, fmt, ops};
 
 use sqlx::types::chrono::Utc;
 use zksync_system_constants::L1_MESSENGER_ADDRESS;
@@ -388,6 +388,48 @@ impl EventsDal<'_, '_> {
         .collect();
         Ok(Some(events))
     }
+
+    /// Returns items to be added to logs blooms (emitter addresses and non-empty topics) for all events
+    /// in the specified miniblock range, grouped by miniblock. Miniblocks without events are not present
+    /// in the returned map.
+    pub async fn get_bloom_items_for_miniblocks(
+        &mut self,
+        miniblock_range: ops::RangeInclusive<MiniblockNumber>,
+    ) -> sqlx::Result<HashMap<MiniblockNumber, Vec<Vec<u8>>>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                miniblock_number,
+                address,
+                topic1,
+                topic2,
+                topic3,
+                topic4
+            FROM
+                events
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+            "#,
+            i64::from(miniblock_range.start().0),
+            i64::from(miniblock_range.end().0),
+        )
+        .instrument("get_bloom_items_for_miniblocks")
+        .with_arg("miniblock_range", &miniblock_range)
+        .report_latency()
+        .fetch_all(self.storage)
+        .await?;
+
+        let mut items_by_miniblock = HashMap::<_, Vec<_>>::new();
+        for row in rows {
+            let items = items_by_miniblock
+                .entry(MiniblockNumber(row.miniblock_number as u32))
+                .or_default();
+            items.push(row.address);
+            let topics = [row.topic1, row.topic2, row.topic3, row.topic4];
+            items.extend(topics.into_iter().filter(|topic| !topic.is_empty()));
+        }
+        Ok(items_by_miniblock)
+    }
 }
 
 #[cfg(test)]
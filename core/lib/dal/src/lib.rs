@@ -10,20 +10,16 @@ use crate::{
     basic_witness_input_producer_dal::BasicWitnessInputProducerDal, blocks_dal::BlocksDal,
     blocks_web3_dal::BlocksWeb3Dal, consensus_dal::ConsensusDal,
     contract_verification_dal::ContractVerificationDal, eth_sender_dal::EthSenderDal,
-    events_dal::EventsDal, events_web3_dal::EventsWeb3Dal, factory_deps_dal::FactoryDepsDal,
-    fri_gpu_prover_queue_dal::FriGpuProverQueueDal,
-    fri_proof_compressor_dal::FriProofCompressorDal,
-    fri_protocol_versions_dal::FriProtocolVersionsDal, fri_prover_dal::FriProverDal,
-    fri_scheduler_dependency_tracker_dal::FriSchedulerDependencyTrackerDal,
-    fri_witness_generator_dal::FriWitnessGeneratorDal, proof_generation_dal::ProofGenerationDal,
+    eth_watch_dal::EthWatchDal, events_dal::EventsDal, events_web3_dal::EventsWeb3Dal,
+    factory_deps_dal::FactoryDepsDal, proof_generation_dal::ProofGenerationDal,
     protocol_versions_dal::ProtocolVersionsDal,
-    protocol_versions_web3_dal::ProtocolVersionsWeb3Dal,
+    protocol_versions_web3_dal::ProtocolVersionsWeb3Dal, shadow_vm_dal::ShadowVmDal,
     snapshot_recovery_dal::SnapshotRecoveryDal, snapshots_creator_dal::SnapshotsCreatorDal,
     snapshots_dal::SnapshotsDal, storage_logs_dal::StorageLogsDal,
     storage_logs_dedup_dal::StorageLogsDedupDal, storage_web3_dal::StorageWeb3Dal,
     sync_dal::SyncDal, system_dal::SystemDal, tokens_dal::TokensDal,
     tokens_web3_dal::TokensWeb3Dal, transactions_dal::TransactionsDal,
-    transactions_web3_dal::TransactionsWeb3Dal,
+    transactions_web3_dal::TransactionsWeb3Dal, tx_admission_dal::TxAdmissionDal,
 };
 
 #[macro_use]
@@ -35,22 +31,18 @@ pub mod connection;
 pub mod consensus_dal;
 pub mod contract_verification_dal;
 pub mod eth_sender_dal;
+pub mod eth_watch_dal;
 pub mod events_dal;
 pub mod events_web3_dal;
 pub mod factory_deps_dal;
-pub mod fri_gpu_prover_queue_dal;
-pub mod fri_proof_compressor_dal;
-pub mod fri_protocol_versions_dal;
-pub mod fri_prover_dal;
-pub mod fri_scheduler_dependency_tracker_dal;
-pub mod fri_witness_generator_dal;
 pub mod healthcheck;
-mod instrument;
+pub mod instrument;
 mod metrics;
 mod models;
 pub mod proof_generation_dal;
 pub mod protocol_versions_dal;
 pub mod protocol_versions_web3_dal;
+pub mod shadow_vm_dal;
 pub mod snapshot_recovery_dal;
 pub mod snapshots_creator_dal;
 pub mod snapshots_dal;
@@ -60,11 +52,13 @@ pub mod storage_logs_dedup_dal;
 pub mod storage_web3_dal;
 pub mod sync_dal;
 pub mod system_dal;
+pub mod testonly;
 pub mod time_utils;
 pub mod tokens_dal;
 pub mod tokens_web3_dal;
 pub mod transactions_dal;
 pub mod transactions_web3_dal;
+pub mod tx_admission_dal;
 
 #[cfg(test)]
 mod tests;
@@ -148,40 +142,14 @@ impl<'a> StorageProcessor<'a> {
         ProtocolVersionsWeb3Dal { storage: self }
     }
 
-    pub fn fri_witness_generator_dal(&mut self) -> FriWitnessGeneratorDal<'_, 'a> {
-        FriWitnessGeneratorDal { storage: self }
-    }
-
-    pub fn fri_prover_jobs_dal(&mut self) -> FriProverDal<'_, 'a> {
-        FriProverDal { storage: self }
-    }
-
     pub fn sync_dal(&mut self) -> SyncDal<'_, 'a> {
         SyncDal { storage: self }
     }
 
-    pub fn fri_scheduler_dependency_tracker_dal(
-        &mut self,
-    ) -> FriSchedulerDependencyTrackerDal<'_, 'a> {
-        FriSchedulerDependencyTrackerDal { storage: self }
-    }
-
     pub fn proof_generation_dal(&mut self) -> ProofGenerationDal<'_, 'a> {
         ProofGenerationDal { storage: self }
     }
 
-    pub fn fri_gpu_prover_queue_dal(&mut self) -> FriGpuProverQueueDal<'_, 'a> {
-        FriGpuProverQueueDal { storage: self }
-    }
-
-    pub fn fri_protocol_versions_dal(&mut self) -> FriProtocolVersionsDal<'_, 'a> {
-        FriProtocolVersionsDal { storage: self }
-    }
-
-    pub fn fri_proof_compressor_dal(&mut self) -> FriProofCompressorDal<'_, 'a> {
-        FriProofCompressorDal { storage: self }
-    }
-
     pub fn system_dal(&mut self) -> SystemDal<'_, 'a> {
         SystemDal { storage: self }
     }
@@ -197,4 +165,16 @@ impl<'a> StorageProcessor<'a> {
     pub fn snapshot_recovery_dal(&mut self) -> SnapshotRecoveryDal<'_, 'a> {
         SnapshotRecoveryDal { storage: self }
     }
+
+    pub fn shadow_vm_dal(&mut self) -> ShadowVmDal<'_, 'a> {
+        ShadowVmDal { storage: self }
+    }
+
+    pub fn tx_admission_dal(&mut self) -> TxAdmissionDal<'_, 'a> {
+        TxAdmissionDal { storage: self }
+    }
+
+    pub fn eth_watch_dal(&mut self) -> EthWatchDal<'_, 'a> {
+        EthWatchDal { storage: self }
+    }
 }
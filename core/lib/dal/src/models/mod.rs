@@ -6,11 +6,9 @@ pub mod storage_event;
 pub mod storage_fee_monitor;
 pub mod storage_log;
 pub mod storage_protocol_version;
-pub mod storage_prover_job_info;
 pub mod storage_sync;
 pub mod storage_transaction;
 pub mod storage_verification_request;
-pub mod storage_witness_job_info;
 #[cfg(test)]
 mod tests;
 
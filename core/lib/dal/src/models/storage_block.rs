@@ -421,6 +421,7 @@ pub struct StorageMiniblockHeader {
     // `min(virtual_blocks`, `miniblock_number - virtual_block_number`), i.e. making sure that virtual blocks
     // never go beyond the miniblock they are based on.
     pub virtual_blocks: i64,
+    pub logs_bloom: Option<Vec<u8>>,
 }
 
 impl From<StorageMiniblockHeader> for MiniblockHeader {
@@ -462,6 +463,10 @@ impl From<StorageMiniblockHeader> for MiniblockHeader {
             gas_per_pubdata_limit: row.gas_per_pubdata_limit as u64,
             protocol_version,
             virtual_blocks: row.virtual_blocks as u32,
+            logs_bloom: row
+                .logs_bloom
+                .map(|bloom| H2048::from_slice(&bloom))
+                .unwrap_or_default(),
         }
     }
 }
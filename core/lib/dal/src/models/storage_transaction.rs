@@ -12,9 +12,10 @@ use zksync_types::{
     transaction_request::PaymasterParams,
     vm_trace::Call,
     web3::types::U64,
-    Address, Bytes, Execute, ExecuteTransactionCommon, L1TxCommonData, L2ChainId, L2TxCommonData,
-    Nonce, PackedEthSignature, PriorityOpId, Transaction, EIP_1559_TX_TYPE, EIP_2930_TX_TYPE,
-    EIP_712_TX_TYPE, H160, H256, PRIORITY_OPERATION_L2_TX_TYPE, PROTOCOL_UPGRADE_TX_TYPE, U256,
+    Address, Bytes, Execute, ExecuteTransactionCommon, L1BatchNumber, L1TxCommonData, L2ChainId,
+    L2TxCommonData, MiniblockNumber, Nonce, PackedEthSignature, PriorityOpId, Transaction,
+    EIP_1559_TX_TYPE, EIP_2930_TX_TYPE, EIP_712_TX_TYPE, H160, H256, PRIORITY_OPERATION_L2_TX_TYPE,
+    PROTOCOL_UPGRADE_TX_TYPE, U256,
 };
 use zksync_utils::{bigdecimal_to_u256, h256_to_account_address};
 
@@ -470,6 +471,50 @@ impl From<StorageTransactionDetails> for TransactionDetails {
     }
 }
 
+#[derive(Debug, Clone)]
+pub(crate) struct StoragePriorityOpStatus {
+    pub priority_op_id: i64,
+    pub hash: Vec<u8>,
+    pub received_at: NaiveDateTime,
+    pub miniblock_number: Option<i64>,
+    pub l1_batch_number: Option<i64>,
+    pub error: Option<String>,
+    pub l1_tx_hash: Option<Vec<u8>>,
+    pub expiration_timestamp: Option<i64>,
+    pub eth_execute_tx_hash: Option<String>,
+}
+
+impl From<StoragePriorityOpStatus> for api::PriorityOpStatus {
+    fn from(op: StoragePriorityOpStatus) -> Self {
+        // Priority operations cannot be skipped, so an operation not included into a miniblock is pending
+        // even if it was rejected by the state keeper.
+        let status = if op.miniblock_number.is_none() {
+            TransactionStatus::Pending
+        } else if op.error.is_some() {
+            TransactionStatus::Failed
+        } else if op.eth_execute_tx_hash.is_some() {
+            TransactionStatus::Verified
+        } else {
+            TransactionStatus::Included
+        };
+
+        Self {
+            serial_id: PriorityOpId(op.priority_op_id as u64),
+            l1_tx_hash: op.l1_tx_hash.as_deref().map(H256::from_slice),
+            l2_tx_hash: H256::from_slice(&op.hash),
+            status,
+            received_at: DateTime::<Utc>::from_naive_utc_and_offset(op.received_at, Utc),
+            expiration_timestamp: op.expiration_timestamp.map(|ts| ts as u64),
+            miniblock_number: op
+                .miniblock_number
+                .map(|number| MiniblockNumber(number as u32)),
+            l1_batch_number: op
+                .l1_batch_number
+                .map(|number| L1BatchNumber(number as u32)),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct StorageApiTransaction {
     pub tx_hash: Vec<u8>,
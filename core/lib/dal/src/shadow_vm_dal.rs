@@ -0,0 +1,92 @@
+use zksync_types::{L1BatchNumber, H256};
+
+use crate::{instrument::InstrumentExt, StorageProcessor};
+
+/// Mismatch between the primary and shadow VM execution in the state keeper.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShadowVmMismatch {
+    pub l1_batch_number: L1BatchNumber,
+    /// Hash of the transaction for which the mismatch was detected. `None` for mismatches
+    /// detected when finishing the batch.
+    pub tx_hash: Option<H256>,
+    /// Kind of the mismatch (e.g., `execution_result` or `storage_writes`).
+    pub kind: String,
+    /// VM version used by the shadow VM.
+    pub shadow_vm_version: String,
+    /// Free-form mismatch details.
+    pub details: serde_json::Value,
+}
+
+#[derive(Debug)]
+pub struct ShadowVmDal<'a, 'c> {
+    pub(crate) storage: &'a mut StorageProcessor<'c>,
+}
+
+impl ShadowVmDal<'_, '_> {
+    pub async fn insert_mismatch(&mut self, mismatch: &ShadowVmMismatch) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                shadow_vm_mismatches (
+                    l1_batch_number,
+                    tx_hash,
+                    kind,
+                    shadow_vm_version,
+                    details,
+                    created_at
+                )
+            VALUES
+                ($1, $2, $3, $4, $5, NOW())
+            "#,
+            i64::from(mismatch.l1_batch_number.0),
+            mismatch.tx_hash.as_ref().map(H256::as_bytes),
+            &mismatch.kind,
+            &mismatch.shadow_vm_version,
+            &mismatch.details,
+        )
+        .instrument("insert_shadow_vm_mismatch")
+        .with_arg("l1_batch_number", &mismatch.l1_batch_number)
+        .with_arg("kind", &mismatch.kind)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_mismatches(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> sqlx::Result<Vec<ShadowVmMismatch>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                tx_hash,
+                kind,
+                shadow_vm_version,
+                details
+            FROM
+                shadow_vm_mismatches
+            WHERE
+                l1_batch_number = $1
+            ORDER BY
+                id
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("get_shadow_vm_mismatches")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| ShadowVmMismatch {
+                l1_batch_number,
+                tx_hash: row.tx_hash.as_deref().map(H256::from_slice),
+                kind: row.kind,
+                shadow_vm_version: row.shadow_vm_version,
+                details: row.details,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,29 @@
+//! Test-only DAL utilities used to emulate storage states that cannot be produced by the public DAL methods.
+
+use std::ops;
+
+use zksync_types::MiniblockNumber;
+
+use crate::StorageProcessor;
+
+/// Removes logs blooms for the specified miniblocks, emulating miniblocks sealed before logs blooms
+/// were introduced.
+pub async fn clear_logs_blooms(
+    storage: &mut StorageProcessor<'_>,
+    numbers: ops::RangeInclusive<MiniblockNumber>,
+) -> sqlx::Result<()> {
+    sqlx::query!(
+        r#"
+        UPDATE miniblocks
+        SET
+            logs_bloom = NULL
+        WHERE
+            number BETWEEN $1 AND $2
+        "#,
+        i64::from(numbers.start().0),
+        i64::from(numbers.end().0)
+    )
+    .execute(storage.conn())
+    .await?;
+    Ok(())
+}
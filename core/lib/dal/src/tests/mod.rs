@@ -19,7 +19,7 @@ use crate::{
     blocks_dal::BlocksDal,
     connection::ConnectionPool,
     protocol_versions_dal::ProtocolVersionsDal,
-    transactions_dal::{L2TxSubmissionResult, TransactionsDal},
+    transactions_dal::{L2TxSubmissionResult, PriorityOpsRollback, TransactionsDal},
     transactions_web3_dal::TransactionsWeb3Dal,
 };
 
@@ -45,6 +45,7 @@ pub(crate) fn create_miniblock_header(number: u32) -> MiniblockHeader {
         base_system_contracts_hashes: BaseSystemContractsHashes::default(),
         protocol_version: Some(protocol_version),
         virtual_blocks: 1,
+        logs_bloom: Default::default(),
     }
 }
 
@@ -302,3 +303,55 @@ async fn remove_stuck_txs() {
 
     assert_eq!(receipts.len(), 1);
 }
+
+#[tokio::test]
+async fn rolling_back_priority_ops() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    let storage = &mut connection_pool.access_storage().await.unwrap();
+    let mut protocol_versions_dal = ProtocolVersionsDal { storage };
+    protocol_versions_dal
+        .save_protocol_version_with_tx(Default::default())
+        .await;
+
+    let storage = protocol_versions_dal.storage;
+    let mut transactions_dal = TransactionsDal { storage };
+    for (serial_id, l1_block_number) in [(0, 1), (1, 5), (2, 6)] {
+        let mut tx = mock_l1_execute();
+        tx.common_data.serial_id = PriorityOpId(serial_id);
+        tx.common_data.canonical_tx_hash = H256::from_low_u64_be(serial_id + 1);
+        tx.common_data.eth_block = l1_block_number;
+        transactions_dal
+            .insert_transaction_l1(tx, L1BlockNumber(l1_block_number as u32))
+            .await;
+    }
+    // Load the first 2 operations into the mempool.
+    transactions_dal.reset_mempool().await.unwrap();
+    let txs = transactions_dal
+        .sync_mempool(&[], &[], 0, 0, 2)
+        .await
+        .unwrap();
+    assert_eq!(txs.len(), 2);
+
+    let rollback = transactions_dal
+        .rollback_priority_ops(L1BlockNumber(4))
+        .await
+        .unwrap();
+    assert_eq!(
+        rollback,
+        PriorityOpsRollback {
+            removed: vec![PriorityOpId(2)],
+            in_mempool: vec![PriorityOpId(1)],
+            executed: vec![],
+        }
+    );
+    assert_eq!(
+        transactions_dal.last_priority_id().await,
+        Some(PriorityOpId(1))
+    );
+
+    let rollback = transactions_dal
+        .rollback_priority_ops(L1BlockNumber(6))
+        .await
+        .unwrap();
+    assert_eq!(rollback, PriorityOpsRollback::default());
+}
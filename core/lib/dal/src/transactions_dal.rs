@@ -3,7 +3,10 @@ use std::{collections::HashMap, fmt, time::Duration};
 use anyhow::Context as _;
 use bigdecimal::BigDecimal;
 use itertools::Itertools;
-use sqlx::{error, types::chrono::NaiveDateTime};
+use sqlx::{
+    error,
+    types::chrono::{DateTime, NaiveDateTime, Utc},
+};
 use zksync_types::{
     block::MiniblockExecutionData,
     fee::TransactionExecutionMetrics,
@@ -45,6 +48,32 @@ impl fmt::Display for L2TxSubmissionResult {
     }
 }
 
+/// Information about pending priority operations, i.e. ones not included into miniblocks yet.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PendingPriorityOps {
+    /// Number of pending operations.
+    pub count: usize,
+    /// Serial ID of the oldest pending operation.
+    pub oldest_serial_id: PriorityOpId,
+    /// Time when the oldest pending operation was received by the node.
+    pub oldest_received_at: DateTime<Utc>,
+    /// Expiration timestamp of the oldest pending operation set by the L1 contract, in seconds since the Unix epoch.
+    /// `None` if the operation was received before the expiration was persisted.
+    pub oldest_expiration_timestamp: Option<u64>,
+}
+
+/// Outcome of rolling back priority operations received in reorged L1 blocks.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PriorityOpsRollback {
+    /// Operations removed from the storage.
+    pub removed: Vec<PriorityOpId>,
+    /// Operations loaded into the mempool, but not executed yet. Such operations cannot be safely removed
+    /// since the state keeper may execute them at any moment.
+    pub in_mempool: Vec<PriorityOpId>,
+    /// Operations already executed in a miniblock.
+    pub executed: Vec<PriorityOpId>,
+}
+
 #[derive(Debug)]
 pub struct TransactionsDal<'c, 'a> {
     pub(crate) storage: &'c mut StorageProcessor<'a>,
@@ -152,6 +181,25 @@ impl TransactionsDal<'_, '_> {
             .fetch_optional(self.storage.conn())
             .await
             .unwrap();
+
+            // L1 data of the operation not present in the `transactions` table.
+            let l1_tx_hash = tx.common_data.eth_hash;
+            let expiration_timestamp = tx.common_data.deadline_block as i64;
+            sqlx::query!(
+                r#"
+                INSERT INTO
+                    priority_op_l1_info (priority_op_id, l1_tx_hash, expiration_timestamp)
+                VALUES
+                    ($1, $2, $3)
+                ON CONFLICT (priority_op_id) DO NOTHING
+                "#,
+                serial_id,
+                l1_tx_hash.as_bytes(),
+                expiration_timestamp,
+            )
+            .execute(self.storage.conn())
+            .await
+            .unwrap();
         }
     }
 
@@ -1074,6 +1122,117 @@ impl TransactionsDal<'_, '_> {
         }
     }
 
+    /// Returns information about priority operations not included into miniblocks yet, or `None`
+    /// if there are no such operations. Since priority operations cannot be skipped, this includes operations
+    /// rejected by the state keeper.
+    pub async fn get_pending_priority_ops(&mut self) -> sqlx::Result<Option<PendingPriorityOps>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                transactions.priority_op_id AS "priority_op_id!",
+                transactions.received_at,
+                priority_op_l1_info.expiration_timestamp AS "expiration_timestamp?",
+                COUNT(*) OVER () AS "pending_count!"
+            FROM
+                transactions
+                LEFT JOIN priority_op_l1_info ON priority_op_l1_info.priority_op_id = transactions.priority_op_id
+            WHERE
+                transactions.is_priority = TRUE
+                AND transactions.miniblock_number IS NULL
+            ORDER BY
+                transactions.priority_op_id
+            LIMIT
+                1
+            "#
+        )
+        .instrument("get_pending_priority_ops")
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| PendingPriorityOps {
+            count: row.pending_count as usize,
+            oldest_serial_id: PriorityOpId(row.priority_op_id as u64),
+            oldest_received_at: DateTime::from_naive_utc_and_offset(row.received_at, Utc),
+            oldest_expiration_timestamp: row.expiration_timestamp.map(|ts| ts as u64),
+        }))
+    }
+
+    /// Rolls back priority operations received in L1 blocks after `last_valid_l1_block`, e.g. because
+    /// these blocks were reorged. Only operations that are neither executed nor loaded into the mempool
+    /// are removed; other operations are left intact and are returned so that the caller can alert on them.
+    pub async fn rollback_priority_ops(
+        &mut self,
+        last_valid_l1_block: L1BlockNumber,
+    ) -> sqlx::Result<PriorityOpsRollback> {
+        let mut transaction = self.storage.start_transaction().await?;
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                priority_op_id AS "priority_op_id!",
+                miniblock_number,
+                in_mempool
+            FROM
+                transactions
+            WHERE
+                is_priority = TRUE
+                AND priority_op_id IS NOT NULL
+                AND l1_block_number > $1
+            ORDER BY
+                priority_op_id
+            FOR UPDATE
+            "#,
+            last_valid_l1_block.0 as i32
+        )
+        .instrument("rollback_priority_ops#select")
+        .with_arg("last_valid_l1_block", &last_valid_l1_block)
+        .fetch_all(&mut transaction)
+        .await?;
+
+        let mut rollback = PriorityOpsRollback::default();
+        for row in rows {
+            let id = PriorityOpId(row.priority_op_id as u64);
+            if row.miniblock_number.is_some() {
+                rollback.executed.push(id);
+            } else if row.in_mempool {
+                rollback.in_mempool.push(id);
+            } else {
+                rollback.removed.push(id);
+            }
+        }
+
+        if !rollback.removed.is_empty() {
+            let removed_ids: Vec<_> = rollback.removed.iter().map(|id| id.0 as i64).collect();
+            sqlx::query!(
+                r#"
+                DELETE FROM transactions
+                WHERE
+                    priority_op_id = ANY ($1)
+                "#,
+                &removed_ids
+            )
+            .instrument("rollback_priority_ops#remove_txs")
+            .with_arg("removed_ids.len", &removed_ids.len())
+            .execute(&mut transaction)
+            .await?;
+
+            sqlx::query!(
+                r#"
+                DELETE FROM priority_op_l1_info
+                WHERE
+                    priority_op_id = ANY ($1)
+                "#,
+                &removed_ids
+            )
+            .instrument("rollback_priority_ops#remove_l1_info")
+            .with_arg("removed_ids.len", &removed_ids.len())
+            .execute(&mut transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+        Ok(rollback)
+    }
+
     /// Returns miniblocks with their transactions that state_keeper needs to re-execute on restart.
     /// These are the transactions that are included to some miniblock,
     /// but not included to L1 batch. The order of the transactions is the same as it was
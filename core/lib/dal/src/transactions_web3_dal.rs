@@ -1,14 +1,15 @@
 use sqlx::types::chrono::NaiveDateTime;
 use zksync_types::{
-    api, api::TransactionReceipt, Address, L2ChainId, MiniblockNumber, Transaction,
-    ACCOUNT_CODE_STORAGE_ADDRESS, FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH, H256, U256,
+    api, api::TransactionReceipt, block::build_bloom, Address, L1BatchNumber, L2ChainId,
+    MiniblockNumber, PriorityOpId, Transaction, ACCOUNT_CODE_STORAGE_ADDRESS,
+    FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH, H256, U256,
 };
 
 use crate::{
     instrument::InstrumentExt,
     models::storage_transaction::{
-        StorageApiTransaction, StorageTransaction, StorageTransactionDetails,
-        StorageTransactionReceipt,
+        StorageApiTransaction, StoragePriorityOpStatus, StorageTransaction,
+        StorageTransactionDetails, StorageTransactionReceipt,
     },
     SqlxError, StorageProcessor,
 };
@@ -107,6 +108,8 @@ impl TransactionsWeb3Dal<'_, '_> {
                         log
                     })
                     .collect();
+                receipt.logs_bloom =
+                    build_bloom(receipt.logs.iter().flat_map(api::Log::bloom_items));
             }
 
             let l2_to_l1_logs_for_tx = l2_to_l1_logs.remove(&receipt.transaction_hash);
@@ -274,6 +277,113 @@ impl TransactionsWeb3Dal<'_, '_> {
         }
     }
 
+    /// Returns the status of the priority operation with the specified identifier. If there are several operations
+    /// created by the same L1 transaction, returns the first of them.
+    pub async fn get_priority_op_status(
+        &mut self,
+        id: api::PriorityOpIdentifier,
+    ) -> sqlx::Result<Option<api::PriorityOpStatus>> {
+        let serial_id = match id {
+            api::PriorityOpIdentifier::SerialId(serial_id) => serial_id,
+            api::PriorityOpIdentifier::L1TxHash(l1_tx_hash) => {
+                let Some(serial_id) = self.get_priority_op_id_by_l1_tx_hash(l1_tx_hash).await?
+                else {
+                    return Ok(None);
+                };
+                serial_id
+            }
+        };
+
+        let op = sqlx::query_as!(
+            StoragePriorityOpStatus,
+            r#"
+            SELECT
+                transactions.priority_op_id AS "priority_op_id!",
+                transactions.hash,
+                transactions.received_at,
+                transactions.miniblock_number,
+                transactions.l1_batch_number,
+                transactions.error,
+                priority_op_l1_info.l1_tx_hash AS "l1_tx_hash?",
+                priority_op_l1_info.expiration_timestamp AS "expiration_timestamp?",
+                execute_tx.tx_hash AS "eth_execute_tx_hash?"
+            FROM
+                transactions
+                LEFT JOIN priority_op_l1_info ON priority_op_l1_info.priority_op_id = transactions.priority_op_id
+                LEFT JOIN l1_batches ON l1_batches.number = transactions.l1_batch_number
+                LEFT JOIN eth_txs_history AS execute_tx ON (
+                    l1_batches.eth_execute_tx_id = execute_tx.eth_tx_id
+                    AND execute_tx.confirmed_at IS NOT NULL
+                )
+            WHERE
+                transactions.is_priority = TRUE
+                AND transactions.priority_op_id = $1
+            "#,
+            serial_id.0 as i64
+        )
+        .instrument("get_priority_op_status")
+        .with_arg("serial_id", &serial_id)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(op.map(Into::into))
+    }
+
+    async fn get_priority_op_id_by_l1_tx_hash(
+        &mut self,
+        l1_tx_hash: H256,
+    ) -> sqlx::Result<Option<PriorityOpId>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                priority_op_id
+            FROM
+                priority_op_l1_info
+            WHERE
+                l1_tx_hash = $1
+            ORDER BY
+                priority_op_id
+            LIMIT
+                1
+            "#,
+            l1_tx_hash.as_bytes()
+        )
+        .instrument("get_priority_op_id_by_l1_tx_hash")
+        .with_arg("l1_tx_hash", &l1_tx_hash)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| PriorityOpId(row.priority_op_id as u64)))
+    }
+
+    /// Returns the number of the L1 batch containing the specified transaction. Returns `None` if the transaction
+    /// is unknown or is not included into a sealed L1 batch yet.
+    pub async fn get_l1_batch_number_of_transaction(
+        &mut self,
+        hash: H256,
+    ) -> sqlx::Result<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                miniblocks.l1_batch_number
+            FROM
+                transactions
+                INNER JOIN miniblocks ON miniblocks.number = transactions.miniblock_number
+            WHERE
+                transactions.hash = $1
+            "#,
+            hash.as_bytes()
+        )
+        .instrument("get_l1_batch_number_of_transaction")
+        .with_arg("hash", &hash)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row
+            .and_then(|row| row.l1_batch_number)
+            .map(|number| L1BatchNumber(number as u32)))
+    }
+
     /// Returns hashes of txs which were received after `from_timestamp` and the time of receiving the last tx.
     pub async fn get_pending_txs_hashes_after(
         &mut self,
@@ -390,11 +500,19 @@ impl TransactionsWeb3Dal<'_, '_> {
 mod tests {
     use std::collections::HashMap;
 
-    use zksync_types::{fee::TransactionExecutionMetrics, l2::L2Tx, Nonce, ProtocolVersion};
+    use assert_matches::assert_matches;
+    use zksync_types::{
+        fee::TransactionExecutionMetrics,
+        l2::L2Tx,
+        tx::{tx_execution_info::TxExecutionStatus, ExecutionMetrics, TransactionExecutionResult},
+        L1BlockNumber, Nonce, ProtocolVersion,
+    };
 
     use super::*;
     use crate::{
-        tests::{create_miniblock_header, mock_execution_result, mock_l2_transaction},
+        tests::{
+            create_miniblock_header, mock_execution_result, mock_l1_execute, mock_l2_transaction,
+        },
         ConnectionPool,
     };
 
@@ -632,4 +750,91 @@ mod tests {
             .unwrap();
         assert_eq!(next_nonce, 2.into());
     }
+
+    #[tokio::test]
+    async fn getting_priority_op_status() {
+        let connection_pool = ConnectionPool::test_pool().await;
+        let mut conn = connection_pool.access_storage().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion::default())
+            .await;
+
+        let tx = mock_l1_execute();
+        let serial_id = tx.serial_id();
+        let l1_tx_hash = tx.common_data.eth_hash;
+        conn.transactions_dal()
+            .insert_transaction_l1(tx.clone(), L1BlockNumber(1))
+            .await;
+
+        let pending_ops = conn
+            .transactions_dal()
+            .get_pending_priority_ops()
+            .await
+            .unwrap()
+            .expect("no pending priority ops");
+        assert_eq!(pending_ops.count, 1);
+        assert_eq!(pending_ops.oldest_serial_id, serial_id);
+        assert_eq!(
+            pending_ops.oldest_expiration_timestamp,
+            Some(tx.common_data.deadline_block)
+        );
+
+        for id in [
+            api::PriorityOpIdentifier::SerialId(serial_id),
+            api::PriorityOpIdentifier::L1TxHash(l1_tx_hash),
+        ] {
+            let status = conn
+                .transactions_web3_dal()
+                .get_priority_op_status(id)
+                .await
+                .unwrap()
+                .expect("no priority op");
+            assert_eq!(status.serial_id, serial_id);
+            assert_eq!(status.l1_tx_hash, Some(l1_tx_hash));
+            assert_eq!(status.l2_tx_hash, tx.hash());
+            assert_matches!(status.status, api::TransactionStatus::Pending);
+            assert_eq!(status.miniblock_number, None);
+        }
+        let missing_op = conn
+            .transactions_web3_dal()
+            .get_priority_op_status(api::PriorityOpIdentifier::L1TxHash(H256::repeat_byte(1)))
+            .await
+            .unwrap();
+        assert!(missing_op.is_none());
+
+        let miniblock = create_miniblock_header(1);
+        conn.blocks_dal()
+            .insert_miniblock(&miniblock)
+            .await
+            .unwrap();
+        let tx_result = TransactionExecutionResult {
+            hash: tx.hash(),
+            transaction: tx.into(),
+            execution_info: ExecutionMetrics::default(),
+            execution_status: TxExecutionStatus::Failure,
+            refunded_gas: 0,
+            operator_suggested_refund: 0,
+            compressed_bytecodes: vec![],
+            call_traces: vec![],
+            revert_reason: None,
+        };
+        conn.transactions_dal()
+            .mark_txs_as_executed_in_miniblock(MiniblockNumber(1), &[tx_result], U256::from(1))
+            .await;
+
+        let status = conn
+            .transactions_web3_dal()
+            .get_priority_op_status(api::PriorityOpIdentifier::SerialId(serial_id))
+            .await
+            .unwrap()
+            .expect("no priority op");
+        assert_matches!(status.status, api::TransactionStatus::Failed);
+        assert_eq!(status.miniblock_number, Some(MiniblockNumber(1)));
+        let pending_ops = conn
+            .transactions_dal()
+            .get_pending_priority_ops()
+            .await
+            .unwrap();
+        assert_eq!(pending_ops, None);
+    }
 }
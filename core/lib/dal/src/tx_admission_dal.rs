@@ -0,0 +1,131 @@
+use std::{fmt, str::FromStr};
+
+use zksync_types::Address;
+
+use crate::{instrument::InstrumentExt, StorageProcessor};
+
+/// Kind of a transaction admission rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TxAdmissionRuleKind {
+    /// Transactions initiated by the address are rejected.
+    DeniedSender,
+    /// The address is allowed to deploy contracts.
+    AllowedDeployer,
+    /// The contract at the address is allowed to be called.
+    AllowedContract,
+}
+
+impl TxAdmissionRuleKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::DeniedSender => "denied_sender",
+            Self::AllowedDeployer => "allowed_deployer",
+            Self::AllowedContract => "allowed_contract",
+        }
+    }
+}
+
+impl fmt::Display for TxAdmissionRuleKind {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TxAdmissionRuleKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "denied_sender" => Ok(Self::DeniedSender),
+            "allowed_deployer" => Ok(Self::AllowedDeployer),
+            "allowed_contract" => Ok(Self::AllowedContract),
+            _ => Err(format!("unknown transaction admission rule kind: `{s}`")),
+        }
+    }
+}
+
+/// Rule for the transaction admission policy stored in Postgres.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TxAdmissionRule {
+    pub address: Address,
+    pub kind: TxAdmissionRuleKind,
+}
+
+#[derive(Debug)]
+pub struct TxAdmissionDal<'a, 'c> {
+    pub(crate) storage: &'a mut StorageProcessor<'c>,
+}
+
+impl TxAdmissionDal<'_, '_> {
+    /// Returns all stored rules. Rules with unknown kinds are skipped with a warning.
+    pub async fn get_rules(&mut self) -> sqlx::Result<Vec<TxAdmissionRule>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                address,
+                kind
+            FROM
+                tx_admission_rules
+            "#
+        )
+        .instrument("get_tx_admission_rules")
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                let kind = row
+                    .kind
+                    .parse()
+                    .map_err(|err| tracing::warn!("Skipping transaction admission rule: {err}"))
+                    .ok()?;
+                Some(TxAdmissionRule {
+                    address: Address::from_slice(&row.address),
+                    kind,
+                })
+            })
+            .collect())
+    }
+
+    /// Inserts a rule. Returns `false` if the rule already exists.
+    pub async fn insert_rule(&mut self, rule: TxAdmissionRule) -> sqlx::Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO
+                tx_admission_rules (address, kind, created_at)
+            VALUES
+                ($1, $2, NOW())
+            ON CONFLICT (address, kind) DO NOTHING
+            "#,
+            rule.address.as_bytes(),
+            rule.kind.as_str()
+        )
+        .instrument("insert_tx_admission_rule")
+        .with_arg("address", &rule.address)
+        .with_arg("kind", &rule.kind)
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Removes a rule. Returns `false` if the rule doesn't exist.
+    pub async fn remove_rule(&mut self, rule: TxAdmissionRule) -> sqlx::Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM tx_admission_rules
+            WHERE
+                address = $1
+                AND kind = $2
+            "#,
+            rule.address.as_bytes(),
+            rule.kind.as_str()
+        )
+        .instrument("remove_tx_admission_rule")
+        .with_arg("address", &rule.address)
+        .with_arg("kind", &rule.kind)
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -1,5 +1,6 @@
 use zksync_config::configs::chain::{
-    CircuitBreakerConfig, MempoolConfig, NetworkConfig, OperationsManagerConfig, StateKeeperConfig,
+    CircuitBreakerConfig, L2CongestionConfig, MempoolConfig, NetworkConfig,
+    OperationsManagerConfig, StateKeeperConfig,
 };
 
 use crate::{envy_load, FromEnv};
@@ -12,7 +13,15 @@ impl FromEnv for NetworkConfig {
 
 impl FromEnv for StateKeeperConfig {
     fn from_env() -> anyhow::Result<Self> {
-        envy_load("state_keeper", "CHAIN_STATE_KEEPER_")
+        let config: Self = envy_load("state_keeper", "CHAIN_STATE_KEEPER_")?;
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+impl FromEnv for L2CongestionConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load("l2_congestion", "CHAIN_L2_CONGESTION_")
     }
 }
 
@@ -37,7 +46,7 @@ impl FromEnv for MempoolConfig {
 #[cfg(test)]
 mod tests {
     use zksync_basic_types::L2ChainId;
-    use zksync_config::configs::chain::FeeModelVersion;
+    use zksync_config::configs::chain::{FeeModelVersion, SealCriterionName, ShadowVmVersion};
 
     use super::*;
     use crate::test_utils::{addr, EnvMutex};
@@ -94,6 +103,23 @@ mod tests {
             virtual_blocks_per_miniblock: 1,
             upload_witness_inputs_to_gcs: false,
             enum_index_migration_chunk_size: Some(2_000),
+            seal_criteria: Some(vec![
+                SealCriterionName::Slots,
+                SealCriterionName::Gas,
+                SealCriterionName::PubdataCost,
+            ]),
+            target_batch_pubdata_cost_wei: Some(1_000_000_000_000_000),
+            high_l1_gas_price_threshold_wei: Some(100_000_000_000),
+            high_l1_gas_price_block_commit_deadline_ms: Some(10_000),
+            shadow_vm_version: Some(ShadowVmVersion::Vm1_4_2),
+            tx_admission_enabled: true,
+            tx_admission_denied_senders: vec![
+                addr("0000000000000000000000000000000000000bad"),
+                addr("0000000000000000000000000000000000001bad"),
+            ],
+            tx_admission_allowed_deployers: vec![addr("de03a0B5963f75f1C8485B355fF6D30f3093BDE7")],
+            tx_admission_allowed_contracts: vec![],
+            tx_admission_reload_interval_ms: Some(30_000),
         }
     }
 
@@ -127,6 +153,15 @@ mod tests {
             CHAIN_STATE_KEEPER_ENUM_INDEX_MIGRATION_CHUNK_SIZE="2000"
             CHAIN_STATE_KEEPER_VIRTUAL_BLOCKS_PER_MINIBLOCK="1"
             CHAIN_STATE_KEEPER_VIRTUAL_BLOCKS_INTERVAL="1"
+            CHAIN_STATE_KEEPER_SEAL_CRITERIA="slots,gas,pubdata_cost"
+            CHAIN_STATE_KEEPER_TARGET_BATCH_PUBDATA_COST_WEI="1000000000000000"
+            CHAIN_STATE_KEEPER_HIGH_L1_GAS_PRICE_THRESHOLD_WEI="100000000000"
+            CHAIN_STATE_KEEPER_HIGH_L1_GAS_PRICE_BLOCK_COMMIT_DEADLINE_MS="10000"
+            CHAIN_STATE_KEEPER_SHADOW_VM_VERSION="vm1_4_2"
+            CHAIN_STATE_KEEPER_TX_ADMISSION_ENABLED="true"
+            CHAIN_STATE_KEEPER_TX_ADMISSION_DENIED_SENDERS="0x0000000000000000000000000000000000000bad,0x0000000000000000000000000000000000001bad"
+            CHAIN_STATE_KEEPER_TX_ADMISSION_ALLOWED_DEPLOYERS="0xde03a0B5963f75f1C8485B355fF6D30f3093BDE7"
+            CHAIN_STATE_KEEPER_TX_ADMISSION_RELOAD_INTERVAL_MS="30000"
         "#;
         lock.set_env(config);
 
@@ -134,6 +169,29 @@ mod tests {
         assert_eq!(actual, expected_state_keeper_config());
     }
 
+    fn expected_l2_congestion_config() -> L2CongestionConfig {
+        L2CongestionConfig {
+            target_utilization: 0.5,
+            max_change_denominator: Some(8),
+            max_multiplier: None,
+            lookback_batches: Some(50),
+        }
+    }
+
+    #[test]
+    fn l2_congestion_from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            CHAIN_L2_CONGESTION_TARGET_UTILIZATION="0.5"
+            CHAIN_L2_CONGESTION_MAX_CHANGE_DENOMINATOR="8"
+            CHAIN_L2_CONGESTION_LOOKBACK_BATCHES="50"
+        "#;
+        lock.set_env(config);
+
+        let actual = L2CongestionConfig::from_env().unwrap();
+        assert_eq!(actual, expected_l2_congestion_config());
+    }
+
     fn expected_operations_manager_config() -> OperationsManagerConfig {
         OperationsManagerConfig {
             delay_interval: 100,
@@ -186,6 +244,7 @@ mod tests {
             http_req_max_retry_number: 5,
             http_req_retry_interval_sec: 2,
             replication_lag_limit_sec: Some(10),
+            priority_op_deadline_sec: Some(3600),
         }
     }
 
@@ -197,6 +256,7 @@ mod tests {
             CHAIN_CIRCUIT_BREAKER_HTTP_REQ_MAX_RETRY_NUMBER="5"
             CHAIN_CIRCUIT_BREAKER_HTTP_REQ_RETRY_INTERVAL_SEC="2"
             CHAIN_CIRCUIT_BREAKER_REPLICATION_LAG_LIMIT_SEC="10"
+            CHAIN_CIRCUIT_BREAKER_PRIORITY_OP_DEADLINE_SEC="3600"
         "#;
         lock.set_env(config);
 
@@ -73,6 +73,8 @@ mod tests {
         let mut lock = MUTEX.lock();
         let config = r#"
             DATABASE_STATE_KEEPER_DB_PATH="/db/state_keeper"
+            DATABASE_ROCKSDB_BACKUP_INTERVAL_SEC=3600
+            DATABASE_RESTORE_ROCKSDB_FROM_BACKUP=true
             DATABASE_MERKLE_TREE_PATH="/db/tree"
             DATABASE_MERKLE_TREE_MODE=lightweight
             DATABASE_MERKLE_TREE_MULTI_GET_CHUNK_SIZE=250
@@ -84,6 +86,11 @@ mod tests {
 
         let db_config = DBConfig::from_env().unwrap();
         assert_eq!(db_config.state_keeper_db_path, "/db/state_keeper");
+        assert_eq!(
+            db_config.rocksdb_backup_interval(),
+            Some(Duration::from_secs(3600))
+        );
+        assert!(db_config.restore_rocksdb_from_backup);
         assert_eq!(db_config.merkle_tree.path, "/db/tree");
         assert_eq!(db_config.merkle_tree.mode, MerkleTreeMode::Lightweight);
         assert_eq!(db_config.merkle_tree.multi_get_chunk_size, 250);
@@ -97,6 +104,8 @@ mod tests {
         let mut lock = MUTEX.lock();
         lock.remove_env(&[
             "DATABASE_STATE_KEEPER_DB_PATH",
+            "DATABASE_ROCKSDB_BACKUP_INTERVAL_SEC",
+            "DATABASE_RESTORE_ROCKSDB_FROM_BACKUP",
             "DATABASE_MERKLE_TREE_BACKUP_PATH",
             "DATABASE_MERKLE_TREE_PATH",
             "DATABASE_MERKLE_TREE_MODE",
@@ -109,6 +118,8 @@ mod tests {
 
         let db_config = DBConfig::from_env().unwrap();
         assert_eq!(db_config.state_keeper_db_path, "./db/state_keeper");
+        assert_eq!(db_config.rocksdb_backup_interval(), None);
+        assert!(!db_config.restore_rocksdb_from_backup);
         assert_eq!(db_config.merkle_tree.path, "./db/lightweight-new");
         assert_eq!(db_config.merkle_tree.mode, MerkleTreeMode::Full);
         assert_eq!(db_config.merkle_tree.multi_get_chunk_size, 500);
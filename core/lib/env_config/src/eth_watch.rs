@@ -10,6 +10,8 @@ impl FromEnv for ETHWatchConfig {
 
 #[cfg(test)]
 mod tests {
+    use zksync_config::configs::eth_watch::L1BlockTag;
+
     use super::*;
     use crate::test_utils::EnvMutex;
 
@@ -18,6 +20,7 @@ mod tests {
     fn expected_config() -> ETHWatchConfig {
         ETHWatchConfig {
             confirmations_for_eth_event: Some(0),
+            block_tag: Some(L1BlockTag::Safe),
             eth_node_poll_interval: 300,
         }
     }
@@ -27,6 +30,7 @@ mod tests {
         let mut lock = MUTEX.lock();
         let config = r#"
             ETH_WATCH_CONFIRMATIONS_FOR_ETH_EVENT="0"
+            ETH_WATCH_BLOCK_TAG="safe"
             ETH_WATCH_ETH_NODE_POLL_INTERVAL="300"
         "#;
         lock.set_env(config);
@@ -10,7 +10,7 @@ impl FromEnv for FriProverConfig {
 
 #[cfg(test)]
 mod tests {
-    use zksync_config::configs::fri_prover::SetupLoadMode;
+    use zksync_config::configs::fri_prover::{KeystoreSource, SetupLoadMode};
 
     use super::*;
     use crate::test_utils::EnvMutex;
@@ -26,6 +26,7 @@ mod tests {
             base_layer_circuit_ids_to_be_verified: vec![1, 5],
             recursive_layer_circuit_ids_to_be_verified: vec![1, 2, 3],
             setup_load_mode: SetupLoadMode::FromDisk,
+            keystore_source: KeystoreSource::ObjectStore,
             specialized_group_id: 10,
             witness_vector_generator_thread_count: Some(5),
             queue_capacity: 10,
@@ -47,6 +48,7 @@ mod tests {
             FRI_PROVER_BASE_LAYER_CIRCUIT_IDS_TO_BE_VERIFIED="1,5"
             FRI_PROVER_RECURSIVE_LAYER_CIRCUIT_IDS_TO_BE_VERIFIED="1,2,3"
             FRI_PROVER_SETUP_LOAD_MODE="FromDisk"
+            FRI_PROVER_KEYSTORE_SOURCE="ObjectStore"
             FRI_PROVER_SPECIALIZED_GROUP_ID="10"
             FRI_PROVER_WITNESS_VECTOR_GENERATOR_THREAD_COUNT="5"
             FRI_PROVER_QUEUE_CAPACITY="10"
@@ -6,7 +6,7 @@ use std::{
 use zksync_basic_types::basic_fri_types::CircuitIdRoundTuple;
 use zksync_config::configs::fri_prover_group::FriProverGroupConfig;
 
-use crate::FromEnv;
+use crate::{envy_load, FromEnv};
 
 fn load_from_env_variable() -> HashMap<String, HashSet<CircuitIdRoundTuple>> {
     // Prepare a hash map to store the mapping of group to a vector of tuples
@@ -67,6 +67,10 @@ impl FromEnv for FriProverGroupConfig {
             group_10: groups.remove("group_10").unwrap_or_default(),
             group_11: groups.remove("group_11").unwrap_or_default(),
             group_12: groups.remove("group_12").unwrap_or_default(),
+            scheduling: envy_load(
+                "fri_prover_group_scheduling",
+                "FRI_PROVER_GROUP_SCHEDULING_",
+            )?,
         };
         config.validate()?;
         Ok(config)
@@ -77,7 +81,14 @@ impl FromEnv for FriProverGroupConfig {
 mod tests {
     use std::collections::HashSet;
 
+    use zksync_config::configs::fri_prover_group::{
+        FriProverSchedulingConfig, FriProverSchedulingPolicy,
+    };
+
     use super::*;
+    use crate::test_utils::EnvMutex;
+
+    static MUTEX: EnvMutex = EnvMutex::new();
 
     fn expected_config() -> FriProverGroupConfig {
         FriProverGroupConfig {
@@ -148,6 +159,14 @@ mod tests {
             ]
             .into_iter()
             .collect::<HashSet<_>>(),
+            scheduling: FriProverSchedulingConfig {
+                policy: FriProverSchedulingPolicy::RoundWeights,
+                basic_circuits_weight: 0,
+                leaf_aggregation_weight: 1,
+                node_aggregation_weight: 5,
+                scheduler_weight: 10,
+                reserved_aggregation_capacity_percent: 20,
+            },
         }
     }
 
@@ -246,6 +265,16 @@ mod tests {
             );
         }
 
+        let scheduling_config = r#"
+            FRI_PROVER_GROUP_SCHEDULING_POLICY="round_weights"
+            FRI_PROVER_GROUP_SCHEDULING_LEAF_AGGREGATION_WEIGHT=1
+            FRI_PROVER_GROUP_SCHEDULING_NODE_AGGREGATION_WEIGHT=5
+            FRI_PROVER_GROUP_SCHEDULING_SCHEDULER_WEIGHT=10
+            FRI_PROVER_GROUP_SCHEDULING_RESERVED_AGGREGATION_CAPACITY_PERCENT=20
+        "#;
+        let mut lock = MUTEX.lock();
+        lock.set_env(scheduling_config);
+
         let actual = FriProverGroupConfig::from_env().unwrap();
         assert_eq!(actual, expected_config());
     }
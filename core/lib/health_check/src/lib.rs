@@ -173,6 +173,26 @@ impl AppHealthCheck {
         health
     }
 
+    /// Checks health of a single component with the specified name. Returns `None` if there is no such component.
+    /// If the component is redefined, the last definition is used, similarly to [`Self::check_health()`].
+    pub async fn check_component_health(&self, name: &str) -> Option<Health> {
+        let health_check = self
+            .components
+            .lock()
+            .expect("`AppHealthCheck` is poisoned")
+            .iter()
+            .rev()
+            .find(|check| check.name() == name)
+            .cloned()?;
+        let (_, health) = Self::check_health_with_time_limit(
+            health_check.as_ref(),
+            self.slow_time_limit,
+            self.hard_time_limit,
+        )
+        .await;
+        Some(health)
+    }
+
     async fn check_health_with_time_limit(
         check: &dyn CheckHealth,
         slow_time_limit: Duration,
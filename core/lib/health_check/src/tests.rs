@@ -134,3 +134,31 @@ async fn aggregating_health_checks() {
         HealthStatus::Affected
     );
 }
+
+#[tokio::test]
+async fn checking_single_component_health() {
+    let (first_check, first_updater) = ReactiveHealthCheck::new("first");
+    let (second_check, _second_updater) = ReactiveHealthCheck::new("second");
+    let checks = AppHealthCheck::default();
+    checks.insert_component(first_check);
+    checks.insert_component(second_check);
+
+    assert!(checks.check_component_health("unknown").await.is_none());
+    let health = checks.check_component_health("first").await.unwrap();
+    assert_matches!(health.status(), HealthStatus::NotReady);
+
+    first_updater.update(HealthStatus::Ready.into());
+    let health = checks.check_component_health("first").await.unwrap();
+    assert_matches!(health.status(), HealthStatus::Ready);
+    let health = checks.check_component_health("second").await.unwrap();
+    assert_matches!(health.status(), HealthStatus::NotReady);
+
+    // The last definition of a redefined component should be used.
+    let (redefined_check, redefined_updater) = ReactiveHealthCheck::new("first");
+    checks.insert_component(redefined_check);
+    let health = checks.check_component_health("first").await.unwrap();
+    assert_matches!(health.status(), HealthStatus::NotReady);
+    redefined_updater.update(HealthStatus::Affected.into());
+    let health = checks.check_component_health("first").await.unwrap();
+    assert_matches!(health.status(), HealthStatus::Affected);
+}
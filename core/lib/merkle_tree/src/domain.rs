@@ -1,8 +1,11 @@
 //! Tying the Merkle tree implementation to the problem domain.
 
+use std::path::Path;
+
 use rayon::{ThreadPool, ThreadPoolBuilder};
 use zksync_crypto::hasher::blake2::Blake2Hasher;
 use zksync_prover_interface::inputs::{PrepareBasicCircuitsJob, StorageLogMetadata};
+use zksync_storage::rocksdb;
 use zksync_types::{
     writes::{InitialStorageWrite, RepeatedStorageWrite},
     L1BatchNumber, StorageKey,
@@ -397,6 +400,17 @@ impl ZkSyncTree {
     pub fn reset(&mut self) {
         self.tree.db.reset();
     }
+
+    /// Creates an online RocksDB checkpoint of the tree at the specified `path`. Like [`Self::reader()`],
+    /// the checkpoint **does not** include uncommitted changes to the tree; call [`Self::save()`] beforehand
+    /// to include them.
+    ///
+    /// # Errors
+    ///
+    /// Propagates RocksDB I/O errors.
+    pub fn create_checkpoint(&self, path: &Path) -> Result<(), rocksdb::Error> {
+        self.tree.db.inner().create_checkpoint(path)
+    }
 }
 
 /// Readonly handle to a [`ZkSyncTree`].
@@ -11,6 +11,7 @@ use zksync_types::{
         SnapshotFactoryDependencies, SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey,
     },
     storage::witness_block_state::WitnessBlockState,
+    system_contracts::BaseSystemContractsBytecodes,
     L1BatchNumber,
 };
 
@@ -129,6 +130,17 @@ impl StoredObject for WitnessBlockState {
     serialize_using_bincode!();
 }
 
+impl StoredObject for BaseSystemContractsBytecodes {
+    const BUCKET: Bucket = Bucket::BaseSystemContracts;
+    type Key<'a> = &'a str;
+
+    fn encode_key(key: Self::Key<'_>) -> String {
+        format!("base_system_contracts_{key}.bin")
+    }
+
+    serialize_using_bincode!();
+}
+
 impl dyn ObjectStore + '_ {
     /// Fetches the value for the given key if it exists.
     ///
@@ -24,6 +24,9 @@ pub enum Bucket {
     SchedulerWitnessJobsFri,
     ProofsFri,
     StorageSnapshot,
+    BaseSystemContracts,
+    ProverKeys,
+    RocksdbBackups,
 }
 
 impl Bucket {
@@ -40,6 +43,9 @@ impl Bucket {
             Self::SchedulerWitnessJobsFri => "scheduler_witness_jobs_fri",
             Self::ProofsFri => "proofs_fri",
             Self::StorageSnapshot => "storage_logs_snapshots",
+            Self::BaseSystemContracts => "base_system_contracts",
+            Self::ProverKeys => "prover_keys",
+            Self::RocksdbBackups => "rocksdb_backups",
         }
     }
 }
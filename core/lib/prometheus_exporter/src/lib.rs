@@ -28,7 +28,7 @@ fn configure_legacy_exporter(builder: PrometheusBuilder) -> PrometheusBuilder {
         .unwrap()
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum PrometheusTransport {
     Pull {
         port: u16,
@@ -40,7 +40,7 @@ enum PrometheusTransport {
 }
 
 /// Configuration of a Prometheus exporter.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct PrometheusExporterConfig {
     transport: PrometheusTransport,
     use_new_facade: bool,
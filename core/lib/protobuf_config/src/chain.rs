@@ -1,5 +1,5 @@
 use anyhow::Context as _;
-use zksync_basic_types::network::Network;
+use zksync_basic_types::{network::Network, Address};
 use zksync_config::configs;
 use zksync_protobuf::{repr::ProtoRepr, required};
 
@@ -51,6 +51,54 @@ impl proto::FeeModelVersion {
     }
 }
 
+impl proto::SealCriterion {
+    fn new(n: &configs::chain::SealCriterionName) -> Self {
+        use configs::chain::SealCriterionName as From;
+        match n {
+            From::Slots => Self::Slots,
+            From::Gas => Self::Gas,
+            From::PubdataBytes => Self::PubdataBytes,
+            From::Circuits => Self::Circuits,
+            From::TxEncodingSize => Self::TxEncodingSize,
+            From::GasForBatchTip => Self::GasForBatchTip,
+            From::PubdataCost => Self::PubdataCost,
+        }
+    }
+
+    fn parse(&self) -> configs::chain::SealCriterionName {
+        use configs::chain::SealCriterionName as To;
+        match self {
+            Self::Slots => To::Slots,
+            Self::Gas => To::Gas,
+            Self::PubdataBytes => To::PubdataBytes,
+            Self::Circuits => To::Circuits,
+            Self::TxEncodingSize => To::TxEncodingSize,
+            Self::GasForBatchTip => To::GasForBatchTip,
+            Self::PubdataCost => To::PubdataCost,
+        }
+    }
+}
+
+impl proto::ShadowVmVersion {
+    fn new(n: &configs::chain::ShadowVmVersion) -> Self {
+        use configs::chain::ShadowVmVersion as From;
+        match n {
+            From::VmBoojumIntegration => Self::VmBoojumIntegration,
+            From::Vm1_4_1 => Self::Vm141,
+            From::Vm1_4_2 => Self::Vm142,
+        }
+    }
+
+    fn parse(&self) -> configs::chain::ShadowVmVersion {
+        use configs::chain::ShadowVmVersion as To;
+        match self {
+            Self::VmBoojumIntegration => To::VmBoojumIntegration,
+            Self::Vm141 => To::Vm1_4_1,
+            Self::Vm142 => To::Vm1_4_2,
+        }
+    }
+}
+
 impl ProtoRepr for proto::EthNetwork {
     type Type = configs::chain::NetworkConfig;
     fn read(&self) -> anyhow::Result<Self::Type> {
@@ -77,10 +125,25 @@ impl ProtoRepr for proto::EthNetwork {
     }
 }
 
+fn parse_addresses(addresses: &[Vec<u8>]) -> anyhow::Result<Vec<Address>> {
+    addresses
+        .iter()
+        .enumerate()
+        .map(|(i, address)| parse_h160(address).context(i))
+        .collect()
+}
+
+fn build_addresses(addresses: &[Address]) -> Vec<Vec<u8>> {
+    addresses
+        .iter()
+        .map(|address| address.as_bytes().into())
+        .collect()
+}
+
 impl ProtoRepr for proto::StateKeeper {
     type Type = configs::chain::StateKeeperConfig;
     fn read(&self) -> anyhow::Result<Self::Type> {
-        Ok(Self::Type {
+        let config = Self::Type {
             transaction_slots: required(&self.transaction_slots)
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("transaction_slots")?,
@@ -140,7 +203,40 @@ impl ProtoRepr for proto::StateKeeper {
                 .map(|x| x.try_into())
                 .transpose()
                 .context("enum_index_migration_chunk_size")?,
-        })
+            seal_criteria: self
+                .seal_criteria
+                .as_ref()
+                .map(|criteria| {
+                    criteria
+                        .criteria
+                        .iter()
+                        .enumerate()
+                        .map(|(i, x)| Ok(proto::SealCriterion::try_from(*x).context(i)?.parse()))
+                        .collect::<anyhow::Result<_>>()
+                        .context("criteria")
+                })
+                .transpose()
+                .context("seal_criteria")?,
+            target_batch_pubdata_cost_wei: self.target_batch_pubdata_cost_wei,
+            high_l1_gas_price_threshold_wei: self.high_l1_gas_price_threshold_wei,
+            high_l1_gas_price_block_commit_deadline_ms: self
+                .high_l1_gas_price_block_commit_deadline_ms,
+            shadow_vm_version: self
+                .shadow_vm_version
+                .map(|x| anyhow::Ok(proto::ShadowVmVersion::try_from(x)?.parse()))
+                .transpose()
+                .context("shadow_vm_version")?,
+            tx_admission_enabled: self.tx_admission_enabled.unwrap_or_default(),
+            tx_admission_denied_senders: parse_addresses(&self.tx_admission_denied_senders)
+                .context("tx_admission_denied_senders")?,
+            tx_admission_allowed_deployers: parse_addresses(&self.tx_admission_allowed_deployers)
+                .context("tx_admission_allowed_deployers")?,
+            tx_admission_allowed_contracts: parse_addresses(&self.tx_admission_allowed_contracts)
+                .context("tx_admission_allowed_contracts")?,
+            tx_admission_reload_interval_ms: self.tx_admission_reload_interval_ms,
+        };
+        config.validate()?;
+        Ok(config)
     }
 
     fn build(this: &Self::Type) -> Self {
@@ -176,6 +272,50 @@ impl ProtoRepr for proto::StateKeeper {
                 .enum_index_migration_chunk_size
                 .as_ref()
                 .map(|x| (*x).try_into().unwrap()),
+            seal_criteria: this
+                .seal_criteria
+                .as_ref()
+                .map(|criteria| proto::SealCriteria {
+                    criteria: criteria
+                        .iter()
+                        .map(|x| proto::SealCriterion::new(x).into())
+                        .collect(),
+                }),
+            target_batch_pubdata_cost_wei: this.target_batch_pubdata_cost_wei,
+            high_l1_gas_price_threshold_wei: this.high_l1_gas_price_threshold_wei,
+            high_l1_gas_price_block_commit_deadline_ms: this
+                .high_l1_gas_price_block_commit_deadline_ms,
+            shadow_vm_version: this
+                .shadow_vm_version
+                .as_ref()
+                .map(|x| proto::ShadowVmVersion::new(x).into()),
+            tx_admission_enabled: Some(this.tx_admission_enabled),
+            tx_admission_denied_senders: build_addresses(&this.tx_admission_denied_senders),
+            tx_admission_allowed_deployers: build_addresses(&this.tx_admission_allowed_deployers),
+            tx_admission_allowed_contracts: build_addresses(&this.tx_admission_allowed_contracts),
+            tx_admission_reload_interval_ms: this.tx_admission_reload_interval_ms,
+        }
+    }
+}
+
+impl ProtoRepr for proto::L2Congestion {
+    type Type = configs::chain::L2CongestionConfig;
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            target_utilization: *required(&self.target_utilization)
+                .context("target_utilization")?,
+            max_change_denominator: self.max_change_denominator,
+            max_multiplier: self.max_multiplier,
+            lookback_batches: self.lookback_batches,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            target_utilization: Some(this.target_utilization),
+            max_change_denominator: this.max_change_denominator,
+            max_multiplier: this.max_multiplier,
+            lookback_batches: this.lookback_batches,
         }
     }
 }
@@ -234,6 +374,7 @@ impl ProtoRepr for proto::CircuitBreaker {
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("http_req_retry_interval_sec")?,
             replication_lag_limit_sec: self.replication_lag_limit_sec,
+            priority_op_deadline_sec: self.priority_op_deadline_sec,
         })
     }
 
@@ -243,6 +384,7 @@ impl ProtoRepr for proto::CircuitBreaker {
             http_req_max_retry_number: Some(this.http_req_max_retry_number.try_into().unwrap()),
             http_req_retry_interval_sec: Some(this.http_req_retry_interval_sec.into()),
             replication_lag_limit_sec: this.replication_lag_limit_sec,
+            priority_op_deadline_sec: this.priority_op_deadline_sec,
         }
     }
 }
@@ -152,6 +152,20 @@ impl ProtoRepr for proto::Contracts {
                 .map(|x| parse_h160(x))
                 .transpose()
                 .context("transparent_proxy_admin_addr")?,
+            base_system_contracts_dir: self.base_system_contracts_dir.clone(),
+            base_system_contracts_object_key: self.base_system_contracts_object_key.clone(),
+            bootloader_hash: self
+                .bootloader_hash
+                .as_ref()
+                .map(|x| parse_h256(x))
+                .transpose()
+                .context("bootloader_hash")?,
+            default_aa_hash: self
+                .default_aa_hash
+                .as_ref()
+                .map(|x| parse_h256(x))
+                .transpose()
+                .context("default_aa_hash")?,
         })
     }
 
@@ -224,6 +238,10 @@ impl ProtoRepr for proto::Contracts {
                 .transparent_proxy_admin_addr
                 .as_ref()
                 .map(|x| x.as_bytes().into()),
+            base_system_contracts_dir: this.base_system_contracts_dir.clone(),
+            base_system_contracts_object_key: this.base_system_contracts_object_key.clone(),
+            bootloader_hash: this.bootloader_hash.as_ref().map(|x| x.as_bytes().into()),
+            default_aa_hash: this.default_aa_hash.as_ref().map(|x| x.as_bytes().into()),
         }
     }
 }
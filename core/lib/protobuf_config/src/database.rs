@@ -72,6 +72,8 @@ impl ProtoRepr for proto::Db {
                 .context("state_keeper_db_path")?
                 .clone(),
             merkle_tree: read_required_repr(&self.merkle_tree).context("merkle_tree")?,
+            rocksdb_backup_interval_sec: self.rocksdb_backup_interval_sec,
+            restore_rocksdb_from_backup: self.restore_rocksdb_from_backup.unwrap_or(false),
         })
     }
 
@@ -79,6 +81,8 @@ impl ProtoRepr for proto::Db {
         Self {
             state_keeper_db_path: Some(this.state_keeper_db_path.clone()),
             merkle_tree: Some(ProtoRepr::build(&this.merkle_tree)),
+            rocksdb_backup_interval_sec: this.rocksdb_backup_interval_sec,
+            restore_rocksdb_from_backup: Some(this.restore_rocksdb_from_backup),
         }
     }
 }
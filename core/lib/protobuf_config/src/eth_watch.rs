@@ -4,11 +4,34 @@ use zksync_protobuf::{repr::ProtoRepr, required};
 
 use crate::proto::eth_watch as proto;
 
+impl proto::L1BlockTag {
+    fn new(n: &configs::eth_watch::L1BlockTag) -> Self {
+        use configs::eth_watch::L1BlockTag as From;
+        match n {
+            From::Finalized => Self::Finalized,
+            From::Safe => Self::Safe,
+        }
+    }
+
+    fn parse(&self) -> configs::eth_watch::L1BlockTag {
+        use configs::eth_watch::L1BlockTag as To;
+        match self {
+            Self::Finalized => To::Finalized,
+            Self::Safe => To::Safe,
+        }
+    }
+}
+
 impl ProtoRepr for proto::EthWatch {
     type Type = configs::ETHWatchConfig;
     fn read(&self) -> anyhow::Result<Self::Type> {
         Ok(Self::Type {
             confirmations_for_eth_event: self.confirmations_for_eth_event,
+            block_tag: self
+                .block_tag
+                .map(|x| anyhow::Ok(proto::L1BlockTag::try_from(x)?.parse()))
+                .transpose()
+                .context("block_tag")?,
             eth_node_poll_interval: *required(&self.eth_node_poll_interval)
                 .context("eth_node_poll_interval")?,
         })
@@ -18,6 +41,10 @@ impl ProtoRepr for proto::EthWatch {
         Self {
             confirmations_for_eth_event: this.confirmations_for_eth_event,
             eth_node_poll_interval: Some(this.eth_node_poll_interval),
+            block_tag: this
+                .block_tag
+                .as_ref()
+                .map(|x| proto::L1BlockTag::new(x).into()),
         }
     }
 }
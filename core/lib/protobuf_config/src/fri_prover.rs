@@ -22,6 +22,24 @@ impl proto::SetupLoadMode {
     }
 }
 
+impl proto::KeystoreSource {
+    fn new(x: &configs::fri_prover::KeystoreSource) -> Self {
+        use configs::fri_prover::KeystoreSource as From;
+        match x {
+            From::LocalDisk => Self::LocalDisk,
+            From::ObjectStore => Self::ObjectStore,
+        }
+    }
+
+    fn parse(&self) -> configs::fri_prover::KeystoreSource {
+        use configs::fri_prover::KeystoreSource as To;
+        match self {
+            Self::LocalDisk => To::LocalDisk,
+            Self::ObjectStore => To::ObjectStore,
+        }
+    }
+}
+
 impl ProtoRepr for proto::FriProver {
     type Type = configs::FriProverConfig;
     fn read(&self) -> anyhow::Result<Self::Type> {
@@ -50,6 +68,12 @@ impl ProtoRepr for proto::FriProver {
                 .and_then(|x| Ok(proto::SetupLoadMode::try_from(*x)?))
                 .context("setup_load_mode")?
                 .parse(),
+            keystore_source: self
+                .keystore_source
+                .map(|x| anyhow::Ok(proto::KeystoreSource::try_from(x)?.parse()))
+                .transpose()
+                .context("keystore_source")?
+                .unwrap_or_default(),
             specialized_group_id: required(&self.specialized_group_id)
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("specialized_group_id")?,
@@ -85,6 +109,7 @@ impl ProtoRepr for proto::FriProver {
                 this.recursive_layer_circuit_ids_to_be_verified.clone(),
             ),
             setup_load_mode: Some(proto::SetupLoadMode::new(&this.setup_load_mode).into()),
+            keystore_source: Some(proto::KeystoreSource::new(&this.keystore_source).into()),
             specialized_group_id: Some(this.specialized_group_id.into()),
             witness_vector_generator_thread_count: this
                 .witness_vector_generator_thread_count
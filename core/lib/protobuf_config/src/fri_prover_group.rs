@@ -28,6 +28,65 @@ impl ProtoRepr for proto::CircuitIdRoundTuple {
     }
 }
 
+impl proto::FriProverSchedulingPolicy {
+    fn new(x: &configs::fri_prover_group::FriProverSchedulingPolicy) -> Self {
+        use configs::fri_prover_group::FriProverSchedulingPolicy as From;
+        match x {
+            From::Default => Self::Default,
+            From::AggregationRoundFirst => Self::AggregationRoundFirst,
+            From::OldestBatchFirst => Self::OldestBatchFirst,
+            From::RoundWeights => Self::RoundWeights,
+        }
+    }
+
+    fn parse(&self) -> configs::fri_prover_group::FriProverSchedulingPolicy {
+        use configs::fri_prover_group::FriProverSchedulingPolicy as To;
+        match self {
+            Self::Default => To::Default,
+            Self::AggregationRoundFirst => To::AggregationRoundFirst,
+            Self::OldestBatchFirst => To::OldestBatchFirst,
+            Self::RoundWeights => To::RoundWeights,
+        }
+    }
+}
+
+impl ProtoRepr for proto::FriProverScheduling {
+    type Type = configs::fri_prover_group::FriProverSchedulingConfig;
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            policy: self
+                .policy
+                .map(|x| anyhow::Ok(proto::FriProverSchedulingPolicy::try_from(x)?.parse()))
+                .transpose()
+                .context("policy")?
+                .unwrap_or_default(),
+            basic_circuits_weight: self.basic_circuits_weight.unwrap_or_default(),
+            leaf_aggregation_weight: self.leaf_aggregation_weight.unwrap_or_default(),
+            node_aggregation_weight: self.node_aggregation_weight.unwrap_or_default(),
+            scheduler_weight: self.scheduler_weight.unwrap_or_default(),
+            reserved_aggregation_capacity_percent: self
+                .reserved_aggregation_capacity_percent
+                .map(u8::try_from)
+                .transpose()
+                .context("reserved_aggregation_capacity_percent")?
+                .unwrap_or_default(),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            policy: Some(proto::FriProverSchedulingPolicy::new(&this.policy).into()),
+            basic_circuits_weight: Some(this.basic_circuits_weight),
+            leaf_aggregation_weight: Some(this.leaf_aggregation_weight),
+            node_aggregation_weight: Some(this.node_aggregation_weight),
+            scheduler_weight: Some(this.scheduler_weight),
+            reserved_aggregation_capacity_percent: Some(
+                this.reserved_aggregation_capacity_percent.into(),
+            ),
+        }
+    }
+}
+
 fn read_vec(v: &[proto::CircuitIdRoundTuple]) -> anyhow::Result<HashSet<CircuitIdRoundTuple>> {
     v.iter()
         .enumerate()
@@ -58,6 +117,13 @@ impl ProtoRepr for proto::FriProverGroup {
             group_10: read_vec(&self.group_10).context("group_10")?,
             group_11: read_vec(&self.group_11).context("group_11")?,
             group_12: read_vec(&self.group_12).context("group_12")?,
+            scheduling: self
+                .scheduling
+                .as_ref()
+                .map(ProtoRepr::read)
+                .transpose()
+                .context("scheduling")?
+                .unwrap_or_default(),
         })
     }
 
@@ -76,6 +142,7 @@ impl ProtoRepr for proto::FriProverGroup {
             group_10: build_vec(&this.group_10),
             group_11: build_vec(&this.group_11),
             group_12: build_vec(&this.group_12),
+            scheduling: Some(ProtoRepr::build(&this.scheduling)),
         }
     }
 }
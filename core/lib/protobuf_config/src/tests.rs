@@ -1,3 +1,6 @@
+use zksync_config::configs::chain::{SealCriterionName, StateKeeperConfig};
+use zksync_protobuf::repr::ProtoRepr;
+
 use crate::{
     proto,
     testonly::{encode_decode, ReprConv},
@@ -16,6 +19,7 @@ fn test_encoding() {
     encode_decode::<ReprConv<proto::utils::Prometheus>>(rng);
     encode_decode::<ReprConv<proto::chain::EthNetwork>>(rng);
     encode_decode::<ReprConv<proto::chain::StateKeeper>>(rng);
+    encode_decode::<ReprConv<proto::chain::L2Congestion>>(rng);
     encode_decode::<ReprConv<proto::chain::OperationsManager>>(rng);
     encode_decode::<ReprConv<proto::chain::Mempool>>(rng);
     encode_decode::<ReprConv<proto::chain::CircuitBreaker>>(rng);
@@ -43,3 +47,18 @@ fn test_encoding() {
     encode_decode::<ReprConv<proto::witness_generator::WitnessGenerator>>(rng);
     encode_decode::<ReprConv<proto::observability::Observability>>(rng);
 }
+
+#[test]
+fn invalid_state_keeper_config_is_rejected() {
+    let config = StateKeeperConfig {
+        seal_criteria: Some(vec![SealCriterionName::PubdataCost]),
+        target_batch_pubdata_cost_wei: None,
+        ..StateKeeperConfig::for_tests()
+    };
+    let proto = proto::chain::StateKeeper::build(&config);
+    let err = proto.read().unwrap_err();
+    assert!(
+        format!("{err:#}").contains("target_batch_pubdata_cost_wei"),
+        "{err:#}"
+    );
+}
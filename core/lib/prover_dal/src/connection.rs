@@ -0,0 +1,144 @@
+//! Connection primitives for the prover database.
+//!
+//! The prover database is a separate Postgres instance from the one used by the main node. To make it impossible
+//! to accidentally query prover tables via the main node pool (or vice versa), prover DAL methods are only exposed
+//! on [`ProverStorageProcessor`], which can only be obtained from a [`ProverConnectionPool`].
+
+use std::{future::Future, time::Duration};
+
+use sqlx::PgConnection;
+use zksync_dal::{
+    connection::{ConnectionPoolBuilder, StorageProcessorWrapper, TestTemplate},
+    ConnectionPool, StorageProcessor,
+};
+
+/// Builder for [`ProverConnectionPool`]s.
+#[derive(Debug, Clone)]
+pub struct ProverConnectionPoolBuilder(ConnectionPoolBuilder);
+
+impl ProverConnectionPoolBuilder {
+    /// Overrides the maximum number of connections that can be allocated by the pool.
+    pub fn set_max_size(&mut self, max_size: u32) -> &mut Self {
+        self.0.set_max_size(max_size);
+        self
+    }
+
+    /// Sets the acquire timeout for a single connection attempt. See [`ConnectionPoolBuilder::set_acquire_timeout()`]
+    /// for details.
+    pub fn set_acquire_timeout(&mut self, timeout: Option<Duration>) -> &mut Self {
+        self.0.set_acquire_timeout(timeout);
+        self
+    }
+
+    /// Sets the statement timeout for the pool. See [`ConnectionPoolBuilder::set_statement_timeout()`]
+    /// for details.
+    pub fn set_statement_timeout(&mut self, timeout: Option<Duration>) -> &mut Self {
+        self.0.set_statement_timeout(timeout);
+        self
+    }
+
+    /// Returns the maximum number of connections that can be allocated by the pool.
+    pub fn max_size(&self) -> u32 {
+        self.0.max_size()
+    }
+
+    /// Builds a connection pool from this builder.
+    pub async fn build(&self) -> anyhow::Result<ProverConnectionPool> {
+        self.0.build().await.map(ProverConnectionPool)
+    }
+}
+
+/// Connection pool for the prover database.
+#[derive(Debug, Clone)]
+pub struct ProverConnectionPool(ConnectionPool);
+
+impl ProverConnectionPool {
+    /// Initializes a builder for connection pools. `database_url` should point to the prover database
+    /// (i.e., be obtained from `PostgresConfig::prover_url()`).
+    pub fn builder(database_url: &str, max_pool_size: u32) -> ProverConnectionPoolBuilder {
+        ProverConnectionPoolBuilder(ConnectionPool::builder(database_url, max_pool_size))
+    }
+
+    /// Initializes a builder for connection pools with a single connection. This is equivalent
+    /// to calling `Self::builder(db_url, 1)`.
+    pub fn singleton(database_url: &str) -> ProverConnectionPoolBuilder {
+        Self::builder(database_url, 1)
+    }
+
+    /// Creates a test pool connected to a fresh copy of the test prover database.
+    /// See [`ConnectionPool::test_pool()`] for details.
+    pub async fn test_pool() -> Self {
+        const DEFAULT_CONNECTIONS: u32 = 50; // Expected to be enough for any unit test.
+        const TEST_ACQUIRE_TIMEOUT: Duration = Duration::from_secs(10);
+
+        let mut builder = TestTemplate::prover_empty()
+            .expect("failed creating test template")
+            .create_db(DEFAULT_CONNECTIONS)
+            .await
+            .expect("failed creating database for tests");
+        let pool = builder
+            .set_acquire_timeout(Some(TEST_ACQUIRE_TIMEOUT))
+            .build()
+            .await
+            .expect("cannot build connection pool");
+        Self(pool)
+    }
+
+    /// Returns the maximum number of connections in this pool specified during its creation.
+    pub fn max_size(&self) -> u32 {
+        self.0.max_size()
+    }
+
+    /// Uses this pool to report Postgres-wide metrics for the prover database. See
+    /// [`ConnectionPool::run_postgres_metrics_scraping()`] for details.
+    pub async fn run_postgres_metrics_scraping(self, scrape_interval: Duration) {
+        self.0.run_postgres_metrics_scraping(scrape_interval).await;
+    }
+
+    /// Creates a [`ProverStorageProcessor`] over a recoverable connection. See [`ConnectionPool::access_storage()`]
+    /// for details.
+    pub async fn access_storage(&self) -> anyhow::Result<ProverStorageProcessor<'_>> {
+        self.0.access_storage().await.map(ProverStorageProcessor)
+    }
+
+    /// A version of `access_storage` that would also expose the duration of the connection
+    /// acquisition tagged to the `requester` name. See [`ConnectionPool::access_storage_tagged()`] for details.
+    #[track_caller]
+    pub fn access_storage_tagged(
+        &self,
+        requester: &'static str,
+    ) -> impl Future<Output = anyhow::Result<ProverStorageProcessor<'_>>> + '_ {
+        // `#[track_caller]` propagates the caller location to the wrapped pool.
+        let storage = self.0.access_storage_tagged(requester);
+        async move { storage.await.map(ProverStorageProcessor) }
+    }
+}
+
+/// Storage processor for the prover database. Provides access to prover DALs.
+#[derive(Debug)]
+pub struct ProverStorageProcessor<'a>(StorageProcessor<'a>);
+
+impl<'a> ProverStorageProcessor<'a> {
+    pub async fn start_transaction(&mut self) -> sqlx::Result<ProverStorageProcessor<'_>> {
+        self.0.start_transaction().await.map(ProverStorageProcessor)
+    }
+
+    /// Checks if the processor is currently within database transaction.
+    pub fn in_transaction(&self) -> bool {
+        self.0.in_transaction()
+    }
+
+    pub async fn commit(self) -> sqlx::Result<()> {
+        self.0.commit().await
+    }
+
+    pub(crate) fn conn(&mut self) -> &mut PgConnection {
+        StorageProcessorWrapper::conn(self)
+    }
+}
+
+impl<'a> StorageProcessorWrapper<'a> for ProverStorageProcessor<'a> {
+    fn storage_processor(&mut self) -> &mut StorageProcessor<'a> {
+        &mut self.0
+    }
+}
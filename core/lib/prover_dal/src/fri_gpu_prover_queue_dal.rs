@@ -1,14 +1,15 @@
 use std::time::Duration;
 
+use zksync_dal::time_utils::pg_interval_from_duration;
+
 use crate::{
     fri_prover_dal::types::{GpuProverInstanceStatus, SocketAddress},
-    time_utils::pg_interval_from_duration,
-    StorageProcessor,
+    ProverStorageProcessor,
 };
 
 #[derive(Debug)]
 pub struct FriGpuProverQueueDal<'a, 'c> {
-    pub(crate) storage: &'a mut StorageProcessor<'c>,
+    pub(crate) storage: &'a mut ProverStorageProcessor<'c>,
 }
 
 impl FriGpuProverQueueDal<'_, '_> {
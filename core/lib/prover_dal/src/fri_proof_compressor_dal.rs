@@ -2,17 +2,17 @@ use std::{collections::HashMap, str::FromStr, time::Duration};
 
 use sqlx::Row;
 use strum::{Display, EnumString};
+use zksync_dal::time_utils::{duration_to_naive_time, pg_interval_from_duration};
 use zksync_types::L1BatchNumber;
 
 use crate::{
-    fri_prover_dal::types::{JobCountStatistics, StuckJobs},
-    time_utils::{duration_to_naive_time, pg_interval_from_duration},
-    StorageProcessor,
+    fri_prover_dal::types::{JobCountStatistics, ProofCompressionJobFriInfo, StuckJobs},
+    ProverStorageProcessor,
 };
 
 #[derive(Debug)]
 pub struct FriProofCompressorDal<'a, 'c> {
-    pub(crate) storage: &'a mut StorageProcessor<'c>,
+    pub(crate) storage: &'a mut ProverStorageProcessor<'c>,
 }
 
 #[derive(Debug, EnumString, Display)]
@@ -327,4 +327,169 @@ impl FriProofCompressorDal<'_, '_> {
             .collect()
         }
     }
+
+    pub async fn get_proof_compression_job_for_l1_batch(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> Option<ProofCompressionJobFriInfo> {
+        sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number,
+                status,
+                attempts,
+                error,
+                picked_by,
+                updated_at
+            FROM
+                proof_compression_jobs_fri
+            WHERE
+                l1_batch_number = $1
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .fetch_optional(self.storage.conn())
+        .await
+        .unwrap()
+        .map(|row| ProofCompressionJobFriInfo {
+            l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+            status: row.status,
+            attempts: row.attempts as u32,
+            error: row.error,
+            picked_by: row.picked_by,
+            updated_at: row.updated_at,
+        })
+    }
+
+    /// Requeues the proof compression job for the specified L1 batch if it has one of the specified `statuses`,
+    /// resetting its attempts. Returns the number of requeued jobs (0 or 1).
+    pub async fn requeue_proof_compression_job_for_l1_batch(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        statuses: &[String],
+    ) -> u64 {
+        sqlx::query!(
+            r#"
+            UPDATE proof_compression_jobs_fri
+            SET
+                status = $1,
+                attempts = 0,
+                error = NULL,
+                picked_by = NULL,
+                updated_at = NOW(),
+                processing_started_at = NOW()
+            WHERE
+                l1_batch_number = $2
+                AND status = ANY ($3)
+            "#,
+            ProofCompressionJobStatus::Queued.to_string(),
+            i64::from(l1_batch_number.0),
+            statuses
+        )
+        .execute(self.storage.conn())
+        .await
+        .unwrap()
+        .rows_affected()
+    }
+
+    /// Removes the proof compression job for the specified L1 batch unless the proof was already sent
+    /// to the server. Returns the number of removed jobs (0 or 1).
+    pub async fn delete_proof_compression_job_for_l1_batch(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> u64 {
+        sqlx::query!(
+            r#"
+            DELETE FROM proof_compression_jobs_fri
+            WHERE
+                l1_batch_number = $1
+                AND status <> $2
+            "#,
+            i64::from(l1_batch_number.0),
+            ProofCompressionJobStatus::SentToServer.to_string()
+        )
+        .execute(self.storage.conn())
+        .await
+        .unwrap()
+        .rows_affected()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProverConnectionPool;
+
+    async fn prepare_failed_jobs(storage: &mut ProverStorageProcessor<'_>) {
+        let mut dal = storage.fri_proof_compressor_dal();
+        for l1_batch_number in [1, 2] {
+            dal.insert_proof_compression_job(L1BatchNumber(l1_batch_number), "fri_proof")
+                .await;
+            dal.mark_proof_compression_job_failed("error", L1BatchNumber(l1_batch_number))
+                .await;
+        }
+    }
+
+    #[tokio::test]
+    async fn requeuing_proof_compression_job_for_l1_batch() {
+        let pool = ProverConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_failed_jobs(&mut storage).await;
+
+        let mut dal = storage.fri_proof_compressor_dal();
+        let requeued_count = dal
+            .requeue_proof_compression_job_for_l1_batch(
+                L1BatchNumber(1),
+                &["in_progress".to_owned()],
+            )
+            .await;
+        assert_eq!(requeued_count, 0);
+        let requeued_count = dal
+            .requeue_proof_compression_job_for_l1_batch(L1BatchNumber(1), &["failed".to_owned()])
+            .await;
+        assert_eq!(requeued_count, 1);
+
+        let job = dal
+            .get_proof_compression_job_for_l1_batch(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(job.status, "queued");
+        assert_eq!(job.attempts, 0);
+        assert_eq!(job.error, None);
+        assert_eq!(job.picked_by, None);
+        // Jobs for other L1 batches must not be affected.
+        let job = dal
+            .get_proof_compression_job_for_l1_batch(L1BatchNumber(2))
+            .await
+            .unwrap();
+        assert_eq!(job.status, "failed");
+    }
+
+    #[tokio::test]
+    async fn deleting_proof_compression_job_for_l1_batch() {
+        let pool = ProverConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_failed_jobs(&mut storage).await;
+
+        let mut dal = storage.fri_proof_compressor_dal();
+        // Jobs with proofs sent to the server must be retained.
+        dal.mark_proof_sent_to_server(L1BatchNumber(2)).await;
+        let deleted_count = dal
+            .delete_proof_compression_job_for_l1_batch(L1BatchNumber(2))
+            .await;
+        assert_eq!(deleted_count, 0);
+        assert!(dal
+            .get_proof_compression_job_for_l1_batch(L1BatchNumber(2))
+            .await
+            .is_some());
+
+        let deleted_count = dal
+            .delete_proof_compression_job_for_l1_batch(L1BatchNumber(1))
+            .await;
+        assert_eq!(deleted_count, 1);
+        assert!(dal
+            .get_proof_compression_job_for_l1_batch(L1BatchNumber(1))
+            .await
+            .is_none());
+    }
 }
@@ -1,18 +1,20 @@
 use std::{collections::HashMap, convert::TryFrom, time::Duration};
 
+use zksync_dal::{
+    connection::StorageProcessorWrapper,
+    instrument::InstrumentExt,
+    time_utils::{duration_to_naive_time, pg_interval_from_duration},
+};
 use zksync_types::{
     basic_fri_types::{AggregationRound, CircuitIdRoundTuple},
     protocol_version::FriProtocolVersionId,
     L1BatchNumber,
 };
 
-use self::types::{FriProverJobMetadata, JobCountStatistics, StuckJobs};
-use crate::{
-    instrument::InstrumentExt,
-    metrics::MethodLatency,
-    time_utils::{duration_to_naive_time, pg_interval_from_duration},
-    StorageProcessor,
+use self::types::{
+    FriProverJobMetadata, JobCountStatistics, JobPriorityWeights, ProverJobFriInfo, StuckJobs,
 };
+use crate::{metrics::MethodLatency, ProverStorageProcessor};
 
 // TODO (PLA-775): Should not be an embedded submodule in a concrete DAL file.
 pub mod types {
@@ -20,7 +22,7 @@ pub mod types {
 
     use std::{net::IpAddr, ops::Add};
 
-    use sqlx::types::chrono::{DateTime, Utc};
+    use sqlx::types::chrono::{DateTime, NaiveDateTime, Utc};
     use zksync_types::{basic_fri_types::AggregationRound, L1BatchNumber};
 
     // This currently lives in `zksync_prover_types` -- we don't want a dependency between prover types (`zkevm_test_harness`) and DAL.
@@ -38,6 +40,39 @@ pub mod types {
         pub is_node_final_proof: bool,
     }
 
+    /// Priority weights of aggregation rounds used to order queued prover jobs. A job for L1 batch `N`
+    /// from a round with weight `w` is ordered as if it was for L1 batch `N - w`; ties are broken in favor
+    /// of later aggregation rounds, then older L1 batches.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct JobPriorityWeights([i64; 4]);
+
+    impl JobPriorityWeights {
+        /// Jobs from later aggregation rounds are always picked first. L1 batch numbers fit into `u32`,
+        /// so a weight of `2^32` per round guarantees that earlier rounds never overtake later ones.
+        pub const AGGREGATION_ROUND_FIRST: Self = Self([0, 1 << 32, 2 << 32, 3 << 32]);
+        /// Jobs for the oldest L1 batch are always picked first.
+        pub const OLDEST_BATCH_FIRST: Self = Self([0; 4]);
+
+        pub fn new(
+            basic_circuits: u32,
+            leaf_aggregation: u32,
+            node_aggregation: u32,
+            scheduler: u32,
+        ) -> Self {
+            Self([
+                basic_circuits.into(),
+                leaf_aggregation.into(),
+                node_aggregation.into(),
+                scheduler.into(),
+            ])
+        }
+
+        /// Returns weights indexed by the aggregation round.
+        pub(crate) fn as_slice(&self) -> &[i64] {
+            &self.0
+        }
+    }
+
     #[derive(Debug, Clone, Copy, Default)]
     pub struct JobCountStatistics {
         pub queued: usize,
@@ -66,6 +101,48 @@ pub mod types {
         pub attempts: u64,
     }
 
+    /// Detailed information about a prover job.
+    #[derive(Debug, Clone)]
+    pub struct ProverJobFriInfo {
+        pub id: u32,
+        pub l1_batch_number: L1BatchNumber,
+        pub circuit_id: u8,
+        pub aggregation_round: AggregationRound,
+        pub sequence_number: usize,
+        pub depth: u16,
+        pub status: String,
+        pub attempts: u32,
+        pub error: Option<String>,
+        pub picked_by: Option<String>,
+        pub updated_at: NaiveDateTime,
+    }
+
+    /// Detailed information about a witness generator job. Circuit ID and depth are only present
+    /// for rounds that have multiple jobs per L1 batch (i.e., leaf and node aggregation).
+    #[derive(Debug, Clone)]
+    pub struct WitnessJobFriInfo {
+        pub l1_batch_number: L1BatchNumber,
+        pub aggregation_round: AggregationRound,
+        pub circuit_id: Option<u8>,
+        pub depth: Option<u16>,
+        pub status: String,
+        pub attempts: u32,
+        pub error: Option<String>,
+        pub picked_by: Option<String>,
+        pub updated_at: NaiveDateTime,
+    }
+
+    /// Detailed information about a proof compression job.
+    #[derive(Debug, Clone)]
+    pub struct ProofCompressionJobFriInfo {
+        pub l1_batch_number: L1BatchNumber,
+        pub status: String,
+        pub attempts: u32,
+        pub error: Option<String>,
+        pub picked_by: Option<String>,
+        pub updated_at: NaiveDateTime,
+    }
+
     // TODO (PLA-774): Redundant structure, should be replaced with `std::net::SocketAddr`.
     #[derive(Debug, Clone)]
     pub struct SocketAddress {
@@ -237,7 +314,7 @@ pub mod types {
 
 #[derive(Debug)]
 pub struct FriProverDal<'a, 'c> {
-    pub(crate) storage: &'a mut StorageProcessor<'c>,
+    pub(crate) storage: &'a mut ProverStorageProcessor<'c>,
 }
 
 impl FriProverDal<'_, '_> {
@@ -272,10 +349,81 @@ impl FriProverDal<'_, '_> {
         drop(latency);
     }
 
+    /// Picks the next queued job for a generic prover, ordering jobs according to `weights`.
     pub async fn get_next_job(
         &mut self,
         protocol_versions: &[FriProtocolVersionId],
         picked_by: &str,
+        weights: &JobPriorityWeights,
+    ) -> Option<FriProverJobMetadata> {
+        if *weights == JobPriorityWeights::AGGREGATION_ROUND_FIRST {
+            // Use a query with a static ordering, which can be served by the index on queued jobs.
+            return self
+                .get_next_job_by_aggregation_round(protocol_versions, picked_by)
+                .await;
+        }
+
+        let protocol_versions: Vec<i32> = protocol_versions.iter().map(|&id| id as i32).collect();
+        sqlx::query!(
+            r#"
+            UPDATE prover_jobs_fri
+            SET
+                status = 'in_progress',
+                attempts = attempts + 1,
+                updated_at = NOW(),
+                processing_started_at = NOW(),
+                picked_by = $2
+            WHERE
+                id = (
+                    SELECT
+                        id
+                    FROM
+                        prover_jobs_fri
+                    WHERE
+                        status = 'queued'
+                        AND protocol_version = ANY ($1)
+                    ORDER BY
+                        l1_batch_number - ($3::BIGINT[]) [aggregation_round + 1] ASC,
+                        aggregation_round DESC,
+                        l1_batch_number ASC,
+                        id ASC
+                    LIMIT
+                        1
+                    FOR UPDATE
+                        SKIP LOCKED
+                )
+            RETURNING
+                prover_jobs_fri.id,
+                prover_jobs_fri.l1_batch_number,
+                prover_jobs_fri.circuit_id,
+                prover_jobs_fri.aggregation_round,
+                prover_jobs_fri.sequence_number,
+                prover_jobs_fri.depth,
+                prover_jobs_fri.is_node_final_proof
+            "#,
+            &protocol_versions[..],
+            picked_by,
+            weights.as_slice(),
+        )
+        .fetch_optional(self.storage.conn())
+        .await
+        .unwrap()
+        .map(|row| FriProverJobMetadata {
+            id: row.id as u32,
+            block_number: L1BatchNumber(row.l1_batch_number as u32),
+            circuit_id: row.circuit_id as u8,
+            aggregation_round: AggregationRound::try_from(i32::from(row.aggregation_round))
+                .unwrap(),
+            sequence_number: row.sequence_number as usize,
+            depth: row.depth as u16,
+            is_node_final_proof: row.is_node_final_proof,
+        })
+    }
+
+    async fn get_next_job_by_aggregation_round(
+        &mut self,
+        protocol_versions: &[FriProtocolVersionId],
+        picked_by: &str,
     ) -> Option<FriProverJobMetadata> {
         let protocol_versions: Vec<i32> = protocol_versions.iter().map(|&id| id as i32).collect();
         sqlx::query!(
@@ -337,6 +485,7 @@ impl FriProverDal<'_, '_> {
         circuits_to_pick: &[CircuitIdRoundTuple],
         protocol_versions: &[FriProtocolVersionId],
         picked_by: &str,
+        weights: &JobPriorityWeights,
     ) -> Option<FriProverJobMetadata> {
         let circuit_ids: Vec<_> = circuits_to_pick
             .iter()
@@ -384,8 +533,9 @@ impl FriProverDal<'_, '_> {
                                 1
                         ) AS pj ON TRUE
                     ORDER BY
-                        pj.l1_batch_number ASC,
+                        pj.l1_batch_number - ($5::BIGINT[]) [pj.aggregation_round + 1] ASC,
                         pj.aggregation_round DESC,
+                        pj.l1_batch_number ASC,
                         pj.id ASC
                     LIMIT
                         1
@@ -405,6 +555,7 @@ impl FriProverDal<'_, '_> {
             &aggregation_rounds[..],
             &protocol_versions[..],
             picked_by,
+            weights.as_slice(),
         )
         .fetch_optional(self.storage.conn())
         .await
@@ -493,7 +644,7 @@ impl FriProverDal<'_, '_> {
         .instrument("save_fri_proof")
         .report_latency()
         .with_arg("id", &id)
-        .fetch_optional(self.storage)
+        .fetch_optional(self.storage.storage_processor())
         .await
         .unwrap()
         .map(|row| FriProverJobMetadata {
@@ -673,6 +824,33 @@ impl FriProverDal<'_, '_> {
         }
     }
 
+    /// Returns the number of in-progress jobs from `min_aggregation_round` or later rounds, and the total number
+    /// of in-progress jobs.
+    pub async fn in_progress_job_counts(
+        &mut self,
+        min_aggregation_round: AggregationRound,
+    ) -> (usize, usize) {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) FILTER (
+                    WHERE
+                        aggregation_round >= $1
+                ) AS "aggregation!",
+                COUNT(*) AS "total!"
+            FROM
+                prover_jobs_fri
+            WHERE
+                status IN ('in_progress', 'in_gpu_proof')
+            "#,
+            min_aggregation_round as i16
+        )
+        .fetch_one(self.storage.conn())
+        .await
+        .unwrap();
+        (row.aggregation as usize, row.total as usize)
+    }
+
     pub async fn min_unproved_l1_batch_number(&mut self) -> HashMap<(u8, u8), L1BatchNumber> {
         {
             sqlx::query!(
@@ -789,4 +967,419 @@ impl FriProverDal<'_, '_> {
         .ok()?
         .map(|row| row.id as u32)
     }
+
+    pub async fn get_prover_jobs_for_l1_batch(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> Vec<ProverJobFriInfo> {
+        sqlx::query!(
+            r#"
+            SELECT
+                id,
+                l1_batch_number,
+                circuit_id,
+                aggregation_round,
+                sequence_number,
+                depth,
+                status,
+                attempts,
+                error,
+                picked_by,
+                updated_at
+            FROM
+                prover_jobs_fri
+            WHERE
+                l1_batch_number = $1
+            ORDER BY
+                aggregation_round,
+                circuit_id,
+                depth,
+                sequence_number
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .fetch_all(self.storage.conn())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|row| ProverJobFriInfo {
+            id: row.id as u32,
+            l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+            circuit_id: row.circuit_id as u8,
+            aggregation_round: AggregationRound::try_from(i32::from(row.aggregation_round))
+                .unwrap(),
+            sequence_number: row.sequence_number as usize,
+            depth: row.depth as u16,
+            status: row.status,
+            attempts: row.attempts as u32,
+            error: row.error,
+            picked_by: row.picked_by,
+            updated_at: row.updated_at,
+        })
+        .collect()
+    }
+
+    /// Requeues prover jobs for the specified L1 batch that have one of the specified `statuses`,
+    /// resetting their attempts. Returns the number of requeued jobs.
+    pub async fn requeue_jobs_for_l1_batch(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        statuses: &[String],
+    ) -> u64 {
+        sqlx::query!(
+            r#"
+            UPDATE prover_jobs_fri
+            SET
+                status = 'queued',
+                attempts = 0,
+                error = NULL,
+                picked_by = NULL,
+                updated_at = NOW(),
+                processing_started_at = NOW()
+            WHERE
+                l1_batch_number = $1
+                AND status = ANY ($2)
+            "#,
+            i64::from(l1_batch_number.0),
+            statuses
+        )
+        .execute(self.storage.conn())
+        .await
+        .unwrap()
+        .rows_affected()
+    }
+
+    /// Removes prover jobs for the specified L1 batch starting from the specified aggregation round.
+    /// Returns the number of removed jobs.
+    pub async fn delete_jobs_for_l1_batch(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        from_round: AggregationRound,
+    ) -> u64 {
+        sqlx::query!(
+            r#"
+            DELETE FROM prover_jobs_fri
+            WHERE
+                l1_batch_number = $1
+                AND aggregation_round >= $2
+            "#,
+            i64::from(l1_batch_number.0),
+            from_round as i16
+        )
+        .execute(self.storage.conn())
+        .await
+        .unwrap()
+        .rows_affected()
+    }
+
+    /// Marks all prover jobs for the specified L1 batch that aren't successful as skipped, so that they
+    /// are never picked up again. Returns the number of skipped jobs.
+    pub async fn skip_jobs_for_l1_batch(&mut self, l1_batch_number: L1BatchNumber) -> u64 {
+        sqlx::query!(
+            r#"
+            UPDATE prover_jobs_fri
+            SET
+                status = 'skipped',
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $1
+                AND status NOT IN ('successful', 'skipped')
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .execute(self.storage.conn())
+        .await
+        .unwrap()
+        .rows_affected()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::protocol_version::L1VerifierConfig;
+
+    use super::*;
+    use crate::ProverConnectionPool;
+
+    /// Jobs inserted by [`prepare_jobs()`] as `(L1 batch number, aggregation round)` pairs.
+    const JOBS: [(u32, AggregationRound); 3] = [
+        (1, AggregationRound::BasicCircuits),
+        (2, AggregationRound::LeafAggregation),
+        (3, AggregationRound::NodeAggregation),
+    ];
+
+    async fn prepare_jobs(storage: &mut ProverStorageProcessor<'_>) {
+        storage
+            .fri_protocol_versions_dal()
+            .save_prover_protocol_version(
+                FriProtocolVersionId::latest(),
+                L1VerifierConfig::default(),
+            )
+            .await;
+        for (l1_batch_number, aggregation_round) in JOBS {
+            storage
+                .fri_prover_jobs_dal()
+                .insert_prover_job(
+                    L1BatchNumber(l1_batch_number),
+                    1,
+                    0,
+                    0,
+                    aggregation_round,
+                    "circuit_blob_url",
+                    false,
+                    FriProtocolVersionId::latest(),
+                )
+                .await;
+        }
+    }
+
+    fn circuits_to_pick() -> Vec<CircuitIdRoundTuple> {
+        JOBS.iter()
+            .map(|&(_, round)| CircuitIdRoundTuple::new(1, round as u8))
+            .collect()
+    }
+
+    async fn pick_all_jobs(
+        storage: &mut ProverStorageProcessor<'_>,
+        circuits_to_pick: Option<&[CircuitIdRoundTuple]>,
+        weights: &JobPriorityWeights,
+    ) -> Vec<(u32, AggregationRound)> {
+        let protocol_versions = [FriProtocolVersionId::latest()];
+        let mut picked_jobs = vec![];
+        loop {
+            let mut dal = storage.fri_prover_jobs_dal();
+            let job = match circuits_to_pick {
+                Some(circuits) => {
+                    dal.get_next_job_for_circuit_id_round(
+                        circuits,
+                        &protocol_versions,
+                        "test",
+                        weights,
+                    )
+                    .await
+                }
+                None => dal.get_next_job(&protocol_versions, "test", weights).await,
+            };
+            let Some(job) = job else {
+                return picked_jobs;
+            };
+            picked_jobs.push((job.block_number.0, job.aggregation_round));
+        }
+    }
+
+    #[tokio::test]
+    async fn default_job_ordering() {
+        let pool = ProverConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_jobs(&mut storage).await;
+        let mut transaction = storage.start_transaction().await.unwrap();
+
+        // Generic provers pick jobs from later aggregation rounds first.
+        let picked_jobs = pick_all_jobs(
+            &mut transaction,
+            None,
+            &JobPriorityWeights::AGGREGATION_ROUND_FIRST,
+        )
+        .await;
+        let mut expected_jobs = JOBS.to_vec();
+        expected_jobs.reverse();
+        assert_eq!(picked_jobs, expected_jobs);
+        drop(transaction); // Rolls back picked jobs
+
+        // Specialized provers pick jobs for the oldest L1 batch first.
+        let picked_jobs = pick_all_jobs(
+            &mut storage,
+            Some(&circuits_to_pick()),
+            &JobPriorityWeights::OLDEST_BATCH_FIRST,
+        )
+        .await;
+        assert_eq!(picked_jobs, JOBS);
+    }
+
+    #[tokio::test]
+    async fn job_ordering_with_custom_weights() {
+        let pool = ProverConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_jobs(&mut storage).await;
+        let mut transaction = storage.start_transaction().await.unwrap();
+
+        // Jobs are ordered as if they were for L1 batches 1, 2 and 3 - 1 = 2 respectively;
+        // the tie is broken in favor of the later aggregation round.
+        let weights = JobPriorityWeights::new(0, 0, 1, 0);
+        let expected_jobs = [JOBS[0], JOBS[2], JOBS[1]];
+
+        let picked_jobs = pick_all_jobs(&mut transaction, None, &weights).await;
+        assert_eq!(picked_jobs, expected_jobs);
+        drop(transaction); // Rolls back picked jobs
+
+        let picked_jobs = pick_all_jobs(&mut storage, Some(&circuits_to_pick()), &weights).await;
+        assert_eq!(picked_jobs, expected_jobs);
+    }
+
+    /// Inserts jobs from all aggregation rounds for L1 batch #1, and a basic circuit job for L1 batch #2.
+    async fn prepare_l1_batch_jobs(storage: &mut ProverStorageProcessor<'_>) {
+        prepare_jobs(storage).await;
+        let l1_batch_jobs = [
+            (1, AggregationRound::BasicCircuits),
+            (0, AggregationRound::LeafAggregation),
+            (0, AggregationRound::NodeAggregation),
+            (0, AggregationRound::Scheduler),
+        ];
+        for (sequence_number, aggregation_round) in l1_batch_jobs {
+            storage
+                .fri_prover_jobs_dal()
+                .insert_prover_job(
+                    L1BatchNumber(1),
+                    1,
+                    0,
+                    sequence_number,
+                    aggregation_round,
+                    "circuit_blob_url",
+                    false,
+                    FriProtocolVersionId::latest(),
+                )
+                .await;
+        }
+    }
+
+    async fn job_statuses(
+        storage: &mut ProverStorageProcessor<'_>,
+        l1_batch_number: L1BatchNumber,
+    ) -> Vec<(AggregationRound, String)> {
+        storage
+            .fri_prover_jobs_dal()
+            .get_prover_jobs_for_l1_batch(l1_batch_number)
+            .await
+            .into_iter()
+            .map(|job| (job.aggregation_round, job.status))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn requeuing_jobs_for_l1_batch() {
+        let pool = ProverConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_l1_batch_jobs(&mut storage).await;
+
+        let jobs = storage
+            .fri_prover_jobs_dal()
+            .get_prover_jobs_for_l1_batch(L1BatchNumber(1))
+            .await;
+        assert_eq!(jobs.len(), 5);
+        let mut dal = storage.fri_prover_jobs_dal();
+        dal.save_proof_error(jobs[0].id, "error".to_owned()).await;
+        dal.update_status(jobs[1].id, "in_progress").await;
+        dal.update_status(jobs[2].id, "successful").await;
+        // A job for another L1 batch must not be affected.
+        let other_job = dal.get_prover_jobs_for_l1_batch(L1BatchNumber(2)).await;
+        dal.save_proof_error(other_job[0].id, "error".to_owned())
+            .await;
+
+        let requeued_count = dal
+            .requeue_jobs_for_l1_batch(L1BatchNumber(1), &["failed".to_owned()])
+            .await;
+        assert_eq!(requeued_count, 1);
+        let requeued_job = &dal.get_prover_jobs_for_l1_batch(L1BatchNumber(1)).await[0];
+        assert_eq!(requeued_job.id, jobs[0].id);
+        assert_eq!(requeued_job.status, "queued");
+        assert_eq!(requeued_job.attempts, 0);
+        assert_eq!(requeued_job.error, None);
+        assert_eq!(requeued_job.picked_by, None);
+
+        let requeued_count = dal
+            .requeue_jobs_for_l1_batch(
+                L1BatchNumber(1),
+                &["failed".to_owned(), "in_progress".to_owned()],
+            )
+            .await;
+        assert_eq!(requeued_count, 1);
+        let statuses: Vec<_> = job_statuses(&mut storage, L1BatchNumber(1))
+            .await
+            .into_iter()
+            .map(|(_, status)| status)
+            .collect();
+        assert_eq!(
+            statuses,
+            ["queued", "queued", "successful", "queued", "queued"]
+        );
+        let other_job = &storage
+            .fri_prover_jobs_dal()
+            .get_prover_jobs_for_l1_batch(L1BatchNumber(2))
+            .await[0];
+        assert_eq!(other_job.status, "failed");
+    }
+
+    #[tokio::test]
+    async fn deleting_jobs_for_l1_batch() {
+        let pool = ProverConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_l1_batch_jobs(&mut storage).await;
+
+        let deleted_count = storage
+            .fri_prover_jobs_dal()
+            .delete_jobs_for_l1_batch(L1BatchNumber(1), AggregationRound::NodeAggregation)
+            .await;
+        assert_eq!(deleted_count, 2);
+        let rounds: Vec<_> = job_statuses(&mut storage, L1BatchNumber(1))
+            .await
+            .into_iter()
+            .map(|(round, _)| round)
+            .collect();
+        assert_eq!(
+            rounds,
+            [
+                AggregationRound::BasicCircuits,
+                AggregationRound::BasicCircuits,
+                AggregationRound::LeafAggregation
+            ]
+        );
+
+        let deleted_count = storage
+            .fri_prover_jobs_dal()
+            .delete_jobs_for_l1_batch(L1BatchNumber(1), AggregationRound::BasicCircuits)
+            .await;
+        assert_eq!(deleted_count, 3);
+        assert!(job_statuses(&mut storage, L1BatchNumber(1))
+            .await
+            .is_empty());
+        // Jobs for other L1 batches must be retained.
+        let other_jobs = job_statuses(&mut storage, L1BatchNumber(2)).await;
+        assert_eq!(other_jobs.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn skipping_jobs_for_l1_batch() {
+        let pool = ProverConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_l1_batch_jobs(&mut storage).await;
+
+        let jobs = storage
+            .fri_prover_jobs_dal()
+            .get_prover_jobs_for_l1_batch(L1BatchNumber(1))
+            .await;
+        let mut dal = storage.fri_prover_jobs_dal();
+        dal.update_status(jobs[0].id, "successful").await;
+        dal.save_proof_error(jobs[1].id, "error".to_owned()).await;
+
+        let skipped_count = dal.skip_jobs_for_l1_batch(L1BatchNumber(1)).await;
+        assert_eq!(skipped_count, 4);
+        let statuses: Vec<_> = job_statuses(&mut storage, L1BatchNumber(1))
+            .await
+            .into_iter()
+            .map(|(_, status)| status)
+            .collect();
+        assert_eq!(
+            statuses,
+            ["successful", "skipped", "skipped", "skipped", "skipped"]
+        );
+
+        // Skipping is idempotent.
+        let skipped_count = storage
+            .fri_prover_jobs_dal()
+            .skip_jobs_for_l1_batch(L1BatchNumber(1))
+            .await;
+        assert_eq!(skipped_count, 0);
+        let other_jobs = job_statuses(&mut storage, L1BatchNumber(2)).await;
+        assert_eq!(other_jobs, [(JOBS[1].1, "queued".to_owned())]);
+    }
 }
@@ -0,0 +1,303 @@
+use zksync_types::{basic_fri_types::FinalProofIds, L1BatchNumber};
+
+use crate::{fri_prover_dal::types, ProverStorageProcessor};
+
+#[derive(Debug)]
+pub struct FriSchedulerDependencyTrackerDal<'a, 'c> {
+    pub storage: &'a mut ProverStorageProcessor<'c>,
+}
+
+impl FriSchedulerDependencyTrackerDal<'_, '_> {
+    pub async fn get_l1_batches_ready_for_queuing(&mut self) -> Vec<i64> {
+        sqlx::query!(
+            r#"
+            UPDATE scheduler_dependency_tracker_fri
+            SET
+                status = 'queuing'
+            WHERE
+                l1_batch_number IN (
+                    SELECT
+                        l1_batch_number
+                    FROM
+                        scheduler_dependency_tracker_fri
+                    WHERE
+                        status != 'queued'
+                        AND status != 'skipped'
+                        AND circuit_1_final_prover_job_id IS NOT NULL
+                        AND circuit_2_final_prover_job_id IS NOT NULL
+                        AND circuit_3_final_prover_job_id IS NOT NULL
+                        AND circuit_4_final_prover_job_id IS NOT NULL
+                        AND circuit_5_final_prover_job_id IS NOT NULL
+                        AND circuit_6_final_prover_job_id IS NOT NULL
+                        AND circuit_7_final_prover_job_id IS NOT NULL
+                        AND circuit_8_final_prover_job_id IS NOT NULL
+                        AND circuit_9_final_prover_job_id IS NOT NULL
+                        AND circuit_10_final_prover_job_id IS NOT NULL
+                        AND circuit_11_final_prover_job_id IS NOT NULL
+                        AND circuit_12_final_prover_job_id IS NOT NULL
+                        AND circuit_13_final_prover_job_id IS NOT NULL
+                        AND eip_4844_final_prover_job_id_0 IS NOT NULL
+                        AND eip_4844_final_prover_job_id_1 IS NOT NULL
+                )
+            RETURNING
+                l1_batch_number;
+            "#,
+        )
+        .fetch_all(self.storage.conn())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|row| row.l1_batch_number)
+        .collect()
+    }
+
+    pub async fn mark_l1_batches_queued(&mut self, l1_batches: Vec<i64>) {
+        sqlx::query!(
+            r#"
+            UPDATE scheduler_dependency_tracker_fri
+            SET
+                status = 'queued'
+            WHERE
+                l1_batch_number = ANY ($1)
+            "#,
+            &l1_batches[..]
+        )
+        .execute(self.storage.conn())
+        .await
+        .unwrap();
+    }
+
+    pub async fn set_final_prover_job_id_for_l1_batch(
+        &mut self,
+        circuit_id: u8,
+        final_prover_job_id: u32,
+        l1_batch_number: L1BatchNumber,
+        // As of 1.4.2, there exist only 2 blobs. Their order matter.
+        // `blob_ordering` is used to determine which blob is the first one and which is the second.
+        // This will be changed when 1.5.0 will land and there will be a single node proof for blobs.
+        blob_ordering: usize,
+    ) {
+        let query = if circuit_id != types::EIP_4844_CIRCUIT_ID {
+            format!(
+                r#"
+                UPDATE scheduler_dependency_tracker_fri
+                SET circuit_{}_final_prover_job_id = $1
+                WHERE l1_batch_number = $2
+            "#,
+                circuit_id
+            )
+        } else {
+            format!(
+                r#"
+                    UPDATE scheduler_dependency_tracker_fri
+                    SET eip_4844_final_prover_job_id_{} = $1
+                    WHERE l1_batch_number = $2
+                "#,
+                blob_ordering,
+            )
+        };
+        sqlx::query(&query)
+            .bind(i64::from(final_prover_job_id))
+            .bind(i64::from(l1_batch_number.0))
+            .execute(self.storage.conn())
+            .await
+            .unwrap();
+    }
+
+    pub async fn get_final_prover_job_ids_for(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> FinalProofIds {
+        sqlx::query!(
+            r#"
+            SELECT
+                *
+            FROM
+                scheduler_dependency_tracker_fri
+            WHERE
+                l1_batch_number = $1
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .fetch_all(self.storage.conn())
+        .await
+        .unwrap()
+        .into_iter()
+        .next()
+        .map(|row| FinalProofIds {
+            node_proof_ids: [
+                row.circuit_1_final_prover_job_id.unwrap() as u32,
+                row.circuit_2_final_prover_job_id.unwrap() as u32,
+                row.circuit_3_final_prover_job_id.unwrap() as u32,
+                row.circuit_4_final_prover_job_id.unwrap() as u32,
+                row.circuit_5_final_prover_job_id.unwrap() as u32,
+                row.circuit_6_final_prover_job_id.unwrap() as u32,
+                row.circuit_7_final_prover_job_id.unwrap() as u32,
+                row.circuit_8_final_prover_job_id.unwrap() as u32,
+                row.circuit_9_final_prover_job_id.unwrap() as u32,
+                row.circuit_10_final_prover_job_id.unwrap() as u32,
+                row.circuit_11_final_prover_job_id.unwrap() as u32,
+                row.circuit_12_final_prover_job_id.unwrap() as u32,
+                row.circuit_13_final_prover_job_id.unwrap() as u32,
+            ],
+            eip_4844_proof_ids: [
+                row.eip_4844_final_prover_job_id_0.unwrap() as u32,
+                row.eip_4844_final_prover_job_id_1.unwrap() as u32,
+            ],
+        })
+        .unwrap()
+    }
+
+    /// Resets final prover job IDs for the specified L1 batch, so that the scheduler job for the batch
+    /// is queued only after all node aggregation proofs are regenerated.
+    pub async fn reset_final_prover_job_ids_for_l1_batch(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) {
+        sqlx::query!(
+            r#"
+            UPDATE scheduler_dependency_tracker_fri
+            SET
+                status = 'waiting_for_proofs',
+                circuit_1_final_prover_job_id = NULL,
+                circuit_2_final_prover_job_id = NULL,
+                circuit_3_final_prover_job_id = NULL,
+                circuit_4_final_prover_job_id = NULL,
+                circuit_5_final_prover_job_id = NULL,
+                circuit_6_final_prover_job_id = NULL,
+                circuit_7_final_prover_job_id = NULL,
+                circuit_8_final_prover_job_id = NULL,
+                circuit_9_final_prover_job_id = NULL,
+                circuit_10_final_prover_job_id = NULL,
+                circuit_11_final_prover_job_id = NULL,
+                circuit_12_final_prover_job_id = NULL,
+                circuit_13_final_prover_job_id = NULL,
+                eip_4844_final_prover_job_id_0 = NULL,
+                eip_4844_final_prover_job_id_1 = NULL,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $1
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .execute(self.storage.conn())
+        .await
+        .unwrap();
+    }
+
+    /// Marks the specified L1 batch as skipped, so that its scheduler job is never queued.
+    pub async fn skip_l1_batch(&mut self, l1_batch_number: L1BatchNumber) {
+        sqlx::query!(
+            r#"
+            UPDATE scheduler_dependency_tracker_fri
+            SET
+                status = 'skipped',
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $1
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .execute(self.storage.conn())
+        .await
+        .unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::protocol_version::{FriProtocolVersionId, L1VerifierConfig};
+
+    use super::*;
+    use crate::ProverConnectionPool;
+
+    async fn prepare_l1_batches(storage: &mut ProverStorageProcessor<'_>, l1_batches: &[u32]) {
+        storage
+            .fri_protocol_versions_dal()
+            .save_prover_protocol_version(
+                FriProtocolVersionId::latest(),
+                L1VerifierConfig::default(),
+            )
+            .await;
+        for &l1_batch_number in l1_batches {
+            storage
+                .fri_witness_generator_dal()
+                .create_aggregation_jobs(
+                    L1BatchNumber(l1_batch_number),
+                    &vec![],
+                    "scheduler_partial_input",
+                    |circuit_id| circuit_id,
+                    FriProtocolVersionId::latest(),
+                )
+                .await;
+        }
+    }
+
+    async fn set_final_prover_job_ids(
+        storage: &mut ProverStorageProcessor<'_>,
+        l1_batch_number: L1BatchNumber,
+    ) {
+        let mut dal = storage.fri_scheduler_dependency_tracker_dal();
+        for circuit_id in 1..=13 {
+            dal.set_final_prover_job_id_for_l1_batch(
+                circuit_id,
+                circuit_id.into(),
+                l1_batch_number,
+                0,
+            )
+            .await;
+        }
+        for blob_ordering in [0, 1] {
+            dal.set_final_prover_job_id_for_l1_batch(
+                types::EIP_4844_CIRCUIT_ID,
+                100 + blob_ordering as u32,
+                l1_batch_number,
+                blob_ordering,
+            )
+            .await;
+        }
+    }
+
+    #[tokio::test]
+    async fn skipped_l1_batch_is_not_queued() {
+        let pool = ProverConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_l1_batches(&mut storage, &[1, 2]).await;
+        storage
+            .fri_scheduler_dependency_tracker_dal()
+            .skip_l1_batch(L1BatchNumber(1))
+            .await;
+        for l1_batch_number in [1, 2] {
+            set_final_prover_job_ids(&mut storage, L1BatchNumber(l1_batch_number)).await;
+        }
+
+        let ready_l1_batches = storage
+            .fri_scheduler_dependency_tracker_dal()
+            .get_l1_batches_ready_for_queuing()
+            .await;
+        assert_eq!(ready_l1_batches, [2]);
+    }
+
+    #[tokio::test]
+    async fn resetting_final_prover_job_ids_for_l1_batch() {
+        let pool = ProverConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_l1_batches(&mut storage, &[1]).await;
+        set_final_prover_job_ids(&mut storage, L1BatchNumber(1)).await;
+        let mut dal = storage.fri_scheduler_dependency_tracker_dal();
+        dal.skip_l1_batch(L1BatchNumber(1)).await;
+
+        // Resetting should also clear the skipped status.
+        dal.reset_final_prover_job_ids_for_l1_batch(L1BatchNumber(1))
+            .await;
+        let ready_l1_batches = dal.get_l1_batches_ready_for_queuing().await;
+        assert!(ready_l1_batches.is_empty(), "{ready_l1_batches:?}");
+
+        set_final_prover_job_ids(&mut storage, L1BatchNumber(1)).await;
+        let ready_l1_batches = storage
+            .fri_scheduler_dependency_tracker_dal()
+            .get_l1_batches_ready_for_queuing()
+            .await;
+        assert_eq!(ready_l1_batches, [1]);
+    }
+}
@@ -1,6 +1,7 @@
 use std::{collections::HashMap, convert::TryFrom, time::Duration};
 
 use sqlx::Row;
+use zksync_dal::time_utils::{duration_to_naive_time, pg_interval_from_duration};
 use zksync_types::{
     basic_fri_types::{AggregationRound, Eip4844Blobs},
     protocol_version::FriProtocolVersionId,
@@ -10,15 +11,15 @@ use zksync_types::{
 use crate::{
     fri_prover_dal::types::{
         JobCountStatistics, LeafAggregationJobMetadata, NodeAggregationJobMetadata, StuckJobs,
+        WitnessJobFriInfo,
     },
     metrics::MethodLatency,
-    time_utils::{duration_to_naive_time, pg_interval_from_duration},
-    StorageProcessor,
+    ProverStorageProcessor,
 };
 
 #[derive(Debug)]
 pub struct FriWitnessGeneratorDal<'a, 'c> {
-    pub(crate) storage: &'a mut StorageProcessor<'c>,
+    pub(crate) storage: &'a mut ProverStorageProcessor<'c>,
 }
 
 #[derive(Debug, strum::Display, strum::EnumString, strum::AsRefStr)]
@@ -1122,6 +1123,144 @@ impl FriWitnessGeneratorDal<'_, '_> {
         }
     }
 
+    pub async fn get_witness_jobs_for_l1_batch(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        aggregation_round: AggregationRound,
+    ) -> Vec<WitnessJobFriInfo> {
+        let table_name = Self::input_table_name_for(aggregation_round);
+        let (circuit_id, depth) = match aggregation_round {
+            AggregationRound::LeafAggregation => ("circuit_id", "NULL::INT"),
+            AggregationRound::NodeAggregation => ("circuit_id", "depth"),
+            AggregationRound::BasicCircuits | AggregationRound::Scheduler => {
+                ("NULL::SMALLINT", "NULL::INT")
+            }
+        };
+        let sql = format!(
+            r#"
+                SELECT {circuit_id} AS "circuit_id", {depth} AS "depth", status, attempts, error,
+                    picked_by, updated_at
+                FROM {table_name}
+                WHERE l1_batch_number = $1
+                ORDER BY 1, 2
+                "#
+        );
+        sqlx::query(&sql)
+            .bind(i64::from(l1_batch_number.0))
+            .fetch_all(self.storage.conn())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|row| WitnessJobFriInfo {
+                l1_batch_number,
+                aggregation_round,
+                circuit_id: row.get::<Option<i16>, _>("circuit_id").map(|id| id as u8),
+                depth: row.get::<Option<i32>, _>("depth").map(|depth| depth as u16),
+                status: row.get("status"),
+                attempts: row.get::<i16, _>("attempts") as u32,
+                error: row.get("error"),
+                picked_by: row.get("picked_by"),
+                updated_at: row.get("updated_at"),
+            })
+            .collect()
+    }
+
+    /// Requeues witness generator jobs for the specified L1 batch and aggregation round that have one
+    /// of the specified `statuses`, resetting their attempts. Returns the number of requeued jobs.
+    pub async fn requeue_witness_jobs_for_l1_batch(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        aggregation_round: AggregationRound,
+        statuses: &[String],
+    ) -> u64 {
+        let table_name = Self::input_table_name_for(aggregation_round);
+        let sql = format!(
+            r#"
+                UPDATE {table_name}
+                SET status = 'queued', attempts = 0, error = NULL, picked_by = NULL,
+                    updated_at = NOW(), processing_started_at = NOW()
+                WHERE l1_batch_number = $1 AND status = ANY($2)
+                "#
+        );
+        sqlx::query(&sql)
+            .bind(i64::from(l1_batch_number.0))
+            .bind(statuses)
+            .execute(self.storage.conn())
+            .await
+            .unwrap()
+            .rows_affected()
+    }
+
+    /// Resets all witness generator jobs for the specified L1 batch and aggregation round to the specified
+    /// `status` (e.g., `queued` or `waiting_for_proofs`). For node aggregation, jobs with non-zero depth
+    /// are removed, since they are recreated once the depth-zero jobs are processed.
+    pub async fn reset_witness_jobs_for_l1_batch(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        aggregation_round: AggregationRound,
+        status: &str,
+    ) -> u64 {
+        if aggregation_round == AggregationRound::NodeAggregation {
+            sqlx::query!(
+                r#"
+                DELETE FROM node_aggregation_witness_jobs_fri
+                WHERE
+                    l1_batch_number = $1
+                    AND depth > 0
+                "#,
+                i64::from(l1_batch_number.0)
+            )
+            .execute(self.storage.conn())
+            .await
+            .unwrap();
+        }
+
+        let table_name = Self::input_table_name_for(aggregation_round);
+        let sql = format!(
+            r#"
+                UPDATE {table_name}
+                SET status = $2, attempts = 0, error = NULL, picked_by = NULL,
+                    updated_at = NOW(), processing_started_at = NULL
+                WHERE l1_batch_number = $1
+                "#
+        );
+        sqlx::query(&sql)
+            .bind(i64::from(l1_batch_number.0))
+            .bind(status)
+            .execute(self.storage.conn())
+            .await
+            .unwrap()
+            .rows_affected()
+    }
+
+    /// Marks all witness generator jobs for the specified L1 batch that aren't successful as skipped,
+    /// so that they are never picked up again. Returns the number of skipped jobs.
+    pub async fn skip_witness_jobs_for_l1_batch(&mut self, l1_batch_number: L1BatchNumber) -> u64 {
+        let mut skipped_jobs = 0;
+        for aggregation_round in [
+            AggregationRound::BasicCircuits,
+            AggregationRound::LeafAggregation,
+            AggregationRound::NodeAggregation,
+            AggregationRound::Scheduler,
+        ] {
+            let table_name = Self::input_table_name_for(aggregation_round);
+            let sql = format!(
+                r#"
+                    UPDATE {table_name}
+                    SET status = 'skipped', updated_at = NOW()
+                    WHERE l1_batch_number = $1 AND status NOT IN ('successful', 'skipped')
+                    "#
+            );
+            skipped_jobs += sqlx::query(&sql)
+                .bind(i64::from(l1_batch_number.0))
+                .execute(self.storage.conn())
+                .await
+                .unwrap()
+                .rows_affected();
+        }
+        skipped_jobs
+    }
+
     fn input_table_name_for(aggregation_round: AggregationRound) -> &'static str {
         match aggregation_round {
             AggregationRound::BasicCircuits => "witness_inputs_fri",
@@ -1153,4 +1292,240 @@ impl FriWitnessGeneratorDal<'_, '_> {
         .map(|id| FriProtocolVersionId::try_from(id as u16).unwrap())
         .unwrap()
     }
+
+    /// Returns the time elapsed since witness inputs for the specified L1 batch were received
+    /// by the prover subsystem, or `None` if there are no inputs for the batch.
+    pub async fn get_witness_inputs_age(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> Option<Duration> {
+        let age_secs = sqlx::query!(
+            r#"
+            SELECT
+                EXTRACT(
+                    EPOCH
+                    FROM
+                        (NOW() - created_at)
+                )::DOUBLE PRECISION AS "age_secs!"
+            FROM
+                witness_inputs_fri
+            WHERE
+                l1_batch_number = $1
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .fetch_optional(self.storage.conn())
+        .await
+        .unwrap()?
+        .age_secs;
+        Some(Duration::from_secs_f64(age_secs.max(0.0)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::protocol_version::L1VerifierConfig;
+
+    use super::*;
+    use crate::ProverConnectionPool;
+
+    async fn prepare_protocol_version(storage: &mut ProverStorageProcessor<'_>) {
+        storage
+            .fri_protocol_versions_dal()
+            .save_prover_protocol_version(
+                FriProtocolVersionId::latest(),
+                L1VerifierConfig::default(),
+            )
+            .await;
+    }
+
+    async fn prepare_failed_basic_witness_jobs(storage: &mut ProverStorageProcessor<'_>) {
+        prepare_protocol_version(storage).await;
+        for l1_batch_number in [1, 2] {
+            let mut dal = storage.fri_witness_generator_dal();
+            dal.save_witness_inputs(
+                L1BatchNumber(l1_batch_number),
+                "witness_inputs",
+                FriProtocolVersionId::latest(),
+                Eip4844Blobs::from(vec![0; 1]),
+            )
+            .await;
+            dal.mark_witness_job_failed("error", L1BatchNumber(l1_batch_number))
+                .await;
+        }
+    }
+
+    #[tokio::test]
+    async fn resetting_basic_witness_jobs_for_l1_batch() {
+        let pool = ProverConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_failed_basic_witness_jobs(&mut storage).await;
+
+        let reset_count = storage
+            .fri_witness_generator_dal()
+            .reset_witness_jobs_for_l1_batch(
+                L1BatchNumber(1),
+                AggregationRound::BasicCircuits,
+                "queued",
+            )
+            .await;
+        assert_eq!(reset_count, 1);
+
+        let jobs = storage
+            .fri_witness_generator_dal()
+            .get_witness_jobs_for_l1_batch(L1BatchNumber(1), AggregationRound::BasicCircuits)
+            .await;
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].status, "queued");
+        assert_eq!(jobs[0].attempts, 0);
+        assert_eq!(jobs[0].error, None);
+
+        // Jobs for other L1 batches must not be affected.
+        let jobs = storage
+            .fri_witness_generator_dal()
+            .get_witness_jobs_for_l1_batch(L1BatchNumber(2), AggregationRound::BasicCircuits)
+            .await;
+        assert_eq!(jobs[0].status, "failed");
+        assert_eq!(jobs[0].error.as_deref(), Some("error"));
+    }
+
+    #[tokio::test]
+    async fn resetting_node_aggregation_jobs_for_l1_batch() {
+        let pool = ProverConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_protocol_version(&mut storage).await;
+        for depth in [0, 1, 2] {
+            storage
+                .fri_witness_generator_dal()
+                .insert_node_aggregation_jobs(
+                    L1BatchNumber(1),
+                    1,
+                    None,
+                    depth,
+                    "aggregations",
+                    FriProtocolVersionId::latest(),
+                )
+                .await;
+        }
+
+        let reset_count = storage
+            .fri_witness_generator_dal()
+            .reset_witness_jobs_for_l1_batch(
+                L1BatchNumber(1),
+                AggregationRound::NodeAggregation,
+                "queued",
+            )
+            .await;
+        // Jobs with non-zero depth are removed rather than reset.
+        assert_eq!(reset_count, 1);
+
+        let jobs = storage
+            .fri_witness_generator_dal()
+            .get_witness_jobs_for_l1_batch(L1BatchNumber(1), AggregationRound::NodeAggregation)
+            .await;
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].circuit_id, Some(1));
+        assert_eq!(jobs[0].depth, Some(0));
+        assert_eq!(jobs[0].status, "queued");
+        assert_eq!(jobs[0].picked_by, None);
+    }
+
+    #[tokio::test]
+    async fn requeuing_witness_jobs_for_l1_batch() {
+        let pool = ProverConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_failed_basic_witness_jobs(&mut storage).await;
+
+        let mut dal = storage.fri_witness_generator_dal();
+        let requeued_count = dal
+            .requeue_witness_jobs_for_l1_batch(
+                L1BatchNumber(1),
+                AggregationRound::LeafAggregation,
+                &["failed".to_owned()],
+            )
+            .await;
+        assert_eq!(requeued_count, 0);
+        let requeued_count = dal
+            .requeue_witness_jobs_for_l1_batch(
+                L1BatchNumber(1),
+                AggregationRound::BasicCircuits,
+                &["in_progress".to_owned()],
+            )
+            .await;
+        assert_eq!(requeued_count, 0);
+
+        let requeued_count = dal
+            .requeue_witness_jobs_for_l1_batch(
+                L1BatchNumber(1),
+                AggregationRound::BasicCircuits,
+                &["failed".to_owned()],
+            )
+            .await;
+        assert_eq!(requeued_count, 1);
+        let jobs = dal
+            .get_witness_jobs_for_l1_batch(L1BatchNumber(1), AggregationRound::BasicCircuits)
+            .await;
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].status, "queued");
+        assert_eq!(jobs[0].attempts, 0);
+        assert_eq!(jobs[0].error, None);
+        assert_eq!(jobs[0].picked_by, None);
+
+        // Jobs for other L1 batches must not be affected.
+        let jobs = dal
+            .get_witness_jobs_for_l1_batch(L1BatchNumber(2), AggregationRound::BasicCircuits)
+            .await;
+        assert_eq!(jobs[0].status, "failed");
+    }
+
+    #[tokio::test]
+    async fn skipping_witness_jobs_for_l1_batch() {
+        let pool = ProverConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_failed_basic_witness_jobs(&mut storage).await;
+        let mut dal = storage.fri_witness_generator_dal();
+        for l1_batch_number in [1, 2] {
+            dal.create_aggregation_jobs(
+                L1BatchNumber(l1_batch_number),
+                &vec![
+                    (1, "closed_form_inputs".to_owned(), 1),
+                    (2, "closed_form_inputs".to_owned(), 1),
+                ],
+                "scheduler_partial_input",
+                |circuit_id| circuit_id,
+                FriProtocolVersionId::latest(),
+            )
+            .await;
+        }
+        dal.mark_witness_job_as_successful(L1BatchNumber(1), Duration::from_secs(1))
+            .await;
+
+        // 2 leaf aggregation jobs, 2 node aggregation jobs and a scheduler job
+        let skipped_count = dal.skip_witness_jobs_for_l1_batch(L1BatchNumber(1)).await;
+        assert_eq!(skipped_count, 5);
+        let basic_jobs = dal
+            .get_witness_jobs_for_l1_batch(L1BatchNumber(1), AggregationRound::BasicCircuits)
+            .await;
+        assert_eq!(basic_jobs[0].status, "successful");
+        for aggregation_round in [
+            AggregationRound::LeafAggregation,
+            AggregationRound::NodeAggregation,
+            AggregationRound::Scheduler,
+        ] {
+            let jobs = dal
+                .get_witness_jobs_for_l1_batch(L1BatchNumber(1), aggregation_round)
+                .await;
+            assert!(!jobs.is_empty());
+            assert!(jobs.iter().all(|job| job.status == "skipped"), "{jobs:?}");
+        }
+
+        // Skipping is idempotent.
+        let skipped_count = dal.skip_witness_jobs_for_l1_batch(L1BatchNumber(1)).await;
+        assert_eq!(skipped_count, 0);
+        // Jobs for other L1 batches must not be affected.
+        let jobs = dal
+            .get_witness_jobs_for_l1_batch(L1BatchNumber(2), AggregationRound::Scheduler)
+            .await;
+        assert_eq!(jobs[0].status, "waiting_for_proofs");
+    }
 }
@@ -0,0 +1,58 @@
+//! Data access layer (DAL) for the prover subsystem.
+//!
+//! Prover tables live in a dedicated Postgres database (configured via `DATABASE_PROVER_URL`), so that prover load
+//! doesn't cause lock contention on the main node database. Most prover components only need this database,
+//! the prover gateway and the object store; the basic witness generator is an exception, since it still reads
+//! L1 batch storage from the main node database via `zksync_dal`.
+
+// Linter settings.
+#![warn(clippy::cast_lossless)]
+
+pub use crate::connection::{
+    ProverConnectionPool, ProverConnectionPoolBuilder, ProverStorageProcessor,
+};
+use crate::{
+    fri_gpu_prover_queue_dal::FriGpuProverQueueDal,
+    fri_proof_compressor_dal::FriProofCompressorDal,
+    fri_protocol_versions_dal::FriProtocolVersionsDal, fri_prover_dal::FriProverDal,
+    fri_scheduler_dependency_tracker_dal::FriSchedulerDependencyTrackerDal,
+    fri_witness_generator_dal::FriWitnessGeneratorDal,
+};
+
+pub mod connection;
+pub mod fri_gpu_prover_queue_dal;
+pub mod fri_proof_compressor_dal;
+pub mod fri_protocol_versions_dal;
+pub mod fri_prover_dal;
+pub mod fri_scheduler_dependency_tracker_dal;
+pub mod fri_witness_generator_dal;
+mod metrics;
+mod models;
+
+impl<'a> ProverStorageProcessor<'a> {
+    pub fn fri_witness_generator_dal(&mut self) -> FriWitnessGeneratorDal<'_, 'a> {
+        FriWitnessGeneratorDal { storage: self }
+    }
+
+    pub fn fri_prover_jobs_dal(&mut self) -> FriProverDal<'_, 'a> {
+        FriProverDal { storage: self }
+    }
+
+    pub fn fri_scheduler_dependency_tracker_dal(
+        &mut self,
+    ) -> FriSchedulerDependencyTrackerDal<'_, 'a> {
+        FriSchedulerDependencyTrackerDal { storage: self }
+    }
+
+    pub fn fri_gpu_prover_queue_dal(&mut self) -> FriGpuProverQueueDal<'_, 'a> {
+        FriGpuProverQueueDal { storage: self }
+    }
+
+    pub fn fri_protocol_versions_dal(&mut self) -> FriProtocolVersionsDal<'_, 'a> {
+        FriProtocolVersionsDal { storage: self }
+    }
+
+    pub fn fri_proof_compressor_dal(&mut self) -> FriProofCompressorDal<'_, 'a> {
+        FriProofCompressorDal { storage: self }
+    }
+}
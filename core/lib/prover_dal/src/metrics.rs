@@ -0,0 +1,39 @@
+//! Metrics for the prover DAL.
+
+use std::{thread, time::Duration};
+
+use vise::{Buckets, Histogram, LabeledFamily, LatencyObserver, Metrics};
+
+/// Request-related prover DB metrics.
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "prover_sql")]
+pub(crate) struct RequestMetrics {
+    /// Latency of a prover DB request.
+    #[metrics(buckets = Buckets::LATENCIES, labels = ["method"])]
+    pub request: LabeledFamily<&'static str, Histogram<Duration>>,
+}
+
+#[vise::register]
+pub(crate) static REQUEST_METRICS: vise::Global<RequestMetrics> = vise::Global::new();
+
+/// Reporter of latency for DAL methods.
+///
+/// Should be created at the start of the relevant method and dropped when the latency needs to be reported.
+#[derive(Debug)]
+pub(crate) struct MethodLatency(Option<LatencyObserver<'static>>);
+
+impl MethodLatency {
+    pub fn new(name: &'static str) -> Self {
+        Self(Some(REQUEST_METRICS.request[&name].start()))
+    }
+}
+
+impl Drop for MethodLatency {
+    fn drop(&mut self) {
+        if !thread::panicking() {
+            let observer = self.0.take().unwrap();
+            // `unwrap()` is safe; the observer is only taken out on drop
+            observer.observe();
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod storage_prover_job_info;
+pub mod storage_witness_job_info;
@@ -610,6 +610,29 @@ impl RocksdbStorage {
         number_bytes.map(|bytes| L1BatchNumber(deserialize_l1_batch_number(&bytes)))
     }
 
+    /// Creates an online checkpoint of the underlying RocksDB at the specified `path` (which must not exist).
+    /// Only changes persisted to RocksDB are included into the checkpoint.
+    ///
+    /// # Return value
+    ///
+    /// Returns the last processed L1 batch number + 1 recorded in the checkpoint, or `None` if the storage is empty.
+    ///
+    /// # Errors
+    ///
+    /// Propagates RocksDB I/O errors.
+    pub async fn create_checkpoint(&self, path: &Path) -> anyhow::Result<Option<L1BatchNumber>> {
+        // The L1 batch number can only be changed by `&mut self` methods, so it cannot diverge
+        // from the checkpoint contents.
+        let l1_batch_number = self.l1_batch_number().await;
+        let db = self.db.clone();
+        let path = path.to_path_buf();
+        tokio::task::spawn_blocking(move || db.create_checkpoint(&path))
+            .await
+            .context("panicked creating state keeper RocksDB checkpoint")?
+            .context("failed creating state keeper RocksDB checkpoint")?;
+        Ok(l1_batch_number)
+    }
+
     fn serialize_state_key(key: H256) -> [u8; 32] {
         key.to_fixed_bytes()
     }
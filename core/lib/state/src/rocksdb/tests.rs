@@ -105,6 +105,30 @@ async fn rocksdb_storage_syncing_with_postgres() {
     }
 }
 
+#[tokio::test]
+async fn creating_checkpoint_for_rocksdb_storage() {
+    let pool = ConnectionPool::test_pool().await;
+    let mut conn = pool.access_storage().await.unwrap();
+    prepare_postgres(&mut conn).await;
+    let storage_logs = gen_storage_logs(20..40);
+    create_miniblock(&mut conn, MiniblockNumber(1), storage_logs.clone()).await;
+    create_l1_batch(&mut conn, L1BatchNumber(1), &storage_logs).await;
+
+    let dir = TempDir::new().expect("cannot create temporary dir for state keeper");
+    let db_dir = TempDir::new().expect("cannot create temporary dir for state keeper");
+    let storage = sync_test_storage(&db_dir, &mut conn).await;
+    let checkpoint_path = dir.path().join("checkpoint");
+    let l1_batch_number = storage.create_checkpoint(&checkpoint_path).await.unwrap();
+    assert_eq!(l1_batch_number, Some(L1BatchNumber(2)));
+    drop(storage);
+
+    let mut storage = RocksdbStorage::new(checkpoint_path).await.unwrap();
+    assert_eq!(storage.l1_batch_number().await, Some(L1BatchNumber(2)));
+    for log in &storage_logs {
+        assert_eq!(storage.read_value(&log.key), log.value);
+    }
+}
+
 #[tokio::test]
 async fn rocksdb_storage_syncing_fault_tolerance() {
     let pool = ConnectionPool::test_pool().await;
@@ -1,3 +1,5 @@
+use std::iter;
+
 use chrono::{DateTime, Utc};
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use strum::Display;
@@ -11,10 +13,11 @@ pub use crate::transaction_request::{
     Eip712Meta, SerializationTransactionError, TransactionRequest,
 };
 use crate::{
+    block::bloom_contains,
     protocol_version::L1VerifierConfig,
     vm_trace::{Call, CallType},
     web3::types::{AccessList, Index, H2048},
-    Address, MiniblockNumber, ProtocolVersionId,
+    Address, MiniblockNumber, PriorityOpId, ProtocolVersionId,
 };
 
 pub mod en;
@@ -453,6 +456,11 @@ impl Log {
         }
         false
     }
+
+    /// Returns items to be added to a logs bloom for this log: the emitter address and topics.
+    pub fn bloom_items(&self) -> impl Iterator<Item = &[u8]> + '_ {
+        iter::once(self.address.as_bytes()).chain(self.topics.iter().map(H256::as_bytes))
+    }
 }
 
 /// A log produced by a transaction.
@@ -577,6 +585,31 @@ pub struct TransactionDetails {
     pub eth_execute_tx_hash: Option<H256>,
 }
 
+/// Identifier of a priority operation used in `zks_getPriorityOpStatus`: either its serial ID (a JSON number)
+/// or the hash of the L1 transaction that created it.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PriorityOpIdentifier {
+    SerialId(PriorityOpId),
+    L1TxHash(H256),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PriorityOpStatus {
+    pub serial_id: PriorityOpId,
+    /// Hash of the L1 transaction that created the operation. `None` if the operation was received
+    /// before such hashes were persisted, or by an external node.
+    pub l1_tx_hash: Option<H256>,
+    pub l2_tx_hash: H256,
+    pub status: TransactionStatus,
+    pub received_at: DateTime<Utc>,
+    /// Expiration timestamp of the operation set by the L1 contract, in seconds since the Unix epoch.
+    pub expiration_timestamp: Option<u64>,
+    pub miniblock_number: Option<MiniblockNumber>,
+    pub l1_batch_number: Option<L1BatchNumber>,
+}
+
 #[derive(Debug, Clone)]
 pub struct GetLogsFilter {
     pub from_block: MiniblockNumber,
@@ -585,6 +618,25 @@ pub struct GetLogsFilter {
     pub topics: Vec<(u32, Vec<H256>)>,
 }
 
+impl GetLogsFilter {
+    /// Checks whether a block with the specified logs bloom may contain logs matching this filter.
+    /// If this method returns `false`, the block definitely doesn't contain matching logs.
+    pub fn may_match_bloom(&self, bloom: &H2048) -> bool {
+        let address_matches = self.addresses.is_empty()
+            || self
+                .addresses
+                .iter()
+                .any(|address| bloom_contains(bloom, address.as_bytes()));
+        address_matches
+            && self.topics.iter().all(|(_, topics)| {
+                topics.is_empty()
+                    || topics
+                        .iter()
+                        .any(|topic| bloom_contains(bloom, topic.as_bytes()))
+            })
+    }
+}
+
 /// Result of debugging block
 /// For some reasons geth returns result as {result: DebugCall}
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -718,6 +770,85 @@ pub struct L1BatchDetails {
     pub base: BlockDetailsBase,
 }
 
+/// Stage of the L1 batch lifecycle. Stages are ordered; an L1 batch always moves through them in order.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize
+)]
+#[serde(rename_all = "camelCase")]
+pub enum L1BatchStage {
+    /// L1 batch is sealed by the state keeper.
+    Sealed,
+    /// Commit transaction for the L1 batch is confirmed on L1.
+    Committed,
+    /// Prove transaction for the L1 batch is confirmed on L1.
+    Proven,
+    /// Execute transaction for the L1 batch is confirmed on L1. Withdrawals from executed batches can be finalized.
+    Executed,
+}
+
+/// Notification about an L1 batch reaching a new lifecycle stage.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1BatchStatusUpdate {
+    pub l1_batch_number: L1BatchNumber,
+    pub stage: L1BatchStage,
+    /// Hash of the L1 transaction that moved the batch to `stage`. `None` for the `sealed` stage.
+    pub l1_tx_hash: Option<H256>,
+}
+
+impl L1BatchStatusUpdate {
+    /// Returns the latest lifecycle stage reached by the L1 batch with the specified details.
+    pub fn latest(details: &L1BatchDetails) -> Self {
+        let (stage, l1_tx_hash) = if let Some(hash) = details.base.execute_tx_hash {
+            (L1BatchStage::Executed, Some(hash))
+        } else if let Some(hash) = details.base.prove_tx_hash {
+            (L1BatchStage::Proven, Some(hash))
+        } else if let Some(hash) = details.base.commit_tx_hash {
+            (L1BatchStage::Committed, Some(hash))
+        } else {
+            (L1BatchStage::Sealed, None)
+        };
+        Self {
+            l1_batch_number: details.number,
+            stage,
+            l1_tx_hash,
+        }
+    }
+}
+
+/// Notification about the L1 batch of a transaction reaching a new lifecycle stage.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionFinalityUpdate {
+    pub transaction_hash: H256,
+    #[serde(flatten)]
+    pub l1_batch: L1BatchStatusUpdate,
+}
+
+/// Response of `eth_feeHistory`. In addition to standard fields, it contains blob-related fields introduced in EIP-4844.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeHistory {
+    #[serde(flatten)]
+    pub inner: zksync_basic_types::web3::types::FeeHistory,
+    /// Base fee per blob gas for each block and the next one. Derived from the pubdata price of each block,
+    /// since the price of publishing a single pubdata byte corresponds to a single unit of blob gas.
+    #[serde(default)]
+    pub base_fee_per_blob_gas: Vec<U256>,
+    /// Blob gas used ratio for each block. Since L2 transactions cannot carry blobs, it is always 0.
+    #[serde(default)]
+    pub blob_gas_used_ratio: Vec<f64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StorageProof {
@@ -733,3 +864,121 @@ pub struct Proof {
     pub address: Address,
     pub storage_proof: Vec<StorageProof>,
 }
+
+/// Storage slot proof returned by `eth_getProof` (see [EIP-1186]).
+///
+/// [EIP-1186]: https://eips.ethereum.org/EIPS/eip-1186
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Eip1186StorageProof {
+    pub key: H256,
+    pub value: U256,
+    /// Merkle path for the slot in the zkSync Merkle tree. Unlike Ethereum, this is not a list of RLP-encoded
+    /// trie nodes; each element is a 32-byte sibling hash, ordered from the leaf level to the root level
+    /// (same as in `zks_getProof`). Hashes for empty subtrees at the beginning of the path are omitted.
+    pub proof: Vec<Bytes>,
+    /// Enumeration index of the tree leaf for the slot. 0 if the slot was never written to.
+    pub index: u64,
+}
+
+/// Account proof returned by `eth_getProof` (see [EIP-1186]).
+///
+/// zkSync has no per-account tries; all storage slots (including those holding account balances, nonces
+/// and bytecode hashes in system contracts) are stored in a single Merkle tree, which is updated once per L1 batch.
+///
+/// [EIP-1186]: https://eips.ethereum.org/EIPS/eip-1186
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Eip1186Proof {
+    pub address: Address,
+    /// Proofs for the system contract slots holding the account nonce, ETH balance and bytecode hash
+    /// (exactly in this order). Each element is encoded as the big-endian 8-byte leaf enumeration index
+    /// followed by the Merkle path of the slot in the format described in [`Eip1186StorageProof::proof`].
+    pub account_proof: Vec<Bytes>,
+    pub balance: U256,
+    /// Hash of the account bytecode in the zkSync format (i.e., *not* a `keccak256` digest of the bytecode).
+    /// Zero for accounts without a deployed contract.
+    pub code_hash: H256,
+    pub nonce: U256,
+    /// Root hash of the zkSync Merkle tree after the L1 batch. Shared by all accounts.
+    pub storage_hash: H256,
+    pub storage_proof: Vec<Eip1186StorageProof>,
+    /// L1 batch for which the proofs are provided, i.e., the batch containing the requested block.
+    pub l1_batch_number: L1BatchNumber,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::build_bloom;
+
+    fn logs_filter(addresses: Vec<Address>, topics: Vec<(u32, Vec<H256>)>) -> GetLogsFilter {
+        GetLogsFilter {
+            from_block: MiniblockNumber(0),
+            to_block: MiniblockNumber(1_000),
+            addresses,
+            topics,
+        }
+    }
+
+    fn test_bloom() -> H2048 {
+        let bloom = build_bloom([
+            Address::repeat_byte(1).as_bytes(),
+            H256::repeat_byte(2).as_bytes(),
+        ]);
+        // Sanity check: the bloom must not contain items used as misses in tests.
+        assert!(!bloom_contains(&bloom, Address::repeat_byte(3).as_bytes()));
+        assert!(!bloom_contains(&bloom, H256::repeat_byte(4).as_bytes()));
+        bloom
+    }
+
+    #[test]
+    fn empty_logs_filter_matches_any_bloom() {
+        let filter = logs_filter(vec![], vec![]);
+        assert!(filter.may_match_bloom(&test_bloom()));
+        assert!(filter.may_match_bloom(&H2048::zero()));
+
+        // Topic positions without allowed values don't restrict matching.
+        let filter = logs_filter(vec![], vec![(1, vec![]), (3, vec![])]);
+        assert!(filter.may_match_bloom(&H2048::zero()));
+    }
+
+    #[test]
+    fn matching_bloom_by_address() {
+        let bloom = test_bloom();
+        let (address, other_address) = (Address::repeat_byte(1), Address::repeat_byte(3));
+
+        assert!(logs_filter(vec![address], vec![]).may_match_bloom(&bloom));
+        assert!(logs_filter(vec![other_address, address], vec![]).may_match_bloom(&bloom));
+        assert!(!logs_filter(vec![other_address], vec![]).may_match_bloom(&bloom));
+        assert!(!logs_filter(vec![address], vec![]).may_match_bloom(&H2048::zero()));
+    }
+
+    #[test]
+    fn matching_bloom_by_topics() {
+        let bloom = test_bloom();
+        let (topic, other_topic) = (H256::repeat_byte(2), H256::repeat_byte(4));
+
+        assert!(logs_filter(vec![], vec![(1, vec![topic])]).may_match_bloom(&bloom));
+        assert!(logs_filter(vec![], vec![(2, vec![other_topic, topic])]).may_match_bloom(&bloom));
+        assert!(!logs_filter(vec![], vec![(1, vec![other_topic])]).may_match_bloom(&bloom));
+        // All topic positions must match.
+        let filter = logs_filter(vec![], vec![(1, vec![topic]), (2, vec![other_topic])]);
+        assert!(!filter.may_match_bloom(&bloom));
+        let filter = logs_filter(vec![], vec![(1, vec![topic]), (2, vec![])]);
+        assert!(filter.may_match_bloom(&bloom));
+    }
+
+    #[test]
+    fn matching_bloom_by_address_and_topics() {
+        let bloom = test_bloom();
+        let (address, other_address) = (Address::repeat_byte(1), Address::repeat_byte(3));
+        let (topic, other_topic) = (H256::repeat_byte(2), H256::repeat_byte(4));
+
+        assert!(logs_filter(vec![address], vec![(1, vec![topic])]).may_match_bloom(&bloom));
+        assert!(!logs_filter(vec![address], vec![(1, vec![other_topic])]).may_match_bloom(&bloom));
+        assert!(!logs_filter(vec![other_address], vec![(1, vec![topic])]).may_match_bloom(&bloom));
+        let filter = logs_filter(vec![other_address], vec![(1, vec![other_topic])]);
+        assert!(!filter.may_match_bloom(&bloom));
+    }
+}
@@ -76,6 +76,8 @@ pub struct MiniblockHeader {
     pub protocol_version: Option<ProtocolVersionId>,
     /// The maximal number of virtual blocks to be created in the miniblock.
     pub virtual_blocks: u32,
+    /// Bloom filter for the event logs in the miniblock (see [`build_bloom()`]).
+    pub logs_bloom: H2048,
 }
 
 /// Data needed to execute a miniblock in the VM.
@@ -131,6 +133,36 @@ impl L1BatchHeader {
     }
 }
 
+/// Builds a logs bloom from the provided items in the same way as Ethereum does. Items are emitter addresses
+/// and topics of the event logs.
+pub fn build_bloom<'a>(items: impl IntoIterator<Item = &'a [u8]>) -> H2048 {
+    let mut bloom = H2048::zero();
+    for item in items {
+        for (byte_idx, mask) in bloom_bits(item) {
+            bloom.0[byte_idx] |= mask;
+        }
+    }
+    bloom
+}
+
+/// Checks whether the logs bloom may contain the specified item. False positives are possible,
+/// but false negatives are not.
+pub fn bloom_contains(bloom: &H2048, item: &[u8]) -> bool {
+    bloom_bits(item).all(|(byte_idx, mask)| bloom.0[byte_idx] & mask != 0)
+}
+
+/// Returns positions of the 3 bloom bits set for the item. Each position is determined by the 11 lower bits
+/// of a pair of bytes in the `keccak256` digest of the item.
+fn bloom_bits(item: &[u8]) -> impl Iterator<Item = (usize, u8)> {
+    const BLOOM_BYTES: usize = 256;
+
+    let hash = keccak256(item);
+    (0..3).map(move |i| {
+        let bit_idx = (usize::from(hash[2 * i]) << 8 | usize::from(hash[2 * i + 1])) & 2_047;
+        (BLOOM_BYTES - 1 - bit_idx / 8, 1_u8 << (bit_idx % 8))
+    })
+}
+
 #[derive(Clone, Copy, Eq, PartialEq, Default)]
 pub struct BlockGasCount {
     pub commit: u32,
@@ -260,6 +292,23 @@ pub struct L1BatchTreeData {
 mod tests {
     use super::*;
 
+    #[test]
+    fn building_logs_bloom() {
+        let address = Address::repeat_byte(1);
+        let topic = H256::repeat_byte(2);
+        let bloom = build_bloom([address.as_bytes(), topic.as_bytes()]);
+
+        let set_bits: u32 = bloom.0.iter().map(|byte| byte.count_ones()).sum();
+        assert!((1..=6).contains(&set_bits), "{set_bits}");
+        assert!(bloom_contains(&bloom, address.as_bytes()));
+        assert!(bloom_contains(&bloom, topic.as_bytes()));
+        assert!(!bloom_contains(&H2048::zero(), address.as_bytes()));
+
+        let address_bloom = build_bloom([address.as_bytes()]);
+        assert!(bloom_contains(&address_bloom, address.as_bytes()));
+        assert_eq!(address_bloom | bloom, bloom);
+    }
+
     #[test]
     fn test_legacy_miniblock_hashes() {
         // The comparing with the hash taken from explorer
@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, iter};
 
 use itertools::Itertools;
 use once_cell::sync::Lazy;
@@ -39,6 +39,11 @@ impl VmEvent {
                 topic: (idx as u32, topic),
             })
     }
+
+    /// Returns items to be added to a logs bloom for this event: the emitter address and indexed topics.
+    pub fn bloom_items(&self) -> impl Iterator<Item = &[u8]> + '_ {
+        iter::once(self.address.as_bytes()).chain(self.indexed_topics.iter().map(H256::as_bytes))
+    }
 }
 
 pub static DEPLOY_EVENT_SIGNATURE: Lazy<H256> = Lazy::new(|| {
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use zksync_config::configs::chain::{FeeModelVersion, StateKeeperConfig};
+use zksync_config::configs::chain::{self, FeeModelVersion, StateKeeperConfig};
 use zksync_system_constants::L1_GAS_PER_PUBDATA_BYTE;
 
 use crate::ProtocolVersionId;
@@ -198,6 +198,65 @@ pub struct FeeModelConfigV2 {
     pub max_gas_per_batch: u64,
     /// The maximum amount of pubdata that can be used by the batch. Note that if the calldata is used as pubdata, this variable should not exceed 128kb.
     pub max_pubdata_per_batch: u64,
+    /// Params of the L2 congestion component of the fair L2 gas price. If not set, the L2 gas price doesn't depend on the L2 demand.
+    #[serde(default)]
+    pub l2_congestion: Option<L2CongestionConfig>,
+}
+
+/// Config params for the L2 congestion component of the fee model. The fair L2 gas price is multiplied by a congestion multiplier,
+/// which is updated after each L1 batch similarly to the EIP-1559 base fee: it grows if the batch utilization (by gas or transaction slots)
+/// is above the target, and decreases if it is below the target.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct L2CongestionConfig {
+    /// Target utilization of an L1 batch. It has range from 0 (exclusive) to 1.
+    pub target_utilization: f64,
+    /// Bounds the change of the multiplier per L1 batch: the multiplier can change by at most `1 / max_change_denominator` of its value.
+    pub max_change_denominator: u64,
+    /// The maximum value of the multiplier. The minimum value is always 1, i.e. the congestion component never lowers the L2 gas price
+    /// below the one computed from the operator costs.
+    pub max_multiplier: f64,
+}
+
+impl L2CongestionConfig {
+    pub const DEFAULT_MAX_CHANGE_DENOMINATOR: u64 = 8;
+    pub const DEFAULT_MAX_MULTIPLIER: f64 = 10.0;
+
+    /// Resolves defaults for the L2 congestion params and checks that they are valid.
+    pub fn from_config(config: &chain::L2CongestionConfig) -> anyhow::Result<Self> {
+        let this = Self {
+            target_utilization: config.target_utilization,
+            max_change_denominator: config
+                .max_change_denominator
+                .unwrap_or(Self::DEFAULT_MAX_CHANGE_DENOMINATOR),
+            max_multiplier: config
+                .max_multiplier
+                .unwrap_or(Self::DEFAULT_MAX_MULTIPLIER),
+        };
+        anyhow::ensure!(
+            this.target_utilization > 0.0 && this.target_utilization <= 1.0,
+            "L2 congestion target utilization must be in (0, 1], got {}",
+            this.target_utilization
+        );
+        anyhow::ensure!(
+            this.max_change_denominator > 0,
+            "L2 congestion max change denominator must be positive"
+        );
+        anyhow::ensure!(
+            this.max_multiplier >= 1.0,
+            "L2 congestion max multiplier must be at least 1, got {}",
+            this.max_multiplier
+        );
+        Ok(this)
+    }
+
+    /// Returns the congestion multiplier after an L1 batch with the specified utilization (from 0 to 1) is sealed.
+    pub fn next_multiplier(&self, multiplier: f64, batch_utilization: f64) -> f64 {
+        let batch_utilization = batch_utilization.clamp(0.0, 1.0);
+        let relative_change = (batch_utilization - self.target_utilization)
+            / self.target_utilization
+            / self.max_change_denominator as f64;
+        (multiplier * (1.0 + relative_change)).clamp(1.0, self.max_multiplier)
+    }
 }
 
 impl Default for FeeModelConfig {
@@ -223,6 +282,8 @@ impl FeeModelConfig {
                 batch_overhead_l1_gas: state_keeper_config.batch_overhead_l1_gas,
                 max_gas_per_batch: state_keeper_config.max_gas_per_batch,
                 max_pubdata_per_batch: state_keeper_config.max_pubdata_per_batch,
+                // Set by the fee input provider if the L2 congestion monitor is enabled.
+                l2_congestion: None,
             }),
         }
     }
@@ -239,6 +300,16 @@ pub struct FeeParamsV2 {
     pub config: FeeModelConfigV2,
     pub l1_gas_price: u64,
     pub l1_pubdata_price: u64,
+    /// Current value of the L2 congestion multiplier applied to the fair L2 gas price. Equals 1 if the congestion
+    /// component is disabled.
+    #[serde(default = "FeeParamsV2::default_l2_congestion_multiplier")]
+    pub l2_congestion_multiplier: f64,
+}
+
+impl FeeParamsV2 {
+    fn default_l2_congestion_multiplier() -> f64 {
+        1.0
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
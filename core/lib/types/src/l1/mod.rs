@@ -79,7 +79,8 @@ pub struct L1TxCommonData {
     pub sender: Address,
     /// Unique ID of the priority operation.
     pub serial_id: PriorityOpId,
-    /// Ethereum deadline block until which operation must be processed.
+    /// Deadline until which the operation must be processed. Despite the name, L1 contracts emit it as
+    /// the expiration timestamp (in seconds since the Unix epoch).
     pub deadline_block: u64,
     /// Additional payment to the operator as an incentive to perform the operation. The contract uses a value of 192 bits.
     pub layer_2_tip_fee: U256,
@@ -221,7 +222,7 @@ impl TryFrom<Log> for L1Tx {
             &[
                 ParamType::Uint(256),                         // tx ID
                 ParamType::FixedBytes(32),                    // tx hash
-                ParamType::Uint(64),                          // expiration block
+                ParamType::Uint(64),                          // expiration timestamp
                 transaction_param_type,                       // transaction data
                 ParamType::Array(Box::new(ParamType::Bytes)), // factory deps
             ],
@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
 use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
 use zksync_basic_types::{AccountTreeId, Address, U256};
 use zksync_contracts::{read_sys_contract_bytecode, ContractLanguage, SystemContractsRepo};
 use zksync_system_constants::{
@@ -172,3 +173,11 @@ pub fn get_system_smart_contracts_from_dir(path: PathBuf) -> Vec<DeployedContrac
         })
         .collect::<Vec<_>>()
 }
+
+/// Raw bytecodes of the base system contracts (the bootloader and the default account).
+/// Used to store custom base system contracts in an object store.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BaseSystemContractsBytecodes {
+    pub bootloader: Vec<u8>,
+    pub default_aa: Vec<u8>,
+}
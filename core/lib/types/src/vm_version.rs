@@ -1,3 +1,5 @@
+use zksync_config::configs::chain::ShadowVmVersion;
+
 #[derive(Debug, Clone, Copy)]
 pub enum VmVersion {
     M5WithoutRefunds,
@@ -18,3 +20,13 @@ impl VmVersion {
         Self::Vm1_4_2
     }
 }
+
+impl From<ShadowVmVersion> for VmVersion {
+    fn from(version: ShadowVmVersion) -> Self {
+        match version {
+            ShadowVmVersion::VmBoojumIntegration => Self::VmBoojumIntegration,
+            ShadowVmVersion::Vm1_4_1 => Self::Vm1_4_1,
+            ShadowVmVersion::Vm1_4_2 => Self::Vm1_4_2,
+        }
+    }
+}
@@ -39,11 +39,15 @@ pub enum Web3Error {
     LogsLimitExceeded(usize, u32, u32),
     #[error("invalid filter: if blockHash is supplied fromBlock and toBlock must not be")]
     InvalidFilterBlockHash,
+    #[error("invalid reward percentiles {0:?}: percentiles must be in the [0, 100] range and sorted in ascending order")]
+    InvalidRewardPercentiles(Vec<f32>),
     #[error("Not implemented")]
     NotImplemented,
 
     #[error("Tree API is not available")]
     TreeApiUnavailable,
+    #[error("Merkle tree data for block #{0} is not available yet; repeat request later")]
+    NoTreeData(MiniblockNumber),
     #[error("Internal error")]
     InternalError(#[from] anyhow::Error),
 }
@@ -3,14 +3,17 @@ use jsonrpsee::{
     proc_macros::rpc,
 };
 use zksync_types::{
-    api::{BlockId, BlockIdVariant, BlockNumber, Transaction, TransactionVariant},
+    api::{
+        BlockId, BlockIdVariant, BlockNumber, Eip1186Proof, FeeHistory, Transaction,
+        TransactionVariant,
+    },
     transaction_request::CallRequest,
     Address, H256,
 };
 
 use crate::types::{
-    Block, Bytes, FeeHistory, Filter, FilterChanges, Index, Log, PubSubFilter, SyncState,
-    TransactionReceipt, U256, U64,
+    Block, Bytes, Filter, FilterChanges, Index, Log, PubSubFilter, SyncState, TransactionReceipt,
+    U256, U64,
 };
 
 #[cfg_attr(
@@ -174,6 +177,14 @@ pub trait EthNamespace {
         newest_block: BlockNumber,
         reward_percentiles: Vec<f32>,
     ) -> RpcResult<FeeHistory>;
+
+    #[method(name = "getProof")]
+    async fn get_proof(
+        &self,
+        address: Address,
+        keys: Vec<H256>,
+        block: BlockIdVariant,
+    ) -> RpcResult<Eip1186Proof>;
 }
 
 #[rpc(server, namespace = "eth")]
@@ -17,5 +17,5 @@ pub use self::{
 pub use self::{
     debug::DebugNamespaceServer, en::EnNamespaceServer, eth::EthNamespaceServer,
     eth::EthPubSubServer, net::NetNamespaceServer, snapshots::SnapshotsNamespaceClient,
-    web3::Web3NamespaceServer, zks::ZksNamespaceServer,
+    web3::Web3NamespaceServer, zks::ZksNamespaceServer, zks::ZksPubSubServer,
 };
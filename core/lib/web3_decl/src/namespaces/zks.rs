@@ -1,10 +1,13 @@
 use std::collections::HashMap;
 
-use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use jsonrpsee::{
+    core::{RpcResult, SubscriptionResult},
+    proc_macros::rpc,
+};
 use zksync_types::{
     api::{
-        BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion,
-        TransactionDetails,
+        BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, PriorityOpIdentifier,
+        PriorityOpStatus, Proof, ProtocolVersion, TransactionDetails,
     },
     fee::Fee,
     fee_model::FeeParams,
@@ -12,7 +15,7 @@ use zksync_types::{
     Address, L1BatchNumber, MiniblockNumber, H256, U256, U64,
 };
 
-use crate::types::Token;
+use crate::types::{PubSubResult, Token, ZksPubSubFilter};
 
 #[cfg_attr(
     all(feature = "client", feature = "server"),
@@ -86,6 +89,14 @@ pub trait ZksNamespace {
     #[method(name = "getTransactionDetails")]
     async fn get_transaction_details(&self, hash: H256) -> RpcResult<Option<TransactionDetails>>;
 
+    /// Returns the status of a priority operation specified by its serial ID or the hash of the L1 transaction
+    /// that created it.
+    #[method(name = "getPriorityOpStatus")]
+    async fn get_priority_op_status(
+        &self,
+        id: PriorityOpIdentifier,
+    ) -> RpcResult<Option<PriorityOpStatus>>;
+
     #[method(name = "getRawBlockTransactions")]
     async fn get_raw_block_transactions(
         &self,
@@ -119,3 +130,18 @@ pub trait ZksNamespace {
         l1_batch_number: L1BatchNumber,
     ) -> RpcResult<Option<Proof>>;
 }
+
+/// zkSync-specific subscriptions. Supported subscription types are:
+///
+/// - `l1Batches`: notifies about L1 batches reaching new lifecycle stages (sealed, committed, proven, executed).
+/// - `transactionFinality`: notifies about the L1 batch of the transaction specified in the filter reaching
+///   new lifecycle stages. The subscription ends after the batch is executed.
+#[rpc(server, namespace = "zks")]
+pub trait ZksPubSub {
+    #[subscription(name = "subscribe" => "subscription", unsubscribe = "unsubscribe", item = PubSubResult)]
+    async fn subscribe(
+        &self,
+        sub_type: String,
+        filter: Option<ZksPubSubFilter>,
+    ) -> SubscriptionResult;
+}
@@ -14,7 +14,10 @@ use core::{
 use rlp::Rlp;
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 pub use zksync_types::{
-    api::{Block, BlockNumber, Log, TransactionReceipt, TransactionRequest},
+    api::{
+        Block, BlockNumber, L1BatchStage, L1BatchStatusUpdate, Log, TransactionFinalityUpdate,
+        TransactionReceipt, TransactionRequest,
+    },
     vm_trace::{ContractSourceDebugInfo, VmDebugTrace, VmExecutionStep},
     web3::{
         ethabi,
@@ -346,11 +349,26 @@ pub enum PubSubResult {
     Log(Log),
     TxHash(H256),
     Syncing(bool),
+    // Must be placed before `L1BatchStatus` so that untagged deserialization picks the most specific variant.
+    TransactionFinality(TransactionFinalityUpdate),
+    L1BatchStatus(L1BatchStatusUpdate),
+}
+
+/// Filter for `zks_subscribe` subscriptions.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZksPubSubFilter {
+    /// Transaction hash. Required for `transactionFinality` subscriptions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_hash: Option<H256>,
 }
 
 #[cfg(test)]
 mod tests {
-    use zksync_types::api::{BlockId, BlockIdVariant};
+    use zksync_types::{
+        api::{BlockId, BlockIdVariant},
+        L1BatchNumber,
+    };
 
     use super::*;
 
@@ -442,4 +460,42 @@ mod tests {
         let restored_value: ValueOrArray<Address> = serde_json::from_value(json).unwrap();
         assert_eq!(restored_value, value);
     }
+
+    #[test]
+    fn serializing_l1_batch_pub_sub_results() {
+        let update = L1BatchStatusUpdate {
+            l1_batch_number: L1BatchNumber(42),
+            stage: L1BatchStage::Committed,
+            l1_tx_hash: Some(H256::repeat_byte(0x11)),
+        };
+        let json = serde_json::to_value(PubSubResult::L1BatchStatus(update.clone())).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "l1BatchNumber": 42,
+                "stage": "committed",
+                "l1TxHash": format!("{:?}", H256::repeat_byte(0x11)),
+            })
+        );
+        let restored: PubSubResult = serde_json::from_value(json).unwrap();
+        match restored {
+            PubSubResult::L1BatchStatus(restored) => assert_eq!(restored, update),
+            other => panic!("Unexpected deserialized result: {other:?}"),
+        }
+
+        let update = TransactionFinalityUpdate {
+            transaction_hash: H256::repeat_byte(0x22),
+            l1_batch: L1BatchStatusUpdate {
+                l1_batch_number: L1BatchNumber(42),
+                stage: L1BatchStage::Sealed,
+                l1_tx_hash: None,
+            },
+        };
+        let json = serde_json::to_value(PubSubResult::TransactionFinality(update.clone())).unwrap();
+        let restored: PubSubResult = serde_json::from_value(json).unwrap();
+        match restored {
+            PubSubResult::TransactionFinality(restored) => assert_eq!(restored, update),
+            other => panic!("Unexpected deserialized result: {other:?}"),
+        }
+    }
 }
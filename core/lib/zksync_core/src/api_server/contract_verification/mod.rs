@@ -1,4 +1,7 @@
-use std::{net::SocketAddr, time::Duration};
+use std::{
+    net::{SocketAddr, TcpListener},
+    time::Duration,
+};
 
 use actix_cors::Cors;
 use actix_web::{dev::Server, web, App, HttpResponse, HttpServer};
@@ -13,7 +16,7 @@ mod api_decl;
 mod api_impl;
 mod metrics;
 
-fn start_server(api: RestApi, bind_to: SocketAddr) -> Server {
+fn start_server(api: RestApi, listener: TcpListener) -> Server {
     HttpServer::new(move || {
         let api = api.clone();
         App::new()
@@ -32,7 +35,7 @@ fn start_server(api: RestApi, bind_to: SocketAddr) -> Server {
                 web::get().to(|| async { HttpResponse::Ok().finish() }),
             )
     })
-    .bind(bind_to)
+    .listen(listener)
     .unwrap()
     .shutdown_timeout(60)
     .keep_alive(Duration::from_secs(10))
@@ -41,11 +44,59 @@ fn start_server(api: RestApi, bind_to: SocketAddr) -> Server {
     .run()
 }
 
+/// Address the contract verification API server listens on.
+#[derive(Debug)]
+enum ServerBinding {
+    Address(SocketAddr),
+    Listener(TcpListener),
+}
+
+impl ServerBinding {
+    fn into_listener(self) -> TcpListener {
+        match self {
+            Self::Address(addr) => TcpListener::bind(addr).unwrap_or_else(|err| {
+                panic!("Failed binding contract verification API to {addr}: {err}")
+            }),
+            Self::Listener(listener) => listener,
+        }
+    }
+}
+
 /// Start HTTP REST API
 pub fn start_server_thread_detached(
     master_connection_pool: ConnectionPool,
     replica_connection_pool: ConnectionPool,
     api_config: ContractVerificationApiConfig,
+    stop_receiver: watch::Receiver<bool>,
+) -> JoinHandle<anyhow::Result<()>> {
+    spawn_server_thread(
+        master_connection_pool,
+        replica_connection_pool,
+        ServerBinding::Address(api_config.bind_addr()),
+        stop_receiver,
+    )
+}
+
+/// Same as [`start_server_thread_detached()`], but serves the API on an already bound TCP listener.
+/// This allows binding to an ephemeral port and learning the server address before the server is started.
+pub fn start_server_thread_detached_with_listener(
+    master_connection_pool: ConnectionPool,
+    replica_connection_pool: ConnectionPool,
+    listener: TcpListener,
+    stop_receiver: watch::Receiver<bool>,
+) -> JoinHandle<anyhow::Result<()>> {
+    spawn_server_thread(
+        master_connection_pool,
+        replica_connection_pool,
+        ServerBinding::Listener(listener),
+        stop_receiver,
+    )
+}
+
+fn spawn_server_thread(
+    master_connection_pool: ConnectionPool,
+    replica_connection_pool: ConnectionPool,
+    binding: ServerBinding,
     mut stop_receiver: watch::Receiver<bool>,
 ) -> JoinHandle<anyhow::Result<()>> {
     let (handler, panic_sender) = spawn_panic_handler();
@@ -56,10 +107,10 @@ pub fn start_server_thread_detached(
             let _panic_sentinel = ThreadPanicNotify(panic_sender.clone());
 
             actix_rt::System::new().block_on(async move {
-                let bind_address = api_config.bind_addr();
+                let listener = binding.into_listener();
                 let api = RestApi::new(master_connection_pool, replica_connection_pool);
 
-                let server = start_server(api, bind_address);
+                let server = start_server(api, listener);
                 let close_handle = server.handle();
                 actix_rt::spawn(async move {
                     if stop_receiver.changed().await.is_ok() {
@@ -351,6 +351,27 @@ impl AsyncTreeReader {
     }
 }
 
+impl LazyAsyncTreeReader {
+    /// Waits until the tree is initialized and runs the HTTP API server for it. If the stop signal is received
+    /// before the tree is initialized, returns immediately.
+    pub async fn run_api_server(
+        self,
+        bind_address: SocketAddr,
+        mut stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        let tree_reader = tokio::select! {
+            tree_reader = self.wait() => tree_reader,
+            _ = stop_receiver.changed() => {
+                tracing::info!("Stop signal received before Merkle tree is initialized; not starting tree API server");
+                return Ok(());
+            }
+        };
+        tree_reader
+            .run_api_server(bind_address, stop_receiver)
+            .await
+    }
+}
+
 /// `axum`-powered REST server for Merkle tree API.
 #[must_use = "Server must be `run()`"]
 struct MerkleTreeServer {
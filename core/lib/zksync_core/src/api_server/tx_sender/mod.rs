@@ -38,6 +38,7 @@ use crate::{
     },
     fee_model::BatchFeeModelInputProvider,
     state_keeper::seal_criteria::{ConditionalSealer, NoopSealer, SealData},
+    tx_admission::{AllowAllPolicy, TxAdmissionPolicy},
     utils::pending_protocol_version,
 };
 
@@ -150,6 +151,8 @@ pub struct TxSenderBuilder {
     tx_sink: Arc<dyn TxSink>,
     /// Batch sealer used to check whether transaction can be executed by the sequencer.
     sealer: Option<Arc<dyn ConditionalSealer>>,
+    /// Policy used to check whether transaction is allowed to be included into the chain.
+    admission_policy: Option<Arc<dyn TxAdmissionPolicy>>,
 }
 
 impl TxSenderBuilder {
@@ -163,6 +166,7 @@ impl TxSenderBuilder {
             replica_connection_pool,
             tx_sink,
             sealer: None,
+            admission_policy: None,
         }
     }
 
@@ -171,6 +175,11 @@ impl TxSenderBuilder {
         self
     }
 
+    pub fn with_admission_policy(mut self, admission_policy: Arc<dyn TxAdmissionPolicy>) -> Self {
+        self.admission_policy = Some(admission_policy);
+        self
+    }
+
     pub async fn build(
         self,
         batch_fee_input_provider: Arc<dyn BatchFeeModelInputProvider>,
@@ -180,6 +189,10 @@ impl TxSenderBuilder {
     ) -> TxSender {
         // Use noop sealer if no sealer was explicitly provided.
         let sealer = self.sealer.unwrap_or_else(|| Arc::new(NoopSealer));
+        // Admit all transactions if no admission policy was explicitly provided.
+        let admission_policy = self
+            .admission_policy
+            .unwrap_or_else(|| Arc::new(AllowAllPolicy));
 
         TxSender(Arc::new(TxSenderInner {
             sender_config: self.config,
@@ -190,6 +203,7 @@ impl TxSenderBuilder {
             vm_concurrency_limiter,
             storage_caches,
             sealer,
+            admission_policy,
             executor: TransactionExecutor::Real,
         }))
     }
@@ -248,6 +262,8 @@ pub struct TxSenderInner {
     storage_caches: PostgresStorageCaches,
     /// Batch sealer used to check whether transaction can be executed by the sequencer.
     sealer: Arc<dyn ConditionalSealer>,
+    /// Policy used to check whether transaction is allowed to be included into the chain.
+    admission_policy: Arc<dyn TxAdmissionPolicy>,
     pub(super) executor: TransactionExecutor,
 }
 
@@ -391,6 +407,8 @@ impl TxSender {
     }
 
     async fn validate_tx(&self, tx: &L2Tx) -> Result<(), SubmitTxError> {
+        self.0.admission_policy.check(tx.into())?;
+
         let max_gas = U256::from(u32::MAX);
         if tx.common_data.fee.gas_limit > max_gas
             || tx.common_data.fee.gas_per_pubdata_limit > max_gas
@@ -3,7 +3,10 @@ use thiserror::Error;
 use zksync_types::{l2::error::TxCheckError, U256};
 use zksync_web3_decl::error::EnrichedClientError;
 
-use crate::api_server::execution_sandbox::{SandboxExecutionError, ValidationError};
+use crate::{
+    api_server::execution_sandbox::{SandboxExecutionError, ValidationError},
+    tx_admission::TxAdmissionDenial,
+};
 
 /// Errors that con occur submitting a transaction or estimating gas for its execution.
 #[derive(Debug, Error)]
@@ -70,6 +73,9 @@ pub enum SubmitTxError {
     ProxyError(#[from] EnrichedClientError),
     #[error("not enough gas to publish compressed bytecodes")]
     FailedToPublishCompressedBytecodes,
+    /// Transaction is denied by the configured transaction admission policy.
+    #[error("transaction is not allowed: {0}")]
+    NotAdmitted(#[from] TxAdmissionDenial),
     /// Catch-all internal error (e.g., database error) that should not be exposed to the caller.
     #[error("internal error")]
     Internal(#[from] anyhow::Error),
@@ -104,6 +110,7 @@ impl SubmitTxError {
             Self::IntrinsicGas => "intrinsic-gas",
             Self::ProxyError(_) => "proxy-error",
             Self::FailedToPublishCompressedBytecodes => "failed-to-publish-compressed-bytecodes",
+            Self::NotAdmitted(_) => "not-admitted",
             Self::Internal(_) => "internal",
         }
     }
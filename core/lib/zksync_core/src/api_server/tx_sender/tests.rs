@@ -1,12 +1,19 @@
 //! Tests for the transaction sender.
 
+use std::collections::HashSet;
+
+use assert_matches::assert_matches;
 use zksync_types::{get_nonce_key, L1BatchNumber, StorageLog};
 
 use super::*;
 use crate::{
     api_server::execution_sandbox::{testonly::MockTransactionExecutor, VmConcurrencyBarrier},
     genesis::{ensure_genesis_state, GenesisParams},
-    utils::testonly::{create_miniblock, prepare_recovery_snapshot, MockBatchFeeParamsProvider},
+    tx_admission::{TxAdmissionDenial, TxAdmissionRules},
+    utils::testonly::{
+        create_l2_transaction, create_miniblock, prepare_recovery_snapshot,
+        MockBatchFeeParamsProvider,
+    },
 };
 
 pub(crate) async fn create_test_tx_sender(
@@ -28,6 +35,7 @@ pub(crate) async fn create_test_tx_sender(
         pool,
         batch_fee_model_input_provider,
         storage_caches,
+        Arc::new(AllowAllPolicy),
     )
     .await;
 
@@ -139,3 +147,37 @@ async fn getting_nonce_for_account_after_snapshot_recovery() {
     let nonce = tx_sender.get_expected_nonce(missing_address).await.unwrap();
     assert_eq!(nonce, Nonce(0));
 }
+
+#[tokio::test]
+async fn submitting_tx_denied_by_admission_policy() {
+    let pool = ConnectionPool::test_pool().await;
+    let tx_sender_config = TxSenderConfig::new(
+        &StateKeeperConfig::for_tests(),
+        &Web3JsonRpcConfig::for_tests(),
+        L2ChainId::default(),
+    );
+
+    let tx = create_l2_transaction(10, 100);
+    let sender = tx.initiator_account();
+    let rules = TxAdmissionRules {
+        denied_senders: HashSet::from([sender]),
+        ..TxAdmissionRules::default()
+    };
+    let (vm_concurrency_limiter, _vm_barrier) = VmConcurrencyLimiter::new(1);
+    let tx_sink = master_pool_sink::MasterPoolSink::new(pool.clone());
+    let tx_sender = TxSenderBuilder::new(tx_sender_config, pool, Arc::new(tx_sink))
+        .with_admission_policy(Arc::new(rules))
+        .build(
+            Arc::new(MockBatchFeeParamsProvider::default()),
+            Arc::new(vm_concurrency_limiter),
+            ApiContracts::load_from_disk(),
+            PostgresStorageCaches::new(1, 1),
+        )
+        .await;
+
+    let err = tx_sender.submit_tx(tx).await.unwrap_err();
+    assert_matches!(
+        err,
+        SubmitTxError::NotAdmitted(TxAdmissionDenial::DeniedSender(address)) if address == sender
+    );
+}
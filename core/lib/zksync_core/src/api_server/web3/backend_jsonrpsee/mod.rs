@@ -38,11 +38,12 @@ impl MethodTracer {
             | Web3Error::TooManyTopics
             | Web3Error::FilterNotFound
             | Web3Error::InvalidFilterBlockHash
+            | Web3Error::InvalidRewardPercentiles(_)
             | Web3Error::LogsLimitExceeded(_, _, _) => ErrorCode::InvalidParams.code(),
             Web3Error::SubmitTransactionError(_, _)
             | Web3Error::SerializationError(_)
             | Web3Error::ProxyError(_) => 3,
-            Web3Error::TreeApiUnavailable => 6,
+            Web3Error::TreeApiUnavailable | Web3Error::NoTreeData(_) => 6,
         };
         let message = match err {
             // Do not expose internal error details to the client.
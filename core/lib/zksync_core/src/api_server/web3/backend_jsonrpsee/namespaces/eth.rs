@@ -1,10 +1,10 @@
 use zksync_types::{
     api::{
-        Block, BlockId, BlockIdVariant, BlockNumber, Log, Transaction, TransactionId,
-        TransactionReceipt, TransactionVariant,
+        Block, BlockId, BlockIdVariant, BlockNumber, Eip1186Proof, FeeHistory, Log, Transaction,
+        TransactionId, TransactionReceipt, TransactionVariant,
     },
     transaction_request::CallRequest,
-    web3::types::{FeeHistory, Index, SyncState},
+    web3::types::{Index, SyncState},
     Address, Bytes, H256, U256, U64,
 };
 use zksync_web3_decl::{
@@ -258,4 +258,15 @@ impl EthNamespaceServer for EthNamespace {
             .await
             .map_err(|err| self.current_method().map_err(err))
     }
+
+    async fn get_proof(
+        &self,
+        address: Address,
+        keys: Vec<H256>,
+        block: BlockIdVariant,
+    ) -> RpcResult<Eip1186Proof> {
+        self.get_proof_impl(address, keys, block.into())
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
 }
@@ -2,8 +2,8 @@ use std::collections::HashMap;
 
 use zksync_types::{
     api::{
-        BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion,
-        TransactionDetails,
+        BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, PriorityOpIdentifier,
+        PriorityOpStatus, Proof, ProtocolVersion, TransactionDetails,
     },
     fee::Fee,
     fee_model::FeeParams,
@@ -116,6 +116,15 @@ impl ZksNamespaceServer for ZksNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn get_priority_op_status(
+        &self,
+        id: PriorityOpIdentifier,
+    ) -> RpcResult<Option<PriorityOpStatus>> {
+        self.get_priority_op_status_impl(id)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn get_raw_block_transactions(
         &self,
         block_number: MiniblockNumber,
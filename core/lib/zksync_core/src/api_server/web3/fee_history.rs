@@ -0,0 +1,228 @@
+//! Per-miniblock data used by `eth_feeHistory`.
+
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+
+use lru::LruCache;
+use multivm::vm_latest::constants::BLOCK_GAS_LIMIT;
+use zksync_dal::{
+    blocks_web3_dal::{MiniblockFeeData, TransactionFeeData},
+    StorageProcessor,
+};
+use zksync_types::{MiniblockNumber, H256, U256};
+use zksync_web3_decl::error::Web3Error;
+
+/// Fee history data for a single miniblock.
+#[derive(Debug)]
+pub(super) struct BlockFeeHistory {
+    hash: H256,
+    pub base_fee_per_gas: U256,
+    pub base_fee_per_blob_gas: U256,
+    gas_used: U256,
+    /// Priority fees paid by the miniblock transactions together with the gas used by them,
+    /// sorted by the priority fee.
+    rewards: Vec<(U256, U256)>,
+}
+
+impl BlockFeeHistory {
+    fn new(data: MiniblockFeeData, transactions: &[TransactionFeeData]) -> Self {
+        let mut rewards: Vec<_> = transactions
+            .iter()
+            .map(|tx| {
+                let reward = tx.effective_gas_price.saturating_sub(data.base_fee_per_gas);
+                (reward, tx.gas_used)
+            })
+            .collect();
+        rewards.sort_unstable_by_key(|(reward, _)| *reward);
+
+        Self {
+            hash: data.hash,
+            base_fee_per_gas: data.base_fee_per_gas,
+            // The price of publishing a single pubdata byte corresponds to the price of a single unit of blob gas.
+            base_fee_per_blob_gas: data.fair_pubdata_price.into(),
+            gas_used: transactions
+                .iter()
+                .fold(U256::zero(), |acc, tx| acc + tx.gas_used),
+            rewards,
+        }
+    }
+
+    /// Returns the ratio of the gas used by the miniblock to the gas limit enforced by the VM. The gas limit reported
+    /// in API blocks (`u32::MAX`) is not used since it's nominal.
+    pub fn gas_used_ratio(&self) -> f64 {
+        // Gas used by a miniblock always fits into `u64` since it cannot exceed the sum of transaction gas limits.
+        self.gas_used.low_u64() as f64 / f64::from(BLOCK_GAS_LIMIT)
+    }
+
+    /// Returns priority fees at the specified percentiles, with transactions weighted by the gas used
+    /// (same as in the reference Ethereum implementation). Percentiles are assumed to be validated
+    /// using [`validate_reward_percentiles()`].
+    pub fn rewards(&self, percentiles: &[f32]) -> Vec<U256> {
+        if self.rewards.is_empty() {
+            return vec![U256::zero(); percentiles.len()];
+        }
+
+        let mut tx_index = 0;
+        let mut cumulative_gas_used = self.rewards[0].1;
+        percentiles
+            .iter()
+            .map(|&percentile| {
+                // Percentiles are multiplied by 100 in order to retain 2 decimal digits.
+                let scaled_percentile = (f64::from(percentile) * 100.0).round() as u64;
+                let threshold_gas_used = self.gas_used * scaled_percentile / 10_000;
+                while cumulative_gas_used < threshold_gas_used && tx_index < self.rewards.len() - 1
+                {
+                    tx_index += 1;
+                    cumulative_gas_used += self.rewards[tx_index].1;
+                }
+                self.rewards[tx_index].0
+            })
+            .collect()
+    }
+}
+
+/// Checks that reward percentiles are in the `[0, 100]` range and are sorted in the ascending order.
+pub(super) fn validate_reward_percentiles(percentiles: &[f32]) -> Result<(), Web3Error> {
+    let mut prev_percentile = 0.0;
+    for &percentile in percentiles {
+        if !(prev_percentile..=100.0).contains(&percentile) {
+            return Err(Web3Error::InvalidRewardPercentiles(percentiles.to_vec()));
+        }
+        prev_percentile = percentile;
+    }
+    Ok(())
+}
+
+/// LRU cache of miniblock fee history data. Since `eth_feeHistory` is usually requested for the latest miniblocks,
+/// this allows to avoid loading transactions for the same miniblocks on each request.
+///
+/// Cached entries are checked against miniblock hashes loaded from Postgres, so that the cache remains valid
+/// if miniblocks are rolled back.
+#[derive(Debug)]
+pub(super) struct FeeHistoryCache(Mutex<LruCache<MiniblockNumber, Arc<BlockFeeHistory>>>);
+
+impl FeeHistoryCache {
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self(Mutex::new(LruCache::new(capacity)))
+    }
+
+    /// Loads fee history for up to `block_count` miniblocks ending with `newest_block`.
+    /// Returned data is ordered by miniblock number in the ascending order.
+    pub async fn load(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        newest_block: MiniblockNumber,
+        block_count: u64,
+    ) -> anyhow::Result<Vec<Arc<BlockFeeHistory>>> {
+        let mut blocks_data = storage
+            .blocks_web3_dal()
+            .get_miniblocks_fee_data(newest_block, block_count)
+            .await?;
+        // DAL method returns data in DESC order while we need ASC.
+        blocks_data.reverse();
+
+        let cached_blocks: Vec<_> = {
+            let mut cache = self.0.lock().unwrap();
+            blocks_data
+                .iter()
+                .map(|data| {
+                    cache
+                        .get(&data.number)
+                        .filter(|cached| cached.hash == data.hash)
+                        .cloned()
+                })
+                .collect()
+        };
+
+        let missing_numbers = blocks_data
+            .iter()
+            .zip(&cached_blocks)
+            .filter_map(|(data, cached)| cached.is_none().then_some(data.number));
+        let missing_range = missing_numbers
+            .clone()
+            .min()
+            .zip(missing_numbers.max())
+            .map(|(start, end)| start..=end);
+        let Some(missing_range) = missing_range else {
+            return Ok(cached_blocks.into_iter().flatten().collect());
+        };
+
+        let transactions = storage
+            .blocks_web3_dal()
+            .get_transactions_fee_data(missing_range)
+            .await?;
+        let mut cache = self.0.lock().unwrap();
+        let blocks = blocks_data
+            .into_iter()
+            .zip(cached_blocks)
+            .map(|(data, cached)| {
+                if let Some(cached) = cached {
+                    return cached;
+                }
+                // Transactions are sorted by miniblock number, so we can find the relevant range using binary search.
+                let start = transactions.partition_point(|tx| tx.miniblock_number < data.number);
+                let end = transactions.partition_point(|tx| tx.miniblock_number <= data.number);
+                let number = data.number;
+                let block = Arc::new(BlockFeeHistory::new(data, &transactions[start..end]));
+                cache.put(number, block.clone());
+                block
+            })
+            .collect();
+        Ok(blocks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block_with_rewards(rewards: &[(u64, u64)]) -> BlockFeeHistory {
+        let data = MiniblockFeeData {
+            number: MiniblockNumber(1),
+            hash: H256::zero(),
+            base_fee_per_gas: 100.into(),
+            fair_pubdata_price: 1_000,
+        };
+        let transactions: Vec<_> = rewards
+            .iter()
+            .map(|&(reward, gas_used)| TransactionFeeData {
+                miniblock_number: MiniblockNumber(1),
+                effective_gas_price: (100 + reward).into(),
+                gas_used: gas_used.into(),
+            })
+            .collect();
+        BlockFeeHistory::new(data, &transactions)
+    }
+
+    #[test]
+    fn computing_reward_percentiles() {
+        let block = block_with_rewards(&[]);
+        assert_eq!(block.rewards(&[10.0, 50.0]), [U256::zero(); 2]);
+        assert_eq!(block.gas_used_ratio(), 0.0);
+        assert_eq!(block.base_fee_per_blob_gas, 1_000.into());
+
+        let block = block_with_rewards(&[(30, 100), (10, 100), (20, 200)]);
+        assert_eq!(
+            block.rewards(&[0.0, 25.0, 50.0, 75.0, 100.0]),
+            [10, 10, 20, 20, 30].map(U256::from)
+        );
+        assert_eq!(block.rewards(&[50.5]), [U256::from(20)]);
+        assert_eq!(block.gas_used, 400.into());
+
+        let half_gas_limit = u64::from(BLOCK_GAS_LIMIT) / 2;
+        let block = block_with_rewards(&[(10, half_gas_limit)]);
+        assert!((block.gas_used_ratio() - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn validating_reward_percentiles() {
+        validate_reward_percentiles(&[]).unwrap();
+        validate_reward_percentiles(&[0.0, 10.0, 10.0, 99.5, 100.0]).unwrap();
+        validate_reward_percentiles(&[-1.0]).unwrap_err();
+        validate_reward_percentiles(&[101.0]).unwrap_err();
+        validate_reward_percentiles(&[50.0, 20.0]).unwrap_err();
+        validate_reward_percentiles(&[f32::NAN]).unwrap_err();
+    }
+}
@@ -100,7 +100,9 @@ enum Web3ErrorKind {
     FilterNotFound,
     LogsLimitExceeded,
     InvalidFilterBlockHash,
+    InvalidRewardPercentiles,
     TreeApiUnavailable,
+    NoTreeData,
     Internal,
 }
 
@@ -116,7 +118,9 @@ impl Web3ErrorKind {
             Web3Error::FilterNotFound => Self::FilterNotFound,
             Web3Error::LogsLimitExceeded(..) => Self::LogsLimitExceeded,
             Web3Error::InvalidFilterBlockHash => Self::InvalidFilterBlockHash,
+            Web3Error::InvalidRewardPercentiles(_) => Self::InvalidRewardPercentiles,
             Web3Error::TreeApiUnavailable => Self::TreeApiUnavailable,
+            Web3Error::NoTreeData(_) => Self::NoTreeData,
             Web3Error::InternalError(_) | Web3Error::NotImplemented => Self::Internal,
         }
     }
@@ -179,6 +183,9 @@ pub(super) struct ApiMetrics {
     pub web3_in_flight_requests: Family<ApiTransportLabel, Histogram<usize>>,
     /// Number of currently open WebSocket sessions.
     pub ws_open_sessions: Gauge,
+    /// Number of miniblocks skipped in logs queries thanks to miniblock logs blooms.
+    #[metrics(buckets = Buckets::exponential(1.0..=65_536.0, 4.0))]
+    pub logs_bloom_skipped_blocks: Histogram<u64>,
 }
 
 impl ApiMetrics {
@@ -260,6 +267,8 @@ pub(super) enum SubscriptionType {
     Blocks,
     Txs,
     Logs,
+    L1Batches,
+    TxFinality,
 }
 
 #[derive(Debug, Metrics)]
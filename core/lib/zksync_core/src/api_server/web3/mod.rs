@@ -1,4 +1,10 @@
-use std::{collections::HashSet, net::SocketAddr, num::NonZeroU32, sync::Arc, time::Duration};
+use std::{
+    collections::HashSet,
+    net::SocketAddr,
+    num::{NonZeroU32, NonZeroUsize},
+    sync::Arc,
+    time::Duration,
+};
 
 use anyhow::Context as _;
 use chrono::NaiveDateTime;
@@ -20,12 +26,14 @@ use zksync_web3_decl::{
     namespaces::{
         DebugNamespaceServer, EnNamespaceServer, EthNamespaceServer, EthPubSubServer,
         NetNamespaceServer, SnapshotsNamespaceServer, Web3NamespaceServer, ZksNamespaceServer,
+        ZksPubSubServer,
     },
     types::Filter,
 };
 
 use self::{
     backend_jsonrpsee::{LimitMiddleware, MetadataMiddleware, MethodTracer},
+    fee_history::FeeHistoryCache,
     metrics::API_METRICS,
     namespaces::{
         DebugNamespace, EnNamespace, EthNamespace, NetNamespace, SnapshotsNamespace, Web3Namespace,
@@ -45,6 +53,7 @@ use crate::{
 };
 
 pub mod backend_jsonrpsee;
+mod fee_history;
 mod metrics;
 pub mod namespaces;
 mod pubsub;
@@ -310,6 +319,10 @@ impl ApiServer {
             ))))
         };
 
+        let fee_history_cache_capacity =
+            NonZeroUsize::new(self.config.fee_history_limit as usize).unwrap_or(NonZeroUsize::MIN);
+        let fee_history_cache = Arc::new(FeeHistoryCache::new(fee_history_cache_capacity));
+
         Ok(RpcState {
             current_method: self.method_tracer,
             installed_filters,
@@ -320,6 +333,7 @@ impl ApiServer {
             start_info,
             last_sealed_miniblock,
             tree_api: self.optional.tree_api,
+            fee_history_cache,
         })
     }
 
@@ -335,8 +349,10 @@ impl ApiServer {
         // Collect all the methods into a single RPC module.
         let mut rpc = RpcModule::new(());
         if let Some(pub_sub) = pub_sub {
-            rpc.merge(pub_sub.into_rpc())
+            rpc.merge(EthPubSubServer::into_rpc(pub_sub.clone()))
                 .expect("Can't merge eth pubsub namespace");
+            rpc.merge(ZksPubSubServer::into_rpc(pub_sub))
+                .expect("Can't merge zks pubsub namespace");
         }
 
         if namespaces.contains(&Namespace::Eth) {
@@ -442,16 +458,12 @@ impl ApiServer {
         let pub_sub = if matches!(transport, ApiTransport::WebSocket(_))
             && self.namespaces.contains(&Namespace::Pubsub)
         {
-            let mut pub_sub = EthSubscribe::new();
+            let mut pub_sub = EthSubscribe::new(self.pool.clone());
             if let Some(sender) = &self.optional.pub_sub_events_sender {
                 pub_sub.set_events_sender(sender.clone());
             }
 
-            tasks.extend(pub_sub.spawn_notifiers(
-                self.pool.clone(),
-                self.polling_interval,
-                stop_receiver.clone(),
-            ));
+            tasks.extend(pub_sub.spawn_notifiers(self.polling_interval, stop_receiver.clone()));
             Some(pub_sub)
         } else {
             None
@@ -1,32 +1,43 @@
 use anyhow::Context as _;
+use zksync_dal::StorageProcessor;
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     api::{
-        BlockId, BlockNumber, GetLogsFilter, Transaction, TransactionId, TransactionReceipt,
-        TransactionVariant,
+        BlockId, BlockNumber, Eip1186Proof, Eip1186StorageProof, FeeHistory, GetLogsFilter,
+        Transaction, TransactionId, TransactionReceipt, TransactionVariant,
     },
+    get_code_key, get_nonce_key,
     l2::{L2Tx, TransactionType},
     transaction_request::CallRequest,
-    utils::decompose_full_nonce,
+    utils::{decompose_full_nonce, storage_key_for_eth_balance},
     web3::{
         self,
-        types::{FeeHistory, SyncInfo, SyncState},
+        types::{SyncInfo, SyncState},
     },
-    AccountTreeId, Bytes, MiniblockNumber, StorageKey, H256, L2_ETH_TOKEN_ADDRESS, U256,
+    AccountTreeId, Bytes, MiniblockNumber, StorageKey, H2048, H256, L2_ETH_TOKEN_ADDRESS, U256,
 };
-use zksync_utils::u256_to_h256;
+use zksync_utils::{h256_to_u256, u256_to_h256};
 use zksync_web3_decl::{
     error::Web3Error,
     types::{Address, Block, Filter, FilterChanges, Log, U64},
 };
 
-use crate::api_server::web3::{
-    backend_jsonrpsee::MethodTracer, metrics::API_METRICS, state::RpcState, TypedFilter,
+use crate::api_server::{
+    tree::TreeEntryWithProof,
+    web3::{
+        backend_jsonrpsee::MethodTracer, fee_history::validate_reward_percentiles,
+        metrics::API_METRICS, state::RpcState, TypedFilter,
+    },
 };
 
 pub const EVENT_TOPIC_NUMBER_LIMIT: usize = 4;
 pub const PROTOCOL_VERSION: &str = "zks/1";
 
+/// Minimum number of miniblocks in a logs query for which miniblock logs blooms are used to narrow the queried range.
+const LOGS_BLOOM_MIN_RANGE: u32 = 100;
+/// Number of miniblock logs blooms loaded from the storage at once.
+const LOGS_BLOOM_CHUNK_SIZE: u32 = 1_000;
+
 #[derive(Debug)]
 pub(crate) struct EthNamespace {
     state: RpcState,
@@ -680,6 +691,7 @@ impl EthNamespace {
     ) -> Result<FeeHistory, Web3Error> {
         self.current_method()
             .set_block_id(BlockId::Number(newest_block));
+        validate_reward_percentiles(&reward_percentiles)?;
 
         // Limit `block_count`.
         let block_count = block_count
@@ -698,33 +710,160 @@ impl EthNamespace {
             .await?;
         self.set_block_diff(newest_miniblock);
 
-        let mut base_fee_per_gas = connection
-            .blocks_web3_dal()
-            .get_fee_history(newest_miniblock, block_count)
+        let blocks = self
+            .state
+            .fee_history_cache
+            .load(&mut connection, newest_miniblock, block_count)
             .await
-            .context("get_fee_history")?;
-        // DAL method returns fees in DESC order while we need ASC.
-        base_fee_per_gas.reverse();
-
-        let oldest_block = newest_miniblock.0 + 1 - base_fee_per_gas.len() as u32;
-        // We do not store gas used ratio for blocks, returns array of zeroes as a placeholder.
-        let gas_used_ratio = vec![0.0; base_fee_per_gas.len()];
-        // Effective priority gas price is currently 0.
-        let reward = Some(vec![
-            vec![U256::zero(); reward_percentiles.len()];
-            base_fee_per_gas.len()
-        ]);
-
-        // `base_fee_per_gas` for next miniblock cannot be calculated, appending last fee as a placeholder.
-        base_fee_per_gas.push(*base_fee_per_gas.last().unwrap());
+            .context("fee_history_cache.load()")?;
+        drop(connection);
+
+        let oldest_block = newest_miniblock.0 + 1 - blocks.len() as u32;
+        let mut base_fee_per_gas: Vec<_> =
+            blocks.iter().map(|block| block.base_fee_per_gas).collect();
+        let gas_used_ratio = blocks.iter().map(|block| block.gas_used_ratio()).collect();
+        let reward = blocks
+            .iter()
+            .map(|block| block.rewards(&reward_percentiles))
+            .collect();
+        let mut base_fee_per_blob_gas: Vec<_> = blocks
+            .iter()
+            .map(|block| block.base_fee_per_blob_gas)
+            .collect();
+        // L2 transactions cannot carry blobs.
+        let blob_gas_used_ratio = vec![0.0; blocks.len()];
+
+        // For the latest miniblock, fees for the next miniblock are estimated using the current fee input
+        // (which reflects the L2 congestion). Otherwise, we append the last fees as a placeholder.
+        let (next_base_fee_per_gas, next_base_fee_per_blob_gas) =
+            if matches!(newest_block, BlockNumber::Latest | BlockNumber::Pending) {
+                let tx_sender = &self.state.tx_sender;
+                let gas_price = tx_sender.gas_price().await.context("gas_price")?;
+                let fee_input = tx_sender
+                    .0
+                    .batch_fee_input_provider
+                    .get_batch_fee_input()
+                    .await;
+                (gas_price.into(), fee_input.fair_pubdata_price().into())
+            } else {
+                (
+                    *base_fee_per_gas.last().unwrap(),
+                    *base_fee_per_blob_gas.last().unwrap(),
+                )
+            };
+        base_fee_per_gas.push(next_base_fee_per_gas);
+        base_fee_per_blob_gas.push(next_base_fee_per_blob_gas);
+
         Ok(FeeHistory {
-            oldest_block: web3::types::BlockNumber::Number(oldest_block.into()),
-            base_fee_per_gas,
-            gas_used_ratio,
-            reward,
+            inner: web3::types::FeeHistory {
+                oldest_block: web3::types::BlockNumber::Number(oldest_block.into()),
+                base_fee_per_gas,
+                gas_used_ratio,
+                reward: Some(reward),
+            },
+            base_fee_per_blob_gas,
+            blob_gas_used_ratio,
         })
     }
 
+    #[tracing::instrument(skip(self, keys))]
+    pub async fn get_proof_impl(
+        &self,
+        address: Address,
+        keys: Vec<H256>,
+        block_id: BlockId,
+    ) -> Result<Eip1186Proof, Web3Error> {
+        self.current_method().set_block_id(block_id);
+
+        let mut connection = self
+            .state
+            .connection_pool
+            .access_storage_tagged("api")
+            .await?;
+        let block_number = self.state.resolve_block(&mut connection, block_id).await?;
+        self.set_block_diff(block_number);
+
+        // The Merkle tree is only updated once per L1 batch, so proofs are provided for the state
+        // after the L1 batch containing the block.
+        let l1_batch_number = connection
+            .blocks_web3_dal()
+            .get_l1_batch_number_of_miniblock(block_number)
+            .await
+            .context("get_l1_batch_number_of_miniblock")?
+            .ok_or(Web3Error::NoTreeData(block_number))?;
+        let storage_hash = connection
+            .blocks_dal()
+            .get_l1_batch_state_root(l1_batch_number)
+            .await
+            .context("get_l1_batch_state_root")?
+            .ok_or(Web3Error::NoTreeData(block_number))?;
+        drop(connection);
+
+        let account_keys = [
+            get_nonce_key(&address),
+            storage_key_for_eth_balance(&address),
+            get_code_key(&address),
+        ];
+        let hashed_keys =
+            account_keys
+                .iter()
+                .map(StorageKey::hashed_key_u256)
+                .chain(keys.iter().map(|key| {
+                    StorageKey::new(AccountTreeId::new(address), *key).hashed_key_u256()
+                }))
+                .collect();
+        let mut proofs = self
+            .state
+            .get_tree_proofs(l1_batch_number, hashed_keys)
+            .await?
+            .ok_or(Web3Error::NoTreeData(block_number))?;
+        if proofs.len() != account_keys.len() + keys.len() {
+            let err = anyhow::anyhow!(
+                "Tree API returned unexpected number of proofs: {}",
+                proofs.len()
+            );
+            return Err(err.into());
+        }
+
+        let storage_proofs = proofs.split_off(account_keys.len());
+        let storage_proof = storage_proofs
+            .into_iter()
+            .zip(keys)
+            .map(|(proof, key)| Eip1186StorageProof {
+                key,
+                value: h256_to_u256(proof.value),
+                proof: proof
+                    .merkle_path
+                    .iter()
+                    .map(|hash| Bytes(hash.as_bytes().to_vec()))
+                    .collect(),
+                index: proof.index,
+            })
+            .collect();
+        let (nonce, _) = decompose_full_nonce(h256_to_u256(proofs[0].value));
+
+        Ok(Eip1186Proof {
+            address,
+            account_proof: proofs.iter().map(Self::encode_account_proof).collect(),
+            balance: h256_to_u256(proofs[1].value),
+            code_hash: proofs[2].value,
+            nonce,
+            storage_hash,
+            storage_proof,
+            l1_batch_number,
+        })
+    }
+
+    /// Encodes a proof for an account data slot as described in [`Eip1186Proof::account_proof`] docs.
+    fn encode_account_proof(proof: &TreeEntryWithProof) -> Bytes {
+        let mut bytes = Vec::with_capacity(8 + proof.merkle_path.len() * 32);
+        bytes.extend_from_slice(&proof.index.to_be_bytes());
+        for hash in &proof.merkle_path {
+            bytes.extend_from_slice(hash.as_bytes());
+        }
+        Bytes(bytes)
+    }
+
     #[tracing::instrument(skip(self, typed_filter))]
     async fn filter_changes(
         &self,
@@ -814,6 +953,12 @@ impl EthNamespace {
                     .connection_pool
                     .access_storage_tagged("api")
                     .await?;
+                let Some(get_logs_filter) =
+                    Self::narrow_logs_filter(&mut storage, get_logs_filter).await?
+                else {
+                    *from_block = to_block + 1;
+                    return Ok(FilterChanges::Logs(vec![]));
+                };
 
                 // Check if there is more than one block in range and there are more than `req_entities_limit` logs that satisfies filter.
                 // In this case we should return error and suggest requesting logs with smaller block range.
@@ -845,6 +990,66 @@ impl EthNamespace {
             }
         })
     }
+
+    /// Narrows the miniblock range of a logs query using miniblock logs blooms, so that the range starts and ends
+    /// with miniblocks that may contain matching logs. Returns `None` if no miniblock in the range can contain
+    /// matching logs. Miniblocks without a stored logs bloom are conservatively considered as matching.
+    async fn narrow_logs_filter(
+        storage: &mut StorageProcessor<'_>,
+        mut filter: GetLogsFilter,
+    ) -> anyhow::Result<Option<GetLogsFilter>> {
+        let is_trivial = filter.addresses.is_empty() && filter.topics.is_empty();
+        if is_trivial
+            || filter.to_block.0.saturating_sub(filter.from_block.0) < LOGS_BLOOM_MIN_RANGE
+        {
+            return Ok(Some(filter));
+        }
+        let may_match =
+            |bloom: &Option<H2048>| bloom.map_or(true, |bloom| filter.may_match_bloom(&bloom));
+
+        let mut chunk_start = filter.from_block;
+        let first_match = loop {
+            if chunk_start > filter.to_block {
+                return Ok(None);
+            }
+            let chunk_end = filter
+                .to_block
+                .min(chunk_start + (LOGS_BLOOM_CHUNK_SIZE - 1));
+            let blooms = storage
+                .blocks_web3_dal()
+                .get_logs_blooms(chunk_start..=chunk_end)
+                .await
+                .context("get_logs_blooms")?;
+            if let Some((number, _)) = blooms.iter().find(|(_, bloom)| may_match(bloom)) {
+                break *number;
+            }
+            chunk_start = chunk_end + 1;
+        };
+
+        let mut chunk_end = filter.to_block;
+        let last_match = loop {
+            let chunk_start =
+                MiniblockNumber(chunk_end.0.saturating_sub(LOGS_BLOOM_CHUNK_SIZE - 1))
+                    .max(first_match);
+            let blooms = storage
+                .blocks_web3_dal()
+                .get_logs_blooms(chunk_start..=chunk_end)
+                .await
+                .context("get_logs_blooms")?;
+            if let Some((number, _)) = blooms.iter().rev().find(|(_, bloom)| may_match(bloom)) {
+                break *number;
+            }
+            // Since `first_match` matches the filter, we cannot get past it.
+            chunk_end = chunk_start - 1;
+        };
+
+        API_METRICS.logs_bloom_skipped_blocks.observe(
+            (first_match.0 - filter.from_block.0 + filter.to_block.0 - last_match.0).into(),
+        );
+        filter.from_block = first_match;
+        filter.to_block = last_match;
+        Ok(Some(filter))
+    }
 }
 
 // Bogus methods.
@@ -886,3 +1091,115 @@ impl EthNamespace {
     // - `compile_solidity`.
     // - `compile_serpent`.
 }
+
+#[cfg(test)]
+mod tests {
+    use std::ops;
+
+    use zksync_dal::{testonly::clear_logs_blooms, ConnectionPool};
+    use zksync_types::{block::build_bloom, ProtocolVersion};
+
+    use super::*;
+    use crate::utils::testonly::create_miniblock;
+
+    const MINIBLOCK_COUNT: u32 = LOGS_BLOOM_CHUNK_SIZE + 200;
+
+    fn matching_address() -> Address {
+        Address::repeat_byte(1)
+    }
+
+    fn matching_topic() -> H256 {
+        H256::repeat_byte(2)
+    }
+
+    /// Inserts miniblocks with empty logs blooms, except for `matching_miniblocks`, blooms for which
+    /// contain `matching_address()` and `matching_topic()`.
+    async fn prepare_storage(storage: &mut StorageProcessor<'_>, matching_miniblocks: &[u32]) {
+        storage
+            .protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion::default())
+            .await;
+        for number in 0..MINIBLOCK_COUNT {
+            let mut miniblock = create_miniblock(number);
+            if matching_miniblocks.contains(&number) {
+                miniblock.logs_bloom =
+                    build_bloom([matching_address().as_bytes(), matching_topic().as_bytes()]);
+            }
+            storage
+                .blocks_dal()
+                .insert_miniblock(&miniblock)
+                .await
+                .unwrap();
+        }
+    }
+
+    async fn narrow_range(
+        storage: &mut StorageProcessor<'_>,
+        range: ops::RangeInclusive<u32>,
+        addresses: Vec<Address>,
+        topics: Vec<(u32, Vec<H256>)>,
+    ) -> Option<ops::RangeInclusive<u32>> {
+        let filter = GetLogsFilter {
+            from_block: MiniblockNumber(*range.start()),
+            to_block: MiniblockNumber(*range.end()),
+            addresses,
+            topics,
+        };
+        let filter = EthNamespace::narrow_logs_filter(storage, filter)
+            .await
+            .unwrap()?;
+        Some(filter.from_block.0..=filter.to_block.0)
+    }
+
+    #[tokio::test]
+    async fn narrowing_logs_filter() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage, &[1_050, 1_100]).await;
+        let full_range = 0..=MINIBLOCK_COUNT - 1;
+        let address = matching_address();
+        let topic = matching_topic();
+
+        // Trivial filters and short ranges are not narrowed.
+        let range = narrow_range(&mut storage, full_range.clone(), vec![], vec![]).await;
+        assert_eq!(range, Some(full_range.clone()));
+        let range = narrow_range(&mut storage, 1_000..=1_010, vec![address], vec![]).await;
+        assert_eq!(range, Some(1_000..=1_010));
+
+        // The first matching miniblock is not in the first queried chunk.
+        let range = narrow_range(&mut storage, full_range.clone(), vec![address], vec![]).await;
+        assert_eq!(range, Some(1_050..=1_100));
+        let topics = vec![(1, vec![topic])];
+        let range = narrow_range(&mut storage, full_range.clone(), vec![], topics.clone()).await;
+        assert_eq!(range, Some(1_050..=1_100));
+        let range = narrow_range(&mut storage, full_range.clone(), vec![address], topics).await;
+        assert_eq!(range, Some(1_050..=1_100));
+        let range = narrow_range(&mut storage, 900..=1_075, vec![address], vec![]).await;
+        assert_eq!(range, Some(1_050..=1_050));
+
+        // No miniblock in the range can contain matching logs.
+        let other_address = Address::repeat_byte(3);
+        let range = narrow_range(
+            &mut storage,
+            full_range.clone(),
+            vec![other_address],
+            vec![],
+        )
+        .await;
+        assert_eq!(range, None);
+        let topics = vec![(1, vec![H256::repeat_byte(4)])];
+        let range = narrow_range(&mut storage, full_range.clone(), vec![address], topics).await;
+        assert_eq!(range, None);
+        let range = narrow_range(&mut storage, 0..=1_000, vec![address], vec![]).await;
+        assert_eq!(range, None);
+
+        // Miniblocks without a logs bloom are considered as matching.
+        clear_logs_blooms(&mut storage, MiniblockNumber(10)..=MiniblockNumber(10))
+            .await
+            .unwrap();
+        let range = narrow_range(&mut storage, full_range.clone(), vec![address], vec![]).await;
+        assert_eq!(range, Some(10..=1_100));
+        let range = narrow_range(&mut storage, full_range, vec![other_address], vec![]).await;
+        assert_eq!(range, Some(10..=10));
+    }
+}
@@ -6,8 +6,9 @@ use zksync_mini_merkle_tree::MiniMerkleTree;
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     api::{
-        BlockDetails, BridgeAddresses, GetLogsFilter, L1BatchDetails, L2ToL1LogProof, Proof,
-        ProtocolVersion, StorageProof, TransactionDetails,
+        BlockDetails, BridgeAddresses, GetLogsFilter, L1BatchDetails, L2ToL1LogProof,
+        PriorityOpIdentifier, PriorityOpStatus, Proof, ProtocolVersion, StorageProof,
+        TransactionDetails,
     },
     fee::Fee,
     fee_model::FeeParams,
@@ -26,10 +27,7 @@ use zksync_web3_decl::{
     types::{Address, Token, H256},
 };
 
-use crate::api_server::{
-    tree::TreeApiError,
-    web3::{backend_jsonrpsee::MethodTracer, RpcState},
-};
+use crate::api_server::web3::{backend_jsonrpsee::MethodTracer, RpcState};
 
 #[derive(Debug)]
 pub(crate) struct ZksNamespace {
@@ -421,6 +419,19 @@ impl ZksNamespace {
         Ok(tx_details)
     }
 
+    #[tracing::instrument(skip(self))]
+    pub async fn get_priority_op_status_impl(
+        &self,
+        id: PriorityOpIdentifier,
+    ) -> Result<Option<PriorityOpStatus>, Web3Error> {
+        let mut storage = self.access_storage().await?;
+        Ok(storage
+            .transactions_web3_dal()
+            .get_priority_op_status(id)
+            .await
+            .context("get_priority_op_status")?)
+    }
+
     #[tracing::instrument(skip(self))]
     pub async fn get_l1_batch_details_impl(
         &self,
@@ -505,25 +516,12 @@ impl ZksNamespace {
             .iter()
             .map(|key| StorageKey::new(AccountTreeId::new(address), *key).hashed_key_u256())
             .collect();
-        let tree_api = self
+        let Some(proofs) = self
             .state
-            .tree_api
-            .as_deref()
-            .ok_or(Web3Error::TreeApiUnavailable)?;
-        let proofs_result = tree_api.get_proofs(l1_batch_number, hashed_keys).await;
-        let proofs = match proofs_result {
-            Ok(proofs) => proofs,
-            Err(TreeApiError::NotReady) => return Err(Web3Error::TreeApiUnavailable),
-            Err(TreeApiError::NoVersion(err)) => {
-                return if err.missing_version > err.version_count {
-                    Ok(None)
-                } else {
-                    Err(Web3Error::InternalError(anyhow::anyhow!(
-                        "L1 batch #{l1_batch_number} is pruned in Merkle tree, but not in Postgres"
-                    )))
-                };
-            }
-            Err(TreeApiError::Internal(err)) => return Err(Web3Error::InternalError(err)),
+            .get_tree_proofs(l1_batch_number, hashed_keys)
+            .await?
+        else {
+            return Ok(None);
         };
 
         let storage_proof = proofs
@@ -7,8 +7,11 @@ use tokio::{
     task::JoinHandle,
     time::{interval, Duration},
 };
-use zksync_dal::ConnectionPool;
-use zksync_types::{MiniblockNumber, H128, H256};
+use zksync_dal::{ConnectionPool, StorageProcessor};
+use zksync_types::{
+    api::{L1BatchStage, L1BatchStatusUpdate, TransactionFinalityUpdate},
+    L1BatchNumber, MiniblockNumber, H128, H256,
+};
 use zksync_web3_decl::{
     jsonrpsee::{
         core::{server::SubscriptionMessage, SubscriptionResult},
@@ -16,8 +19,8 @@ use zksync_web3_decl::{
         types::{error::ErrorCode, ErrorObject, SubscriptionId},
         PendingSubscriptionSink, SendTimeoutError, SubscriptionSink,
     },
-    namespaces::EthPubSubServer,
-    types::{BlockHeader, Log, PubSubFilter, PubSubResult},
+    namespaces::{EthPubSubServer, ZksPubSubServer},
+    types::{BlockHeader, Log, PubSubFilter, PubSubResult, ZksPubSubFilter},
 };
 
 use super::{
@@ -28,6 +31,14 @@ use crate::api_server::execution_sandbox::BlockStartInfo;
 
 const BROADCAST_CHANNEL_CAPACITY: usize = 1024;
 const SUBSCRIPTION_SINK_SEND_TIMEOUT: Duration = Duration::from_secs(1);
+/// Maximum number of L1 batch status updates loaded from Postgres at once.
+const L1_BATCH_UPDATES_CHUNK_SIZE: u32 = 100;
+const L1_BATCH_STAGES: [L1BatchStage; 4] = [
+    L1BatchStage::Sealed,
+    L1BatchStage::Committed,
+    L1BatchStage::Proven,
+    L1BatchStage::Executed,
+];
 
 #[derive(Debug, Clone, Copy)]
 pub struct EthSubscriptionIdProvider;
@@ -213,26 +224,196 @@ impl PubSubNotifier {
             .await
             .context("events_web3_dal().get_all_logs()")
     }
+
+    async fn notify_l1_batches(self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        // Last L1 batch number in each lifecycle stage, indexed by the stage ordinal.
+        let mut last_l1_batches = [None; L1_BATCH_STAGES.len()];
+        let mut storage = self
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .context("access_storage_tagged")?;
+        for (stage, last_l1_batch) in L1_BATCH_STAGES.into_iter().zip(&mut last_l1_batches) {
+            *last_l1_batch = Self::last_l1_batch_in_stage(&mut storage, stage).await?;
+        }
+        drop(storage);
+
+        let mut timer = interval(self.polling_interval);
+        loop {
+            if *stop_receiver.borrow() {
+                tracing::info!("Stop signal received, pubsub_l1_batches_notifier is shutting down");
+                break;
+            }
+            timer.tick().await;
+
+            let db_latency = PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::L1Batches].start();
+            let mut storage = self
+                .connection_pool
+                .access_storage_tagged("api")
+                .await
+                .context("access_storage_tagged")?;
+            let mut new_updates = vec![];
+            for (stage, last_l1_batch) in L1_BATCH_STAGES.into_iter().zip(&mut last_l1_batches) {
+                let Some(new_last_l1_batch) =
+                    Self::last_l1_batch_in_stage(&mut storage, stage).await?
+                else {
+                    continue;
+                };
+                let mut chunk_start = match *last_l1_batch {
+                    Some(number) => number + 1,
+                    None => {
+                        // No L1 batches were in this stage previously, so we need to start from the earliest batch
+                        // in the storage (which is not necessarily the genesis batch after snapshot recovery).
+                        let earliest_l1_batch = storage
+                            .blocks_dal()
+                            .get_earliest_l1_batch_number()
+                            .await
+                            .context("get_earliest_l1_batch_number()")?;
+                        earliest_l1_batch.unwrap_or(L1BatchNumber(0))
+                    }
+                };
+                while chunk_start <= new_last_l1_batch {
+                    let chunk_end =
+                        new_last_l1_batch.min(chunk_start + (L1_BATCH_UPDATES_CHUNK_SIZE - 1));
+                    let updates = storage
+                        .blocks_web3_dal()
+                        .get_l1_batch_status_updates(chunk_start..=chunk_end, stage)
+                        .await
+                        .with_context(|| {
+                            format!("get_l1_batch_status_updates({chunk_start}..={chunk_end}, {stage:?})")
+                        })?;
+                    new_updates.extend(updates.into_iter().map(PubSubResult::L1BatchStatus));
+                    chunk_start = chunk_end + 1;
+                }
+                *last_l1_batch = Some(new_last_l1_batch);
+            }
+            drop(storage);
+            db_latency.observe();
+
+            if !new_updates.is_empty() {
+                self.send_pub_sub_results(new_updates, SubscriptionType::L1Batches);
+            }
+            self.emit_event(PubSubEvent::NotifyIterationFinished(
+                SubscriptionType::L1Batches,
+            ));
+        }
+        Ok(())
+    }
+
+    async fn last_l1_batch_in_stage(
+        storage: &mut StorageProcessor<'_>,
+        stage: L1BatchStage,
+    ) -> anyhow::Result<Option<L1BatchNumber>> {
+        let mut blocks_dal = storage.blocks_dal();
+        let last_l1_batch = match stage {
+            L1BatchStage::Sealed => blocks_dal.get_sealed_l1_batch_number().await,
+            L1BatchStage::Committed => {
+                blocks_dal
+                    .get_number_of_last_l1_batch_committed_on_eth()
+                    .await
+            }
+            L1BatchStage::Proven => blocks_dal.get_number_of_last_l1_batch_proven_on_eth().await,
+            L1BatchStage::Executed => {
+                blocks_dal
+                    .get_number_of_last_l1_batch_executed_on_eth()
+                    .await
+            }
+        };
+        last_l1_batch.with_context(|| format!("failed getting last L1 batch in stage {stage:?}"))
+    }
+}
+
+/// Tracks the lifecycle stage of the L1 batch containing a certain transaction.
+#[derive(Debug)]
+struct TxFinalityTracker {
+    tx_hash: H256,
+    l1_batch_number: Option<L1BatchNumber>,
+    stage: Option<L1BatchStage>,
 }
 
-/// Subscription support for Web3 APIs.
+impl TxFinalityTracker {
+    fn new(tx_hash: H256) -> Self {
+        Self {
+            tx_hash,
+            l1_batch_number: None,
+            stage: None,
+        }
+    }
+
+    fn is_finalized(&self) -> bool {
+        self.stage == Some(L1BatchStage::Executed)
+    }
+
+    /// Loads the current state of the transaction from the storage. Returns an update if the state has changed.
+    async fn refresh(
+        &mut self,
+        connection_pool: &ConnectionPool,
+    ) -> anyhow::Result<Option<TransactionFinalityUpdate>> {
+        let mut storage = connection_pool
+            .access_storage_tagged("api")
+            .await
+            .context("access_storage_tagged")?;
+        let l1_batch_number = storage
+            .transactions_web3_dal()
+            .get_l1_batch_number_of_transaction(self.tx_hash)
+            .await
+            .with_context(|| format!("get_l1_batch_number_of_transaction({:?})", self.tx_hash))?;
+        let Some(l1_batch_number) = l1_batch_number else {
+            return Ok(None);
+        };
+        let details = storage
+            .blocks_web3_dal()
+            .get_l1_batch_details(l1_batch_number)
+            .await
+            .with_context(|| format!("get_l1_batch_details({l1_batch_number})"))?;
+        let Some(details) = details else {
+            return Ok(None);
+        };
+
+        self.l1_batch_number = Some(l1_batch_number);
+        Ok(self.observe(L1BatchStatusUpdate::latest(&details)))
+    }
+
+    /// Observes an L1 batch status update. Returns a transaction update if the update is relevant for the tracked
+    /// transaction.
+    fn observe(&mut self, update: L1BatchStatusUpdate) -> Option<TransactionFinalityUpdate> {
+        let is_relevant = self.l1_batch_number == Some(update.l1_batch_number)
+            && self.stage.map_or(true, |stage| stage < update.stage);
+        if !is_relevant {
+            return None;
+        }
+        self.stage = Some(update.stage);
+        Some(TransactionFinalityUpdate {
+            transaction_hash: self.tx_hash,
+            l1_batch: update,
+        })
+    }
+}
+
+/// Subscription support for Web3 APIs (both `eth_subscribe` and `zks_subscribe`).
+#[derive(Clone)]
 pub(super) struct EthSubscribe {
+    connection_pool: ConnectionPool,
     blocks: broadcast::Sender<Vec<PubSubResult>>,
     transactions: broadcast::Sender<Vec<PubSubResult>>,
     logs: broadcast::Sender<Vec<PubSubResult>>,
+    l1_batches: broadcast::Sender<Vec<PubSubResult>>,
     events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
 }
 
 impl EthSubscribe {
-    pub fn new() -> Self {
+    pub fn new(connection_pool: ConnectionPool) -> Self {
         let (blocks, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
         let (transactions, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
         let (logs, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
+        let (l1_batches, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
 
         Self {
+            connection_pool,
             blocks,
             transactions,
             logs,
+            l1_batches,
             events_sender: None,
         }
     }
@@ -329,6 +510,100 @@ impl EthSubscribe {
         Ok(())
     }
 
+    async fn run_tx_finality_subscriber(
+        sink: SubscriptionSink,
+        mut receiver: broadcast::Receiver<Vec<PubSubResult>>,
+        connection_pool: ConnectionPool,
+        tx_hash: H256,
+    ) {
+        let subscription_type = SubscriptionType::TxFinality;
+        let _guard = PUB_SUB_METRICS.active_subscribers[&subscription_type].inc_guard(1);
+        let lifetime_latency = PUB_SUB_METRICS.subscriber_lifetime[&subscription_type].start();
+        let closed = sink.closed().fuse();
+        tokio::pin!(closed);
+
+        let mut tracker = TxFinalityTracker::new(tx_hash);
+        // The tracker state is loaded from the storage after subscribing to updates, so that no updates are missed.
+        let mut needs_refresh = true;
+        loop {
+            let mut tx_updates = vec![];
+            if needs_refresh {
+                match tracker.refresh(&connection_pool).await {
+                    Ok(update) => tx_updates.extend(update),
+                    Err(err) => {
+                        tracing::warn!(
+                            "Failed refreshing finality for transaction {tx_hash:?}: {err:#}"
+                        );
+                        break;
+                    }
+                }
+                needs_refresh = false;
+            }
+            let handle_result = Self::send_tx_finality_updates(&sink, tx_updates).await;
+            if handle_result.is_err() {
+                PUB_SUB_METRICS.subscriber_send_timeouts[&subscription_type].inc();
+                break;
+            }
+            if tracker.is_finalized() {
+                break;
+            }
+
+            tokio::select! {
+                new_items_result = receiver.recv() => {
+                    let new_items = match new_items_result {
+                        Ok(items) => items,
+                        Err(broadcast::error::RecvError::Closed) => break,
+                        Err(broadcast::error::RecvError::Lagged(message_count)) => {
+                            PUB_SUB_METRICS
+                                .skipped_broadcast_messages[&subscription_type]
+                                .observe(message_count);
+                            break;
+                        }
+                    };
+
+                    let mut tx_updates = vec![];
+                    for item in new_items {
+                        let PubSubResult::L1BatchStatus(update) = item else {
+                            continue;
+                        };
+                        if tracker.l1_batch_number.is_none() {
+                            // The transaction may have been included into the newly sealed batch.
+                            needs_refresh |= update.stage == L1BatchStage::Sealed;
+                        } else {
+                            tx_updates.extend(tracker.observe(update));
+                        }
+                    }
+                    let handle_result = Self::send_tx_finality_updates(&sink, tx_updates).await;
+                    if handle_result.is_err() {
+                        PUB_SUB_METRICS.subscriber_send_timeouts[&subscription_type].inc();
+                        break;
+                    }
+                }
+                _ = &mut closed => {
+                    break;
+                }
+            }
+        }
+        lifetime_latency.observe();
+    }
+
+    async fn send_tx_finality_updates(
+        sink: &SubscriptionSink,
+        updates: Vec<TransactionFinalityUpdate>,
+    ) -> Result<(), SendTimeoutError> {
+        for update in updates {
+            let item = PubSubResult::TransactionFinality(update);
+            sink.send_timeout(
+                SubscriptionMessage::from_json(&item)
+                    .expect("PubSubResult always serializable to json;qed"),
+                SUBSCRIPTION_SINK_SEND_TIMEOUT,
+            )
+            .await?;
+            PUB_SUB_METRICS.notify[&SubscriptionType::TxFinality].inc();
+        }
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self, pending_sink))]
     pub async fn sub(
         &self,
@@ -412,18 +687,69 @@ impl EthSubscribe {
         }
     }
 
+    #[tracing::instrument(skip(self, pending_sink))]
+    pub async fn sub_zks(
+        &self,
+        pending_sink: PendingSubscriptionSink,
+        sub_type: String,
+        params: Option<ZksPubSubFilter>,
+    ) {
+        let sub_type = match sub_type.as_str() {
+            "l1Batches" => {
+                let Ok(sink) = pending_sink.accept().await else {
+                    return;
+                };
+                let l1_batches_rx = self.l1_batches.subscribe();
+                tokio::spawn(Self::run_subscriber(
+                    sink,
+                    SubscriptionType::L1Batches,
+                    l1_batches_rx,
+                    None,
+                ));
+                Some(SubscriptionType::L1Batches)
+            }
+            "transactionFinality" => {
+                if let Some(tx_hash) = params.and_then(|params| params.transaction_hash) {
+                    let Ok(sink) = pending_sink.accept().await else {
+                        return;
+                    };
+                    let l1_batches_rx = self.l1_batches.subscribe();
+                    tokio::spawn(Self::run_tx_finality_subscriber(
+                        sink,
+                        l1_batches_rx,
+                        self.connection_pool.clone(),
+                        tx_hash,
+                    ));
+                    Some(SubscriptionType::TxFinality)
+                } else {
+                    Self::reject(pending_sink).await;
+                    None
+                }
+            }
+            _ => {
+                Self::reject(pending_sink).await;
+                None
+            }
+        };
+
+        if let Some(sub_type) = sub_type {
+            if let Some(sender) = &self.events_sender {
+                sender.send(PubSubEvent::Subscribed(sub_type)).ok();
+            }
+        }
+    }
+
     /// Spawns notifier tasks. This should be called once per instance.
     pub fn spawn_notifiers(
         &self,
-        connection_pool: ConnectionPool,
         polling_interval: Duration,
         stop_receiver: watch::Receiver<bool>,
     ) -> Vec<JoinHandle<anyhow::Result<()>>> {
-        let mut notifier_tasks = Vec::with_capacity(3);
+        let mut notifier_tasks = Vec::with_capacity(4);
 
         let notifier = PubSubNotifier {
             sender: self.blocks.clone(),
-            connection_pool: connection_pool.clone(),
+            connection_pool: self.connection_pool.clone(),
             polling_interval,
             events_sender: self.events_sender.clone(),
         };
@@ -432,7 +758,7 @@ impl EthSubscribe {
 
         let notifier = PubSubNotifier {
             sender: self.transactions.clone(),
-            connection_pool: connection_pool.clone(),
+            connection_pool: self.connection_pool.clone(),
             polling_interval,
             events_sender: self.events_sender.clone(),
         };
@@ -441,11 +767,20 @@ impl EthSubscribe {
 
         let notifier = PubSubNotifier {
             sender: self.logs.clone(),
-            connection_pool,
+            connection_pool: self.connection_pool.clone(),
+            polling_interval,
+            events_sender: self.events_sender.clone(),
+        };
+        let notifier_task = tokio::spawn(notifier.notify_logs(stop_receiver.clone()));
+        notifier_tasks.push(notifier_task);
+
+        let notifier = PubSubNotifier {
+            sender: self.l1_batches.clone(),
+            connection_pool: self.connection_pool.clone(),
             polling_interval,
             events_sender: self.events_sender.clone(),
         };
-        let notifier_task = tokio::spawn(notifier.notify_logs(stop_receiver));
+        let notifier_task = tokio::spawn(notifier.notify_l1_batches(stop_receiver));
 
         notifier_tasks.push(notifier_task);
         notifier_tasks
@@ -464,3 +799,16 @@ impl EthPubSubServer for EthSubscribe {
         Ok(())
     }
 }
+
+#[async_trait::async_trait]
+impl ZksPubSubServer for EthSubscribe {
+    async fn subscribe(
+        &self,
+        pending: PendingSubscriptionSink,
+        sub_type: String,
+        filter: Option<ZksPubSubFilter>,
+    ) -> SubscriptionResult {
+        self.sub_zks(pending, sub_type, filter).await;
+        Ok(())
+    }
+}
@@ -21,13 +21,14 @@ use zksync_web3_decl::{error::Web3Error, types::Filter};
 
 use super::{
     backend_jsonrpsee::MethodTracer,
+    fee_history::FeeHistoryCache,
     metrics::{FilterType, FILTER_METRICS},
     TypedFilter,
 };
 use crate::{
     api_server::{
         execution_sandbox::{BlockArgs, BlockArgsError, BlockStartInfo},
-        tree::TreeApiClient,
+        tree::{TreeApiClient, TreeApiError, TreeEntryWithProof},
         tx_sender::{tx_sink::TxSink, TxSender},
     },
     sync_layer::SyncState,
@@ -211,6 +212,7 @@ pub(crate) struct RpcState {
     /// from a snapshot.
     pub(super) start_info: BlockStartInfo,
     pub(super) last_sealed_miniblock: SealedMiniblockNumber,
+    pub(super) fee_history_cache: Arc<FeeHistoryCache>,
 }
 
 impl RpcState {
@@ -236,6 +238,34 @@ impl RpcState {
         self.tx_sender.0.tx_sink.as_ref()
     }
 
+    /// Fetches Merkle proofs for the specified `hashed_keys` at the specified L1 batch from the tree API.
+    /// Returns `Ok(None)` if the L1 batch is not yet processed by the Merkle tree.
+    pub(crate) async fn get_tree_proofs(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<Option<Vec<TreeEntryWithProof>>, Web3Error> {
+        let tree_api = self
+            .tree_api
+            .as_deref()
+            .ok_or(Web3Error::TreeApiUnavailable)?;
+        let proofs_result = tree_api.get_proofs(l1_batch_number, hashed_keys).await;
+        match proofs_result {
+            Ok(proofs) => Ok(Some(proofs)),
+            Err(TreeApiError::NotReady) => Err(Web3Error::TreeApiUnavailable),
+            Err(TreeApiError::NoVersion(err)) => {
+                if err.missing_version > err.version_count {
+                    Ok(None)
+                } else {
+                    Err(Web3Error::InternalError(anyhow::anyhow!(
+                        "L1 batch #{l1_batch_number} is pruned in Merkle tree, but not in Postgres"
+                    )))
+                }
+            }
+            Err(TreeApiError::Internal(err)) => Err(Web3Error::InternalError(err)),
+        }
+    }
+
     /// Resolves the specified block ID to a block number, which is guaranteed to be present in the node storage.
     pub(crate) async fn resolve_block(
         &self,
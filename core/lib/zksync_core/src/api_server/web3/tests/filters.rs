@@ -3,6 +3,7 @@
 use std::fmt::Debug;
 
 use jsonrpsee::{core::client::Error, types::error::ErrorCode};
+use zksync_types::block::build_bloom;
 use zksync_web3_decl::{jsonrpsee::core::ClientError as RpcError, types::FilterChanges};
 
 use super::*;
@@ -267,6 +268,74 @@ async fn log_filter_changes_with_block_boundaries() {
     test_http_server(LogFilterChangesWithBlockBoundariesTest).await;
 }
 
+#[derive(Debug)]
+struct GetLogsWithBloomsTest;
+
+#[async_trait]
+impl HttpTest for GetLogsWithBloomsTest {
+    async fn test(&self, client: &HttpClient, pool: &ConnectionPool) -> anyhow::Result<()> {
+        const LAST_MINIBLOCK: u32 = 150;
+
+        let mut storage = pool.access_storage().await?;
+        // Events in miniblock #1 are not reflected in its (empty) logs bloom, so non-trivial `eth_getLogs` queries
+        // over a large enough range should skip this miniblock.
+        let (_, first_events) = store_events(&mut storage, 1, 0).await?;
+        for number in 2..LAST_MINIBLOCK {
+            storage
+                .blocks_dal()
+                .insert_miniblock(&create_miniblock(number))
+                .await?;
+        }
+        let (_, last_events) = store_events(&mut storage, LAST_MINIBLOCK, 4).await?;
+        let bloom = build_bloom(last_events.iter().flat_map(VmEvent::bloom_items));
+        storage
+            .blocks_dal()
+            .range_update_logs_bloom(MiniblockNumber(LAST_MINIBLOCK), &[bloom])
+            .await?;
+        drop(storage);
+
+        let all_blocks_filter = Filter {
+            from_block: Some(api::BlockNumber::Number(1.into())),
+            to_block: Some(api::BlockNumber::Number(LAST_MINIBLOCK.into())),
+            ..Filter::default()
+        };
+        // Blooms are not used for trivial filters.
+        let all_logs = client.get_logs(all_blocks_filter.clone()).await?;
+        let all_events: Vec<_> = first_events.iter().chain(&last_events).collect();
+        assert_logs_match(&all_logs, &all_events);
+
+        let address_filter = Filter {
+            address: Some(Address::repeat_byte(23).into()),
+            ..all_blocks_filter.clone()
+        };
+        let address_logs = client.get_logs(address_filter).await?;
+        assert_logs_match(&address_logs, &[&last_events[0], &last_events[3]]);
+
+        let topics_filter = Filter {
+            topics: Some(vec![Some(H256::repeat_byte(42).into())]),
+            ..all_blocks_filter
+        };
+        let topics_logs = client.get_logs(topics_filter).await?;
+        assert_logs_match(&topics_logs, &[&last_events[1], &last_events[3]]);
+
+        // Blooms are not used for short ranges either.
+        let short_range_filter = Filter {
+            from_block: Some(api::BlockNumber::Number(1.into())),
+            to_block: Some(api::BlockNumber::Number(1.into())),
+            address: Some(Address::repeat_byte(23).into()),
+            ..Filter::default()
+        };
+        let short_range_logs = client.get_logs(short_range_filter).await?;
+        assert_logs_match(&short_range_logs, &[&first_events[0], &first_events[3]]);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_logs_with_blooms() {
+    test_http_server(GetLogsWithBloomsTest).await;
+}
+
 fn assert_not_implemented<T: Debug>(result: Result<T, Error>) {
     assert_matches!(result, Err(Error::Call(e)) => {
         assert_eq!(e.code(), ErrorCode::InternalError.code());
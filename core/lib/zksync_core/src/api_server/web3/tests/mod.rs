@@ -29,8 +29,11 @@ use zksync_types::{
         tx_execution_info::TxExecutionStatus, ExecutionMetrics, IncludedTxLocation,
         TransactionExecutionResult,
     },
-    utils::{storage_key_for_eth_balance, storage_key_for_standard_token_balance},
-    AccountTreeId, Address, L1BatchNumber, Nonce, StorageKey, StorageLog, VmEvent, H256, U64,
+    utils::{
+        nonces_to_full_nonce, storage_key_for_eth_balance, storage_key_for_standard_token_balance,
+    },
+    AccountTreeId, Address, Bytes, L1BatchNumber, Nonce, StorageKey, StorageLog, VmEvent, H256,
+    U64,
 };
 use zksync_utils::u256_to_h256;
 use zksync_web3_decl::{
@@ -42,9 +45,11 @@ use super::{metrics::ApiTransportLabel, *};
 use crate::{
     api_server::{
         execution_sandbox::testonly::MockTransactionExecutor,
+        tree::{TreeApiError, TreeEntryWithProof},
         tx_sender::tests::create_test_tx_sender,
     },
     genesis::{ensure_genesis_state, GenesisParams},
+    metadata_calculator::MerkleTreeInfo,
     utils::testonly::{
         create_l1_batch, create_l1_batch_metadata, create_l2_transaction, create_miniblock,
         l1_batch_metadata_to_commitment_artifacts, prepare_recovery_snapshot,
@@ -118,6 +123,7 @@ pub(crate) async fn spawn_http_server(
         None,
         tx_executor,
         method_tracer,
+        None,
         stop_receiver,
     )
     .await
@@ -137,11 +143,13 @@ async fn spawn_ws_server(
         websocket_requests_per_minute_limit,
         MockTransactionExecutor::default(),
         Arc::default(),
+        None,
         stop_receiver,
     )
     .await
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn spawn_server(
     transport: ApiTransportLabel,
     api_config: InternalApiConfig,
@@ -149,6 +157,7 @@ async fn spawn_server(
     websocket_requests_per_minute_limit: Option<NonZeroU32>,
     tx_executor: MockTransactionExecutor,
     method_tracer: Arc<MethodTracer>,
+    tree_api: Option<Arc<dyn TreeApiClient>>,
     stop_receiver: watch::Receiver<bool>,
 ) -> (ApiServerHandles, mpsc::UnboundedReceiver<PubSubEvent>) {
     let (tx_sender, vm_barrier) =
@@ -171,13 +180,17 @@ async fn spawn_server(
             builder
         }
     };
-    let server_handles = server_builder
+    let mut server_builder = server_builder
         .with_polling_interval(POLL_INTERVAL)
         .with_tx_sender(tx_sender)
         .with_vm_barrier(vm_barrier)
         .with_pub_sub_events(pub_sub_events_sender)
         .with_method_tracer(method_tracer)
-        .enable_api_namespaces(namespaces)
+        .enable_api_namespaces(namespaces);
+    if let Some(tree_api) = tree_api {
+        server_builder = server_builder.with_tree_api(tree_api);
+    }
+    let server_handles = server_builder
         .build()
         .expect("Unable to build API server")
         .run(stop_receiver)
@@ -201,6 +214,11 @@ trait HttpTest: Send + Sync {
         Arc::default()
     }
 
+    /// Tree API client used by the server. By default, the tree API is not configured.
+    fn tree_api(&self) -> Option<Arc<dyn TreeApiClient>> {
+        None
+    }
+
     async fn test(&self, client: &HttpClient, pool: &ConnectionPool) -> anyhow::Result<()>;
 
     /// Overrides the `filters_disabled` configuration parameter for HTTP server startup
@@ -283,11 +301,14 @@ async fn test_http_server(test: impl HttpTest) {
     let web3_config = Web3JsonRpcConfig::for_tests();
     let mut api_config = InternalApiConfig::new(&network_config, &web3_config, &contracts_config);
     api_config.filters_disabled = test.filters_disabled();
-    let mut server_handles = spawn_http_server(
+    let (mut server_handles, _) = spawn_server(
+        ApiTransportLabel::Http,
         api_config,
         pool.clone(),
+        None,
         test.transaction_executor(),
         test.method_tracer(),
+        test.tree_api(),
         stop_receiver,
     )
     .await;
@@ -956,6 +977,250 @@ async fn getting_all_account_balances() {
     test_http_server(AllAccountBalancesTest).await;
 }
 
+#[derive(Debug)]
+struct FeeHistoryTest;
+
+#[async_trait]
+impl HttpTest for FeeHistoryTest {
+    async fn test(&self, client: &HttpClient, pool: &ConnectionPool) -> anyhow::Result<()> {
+        let mut storage = pool.access_storage().await?;
+        let tx_results = [
+            execute_l2_transaction(create_l2_transaction(10, 200)),
+            execute_l2_transaction(create_l2_transaction(10, 200)),
+        ];
+        let miniblock = store_miniblock(&mut storage, MiniblockNumber(1), &tx_results).await?;
+        drop(storage);
+
+        let reward_percentiles = vec![10.0, 50.0, 90.0];
+        let history = client
+            .fee_history(
+                10.into(),
+                api::BlockNumber::Number(1.into()),
+                reward_percentiles,
+            )
+            .await?;
+        assert_eq!(
+            history.inner.oldest_block,
+            zksync_types::web3::types::BlockNumber::Number(0.into())
+        );
+        // Includes fees for miniblocks #0, #1 and the next miniblock.
+        assert_eq!(history.inner.base_fee_per_gas.len(), 3);
+        assert_eq!(
+            history.inner.base_fee_per_gas[1],
+            miniblock.base_fee_per_gas.into()
+        );
+        assert_eq!(history.inner.gas_used_ratio.len(), 2);
+        assert_eq!(history.inner.gas_used_ratio[0], 0.0);
+        assert!(history.inner.gas_used_ratio[1] > 0.0);
+        let reward = history.inner.reward.context("no rewards")?;
+        assert_eq!(reward, [vec![U256::zero(); 3], vec![U256::zero(); 3]]);
+        let expected_blob_fee = miniblock.batch_fee_input.fair_pubdata_price();
+        assert_eq!(history.base_fee_per_blob_gas.len(), 3);
+        assert_eq!(history.base_fee_per_blob_gas[1], expected_blob_fee.into());
+        assert_eq!(history.blob_gas_used_ratio, [0.0; 2]);
+
+        // Repeated request should be served (partially) from the cache and return the same data.
+        let cached_history = client
+            .fee_history(1.into(), api::BlockNumber::Number(1.into()), vec![])
+            .await?;
+        assert_eq!(
+            cached_history.inner.gas_used_ratio,
+            history.inner.gas_used_ratio[1..]
+        );
+
+        let err = client
+            .fee_history(10.into(), api::BlockNumber::Latest, vec![50.0, 10.0])
+            .await
+            .unwrap_err();
+        if let ClientError::Call(error) = err {
+            assert_eq!(error.code(), ErrorCode::InvalidParams.code());
+        } else {
+            panic!("Unexpected error: {err:?}");
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_fee_history() {
+    test_http_server(FeeHistoryTest).await;
+}
+
+#[derive(Debug)]
+struct ProofWithoutTreeDataTest;
+
+#[async_trait]
+impl HttpTest for ProofWithoutTreeDataTest {
+    async fn test(&self, client: &HttpClient, pool: &ConnectionPool) -> anyhow::Result<()> {
+        let mut storage = pool.access_storage().await?;
+        store_miniblock(&mut storage, MiniblockNumber(1), &[]).await?;
+        drop(storage);
+
+        // Miniblock #1 is not included into an L1 batch yet.
+        let block = api::BlockIdVariant::BlockNumber(api::BlockNumber::Number(1.into()));
+        let err = EthNamespaceClient::get_proof(
+            client,
+            Address::repeat_byte(1),
+            vec![H256::zero()],
+            block,
+        )
+        .await
+        .unwrap_err();
+        if let ClientError::Call(error) = err {
+            assert_eq!(error.code(), 6);
+            assert!(error.message().contains("block #1"), "{error:?}");
+        } else {
+            panic!("Unexpected error: {err:?}");
+        }
+
+        // The genesis L1 batch is processed, but the tree API is not configured for the test server.
+        let block = api::BlockIdVariant::BlockNumber(api::BlockNumber::Number(0.into()));
+        let err = EthNamespaceClient::get_proof(client, Address::repeat_byte(1), vec![], block)
+            .await
+            .unwrap_err();
+        if let ClientError::Call(error) = err {
+            assert_eq!(error.code(), 6);
+            assert!(error.message().contains("Tree API"), "{error:?}");
+        } else {
+            panic!("Unexpected error: {err:?}");
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_proof_without_tree_data() {
+    test_http_server(ProofWithoutTreeDataTest).await;
+}
+
+/// Tree API mock serving proofs for the genesis L1 batch. All entries share the same Merkle path.
+#[derive(Debug)]
+struct MockTreeApi {
+    entries: HashMap<U256, (H256, u64)>,
+}
+
+impl MockTreeApi {
+    fn merkle_path() -> Vec<H256> {
+        vec![H256::repeat_byte(0xaa), H256::repeat_byte(0xbb)]
+    }
+}
+
+#[async_trait]
+impl TreeApiClient for MockTreeApi {
+    async fn get_info(&self) -> Result<MerkleTreeInfo, TreeApiError> {
+        Err(TreeApiError::NotReady)
+    }
+
+    async fn get_proofs(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<Vec<TreeEntryWithProof>, TreeApiError> {
+        if l1_batch_number != L1BatchNumber(0) {
+            let err = anyhow::anyhow!("unexpected L1 batch #{l1_batch_number}");
+            return Err(TreeApiError::Internal(err));
+        }
+        let entries = hashed_keys.iter().map(|key| {
+            let (value, index) = self.entries.get(key).copied().unwrap_or_default();
+            TreeEntryWithProof {
+                value,
+                index,
+                merkle_path: Self::merkle_path(),
+            }
+        });
+        Ok(entries.collect())
+    }
+}
+
+#[derive(Debug)]
+struct ProofTest;
+
+impl ProofTest {
+    const SLOT: H256 = H256::zero();
+
+    fn address() -> Address {
+        Address::repeat_byte(1)
+    }
+
+    fn code_hash() -> H256 {
+        H256::repeat_byte(0xc0)
+    }
+}
+
+#[async_trait]
+impl HttpTest for ProofTest {
+    fn tree_api(&self) -> Option<Arc<dyn TreeApiClient>> {
+        let address = Self::address();
+        let full_nonce = nonces_to_full_nonce(3.into(), 2.into());
+        let entries = [
+            (get_nonce_key(&address), u256_to_h256(full_nonce), 10),
+            (
+                storage_key_for_eth_balance(&address),
+                u256_to_h256(1_000.into()),
+                11,
+            ),
+            (get_code_key(&address), Self::code_hash(), 12),
+            (
+                StorageKey::new(AccountTreeId::new(address), Self::SLOT),
+                u256_to_h256(42.into()),
+                13,
+            ),
+        ];
+        let entries = entries
+            .into_iter()
+            .map(|(key, value, index)| (key.hashed_key_u256(), (value, index)))
+            .collect();
+        Some(Arc::new(MockTreeApi { entries }))
+    }
+
+    async fn test(&self, client: &HttpClient, pool: &ConnectionPool) -> anyhow::Result<()> {
+        let genesis_root_hash = pool
+            .access_storage()
+            .await?
+            .blocks_dal()
+            .get_l1_batch_state_root(L1BatchNumber(0))
+            .await?
+            .context("no genesis root hash")?;
+
+        let block = api::BlockIdVariant::BlockNumber(api::BlockNumber::Number(0.into()));
+        let proof =
+            EthNamespaceClient::get_proof(client, Self::address(), vec![Self::SLOT], block).await?;
+        assert_eq!(proof.address, Self::address());
+        assert_eq!(proof.l1_batch_number, L1BatchNumber(0));
+        assert_eq!(proof.storage_hash, genesis_root_hash);
+        assert_eq!(proof.nonce, 3.into());
+        assert_eq!(proof.balance, 1_000.into());
+        assert_eq!(proof.code_hash, Self::code_hash());
+
+        let merkle_path = MockTreeApi::merkle_path();
+        assert_eq!(proof.account_proof.len(), 3);
+        for (account_proof, index) in proof.account_proof.iter().zip(10_u64..) {
+            let mut expected_proof = index.to_be_bytes().to_vec();
+            for hash in &merkle_path {
+                expected_proof.extend_from_slice(hash.as_bytes());
+            }
+            assert_eq!(account_proof.0, expected_proof);
+        }
+
+        assert_eq!(proof.storage_proof.len(), 1);
+        let storage_proof = &proof.storage_proof[0];
+        assert_eq!(storage_proof.key, Self::SLOT);
+        assert_eq!(storage_proof.value, 42.into());
+        assert_eq!(storage_proof.index, 13);
+        let expected_path: Vec<_> = merkle_path
+            .iter()
+            .map(|hash| Bytes(hash.as_bytes().to_vec()))
+            .collect();
+        assert_eq!(storage_proof.proof, expected_path);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_proof() {
+    test_http_server(ProofTest).await;
+}
+
 #[derive(Debug, Default)]
 struct RpcCallsTracingTest {
     tracer: Arc<MethodTracer>,
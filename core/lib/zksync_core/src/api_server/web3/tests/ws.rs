@@ -8,7 +8,9 @@ use reqwest::StatusCode;
 use tokio::sync::watch;
 use zksync_config::configs::chain::NetworkConfig;
 use zksync_dal::ConnectionPool;
-use zksync_types::{api, Address, L1BatchNumber, H256, U64};
+use zksync_types::{
+    aggregated_operations::AggregatedActionType, api, Address, L1BatchNumber, H256, U64,
+};
 use zksync_web3_decl::{
     jsonrpsee::{
         core::client::{Subscription, SubscriptionClientT},
@@ -108,10 +110,9 @@ async fn notifiers_start_after_snapshot_recovery() {
 
     let (stop_sender, stop_receiver) = watch::channel(false);
     let (events_sender, mut events_receiver) = mpsc::unbounded_channel();
-    let mut subscribe_logic = EthSubscribe::new();
+    let mut subscribe_logic = EthSubscribe::new(pool.clone());
     subscribe_logic.set_events_sender(events_sender);
-    let notifier_handles =
-        subscribe_logic.spawn_notifiers(pool.clone(), POLL_INTERVAL, stop_receiver);
+    let notifier_handles = subscribe_logic.spawn_notifiers(POLL_INTERVAL, stop_receiver);
     assert!(!notifier_handles.is_empty());
 
     // Wait a little doing nothing and check that notifier tasks are still active (i.e., have not panicked).
@@ -132,6 +133,7 @@ async fn notifiers_start_after_snapshot_recovery() {
             SubscriptionType::Blocks,
             SubscriptionType::Txs,
             SubscriptionType::Logs,
+            SubscriptionType::L1Batches,
         ],
     )
     .await;
@@ -316,6 +318,105 @@ async fn basic_subscriptions_after_snapshot_recovery() {
     .await;
 }
 
+#[derive(Debug)]
+struct L1BatchSubscriptionsTest;
+
+#[async_trait]
+impl WsTest for L1BatchSubscriptionsTest {
+    async fn test(
+        &self,
+        client: &WsClient,
+        pool: &ConnectionPool,
+        mut pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        wait_for_notifiers(&mut pub_sub_events, &[SubscriptionType::L1Batches]).await;
+
+        let mut storage = pool.access_storage().await?;
+        let tx_result = execute_l2_transaction(create_l2_transaction(1, 2));
+        let tx_hash = tx_result.hash;
+        store_miniblock(&mut storage, MiniblockNumber(1), &[tx_result]).await?;
+
+        let params = rpc_params!["l1Batches"];
+        let mut l1_batches_subscription = client
+            .subscribe::<api::L1BatchStatusUpdate, _>("zks_subscribe", params, "zks_unsubscribe")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::L1Batches).await;
+        let params = rpc_params![
+            "transactionFinality",
+            serde_json::json!({ "transactionHash": tx_hash })
+        ];
+        let mut tx_subscription = client
+            .subscribe::<api::TransactionFinalityUpdate, _>(
+                "zks_subscribe",
+                params,
+                "zks_unsubscribe",
+            )
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::TxFinality).await;
+
+        seal_l1_batch(&mut storage, L1BatchNumber(1)).await?;
+        let expected_update = api::L1BatchStatusUpdate {
+            l1_batch_number: L1BatchNumber(1),
+            stage: api::L1BatchStage::Sealed,
+            l1_tx_hash: None,
+        };
+        let update = tokio::time::timeout(TEST_TIMEOUT, l1_batches_subscription.next())
+            .await
+            .context("Timed out waiting for L1 batch update")?
+            .context("L1 batches subscription terminated")??;
+        assert_eq!(update, expected_update);
+        let tx_update = tokio::time::timeout(TEST_TIMEOUT, tx_subscription.next())
+            .await
+            .context("Timed out waiting for transaction update")?
+            .context("Transaction finality subscription terminated")??;
+        assert_eq!(tx_update.transaction_hash, tx_hash);
+        assert_eq!(tx_update.l1_batch, expected_update);
+
+        let actions = [
+            (AggregatedActionType::Commit, api::L1BatchStage::Committed),
+            (
+                AggregatedActionType::PublishProofOnchain,
+                api::L1BatchStage::Proven,
+            ),
+            (AggregatedActionType::Execute, api::L1BatchStage::Executed),
+        ];
+        for (i, (action, stage)) in actions.into_iter().enumerate() {
+            let l1_tx_hash = H256::repeat_byte(i as u8 + 1);
+            storage
+                .eth_sender_dal()
+                .insert_bogus_confirmed_eth_tx(
+                    L1BatchNumber(1),
+                    action,
+                    l1_tx_hash,
+                    chrono::Utc::now(),
+                )
+                .await?;
+            let expected_update = api::L1BatchStatusUpdate {
+                l1_batch_number: L1BatchNumber(1),
+                stage,
+                l1_tx_hash: Some(l1_tx_hash),
+            };
+
+            let update = tokio::time::timeout(TEST_TIMEOUT, l1_batches_subscription.next())
+                .await
+                .context("Timed out waiting for L1 batch update")?
+                .context("L1 batches subscription terminated")??;
+            assert_eq!(update, expected_update);
+            let tx_update = tokio::time::timeout(TEST_TIMEOUT, tx_subscription.next())
+                .await
+                .context("Timed out waiting for transaction update")?
+                .context("Transaction finality subscription terminated")??;
+            assert_eq!(tx_update.l1_batch, expected_update);
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn l1_batch_subscriptions() {
+    test_ws_server(L1BatchSubscriptionsTest).await;
+}
+
 #[derive(Debug)]
 struct LogSubscriptionsTest {
     snapshot_recovery: bool,
@@ -0,0 +1,418 @@
+//! Loading of base system contracts (the bootloader and the default account) used by the server.
+//!
+//! By default, base system contracts are read from `ZKSYNC_HOME`. A chain operator may override them
+//! by specifying a directory or an object store key with custom bytecodes in [`ContractsConfig`].
+
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
+
+use anyhow::Context as _;
+use zksync_config::ContractsConfig;
+use zksync_contracts::{BaseSystemContracts, BaseSystemContractsHashes};
+use zksync_dal::StorageProcessor;
+use zksync_object_store::ObjectStore;
+use zksync_types::{
+    system_contracts::BaseSystemContractsBytecodes, MiniblockNumber, ProtocolVersionId, H256,
+};
+use zksync_utils::{
+    be_words_to_bytes,
+    bytecode::{hash_bytecode, validate_bytecode},
+};
+
+/// Name of the bootloader bytecode file in the custom base system contracts directory.
+pub const BOOTLOADER_FILE_NAME: &str = "bootloader.zbin";
+/// Name of the default account bytecode file in the custom base system contracts directory.
+pub const DEFAULT_AA_FILE_NAME: &str = "default_aa.zbin";
+
+#[derive(Debug)]
+enum BaseSystemContractsSource {
+    /// Contracts built into the server (i.e., read from `ZKSYNC_HOME`).
+    Default,
+    Dir(PathBuf),
+    ObjectStore {
+        store: Arc<dyn ObjectStore>,
+        key: String,
+    },
+}
+
+/// Loader of base system contracts based on [`ContractsConfig`].
+#[derive(Debug)]
+pub struct BaseSystemContractsLoader {
+    source: BaseSystemContractsSource,
+    expected_bootloader_hash: Option<H256>,
+    expected_default_aa_hash: Option<H256>,
+}
+
+impl BaseSystemContractsLoader {
+    /// Creates a loader based on the provided config. `object_store` is only used if the config
+    /// specifies an object store key for contracts.
+    pub fn new(
+        config: &ContractsConfig,
+        object_store: Option<Arc<dyn ObjectStore>>,
+    ) -> anyhow::Result<Self> {
+        let source = match (
+            &config.base_system_contracts_dir,
+            &config.base_system_contracts_object_key,
+        ) {
+            (Some(_), Some(_)) => anyhow::bail!(
+                "`base_system_contracts_dir` and `base_system_contracts_object_key` are mutually exclusive"
+            ),
+            (Some(dir), None) => BaseSystemContractsSource::Dir(dir.into()),
+            (None, Some(key)) => BaseSystemContractsSource::ObjectStore {
+                store: object_store.context(
+                    "object store is required to load base system contracts by `base_system_contracts_object_key`",
+                )?,
+                key: key.clone(),
+            },
+            (None, None) => BaseSystemContractsSource::Default,
+        };
+
+        Ok(Self {
+            source,
+            expected_bootloader_hash: config.bootloader_hash,
+            expected_default_aa_hash: config.default_aa_hash,
+        })
+    }
+
+    /// Checks whether the loader provides custom (i.e., not built-in) base system contracts.
+    pub fn is_custom(&self) -> bool {
+        !matches!(self.source, BaseSystemContractsSource::Default)
+    }
+
+    /// Loads base system contracts and checks their hashes against the expected ones, if any.
+    pub async fn load(&self) -> anyhow::Result<BaseSystemContracts> {
+        let bytecodes = match &self.source {
+            BaseSystemContractsSource::Default => {
+                let contracts = BaseSystemContracts::load_from_disk();
+                return self.check_hashes(contracts.hashes()).map(|()| contracts);
+            }
+            BaseSystemContractsSource::Dir(dir) => {
+                let bootloader_path = dir.join(BOOTLOADER_FILE_NAME);
+                let bootloader = tokio::fs::read(&bootloader_path).await.with_context(|| {
+                    format!("failed reading bootloader from {bootloader_path:?}")
+                })?;
+                let default_aa_path = dir.join(DEFAULT_AA_FILE_NAME);
+                let default_aa = tokio::fs::read(&default_aa_path).await.with_context(|| {
+                    format!("failed reading default account from {default_aa_path:?}")
+                })?;
+                BaseSystemContractsBytecodes {
+                    bootloader,
+                    default_aa,
+                }
+            }
+            BaseSystemContractsSource::ObjectStore { store, key } => store
+                .get(key.as_str())
+                .await
+                .with_context(|| format!("failed loading base system contracts `{key}`"))?,
+        };
+
+        validate_bytecode(&bytecodes.bootloader).context("bootloader bytecode is invalid")?;
+        validate_bytecode(&bytecodes.default_aa).context("default account bytecode is invalid")?;
+        let hashes = BaseSystemContractsHashes {
+            bootloader: hash_bytecode(&bytecodes.bootloader),
+            default_aa: hash_bytecode(&bytecodes.default_aa),
+        };
+        self.check_hashes(hashes)?;
+        tracing::info!(
+            "Loaded custom base system contracts from {:?}: {hashes:?}",
+            self.source
+        );
+
+        Ok(BaseSystemContracts::from_bytecodes(
+            bytecodes.bootloader,
+            bytecodes.default_aa,
+        ))
+    }
+
+    fn check_hashes(&self, hashes: BaseSystemContractsHashes) -> anyhow::Result<()> {
+        if let Some(expected) = self.expected_bootloader_hash {
+            anyhow::ensure!(
+                hashes.bootloader == expected,
+                "bootloader hash mismatch: expected {expected:?}, got {:?}",
+                hashes.bootloader
+            );
+        }
+        if let Some(expected) = self.expected_default_aa_hash {
+            anyhow::ensure!(
+                hashes.default_aa == expected,
+                "default account hash mismatch: expected {expected:?}, got {:?}",
+                hashes.default_aa
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Checks that `contracts` correspond to the protocol version currently used by the node, or to one of the protocol
+/// upgrades already saved to Postgres. Makes sure that their bytecodes are present among factory dependencies
+/// for all these versions, so that the state keeper can load them.
+pub async fn ensure_base_system_contracts_for_protocol_version(
+    storage: &mut StorageProcessor<'_>,
+    contracts: &BaseSystemContracts,
+) -> anyhow::Result<()> {
+    let active_version_id = storage.protocol_versions_dal().last_used_version_id().await;
+    let mut version_ids = storage.protocol_versions_dal().all_version_ids().await;
+    version_ids.retain(|&id| active_version_id.map_or(true, |active_id| id >= active_id));
+    version_ids.sort_unstable();
+
+    let mut matching_version_ids = vec![];
+    for version_id in version_ids {
+        if ensure_contracts_for_version(storage, contracts, version_id).await? {
+            matching_version_ids.push(version_id);
+        }
+    }
+
+    let hashes = contracts.hashes();
+    anyhow::ensure!(
+        !matching_version_ids.is_empty(),
+        "configured base system contracts {hashes:?} do not match base system contracts for the active \
+         protocol version {active_version_id:?} or any of the subsequent protocol versions"
+    );
+    tracing::info!(
+        "Configured base system contracts {hashes:?} are used by protocol versions {matching_version_ids:?}"
+    );
+    Ok(())
+}
+
+/// Tracks protocol versions saved to Postgres (e.g., by `eth_watch` on protocol upgrades) and makes sure that
+/// custom base system contracts are present among factory deps for all versions using them.
+#[derive(Debug)]
+pub struct CustomBaseSystemContractsTracker {
+    contracts: BaseSystemContracts,
+    last_checked_version_id: Option<ProtocolVersionId>,
+}
+
+impl CustomBaseSystemContractsTracker {
+    pub fn new(contracts: BaseSystemContracts) -> Self {
+        Self {
+            contracts,
+            last_checked_version_id: None,
+        }
+    }
+
+    /// Checks protocol versions added since the last call.
+    pub async fn check_new_versions(
+        &mut self,
+        storage: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<()> {
+        let Some(last_version_id) = storage.protocol_versions_dal().last_version_id().await else {
+            return Ok(());
+        };
+        if self.last_checked_version_id == Some(last_version_id) {
+            return Ok(());
+        }
+
+        let mut version_ids = storage.protocol_versions_dal().all_version_ids().await;
+        version_ids.retain(|&id| {
+            self.last_checked_version_id
+                .map_or(true, |checked| id > checked)
+        });
+        version_ids.sort_unstable();
+        for version_id in version_ids {
+            if ensure_contracts_for_version(storage, &self.contracts, version_id).await? {
+                tracing::info!(
+                    "Custom base system contracts {:?} are used by protocol version {version_id:?}",
+                    self.contracts.hashes()
+                );
+            }
+        }
+        self.last_checked_version_id = Some(last_version_id);
+        Ok(())
+    }
+}
+
+/// Returns `false` if `contracts` are not used by the specified protocol version. Otherwise, inserts their bytecodes
+/// into factory deps unless they are already present.
+async fn ensure_contracts_for_version(
+    storage: &mut StorageProcessor<'_>,
+    contracts: &BaseSystemContracts,
+    version_id: ProtocolVersionId,
+) -> anyhow::Result<bool> {
+    let version = storage
+        .protocol_versions_dal()
+        .get_protocol_version(version_id)
+        .await
+        .with_context(|| format!("protocol version {version_id:?} disappeared from Postgres"))?;
+    if version.base_system_contracts_hashes != contracts.hashes() {
+        return Ok(false);
+    }
+
+    let mut factory_deps = HashMap::new();
+    for contract in [&contracts.bootloader, &contracts.default_aa] {
+        let existing_dep = storage
+            .factory_deps_dal()
+            .get_factory_dep(contract.hash)
+            .await
+            .with_context(|| format!("failed loading factory dep {:?}", contract.hash))?;
+        if existing_dep.is_none() {
+            factory_deps.insert(contract.hash, be_words_to_bytes(&contract.code));
+        }
+    }
+    if factory_deps.is_empty() {
+        return Ok(true);
+    }
+
+    tracing::info!(
+        "Inserting base system contracts {:?} for protocol version {version_id:?} to Postgres",
+        factory_deps.keys().collect::<Vec<_>>()
+    );
+    storage
+        .factory_deps_dal()
+        .insert_factory_deps(MiniblockNumber(0), &factory_deps)
+        .await
+        .context("failed inserting base system contracts to Postgres")?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_dal::ConnectionPool;
+    use zksync_object_store::ObjectStoreFactory;
+    use zksync_types::{protocol_version::ProtocolVersion, L2ChainId};
+
+    use super::*;
+    use crate::genesis::{ensure_genesis_state, GenesisParams};
+
+    fn config_with_dir(dir: &str) -> ContractsConfig {
+        ContractsConfig {
+            base_system_contracts_dir: Some(dir.to_owned()),
+            ..ContractsConfig::for_tests()
+        }
+    }
+
+    fn mock_bytecodes() -> BaseSystemContractsBytecodes {
+        BaseSystemContractsBytecodes {
+            bootloader: vec![1; 32],
+            default_aa: vec![2; 32],
+        }
+    }
+
+    #[tokio::test]
+    async fn loading_contracts_from_dir() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let bytecodes = mock_bytecodes();
+        std::fs::write(
+            temp_dir.path().join(BOOTLOADER_FILE_NAME),
+            &bytecodes.bootloader,
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join(DEFAULT_AA_FILE_NAME),
+            &bytecodes.default_aa,
+        )
+        .unwrap();
+
+        let config = config_with_dir(temp_dir.path().to_str().unwrap());
+        let loader = BaseSystemContractsLoader::new(&config, None).unwrap();
+        assert!(loader.is_custom());
+        let contracts = loader.load().await.unwrap();
+        assert_eq!(
+            contracts.bootloader.hash,
+            hash_bytecode(&bytecodes.bootloader)
+        );
+        assert_eq!(
+            contracts.default_aa.hash,
+            hash_bytecode(&bytecodes.default_aa)
+        );
+
+        let config = ContractsConfig {
+            bootloader_hash: Some(H256::repeat_byte(0xff)),
+            ..config
+        };
+        let loader = BaseSystemContractsLoader::new(&config, None).unwrap();
+        let err = loader.load().await.unwrap_err().to_string();
+        assert!(err.contains("bootloader hash mismatch"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn loading_contracts_from_object_store() {
+        let object_store = ObjectStoreFactory::mock().create_store().await;
+        let bytecodes = mock_bytecodes();
+        object_store.put("custom", &bytecodes).await.unwrap();
+
+        let config = ContractsConfig {
+            base_system_contracts_object_key: Some("custom".to_owned()),
+            default_aa_hash: Some(hash_bytecode(&bytecodes.default_aa)),
+            ..ContractsConfig::for_tests()
+        };
+        assert!(BaseSystemContractsLoader::new(&config, None).is_err());
+
+        let loader = BaseSystemContractsLoader::new(&config, Some(object_store)).unwrap();
+        let contracts = loader.load().await.unwrap();
+        assert_eq!(
+            contracts.bootloader.hash,
+            hash_bytecode(&bytecodes.bootloader)
+        );
+    }
+
+    #[tokio::test]
+    async fn loading_invalid_bytecode() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join(BOOTLOADER_FILE_NAME), [1; 31]).unwrap();
+        std::fs::write(temp_dir.path().join(DEFAULT_AA_FILE_NAME), [2; 32]).unwrap();
+
+        let config = config_with_dir(temp_dir.path().to_str().unwrap());
+        let loader = BaseSystemContractsLoader::new(&config, None).unwrap();
+        let err = loader.load().await.unwrap_err().to_string();
+        assert!(err.contains("bootloader bytecode is invalid"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn ensuring_contracts_for_protocol_upgrade() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        ensure_genesis_state(&mut storage, L2ChainId::default(), &GenesisParams::mock())
+            .await
+            .unwrap();
+
+        let bytecodes = mock_bytecodes();
+        let contracts =
+            BaseSystemContracts::from_bytecodes(bytecodes.bootloader, bytecodes.default_aa);
+        let err = ensure_base_system_contracts_for_protocol_version(&mut storage, &contracts)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("do not match"), "{err}");
+
+        let mut tracker = CustomBaseSystemContractsTracker::new(contracts.clone());
+        tracker.check_new_versions(&mut storage).await.unwrap();
+        let bootloader_dep = storage
+            .factory_deps_dal()
+            .get_factory_dep(contracts.bootloader.hash)
+            .await
+            .unwrap();
+        assert_eq!(bootloader_dep, None);
+
+        // Emulate a protocol upgrade using the custom contracts.
+        storage
+            .protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion {
+                id: ProtocolVersionId::next(),
+                base_system_contracts_hashes: contracts.hashes(),
+                ..ProtocolVersion::default()
+            })
+            .await;
+        tracker.check_new_versions(&mut storage).await.unwrap();
+        for contract in [&contracts.bootloader, &contracts.default_aa] {
+            let dep = storage
+                .factory_deps_dal()
+                .get_factory_dep(contract.hash)
+                .await
+                .unwrap();
+            assert_eq!(dep, Some(be_words_to_bytes(&contract.code)));
+        }
+        // Repeated checks are no-ops.
+        tracker.check_new_versions(&mut storage).await.unwrap();
+
+        // The upgrade is not active yet, but the custom contracts are used by a pending protocol version.
+        ensure_base_system_contracts_for_protocol_version(&mut storage, &contracts)
+            .await
+            .unwrap();
+        // Built-in contracts are still used by the active protocol version.
+        ensure_base_system_contracts_for_protocol_version(
+            &mut storage,
+            &BaseSystemContracts::load_from_disk(),
+        )
+        .await
+        .unwrap();
+    }
+}
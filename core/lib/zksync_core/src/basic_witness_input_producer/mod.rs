@@ -6,7 +6,7 @@ use multivm::interface::{L2BlockEnv, VmInterface};
 use tokio::{runtime::Handle, task::JoinHandle};
 use vm_utils::{create_vm, execute_tx};
 use zksync_dal::{basic_witness_input_producer_dal::JOB_MAX_ATTEMPT, ConnectionPool};
-use zksync_object_store::{ObjectStore, ObjectStoreFactory};
+use zksync_object_store::ObjectStore;
 use zksync_queued_job_processor::JobProcessor;
 use zksync_types::{witness_block_state::WitnessBlockState, L1BatchNumber, L2ChainId};
 
@@ -26,16 +26,16 @@ pub struct BasicWitnessInputProducer {
 }
 
 impl BasicWitnessInputProducer {
-    pub async fn new(
+    pub fn new(
         connection_pool: ConnectionPool,
-        store_factory: &ObjectStoreFactory,
+        object_store: Arc<dyn ObjectStore>,
         l2_chain_id: L2ChainId,
-    ) -> anyhow::Result<Self> {
-        Ok(BasicWitnessInputProducer {
+    ) -> Self {
+        Self {
             connection_pool,
-            object_store: store_factory.create_store().await,
+            object_store,
             l2_chain_id,
-        })
+        }
     }
 
     fn process_job_impl(
@@ -1,5 +1,6 @@
 use std::{fmt, sync::Arc};
 
+use zksync_config::configs::eth_watch::{L1BlockSelection, L1BlockTag};
 use zksync_contracts::verifier_contract;
 use zksync_eth_client::{CallFunctionArgs, Error as EthClientError, EthInterface};
 use zksync_types::{
@@ -39,12 +40,16 @@ pub trait EthClient: 'static + fmt::Debug + Send + Sync {
         to: BlockNumber,
         retries_left: usize,
     ) -> Result<Vec<Log>, Error>;
-    /// Returns finalized L1 block number.
+    /// Returns the number of the latest L1 block to be processed, according to the configured
+    /// [block selection rule](L1BlockSelection).
     async fn finalized_block_number(&self) -> Result<u64, Error>;
+    /// Returns the hash of the L1 block with the specified number, or `None` if the block is not present on L1.
+    async fn block_hash(&self, number: u64) -> Result<Option<H256>, Error>;
     /// Returns scheduler verification key hash by verifier address.
     async fn scheduler_vk_hash(&self, verifier_address: Address) -> Result<H256, Error>;
-    /// Sets list of topics to return events for.
-    fn set_topics(&mut self, topics: Vec<H256>);
+    /// Sets contract addresses and topics to return events for. Events matching any of the addresses
+    /// and any of the topics (as the first topic) will be returned.
+    fn set_event_filter(&mut self, addresses: Vec<Address>, topics: Vec<H256>);
 }
 
 pub const RETRY_LIMIT: usize = 5;
@@ -54,34 +59,20 @@ const TOO_MANY_RESULTS_ALCHEMY: &str = "response size exceeded";
 #[derive(Debug)]
 pub struct EthHttpQueryClient {
     client: Arc<dyn EthInterface>,
+    addresses: Vec<Address>,
     topics: Vec<H256>,
-    zksync_contract_addr: Address,
-    /// Address of the `Governance` contract. It's optional because it is present only for post-boojum chains.
-    /// If address is some then client will listen to events coming from it.
-    governance_address: Option<Address>,
     verifier_contract_abi: Contract,
-    confirmations_for_eth_event: Option<u64>,
+    block_selection: L1BlockSelection,
 }
 
 impl EthHttpQueryClient {
-    pub fn new(
-        client: Arc<dyn EthInterface>,
-        zksync_contract_addr: Address,
-        governance_address: Option<Address>,
-        confirmations_for_eth_event: Option<u64>,
-    ) -> Self {
-        tracing::debug!(
-            "New eth client, zkSync addr: {:x}, governance addr: {:?}",
-            zksync_contract_addr,
-            governance_address
-        );
+    pub fn new(client: Arc<dyn EthInterface>, block_selection: L1BlockSelection) -> Self {
         Self {
             client,
+            addresses: Vec::new(),
             topics: Vec::new(),
-            zksync_contract_addr,
-            governance_address,
             verifier_contract_abi: verifier_contract(),
-            confirmations_for_eth_event,
+            block_selection,
         }
     }
 
@@ -92,13 +83,7 @@ impl EthHttpQueryClient {
         topics: Vec<H256>,
     ) -> Result<Vec<Log>, Error> {
         let filter = FilterBuilder::default()
-            .address(
-                [Some(self.zksync_contract_addr), self.governance_address]
-                    .iter()
-                    .flatten()
-                    .copied()
-                    .collect(),
-            )
+            .address(self.addresses.clone())
             .from_block(from)
             .to_block(to)
             .topics(Some(topics), None, None, None)
@@ -201,24 +186,37 @@ impl EthClient for EthHttpQueryClient {
     }
 
     async fn finalized_block_number(&self) -> Result<u64, Error> {
-        if let Some(confirmations) = self.confirmations_for_eth_event {
-            let latest_block_number = self.client.block_number("watch").await?.as_u64();
-            Ok(latest_block_number.saturating_sub(confirmations))
-        } else {
-            self.client
-                .block(BlockId::Number(BlockNumber::Finalized), "watch")
-                .await
-                .map_err(Into::into)
-                .map(|res| {
-                    res.expect("Finalized block must be present on L1")
-                        .number
-                        .expect("Finalized block must contain number")
-                        .as_u64()
-                })
-        }
+        let tag = match self.block_selection {
+            L1BlockSelection::Confirmations(confirmations) => {
+                let latest_block_number = self.client.block_number("watch").await?.as_u64();
+                return Ok(latest_block_number.saturating_sub(confirmations));
+            }
+            L1BlockSelection::Tag(L1BlockTag::Finalized) => BlockNumber::Finalized,
+            L1BlockSelection::Tag(L1BlockTag::Safe) => BlockNumber::Safe,
+        };
+        self.client
+            .block(BlockId::Number(tag), "watch")
+            .await
+            .map_err(Into::into)
+            .map(|res| {
+                res.unwrap_or_else(|| panic!("{tag:?} block must be present on L1"))
+                    .number
+                    .unwrap_or_else(|| panic!("{tag:?} block must contain number"))
+                    .as_u64()
+            })
+    }
+
+    async fn block_hash(&self, number: u64) -> Result<Option<H256>, Error> {
+        let block = self
+            .client
+            .block(BlockId::Number(BlockNumber::Number(number.into())), "watch")
+            .await?;
+        Ok(block.and_then(|block| block.hash))
     }
 
-    fn set_topics(&mut self, topics: Vec<H256>) {
+    fn set_event_filter(&mut self, addresses: Vec<Address>, topics: Vec<H256>) {
+        tracing::debug!("Watching events for addresses {addresses:?} with topics {topics:?}");
+        self.addresses = addresses;
         self.topics = topics;
     }
 }
@@ -14,6 +14,8 @@ use crate::eth_watch::{
 /// Listens to operation events coming from the governance contract and saves new protocol upgrade proposals to the database.
 #[derive(Debug)]
 pub struct GovernanceUpgradesEventProcessor {
+    governance_address: Address,
+    /// Address of the diamond proxy. Used to filter out operations not targeting the diamond proxy.
     diamond_proxy_address: Address,
     /// Last protocol version seen. Used to skip events for already known upgrade proposals.
     last_seen_version_id: ProtocolVersionId,
@@ -22,11 +24,13 @@ pub struct GovernanceUpgradesEventProcessor {
 
 impl GovernanceUpgradesEventProcessor {
     pub fn new(
+        governance_address: Address,
         diamond_proxy_address: Address,
         last_seen_version_id: ProtocolVersionId,
         governance_contract: &Contract,
     ) -> Self {
         Self {
+            governance_address,
             diamond_proxy_address,
             last_seen_version_id,
             upgrade_proposal_signature: governance_contract
@@ -39,6 +43,10 @@ impl GovernanceUpgradesEventProcessor {
 
 #[async_trait::async_trait]
 impl EventProcessor for GovernanceUpgradesEventProcessor {
+    fn name(&self) -> &'static str {
+        "governance_upgrades"
+    }
+
     async fn process_events(
         &mut self,
         storage: &mut StorageProcessor<'_>,
@@ -114,7 +122,11 @@ impl EventProcessor for GovernanceUpgradesEventProcessor {
         Ok(())
     }
 
-    fn relevant_topic(&self) -> H256 {
-        self.upgrade_proposal_signature
+    fn relevant_addresses(&self) -> Vec<Address> {
+        vec![self.governance_address]
+    }
+
+    fn relevant_topics(&self) -> Vec<H256> {
+        vec![self.upgrade_proposal_signature]
     }
 }
@@ -1,7 +1,7 @@
 use std::fmt;
 
 use zksync_dal::StorageProcessor;
-use zksync_types::{web3::types::Log, H256};
+use zksync_types::{web3::types::Log, Address, H256};
 
 use crate::eth_watch::client::{Error, EthClient};
 
@@ -9,9 +9,24 @@ pub mod governance_upgrades;
 pub mod priority_ops;
 pub mod upgrades;
 
+/// Processor of L1 events emitted by one or more contracts.
+///
+/// Each processor tracks its own progress: the last L1 block it has successfully processed is persisted
+/// in Postgres keyed by [`Self::name()`]. Since progress is saved after the events are processed,
+/// the same events may be delivered to the processor more than once (e.g., after a restart),
+/// so processing must be idempotent.
+///
+/// If the Ethereum watcher detects an L1 reorg affecting blocks already processed by the processor,
+/// it calls [`Self::rollback()`] and then re-delivers events starting from the last valid L1 block.
 #[async_trait::async_trait]
 pub trait EventProcessor: 'static + fmt::Debug + Send + Sync {
-    /// Processes given events
+    /// Unique name of the processor. Used as a key for the persisted processing progress,
+    /// so it must not change between restarts.
+    fn name(&self) -> &'static str;
+
+    /// Processes given events. Events are guaranteed to be emitted by one of
+    /// [relevant addresses](Self::relevant_addresses()) and to have one of
+    /// [relevant topics](Self::relevant_topics()) as the first topic.
     async fn process_events(
         &mut self,
         storage: &mut StorageProcessor<'_>,
@@ -19,6 +34,22 @@ pub trait EventProcessor: 'static + fmt::Debug + Send + Sync {
         events: Vec<Log>,
     ) -> Result<(), Error>;
 
-    /// Relevant topic which defines what events to be processed
-    fn relevant_topic(&self) -> H256;
+    /// Addresses of contracts emitting events to be processed.
+    fn relevant_addresses(&self) -> Vec<Address>;
+
+    /// Relevant topics which define what events to be processed.
+    fn relevant_topics(&self) -> Vec<H256>;
+
+    /// Reverts effects of events emitted in L1 blocks after `last_valid_l1_block`, which were reorged.
+    /// Called inside a DB transaction.
+    ///
+    /// The default implementation does nothing, which is appropriate for processors that skip
+    /// already processed events.
+    async fn rollback(
+        &mut self,
+        _storage: &mut StorageProcessor<'_>,
+        _last_valid_l1_block: u64,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
 }
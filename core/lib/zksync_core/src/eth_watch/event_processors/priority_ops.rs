@@ -1,14 +1,15 @@
 use std::convert::TryFrom;
 
+use anyhow::Context as _;
 use zksync_contracts::zksync_contract;
 use zksync_dal::StorageProcessor;
-use zksync_types::{l1::L1Tx, web3::types::Log, PriorityOpId, H256};
+use zksync_types::{l1::L1Tx, web3::types::Log, Address, L1BlockNumber, PriorityOpId, H256};
 
 use crate::{
     eth_watch::{
         client::{Error, EthClient},
         event_processors::EventProcessor,
-        metrics::{PollStage, METRICS},
+        metrics::{PollStage, ReorgedPriorityOpStatus, METRICS},
     },
     metrics::{TxStage, APP_METRICS},
 };
@@ -16,13 +17,15 @@ use crate::{
 /// Responsible for saving new priority L1 transactions to the database.
 #[derive(Debug)]
 pub struct PriorityOpsEventProcessor {
+    diamond_proxy_address: Address,
     next_expected_priority_id: PriorityOpId,
     new_priority_request_signature: H256,
 }
 
 impl PriorityOpsEventProcessor {
-    pub fn new(next_expected_priority_id: PriorityOpId) -> Self {
+    pub fn new(diamond_proxy_address: Address, next_expected_priority_id: PriorityOpId) -> Self {
         Self {
+            diamond_proxy_address,
             next_expected_priority_id,
             new_priority_request_signature: zksync_contract()
                 .event("NewPriorityRequest")
@@ -34,6 +37,10 @@ impl PriorityOpsEventProcessor {
 
 #[async_trait::async_trait]
 impl EventProcessor for PriorityOpsEventProcessor {
+    fn name(&self) -> &'static str {
+        "priority_ops"
+    }
+
     async fn process_events(
         &mut self,
         storage: &mut StorageProcessor<'_>,
@@ -99,7 +106,61 @@ impl EventProcessor for PriorityOpsEventProcessor {
         Ok(())
     }
 
-    fn relevant_topic(&self) -> H256 {
-        self.new_priority_request_signature
+    fn relevant_addresses(&self) -> Vec<Address> {
+        vec![self.diamond_proxy_address]
+    }
+
+    fn relevant_topics(&self) -> Vec<H256> {
+        vec![self.new_priority_request_signature]
+    }
+
+    async fn rollback(
+        &mut self,
+        storage: &mut StorageProcessor<'_>,
+        last_valid_l1_block: u64,
+    ) -> anyhow::Result<()> {
+        let last_valid_l1_block = L1BlockNumber(
+            last_valid_l1_block
+                .try_into()
+                .context("L1 block number overflow")?,
+        );
+        let rollback = storage
+            .transactions_dal()
+            .rollback_priority_ops(last_valid_l1_block)
+            .await
+            .context("failed rolling back priority ops")?;
+
+        if !rollback.removed.is_empty() {
+            tracing::warn!(
+                "Removed priority ops {:?} received in reorged L1 blocks after #{last_valid_l1_block}",
+                rollback.removed
+            );
+            METRICS.reorged_priority_ops[&ReorgedPriorityOpStatus::Removed]
+                .inc_by(rollback.removed.len() as u64);
+        }
+        if !rollback.in_mempool.is_empty() {
+            tracing::error!(
+                "Priority ops {:?} received in reorged L1 blocks after #{last_valid_l1_block} are already \
+                 loaded into the mempool and cannot be removed; they may be executed even if they are no longer on L1",
+                rollback.in_mempool
+            );
+            METRICS.reorged_priority_ops[&ReorgedPriorityOpStatus::InMempool]
+                .inc_by(rollback.in_mempool.len() as u64);
+        }
+        if !rollback.executed.is_empty() {
+            tracing::error!(
+                "Priority ops {:?} received in reorged L1 blocks after #{last_valid_l1_block} are already executed",
+                rollback.executed
+            );
+            METRICS.reorged_priority_ops[&ReorgedPriorityOpStatus::Executed]
+                .inc_by(rollback.executed.len() as u64);
+        }
+
+        self.next_expected_priority_id = storage
+            .transactions_dal()
+            .last_priority_id()
+            .await
+            .map_or(PriorityOpId(0), |id| id + 1);
+        Ok(())
     }
 }
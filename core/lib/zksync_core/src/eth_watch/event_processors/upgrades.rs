@@ -1,7 +1,7 @@
 use std::convert::TryFrom;
 
 use zksync_dal::StorageProcessor;
-use zksync_types::{web3::types::Log, ProtocolUpgrade, ProtocolVersionId, H256};
+use zksync_types::{web3::types::Log, Address, ProtocolUpgrade, ProtocolVersionId, H256};
 
 use crate::eth_watch::{
     client::{Error, EthClient},
@@ -17,12 +17,14 @@ pub(crate) const UPGRADE_PROPOSAL_SIGNATURE: H256 = H256([
 /// Responsible for saving new protocol upgrade proposals to the database.
 #[derive(Debug)]
 pub struct UpgradesEventProcessor {
+    diamond_proxy_address: Address,
     last_seen_version_id: ProtocolVersionId,
 }
 
 impl UpgradesEventProcessor {
-    pub fn new(last_seen_version_id: ProtocolVersionId) -> Self {
+    pub fn new(diamond_proxy_address: Address, last_seen_version_id: ProtocolVersionId) -> Self {
         Self {
+            diamond_proxy_address,
             last_seen_version_id,
         }
     }
@@ -30,6 +32,10 @@ impl UpgradesEventProcessor {
 
 #[async_trait::async_trait]
 impl EventProcessor for UpgradesEventProcessor {
+    fn name(&self) -> &'static str {
+        "upgrades"
+    }
+
     async fn process_events(
         &mut self,
         storage: &mut StorageProcessor<'_>,
@@ -83,7 +89,11 @@ impl EventProcessor for UpgradesEventProcessor {
         Ok(())
     }
 
-    fn relevant_topic(&self) -> H256 {
-        UPGRADE_PROPOSAL_SIGNATURE
+    fn relevant_addresses(&self) -> Vec<Address> {
+        vec![self.diamond_proxy_address]
+    }
+
+    fn relevant_topics(&self) -> Vec<H256> {
+        vec![UPGRADE_PROPOSAL_SIGNATURE]
     }
 }
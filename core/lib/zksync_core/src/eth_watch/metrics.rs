@@ -2,7 +2,10 @@
 
 use std::time::Duration;
 
-use vise::{Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Histogram, Metrics};
+use vise::{
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, LabeledFamily,
+    Metrics,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
 #[metrics(label = "stage", rename_all = "snake_case")]
@@ -12,6 +15,18 @@ pub(super) enum PollStage {
     PersistUpgrades,
 }
 
+/// Status of a priority operation received in a reorged L1 block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "status", rename_all = "snake_case")]
+pub(super) enum ReorgedPriorityOpStatus {
+    /// Operation was removed from the storage.
+    Removed,
+    /// Operation was loaded into the mempool and could not be removed.
+    InMempool,
+    /// Operation was already executed.
+    Executed,
+}
+
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "server_eth_watch")]
 pub(super) struct EthWatcherMetrics {
@@ -20,6 +35,14 @@ pub(super) struct EthWatcherMetrics {
     pub poll_eth_node: Family<PollStage, Histogram<Duration>>,
     #[metrics(buckets = Buckets::LATENCIES)]
     pub get_priority_op_events: Histogram<Duration>,
+    /// Last L1 block processed by each event processor.
+    #[metrics(labels = ["processor"])]
+    pub last_processed_l1_block: LabeledFamily<&'static str, Gauge<u64>>,
+    /// Number of detected L1 reorgs affecting processed L1 blocks.
+    pub l1_reorgs: Counter,
+    /// Number of priority operations received in reorged L1 blocks. Operations that couldn't be removed
+    /// (i.e., with a status other than `removed`) require manual investigation.
+    pub reorged_priority_ops: Family<ReorgedPriorityOpStatus, Counter>,
 }
 
 #[vise::register]
@@ -1,14 +1,21 @@
 //! Ethereum watcher polls the Ethereum node for PriorityQueue events.
-//! New events are accepted to the zkSync network once they have the sufficient amount of L1 confirmations.
+//! New events are accepted to the zkSync network once they have the sufficient amount of L1 confirmations,
+//! or once their block is covered by the configured L1 block tag (`finalized` or `safe`).
 //!
 //! Poll interval is configured using the `ETH_POLL_INTERVAL` constant.
 //! Number of confirmations is configured using the `CONFIRMATIONS_FOR_ETH_EVENT` environment variable.
+//!
+//! After each poll, the watcher saves a checkpoint: the hash of the latest processed L1 block. Since L1 block hashes
+//! commit to all previous blocks, a matching checkpoint hash verifies the entire processed range. If the latest
+//! checkpoint doesn't match L1, the watcher looks for the latest matching checkpoint and rolls back event processors
+//! to it.
 
 use std::{sync::Arc, time::Duration};
 
+use anyhow::Context as _;
 use tokio::{sync::watch, task::JoinHandle};
 use zksync_config::ETHWatchConfig;
-use zksync_dal::{ConnectionPool, StorageProcessor};
+use zksync_dal::{eth_watch_dal::L1Checkpoint, ConnectionPool, StorageProcessor};
 use zksync_eth_client::EthInterface;
 use zksync_system_constants::PRIORITY_EXPIRATION;
 use zksync_types::{
@@ -16,21 +23,25 @@ use zksync_types::{
     ProtocolVersionId,
 };
 
+pub use self::event_processors::EventProcessor;
 use self::{
-    client::{Error, EthClient, EthHttpQueryClient, RETRY_LIMIT},
+    client::{EthClient, EthHttpQueryClient, RETRY_LIMIT},
     event_processors::{
         governance_upgrades::GovernanceUpgradesEventProcessor,
-        priority_ops::PriorityOpsEventProcessor, upgrades::UpgradesEventProcessor, EventProcessor,
+        priority_ops::PriorityOpsEventProcessor, upgrades::UpgradesEventProcessor,
     },
     metrics::{PollStage, METRICS},
 };
 
 pub mod client;
-mod event_processors;
+pub mod event_processors;
 mod metrics;
 #[cfg(test)]
 mod tests;
 
+/// Number of the latest L1 checkpoints retained in Postgres. Limits the depth of L1 reorgs that can be handled.
+const RETAINED_L1_CHECKPOINTS: usize = 128;
+
 #[derive(Debug)]
 struct EthWatchState {
     last_seen_version_id: ProtocolVersionId,
@@ -38,21 +49,38 @@ struct EthWatchState {
     last_processed_ethereum_block: u64,
 }
 
+/// Event processor together with its processing progress.
+#[derive(Debug)]
+struct ProcessorWithCursor {
+    processor: Box<dyn EventProcessor>,
+    /// Last L1 block processed by the processor. `None` if the progress is not loaded from Postgres yet.
+    last_processed_l1_block: Option<u64>,
+}
+
+impl ProcessorWithCursor {
+    fn new(processor: Box<dyn EventProcessor>) -> Self {
+        Self {
+            processor,
+            last_processed_l1_block: None,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct EthWatch {
     client: Box<dyn EthClient>,
     poll_interval: Duration,
-    event_processors: Vec<Box<dyn EventProcessor>>,
-
-    last_processed_ethereum_block: u64,
+    event_processors: Vec<ProcessorWithCursor>,
+    /// L1 block to start from for processors without persisted progress.
+    default_start_l1_block: u64,
     pool: ConnectionPool,
 }
 
 impl EthWatch {
     pub async fn new(
         diamond_proxy_address: Address,
-        governance_contract: Option<Contract>,
-        mut client: Box<dyn EthClient>,
+        governance: Option<(Contract, Address)>,
+        client: Box<dyn EthClient>,
         pool: ConnectionPool,
         poll_interval: Duration,
     ) -> Self {
@@ -65,35 +93,77 @@ impl EthWatch {
         drop(storage);
 
         let priority_ops_processor =
-            PriorityOpsEventProcessor::new(state.next_expected_priority_id);
-        let upgrades_processor = UpgradesEventProcessor::new(state.last_seen_version_id);
-        let mut event_processors: Vec<Box<dyn EventProcessor>> = vec![
+            PriorityOpsEventProcessor::new(diamond_proxy_address, state.next_expected_priority_id);
+        let upgrades_processor =
+            UpgradesEventProcessor::new(diamond_proxy_address, state.last_seen_version_id);
+        let mut builtin_processors: Vec<Box<dyn EventProcessor>> = vec![
             Box::new(priority_ops_processor),
             Box::new(upgrades_processor),
         ];
 
-        if let Some(governance_contract) = governance_contract {
+        if let Some((governance_contract, governance_address)) = governance {
             let governance_upgrades_processor = GovernanceUpgradesEventProcessor::new(
+                governance_address,
                 diamond_proxy_address,
                 state.last_seen_version_id,
                 &governance_contract,
             );
-            event_processors.push(Box::new(governance_upgrades_processor))
+            builtin_processors.push(Box::new(governance_upgrades_processor));
         }
-
-        let topics = event_processors
-            .iter()
-            .map(|p| p.relevant_topic())
-            .collect();
-        client.set_topics(topics);
-
-        Self {
+        // Built-in processors have distinct names, so there's no need to check for duplicates.
+        let mut this = Self {
             client,
             poll_interval,
-            event_processors,
-            last_processed_ethereum_block: state.last_processed_ethereum_block,
+            event_processors: builtin_processors
+                .into_iter()
+                .map(ProcessorWithCursor::new)
+                .collect(),
+            default_start_l1_block: state.last_processed_ethereum_block,
             pool,
+        };
+        this.update_event_filter();
+        this
+    }
+
+    /// Adds a custom event processor. Events for all processors are fetched from L1 together,
+    /// but the progress of each processor is tracked separately.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a processor with the same [name](EventProcessor::name()) is already added.
+    pub fn with_event_processor(
+        mut self,
+        processor: Box<dyn EventProcessor>,
+    ) -> anyhow::Result<Self> {
+        let name = processor.name();
+        anyhow::ensure!(
+            self.event_processors
+                .iter()
+                .all(|existing| existing.processor.name() != name),
+            "Event processor `{name}` is added twice"
+        );
+        self.event_processors
+            .push(ProcessorWithCursor::new(processor));
+        self.update_event_filter();
+        Ok(self)
+    }
+
+    fn update_event_filter(&mut self) {
+        let mut addresses = Vec::new();
+        let mut topics = Vec::new();
+        for ProcessorWithCursor { processor, .. } in &self.event_processors {
+            for address in processor.relevant_addresses() {
+                if !addresses.contains(&address) {
+                    addresses.push(address);
+                }
+            }
+            for topic in processor.relevant_topics() {
+                if !topics.contains(&topic) {
+                    topics.push(topic);
+                }
+            }
         }
+        self.client.set_event_filter(addresses, topics);
     }
 
     async fn initialize_state(
@@ -150,41 +220,239 @@ impl EthWatch {
             let mut storage = pool.access_storage_tagged("eth_watch").await.unwrap();
             if let Err(error) = self.loop_iteration(&mut storage).await {
                 // This is an error because otherwise we could potentially miss a priority operation
-                // thus entering priority mode, which is not desired.
-                tracing::error!("Failed to process new blocks {}", error);
-                self.last_processed_ethereum_block =
-                    Self::initialize_state(&*self.client, &mut storage)
-                        .await
-                        .last_processed_ethereum_block;
+                // thus entering priority mode, which is not desired. Progress of the failed processors
+                // is not advanced, so the events will be re-processed on the next iteration.
+                tracing::error!("Failed to process new blocks: {error:#}");
+            }
+        }
+        Ok(())
+    }
+
+    /// Loads progress for processors that don't have it loaded yet.
+    async fn load_cursors(&mut self, storage: &mut StorageProcessor<'_>) -> anyhow::Result<()> {
+        for ProcessorWithCursor {
+            processor,
+            last_processed_l1_block,
+        } in &mut self.event_processors
+        {
+            if last_processed_l1_block.is_some() {
+                continue;
+            }
+            let name = processor.name();
+            let cursor = storage
+                .eth_watch_dal()
+                .get_processor_cursor(name)
+                .await
+                .with_context(|| format!("failed loading progress for event processor `{name}`"))?;
+            let cursor = cursor.unwrap_or(self.default_start_l1_block);
+            tracing::info!("Event processor `{name}` starts from L1 block {cursor}");
+            *last_processed_l1_block = Some(cursor);
+        }
+        Ok(())
+    }
+
+    /// Checks whether the latest L1 checkpoint is reorged. If it is, returns the latest L1 block
+    /// with a valid checkpoint.
+    async fn detect_l1_reorg(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Option<u64>> {
+        let checkpoints = storage
+            .eth_watch_dal()
+            .get_latest_l1_checkpoints(RETAINED_L1_CHECKPOINTS)
+            .await
+            .context("failed loading L1 checkpoints")?;
+        let mut checkpoints = checkpoints.into_iter();
+        let Some(latest_checkpoint) = checkpoints.next() else {
+            return Ok(None);
+        };
+        if self.is_checkpoint_valid(&latest_checkpoint).await? {
+            return Ok(None);
+        }
+
+        METRICS.l1_reorgs.inc();
+        tracing::warn!(
+            "L1 reorg detected: hash of L1 block #{} differs from the one processed",
+            latest_checkpoint.l1_block_number
+        );
+        for checkpoint in checkpoints {
+            if self.is_checkpoint_valid(&checkpoint).await? {
+                tracing::info!(
+                    "Latest L1 block not affected by the reorg: #{}",
+                    checkpoint.l1_block_number
+                );
+                return Ok(Some(checkpoint.l1_block_number));
+            }
+        }
+        anyhow::bail!(
+            "L1 reorg is deeper than all {RETAINED_L1_CHECKPOINTS} retained checkpoints; manual intervention is required"
+        )
+    }
+
+    async fn is_checkpoint_valid(&self, checkpoint: &L1Checkpoint) -> anyhow::Result<bool> {
+        let l1_hash = self.client.block_hash(checkpoint.l1_block_number).await?;
+        Ok(l1_hash == Some(checkpoint.l1_block_hash))
+    }
+
+    /// Rolls back all processors that have processed L1 blocks after `last_valid_l1_block`.
+    async fn rollback(
+        &mut self,
+        storage: &mut StorageProcessor<'_>,
+        last_valid_l1_block: u64,
+    ) -> anyhow::Result<()> {
+        let mut transaction = storage
+            .start_transaction()
+            .await
+            .context("failed starting DB transaction")?;
+        for ProcessorWithCursor {
+            processor,
+            last_processed_l1_block,
+        } in &mut self.event_processors
+        {
+            let cursor = last_processed_l1_block.expect("progress is loaded before rollback");
+            if cursor <= last_valid_l1_block {
+                continue;
+            }
+
+            let name = processor.name();
+            tracing::info!(
+                "Rolling back event processor `{name}` from L1 block #{cursor} to #{last_valid_l1_block}"
+            );
+            processor
+                .rollback(&mut transaction, last_valid_l1_block)
+                .await
+                .with_context(|| format!("failed rolling back event processor `{name}`"))?;
+            transaction
+                .eth_watch_dal()
+                .set_processor_cursor(name, last_valid_l1_block)
+                .await
+                .with_context(|| format!("failed saving progress for event processor `{name}`"))?;
+        }
+        transaction
+            .eth_watch_dal()
+            .remove_l1_checkpoints_after(last_valid_l1_block)
+            .await
+            .context("failed removing reorged L1 checkpoints")?;
+        transaction
+            .commit()
+            .await
+            .context("failed committing DB transaction")?;
+
+        for processor in &mut self.event_processors {
+            if let Some(cursor) = &mut processor.last_processed_l1_block {
+                *cursor = (*cursor).min(last_valid_l1_block);
             }
         }
         Ok(())
     }
 
     #[tracing::instrument(skip(self, storage))]
-    async fn loop_iteration(&mut self, storage: &mut StorageProcessor<'_>) -> Result<(), Error> {
+    async fn loop_iteration(&mut self, storage: &mut StorageProcessor<'_>) -> anyhow::Result<()> {
+        self.load_cursors(storage).await?;
+        if let Some(last_valid_l1_block) = self.detect_l1_reorg(storage).await? {
+            self.rollback(storage, last_valid_l1_block).await?;
+        }
+
         let stage_latency = METRICS.poll_eth_node[&PollStage::Request].start();
         let to_block = self.client.finalized_block_number().await?;
-        if to_block <= self.last_processed_ethereum_block {
+        let from_block = self
+            .event_processors
+            .iter()
+            .filter_map(|processor| processor.last_processed_l1_block)
+            .filter(|&cursor| cursor < to_block)
+            .min();
+        let Some(from_block) = from_block else {
             return Ok(());
-        }
+        };
 
+        let to_block_hash = self
+            .client
+            .block_hash(to_block)
+            .await?
+            .with_context(|| format!("L1 block #{to_block} is missing"))?;
         let events = self
             .client
             .get_events(
-                Web3BlockNumber::Number(self.last_processed_ethereum_block.into()),
+                Web3BlockNumber::Number(from_block.into()),
                 Web3BlockNumber::Number(to_block.into()),
                 RETRY_LIMIT,
             )
             .await?;
+        // If `to_block` was reorged while fetching events, the events may belong to different chains.
+        let to_block_hash_after_fetch = self.client.block_hash(to_block).await?;
+        anyhow::ensure!(
+            to_block_hash_after_fetch == Some(to_block_hash),
+            "L1 block #{to_block} was reorged while fetching events"
+        );
         stage_latency.observe();
 
-        for processor in self.event_processors.iter_mut() {
-            processor
-                .process_events(storage, &*self.client, events.clone())
-                .await?;
+        // The checkpoint must be saved before progress of any processor is advanced, so that
+        // it's possible to detect a reorg of the processed blocks.
+        let checkpoint = L1Checkpoint {
+            l1_block_number: to_block,
+            l1_block_hash: to_block_hash,
+        };
+        let mut dal = storage.eth_watch_dal();
+        dal.insert_l1_checkpoint(checkpoint)
+            .await
+            .context("failed saving L1 checkpoint")?;
+        dal.prune_l1_checkpoints(RETAINED_L1_CHECKPOINTS)
+            .await
+            .context("failed pruning L1 checkpoints")?;
+
+        let mut failed_processors = vec![];
+        for ProcessorWithCursor {
+            processor,
+            last_processed_l1_block,
+        } in &mut self.event_processors
+        {
+            let cursor = last_processed_l1_block.expect("progress is loaded above");
+            if cursor >= to_block {
+                continue;
+            }
+
+            let addresses = processor.relevant_addresses();
+            let topics = processor.relevant_topics();
+            let processor_events = events
+                .iter()
+                .filter(|event| {
+                    addresses.contains(&event.address)
+                        && event
+                            .topics
+                            .first()
+                            .map_or(false, |topic| topics.contains(topic))
+                        && event
+                            .block_number
+                            .map_or(true, |number| number.as_u64() >= cursor)
+                })
+                .cloned()
+                .collect();
+
+            let name = processor.name();
+            let result = processor
+                .process_events(storage, &*self.client, processor_events)
+                .await;
+            if let Err(err) = result {
+                tracing::error!(
+                    "Event processor `{name}` failed processing L1 blocks {cursor}..={to_block}: {err}"
+                );
+                failed_processors.push(name);
+                continue;
+            }
+
+            storage
+                .eth_watch_dal()
+                .set_processor_cursor(name, to_block)
+                .await
+                .with_context(|| format!("failed saving progress for event processor `{name}`"))?;
+            *last_processed_l1_block = Some(to_block);
+            METRICS.last_processed_l1_block[&name].set(to_block);
         }
-        self.last_processed_ethereum_block = to_block;
+
+        anyhow::ensure!(
+            failed_processors.is_empty(),
+            "event processors {failed_processors:?} failed processing L1 events"
+        );
         Ok(())
     }
 }
@@ -197,16 +465,11 @@ pub async fn start_eth_watch(
     governance: (Contract, Address),
     stop_receiver: watch::Receiver<bool>,
 ) -> anyhow::Result<JoinHandle<anyhow::Result<()>>> {
-    let eth_client = EthHttpQueryClient::new(
-        eth_gateway,
-        diamond_proxy_addr,
-        Some(governance.1),
-        config.confirmations_for_eth_event,
-    );
+    let eth_client = EthHttpQueryClient::new(eth_gateway, config.l1_block_selection());
 
     let eth_watch = EthWatch::new(
         diamond_proxy_addr,
-        Some(governance.0),
+        Some(governance),
         Box::new(eth_client),
         pool,
         config.poll_interval(),
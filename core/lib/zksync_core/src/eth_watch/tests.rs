@@ -1,4 +1,11 @@
-use std::{collections::HashMap, convert::TryInto, sync::Arc};
+use std::{
+    collections::HashMap,
+    convert::TryInto,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
 
 use tokio::sync::RwLock;
 use zksync_contracts::{governance_contract, zksync_contract};
@@ -15,13 +22,19 @@ use zksync_types::{
 use super::client::Error;
 use crate::eth_watch::{
     client::EthClient, event_processors::upgrades::UPGRADE_PROPOSAL_SIGNATURE, EthWatch,
+    EventProcessor,
 };
 
+const DIAMOND_PROXY_ADDRESS: Address = Address::repeat_byte(0x1);
+const GOVERNANCE_ADDRESS: Address = Address::repeat_byte(0x2);
+
 #[derive(Debug)]
 struct FakeEthClientData {
     transactions: HashMap<u64, Vec<Log>>,
     diamond_upgrades: HashMap<u64, Vec<Log>>,
     governance_upgrades: HashMap<u64, Vec<Log>>,
+    other_events: HashMap<u64, Vec<Log>>,
+    reorged_block_hashes: HashMap<u64, H256>,
     last_finalized_block_number: u64,
 }
 
@@ -31,6 +44,8 @@ impl FakeEthClientData {
             transactions: Default::default(),
             diamond_upgrades: Default::default(),
             governance_upgrades: Default::default(),
+            other_events: Default::default(),
+            reorged_block_hashes: Default::default(),
             last_finalized_block_number: 0,
         }
     }
@@ -63,9 +78,42 @@ impl FakeEthClientData {
         }
     }
 
+    fn add_other_events(&mut self, events: &[Log]) {
+        for event in events {
+            let eth_block = event.block_number.unwrap().as_u64();
+            self.other_events
+                .entry(eth_block)
+                .or_default()
+                .push(event.clone());
+        }
+    }
+
     fn set_last_finalized_block_number(&mut self, number: u64) {
         self.last_finalized_block_number = number;
     }
+
+    /// Emulates an L1 reorg: removes all events starting from `first_block` and changes hashes of these blocks.
+    fn reorg(&mut self, first_block: u64) {
+        for events in [
+            &mut self.transactions,
+            &mut self.diamond_upgrades,
+            &mut self.governance_upgrades,
+            &mut self.other_events,
+        ] {
+            events.retain(|&number, _| number < first_block);
+        }
+        for number in first_block..=self.last_finalized_block_number {
+            self.reorged_block_hashes
+                .insert(number, H256::from_low_u64_le(number));
+        }
+    }
+
+    fn block_hash(&self, number: u64) -> H256 {
+        self.reorged_block_hashes
+            .get(&number)
+            .copied()
+            .unwrap_or_else(|| H256::from_low_u64_be(number))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -92,6 +140,14 @@ impl FakeEthClient {
         self.inner.write().await.add_governance_upgrades(upgrades);
     }
 
+    async fn add_other_events(&mut self, events: &[Log]) {
+        self.inner.write().await.add_other_events(events);
+    }
+
+    async fn reorg(&mut self, first_block: u64) {
+        self.inner.write().await.reorg(first_block);
+    }
+
     async fn set_last_finalized_block_number(&mut self, number: u64) {
         self.inner
             .write()
@@ -132,11 +188,14 @@ impl EthClient for FakeEthClient {
             if let Some(ops) = self.inner.read().await.governance_upgrades.get(&number) {
                 logs.extend_from_slice(ops);
             }
+            if let Some(events) = self.inner.read().await.other_events.get(&number) {
+                logs.extend_from_slice(events);
+            }
         }
         Ok(logs)
     }
 
-    fn set_topics(&mut self, _topics: Vec<Hash>) {}
+    fn set_event_filter(&mut self, _addresses: Vec<Address>, _topics: Vec<Hash>) {}
 
     async fn scheduler_vk_hash(&self, _verifier_address: Address) -> Result<H256, Error> {
         Ok(H256::zero())
@@ -145,6 +204,11 @@ impl EthClient for FakeEthClient {
     async fn finalized_block_number(&self) -> Result<u64, Error> {
         Ok(self.inner.read().await.last_finalized_block_number)
     }
+
+    async fn block_hash(&self, number: u64) -> Result<Option<H256>, Error> {
+        let inner = self.inner.read().await;
+        Ok((number <= inner.last_finalized_block_number).then(|| inner.block_hash(number)))
+    }
 }
 
 fn build_l1_tx(serial_id: u64, eth_block: u64) -> L1Tx {
@@ -207,7 +271,7 @@ async fn test_normal_operation_l1_txs() {
 
     let mut client = FakeEthClient::new();
     let mut watcher = EthWatch::new(
-        Address::default(),
+        DIAMOND_PROXY_ADDRESS,
         None,
         Box::new(client.clone()),
         connection_pool.clone(),
@@ -255,7 +319,7 @@ async fn test_normal_operation_upgrades() {
 
     let mut client = FakeEthClient::new();
     let mut watcher = EthWatch::new(
-        Address::default(),
+        DIAMOND_PROXY_ADDRESS,
         None,
         Box::new(client.clone()),
         connection_pool.clone(),
@@ -316,7 +380,7 @@ async fn test_gap_in_upgrades() {
 
     let mut client = FakeEthClient::new();
     let mut watcher = EthWatch::new(
-        Address::default(),
+        DIAMOND_PROXY_ADDRESS,
         None,
         Box::new(client.clone()),
         connection_pool.clone(),
@@ -355,8 +419,8 @@ async fn test_normal_operation_governance_upgrades() {
 
     let mut client = FakeEthClient::new();
     let mut watcher = EthWatch::new(
-        Address::default(),
-        Some(governance_contract()),
+        DIAMOND_PROXY_ADDRESS,
+        Some((governance_contract(), GOVERNANCE_ADDRESS)),
         Box::new(client.clone()),
         connection_pool.clone(),
         std::time::Duration::from_nanos(1),
@@ -417,7 +481,7 @@ async fn test_gap_in_single_batch() {
 
     let mut client = FakeEthClient::new();
     let mut watcher = EthWatch::new(
-        Address::default(),
+        DIAMOND_PROXY_ADDRESS,
         None,
         Box::new(client.clone()),
         connection_pool.clone(),
@@ -447,7 +511,7 @@ async fn test_gap_between_batches() {
 
     let mut client = FakeEthClient::new();
     let mut watcher = EthWatch::new(
-        Address::default(),
+        DIAMOND_PROXY_ADDRESS,
         None,
         Box::new(client.clone()),
         connection_pool.clone(),
@@ -482,7 +546,7 @@ async fn test_overlapping_batches() {
 
     let mut client = FakeEthClient::new();
     let mut watcher = EthWatch::new(
-        Address::default(),
+        DIAMOND_PROXY_ADDRESS,
         None,
         Box::new(client.clone()),
         connection_pool.clone(),
@@ -523,6 +587,351 @@ async fn test_overlapping_batches() {
     assert_eq!(tx.common_data.serial_id.0, 4);
 }
 
+const CUSTOM_CONTRACT_ADDRESS: Address = Address::repeat_byte(0x33);
+const CUSTOM_EVENT_TOPIC: H256 = H256::repeat_byte(0x44);
+
+/// Event processor recording all received events.
+#[derive(Debug, Clone)]
+struct RecordingEventProcessor {
+    received_events: Arc<Mutex<Vec<Log>>>,
+    should_fail: Arc<AtomicBool>,
+}
+
+impl RecordingEventProcessor {
+    fn new() -> Self {
+        Self {
+            received_events: Arc::default(),
+            should_fail: Arc::default(),
+        }
+    }
+
+    fn received_blocks(&self) -> Vec<u64> {
+        let events = self.received_events.lock().unwrap();
+        events
+            .iter()
+            .map(|event| event.block_number.unwrap().as_u64())
+            .collect()
+    }
+}
+
+#[async_trait::async_trait]
+impl EventProcessor for RecordingEventProcessor {
+    fn name(&self) -> &'static str {
+        "recording"
+    }
+
+    async fn process_events(
+        &mut self,
+        _storage: &mut StorageProcessor<'_>,
+        _client: &dyn EthClient,
+        events: Vec<Log>,
+    ) -> Result<(), Error> {
+        if self.should_fail.load(Ordering::Relaxed) {
+            return Err(Error::LogParse("processing failed".to_owned()));
+        }
+        self.received_events.lock().unwrap().extend(events);
+        Ok(())
+    }
+
+    fn relevant_addresses(&self) -> Vec<Address> {
+        vec![CUSTOM_CONTRACT_ADDRESS]
+    }
+
+    fn relevant_topics(&self) -> Vec<H256> {
+        vec![CUSTOM_EVENT_TOPIC]
+    }
+}
+
+fn custom_event_log(address: Address, topic: H256, eth_block: u64) -> Log {
+    Log {
+        address,
+        topics: vec![topic],
+        data: Vec::new().into(),
+        block_hash: Some(H256::repeat_byte(0x11)),
+        block_number: Some(eth_block.into()),
+        transaction_hash: Some(H256::random()),
+        transaction_index: Some(0u64.into()),
+        log_index: Some(0u64.into()),
+        transaction_log_index: Some(0u64.into()),
+        log_type: None,
+        removed: None,
+    }
+}
+
+#[tokio::test]
+async fn custom_event_processor() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    setup_db(&connection_pool).await;
+
+    let mut client = FakeEthClient::new();
+    let processor = RecordingEventProcessor::new();
+    let mut watcher = EthWatch::new(
+        DIAMOND_PROXY_ADDRESS,
+        None,
+        Box::new(client.clone()),
+        connection_pool.clone(),
+        std::time::Duration::from_nanos(1),
+    )
+    .await
+    .with_event_processor(Box::new(processor.clone()))
+    .unwrap();
+
+    let mut storage = connection_pool.access_storage().await.unwrap();
+    client.add_transactions(&[build_l1_tx(0, 10)]).await;
+    client
+        .add_other_events(&[
+            custom_event_log(CUSTOM_CONTRACT_ADDRESS, CUSTOM_EVENT_TOPIC, 5),
+            // Events with a matching topic emitted by another contract must be ignored.
+            custom_event_log(DIAMOND_PROXY_ADDRESS, CUSTOM_EVENT_TOPIC, 6),
+            // Events from the watched contract with another topic must be ignored.
+            custom_event_log(CUSTOM_CONTRACT_ADDRESS, H256::repeat_byte(0x55), 7),
+            custom_event_log(CUSTOM_CONTRACT_ADDRESS, CUSTOM_EVENT_TOPIC, 18),
+        ])
+        .await;
+    client.set_last_finalized_block_number(15).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+
+    assert_eq!(processor.received_blocks(), [5]);
+    // Built-in processors must not be affected by the custom one.
+    assert_eq!(get_all_db_txs(&mut storage).await.len(), 1);
+    for name in ["priority_ops", "upgrades", "recording"] {
+        let cursor = storage
+            .eth_watch_dal()
+            .get_processor_cursor(name)
+            .await
+            .unwrap();
+        assert_eq!(cursor, Some(15), "{name}");
+    }
+
+    client.set_last_finalized_block_number(20).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(processor.received_blocks(), [5, 18]);
+
+    // Emulate a restart; the processor should continue from the persisted progress.
+    let processor = RecordingEventProcessor::new();
+    let mut watcher = EthWatch::new(
+        DIAMOND_PROXY_ADDRESS,
+        None,
+        Box::new(client.clone()),
+        connection_pool.clone(),
+        std::time::Duration::from_nanos(1),
+    )
+    .await
+    .with_event_processor(Box::new(processor.clone()))
+    .unwrap();
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert!(processor.received_blocks().is_empty());
+}
+
+#[tokio::test]
+async fn failing_event_processor_does_not_block_others() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    setup_db(&connection_pool).await;
+
+    let mut client = FakeEthClient::new();
+    let processor = RecordingEventProcessor::new();
+    processor.should_fail.store(true, Ordering::Relaxed);
+    let mut watcher = EthWatch::new(
+        DIAMOND_PROXY_ADDRESS,
+        None,
+        Box::new(client.clone()),
+        connection_pool.clone(),
+        std::time::Duration::from_nanos(1),
+    )
+    .await
+    .with_event_processor(Box::new(processor.clone()))
+    .unwrap();
+
+    let mut storage = connection_pool.access_storage().await.unwrap();
+    client.add_transactions(&[build_l1_tx(0, 10)]).await;
+    client
+        .add_other_events(&[custom_event_log(
+            CUSTOM_CONTRACT_ADDRESS,
+            CUSTOM_EVENT_TOPIC,
+            12,
+        )])
+        .await;
+    client.set_last_finalized_block_number(15).await;
+    watcher.loop_iteration(&mut storage).await.unwrap_err();
+
+    assert_eq!(get_all_db_txs(&mut storage).await.len(), 1);
+    let cursor = storage
+        .eth_watch_dal()
+        .get_processor_cursor("priority_ops")
+        .await
+        .unwrap();
+    assert_eq!(cursor, Some(15));
+    let cursor = storage
+        .eth_watch_dal()
+        .get_processor_cursor("recording")
+        .await
+        .unwrap();
+    assert_eq!(cursor, None);
+
+    // The failed events should be re-delivered once the processor recovers.
+    processor.should_fail.store(false, Ordering::Relaxed);
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(processor.received_blocks(), [12]);
+    let cursor = storage
+        .eth_watch_dal()
+        .get_processor_cursor("recording")
+        .await
+        .unwrap();
+    assert_eq!(cursor, Some(15));
+}
+
+#[tokio::test]
+async fn duplicate_event_processor_names() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    setup_db(&connection_pool).await;
+
+    let client = FakeEthClient::new();
+    let processor = RecordingEventProcessor::new();
+    let err = EthWatch::new(
+        DIAMOND_PROXY_ADDRESS,
+        None,
+        Box::new(client),
+        connection_pool.clone(),
+        std::time::Duration::from_nanos(1),
+    )
+    .await
+    .with_event_processor(Box::new(processor.clone()))
+    .unwrap()
+    .with_event_processor(Box::new(processor))
+    .unwrap_err();
+    assert!(err.to_string().contains("added twice"), "{err}");
+}
+
+async fn get_l1_txs(storage: &mut StorageProcessor<'_>) -> Vec<L1Tx> {
+    let db_txs = get_all_db_txs(storage).await;
+    let mut db_txs: Vec<L1Tx> = db_txs
+        .into_iter()
+        .map(|tx| tx.try_into().unwrap())
+        .collect();
+    db_txs.sort_by_key(|tx| tx.common_data.serial_id);
+    db_txs
+}
+
+#[tokio::test]
+async fn rolling_back_priority_ops_after_l1_reorg() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    setup_db(&connection_pool).await;
+
+    let mut client = FakeEthClient::new();
+    let mut watcher = EthWatch::new(
+        DIAMOND_PROXY_ADDRESS,
+        None,
+        Box::new(client.clone()),
+        connection_pool.clone(),
+        std::time::Duration::from_nanos(1),
+    )
+    .await;
+
+    let mut storage = connection_pool.access_storage().await.unwrap();
+    client
+        .add_transactions(&[build_l1_tx(0, 10), build_l1_tx(1, 14)])
+        .await;
+    client.set_last_finalized_block_number(11).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    client.set_last_finalized_block_number(15).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    let last_priority_id = storage.transactions_dal().last_priority_id().await;
+    assert_eq!(last_priority_id, Some(PriorityOpId(1)));
+
+    // The second priority op is moved to another L1 block by the reorg.
+    client.reorg(12).await;
+    client.add_transactions(&[build_l1_tx(1, 16)]).await;
+    client.set_last_finalized_block_number(20).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+
+    let db_txs = get_l1_txs(&mut storage).await;
+    assert_eq!(db_txs.len(), 2);
+    assert_eq!(db_txs[1].common_data.serial_id, PriorityOpId(1));
+    assert_eq!(db_txs[1].common_data.eth_block, 16);
+    let cursor = storage
+        .eth_watch_dal()
+        .get_processor_cursor("priority_ops")
+        .await
+        .unwrap();
+    assert_eq!(cursor, Some(20));
+}
+
+#[tokio::test]
+async fn priority_ops_in_mempool_are_retained_after_l1_reorg() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    setup_db(&connection_pool).await;
+
+    let mut client = FakeEthClient::new();
+    let mut watcher = EthWatch::new(
+        DIAMOND_PROXY_ADDRESS,
+        None,
+        Box::new(client.clone()),
+        connection_pool.clone(),
+        std::time::Duration::from_nanos(1),
+    )
+    .await;
+
+    let mut storage = connection_pool.access_storage().await.unwrap();
+    client
+        .add_transactions(&[build_l1_tx(0, 10), build_l1_tx(1, 14)])
+        .await;
+    client.set_last_finalized_block_number(11).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    client.set_last_finalized_block_number(15).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    // Load priority ops into the mempool.
+    let mempool_txs = storage
+        .transactions_dal()
+        .sync_mempool(&[], &[], 0, 0, 1000)
+        .await
+        .unwrap();
+    assert_eq!(mempool_txs.len(), 2);
+
+    client.reorg(12).await;
+    client.add_transactions(&[build_l1_tx(1, 16)]).await;
+    client.set_last_finalized_block_number(20).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+
+    // The operation cannot be removed, so it should be retained as is.
+    let db_txs = get_l1_txs(&mut storage).await;
+    assert_eq!(db_txs.len(), 2);
+    assert_eq!(db_txs[1].common_data.serial_id, PriorityOpId(1));
+    assert_eq!(db_txs[1].common_data.eth_block, 14);
+}
+
+#[tokio::test]
+async fn too_deep_l1_reorg() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    setup_db(&connection_pool).await;
+
+    let mut client = FakeEthClient::new();
+    let mut watcher = EthWatch::new(
+        DIAMOND_PROXY_ADDRESS,
+        None,
+        Box::new(client.clone()),
+        connection_pool.clone(),
+        std::time::Duration::from_nanos(1),
+    )
+    .await;
+
+    let mut storage = connection_pool.access_storage().await.unwrap();
+    client.add_transactions(&[build_l1_tx(0, 10)]).await;
+    client.set_last_finalized_block_number(15).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+
+    client.reorg(5).await;
+    client.set_last_finalized_block_number(20).await;
+    let err = watcher.loop_iteration(&mut storage).await.unwrap_err();
+    assert!(err.to_string().contains("reorg"), "{err}");
+    // Progress must not be affected.
+    let cursor = storage
+        .eth_watch_dal()
+        .get_processor_cursor("priority_ops")
+        .await
+        .unwrap();
+    assert_eq!(cursor, Some(15));
+}
+
 async fn get_all_db_txs(storage: &mut StorageProcessor<'_>) -> Vec<Transaction> {
     storage.transactions_dal().reset_mempool().await.unwrap();
     storage
@@ -568,7 +977,7 @@ fn tx_into_log(tx: L1Tx) -> Log {
     ]);
 
     Log {
-        address: Address::repeat_byte(0x1),
+        address: DIAMOND_PROXY_ADDRESS,
         topics: vec![zksync_contract()
             .event("NewPriorityRequest")
             .expect("NewPriorityRequest event is missing in abi")
@@ -589,7 +998,7 @@ fn upgrade_into_diamond_proxy_log(upgrade: ProtocolUpgrade, eth_block: u64) -> L
     let diamond_cut = upgrade_into_diamond_cut(upgrade);
     let data = encode(&[diamond_cut, Token::FixedBytes(vec![0u8; 32])]);
     Log {
-        address: Address::repeat_byte(0x1),
+        address: DIAMOND_PROXY_ADDRESS,
         topics: vec![UPGRADE_PROPOSAL_SIGNATURE],
         data: data.into(),
         block_hash: Some(H256::repeat_byte(0x11)),
@@ -615,7 +1024,7 @@ fn upgrade_into_governor_log(upgrade: ProtocolUpgrade, eth_block: u64) -> Log {
         .chain(encode(&[diamond_cut]))
         .collect();
     let governance_call = Token::Tuple(vec![
-        Token::Address(Default::default()),
+        Token::Address(DIAMOND_PROXY_ADDRESS),
         Token::Uint(U256::default()),
         Token::Bytes(diamond_upgrade_calldata),
     ]);
@@ -627,7 +1036,7 @@ fn upgrade_into_governor_log(upgrade: ProtocolUpgrade, eth_block: u64) -> Log {
     let final_data = encode(&[Token::FixedBytes(vec![0u8; 32]), governance_operation]);
 
     Log {
-        address: Address::repeat_byte(0x1),
+        address: GOVERNANCE_ADDRESS,
         topics: vec![
             governance_contract()
                 .event("TransparentOperationScheduled")
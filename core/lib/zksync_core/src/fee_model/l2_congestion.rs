@@ -0,0 +1,224 @@
+//! L2 congestion component of the fee model.
+
+use std::{
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use anyhow::Context as _;
+use tokio::sync::watch;
+use zksync_config::configs::chain::{self, StateKeeperConfig};
+use zksync_dal::ConnectionPool;
+use zksync_types::{block::BlockGasCount, fee_model::L2CongestionConfig};
+
+use super::metrics::FEE_MODEL_METRICS;
+
+/// Computes the L2 congestion multiplier based on the utilization of the latest sealed L1 batches.
+///
+/// The multiplier is recomputed from scratch (starting from 1) over a fixed window of L1 batches, so that all server components
+/// observe the same value regardless of when they were started.
+#[derive(Debug)]
+pub struct L2CongestionMonitor {
+    pool: ConnectionPool,
+    config: L2CongestionConfig,
+    lookback_batches: usize,
+    transaction_slots: usize,
+    max_batch_gas: u32,
+    poll_interval: Duration,
+    multiplier: RwLock<f64>,
+}
+
+impl L2CongestionMonitor {
+    const DEFAULT_LOOKBACK_BATCHES: u32 = 100;
+    const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+    /// Creates a monitor with the specified L2 congestion config. Returns an error if the config is invalid.
+    pub fn new(
+        pool: ConnectionPool,
+        state_keeper_config: &StateKeeperConfig,
+        config: &chain::L2CongestionConfig,
+    ) -> anyhow::Result<Self> {
+        let lookback_batches = config
+            .lookback_batches
+            .unwrap_or(Self::DEFAULT_LOOKBACK_BATCHES);
+        let config = L2CongestionConfig::from_config(config)?;
+
+        Ok(Self {
+            pool,
+            config,
+            lookback_batches: lookback_batches as usize,
+            transaction_slots: state_keeper_config.transaction_slots,
+            max_batch_gas: state_keeper_config.max_single_tx_gas,
+            poll_interval: Self::POLL_INTERVAL,
+            multiplier: RwLock::new(1.0),
+        })
+    }
+
+    pub(super) fn config(&self) -> L2CongestionConfig {
+        self.config
+    }
+
+    /// Returns the current value of the congestion multiplier.
+    pub fn multiplier(&self) -> f64 {
+        *self.multiplier.read().unwrap()
+    }
+
+    /// Returns the utilization of an L1 batch, i.e. the maximum of its utilization by transaction slots and by gas.
+    fn batch_utilization(&self, tx_count: usize, gas_count: BlockGasCount) -> f64 {
+        let slots_utilization = tx_count as f64 / self.transaction_slots as f64;
+        let max_gas = gas_count.commit.max(gas_count.prove).max(gas_count.execute);
+        let gas_utilization = f64::from(max_gas) / f64::from(self.max_batch_gas);
+        slots_utilization.max(gas_utilization)
+    }
+
+    fn compute_multiplier(&self, batches: &[(usize, BlockGasCount)]) -> f64 {
+        batches
+            .iter()
+            .fold(1.0, |multiplier, &(tx_count, gas_count)| {
+                let utilization = self.batch_utilization(tx_count, gas_count);
+                self.config.next_multiplier(multiplier, utilization)
+            })
+    }
+
+    async fn update(&self) -> anyhow::Result<()> {
+        let mut storage = self
+            .pool
+            .access_storage_tagged("l2_congestion_monitor")
+            .await?;
+        let batches = storage
+            .blocks_dal()
+            .get_recent_l1_batches_utilization(self.lookback_batches)
+            .await
+            .context("get_recent_l1_batches_utilization()")?;
+        drop(storage);
+
+        if let Some(&(tx_count, gas_count)) = batches.last() {
+            FEE_MODEL_METRICS
+                .l2_batch_utilization
+                .set(self.batch_utilization(tx_count, gas_count));
+        }
+        let multiplier = self.compute_multiplier(&batches);
+        FEE_MODEL_METRICS.l2_congestion_multiplier.set(multiplier);
+        *self.multiplier.write().unwrap() = multiplier;
+        Ok(())
+    }
+
+    pub async fn run(
+        self: Arc<Self>,
+        mut stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        while !*stop_receiver.borrow_and_update() {
+            if let Err(err) = self.update().await {
+                tracing::warn!("Cannot update L2 congestion multiplier: {err:#}");
+            }
+            // The error means that the timeout has elapsed, which is fine.
+            tokio::time::timeout(self.poll_interval, stop_receiver.changed())
+                .await
+                .ok();
+        }
+        tracing::info!("Stop signal received, L2 congestion monitor is shutting down");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn congestion_config(target_utilization: f64) -> chain::L2CongestionConfig {
+        chain::L2CongestionConfig {
+            target_utilization,
+            max_change_denominator: None,
+            max_multiplier: None,
+            lookback_batches: None,
+        }
+    }
+
+    fn create_monitor(pool: ConnectionPool) -> L2CongestionMonitor {
+        let state_keeper_config = StateKeeperConfig {
+            transaction_slots: 100,
+            max_single_tx_gas: 1_000_000,
+            ..StateKeeperConfig::for_tests()
+        };
+        L2CongestionMonitor::new(pool, &state_keeper_config, &congestion_config(0.5)).unwrap()
+    }
+
+    #[test]
+    fn invalid_config_is_rejected() {
+        let config = L2CongestionConfig::from_config(&congestion_config(0.5)).unwrap();
+        assert_eq!(
+            config.max_change_denominator,
+            L2CongestionConfig::DEFAULT_MAX_CHANGE_DENOMINATOR
+        );
+        assert_eq!(
+            config.max_multiplier,
+            L2CongestionConfig::DEFAULT_MAX_MULTIPLIER
+        );
+        L2CongestionConfig::from_config(&congestion_config(1.0)).unwrap();
+
+        for target_utilization in [0.0, -0.5, 1.5, f64::NAN] {
+            let err = L2CongestionConfig::from_config(&congestion_config(target_utilization))
+                .unwrap_err();
+            assert!(err.to_string().contains("target utilization"), "{err}");
+        }
+
+        let err = L2CongestionConfig::from_config(&chain::L2CongestionConfig {
+            max_change_denominator: Some(0),
+            ..congestion_config(0.5)
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("denominator"), "{err}");
+
+        let err = L2CongestionConfig::from_config(&chain::L2CongestionConfig {
+            max_multiplier: Some(0.5),
+            ..congestion_config(0.5)
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("max multiplier"), "{err}");
+    }
+
+    #[test]
+    fn next_multiplier_is_bounded() {
+        let config = L2CongestionConfig {
+            target_utilization: 0.5,
+            max_change_denominator: 8,
+            max_multiplier: 2.0,
+        };
+        assert_eq!(config.next_multiplier(1.0, 0.5), 1.0);
+        assert_eq!(config.next_multiplier(1.0, 1.0), 1.125);
+        assert_eq!(config.next_multiplier(1.5, 0.0), 1.3125);
+        // Utilization is clamped to `[0, 1]`.
+        assert_eq!(config.next_multiplier(1.0, 3.0), 1.125);
+        // The multiplier never leaves `[1, max_multiplier]`.
+        assert_eq!(config.next_multiplier(1.0, 0.0), 1.0);
+        assert_eq!(config.next_multiplier(1.9, 1.0), 2.0);
+    }
+
+    #[tokio::test]
+    async fn multiplier_reacts_to_batch_utilization() {
+        let pool = ConnectionPool::test_pool().await;
+        let monitor = create_monitor(pool);
+
+        let empty_gas = BlockGasCount::default();
+        assert_eq!(monitor.batch_utilization(50, empty_gas), 0.5);
+        let gas_count = BlockGasCount {
+            commit: 100_000,
+            prove: 900_000,
+            execute: 0,
+        };
+        assert_eq!(monitor.batch_utilization(10, gas_count), 0.9);
+
+        let full_batches = vec![(100, empty_gas); 5];
+        let congested_multiplier = monitor.compute_multiplier(&full_batches);
+        assert!(congested_multiplier > 1.5, "{congested_multiplier}");
+
+        let mut batches = full_batches;
+        batches.extend(vec![(0, empty_gas); 5]);
+        let relaxed_multiplier = monitor.compute_multiplier(&batches);
+        assert!(
+            relaxed_multiplier < congested_multiplier,
+            "{relaxed_multiplier}"
+        );
+        assert_eq!(monitor.compute_multiplier(&[]), 1.0);
+    }
+}
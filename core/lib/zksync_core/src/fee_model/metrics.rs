@@ -0,0 +1,15 @@
+//! Metrics for the fee model.
+
+use vise::{Gauge, Metrics};
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_fee_model")]
+pub(super) struct FeeModelMetrics {
+    /// Current value of the L2 congestion multiplier applied to the fair L2 gas price.
+    pub l2_congestion_multiplier: Gauge<f64>,
+    /// Utilization (by gas or transaction slots) of the latest sealed L1 batch.
+    pub l2_batch_utilization: Gauge<f64>,
+}
+
+#[vise::register]
+pub(super) static FEE_MODEL_METRICS: vise::Global<FeeModelMetrics> = vise::Global::new();
@@ -10,8 +10,12 @@ use zksync_types::{
 };
 use zksync_utils::ceil_div_u256;
 
+pub use self::l2_congestion::L2CongestionMonitor;
 use crate::l1_gas_price::GasAdjuster;
 
+mod l2_congestion;
+mod metrics;
+
 /// Trait responsible for providing fee info for a batch
 #[async_trait::async_trait]
 pub trait BatchFeeModelInputProvider: fmt::Debug + 'static + Send + Sync {
@@ -55,6 +59,7 @@ pub trait BatchFeeModelInputProvider: fmt::Debug + 'static + Send + Sync {
 pub struct MainNodeFeeInputProvider {
     provider: Arc<GasAdjuster>,
     config: FeeModelConfig,
+    l2_congestion_monitor: Option<Arc<L2CongestionMonitor>>,
 }
 
 impl BatchFeeModelInputProvider for MainNodeFeeInputProvider {
@@ -68,6 +73,10 @@ impl BatchFeeModelInputProvider for MainNodeFeeInputProvider {
                 config,
                 l1_gas_price: self.provider.estimate_effective_gas_price(),
                 l1_pubdata_price: self.provider.estimate_effective_pubdata_price(),
+                l2_congestion_multiplier: self
+                    .l2_congestion_monitor
+                    .as_ref()
+                    .map_or(1.0, |monitor| monitor.multiplier()),
             }),
         }
     }
@@ -75,7 +84,21 @@ impl BatchFeeModelInputProvider for MainNodeFeeInputProvider {
 
 impl MainNodeFeeInputProvider {
     pub fn new(provider: Arc<GasAdjuster>, config: FeeModelConfig) -> Self {
-        Self { provider, config }
+        Self {
+            provider,
+            config,
+            l2_congestion_monitor: None,
+        }
+    }
+
+    /// Makes the fair L2 gas price depend on the L2 congestion multiplier reported by the provided monitor.
+    /// Has no effect for the `V1` fee model.
+    pub fn with_l2_congestion_monitor(mut self, monitor: Arc<L2CongestionMonitor>) -> Self {
+        if let FeeModelConfig::V2(config) = &mut self.config {
+            config.l2_congestion = Some(monitor.config());
+        }
+        self.l2_congestion_monitor = Some(monitor);
+        self
     }
 }
 
@@ -156,6 +179,7 @@ fn compute_batch_fee_model_input_v2(
         config,
         l1_gas_price,
         l1_pubdata_price,
+        l2_congestion_multiplier,
     } = params;
 
     let FeeModelConfigV2 {
@@ -165,6 +189,7 @@ fn compute_batch_fee_model_input_v2(
         batch_overhead_l1_gas,
         max_gas_per_batch,
         max_pubdata_per_batch,
+        l2_congestion,
     } = config;
 
     // Firstly, we scale the gas price and pubdata price in case it is needed.
@@ -187,7 +212,15 @@ fn compute_batch_fee_model_input_v2(
             (l1_batch_overhead_per_gas.as_u64() as f64 * compute_overhead_part) as u64;
 
         // We sum up the minimal L2 gas price (i.e. the raw prover/compute cost of a single L2 gas) and the overhead for batch being closed.
-        minimal_l2_gas_price + gas_overhead_wei
+        let fair_l2_gas_price = minimal_l2_gas_price + gas_overhead_wei;
+
+        // Finally, we apply the congestion multiplier, so that the L2 gas price grows if recent batches were congested.
+        // The multiplier is ignored if the congestion component is disabled in the config.
+        if l2_congestion.is_some() {
+            (fair_l2_gas_price as f64 * l2_congestion_multiplier.max(1.0)) as u64
+        } else {
+            fair_l2_gas_price
+        }
     };
 
     let fair_pubdata_price = {
@@ -214,6 +247,8 @@ fn compute_batch_fee_model_input_v2(
 
 #[cfg(test)]
 mod tests {
+    use zksync_types::fee_model::L2CongestionConfig;
+
     use super::*;
 
     // To test that overflow never happens, we'll use giant L1 gas price, i.e.
@@ -238,12 +273,14 @@ mod tests {
             max_gas_per_batch: 50_000_000,
             // The pubdata will likely never go below that
             max_pubdata_per_batch: 100_000,
+            l2_congestion: None,
         };
 
         let params = FeeParamsV2 {
             config,
             l1_gas_price: GIANT_L1_GAS_PRICE,
             l1_pubdata_price: GIANT_L1_GAS_PRICE,
+            l2_congestion_multiplier: 1.0,
         };
 
         // We'll use scale factor of 3.0
@@ -264,12 +301,14 @@ mod tests {
             batch_overhead_l1_gas: 0,
             max_gas_per_batch: 50_000_000,
             max_pubdata_per_batch: 100_000,
+            l2_congestion: None,
         };
 
         let params = FeeParamsV2 {
             config,
             l1_gas_price: SMALL_L1_GAS_PRICE,
             l1_pubdata_price: SMALL_L1_GAS_PRICE,
+            l2_congestion_multiplier: 1.0,
         };
 
         let input = compute_batch_fee_model_input_v2(params, 1.0, 1.0);
@@ -289,12 +328,14 @@ mod tests {
             batch_overhead_l1_gas: 700_000,
             max_gas_per_batch: 500_000_000,
             max_pubdata_per_batch: 100_000,
+            l2_congestion: None,
         };
 
         let params = FeeParamsV2 {
             config,
             l1_gas_price: GIANT_L1_GAS_PRICE,
             l1_pubdata_price: GIANT_L1_GAS_PRICE,
+            l2_congestion_multiplier: 1.0,
         };
 
         let input = compute_batch_fee_model_input_v2(params, 1.0, 1.0);
@@ -315,12 +356,14 @@ mod tests {
             batch_overhead_l1_gas: 700_000,
             max_gas_per_batch: 500_000_000,
             max_pubdata_per_batch: 100_000,
+            l2_congestion: None,
         };
 
         let params = FeeParamsV2 {
             config,
             l1_gas_price: GIANT_L1_GAS_PRICE,
             l1_pubdata_price: GIANT_L1_GAS_PRICE,
+            l2_congestion_multiplier: 1.0,
         };
 
         let input = compute_batch_fee_model_input_v2(params, 1.0, 1.0);
@@ -341,12 +384,14 @@ mod tests {
             batch_overhead_l1_gas: 700_000,
             max_gas_per_batch: 500_000_000,
             max_pubdata_per_batch: 100_000,
+            l2_congestion: None,
         };
 
         let base_params = FeeParamsV2 {
             config: base_config,
             l1_gas_price: 1_000_000_000,
             l1_pubdata_price: 1_000_000_000,
+            l2_congestion_multiplier: 1.0,
         };
 
         let base_input = compute_batch_fee_model_input_v2(base_params, 1.0, 1.0);
@@ -437,4 +482,42 @@ mod tests {
             "Max pubdata increase lowers pubdata price"
         );
     }
+
+    #[test]
+    fn test_compute_batch_fee_model_input_v2_with_l2_congestion() {
+        let config = FeeModelConfigV2 {
+            minimal_l2_gas_price: 100_000_000,
+            compute_overhead_part: 0.0,
+            pubdata_overhead_part: 0.0,
+            batch_overhead_l1_gas: 700_000,
+            max_gas_per_batch: 500_000_000,
+            max_pubdata_per_batch: 100_000,
+            l2_congestion: Some(L2CongestionConfig {
+                target_utilization: 0.5,
+                max_change_denominator: 8,
+                max_multiplier: 10.0,
+            }),
+        };
+        let params = FeeParamsV2 {
+            config,
+            l1_gas_price: 1_000_000_000,
+            l1_pubdata_price: 1_000_000_000,
+            l2_congestion_multiplier: 1.5,
+        };
+
+        let input = compute_batch_fee_model_input_v2(params, 1.0, 1.0);
+        assert_eq!(input.fair_l2_gas_price, 150_000_000);
+        assert_eq!(input.fair_pubdata_price, 1_000_000_000);
+
+        // The multiplier has no effect if the congestion component is disabled.
+        let params = FeeParamsV2 {
+            config: FeeModelConfigV2 {
+                l2_congestion: None,
+                ..config
+            },
+            ..params
+        };
+        let input = compute_batch_fee_model_input_v2(params, 1.0, 1.0);
+        assert_eq!(input.fair_l2_gas_price, 100_000_000);
+    }
 }
@@ -320,6 +320,7 @@ pub(crate) async fn create_genesis_l1_batch(
         base_system_contracts_hashes: base_system_contracts.hashes(),
         protocol_version: Some(protocol_version),
         virtual_blocks: 0,
+        logs_bloom: Default::default(),
     };
 
     let mut transaction = storage.start_transaction().await?;
@@ -7,7 +7,7 @@ use crate::{
     metrics::{BlockL1Stage, BlockStage, L1StageLatencyLabel, APP_METRICS},
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct L1BatchMetricsReporter {
     reporting_interval_ms: u64,
     connection_pool: ConnectionPool,
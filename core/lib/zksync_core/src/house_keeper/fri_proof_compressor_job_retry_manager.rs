@@ -1,13 +1,13 @@
 use std::time::Duration;
 
 use async_trait::async_trait;
-use zksync_dal::ConnectionPool;
+use prover_dal::ProverConnectionPool;
 
 use crate::house_keeper::periodic_job::PeriodicJob;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct FriProofCompressorJobRetryManager {
-    pool: ConnectionPool,
+    pool: ProverConnectionPool,
     max_attempts: u32,
     processing_timeout: Duration,
     retry_interval_ms: u64,
@@ -18,7 +18,7 @@ impl FriProofCompressorJobRetryManager {
         max_attempts: u32,
         processing_timeout: Duration,
         retry_interval_ms: u64,
-        pool: ConnectionPool,
+        pool: ProverConnectionPool,
     ) -> Self {
         Self {
             max_attempts,
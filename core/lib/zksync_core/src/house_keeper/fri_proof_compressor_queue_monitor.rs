@@ -1,25 +1,25 @@
 use async_trait::async_trait;
-use zksync_dal::{fri_prover_dal::types::JobCountStatistics, ConnectionPool};
+use prover_dal::{fri_prover_dal::types::JobCountStatistics, ProverConnectionPool};
 
 use crate::house_keeper::periodic_job::PeriodicJob;
 
 const PROOF_COMPRESSOR_SERVICE_NAME: &str = "proof_compressor";
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct FriProofCompressorStatsReporter {
     reporting_interval_ms: u64,
-    pool: ConnectionPool,
+    pool: ProverConnectionPool,
 }
 
 impl FriProofCompressorStatsReporter {
-    pub fn new(reporting_interval_ms: u64, pool: ConnectionPool) -> Self {
+    pub fn new(reporting_interval_ms: u64, pool: ProverConnectionPool) -> Self {
         Self {
             reporting_interval_ms,
             pool,
         }
     }
 
-    async fn get_job_statistics(pool: &ConnectionPool) -> JobCountStatistics {
+    async fn get_job_statistics(pool: &ProverConnectionPool) -> JobCountStatistics {
         pool.access_storage()
             .await
             .unwrap()
@@ -1,13 +1,14 @@
 use async_trait::async_trait;
+use prover_dal::ProverConnectionPool;
 use zksync_config::configs::fri_prover_group::FriProverGroupConfig;
 use zksync_dal::ConnectionPool;
 
 use crate::house_keeper::periodic_job::PeriodicJob;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct FriProverStatsReporter {
     reporting_interval_ms: u64,
-    prover_connection_pool: ConnectionPool,
+    prover_connection_pool: ProverConnectionPool,
     db_connection_pool: ConnectionPool,
     config: FriProverGroupConfig,
 }
@@ -15,7 +16,7 @@ pub struct FriProverStatsReporter {
 impl FriProverStatsReporter {
     pub fn new(
         reporting_interval_ms: u64,
-        prover_connection_pool: ConnectionPool,
+        prover_connection_pool: ProverConnectionPool,
         db_connection_pool: ConnectionPool,
         config: FriProverGroupConfig,
     ) -> Self {
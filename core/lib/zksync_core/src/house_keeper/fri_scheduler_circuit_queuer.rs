@@ -1,16 +1,16 @@
 use async_trait::async_trait;
-use zksync_dal::ConnectionPool;
+use prover_dal::ProverConnectionPool;
 
 use crate::house_keeper::periodic_job::PeriodicJob;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SchedulerCircuitQueuer {
     queuing_interval_ms: u64,
-    pool: ConnectionPool,
+    pool: ProverConnectionPool,
 }
 
 impl SchedulerCircuitQueuer {
-    pub fn new(queuing_interval_ms: u64, pool: ConnectionPool) -> Self {
+    pub fn new(queuing_interval_ms: u64, pool: ProverConnectionPool) -> Self {
         Self {
             queuing_interval_ms,
             pool,
@@ -1,21 +1,21 @@
 use std::collections::HashMap;
 
 use async_trait::async_trait;
-use zksync_dal::{fri_prover_dal::types::JobCountStatistics, ConnectionPool};
+use prover_dal::{fri_prover_dal::types::JobCountStatistics, ProverConnectionPool};
 use zksync_types::basic_fri_types::AggregationRound;
 
 use crate::house_keeper::periodic_job::PeriodicJob;
 
 const FRI_WITNESS_GENERATOR_SERVICE_NAME: &str = "fri_witness_generator";
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct FriWitnessGeneratorStatsReporter {
     reporting_interval_ms: u64,
-    pool: ConnectionPool,
+    pool: ProverConnectionPool,
 }
 
 impl FriWitnessGeneratorStatsReporter {
-    pub fn new(pool: ConnectionPool, reporting_interval_ms: u64) -> Self {
+    pub fn new(pool: ProverConnectionPool, reporting_interval_ms: u64) -> Self {
         Self {
             reporting_interval_ms,
             pool,
@@ -1,16 +1,16 @@
 use async_trait::async_trait;
-use zksync_dal::ConnectionPool;
+use prover_dal::ProverConnectionPool;
 
 use crate::house_keeper::periodic_job::PeriodicJob;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct WaitingToQueuedFriWitnessJobMover {
     job_moving_interval_ms: u64,
-    pool: ConnectionPool,
+    pool: ProverConnectionPool,
 }
 
 impl WaitingToQueuedFriWitnessJobMover {
-    pub fn new(job_mover_interval_ms: u64, pool: ConnectionPool) -> Self {
+    pub fn new(job_mover_interval_ms: u64, pool: ProverConnectionPool) -> Self {
         Self {
             job_moving_interval_ms: job_mover_interval_ms,
             pool,
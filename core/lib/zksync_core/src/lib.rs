@@ -2,6 +2,7 @@
 
 use std::{
     net::Ipv4Addr,
+    path::Path,
     str::FromStr,
     sync::Arc,
     time::{Duration, Instant},
@@ -9,14 +10,18 @@ use std::{
 
 use anyhow::Context as _;
 use api_server::tx_sender::master_pool_sink::MasterPoolSink;
-use fee_model::{ApiFeeInputProvider, BatchFeeModelInputProvider, MainNodeFeeInputProvider};
+use fee_model::{
+    ApiFeeInputProvider, BatchFeeModelInputProvider, L2CongestionMonitor, MainNodeFeeInputProvider,
+};
 use futures::channel::oneshot;
 use prometheus_exporter::PrometheusExporterConfig;
+use prover_dal::ProverConnectionPool;
 use temp_config_store::{Secrets, TempConfigStore};
 use tokio::{sync::watch, task::JoinHandle};
 use zksync_circuit_breaker::{
-    l1_txs::FailedL1TransactionChecker, replication_lag::ReplicationLagChecker, CircuitBreaker,
-    CircuitBreakerChecker, CircuitBreakerError,
+    l1_txs::FailedL1TransactionChecker, priority_ops::PendingPriorityOpsMonitor,
+    replication_lag::ReplicationLagChecker, CircuitBreaker, CircuitBreakerChecker,
+    CircuitBreakerError,
 };
 use zksync_concurrency::{ctx, scope};
 use zksync_config::{
@@ -27,11 +32,11 @@ use zksync_config::{
             StateKeeperConfig,
         },
         contracts::ProverAtGenesis,
-        database::{MerkleTreeConfig, MerkleTreeMode},
+        database::MerkleTreeMode,
     },
-    ApiConfig, ContractsConfig, DBConfig, ETHSenderConfig, PostgresConfig,
+    ApiConfig, ContractsConfig, DBConfig, ETHSenderConfig, ObjectStoreConfig, PostgresConfig,
 };
-use zksync_contracts::{governance_contract, BaseSystemContracts};
+use zksync_contracts::governance_contract;
 use zksync_dal::{healthcheck::ConnectionPoolHealthCheck, ConnectionPool};
 use zksync_eth_client::{
     clients::{PKSigningClient, QueryClient},
@@ -58,6 +63,9 @@ use crate::{
         tx_sender::{ApiContracts, TxSender, TxSenderBuilder, TxSenderConfig},
         web3::{self, state::InternalApiConfig, Namespace},
     },
+    base_system_contracts::{
+        ensure_base_system_contracts_for_protocol_version, BaseSystemContractsLoader,
+    },
     basic_witness_input_producer::BasicWitnessInputProducer,
     commitment_generator::CommitmentGenerator,
     eth_sender::{Aggregator, EthTxAggregator, EthTxManager},
@@ -74,15 +82,18 @@ use crate::{
         periodic_job::PeriodicJob,
         waiting_to_queued_fri_witness_job_mover::WaitingToQueuedFriWitnessJobMover,
     },
-    l1_gas_price::GasAdjusterSingleton,
+    l1_gas_price::{GasAdjuster, GasAdjusterSingleton},
     metadata_calculator::{MetadataCalculator, MetadataCalculatorConfig},
     metrics::{InitStage, APP_METRICS},
+    rocksdb_backup::{RocksdbBackupUploader, RocksdbKind},
     state_keeper::{
         create_state_keeper, MempoolFetcher, MempoolGuard, MiniblockSealer, SequencerSealer,
     },
+    tx_admission::{create_tx_admission_policy, AllowAllPolicy, TxAdmissionPolicy},
 };
 
 pub mod api_server;
+pub mod base_system_contracts;
 pub mod basic_witness_input_producer;
 pub mod block_reverter;
 pub mod commitment_generator;
@@ -100,9 +111,11 @@ mod metrics;
 pub mod proof_data_handler;
 pub mod proto;
 pub mod reorg_detector;
+pub mod rocksdb_backup;
 pub mod state_keeper;
 pub mod sync_layer;
 pub mod temp_config_store;
+pub mod tx_admission;
 mod utils;
 
 /// Inserts the initial information about zkSync tokens into the database.
@@ -111,9 +124,26 @@ pub async fn genesis_init(
     eth_sender: &ETHSenderConfig,
     network_config: &NetworkConfig,
     contracts_config: &ContractsConfig,
+    object_store_config: Option<&ObjectStoreConfig>,
     eth_client_url: &str,
     wait_for_set_chain_id: bool,
 ) -> anyhow::Result<()> {
+    let object_store = if contracts_config.base_system_contracts_object_key.is_some() {
+        let object_store_config = object_store_config
+            .context("object store config is required to load custom base system contracts")?;
+        Some(
+            ObjectStoreFactory::new(object_store_config.clone())
+                .create_store()
+                .await,
+        )
+    } else {
+        None
+    };
+    let base_system_contracts = BaseSystemContractsLoader::new(contracts_config, object_store)?
+        .load()
+        .await
+        .context("failed loading base system contracts")?;
+
     let db_url = postgres_config.master_url()?;
     let pool = ConnectionPool::singleton(db_url)
         .build()
@@ -177,7 +207,7 @@ pub async fn genesis_init(
             // We consider the operator to be the first validator for now.
             first_validator: operator_address,
             protocol_version: ProtocolVersionId::latest(),
-            base_system_contracts: BaseSystemContracts::load_from_disk(),
+            base_system_contracts,
             system_contracts: get_system_smart_contracts(),
             first_l1_verifier_config,
         },
@@ -406,6 +436,52 @@ pub async fn initialize_components(
         tokio::spawn(circuit_breaker_checker.run(cb_sender, stop_receiver.clone())),
     ];
 
+    // The L2 congestion monitor is shared among all components using the main node fee model.
+    let uses_fee_model = components.iter().any(|component| {
+        matches!(
+            component,
+            Component::HttpApi | Component::WsApi | Component::StateKeeper
+        )
+    });
+    let l2_congestion_monitor = match &configs.l2_congestion_config {
+        Some(config) if uses_fee_model => {
+            let state_keeper_config = configs
+                .state_keeper_config
+                .as_ref()
+                .context("state_keeper_config")?;
+            let monitor = L2CongestionMonitor::new(
+                replica_connection_pool.clone(),
+                state_keeper_config,
+                config,
+            )
+            .context("L2CongestionMonitor::new()")?;
+            Some(Arc::new(monitor))
+        }
+        _ => None,
+    };
+    if let Some(monitor) = &l2_congestion_monitor {
+        task_futures.push(tokio::spawn(monitor.clone().run(stop_receiver.clone())));
+    }
+
+    // Transaction admission rules are shared among all components accepting or executing transactions,
+    // so that they are reloaded by a single task.
+    let tx_admission_policy: Arc<dyn TxAdmissionPolicy> = match &configs.state_keeper_config {
+        Some(config) if config.tx_admission_enabled => {
+            let admission_pool = ConnectionPool::singleton(postgres_config.master_url()?)
+                .build()
+                .await
+                .context("failed to build tx_admission_pool")?;
+            let (policy, rules_updater) = create_tx_admission_policy(config, admission_pool)
+                .await
+                .context("failed creating transaction admission policy")?;
+            if let Some(updater) = rules_updater {
+                task_futures.push(tokio::spawn(updater.run(stop_receiver.clone())));
+            }
+            policy
+        }
+        _ => Arc::new(AllowAllPolicy),
+    };
+
     if components.contains(&Component::WsApi)
         || components.contains(&Component::HttpApi)
         || components.contains(&Component::ContractVerificationApi)
@@ -445,10 +521,11 @@ pub async fn initialize_components(
                 .get_or_init()
                 .await
                 .context("gas_adjuster.get_or_init()")?;
-            let batch_fee_input_provider = Arc::new(MainNodeFeeInputProvider::new(
+            let batch_fee_input_provider = main_node_fee_input_provider(
                 bounded_gas_adjuster,
-                FeeModelConfig::from_state_keeper_config(&state_keeper_config),
-            ));
+                &state_keeper_config,
+                l2_congestion_monitor.clone(),
+            );
             run_http_api(
                 &mut task_futures,
                 &app_health,
@@ -463,6 +540,7 @@ pub async fn initialize_components(
                 batch_fee_input_provider,
                 state_keeper_config.save_call_traces,
                 storage_caches.clone().unwrap(),
+                tx_admission_policy.clone(),
             )
             .await
             .context("run_http_api")?;
@@ -488,10 +566,11 @@ pub async fn initialize_components(
                 .get_or_init()
                 .await
                 .context("gas_adjuster.get_or_init()")?;
-            let batch_fee_input_provider = Arc::new(MainNodeFeeInputProvider::new(
+            let batch_fee_input_provider = main_node_fee_input_provider(
                 bounded_gas_adjuster,
-                FeeModelConfig::from_state_keeper_config(&state_keeper_config),
-            ));
+                &state_keeper_config,
+                l2_congestion_monitor.clone(),
+            );
             run_ws_api(
                 &mut task_futures,
                 &app_health,
@@ -505,6 +584,7 @@ pub async fn initialize_components(
                 replica_connection_pool.clone(),
                 stop_receiver.clone(),
                 storage_caches,
+                tx_admission_policy.clone(),
             )
             .await
             .context("run_ws_api")?;
@@ -549,10 +629,11 @@ pub async fn initialize_components(
             .state_keeper_config
             .clone()
             .context("state_keeper_config")?;
-        let batch_fee_input_provider = Arc::new(MainNodeFeeInputProvider::new(
+        let batch_fee_input_provider = main_node_fee_input_provider(
             bounded_gas_adjuster,
-            FeeModelConfig::from_state_keeper_config(&state_keeper_config),
-        ));
+            &state_keeper_config,
+            l2_congestion_monitor.clone(),
+        );
         add_state_keeper_to_task_futures(
             &mut task_futures,
             &postgres_config,
@@ -563,6 +644,7 @@ pub async fn initialize_components(
             &configs.mempool_config.clone().context("mempool_config")?,
             batch_fee_input_provider,
             store_factory.create_store().await,
+            tx_admission_policy.clone(),
             stop_receiver.clone(),
         )
         .await
@@ -795,6 +877,21 @@ pub async fn initialize_components(
     Ok((task_futures, stop_sender, cb_receiver, health_check_handle))
 }
 
+fn main_node_fee_input_provider(
+    gas_adjuster: Arc<GasAdjuster>,
+    state_keeper_config: &StateKeeperConfig,
+    l2_congestion_monitor: Option<Arc<L2CongestionMonitor>>,
+) -> Arc<MainNodeFeeInputProvider> {
+    let provider = MainNodeFeeInputProvider::new(
+        gas_adjuster,
+        FeeModelConfig::from_state_keeper_config(state_keeper_config),
+    );
+    Arc::new(match l2_congestion_monitor {
+        Some(monitor) => provider.with_l2_congestion_monitor(monitor),
+        None => provider,
+    })
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn add_state_keeper_to_task_futures(
     task_futures: &mut Vec<JoinHandle<anyhow::Result<()>>>,
@@ -806,6 +903,7 @@ async fn add_state_keeper_to_task_futures(
     mempool_config: &MempoolConfig,
     batch_fee_input_provider: Arc<dyn BatchFeeModelInputProvider>,
     object_store: Arc<dyn ObjectStore>,
+    admission_policy: Arc<dyn TxAdmissionPolicy>,
     stop_receiver: watch::Receiver<bool>,
 ) -> anyhow::Result<()> {
     let pool_builder = ConnectionPool::singleton(postgres_config.master_url()?);
@@ -823,6 +921,24 @@ async fn add_state_keeper_to_task_futures(
         mempool
     };
 
+    let base_system_contracts_loader =
+        BaseSystemContractsLoader::new(contracts_config, Some(object_store.clone()))?;
+    let custom_base_system_contracts = if base_system_contracts_loader.is_custom() {
+        let base_system_contracts = base_system_contracts_loader
+            .load()
+            .await
+            .context("failed loading custom base system contracts")?;
+        let mut storage = state_keeper_pool
+            .access_storage()
+            .await
+            .context("Access storage to check base system contracts")?;
+        ensure_base_system_contracts_for_protocol_version(&mut storage, &base_system_contracts)
+            .await?;
+        Some(base_system_contracts)
+    } else {
+        None
+    };
+
     let miniblock_sealer_pool = pool_builder
         .build()
         .await
@@ -833,8 +949,20 @@ async fn add_state_keeper_to_task_futures(
     );
     task_futures.push(tokio::spawn(miniblock_sealer.run()));
 
+    let backup_handle = db_config.rocksdb_backup_interval().map(|interval| {
+        let (uploader, handle) = RocksdbBackupUploader::new(
+            RocksdbKind::StateKeeperCache,
+            Path::new(&db_config.state_keeper_db_path),
+            interval,
+            object_store.clone(),
+        );
+        task_futures.push(tokio::spawn(uploader.run(stop_receiver.clone())));
+        handle
+    });
+
     let state_keeper = create_state_keeper(
         contracts_config,
+        custom_base_system_contracts,
         state_keeper_config,
         db_config,
         network_config,
@@ -844,12 +972,18 @@ async fn add_state_keeper_to_task_futures(
         batch_fee_input_provider.clone(),
         miniblock_sealer_handle,
         object_store,
+        admission_policy,
+        backup_handle,
         stop_receiver.clone(),
     )
-    .await;
+    .await
+    .context("failed creating state keeper")?;
 
     task_futures.push(tokio::spawn(
-        state_keeper.run_fee_address_migration(state_keeper_pool),
+        state_keeper.run_fee_address_migration(state_keeper_pool.clone()),
+    ));
+    task_futures.push(tokio::spawn(
+        state_keeper.run_logs_bloom_backfill(state_keeper_pool),
     ));
     task_futures.push(tokio::spawn(state_keeper.run()));
 
@@ -903,15 +1037,23 @@ async fn add_trees_to_task_futures(
         MerkleTreeMode::Lightweight => None,
         MerkleTreeMode::Full => Some(store_factory.create_store().await),
     };
+    let backups_enabled =
+        db_config.rocksdb_backup_interval().is_some() || db_config.restore_rocksdb_from_backup;
+    let backup_store = if backups_enabled {
+        Some(store_factory.create_store().await)
+    } else {
+        None
+    };
 
     run_tree(
         task_futures,
         app_health,
         &postgres_config,
-        &db_config.merkle_tree,
+        &db_config,
         api_config,
         &operation_config,
         object_store,
+        backup_store,
         stop_receiver,
     )
     .await
@@ -923,12 +1065,14 @@ async fn run_tree(
     task_futures: &mut Vec<JoinHandle<anyhow::Result<()>>>,
     app_health: &AppHealthCheck,
     postgres_config: &PostgresConfig,
-    merkle_tree_config: &MerkleTreeConfig,
+    db_config: &DBConfig,
     api_config: Option<&MerkleTreeApiConfig>,
     operation_manager: &OperationsManagerConfig,
     object_store: Option<Arc<dyn ObjectStore>>,
+    backup_store: Option<Arc<dyn ObjectStore>>,
     stop_receiver: watch::Receiver<bool>,
 ) -> anyhow::Result<()> {
+    let merkle_tree_config = &db_config.merkle_tree;
     let started_at = Instant::now();
     let mode_str = if matches!(merkle_tree_config.mode, MerkleTreeMode::Full) {
         "full"
@@ -938,20 +1082,32 @@ async fn run_tree(
     tracing::info!("Initializing Merkle tree in {mode_str} mode");
 
     let config = MetadataCalculatorConfig::for_main_node(merkle_tree_config, operation_manager);
-    let metadata_calculator = MetadataCalculator::new(config, object_store)
+    let mut metadata_calculator = MetadataCalculator::new(config, object_store)
         .await
         .context("failed initializing metadata_calculator")?;
+    if let Some(backup_store) = backup_store {
+        if db_config.restore_rocksdb_from_backup {
+            metadata_calculator =
+                metadata_calculator.with_restore_from_backup(backup_store.clone());
+        }
+        if let Some(interval) = db_config.rocksdb_backup_interval() {
+            let (uploader, handle) = RocksdbBackupUploader::new(
+                RocksdbKind::MerkleTree,
+                Path::new(&merkle_tree_config.path),
+                interval,
+                backup_store,
+            );
+            task_futures.push(tokio::spawn(uploader.run(stop_receiver.clone())));
+            metadata_calculator = metadata_calculator.with_backups(handle);
+        }
+    }
     if let Some(api_config) = api_config {
         let address = (Ipv4Addr::UNSPECIFIED, api_config.port).into();
         let tree_reader = metadata_calculator.tree_reader();
         let stop_receiver = stop_receiver.clone();
-        task_futures.push(tokio::spawn(async move {
-            tree_reader
-                .wait()
-                .await
-                .run_api_server(address, stop_receiver)
-                .await
-        }));
+        task_futures.push(tokio::spawn(
+            tree_reader.run_api_server(address, stop_receiver),
+        ));
     }
 
     let tree_health_check = metadata_calculator.tree_health_check();
@@ -983,8 +1139,11 @@ async fn add_basic_witness_input_producer_to_task_futures(
     }
     let started_at = Instant::now();
     tracing::info!("initializing BasicWitnessInputProducer");
-    let producer =
-        BasicWitnessInputProducer::new(connection_pool.clone(), store_factory, l2_chain_id).await?;
+    let producer = BasicWitnessInputProducer::new(
+        connection_pool.clone(),
+        store_factory.create_store().await,
+        l2_chain_id,
+    );
     task_futures.push(tokio::spawn(producer.run(stop_receiver, None)));
     tracing::info!(
         "Initialized BasicWitnessInputProducer in {:?}",
@@ -1025,7 +1184,7 @@ async fn add_house_keeper_to_task_futures(
         connection_pool.clone(),
     );
 
-    let prover_connection_pool = ConnectionPool::builder(
+    let prover_connection_pool = ProverConnectionPool::builder(
         postgres_config.prover_url()?,
         postgres_config.max_connections()?,
     )
@@ -1135,6 +1294,7 @@ fn build_storage_caches(
     Ok(storage_caches)
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn build_tx_sender(
     tx_sender_config: &TxSenderConfig,
     web3_json_config: &Web3JsonRpcConfig,
@@ -1143,6 +1303,7 @@ async fn build_tx_sender(
     master_pool: ConnectionPool,
     batch_fee_model_input_provider: Arc<dyn BatchFeeModelInputProvider>,
     storage_caches: PostgresStorageCaches,
+    admission_policy: Arc<dyn TxAdmissionPolicy>,
 ) -> (TxSender, VmConcurrencyBarrier) {
     let sequencer_sealer = SequencerSealer::new(state_keeper_config.clone());
     let master_pool_sink = MasterPoolSink::new(master_pool);
@@ -1151,7 +1312,8 @@ async fn build_tx_sender(
         replica_pool.clone(),
         Arc::new(master_pool_sink),
     )
-    .with_sealer(Arc::new(sequencer_sealer));
+    .with_sealer(Arc::new(sequencer_sealer))
+    .with_admission_policy(admission_policy);
 
     let max_concurrency = web3_json_config.vm_concurrency_limit();
     let (vm_concurrency_limiter, vm_barrier) = VmConcurrencyLimiter::new(max_concurrency);
@@ -1185,6 +1347,7 @@ async fn run_http_api(
     batch_fee_model_input_provider: Arc<dyn BatchFeeModelInputProvider>,
     with_debug_namespace: bool,
     storage_caches: PostgresStorageCaches,
+    admission_policy: Arc<dyn TxAdmissionPolicy>,
 ) -> anyhow::Result<()> {
     let (tx_sender, vm_barrier) = build_tx_sender(
         tx_sender_config,
@@ -1194,6 +1357,7 @@ async fn run_http_api(
         master_connection_pool,
         batch_fee_model_input_provider,
         storage_caches,
+        admission_policy,
     )
     .await;
 
@@ -1248,6 +1412,7 @@ async fn run_ws_api(
     replica_connection_pool: ConnectionPool,
     stop_receiver: watch::Receiver<bool>,
     storage_caches: PostgresStorageCaches,
+    admission_policy: Arc<dyn TxAdmissionPolicy>,
 ) -> anyhow::Result<()> {
     let (tx_sender, vm_barrier) = build_tx_sender(
         tx_sender_config,
@@ -1257,6 +1422,7 @@ async fn run_ws_api(
         master_connection_pool,
         batch_fee_model_input_provider,
         storage_caches,
+        admission_policy,
     )
     .await;
     let last_miniblock_pool = ConnectionPool::singleton(postgres_config.replica_url()?)
@@ -1332,5 +1498,19 @@ async fn circuit_breakers_for_components(
             replication_lag_limit_sec: circuit_breaker_config.replication_lag_limit_sec,
         }));
     }
+
+    if components
+        .iter()
+        .any(|c| matches!(c, Component::StateKeeper | Component::EthWatcher))
+    {
+        let pool = ConnectionPool::singleton(postgres_config.replica_url()?)
+            .build()
+            .await
+            .context("failed to build priority_ops_monitor_pool")?;
+        circuit_breakers.push(Box::new(PendingPriorityOpsMonitor::new(
+            pool,
+            circuit_breaker_config,
+        )));
+    }
     Ok(circuit_breakers)
 }
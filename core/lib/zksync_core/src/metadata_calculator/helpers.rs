@@ -201,6 +201,19 @@ impl AsyncTree {
     pub fn revert_logs(&mut self, last_l1_batch_to_keep: L1BatchNumber) {
         self.as_mut().revert_logs(last_l1_batch_to_keep);
     }
+
+    pub async fn create_checkpoint(&mut self, path: PathBuf) -> anyhow::Result<()> {
+        let tree = self.inner.take().expect(Self::INCONSISTENT_MSG);
+        let (tree, result) = tokio::task::spawn_blocking(move || {
+            let result = tree.create_checkpoint(&path);
+            (tree, result)
+        })
+        .await
+        .unwrap();
+
+        self.inner = Some(tree);
+        result.context("failed creating Merkle tree RocksDB checkpoint")
+    }
 }
 
 /// Async version of [`ZkSyncTreeReader`].
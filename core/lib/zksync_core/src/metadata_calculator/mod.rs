@@ -2,6 +2,7 @@
 //! stores them in the DB.
 
 use std::{
+    path::Path,
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -22,6 +23,7 @@ use self::{
     helpers::{create_db, Delayer, GenericAsyncTree, MerkleTreeHealth},
     updater::TreeUpdater,
 };
+use crate::rocksdb_backup::{restore_from_backup, RocksdbBackupHandle, RocksdbKind};
 
 mod helpers;
 mod metrics;
@@ -76,6 +78,8 @@ pub struct MetadataCalculator {
     config: MetadataCalculatorConfig,
     tree_reader: watch::Sender<Option<AsyncTreeReader>>,
     object_store: Option<Arc<dyn ObjectStore>>,
+    backup_store: Option<Arc<dyn ObjectStore>>,
+    backup_handle: Option<RocksdbBackupHandle>,
     delayer: Delayer,
     health_updater: HealthUpdater,
     max_l1_batches_per_iter: usize,
@@ -96,6 +100,8 @@ impl MetadataCalculator {
         Ok(Self {
             tree_reader: watch::channel(None).0,
             object_store,
+            backup_store: None,
+            backup_handle: None,
             delayer: Delayer::new(config.delay_interval),
             health_updater,
             max_l1_batches_per_iter: config.max_l1_batches_per_iter,
@@ -103,6 +109,21 @@ impl MetadataCalculator {
         })
     }
 
+    /// Enables restoring the tree RocksDB from the latest backup in `backup_store` if the RocksDB is empty
+    /// on initialization. The restored tree is caught up with Postgres afterwards.
+    #[must_use]
+    pub fn with_restore_from_backup(mut self, backup_store: Arc<dyn ObjectStore>) -> Self {
+        self.backup_store = Some(backup_store);
+        self
+    }
+
+    /// Enables periodic backups of the tree RocksDB using the provided handle.
+    #[must_use]
+    pub fn with_backups(mut self, backup_handle: RocksdbBackupHandle) -> Self {
+        self.backup_handle = Some(backup_handle);
+        self
+    }
+
     /// Returns a health check for this calculator.
     pub fn tree_health_check(&self) -> ReactiveHealthCheck {
         self.health_updater.subscribe()
@@ -117,6 +138,16 @@ impl MetadataCalculator {
         self.health_updater
             .update(MerkleTreeHealth::Initialization.into());
 
+        if let Some(backup_store) = &self.backup_store {
+            restore_from_backup(
+                backup_store.as_ref(),
+                RocksdbKind::MerkleTree,
+                Path::new(&self.config.db_path),
+            )
+            .await
+            .context("failed restoring Merkle tree RocksDB from backup")?;
+        }
+
         let started_at = Instant::now();
         let db = create_db(
             self.config.db_path.clone().into(),
@@ -160,7 +191,12 @@ impl MetadataCalculator {
         );
         self.tree_reader.send_replace(Some(tree_reader));
 
-        let updater = TreeUpdater::new(tree, self.max_l1_batches_per_iter, self.object_store);
+        let updater = TreeUpdater::new(
+            tree,
+            self.max_l1_batches_per_iter,
+            self.object_store,
+            self.backup_handle,
+        );
         updater
             .loop_updating_tree(self.delayer, &pool, stop_receiver, self.health_updater)
             .await
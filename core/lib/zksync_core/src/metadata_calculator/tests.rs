@@ -24,6 +24,7 @@ use zksync_utils::u32_to_h256;
 use super::{GenericAsyncTree, L1BatchWithLogs, MetadataCalculator, MetadataCalculatorConfig};
 use crate::{
     genesis::{ensure_genesis_state, GenesisParams},
+    rocksdb_backup::{RocksdbBackupManifest, RocksdbBackupUploader, RocksdbKind},
     utils::testonly::{create_l1_batch, create_miniblock},
 };
 
@@ -236,6 +237,55 @@ async fn running_metadata_calculator_with_additional_blocks() {
     assert_eq!(root_hash_for_full_tree, updated_root_hash);
 }
 
+#[tokio::test]
+async fn backing_up_and_restoring_tree() {
+    let pool = ConnectionPool::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let backup_store = ObjectStoreFactory::mock().create_store().await;
+
+    let calculator = setup_lightweight_calculator(temp_dir.path(), &pool).await;
+    let (uploader, backup_handle) = RocksdbBackupUploader::new(
+        RocksdbKind::MerkleTree,
+        Path::new(&calculator.config.db_path),
+        Duration::ZERO,
+        backup_store.clone(),
+    );
+    let calculator = calculator.with_backups(backup_handle);
+    reset_db_state(&pool, 5).await;
+    run_calculator(calculator, pool.clone()).await;
+    // The backup handle is dropped together with the calculator, so the uploader will terminate
+    // after uploading all scheduled checkpoints.
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    run_with_timeout(RUN_TIMEOUT, uploader.run(stop_receiver))
+        .await
+        .unwrap();
+
+    let manifest =
+        RocksdbBackupManifest::load_latest(backup_store.as_ref(), RocksdbKind::MerkleTree)
+            .await
+            .unwrap()
+            .expect("no tree backup");
+    assert_eq!(manifest.next_l1_batch_number, L1BatchNumber(6));
+
+    // Restore the tree in another directory and check that it's caught up with Postgres.
+    let new_logs = gen_storage_logs(100..200, 10);
+    extend_db_state(&mut pool.access_storage().await.unwrap(), new_logs).await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let calculator = setup_lightweight_calculator(temp_dir.path(), &pool)
+        .await
+        .with_restore_from_backup(backup_store.clone());
+    let tree = calculator.create_tree().await.unwrap();
+    let GenericAsyncTree::Ready(tree) = tree else {
+        panic!("Unexpected tree state: {tree:?}");
+    };
+    assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(6));
+    drop(tree);
+
+    let calculator = setup_lightweight_calculator(temp_dir.path(), &pool).await;
+    let root_hash = run_calculator(calculator, pool.clone()).await;
+    assert_eq!(root_hash, expected_tree_hash(&pool).await);
+}
+
 #[tokio::test]
 async fn shutting_down_calculator() {
     let pool = ConnectionPool::test_pool().await;
@@ -20,13 +20,14 @@ use super::{
     metrics::{TreeUpdateStage, METRICS},
     MetadataCalculator,
 };
-use crate::utils::wait_for_l1_batch;
+use crate::{rocksdb_backup::RocksdbBackupHandle, utils::wait_for_l1_batch};
 
 #[derive(Debug)]
 pub(super) struct TreeUpdater {
     tree: AsyncTree,
     max_l1_batches_per_iter: usize,
     object_store: Option<Arc<dyn ObjectStore>>,
+    backup_handle: Option<RocksdbBackupHandle>,
 }
 
 impl TreeUpdater {
@@ -34,11 +35,13 @@ impl TreeUpdater {
         tree: AsyncTree,
         max_l1_batches_per_iter: usize,
         object_store: Option<Arc<dyn ObjectStore>>,
+        backup_handle: Option<RocksdbBackupHandle>,
     ) -> Self {
         Self {
             tree,
             max_l1_batches_per_iter,
             object_store,
+            backup_handle,
         }
     }
 
@@ -195,6 +198,26 @@ impl TreeUpdater {
         }
     }
 
+    /// Creates a tree checkpoint to be backed up if backups are enabled and a backup is due.
+    /// Backup errors are not fatal for the tree and are only logged.
+    async fn backup_if_due(&mut self) {
+        let Some(backup_handle) = &mut self.backup_handle else {
+            return;
+        };
+        let tree = &mut self.tree;
+        let next_l1_batch_number = tree.next_l1_batch_number();
+        let result = backup_handle
+            .backup_if_due(next_l1_batch_number, move |path| {
+                tree.create_checkpoint(path)
+            })
+            .await;
+        if let Err(err) = result {
+            tracing::warn!(
+                "Failed backing up Merkle tree at L1 batch #{next_l1_batch_number}: {err:#}"
+            );
+        }
+    }
+
     /// The processing loop for this updater.
     pub async fn loop_updating_tree(
         mut self,
@@ -281,6 +304,7 @@ impl TreeUpdater {
 
             let snapshot = *next_l1_batch_to_seal;
             self.step(storage, &mut next_l1_batch_to_seal).await;
+            self.backup_if_due().await;
             let delay = if snapshot == *next_l1_batch_to_seal {
                 tracing::trace!(
                     "Metadata calculator (next L1 batch: #{next_l1_batch_to_seal}) \
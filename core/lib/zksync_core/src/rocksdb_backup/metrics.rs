@@ -0,0 +1,27 @@
+//! Metrics for RocksDB backups.
+
+use std::time::Duration;
+
+use vise::{Buckets, Counter, Family, Gauge, Histogram, Metrics};
+
+use super::RocksdbKind;
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_rocksdb_backup")]
+pub(super) struct RocksdbBackupMetrics {
+    /// Latency of uploading a backup to the object store.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub upload_latency: Family<RocksdbKind, Histogram<Duration>>,
+    /// Number of failed backup uploads.
+    pub failed_uploads: Family<RocksdbKind, Counter>,
+    /// Size of the latest uploaded backup in bytes.
+    pub size: Family<RocksdbKind, Gauge<u64>>,
+    /// Next L1 batch number of the latest uploaded backup.
+    pub next_l1_batch_number: Family<RocksdbKind, Gauge<u64>>,
+    /// Latency of restoring a RocksDB instance from a backup.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub restore_latency: Family<RocksdbKind, Histogram<Duration>>,
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<RocksdbBackupMetrics> = vise::Global::new();
@@ -0,0 +1,414 @@
+//! Online backups for RocksDB instances maintained by the node, i.e. the state keeper cache and the Merkle tree.
+//!
+//! Backups are based on RocksDB checkpoints. A checkpoint is created by the component owning a RocksDB instance
+//! via [`RocksdbBackupHandle`] at most once per the configured interval; this is cheap because checkpoint files
+//! are hard links to the live DB files. Checkpoints are then uploaded to the object store in the background
+//! by [`RocksdbBackupUploader`], together with a [manifest](RocksdbBackupManifest) recording the L1 batch
+//! the backup corresponds to. Only the latest backup is retained for each instance.
+//!
+//! On node startup, a component with an empty RocksDB directory can [restore](restore_from_backup()) it
+//! from the latest backup. The restored instance is then caught up with Postgres in the same way
+//! as a lagging local instance.
+
+use std::{
+    future::Future,
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context as _;
+use serde::{Deserialize, Serialize};
+use tokio::{
+    fs,
+    sync::{mpsc, watch},
+};
+use vise::{EncodeLabelSet, EncodeLabelValue};
+use zksync_object_store::{Bucket, ObjectStore, ObjectStoreError};
+use zksync_types::L1BatchNumber;
+
+use self::metrics::METRICS;
+
+mod metrics;
+#[cfg(test)]
+mod tests;
+
+/// Kind of RocksDB instance that can be backed up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "db", rename_all = "snake_case")]
+pub enum RocksdbKind {
+    /// State keeper cache (aka secondary storage).
+    StateKeeperCache,
+    /// Merkle tree.
+    MerkleTree,
+}
+
+impl RocksdbKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::StateKeeperCache => "state_keeper_cache",
+            Self::MerkleTree => "merkle_tree",
+        }
+    }
+
+    // Keys are flat since some object store implementations (e.g., the file-backed one) don't support nesting.
+    fn manifest_key(self) -> String {
+        format!("{}_latest_manifest.json", self.as_str())
+    }
+
+    fn file_key(self, next_l1_batch_number: L1BatchNumber, file_name: &str) -> String {
+        format!(
+            "{}_l1_batch_{next_l1_batch_number}_{file_name}",
+            self.as_str()
+        )
+    }
+}
+
+/// Manifest of a RocksDB backup stored in the object store.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RocksdbBackupManifest {
+    /// Next L1 batch to be processed by the backed up RocksDB instance (i.e., the last processed L1 batch + 1).
+    pub next_l1_batch_number: L1BatchNumber,
+    /// Names of the RocksDB checkpoint files constituting the backup.
+    pub files: Vec<String>,
+    /// Total size of the checkpoint files in bytes.
+    pub size: u64,
+}
+
+impl RocksdbBackupManifest {
+    /// Loads the manifest of the latest backup for the specified RocksDB instance.
+    pub async fn load_latest(
+        object_store: &dyn ObjectStore,
+        kind: RocksdbKind,
+    ) -> anyhow::Result<Option<Self>> {
+        let raw_manifest = match object_store
+            .get_raw(Bucket::RocksdbBackups, &kind.manifest_key())
+            .await
+        {
+            Ok(raw_manifest) => raw_manifest,
+            Err(ObjectStoreError::KeyNotFound(_)) => return Ok(None),
+            Err(err) => {
+                return Err(anyhow::Error::from(err))
+                    .with_context(|| format!("failed loading {kind:?} backup manifest"));
+            }
+        };
+        serde_json::from_slice(&raw_manifest)
+            .map(Some)
+            .with_context(|| format!("failed deserializing {kind:?} backup manifest"))
+    }
+}
+
+/// RocksDB checkpoint awaiting upload.
+#[derive(Debug)]
+struct RocksdbCheckpoint {
+    path: PathBuf,
+    next_l1_batch_number: L1BatchNumber,
+}
+
+/// Handle used by a component owning a RocksDB instance to create checkpoints for backups.
+#[derive(Debug)]
+pub struct RocksdbBackupHandle {
+    kind: RocksdbKind,
+    checkpoints_dir: PathBuf,
+    interval: Duration,
+    last_checkpoint: Option<(Instant, L1BatchNumber)>,
+    sender: mpsc::Sender<RocksdbCheckpoint>,
+}
+
+impl RocksdbBackupHandle {
+    /// Creates a checkpoint and schedules it for upload if a backup is due, i.e., if the backup interval has elapsed
+    /// since the previous checkpoint, the RocksDB instance has progressed since then, and the uploader is not
+    /// lagging behind.
+    ///
+    /// `create_checkpoint` must create a checkpoint at the provided path (which doesn't exist) corresponding
+    /// to `next_l1_batch_number`. It is only called if a backup is due.
+    pub async fn backup_if_due<F, Fut>(
+        &mut self,
+        next_l1_batch_number: L1BatchNumber,
+        create_checkpoint: F,
+    ) -> anyhow::Result<()>
+    where
+        F: FnOnce(PathBuf) -> Fut,
+        Fut: Future<Output = anyhow::Result<()>>,
+    {
+        if let Some((created_at, l1_batch_number)) = self.last_checkpoint {
+            if created_at.elapsed() < self.interval || l1_batch_number == next_l1_batch_number {
+                return Ok(());
+            }
+        } else {
+            // Remove checkpoints remaining from the previous node run (e.g., if the node was terminated
+            // during upload). This is safe since no checkpoints are scheduled for upload yet.
+            remove_dir_if_exists(&self.checkpoints_dir).await?;
+        }
+
+        let permit = match self.sender.try_reserve() {
+            Ok(permit) => permit,
+            Err(mpsc::error::TrySendError::Full(())) => {
+                tracing::info!(
+                    "Skipping {kind:?} backup for L1 batch #{next_l1_batch_number} since previous backups \
+                     are still being uploaded",
+                    kind = self.kind
+                );
+                return Ok(());
+            }
+            Err(mpsc::error::TrySendError::Closed(())) => {
+                tracing::info!("RocksDB backup uploader is stopped; skipping backup");
+                return Ok(());
+            }
+        };
+
+        let path = self
+            .checkpoints_dir
+            .join(format!("l1_batch_{next_l1_batch_number}"));
+        fs::create_dir_all(&self.checkpoints_dir)
+            .await
+            .with_context(|| {
+                format!(
+                    "failed creating checkpoints directory `{}`",
+                    self.checkpoints_dir.display()
+                )
+            })?;
+        let result = create_checkpoint(path.clone()).await;
+        // Failed checkpoints are not retried until the backup interval elapses.
+        self.last_checkpoint = Some((Instant::now(), next_l1_batch_number));
+        result.with_context(|| format!("failed creating {:?} checkpoint", self.kind))?;
+        permit.send(RocksdbCheckpoint {
+            path,
+            next_l1_batch_number,
+        });
+        Ok(())
+    }
+}
+
+/// Task uploading RocksDB checkpoints created via [`RocksdbBackupHandle`] to the object store.
+///
+/// Failed uploads are logged and reported via metrics, but are not fatal; the next backup will be attempted
+/// after the backup interval elapses.
+#[derive(Debug)]
+pub struct RocksdbBackupUploader {
+    kind: RocksdbKind,
+    object_store: Arc<dyn ObjectStore>,
+    receiver: mpsc::Receiver<RocksdbCheckpoint>,
+}
+
+impl RocksdbBackupUploader {
+    /// Creates an uploader for the RocksDB instance located at `db_path`, and a handle that should be passed
+    /// to the component owning the instance.
+    pub fn new(
+        kind: RocksdbKind,
+        db_path: &Path,
+        interval: Duration,
+        object_store: Arc<dyn ObjectStore>,
+    ) -> (Self, RocksdbBackupHandle) {
+        // Capacity 1 means that at most one checkpoint can be queued while another one is being uploaded.
+        let (sender, receiver) = mpsc::channel(1);
+        let this = Self {
+            kind,
+            object_store,
+            receiver,
+        };
+        let handle = RocksdbBackupHandle {
+            kind,
+            // Checkpoints are placed next to the DB so that they can use hard links to DB files.
+            checkpoints_dir: sibling_dir(db_path, "_checkpoints"),
+            interval,
+            last_checkpoint: None,
+            sender,
+        };
+        (this, handle)
+    }
+
+    pub async fn run(mut self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let kind = self.kind;
+        loop {
+            let checkpoint = tokio::select! {
+                _ = stop_receiver.changed() => break,
+                checkpoint = self.receiver.recv() => checkpoint,
+            };
+            let Some(checkpoint) = checkpoint else {
+                tracing::info!("{kind:?} backup handle is dropped; stopping backup uploader");
+                return Ok(());
+            };
+
+            let latency = METRICS.upload_latency[&kind].start();
+            match self.upload(&checkpoint).await {
+                Ok(manifest) => {
+                    let latency = latency.observe();
+                    METRICS.size[&kind].set(manifest.size);
+                    METRICS.next_l1_batch_number[&kind].set(manifest.next_l1_batch_number.0.into());
+                    tracing::info!(
+                        "Uploaded {kind:?} backup for L1 batch #{next_l1_batch_number} ({size}B in {file_count} files) \
+                         in {latency:?}",
+                        next_l1_batch_number = manifest.next_l1_batch_number,
+                        size = manifest.size,
+                        file_count = manifest.files.len()
+                    );
+                }
+                Err(err) => {
+                    METRICS.failed_uploads[&kind].inc();
+                    tracing::warn!(
+                        "Failed uploading {kind:?} backup for L1 batch #{}: {err:#}",
+                        checkpoint.next_l1_batch_number
+                    );
+                }
+            }
+            // The checkpoint may be removed on the next node run, so failing to remove it is not critical.
+            if let Err(err) = remove_dir_if_exists(&checkpoint.path).await {
+                tracing::warn!(
+                    "Failed removing {kind:?} checkpoint at `{}`: {err:#}",
+                    checkpoint.path.display()
+                );
+            }
+        }
+        tracing::info!("Stop signal received, {kind:?} backup uploader is shutting down");
+        Ok(())
+    }
+
+    async fn upload(
+        &self,
+        checkpoint: &RocksdbCheckpoint,
+    ) -> anyhow::Result<RocksdbBackupManifest> {
+        let kind = self.kind;
+        let next_l1_batch_number = checkpoint.next_l1_batch_number;
+        let mut files = vec![];
+        let mut size = 0;
+        let mut entries = fs::read_dir(&checkpoint.path)
+            .await
+            .with_context(|| format!("failed listing `{}`", checkpoint.path.display()))?;
+        while let Some(entry) = entries.next_entry().await? {
+            let file_name = entry.file_name().into_string().map_err(|name| {
+                anyhow::anyhow!("checkpoint contains file with non-UTF-8 name: {name:?}")
+            })?;
+            let data = fs::read(entry.path())
+                .await
+                .with_context(|| format!("failed reading checkpoint file `{file_name}`"))?;
+            size += data.len() as u64;
+            let key = kind.file_key(next_l1_batch_number, &file_name);
+            self.object_store
+                .put_raw(Bucket::RocksdbBackups, &key, data)
+                .await
+                .with_context(|| format!("failed uploading checkpoint file `{file_name}`"))?;
+            files.push(file_name);
+        }
+        files.sort_unstable();
+
+        let manifest = RocksdbBackupManifest {
+            next_l1_batch_number,
+            files,
+            size,
+        };
+        let prev_manifest =
+            RocksdbBackupManifest::load_latest(self.object_store.as_ref(), kind).await?;
+        // The manifest is uploaded after all files, so that it never references a partially uploaded backup.
+        let raw_manifest =
+            serde_json::to_vec(&manifest).context("failed serializing backup manifest")?;
+        self.object_store
+            .put_raw(Bucket::RocksdbBackups, &kind.manifest_key(), raw_manifest)
+            .await
+            .context("failed uploading backup manifest")?;
+
+        // Files of the previous backup are no longer referenced and can be removed. If the previous backup
+        // has the same L1 batch number, its files were overwritten by the new backup.
+        let prev_manifest =
+            prev_manifest.filter(|prev| prev.next_l1_batch_number != next_l1_batch_number);
+        if let Some(prev_manifest) = prev_manifest {
+            for file_name in &prev_manifest.files {
+                let key = kind.file_key(prev_manifest.next_l1_batch_number, file_name);
+                if let Err(err) = self
+                    .object_store
+                    .remove_raw(Bucket::RocksdbBackups, &key)
+                    .await
+                {
+                    tracing::warn!("Failed removing obsolete backup file `{key}`: {err}");
+                }
+            }
+        }
+        Ok(manifest)
+    }
+}
+
+/// Restores a RocksDB instance at `db_path` from the latest backup in the object store. The restore is skipped
+/// if the DB directory exists and is not empty, or if there are no backups for the instance.
+///
+/// # Return value
+///
+/// Returns the manifest of the restored backup, or `None` if the restore was skipped.
+pub async fn restore_from_backup(
+    object_store: &dyn ObjectStore,
+    kind: RocksdbKind,
+    db_path: &Path,
+) -> anyhow::Result<Option<RocksdbBackupManifest>> {
+    if !is_empty_dir(db_path).await? {
+        tracing::info!(
+            "{kind:?} RocksDB at `{}` is not empty; skipping restore from backup",
+            db_path.display()
+        );
+        return Ok(None);
+    }
+    let Some(manifest) = RocksdbBackupManifest::load_latest(object_store, kind).await? else {
+        tracing::info!("No {kind:?} backups in the object store; skipping restore from backup");
+        return Ok(None);
+    };
+
+    let latency = METRICS.restore_latency[&kind].start();
+    let next_l1_batch_number = manifest.next_l1_batch_number;
+    tracing::info!(
+        "Restoring {kind:?} RocksDB at `{}` from backup for L1 batch #{next_l1_batch_number} ({}B)",
+        db_path.display(),
+        manifest.size
+    );
+    // Files are downloaded to a temporary directory, so that a partially restored DB is never opened.
+    let restore_dir = sibling_dir(db_path, "_restore");
+    remove_dir_if_exists(&restore_dir).await?;
+    fs::create_dir_all(&restore_dir)
+        .await
+        .with_context(|| format!("failed creating `{}`", restore_dir.display()))?;
+    for file_name in &manifest.files {
+        let key = kind.file_key(next_l1_batch_number, file_name);
+        let data = object_store
+            .get_raw(Bucket::RocksdbBackups, &key)
+            .await
+            .with_context(|| {
+                format!(
+                    "failed downloading backup file `{key}`; the backup may have been replaced concurrently"
+                )
+            })?;
+        fs::write(restore_dir.join(file_name), data)
+            .await
+            .with_context(|| format!("failed writing backup file `{file_name}`"))?;
+    }
+    remove_dir_if_exists(db_path).await?;
+    fs::rename(&restore_dir, db_path)
+        .await
+        .with_context(|| format!("failed moving restored DB to `{}`", db_path.display()))?;
+
+    let latency = latency.observe();
+    tracing::info!(
+        "Restored {kind:?} RocksDB at `{}` from backup for L1 batch #{next_l1_batch_number} in {latency:?}",
+        db_path.display()
+    );
+    Ok(Some(manifest))
+}
+
+fn sibling_dir(path: &Path, suffix: &str) -> PathBuf {
+    let mut dir_name = path.file_name().unwrap_or_default().to_os_string();
+    dir_name.push(suffix);
+    path.with_file_name(dir_name)
+}
+
+async fn is_empty_dir(path: &Path) -> anyhow::Result<bool> {
+    match fs::read_dir(path).await {
+        Ok(mut entries) => Ok(entries.next_entry().await?.is_none()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(true),
+        Err(err) => Err(err).with_context(|| format!("failed reading `{}`", path.display())),
+    }
+}
+
+async fn remove_dir_if_exists(path: &Path) -> anyhow::Result<()> {
+    match fs::remove_dir_all(path).await {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err).with_context(|| format!("failed removing `{}`", path.display())),
+    }
+}
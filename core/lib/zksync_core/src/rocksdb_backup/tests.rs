@@ -0,0 +1,205 @@
+//! Tests for RocksDB backups.
+
+use assert_matches::assert_matches;
+use tempfile::TempDir;
+use zksync_merkle_tree::{domain::ZkSyncTree, RocksDBWrapper, TreeInstruction};
+use zksync_object_store::ObjectStoreFactory;
+use zksync_types::{AccountTreeId, Address, StorageKey, H256};
+
+use super::*;
+
+fn create_tree(path: &Path, l1_batch_count: u32) -> ZkSyncTree {
+    let db = RocksDBWrapper::new(path).unwrap();
+    let mut tree = ZkSyncTree::new_lightweight(db);
+    for number in 0..l1_batch_count {
+        let instructions: Vec<_> = (0..10_u64)
+            .map(|i| {
+                let key = StorageKey::new(
+                    AccountTreeId::new(Address::repeat_byte(1)),
+                    H256::from_low_u64_be(u64::from(number) * 10 + i),
+                );
+                let leaf_index = u64::from(number) * 10 + i + 1;
+                TreeInstruction::write(key, leaf_index, H256::repeat_byte(number as u8 + 1))
+            })
+            .collect();
+        tree.process_l1_batch(&instructions);
+    }
+    tree.save();
+    tree
+}
+
+async fn backup_tree(handle: &mut RocksdbBackupHandle, tree: &ZkSyncTree) {
+    handle
+        .backup_if_due(tree.next_l1_batch_number(), |path| async move {
+            tree.create_checkpoint(&path).map_err(anyhow::Error::from)
+        })
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn backing_up_and_restoring_merkle_tree() {
+    let temp_dir = TempDir::new().unwrap();
+    let tree_path = temp_dir.path().join("tree");
+    let tree = create_tree(&tree_path, 3);
+    let object_store = ObjectStoreFactory::mock().create_store().await;
+    let (uploader, mut handle) = RocksdbBackupUploader::new(
+        RocksdbKind::MerkleTree,
+        &tree_path,
+        Duration::ZERO,
+        object_store.clone(),
+    );
+
+    backup_tree(&mut handle, &tree).await;
+    drop(handle);
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    uploader.run(stop_receiver).await.unwrap();
+
+    let manifest =
+        RocksdbBackupManifest::load_latest(object_store.as_ref(), RocksdbKind::MerkleTree)
+            .await
+            .unwrap()
+            .expect("no backup manifest");
+    assert_eq!(manifest.next_l1_batch_number, L1BatchNumber(3));
+    assert!(!manifest.files.is_empty());
+    assert!(manifest.size > 0);
+    // The checkpoint should be removed after upload.
+    assert!(is_empty_dir(&temp_dir.path().join("tree_checkpoints"))
+        .await
+        .unwrap());
+    // The backup is specific to the DB kind.
+    let state_keeper_manifest =
+        RocksdbBackupManifest::load_latest(object_store.as_ref(), RocksdbKind::StateKeeperCache)
+            .await
+            .unwrap();
+    assert!(state_keeper_manifest.is_none());
+
+    let restored_path = temp_dir.path().join("restored_tree");
+    let restored_manifest = restore_from_backup(
+        object_store.as_ref(),
+        RocksdbKind::MerkleTree,
+        &restored_path,
+    )
+    .await
+    .unwrap();
+    assert_eq!(restored_manifest, Some(manifest));
+
+    let restored_tree = ZkSyncTree::new_lightweight(RocksDBWrapper::new(&restored_path).unwrap());
+    assert_eq!(restored_tree.next_l1_batch_number(), L1BatchNumber(3));
+    assert_eq!(restored_tree.root_hash(), tree.root_hash());
+}
+
+async fn unexpected_checkpoint(path: PathBuf) -> anyhow::Result<()> {
+    panic!("unexpected checkpoint at `{}`", path.display());
+}
+
+#[tokio::test]
+async fn backups_are_only_created_when_due() {
+    let temp_dir = TempDir::new().unwrap();
+    let tree_path = temp_dir.path().join("tree");
+    let object_store = ObjectStoreFactory::mock().create_store().await;
+    let (mut uploader, mut handle) = RocksdbBackupUploader::new(
+        RocksdbKind::MerkleTree,
+        &tree_path,
+        Duration::from_secs(3_600),
+        object_store,
+    );
+
+    let tree = create_tree(&tree_path, 1);
+    backup_tree(&mut handle, &tree).await;
+    let checkpoint = uploader.receiver.try_recv().unwrap();
+    assert_eq!(checkpoint.next_l1_batch_number, L1BatchNumber(1));
+    assert!(!is_empty_dir(&checkpoint.path).await.unwrap());
+
+    // The backup interval has not elapsed yet.
+    handle
+        .backup_if_due(L1BatchNumber(2), unexpected_checkpoint)
+        .await
+        .unwrap();
+    assert!(uploader.receiver.try_recv().is_err());
+
+    // The backup interval has elapsed, but the DB hasn't progressed.
+    handle.interval = Duration::ZERO;
+    handle
+        .backup_if_due(L1BatchNumber(1), unexpected_checkpoint)
+        .await
+        .unwrap();
+    assert!(uploader.receiver.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn obsolete_backup_files_are_removed() {
+    let temp_dir = TempDir::new().unwrap();
+    let tree_path = temp_dir.path().join("tree");
+    let object_store = ObjectStoreFactory::mock().create_store().await;
+    let (mut uploader, mut handle) = RocksdbBackupUploader::new(
+        RocksdbKind::MerkleTree,
+        &tree_path,
+        Duration::ZERO,
+        object_store.clone(),
+    );
+
+    let mut tree = create_tree(&tree_path, 1);
+    backup_tree(&mut handle, &tree).await;
+    let checkpoint = uploader.receiver.recv().await.unwrap();
+    let old_manifest = uploader.upload(&checkpoint).await.unwrap();
+    assert_eq!(old_manifest.next_l1_batch_number, L1BatchNumber(1));
+
+    let instructions = [TreeInstruction::write(
+        StorageKey::new(AccountTreeId::new(Address::repeat_byte(2)), H256::zero()),
+        11,
+        H256::repeat_byte(0xff),
+    )];
+    tree.process_l1_batch(&instructions);
+    tree.save();
+    backup_tree(&mut handle, &tree).await;
+    let checkpoint = uploader.receiver.recv().await.unwrap();
+    let new_manifest = uploader.upload(&checkpoint).await.unwrap();
+    assert_eq!(new_manifest.next_l1_batch_number, L1BatchNumber(2));
+
+    let latest_manifest =
+        RocksdbBackupManifest::load_latest(object_store.as_ref(), RocksdbKind::MerkleTree)
+            .await
+            .unwrap();
+    assert_eq!(latest_manifest, Some(new_manifest));
+    for file_name in &old_manifest.files {
+        let key = RocksdbKind::MerkleTree.file_key(L1BatchNumber(1), file_name);
+        let err = object_store
+            .get_raw(Bucket::RocksdbBackups, &key)
+            .await
+            .unwrap_err();
+        assert_matches!(err, ObjectStoreError::KeyNotFound(_));
+    }
+}
+
+#[tokio::test]
+async fn restore_is_skipped_if_not_applicable() {
+    let temp_dir = TempDir::new().unwrap();
+    let tree_path = temp_dir.path().join("tree");
+    let object_store = ObjectStoreFactory::mock().create_store().await;
+
+    // No backups in the store.
+    let manifest = restore_from_backup(object_store.as_ref(), RocksdbKind::MerkleTree, &tree_path)
+        .await
+        .unwrap();
+    assert_eq!(manifest, None);
+    assert!(is_empty_dir(&tree_path).await.unwrap());
+
+    let tree = create_tree(&tree_path, 1);
+    let (mut uploader, mut handle) = RocksdbBackupUploader::new(
+        RocksdbKind::MerkleTree,
+        &tree_path,
+        Duration::ZERO,
+        object_store.clone(),
+    );
+    backup_tree(&mut handle, &tree).await;
+    let checkpoint = uploader.receiver.recv().await.unwrap();
+    uploader.upload(&checkpoint).await.unwrap();
+    drop(tree);
+
+    // The DB is not empty.
+    let manifest = restore_from_backup(object_store.as_ref(), RocksdbKind::MerkleTree, &tree_path)
+        .await
+        .unwrap();
+    assert_eq!(manifest, None);
+}
@@ -1,5 +1,6 @@
-use std::sync::Arc;
+use std::{path::Path, sync::Arc};
 
+use anyhow::Context as _;
 use async_trait::async_trait;
 use multivm::{
     interface::{
@@ -13,13 +14,18 @@ use multivm::{
 use once_cell::sync::OnceCell;
 use tokio::sync::{mpsc, watch};
 use zksync_dal::ConnectionPool;
+use zksync_object_store::ObjectStore;
 use zksync_state::{RocksdbStorage, StorageView, WriteStorage};
-use zksync_types::{vm_trace::Call, Transaction, U256};
+use zksync_types::{vm_trace::Call, L1BatchNumber, Transaction, VmVersion, U256};
 use zksync_utils::bytecode::CompressedBytecodeInfo;
 
-use super::{BatchExecutor, BatchExecutorHandle, Command, TxExecutionResult};
+use super::{
+    shadow::{ForkableStorage, ShadowVm, ShadowVmConfig},
+    BatchExecutor, BatchExecutorHandle, Command, TxExecutionResult,
+};
 use crate::{
     metrics::{InteractionType, TxStage, APP_METRICS},
+    rocksdb_backup::{restore_from_backup, RocksdbBackupHandle, RocksdbKind},
     state_keeper::{
         metrics::{TxExecutionStage, BATCH_TIP_METRICS, EXECUTOR_METRICS, KEEPER_METRICS},
         types::ExecutionMetricsForCriteria,
@@ -28,7 +34,7 @@ use crate::{
 
 /// The default implementation of [`BatchExecutor`].
 /// Creates a "real" batch executor which maintains the VM (as opposed to the test builder which doesn't use the VM).
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct MainBatchExecutor {
     state_keeper_db_path: String,
     pool: ConnectionPool,
@@ -37,6 +43,8 @@ pub struct MainBatchExecutor {
     upload_witness_inputs_to_gcs: bool,
     enum_index_migration_chunk_size: usize,
     optional_bytecode_compression: bool,
+    shadow_vm_version: Option<VmVersion>,
+    backup_handle: Option<RocksdbBackupHandle>,
 }
 
 impl MainBatchExecutor {
@@ -57,6 +65,80 @@ impl MainBatchExecutor {
             upload_witness_inputs_to_gcs,
             enum_index_migration_chunk_size,
             optional_bytecode_compression,
+            shadow_vm_version: None,
+            backup_handle: None,
+        }
+    }
+
+    /// Enables the shadow VM of the specified version. The shadow VM executes each transaction alongside the primary VM
+    /// and reports mismatches in execution results via metrics and Postgres, without influencing primary execution results.
+    /// Shadow execution happens on the same thread as the primary one, so it slows down transaction processing.
+    pub fn set_shadow_vm_version(&mut self, vm_version: VmVersion) {
+        self.shadow_vm_version = Some(vm_version);
+    }
+
+    /// Enables periodic backups of the state keeper cache using the provided handle. Backups are taken
+    /// when initializing L1 batches.
+    pub fn set_backup_handle(&mut self, backup_handle: RocksdbBackupHandle) {
+        self.backup_handle = Some(backup_handle);
+    }
+
+    /// Restores the state keeper cache from the latest backup in `object_store` if the cache is empty.
+    /// If the restored cache is ahead of Postgres (e.g., because Postgres was restored from an older snapshot),
+    /// it is rolled back to the last sealed L1 batch in Postgres. Otherwise, the cache is caught up with Postgres
+    /// when initializing the next L1 batch.
+    pub async fn restore_from_backup(&self, object_store: &dyn ObjectStore) -> anyhow::Result<()> {
+        let db_path = Path::new(&self.state_keeper_db_path);
+        let Some(manifest) =
+            restore_from_backup(object_store, RocksdbKind::StateKeeperCache, db_path).await?
+        else {
+            return Ok(());
+        };
+
+        let mut storage = self.pool.access_storage_tagged("state_keeper").await?;
+        let last_sealed_l1_batch = storage
+            .blocks_dal()
+            .get_sealed_l1_batch_number()
+            .await
+            .context("failed fetching sealed L1 batch number")?
+            .context("cannot use restored state keeper cache: Postgres contains no L1 batches")?;
+        if manifest.next_l1_batch_number > last_sealed_l1_batch + 1 {
+            tracing::warn!(
+                "State keeper cache restored from backup (next L1 batch: #{}) is ahead of Postgres \
+                 (last sealed L1 batch: #{last_sealed_l1_batch}); rolling it back",
+                manifest.next_l1_batch_number
+            );
+            RocksdbStorage::builder(db_path)
+                .await?
+                .rollback(&mut storage, last_sealed_l1_batch)
+                .await
+                .context("failed rolling back restored state keeper cache")?;
+        }
+        Ok(())
+    }
+
+    async fn backup_if_due(
+        &mut self,
+        storage: &RocksdbStorage,
+        next_l1_batch_number: L1BatchNumber,
+    ) {
+        let Some(backup_handle) = &mut self.backup_handle else {
+            return;
+        };
+        let result = backup_handle
+            .backup_if_due(next_l1_batch_number, |path| async move {
+                let checkpoint_l1_batch_number = storage.create_checkpoint(&path).await?;
+                anyhow::ensure!(
+                    checkpoint_l1_batch_number == Some(next_l1_batch_number),
+                    "unexpected L1 batch number in state keeper cache checkpoint: {checkpoint_l1_batch_number:?}"
+                );
+                Ok(())
+            })
+            .await;
+        if let Err(err) = result {
+            tracing::warn!(
+                "Failed backing up state keeper cache at L1 batch #{next_l1_batch_number}: {err:#}"
+            );
         }
     }
 }
@@ -82,6 +164,8 @@ impl BatchExecutor for MainBatchExecutor {
             .synchronize(&mut conn, stop_receiver)
             .await
             .expect("Failed synchronizing secondary state keeper storage")?;
+        self.backup_if_due(&secondary_storage, l1_batch_params.number)
+            .await;
 
         // Since we process `BatchExecutor` commands one-by-one (the next command is never enqueued
         // until a previous command is processed), capacity 1 is enough for the commands channel.
@@ -90,6 +174,9 @@ impl BatchExecutor for MainBatchExecutor {
             save_call_traces: self.save_call_traces,
             max_allowed_tx_gas_limit: self.max_allowed_tx_gas_limit,
             optional_bytecode_compression: self.optional_bytecode_compression,
+            shadow_vm_config: self
+                .shadow_vm_version
+                .map(|vm_version| ShadowVmConfig::new(vm_version, self.pool.clone())),
             commands: commands_receiver,
         };
         let upload_witness_inputs_to_gcs = self.upload_witness_inputs_to_gcs;
@@ -120,6 +207,7 @@ struct CommandReceiver {
     save_call_traces: bool,
     max_allowed_tx_gas_limit: U256,
     optional_bytecode_compression: bool,
+    shadow_vm_config: Option<ShadowVmConfig>,
     commands: mpsc::Receiver<Command>,
 }
 
@@ -133,23 +221,49 @@ impl CommandReceiver {
     ) {
         tracing::info!("Starting executing batch #{:?}", &l1_batch_params.number);
 
+        let secondary_storage = ForkableStorage::new(secondary_storage);
+        let mut shadow_vm = self.shadow_vm_config.take().map(|config| {
+            ShadowVm::new(
+                config,
+                l1_batch_params.clone(),
+                system_env.clone(),
+                secondary_storage.fork(),
+                self.max_allowed_tx_gas_limit,
+                self.optional_bytecode_compression,
+            )
+        });
         let storage_view = StorageView::new(secondary_storage).to_rc_ptr();
 
         let mut vm = VmInstance::new(l1_batch_params, system_env, storage_view.clone());
 
+        // The shadow VM runs after the primary result is sent, so that the state keeper can process the result
+        // concurrently with shadow execution. Still, the next command is only received once the shadow VM is done,
+        // so enabling it roughly doubles transaction execution latency.
         while let Some(cmd) = self.commands.blocking_recv() {
             match cmd {
                 Command::ExecuteTx(tx, resp) => {
                     let result = self.execute_tx(&tx, &mut vm);
+                    let primary_result = shadow_vm.is_some().then(|| result.clone());
                     resp.send(result).unwrap();
+                    if let (Some(shadow_vm), Some(primary_result)) =
+                        (&mut shadow_vm, primary_result)
+                    {
+                        shadow_vm.execute_tx(&tx, &primary_result);
+                    }
                 }
                 Command::RollbackLastTx(resp) => {
                     self.rollback_last_tx(&mut vm);
                     resp.send(()).unwrap();
+                    if let Some(shadow_vm) = &mut shadow_vm {
+                        shadow_vm.rollback_last_tx();
+                    }
                 }
                 Command::StartNextMiniblock(l2_block_env, resp) => {
                     self.start_next_miniblock(l2_block_env, &mut vm);
                     resp.send(()).unwrap();
+                    if let Some(shadow_vm) = &mut shadow_vm {
+                        shadow_vm.start_next_miniblock(l2_block_env);
+                    }
                 }
                 Command::FinishBatch(resp) => {
                     let vm_block_result = self.finish_batch(&mut vm);
@@ -158,7 +272,13 @@ impl CommandReceiver {
                     } else {
                         None
                     };
+                    let primary_result = shadow_vm.is_some().then(|| vm_block_result.clone());
                     resp.send((vm_block_result, witness_block_state)).unwrap();
+                    if let (Some(shadow_vm), Some(primary_result)) =
+                        (&mut shadow_vm, primary_result)
+                    {
+                        shadow_vm.finish_batch(&primary_result);
+                    }
 
                     // `storage_view` cannot be accessed while borrowed by the VM,
                     // so this is the only point at which storage metrics can be obtained
@@ -20,6 +20,7 @@ use crate::state_keeper::{
 mod tests;
 
 pub mod main_executor;
+mod shadow;
 
 /// Representation of a transaction executed in the virtual machine.
 #[derive(Debug, Clone)]
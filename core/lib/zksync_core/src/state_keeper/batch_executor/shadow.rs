@@ -0,0 +1,448 @@
+//! Shadow VM executing transactions alongside the primary VM in the batch executor.
+
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    rc::Rc,
+};
+
+use multivm::{
+    interface::{
+        ExecutionResult, FinishedL1Batch, Halt, L1BatchEnv, L2BlockEnv, SystemEnv,
+        VmExecutionResultAndLogs, VmInterface, VmInterfaceHistoryEnabled,
+    },
+    vm_latest::HistoryEnabled,
+    VmInstance,
+};
+use tokio::sync::mpsc;
+use zksync_dal::{shadow_vm_dal::ShadowVmMismatch, ConnectionPool};
+use zksync_state::{ReadStorage, StorageView};
+use zksync_types::{
+    zk_evm_types::LogQuery, Address, L1BatchNumber, StorageKey, StorageValue, Transaction,
+    VmVersion, H256, U256,
+};
+
+use super::TxExecutionResult;
+use crate::state_keeper::metrics::{ShadowVmMismatchKind, SHADOW_VM_METRICS};
+
+/// Read-only storage that can be shared among several [`StorageView`]s. Used to fork the storage view
+/// for the shadow VM at the start of an L1 batch.
+#[derive(Debug)]
+pub(super) struct ForkableStorage<S>(Rc<RefCell<S>>);
+
+impl<S> ForkableStorage<S> {
+    pub fn new(storage: S) -> Self {
+        Self(Rc::new(RefCell::new(storage)))
+    }
+
+    /// Returns another handle to the same underlying storage.
+    pub fn fork(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<S: ReadStorage> ReadStorage for ForkableStorage<S> {
+    fn read_value(&mut self, key: &StorageKey) -> StorageValue {
+        self.0.borrow_mut().read_value(key)
+    }
+
+    fn is_write_initial(&mut self, key: &StorageKey) -> bool {
+        self.0.borrow_mut().is_write_initial(key)
+    }
+
+    fn load_factory_dep(&mut self, hash: H256) -> Option<Vec<u8>> {
+        self.0.borrow_mut().load_factory_dep(hash)
+    }
+
+    fn get_enumeration_index(&mut self, key: &StorageKey) -> Option<u64> {
+        self.0.borrow_mut().get_enumeration_index(key)
+    }
+}
+
+/// Configuration of the shadow VM for a single L1 batch.
+#[derive(Debug)]
+pub(super) struct ShadowVmConfig {
+    pub vm_version: VmVersion,
+    pub mismatches_sender: mpsc::UnboundedSender<ShadowVmMismatch>,
+}
+
+impl ShadowVmConfig {
+    /// Creates a config together with a task persisting detected mismatches to Postgres. The task terminates
+    /// once the config (and the shadow VM created from it) is dropped.
+    pub fn new(vm_version: VmVersion, pool: ConnectionPool) -> Self {
+        let (mismatches_sender, mismatches_receiver) = mpsc::unbounded_channel();
+        tokio::spawn(persist_mismatches(pool, mismatches_receiver));
+        Self {
+            vm_version,
+            mismatches_sender,
+        }
+    }
+}
+
+async fn persist_mismatches(
+    pool: ConnectionPool,
+    mut mismatches_receiver: mpsc::UnboundedReceiver<ShadowVmMismatch>,
+) {
+    while let Some(mismatch) = mismatches_receiver.recv().await {
+        let result = match pool.access_storage_tagged("state_keeper").await {
+            Ok(mut storage) => storage
+                .shadow_vm_dal()
+                .insert_mismatch(&mismatch)
+                .await
+                .map_err(anyhow::Error::from),
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            SHADOW_VM_METRICS.persistence_errors.inc();
+            tracing::warn!(
+                "Failed persisting shadow VM mismatch for L1 batch #{}: {err:#}",
+                mismatch.l1_batch_number
+            );
+        }
+    }
+}
+
+/// Mismatch detected by comparing the primary and shadow VM outputs.
+#[derive(Debug)]
+struct Mismatch {
+    kind: ShadowVmMismatchKind,
+    primary: String,
+    shadow: String,
+}
+
+impl Mismatch {
+    fn new(
+        kind: ShadowVmMismatchKind,
+        primary: &impl fmt::Debug,
+        shadow: &impl fmt::Debug,
+    ) -> Self {
+        Self {
+            kind,
+            primary: format!("{primary:?}"),
+            shadow: format!("{shadow:?}"),
+        }
+    }
+}
+
+/// Shadow VM mirroring all commands executed by the primary VM over a forked storage view. After each command,
+/// the shadow VM output is compared with the primary one; mismatches are reported via metrics and persisted to Postgres.
+/// The shadow VM never influences the primary execution; e.g., if it panics, it is just disabled.
+///
+/// Once a mismatch is detected, the shadow VM state has diverged from the primary one, so the shadow VM
+/// is disabled for the rest of the L1 batch.
+pub(super) struct ShadowVm<S: ReadStorage + fmt::Debug> {
+    vm: Option<VmInstance<StorageView<S>, HistoryEnabled>>,
+    vm_version: VmVersion,
+    l1_batch_number: L1BatchNumber,
+    max_allowed_tx_gas_limit: U256,
+    optional_bytecode_compression: bool,
+    mismatches_sender: mpsc::UnboundedSender<ShadowVmMismatch>,
+}
+
+impl<S: ReadStorage + fmt::Debug> fmt::Debug for ShadowVm<S> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("ShadowVm")
+            .field("is_enabled", &self.vm.is_some())
+            .field("vm_version", &self.vm_version)
+            .field("l1_batch_number", &self.l1_batch_number)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: ReadStorage + fmt::Debug> ShadowVm<S> {
+    pub fn new(
+        config: ShadowVmConfig,
+        l1_batch_env: L1BatchEnv,
+        system_env: SystemEnv,
+        storage: S,
+        max_allowed_tx_gas_limit: U256,
+        optional_bytecode_compression: bool,
+    ) -> Self {
+        let l1_batch_number = l1_batch_env.number;
+        tracing::info!(
+            "Starting shadow VM {:?} for L1 batch #{l1_batch_number}",
+            config.vm_version
+        );
+        let storage_view = StorageView::new(storage).to_rc_ptr();
+        let vm = VmInstance::new_with_specific_version(
+            l1_batch_env,
+            system_env,
+            storage_view,
+            config.vm_version,
+        );
+        Self {
+            vm: Some(vm),
+            vm_version: config.vm_version,
+            l1_batch_number,
+            max_allowed_tx_gas_limit,
+            optional_bytecode_compression,
+            mismatches_sender: config.mismatches_sender,
+        }
+    }
+
+    /// Runs an action on the shadow VM catching panics.
+    fn run<R>(
+        &mut self,
+        tx_hash: Option<H256>,
+        action: impl FnOnce(&mut VmInstance<StorageView<S>, HistoryEnabled>) -> R,
+    ) -> Option<R> {
+        let vm = self.vm.as_mut()?;
+        match panic::catch_unwind(AssertUnwindSafe(|| action(vm))) {
+            Ok(output) => Some(output),
+            Err(panic) => {
+                let message = panic
+                    .downcast_ref::<&str>()
+                    .map(|&message| message.to_owned())
+                    .or_else(|| panic.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "(unknown panic)".to_owned());
+                let mismatch = Mismatch {
+                    kind: ShadowVmMismatchKind::Panic,
+                    primary: String::new(),
+                    shadow: message,
+                };
+                self.report(tx_hash, vec![mismatch]);
+                None
+            }
+        }
+    }
+
+    fn report(&mut self, tx_hash: Option<H256>, mismatches: Vec<Mismatch>) {
+        if mismatches.is_empty() {
+            return;
+        }
+
+        for mismatch in mismatches {
+            SHADOW_VM_METRICS.mismatches[&mismatch.kind].inc();
+            tracing::error!(
+                "Shadow VM {:?} mismatch ({}) in L1 batch #{}, tx {tx_hash:?}: primary={}, shadow={}",
+                self.vm_version,
+                mismatch.kind.as_str(),
+                self.l1_batch_number,
+                mismatch.primary,
+                mismatch.shadow
+            );
+            let mismatch = ShadowVmMismatch {
+                l1_batch_number: self.l1_batch_number,
+                tx_hash,
+                kind: mismatch.kind.as_str().to_owned(),
+                shadow_vm_version: format!("{:?}", self.vm_version),
+                details: serde_json::json!({
+                    "primary": mismatch.primary,
+                    "shadow": mismatch.shadow,
+                }),
+            };
+            // The receiver may be dropped if the node is shutting down; this is fine.
+            self.mismatches_sender.send(mismatch).ok();
+        }
+
+        SHADOW_VM_METRICS.disabled_batches.inc();
+        tracing::warn!(
+            "Disabling shadow VM for the rest of L1 batch #{}",
+            self.l1_batch_number
+        );
+        self.vm = None;
+    }
+
+    /// Executes a transaction in the shadow VM and compares the output with the `primary` execution result.
+    /// Mirrors the execution logic of the primary VM (sans tracers and block tip dry run).
+    pub fn execute_tx(&mut self, tx: &Transaction, primary: &TxExecutionResult) {
+        let max_allowed_tx_gas_limit = self.max_allowed_tx_gas_limit;
+        let optional_bytecode_compression = self.optional_bytecode_compression;
+        let tx_hash = tx.hash();
+        let shadow = self.run(Some(tx_hash), |vm| {
+            vm.make_snapshot();
+            if tx.gas_limit() > max_allowed_tx_gas_limit {
+                return None;
+            }
+
+            let latency = SHADOW_VM_METRICS.tx_execution_time.start();
+            let result = if optional_bytecode_compression {
+                vm.make_snapshot();
+                if let (Ok(()), result) =
+                    vm.execute_transaction_with_bytecode_compression(tx.clone(), true)
+                {
+                    vm.pop_snapshot_no_rollback();
+                    result
+                } else {
+                    vm.rollback_to_the_latest_snapshot();
+                    vm.execute_transaction_with_bytecode_compression(tx.clone(), false)
+                        .1
+                }
+            } else {
+                let (published_bytecodes, mut result) =
+                    vm.execute_transaction_with_bytecode_compression(tx.clone(), true);
+                if published_bytecodes.is_err() {
+                    result.result = ExecutionResult::Halt {
+                        reason: Halt::FailedToPublishCompressedBytecodes,
+                    };
+                }
+                result
+            };
+            latency.observe();
+            SHADOW_VM_METRICS.executed_txs.inc();
+            Some(result)
+        });
+
+        if let Some(Some(shadow)) = shadow {
+            let mismatches = compare_tx_results(primary, &shadow);
+            self.report(Some(tx_hash), mismatches);
+        }
+    }
+
+    pub fn rollback_last_tx(&mut self) {
+        self.run(None, |vm| vm.rollback_to_the_latest_snapshot());
+    }
+
+    pub fn start_next_miniblock(&mut self, l2_block_env: L2BlockEnv) {
+        self.run(None, |vm| vm.start_new_l2_block(l2_block_env));
+    }
+
+    /// Finishes the batch in the shadow VM and compares the output with the `primary` one.
+    pub fn finish_batch(&mut self, primary: &FinishedL1Batch) {
+        if let Some(shadow) = self.run(None, |vm| vm.finish_batch()) {
+            let mismatches = compare_finished_batches(primary, &shadow);
+            self.report(None, mismatches);
+        }
+    }
+}
+
+fn storage_writes<'a>(
+    queries: impl Iterator<Item = &'a LogQuery>,
+) -> BTreeMap<(Address, U256), U256> {
+    queries
+        .filter(|query| query.rw_flag && !query.rollback)
+        .map(|query| ((query.address, query.key), query.written_value))
+        .collect()
+}
+
+fn compare_tx_results(
+    primary: &TxExecutionResult,
+    shadow: &VmExecutionResultAndLogs,
+) -> Vec<Mismatch> {
+    let primary = match primary {
+        TxExecutionResult::Success { tx_result, .. } => tx_result,
+        TxExecutionResult::RejectedByVm { reason } => {
+            let expected = ExecutionResult::Halt {
+                reason: reason.clone(),
+            };
+            return compare_execution_results(&expected, &shadow.result);
+        }
+        TxExecutionResult::BootloaderOutOfGasForTx => {
+            let expected = ExecutionResult::Halt {
+                reason: Halt::BootloaderOutOfGas,
+            };
+            return compare_execution_results(&expected, &shadow.result);
+        }
+        // The primary transaction result is not available, so there's nothing to compare.
+        TxExecutionResult::BootloaderOutOfGasForBlockTip => return vec![],
+    };
+
+    let mut mismatches = compare_execution_results(&primary.result, &shadow.result);
+    let primary_refunds = (
+        primary.refunds.gas_refunded,
+        primary.refunds.operator_suggested_refund,
+    );
+    let shadow_refunds = (
+        shadow.refunds.gas_refunded,
+        shadow.refunds.operator_suggested_refund,
+    );
+    if primary_refunds != shadow_refunds {
+        mismatches.push(Mismatch::new(
+            ShadowVmMismatchKind::Refunds,
+            &primary_refunds,
+            &shadow_refunds,
+        ));
+    }
+    if primary.logs.events != shadow.logs.events {
+        mismatches.push(Mismatch::new(
+            ShadowVmMismatchKind::Events,
+            &primary.logs.events,
+            &shadow.logs.events,
+        ));
+    }
+    let primary_logs = (
+        &primary.logs.user_l2_to_l1_logs,
+        &primary.logs.system_l2_to_l1_logs,
+    );
+    let shadow_logs = (
+        &shadow.logs.user_l2_to_l1_logs,
+        &shadow.logs.system_l2_to_l1_logs,
+    );
+    if primary_logs != shadow_logs {
+        mismatches.push(Mismatch::new(
+            ShadowVmMismatchKind::L2ToL1Logs,
+            &primary_logs,
+            &shadow_logs,
+        ));
+    }
+    let primary_writes = storage_writes(primary.logs.storage_logs.iter().map(|log| &log.log_query));
+    let shadow_writes = storage_writes(shadow.logs.storage_logs.iter().map(|log| &log.log_query));
+    if primary_writes != shadow_writes {
+        mismatches.push(Mismatch::new(
+            ShadowVmMismatchKind::StorageWrites,
+            &primary_writes,
+            &shadow_writes,
+        ));
+    }
+    mismatches
+}
+
+fn compare_execution_results(primary: &ExecutionResult, shadow: &ExecutionResult) -> Vec<Mismatch> {
+    if primary == shadow {
+        vec![]
+    } else {
+        vec![Mismatch::new(
+            ShadowVmMismatchKind::ExecutionResult,
+            primary,
+            shadow,
+        )]
+    }
+}
+
+fn compare_finished_batches(primary: &FinishedL1Batch, shadow: &FinishedL1Batch) -> Vec<Mismatch> {
+    let mut mismatches = compare_execution_results(
+        &primary.block_tip_execution_result.result,
+        &shadow.block_tip_execution_result.result,
+    );
+    let primary_state = &primary.final_execution_state;
+    let shadow_state = &shadow.final_execution_state;
+    if primary_state.events != shadow_state.events {
+        mismatches.push(Mismatch::new(
+            ShadowVmMismatchKind::Events,
+            &primary_state.events,
+            &shadow_state.events,
+        ));
+    }
+    let primary_logs = (
+        &primary_state.user_l2_to_l1_logs,
+        &primary_state.system_logs,
+    );
+    let shadow_logs = (&shadow_state.user_l2_to_l1_logs, &shadow_state.system_logs);
+    if primary_logs != shadow_logs {
+        mismatches.push(Mismatch::new(
+            ShadowVmMismatchKind::L2ToL1Logs,
+            &primary_logs,
+            &shadow_logs,
+        ));
+    }
+    let primary_writes = storage_writes(primary_state.deduplicated_storage_log_queries.iter());
+    let shadow_writes = storage_writes(shadow_state.deduplicated_storage_log_queries.iter());
+    if primary_writes != shadow_writes {
+        mismatches.push(Mismatch::new(
+            ShadowVmMismatchKind::StorageWrites,
+            &primary_writes,
+            &shadow_writes,
+        ));
+    }
+    if primary.pubdata_input != shadow.pubdata_input {
+        mismatches.push(Mismatch::new(
+            ShadowVmMismatchKind::Pubdata,
+            &primary.pubdata_input,
+            &shadow.pubdata_input,
+        ));
+    }
+    mismatches
+}
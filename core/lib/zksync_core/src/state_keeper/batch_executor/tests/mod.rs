@@ -1,8 +1,15 @@
+use std::time::Duration;
+
 use assert_matches::assert_matches;
 use test_casing::test_casing;
 use zksync_dal::ConnectionPool;
+use zksync_object_store::ObjectStoreFactory;
+use zksync_state::ReadStorage;
 use zksync_test_account::Account;
-use zksync_types::{get_nonce_key, utils::storage_key_for_eth_balance, PriorityOpId};
+use zksync_types::{
+    get_nonce_key, utils::storage_key_for_eth_balance, AccountTreeId, Address, L1BatchNumber,
+    PriorityOpId, StorageKey, StorageLog, VmVersion, H256,
+};
 
 use self::tester::{AccountLoadNextExecutable, StorageSnapshot, TestConfig, Tester};
 use super::TxExecutionResult;
@@ -133,6 +140,85 @@ async fn execute_l2_and_l1_txs() {
     executor.finish_batch().await;
 }
 
+/// Checks that the shadow VM of the same version as the primary VM doesn't report mismatches.
+#[tokio::test]
+async fn shadow_vm_without_mismatches() {
+    let connection_pool = ConnectionPool::constrained_test_pool(2).await;
+    let mut alice = Account::random();
+    let mut bob = Account::random();
+
+    let mut config = TestConfig::new();
+    config.shadow_vm_version = Some(VmVersion::latest());
+    let tester = Tester::with_config(connection_pool, config);
+
+    tester.genesis().await;
+    tester.fund(&[alice.address()]).await;
+    let executor = tester.create_batch_executor().await;
+
+    let tx = alice.execute();
+    let res = executor.execute_tx(tx.clone()).await;
+    assert_executed(&res);
+    executor.rollback_last_tx().await;
+    let res = executor.execute_tx(tx).await;
+    assert_executed(&res);
+
+    // Bob's wallet is not funded, so the transaction is rejected.
+    let res = executor.execute_tx(bob.execute()).await;
+    assert_rejected(&res);
+    executor.rollback_last_tx().await;
+
+    let res = executor.execute_tx(alice.l1_execute(PriorityOpId(1))).await;
+    assert_executed(&res);
+    executor.finish_batch().await;
+
+    let mismatches = tester.shadow_vm_mismatches(L1BatchNumber(1)).await;
+    assert!(mismatches.is_empty(), "{mismatches:#?}");
+}
+
+/// Checks that a divergence between the primary and shadow VMs is detected and persisted in Postgres.
+#[tokio::test]
+async fn shadow_vm_mismatch_is_detected_and_persisted() {
+    let connection_pool = ConnectionPool::constrained_test_pool(3).await;
+    let mut alice = Account::random();
+
+    let tester = Tester::new(connection_pool);
+    tester.genesis().await;
+    tester.fund(&[alice.address()]).await;
+    let executor = tester.create_batch_executor().await;
+
+    let tx = alice.execute();
+    let mut res = executor.execute_tx(tx.clone()).await;
+    assert_executed(&res);
+    executor.finish_batch().await;
+
+    // Inject a divergence into the primary execution result.
+    let TxExecutionResult::Success { tx_result, .. } = &mut res else {
+        unreachable!();
+    };
+    tx_result.refunds.gas_refunded += 1;
+    tester
+        .execute_in_shadow_vm(VmVersion::latest(), tx.clone(), res)
+        .await;
+
+    // Mismatches are persisted asynchronously, so we need to poll for them.
+    let mismatches = tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            let mismatches = tester.shadow_vm_mismatches(L1BatchNumber(1)).await;
+            if !mismatches.is_empty() {
+                break mismatches;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("Timed out waiting for shadow VM mismatches");
+
+    assert_eq!(mismatches.len(), 1, "{mismatches:#?}");
+    assert_eq!(mismatches[0].kind, "refunds");
+    assert_eq!(mismatches[0].tx_hash, Some(tx.hash()));
+    assert_eq!(mismatches[0].l1_batch_number, L1BatchNumber(1));
+}
+
 /// Checks that we can successfully rollback the transaction and execute it once again.
 #[tokio::test]
 async fn rollback() {
@@ -178,6 +264,28 @@ async fn rollback() {
 }
 
 /// Checks that incorrect transactions are marked as rejected.
+/// Checks that a state keeper cache restored from a backup is rolled back if it's ahead of Postgres.
+#[tokio::test]
+async fn restoring_cache_ahead_of_postgres_from_backup() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    let tester = Tester::new(connection_pool);
+    tester.genesis().await;
+    let key = StorageKey::new(AccountTreeId::new(Address::repeat_byte(1)), H256::zero());
+    let storage_log = StorageLog::new_write_log(key, H256::repeat_byte(0xff));
+    tester
+        .seal_mock_l1_batch(L1BatchNumber(1), vec![storage_log])
+        .await;
+    let object_store = ObjectStoreFactory::mock().create_store().await;
+    tester.back_up_cache(object_store.clone()).await;
+
+    tester.delete_l1_batches(L1BatchNumber(0)).await;
+    // Synchronizing the restored cache would fail if it was not rolled back.
+    let mut cache = tester
+        .restore_cache_from_backup(object_store.as_ref())
+        .await;
+    assert_eq!(cache.read_value(&key), H256::zero());
+}
+
 #[tokio::test]
 async fn reject_tx() {
     let connection_pool = ConnectionPool::constrained_test_pool(1).await;
@@ -377,6 +485,7 @@ async fn bootloader_out_of_gas_for_any_tx() {
             max_allowed_tx_gas_limit: u32::MAX,
             validation_computational_gas_limit: u32::MAX,
             upload_witness_inputs_to_gcs: false,
+            shadow_vm_version: None,
         },
     );
 
@@ -414,6 +523,7 @@ async fn bootloader_tip_out_of_gas() {
         max_allowed_tx_gas_limit: u32::MAX,
         validation_computational_gas_limit: u32::MAX,
         upload_witness_inputs_to_gcs: false,
+        shadow_vm_version: None,
     });
 
     let second_executor = tester.create_batch_executor().await;
@@ -1,36 +1,42 @@
 //! Testing harness for the batch executor.
 //! Contains helper functionality to initialize test context and perform tests without too much boilerplate.
 
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use multivm::{
     interface::{L1BatchEnv, L2BlockEnv, SystemEnv},
     vm_latest::constants::INITIAL_STORAGE_WRITE_PUBDATA_BYTES,
 };
 use tempfile::TempDir;
-use tokio::sync::watch;
+use tokio::{runtime::Handle, sync::watch};
 use zksync_config::configs::chain::StateKeeperConfig;
 use zksync_contracts::{get_loadnext_contract, test_contracts::LoadnextContractExecutionParams};
-use zksync_dal::ConnectionPool;
+use zksync_dal::{shadow_vm_dal::ShadowVmMismatch, ConnectionPool};
+use zksync_object_store::ObjectStore;
+use zksync_state::{PostgresStorage, RocksdbStorage};
 use zksync_test_account::{Account, DeployContractsTx, TxType};
 use zksync_types::{
     block::MiniblockHasher, ethabi::Token, fee::Fee, snapshots::SnapshotRecoveryStatus,
     storage_writes_deduplicator::StorageWritesDeduplicator,
     system_contracts::get_system_smart_contracts, utils::storage_key_for_standard_token_balance,
     AccountTreeId, Address, Execute, L1BatchNumber, L2ChainId, MiniblockNumber, PriorityOpId,
-    ProtocolVersionId, StorageKey, StorageLog, Transaction, H256, L2_ETH_TOKEN_ADDRESS,
+    ProtocolVersionId, StorageKey, StorageLog, Transaction, VmVersion, H256, L2_ETH_TOKEN_ADDRESS,
     SYSTEM_CONTEXT_MINIMAL_BASE_FEE, U256,
 };
 use zksync_utils::u256_to_h256;
 
 use crate::{
     genesis::create_genesis_l1_batch,
+    rocksdb_backup::{RocksdbBackupUploader, RocksdbKind},
     state_keeper::{
-        batch_executor::{BatchExecutorHandle, TxExecutionResult},
+        batch_executor::{
+            shadow::{ShadowVm, ShadowVmConfig},
+            BatchExecutorHandle, TxExecutionResult,
+        },
         tests::{default_l1_batch_env, default_system_env, BASE_SYSTEM_CONTRACTS},
         BatchExecutor, MainBatchExecutor,
     },
-    utils::testonly::prepare_recovery_snapshot,
+    utils::testonly::{create_l1_batch, create_miniblock, prepare_recovery_snapshot},
 };
 
 const DEFAULT_GAS_PER_PUBDATA: u32 = 10000;
@@ -45,6 +51,7 @@ pub(super) struct TestConfig {
     pub(super) max_allowed_tx_gas_limit: u32,
     pub(super) validation_computational_gas_limit: u32,
     pub(super) upload_witness_inputs_to_gcs: bool,
+    pub(super) shadow_vm_version: Option<VmVersion>,
 }
 
 impl TestConfig {
@@ -57,6 +64,7 @@ impl TestConfig {
             max_allowed_tx_gas_limit: config.max_allowed_l2_tx_gas_limit,
             validation_computational_gas_limit: config.validation_computational_gas_limit,
             upload_witness_inputs_to_gcs: false,
+            shadow_vm_version: None,
         }
     }
 }
@@ -98,12 +106,8 @@ impl Tester {
             .await
     }
 
-    async fn create_batch_executor_inner(
-        &self,
-        l1_batch_env: L1BatchEnv,
-        system_env: SystemEnv,
-    ) -> BatchExecutorHandle {
-        let mut builder = MainBatchExecutor::new(
+    fn batch_executor_builder(&self) -> MainBatchExecutor {
+        MainBatchExecutor::new(
             self.db_dir.path().to_str().unwrap().to_owned(),
             self.pool.clone(),
             self.config.max_allowed_tx_gas_limit.into(),
@@ -111,7 +115,18 @@ impl Tester {
             self.config.upload_witness_inputs_to_gcs,
             100,
             false,
-        );
+        )
+    }
+
+    async fn create_batch_executor_inner(
+        &self,
+        l1_batch_env: L1BatchEnv,
+        system_env: SystemEnv,
+    ) -> BatchExecutorHandle {
+        let mut builder = self.batch_executor_builder();
+        if let Some(shadow_vm_version) = self.config.shadow_vm_version {
+            builder.set_shadow_vm_version(shadow_vm_version);
+        }
         let (_stop_sender, stop_receiver) = watch::channel(false);
         builder
             .init_batch(l1_batch_env, system_env, &stop_receiver)
@@ -119,6 +134,156 @@ impl Tester {
             .expect("Batch executor was interrupted")
     }
 
+    /// Returns shadow VM mismatches persisted for the specified L1 batch.
+    pub(super) async fn shadow_vm_mismatches(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> Vec<ShadowVmMismatch> {
+        let mut storage = self.pool.access_storage().await.unwrap();
+        storage
+            .shadow_vm_dal()
+            .get_mismatches(l1_batch_number)
+            .await
+            .unwrap()
+    }
+
+    /// Executes a transaction in a standalone shadow VM for the first L1 batch and compares the output
+    /// with the provided `primary` result. The shadow VM uses Postgres as storage.
+    pub(super) async fn execute_in_shadow_vm(
+        &self,
+        vm_version: VmVersion,
+        tx: Transaction,
+        primary: TxExecutionResult,
+    ) {
+        let (l1_batch_env, system_env) = self.batch_params(L1BatchNumber(1), 100);
+        let config = ShadowVmConfig::new(vm_version, self.pool.clone());
+        let max_allowed_tx_gas_limit = self.config.max_allowed_tx_gas_limit.into();
+        let pool = self.pool.clone();
+        let rt_handle = Handle::current();
+        tokio::task::spawn_blocking(move || {
+            let connection = rt_handle.block_on(pool.access_storage()).unwrap();
+            let storage = PostgresStorage::new(rt_handle, connection, MiniblockNumber(0), true);
+            let mut shadow_vm = ShadowVm::new(
+                config,
+                l1_batch_env,
+                system_env,
+                storage,
+                max_allowed_tx_gas_limit,
+                false,
+            );
+            shadow_vm.execute_tx(&tx, &primary);
+        })
+        .await
+        .unwrap();
+    }
+
+    /// Creates a state keeper cache synchronized with Postgres in a separate directory, and uploads its backup
+    /// to `object_store`.
+    pub(super) async fn back_up_cache(&self, object_store: Arc<dyn ObjectStore>) {
+        let cache_dir = TempDir::new().unwrap();
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let mut storage = self.pool.access_storage().await.unwrap();
+        let cache = RocksdbStorage::builder(cache_dir.path())
+            .await
+            .unwrap()
+            .synchronize(&mut storage, &stop_receiver)
+            .await
+            .unwrap()
+            .expect("cache synchronization was interrupted");
+        let last_sealed_l1_batch = storage
+            .blocks_dal()
+            .get_sealed_l1_batch_number()
+            .await
+            .unwrap()
+            .expect("no L1 batches in Postgres");
+
+        let (uploader, mut handle) = RocksdbBackupUploader::new(
+            RocksdbKind::StateKeeperCache,
+            cache_dir.path(),
+            Duration::ZERO,
+            object_store,
+        );
+        let cache = &cache;
+        handle
+            .backup_if_due(last_sealed_l1_batch + 1, |path| async move {
+                cache.create_checkpoint(&path).await?;
+                Ok(())
+            })
+            .await
+            .unwrap();
+        drop(handle);
+        uploader.run(stop_receiver).await.unwrap();
+    }
+
+    /// Restores the state keeper cache from the latest backup in `object_store` and synchronizes it with Postgres.
+    pub(super) async fn restore_cache_from_backup(
+        &self,
+        object_store: &dyn ObjectStore,
+    ) -> RocksdbStorage {
+        self.batch_executor_builder()
+            .restore_from_backup(object_store)
+            .await
+            .unwrap();
+
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let mut storage = self.pool.access_storage().await.unwrap();
+        RocksdbStorage::builder(self.db_dir.path())
+            .await
+            .unwrap()
+            .synchronize(&mut storage, &stop_receiver)
+            .await
+            .unwrap()
+            .expect("cache synchronization was interrupted")
+    }
+
+    /// Seals a mock L1 batch with a single miniblock containing the provided storage logs. All logs are treated
+    /// as initial writes.
+    pub(super) async fn seal_mock_l1_batch(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        storage_logs: Vec<StorageLog>,
+    ) {
+        let mut storage = self.pool.access_storage().await.unwrap();
+        let miniblock_number = MiniblockNumber(l1_batch_number.0);
+        storage
+            .blocks_dal()
+            .insert_miniblock(&create_miniblock(miniblock_number.0))
+            .await
+            .unwrap();
+        let written_keys: Vec<_> = storage_logs.iter().map(|log| log.key).collect();
+        storage
+            .storage_logs_dal()
+            .insert_storage_logs(miniblock_number, &[(H256::zero(), storage_logs)])
+            .await
+            .unwrap();
+        storage
+            .blocks_dal()
+            .insert_mock_l1_batch(&create_l1_batch(l1_batch_number.0))
+            .await
+            .unwrap();
+        storage
+            .blocks_dal()
+            .mark_miniblocks_as_executed_in_l1_batch(l1_batch_number)
+            .await
+            .unwrap();
+        storage
+            .storage_logs_dedup_dal()
+            .insert_initial_writes(l1_batch_number, &written_keys)
+            .await
+            .unwrap();
+    }
+
+    /// Removes L1 batches after `last_l1_batch_to_keep` from Postgres, emulating Postgres restored from
+    /// an older snapshot. Miniblocks and storage logs are retained.
+    pub(super) async fn delete_l1_batches(&self, last_l1_batch_to_keep: L1BatchNumber) {
+        let mut storage = self.pool.access_storage().await.unwrap();
+        storage
+            .blocks_dal()
+            .delete_l1_batches(last_l1_batch_to_keep)
+            .await
+            .unwrap();
+    }
+
     pub(super) async fn recover_batch_executor(
         &self,
         snapshot: &SnapshotRecoveryStatus,
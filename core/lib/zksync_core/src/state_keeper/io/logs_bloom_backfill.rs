@@ -0,0 +1,231 @@
+//! Backfilling logs blooms for miniblocks sealed before logs blooms were introduced.
+
+use std::time::Duration;
+
+use anyhow::Context as _;
+use tokio::sync::watch;
+use zksync_dal::ConnectionPool;
+use zksync_types::{block::build_bloom, MiniblockNumber};
+
+/// Backfills logs blooms for all miniblocks without them. Should be run as a background task.
+pub(crate) async fn backfill_logs_blooms(
+    pool: ConnectionPool,
+    stop_receiver: watch::Receiver<bool>,
+) -> anyhow::Result<()> {
+    let BackfillOutput {
+        miniblocks_affected,
+    } = backfill_logs_blooms_inner(pool, 10_000, Duration::from_secs(1), stop_receiver).await?;
+
+    tracing::info!("Finished logs bloom backfill with {miniblocks_affected} affected miniblocks");
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+struct BackfillOutput {
+    miniblocks_affected: u64,
+}
+
+/// Miniblocks are processed starting from the latest one without a bloom and moving back to the genesis,
+/// so that the more frequently queried recent miniblocks are backfilled first. New miniblocks always have
+/// their blooms set on sealing, so the backfilled range never grows.
+async fn backfill_logs_blooms_inner(
+    pool: ConnectionPool,
+    chunk_size: u32,
+    sleep_interval: Duration,
+    stop_receiver: watch::Receiver<bool>,
+) -> anyhow::Result<BackfillOutput> {
+    anyhow::ensure!(chunk_size > 0, "Chunk size must be positive");
+
+    let mut storage = pool.access_storage_tagged("state_keeper").await?;
+    let max_miniblock = storage
+        .blocks_dal()
+        .get_max_miniblock_without_logs_bloom()
+        .await
+        .context("Failed getting the latest miniblock without logs bloom")?;
+    drop(storage);
+    let Some(max_miniblock) = max_miniblock else {
+        tracing::info!("All miniblocks have logs blooms; no backfill is necessary");
+        return Ok(BackfillOutput::default());
+    };
+
+    let mut chunk_end = max_miniblock;
+    let mut miniblocks_affected = 0;
+    tracing::info!(
+        "Backfilling logs blooms for miniblocks ..={max_miniblock} in chunks of {chunk_size} miniblocks"
+    );
+    loop {
+        let chunk_start = MiniblockNumber(chunk_end.0.saturating_sub(chunk_size - 1));
+        let chunk = chunk_start..=chunk_end;
+        tracing::debug!("Backfilling logs blooms for miniblocks chunk {chunk:?}");
+
+        let mut storage = pool.access_storage_tagged("state_keeper").await?;
+        let mut bloom_items = storage
+            .events_dal()
+            .get_bloom_items_for_miniblocks(chunk.clone())
+            .await
+            .with_context(|| format!("Failed getting events for miniblocks chunk {chunk:?}"))?;
+        let blooms: Vec<_> = (chunk_start.0..=chunk_end.0)
+            .map(|number| {
+                let items = bloom_items
+                    .remove(&MiniblockNumber(number))
+                    .unwrap_or_default();
+                build_bloom(items.iter().map(Vec::as_slice))
+            })
+            .collect();
+        let rows_affected = storage
+            .blocks_dal()
+            .range_update_logs_bloom(chunk_start, &blooms)
+            .await
+            .with_context(|| format!("Failed backfilling logs blooms for chunk {chunk:?}"))?;
+        drop(storage);
+        tracing::debug!("Backfilled logs blooms for {rows_affected} miniblocks in chunk {chunk:?}");
+        miniblocks_affected += rows_affected;
+
+        if chunk_start == MiniblockNumber(0) {
+            break;
+        }
+        if *stop_receiver.borrow() {
+            tracing::info!("Stop signal received; logs bloom backfill shutting down");
+            break;
+        }
+        chunk_end = chunk_start - 1;
+        tokio::time::sleep(sleep_interval).await;
+    }
+
+    Ok(BackfillOutput {
+        miniblocks_affected,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use test_casing::test_casing;
+    use zksync_dal::{testonly::clear_logs_blooms, StorageProcessor};
+    use zksync_types::{
+        tx::IncludedTxLocation, Address, L1BatchNumber, ProtocolVersion, VmEvent, H2048, H256,
+    };
+
+    use super::*;
+    use crate::utils::testonly::create_miniblock;
+
+    fn create_event(miniblock_number: u32) -> VmEvent {
+        VmEvent {
+            location: (L1BatchNumber(1), 0),
+            address: Address::from_low_u64_be(miniblock_number.into()),
+            indexed_topics: vec![H256::repeat_byte(miniblock_number as u8)],
+            value: vec![],
+        }
+    }
+
+    /// Inserts miniblocks #0..5 with a single event each. Miniblocks #0..3 are inserted without blooms
+    /// (emulating miniblocks sealed before logs blooms were introduced).
+    async fn prepare_storage(storage: &mut StorageProcessor<'_>) {
+        storage
+            .protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion::default())
+            .await;
+        for number in 0..5 {
+            let event = create_event(number);
+            let mut miniblock = create_miniblock(number);
+            if number >= 3 {
+                miniblock.logs_bloom = build_bloom(event.bloom_items());
+            }
+            storage
+                .blocks_dal()
+                .insert_miniblock(&miniblock)
+                .await
+                .unwrap();
+
+            let location = IncludedTxLocation {
+                tx_hash: H256::zero(),
+                tx_index_in_miniblock: 0,
+                tx_initiator_address: Address::zero(),
+            };
+            storage
+                .events_dal()
+                .save_events(miniblock.number, &[(location, vec![&event])])
+                .await;
+        }
+
+        // Emulate miniblocks without blooms by clearing them.
+        clear_logs_blooms(&mut storage, MiniblockNumber(0)..=MiniblockNumber(2))
+            .await
+            .unwrap();
+    }
+
+    async fn assert_backfill(storage: &mut StorageProcessor<'_>) {
+        let blooms = storage
+            .blocks_web3_dal()
+            .get_logs_blooms(MiniblockNumber(0)..=MiniblockNumber(4))
+            .await
+            .unwrap();
+        assert_eq!(blooms.len(), 5);
+        for (number, bloom) in blooms {
+            let bloom = bloom.expect("no bloom");
+            let expected_bloom = build_bloom(create_event(number.0).bloom_items());
+            assert_eq!(bloom, expected_bloom, "miniblock #{number}");
+            assert_ne!(bloom, H2048::zero());
+        }
+    }
+
+    #[test_casing(3, [1, 2, 3])]
+    #[tokio::test]
+    async fn backfill_basics(chunk_size: u32) {
+        let pool = ConnectionPool::constrained_test_pool(1).await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage).await;
+        drop(storage);
+
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let result = backfill_logs_blooms_inner(
+            pool.clone(),
+            chunk_size,
+            Duration::ZERO,
+            stop_receiver.clone(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.miniblocks_affected, 3);
+
+        let mut storage = pool.access_storage().await.unwrap();
+        assert_backfill(&mut storage).await;
+        drop(storage);
+
+        // Check that the backfill can run again and is a no-op.
+        let result = backfill_logs_blooms_inner(pool, chunk_size, Duration::ZERO, stop_receiver)
+            .await
+            .unwrap();
+        assert_eq!(result.miniblocks_affected, 0);
+    }
+
+    #[test_casing(2, [1, 2])]
+    #[tokio::test]
+    async fn stopping_and_resuming_backfill(chunk_size: u32) {
+        let pool = ConnectionPool::constrained_test_pool(1).await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage).await;
+        drop(storage);
+
+        let (_stop_sender, stop_receiver) = watch::channel(true); // signal stop right away
+        let result = backfill_logs_blooms_inner(
+            pool.clone(),
+            chunk_size,
+            Duration::from_secs(1_000),
+            stop_receiver,
+        )
+        .await
+        .unwrap();
+        // Backfill should stop after a single chunk.
+        assert_eq!(result.miniblocks_affected, u64::from(chunk_size));
+
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let result =
+            backfill_logs_blooms_inner(pool.clone(), chunk_size, Duration::ZERO, stop_receiver)
+                .await
+                .unwrap();
+        assert_eq!(result.miniblocks_affected, 3 - u64::from(chunk_size));
+
+        let mut storage = pool.access_storage().await.unwrap();
+        assert_backfill(&mut storage).await;
+    }
+}
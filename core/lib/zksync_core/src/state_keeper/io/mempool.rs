@@ -13,6 +13,7 @@ use multivm::{
 };
 use vm_utils::storage::{l1_batch_params, L1BatchParamsProvider};
 use zksync_config::configs::chain::StateKeeperConfig;
+use zksync_contracts::BaseSystemContracts;
 use zksync_dal::ConnectionPool;
 use zksync_mempool::L2TxFilter;
 use zksync_object_store::ObjectStore;
@@ -24,6 +25,7 @@ use zksync_types::{
 use zksync_utils::time::millis_since_epoch;
 
 use crate::{
+    base_system_contracts::CustomBaseSystemContractsTracker,
     fee_model::BatchFeeModelInputProvider,
     state_keeper::{
         extractors,
@@ -67,6 +69,7 @@ pub struct MempoolIO {
 
     virtual_blocks_interval: u32,
     virtual_blocks_per_miniblock: u32,
+    custom_base_system_contracts: Option<CustomBaseSystemContractsTracker>,
 }
 
 impl IoSealCriteria for MempoolIO {
@@ -179,6 +182,13 @@ impl StateKeeperIO for MempoolIO {
                 self.filter.fee_input
             );
             let mut storage = self.pool.access_storage_tagged("state_keeper").await?;
+            if let Some(tracker) = &mut self.custom_base_system_contracts {
+                // Custom contracts may be used by a protocol upgrade saved after the state keeper has started.
+                tracker
+                    .check_new_versions(&mut storage)
+                    .await
+                    .context("failed checking custom base system contracts")?;
+            }
             let (base_system_contracts, protocol_version) = storage
                 .protocol_versions_dal()
                 .base_system_contracts_by_timestamp(current_timestamp)
@@ -473,9 +483,17 @@ impl MempoolIO {
             chain_id,
             virtual_blocks_interval: config.virtual_blocks_interval,
             virtual_blocks_per_miniblock: config.virtual_blocks_per_miniblock,
+            custom_base_system_contracts: None,
         })
     }
 
+    /// Makes this I/O insert custom base system contracts into Postgres once they are used by a protocol version
+    /// (e.g., after a protocol upgrade).
+    pub fn with_custom_base_system_contracts(mut self, contracts: BaseSystemContracts) -> Self {
+        self.custom_base_system_contracts = Some(CustomBaseSystemContractsTracker::new(contracts));
+        self
+    }
+
     fn update_miniblock_fields(&mut self, miniblock: &MiniblockUpdates) {
         assert_eq!(
             miniblock.number, self.current_miniblock_number.0,
@@ -21,6 +21,7 @@ use super::{
 
 pub(crate) mod common;
 pub(crate) mod fee_address_migration;
+pub(crate) mod logs_bloom_backfill;
 pub(crate) mod mempool;
 pub(crate) mod seal_logic;
 #[cfg(test)]
@@ -94,6 +95,7 @@ pub trait StateKeeperIO: 'static + Send + IoSealCriteria {
     /// Marks the transaction as "not executed", so it can be retrieved from the IO again.
     async fn rollback(&mut self, tx: Transaction);
     /// Marks the transaction as "rejected", e.g. one that is not correct and can't be executed.
+    /// For L1 transactions, this means that the corresponding priority operation is skipped.
     async fn reject(&mut self, tx: &Transaction, error: &str) -> anyhow::Result<()>;
 
     /// Marks the miniblock (aka L2 block) as sealed. Returns the timestamp for the next miniblock.
@@ -10,7 +10,7 @@ use multivm::{
 };
 use zksync_dal::StorageProcessor;
 use zksync_types::{
-    block::{unpack_block_info, L1BatchHeader, MiniblockHeader},
+    block::{build_bloom, unpack_block_info, L1BatchHeader, MiniblockHeader},
     event::{extract_added_tokens, extract_long_l2_to_l1_messages},
     helpers::unix_timestamp_ms,
     l1::L1Tx,
@@ -325,6 +325,20 @@ impl MiniblockSealCommand {
             progress.observe(Some(self.miniblock.executed_transactions.len()));
         }
 
+        let progress = MINIBLOCK_METRICS.start(MiniblockSealStage::ExtractEvents, is_fictive);
+        let miniblock_events = self.extract_events(is_fictive);
+        let miniblock_event_count: usize = miniblock_events
+            .iter()
+            .map(|(_, events)| events.len())
+            .sum();
+        let logs_bloom = build_bloom(
+            miniblock_events
+                .iter()
+                .flat_map(|(_, events)| events)
+                .flat_map(|event| event.bloom_items()),
+        );
+        progress.observe(miniblock_event_count);
+
         let l1_batch_number = self.l1_batch_number;
         let miniblock_number = self.miniblock_number;
         let started_at = Instant::now();
@@ -359,6 +373,7 @@ impl MiniblockSealCommand {
                     .into(),
             ),
             virtual_blocks: self.miniblock.virtual_blocks,
+            logs_bloom,
         };
 
         transaction
@@ -427,13 +442,6 @@ impl MiniblockSealCommand {
         }
         progress.observe(added_tokens_len);
 
-        let progress = MINIBLOCK_METRICS.start(MiniblockSealStage::ExtractEvents, is_fictive);
-        let miniblock_events = self.extract_events(is_fictive);
-        let miniblock_event_count: usize = miniblock_events
-            .iter()
-            .map(|(_, events)| events.len())
-            .sum();
-        progress.observe(miniblock_event_count);
         let progress = MINIBLOCK_METRICS.start(MiniblockSealStage::InsertEvents, is_fictive);
         transaction
             .events_dal()
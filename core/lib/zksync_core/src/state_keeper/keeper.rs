@@ -28,7 +28,11 @@ use super::{
 };
 use crate::{
     gas_tracker::gas_count_from_writes,
-    state_keeper::{io::fee_address_migration, metrics::BATCH_TIP_METRICS},
+    state_keeper::{
+        io::{fee_address_migration, logs_bloom_backfill},
+        metrics::BATCH_TIP_METRICS,
+    },
+    tx_admission::{AllowAllPolicy, TxAdmissionPolicy},
 };
 
 /// Amount of time to block on waiting for some resource. The exact value is not really important,
@@ -68,6 +72,7 @@ pub struct ZkSyncStateKeeper {
     io: Box<dyn StateKeeperIO>,
     batch_executor_base: Box<dyn BatchExecutor>,
     sealer: Arc<dyn ConditionalSealer>,
+    admission_policy: Arc<dyn TxAdmissionPolicy>,
 }
 
 impl ZkSyncStateKeeper {
@@ -82,9 +87,17 @@ impl ZkSyncStateKeeper {
             io,
             batch_executor_base,
             sealer,
+            admission_policy: Arc::new(AllowAllPolicy),
         }
     }
 
+    /// Sets the policy checking whether a transaction can be included into a batch. Transactions
+    /// are admitted unconditionally by default.
+    pub fn with_admission_policy(mut self, admission_policy: Arc<dyn TxAdmissionPolicy>) -> Self {
+        self.admission_policy = admission_policy;
+        self
+    }
+
     /// Temporary method to migrate fee addresses from L1 batches to miniblocks.
     pub fn run_fee_address_migration(
         &self,
@@ -100,6 +113,20 @@ impl ZkSyncStateKeeper {
         }
     }
 
+    /// Backfills logs blooms for miniblocks sealed before logs blooms were introduced.
+    pub fn run_logs_bloom_backfill(
+        &self,
+        pool: ConnectionPool,
+    ) -> impl Future<Output = anyhow::Result<()>> {
+        let stop_receiver = self.stop_receiver.clone();
+        async move {
+            logs_bloom_backfill::backfill_logs_blooms(pool, stop_receiver).await?;
+            future::pending::<()>().await;
+            // ^ Since this is run as a task, we don't want it to exit on success (this would shut down the node).
+            anyhow::Ok(())
+        }
+    }
+
     pub async fn run(mut self) -> anyhow::Result<()> {
         match self.run_inner().await {
             Ok(_) => unreachable!(),
@@ -493,6 +520,26 @@ impl ZkSyncStateKeeper {
             waiting_latency.observe();
 
             let tx_hash = tx.hash();
+            if let Err(denial) = self.admission_policy.check((&tx).into()) {
+                if tx.is_l1() {
+                    // L1 transactions cannot be skipped or rejected, since the priority queue must be processed
+                    // in order. Thus, the only safe option is to stop the state keeper until the policy is changed.
+                    KEEPER_METRICS.denied_l1_transactions.inc();
+                    anyhow::bail!(
+                        "L1 transaction {tx_hash:?} is denied by the admission policy: {denial}; \
+                         the state keeper cannot proceed until the policy is updated"
+                    );
+                }
+                tracing::info!(
+                    "Transaction {tx_hash:?} is denied by the admission policy: {denial}"
+                );
+                self.io
+                    .reject(&tx, &denial.to_string())
+                    .await
+                    .with_context(|| format!("cannot reject transaction {tx_hash:?}"))?;
+                continue;
+            }
+
             let (seal_resolution, exec_result) = self
                 .process_one_tx(batch_executor, updates_manager, tx.clone())
                 .await;
@@ -530,6 +577,12 @@ impl ZkSyncStateKeeper {
                 }
                 SealResolution::Unexecutable(reason) => {
                     batch_executor.rollback_last_tx().await;
+                    if tx.is_l1() {
+                        return Err(anyhow::anyhow!(
+                            "L1 transaction {tx_hash:?} cannot be executed: {reason}"
+                        )
+                        .into());
+                    }
                     self.io
                         .reject(&tx, reason)
                         .await
@@ -727,12 +780,14 @@ impl ZkSyncStateKeeper {
                     gas_count_from_writes(&tx_writes_metrics, updates_manager.protocol_version());
                 let tx_gas_excluding_writes = tx_l1_gas_this_tx + finish_block_l1_gas;
 
+                let pubdata_price = updates_manager.batch_fee_input().fair_pubdata_price();
                 let tx_data = SealData {
                     execution_metrics: tx_execution_metrics + finish_block_execution_metrics,
                     gas_count: tx_gas_excluding_writes + tx_writes_l1_gas,
                     cumulative_size: encoding_len,
                     writes_metrics: tx_writes_metrics,
                     gas_remaining: *gas_remaining,
+                    pubdata_price,
                 };
                 let block_data = SealData {
                     execution_metrics: tx_data.execution_metrics
@@ -744,6 +799,7 @@ impl ZkSyncStateKeeper {
                         + updates_manager.pending_txs_encoding_size(),
                     writes_metrics: block_writes_metrics,
                     gas_remaining: *gas_remaining,
+                    pubdata_price,
                 };
 
                 self.sealer.should_seal_l1_batch(
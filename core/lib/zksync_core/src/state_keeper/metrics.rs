@@ -69,6 +69,8 @@ pub(crate) struct StateKeeperMetrics {
     pub get_tx_from_mempool: Histogram<Duration>,
     /// Number of transactions rejected by the state keeper.
     pub rejected_transactions: Counter,
+    /// Number of L1 transactions denied by the transaction admission policy. Each such transaction stops the state keeper.
+    pub denied_l1_transactions: Counter,
     /// Time spent waiting for the hash of a previous L1 batch.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub wait_for_prev_hash_time: Histogram<Duration>,
@@ -155,10 +157,17 @@ struct TxAggregationLabels {
     seal_resolution: Option<SealResolutionLabel>,
 }
 
+const PUBDATA_COST_RATIO_BUCKETS: Buckets = Buckets::linear(0.0..=1.5, 0.1);
+
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "server_tx_aggregation")]
 pub(super) struct TxAggregationMetrics {
     reason: Family<TxAggregationLabels, Counter>,
+    /// Decisions made by conditional seal criteria for each executed transaction, including `no_seal` ones.
+    criterion_decisions: Family<TxAggregationLabels, Counter>,
+    /// Ratio of the pubdata cost of the open L1 batch to the target pubdata cost.
+    #[metrics(buckets = PUBDATA_COST_RATIO_BUCKETS)]
+    pubdata_cost_ratio: Histogram<f64>,
 }
 
 impl TxAggregationMetrics {
@@ -170,6 +179,21 @@ impl TxAggregationMetrics {
         self.reason[&labels].inc();
     }
 
+    pub fn observe_decision(&self, criterion: &'static str, resolution: &SealResolution) {
+        let labels = TxAggregationLabels {
+            criterion,
+            seal_resolution: Some(resolution.into()),
+        };
+        self.criterion_decisions[&labels].inc();
+    }
+
+    pub fn observe_pubdata_cost(&self, cost_wei: u128, target_cost_wei: u128) {
+        if target_cost_wei > 0 {
+            self.pubdata_cost_ratio
+                .observe(cost_wei as f64 / target_cost_wei as f64);
+        }
+    }
+
     pub fn inc_criterion(&self, criterion: &'static str) {
         let labels = TxAggregationLabels {
             criterion,
@@ -400,6 +424,53 @@ pub(super) struct ExecutorMetrics {
 #[vise::register]
 pub(super) static EXECUTOR_METRICS: vise::Global<ExecutorMetrics> = vise::Global::new();
 
+/// Kind of mismatch between the primary and shadow VM execution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "kind", rename_all = "snake_case")]
+pub(super) enum ShadowVmMismatchKind {
+    ExecutionResult,
+    Refunds,
+    Events,
+    L2ToL1Logs,
+    StorageWrites,
+    Pubdata,
+    Panic,
+}
+
+impl ShadowVmMismatchKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ExecutionResult => "execution_result",
+            Self::Refunds => "refunds",
+            Self::Events => "events",
+            Self::L2ToL1Logs => "l2_to_l1_logs",
+            Self::StorageWrites => "storage_writes",
+            Self::Pubdata => "pubdata",
+            Self::Panic => "panic",
+        }
+    }
+}
+
+/// Metrics for the shadow VM executing transactions alongside the primary VM.
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "state_keeper_shadow_vm")]
+pub(super) struct ShadowVmMetrics {
+    /// Number of transactions executed by the shadow VM.
+    pub executed_txs: Counter,
+    /// Latency of executing a single transaction in the shadow VM.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub tx_execution_time: Histogram<Duration>,
+    /// Number of detected mismatches between the primary and shadow VM.
+    pub mismatches: Family<ShadowVmMismatchKind, Counter>,
+    /// Number of L1 batches for which the shadow VM was disabled because of a mismatch.
+    pub disabled_batches: Counter,
+    /// Number of mismatches that could not be persisted to Postgres.
+    pub persistence_errors: Counter,
+}
+
+#[vise::register]
+pub(super) static SHADOW_VM_METRICS: vise::Global<ShadowVmMetrics> = vise::Global::new();
+
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "batch_tip")]
 pub(crate) struct BatchTipMetrics {
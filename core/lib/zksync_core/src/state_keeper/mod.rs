@@ -1,10 +1,12 @@
 use std::sync::Arc;
 
+use anyhow::Context as _;
 use tokio::sync::watch;
 use zksync_config::{
     configs::chain::{MempoolConfig, NetworkConfig, StateKeeperConfig},
     ContractsConfig, DBConfig,
 };
+use zksync_contracts::BaseSystemContracts;
 use zksync_dal::ConnectionPool;
 use zksync_object_store::ObjectStore;
 
@@ -16,7 +18,10 @@ pub use self::{
     seal_criteria::SequencerSealer,
     types::MempoolGuard,
 };
-use crate::fee_model::BatchFeeModelInputProvider;
+use crate::{
+    fee_model::BatchFeeModelInputProvider, rocksdb_backup::RocksdbBackupHandle,
+    tx_admission::TxAdmissionPolicy,
+};
 
 mod batch_executor;
 pub(crate) mod extractors;
@@ -33,6 +38,7 @@ pub(crate) mod updates;
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn create_state_keeper(
     contracts_config: &ContractsConfig,
+    custom_base_system_contracts: Option<BaseSystemContracts>,
     state_keeper_config: StateKeeperConfig,
     db_config: &DBConfig,
     network_config: &NetworkConfig,
@@ -42,9 +48,11 @@ pub(crate) async fn create_state_keeper(
     batch_fee_input_provider: Arc<dyn BatchFeeModelInputProvider>,
     miniblock_sealer_handle: MiniblockSealerHandle,
     object_store: Arc<dyn ObjectStore>,
+    admission_policy: Arc<dyn TxAdmissionPolicy>,
+    backup_handle: Option<RocksdbBackupHandle>,
     stop_receiver: watch::Receiver<bool>,
-) -> ZkSyncStateKeeper {
-    let batch_executor_base = MainBatchExecutor::new(
+) -> anyhow::Result<ZkSyncStateKeeper> {
+    let mut batch_executor_base = MainBatchExecutor::new(
         db_config.state_keeper_db_path.clone(),
         pool.clone(),
         state_keeper_config.max_allowed_l2_tx_gas_limit.into(),
@@ -53,8 +61,20 @@ pub(crate) async fn create_state_keeper(
         state_keeper_config.enum_index_migration_chunk_size(),
         false,
     );
+    if let Some(shadow_vm_version) = state_keeper_config.shadow_vm_version {
+        batch_executor_base.set_shadow_vm_version(shadow_vm_version.into());
+    }
+    if db_config.restore_rocksdb_from_backup {
+        batch_executor_base
+            .restore_from_backup(object_store.as_ref())
+            .await
+            .context("failed restoring state keeper cache from backup")?;
+    }
+    if let Some(backup_handle) = backup_handle {
+        batch_executor_base.set_backup_handle(backup_handle);
+    }
 
-    let io = MempoolIO::new(
+    let mut io = MempoolIO::new(
         mempool,
         object_store,
         miniblock_sealer_handle,
@@ -67,13 +87,18 @@ pub(crate) async fn create_state_keeper(
         network_config.zksync_network_id,
     )
     .await
-    .expect("Failed initializing main node I/O for state keeper");
+    .context("failed initializing main node I/O for state keeper")?;
+    if let Some(contracts) = custom_base_system_contracts {
+        io = io.with_custom_base_system_contracts(contracts);
+    }
 
     let sealer = SequencerSealer::new(state_keeper_config);
-    ZkSyncStateKeeper::new(
+    let state_keeper = ZkSyncStateKeeper::new(
         stop_receiver,
         Box::new(io),
         Box::new(batch_executor_base),
         Arc::new(sealer),
     )
+    .with_admission_policy(admission_policy);
+    Ok(state_keeper)
 }
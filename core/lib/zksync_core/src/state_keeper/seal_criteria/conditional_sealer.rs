@@ -4,9 +4,9 @@
 //! The conditional sealer abstraction allows to implement different sealing strategies, e.g. the actual
 //! sealing strategy for the main node or noop sealer for the external node.
 
-use std::fmt;
+use std::{collections::HashSet, fmt};
 
-use zksync_config::configs::chain::StateKeeperConfig;
+use zksync_config::configs::chain::{SealCriterionName, StateKeeperConfig};
 use zksync_types::ProtocolVersionId;
 
 use super::{criteria, SealCriterion, SealData, SealResolution, AGGREGATION_METRICS};
@@ -107,6 +107,7 @@ impl ConditionalSealer for SequencerSealer {
                 }
                 SealResolution::NoSeal => { /* Don't do anything */ }
             }
+            AGGREGATION_METRICS.observe_decision(sealer.prom_criterion_name(), &seal_resolution);
 
             final_seal_resolution = final_seal_resolution.stricter(seal_resolution);
         }
@@ -115,8 +116,16 @@ impl ConditionalSealer for SequencerSealer {
 }
 
 impl SequencerSealer {
+    /// Creates a sealer using the criteria specified in `config`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `pubdata_cost` criterion is enabled, but the target pubdata cost is not configured.
+    /// This is checked by [`StateKeeperConfig::validate()`] when the config is loaded.
     pub fn new(config: StateKeeperConfig) -> Self {
-        let sealers = Self::default_sealers(&config);
+        let sealers = Self::configured_sealers(&config);
+        let names: Vec<_> = sealers.iter().map(|s| s.prom_criterion_name()).collect();
+        tracing::info!("Using conditional seal criteria: {names:?}");
         Self { config, sealers }
     }
 
@@ -128,17 +137,59 @@ impl SequencerSealer {
         Self { config, sealers }
     }
 
-    fn default_sealers(config: &StateKeeperConfig) -> Vec<Box<dyn SealCriterion>> {
-        vec![
-            Box::new(criteria::SlotsCriterion),
-            Box::new(criteria::GasCriterion),
-            Box::new(criteria::PubDataBytesCriterion {
+    const DEFAULT_CRITERIA: [SealCriterionName; 6] = [
+        SealCriterionName::Slots,
+        SealCriterionName::Gas,
+        SealCriterionName::PubdataBytes,
+        SealCriterionName::Circuits,
+        SealCriterionName::TxEncodingSize,
+        SealCriterionName::GasForBatchTip,
+    ];
+
+    fn configured_sealers(config: &StateKeeperConfig) -> Vec<Box<dyn SealCriterion>> {
+        let mut names = match &config.seal_criteria {
+            Some(names) => names.clone(),
+            None => {
+                let mut names = Self::DEFAULT_CRITERIA.to_vec();
+                if config.target_batch_pubdata_cost_wei.is_some() {
+                    names.push(SealCriterionName::PubdataCost);
+                }
+                names
+            }
+        };
+        // Remove duplicates while preserving the order of criteria.
+        let mut seen_names = HashSet::with_capacity(names.len());
+        names.retain(|name| seen_names.insert(*name));
+
+        names
+            .into_iter()
+            .map(|name| Self::create_sealer(name, config))
+            .collect()
+    }
+
+    fn create_sealer(
+        name: SealCriterionName,
+        config: &StateKeeperConfig,
+    ) -> Box<dyn SealCriterion> {
+        match name {
+            SealCriterionName::Slots => Box::new(criteria::SlotsCriterion),
+            SealCriterionName::Gas => Box::new(criteria::GasCriterion),
+            SealCriterionName::PubdataBytes => Box::new(criteria::PubDataBytesCriterion {
                 max_pubdata_per_batch: config.max_pubdata_per_batch,
             }),
-            Box::new(criteria::CircuitsCriterion),
-            Box::new(criteria::TxEncodingSizeCriterion),
-            Box::new(criteria::GasForBatchTipCriterion),
-        ]
+            SealCriterionName::Circuits => Box::new(criteria::CircuitsCriterion),
+            SealCriterionName::TxEncodingSize => Box::new(criteria::TxEncodingSizeCriterion),
+            SealCriterionName::GasForBatchTip => Box::new(criteria::GasForBatchTipCriterion),
+            SealCriterionName::PubdataCost => {
+                let target_batch_pubdata_cost_wei =
+                    config.target_batch_pubdata_cost_wei.expect(
+                        "`pubdata_cost` seal criterion requires `target_batch_pubdata_cost_wei` to be set",
+                    );
+                Box::new(criteria::PubdataCostCriterion {
+                    target_batch_pubdata_cost_wei,
+                })
+            }
+        }
     }
 }
 
@@ -169,3 +220,71 @@ impl ConditionalSealer for NoopSealer {
         SealResolution::NoSeal
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn criterion_names(sealer: &SequencerSealer) -> Vec<&'static str> {
+        sealer
+            .sealers
+            .iter()
+            .map(|sealer| sealer.prom_criterion_name())
+            .collect()
+    }
+
+    #[test]
+    fn configuring_seal_criteria() {
+        let sealer = SequencerSealer::new(StateKeeperConfig::default());
+        assert_eq!(
+            sealer.sealers.len(),
+            SequencerSealer::DEFAULT_CRITERIA.len()
+        );
+        assert!(!criterion_names(&sealer).contains(&"pubdata_cost"));
+
+        let config = StateKeeperConfig {
+            target_batch_pubdata_cost_wei: Some(1_000_000),
+            ..StateKeeperConfig::default()
+        };
+        let sealer = SequencerSealer::new(config);
+        assert!(criterion_names(&sealer).contains(&"pubdata_cost"));
+
+        let config = StateKeeperConfig {
+            seal_criteria: Some(vec![
+                SealCriterionName::Slots,
+                SealCriterionName::PubdataCost,
+                SealCriterionName::Slots,
+            ]),
+            target_batch_pubdata_cost_wei: Some(1_000_000),
+            ..StateKeeperConfig::default()
+        };
+        let sealer = SequencerSealer::new(config);
+        assert_eq!(criterion_names(&sealer), ["slots", "pubdata_cost"]);
+    }
+
+    #[test]
+    fn pubdata_cost_criterion_requires_target() {
+        let config = StateKeeperConfig {
+            seal_criteria: Some(vec![SealCriterionName::PubdataCost]),
+            ..StateKeeperConfig::default()
+        };
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("target_batch_pubdata_cost_wei"), "{err}");
+
+        let config = StateKeeperConfig {
+            target_batch_pubdata_cost_wei: Some(1_000_000),
+            ..config
+        };
+        config.validate().unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "target_batch_pubdata_cost_wei")]
+    fn sealer_panics_on_unvalidated_config() {
+        let config = StateKeeperConfig {
+            seal_criteria: Some(vec![SealCriterionName::PubdataCost]),
+            ..StateKeeperConfig::default()
+        };
+        SequencerSealer::new(config);
+    }
+}
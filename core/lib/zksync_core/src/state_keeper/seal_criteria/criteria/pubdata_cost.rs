@@ -0,0 +1,133 @@
+use zksync_types::ProtocolVersionId;
+
+use crate::state_keeper::{
+    metrics::AGGREGATION_METRICS,
+    seal_criteria::{SealCriterion, SealData, SealResolution, StateKeeperConfig},
+};
+
+/// Checks whether we should seal the block because the cost of its pubdata on L1 would exceed
+/// the configured target.
+///
+/// Unlike [`PubDataBytesCriterion`](super::PubDataBytesCriterion), this criterion depends on the pubdata price
+/// the L1 batch was opened with, so batches are sealed earlier when L1 is expensive. It never marks
+/// transactions as unexecutable; a transaction exceeding the target on its own is included and sealed.
+#[derive(Debug)]
+pub struct PubdataCostCriterion {
+    /// Target cost of pubdata published by a single L1 batch in wei.
+    pub target_batch_pubdata_cost_wei: u64,
+}
+
+impl PubdataCostCriterion {
+    fn pubdata_cost(data: &SealData, protocol_version: ProtocolVersionId) -> u128 {
+        let pubdata_size =
+            data.execution_metrics.size() + data.writes_metrics.size(protocol_version);
+        pubdata_size as u128 * u128::from(data.pubdata_price)
+    }
+}
+
+impl SealCriterion for PubdataCostCriterion {
+    fn should_seal(
+        &self,
+        config: &StateKeeperConfig,
+        _block_open_timestamp_ms: u128,
+        tx_count: usize,
+        block_data: &SealData,
+        _tx_data: &SealData,
+        protocol_version: ProtocolVersionId,
+    ) -> SealResolution {
+        if block_data.pubdata_price == 0 {
+            // The pubdata price is unknown (e.g., when checking transaction executability in the API server).
+            return SealResolution::NoSeal;
+        }
+
+        let target_cost = u128::from(self.target_batch_pubdata_cost_wei);
+        let block_cost = Self::pubdata_cost(block_data, protocol_version);
+        let include_and_seal_bound =
+            (target_cost as f64 * config.close_block_at_eth_params_percentage).round() as u128;
+        AGGREGATION_METRICS.observe_pubdata_cost(block_cost, target_cost);
+
+        if block_cost > target_cost {
+            if tx_count > 1 {
+                SealResolution::ExcludeAndSeal
+            } else {
+                SealResolution::IncludeAndSeal
+            }
+        } else if block_cost > include_and_seal_bound {
+            SealResolution::IncludeAndSeal
+        } else {
+            SealResolution::NoSeal
+        }
+    }
+
+    fn prom_criterion_name(&self) -> &'static str {
+        "pubdata_cost"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::tx::ExecutionMetrics;
+
+    use super::*;
+
+    fn block_data(pubdata_size: usize, pubdata_price: u64) -> SealData {
+        SealData {
+            execution_metrics: ExecutionMetrics {
+                l2_l1_long_messages: pubdata_size,
+                ..ExecutionMetrics::default()
+            },
+            pubdata_price,
+            ..SealData::default()
+        }
+    }
+
+    #[test]
+    fn seal_criterion() {
+        // Create an empty config and only setup fields relevant for the test.
+        let config = StateKeeperConfig {
+            close_block_at_eth_params_percentage: 0.9,
+            ..Default::default()
+        };
+        let criterion = PubdataCostCriterion {
+            target_batch_pubdata_cost_wei: 1_000_000,
+        };
+        let should_seal = |data: &SealData, tx_count: usize| {
+            criterion.should_seal(
+                &config,
+                0,
+                tx_count,
+                data,
+                &SealData::default(),
+                ProtocolVersionId::latest(),
+            )
+        };
+
+        assert_eq!(
+            should_seal(&block_data(800, 1_000), 2),
+            SealResolution::NoSeal
+        );
+        assert_eq!(
+            should_seal(&block_data(950, 1_000), 2),
+            SealResolution::IncludeAndSeal
+        );
+        assert_eq!(
+            should_seal(&block_data(1_001, 1_000), 2),
+            SealResolution::ExcludeAndSeal
+        );
+        // A single transaction exceeding the target must not be rejected.
+        assert_eq!(
+            should_seal(&block_data(1_001, 1_000), 1),
+            SealResolution::IncludeAndSeal
+        );
+        // The same amount of pubdata is acceptable if the pubdata price is lower.
+        assert_eq!(
+            should_seal(&block_data(1_001, 500), 2),
+            SealResolution::NoSeal
+        );
+        // Unknown pubdata price never leads to sealing.
+        assert_eq!(
+            should_seal(&block_data(1_000_000, 0), 2),
+            SealResolution::NoSeal
+        );
+    }
+}
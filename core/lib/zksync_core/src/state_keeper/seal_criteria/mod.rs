@@ -82,6 +82,8 @@ pub struct SealData {
     pub(super) cumulative_size: usize,
     pub(super) writes_metrics: DeduplicatedWritesMetrics,
     pub(super) gas_remaining: u32,
+    /// Price of a single pubdata byte (in wei) for the L1 batch. 0 if the price is unknown.
+    pub(super) pubdata_price: u64,
 }
 
 impl SealData {
@@ -102,6 +104,7 @@ impl SealData {
             cumulative_size: transaction.bootloader_encoding_size(),
             writes_metrics,
             gas_remaining: tx_metrics.gas_remaining,
+            pubdata_price: 0,
         }
     }
 }
@@ -135,6 +138,8 @@ pub trait IoSealCriteria {
 pub(super) struct TimeoutSealer {
     block_commit_deadline_ms: u64,
     miniblock_commit_deadline_ms: u64,
+    /// L1 gas price threshold and the extended L1 batch commit deadline used if the threshold is reached.
+    high_l1_gas_price_deadline: Option<(u64, u64)>,
 }
 
 impl TimeoutSealer {
@@ -142,6 +147,19 @@ impl TimeoutSealer {
         Self {
             block_commit_deadline_ms: config.block_commit_deadline_ms,
             miniblock_commit_deadline_ms: config.miniblock_commit_deadline_ms,
+            high_l1_gas_price_deadline: config
+                .high_l1_gas_price_threshold_wei
+                .zip(config.high_l1_gas_price_block_commit_deadline_ms),
+        }
+    }
+
+    /// Returns the L1 batch commit deadline for a batch opened with the specified L1 gas price.
+    /// If L1 gas is expensive, it may be beneficial to wait longer so that the batch overhead
+    /// is amortized over more transactions.
+    fn block_commit_deadline_ms(&self, l1_gas_price: u64) -> u64 {
+        match self.high_l1_gas_price_deadline {
+            Some((threshold, deadline_ms)) if l1_gas_price >= threshold => deadline_ms,
+            _ => self.block_commit_deadline_ms,
         }
     }
 }
@@ -155,7 +173,8 @@ impl IoSealCriteria for TimeoutSealer {
             return false;
         }
 
-        let block_commit_deadline_ms = self.block_commit_deadline_ms;
+        let block_commit_deadline_ms =
+            self.block_commit_deadline_ms(manager.batch_fee_input().l1_gas_price());
         // Verify timestamp
         let should_seal_timeout =
             millis_since(manager.batch_timestamp()) > block_commit_deadline_ms;
@@ -204,6 +223,7 @@ mod tests {
         let mut timeout_miniblock_sealer = TimeoutSealer {
             block_commit_deadline_ms: 10_000,
             miniblock_commit_deadline_ms: 10_000,
+            high_l1_gas_price_deadline: None,
         };
 
         let mut manager = create_updates_manager();
@@ -230,4 +250,22 @@ mod tests {
             "Non-empty miniblock with too recent timestamp shouldn't be sealed"
         );
     }
+
+    #[test]
+    fn timeout_sealer_extends_deadline_for_high_l1_gas_price() {
+        let sealer = TimeoutSealer {
+            block_commit_deadline_ms: 10_000,
+            miniblock_commit_deadline_ms: 1_000,
+            high_l1_gas_price_deadline: Some((100, 60_000)),
+        };
+        assert_eq!(sealer.block_commit_deadline_ms(99), 10_000);
+        assert_eq!(sealer.block_commit_deadline_ms(100), 60_000);
+        assert_eq!(sealer.block_commit_deadline_ms(1_000), 60_000);
+
+        let sealer = TimeoutSealer {
+            high_l1_gas_price_deadline: None,
+            ..sealer
+        };
+        assert_eq!(sealer.block_commit_deadline_ms(1_000), 10_000);
+    }
 }
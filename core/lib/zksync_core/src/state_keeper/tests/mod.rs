@@ -1,4 +1,5 @@
 use std::{
+    collections::HashSet,
     sync::{
         atomic::{AtomicBool, AtomicU64, Ordering},
         Arc,
@@ -31,8 +32,8 @@ use zksync_types::{
 mod tester;
 
 use self::tester::{
-    bootloader_tip_out_of_gas, pending_batch_data, random_tx, random_upgrade_tx, rejected_exec,
-    successful_exec, successful_exec_with_metrics, TestIO, TestScenario,
+    bootloader_tip_out_of_gas, pending_batch_data, random_l1_tx, random_tx, random_upgrade_tx,
+    rejected_exec, successful_exec, successful_exec_with_metrics, TestIO, TestScenario,
 };
 pub(crate) use self::tester::{MockBatchExecutor, TestBatchExecutorBuilder};
 use crate::{
@@ -47,6 +48,7 @@ use crate::{
         updates::UpdatesManager,
         ZkSyncStateKeeper,
     },
+    tx_admission::TxAdmissionRules,
     utils::testonly::create_l2_transaction,
 };
 
@@ -351,6 +353,63 @@ async fn rejected_tx() {
         .await;
 }
 
+#[tokio::test]
+async fn tx_denied_by_admission_policy() {
+    let config = StateKeeperConfig {
+        transaction_slots: 2,
+        ..StateKeeperConfig::default()
+    };
+    let sealer = SequencerSealer::with_sealers(config, vec![Box::new(SlotsCriterion)]);
+
+    let denied_tx = random_tx(1);
+    let policy = TxAdmissionRules {
+        denied_senders: HashSet::from([denied_tx.initiator_account()]),
+        ..TxAdmissionRules::default()
+    };
+    TestScenario::new()
+        .with_admission_policy(Arc::new(policy))
+        .seal_miniblock_when(|updates| updates.miniblock.executed_transactions.len() == 1)
+        // The denied transaction must not be executed, so its execution result is irrelevant.
+        .next_tx("Denied tx", denied_tx.clone(), successful_exec())
+        .tx_rejected(
+            "Tx got rejected",
+            denied_tx,
+            Some("not allowed to send transactions".to_owned()),
+        )
+        .next_tx("Successful tx", random_tx(2), successful_exec())
+        .miniblock_sealed("Miniblock with successful tx")
+        .next_tx("Second successful tx", random_tx(3), successful_exec())
+        .miniblock_sealed("Second miniblock")
+        .batch_sealed("Batch with 2 successful txs")
+        .run(sealer)
+        .await;
+}
+
+#[tokio::test]
+async fn l1_tx_denied_by_admission_policy_stops_state_keeper() {
+    let config = StateKeeperConfig {
+        transaction_slots: 2,
+        ..StateKeeperConfig::default()
+    };
+    let sealer = SequencerSealer::with_sealers(config, vec![Box::new(SlotsCriterion)]);
+
+    let denied_tx = random_l1_tx(1);
+    let policy = TxAdmissionRules {
+        denied_senders: HashSet::from([denied_tx.initiator_account()]),
+        ..TxAdmissionRules::default()
+    };
+    let err = TestScenario::new()
+        .with_admission_policy(Arc::new(policy))
+        .next_tx("Successful tx", random_tx(2), successful_exec())
+        // The denied L1 transaction must be neither executed nor rejected, so its execution result is irrelevant.
+        .next_tx("Denied L1 tx", denied_tx.clone(), successful_exec())
+        .run_expecting_error(sealer)
+        .await;
+    let err = err.to_string();
+    assert!(err.contains("denied by the admission policy"), "{err}");
+    assert!(err.contains(&format!("{:?}", denied_tx.hash())), "{err}");
+}
+
 #[tokio::test]
 async fn bootloader_tip_out_of_gas_flow() {
     let config = StateKeeperConfig {
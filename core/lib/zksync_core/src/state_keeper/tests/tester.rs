@@ -16,9 +16,13 @@ use multivm::{
 };
 use tokio::sync::{mpsc, watch};
 use zksync_types::{
-    block::MiniblockExecutionData, fee_model::BatchFeeInput, protocol_version::ProtocolUpgradeTx,
-    witness_block_state::WitnessBlockState, Address, L1BatchNumber, L2ChainId, MiniblockNumber,
-    ProtocolVersionId, Transaction, H256,
+    block::MiniblockExecutionData,
+    fee_model::BatchFeeInput,
+    l1::{L1Tx, L1TxCommonData},
+    protocol_version::ProtocolUpgradeTx,
+    witness_block_state::WitnessBlockState,
+    Address, L1BatchNumber, L2ChainId, MiniblockNumber, PriorityOpId, ProtocolVersionId,
+    Transaction, H256,
 };
 
 use crate::{
@@ -31,6 +35,7 @@ use crate::{
         updates::UpdatesManager,
         ZkSyncStateKeeper,
     },
+    tx_admission::TxAdmissionPolicy,
     utils::testonly::create_l2_transaction,
 };
 
@@ -53,6 +58,7 @@ pub(crate) struct TestScenario {
     pending_batch: Option<PendingBatchData>,
     l1_batch_seal_fn: Box<SealFn>,
     miniblock_seal_fn: Box<SealFn>,
+    admission_policy: Option<Arc<dyn TxAdmissionPolicy>>,
 }
 
 type SealFn = dyn FnMut(&UpdatesManager) -> bool + Send;
@@ -74,6 +80,7 @@ impl TestScenario {
             pending_batch: None,
             l1_batch_seal_fn: Box::new(|_| false),
             miniblock_seal_fn: Box::new(|_| false),
+            admission_policy: None,
         }
     }
 
@@ -189,20 +196,41 @@ impl TestScenario {
         self
     }
 
+    /// Sets the transaction admission policy for the state keeper.
+    pub(crate) fn with_admission_policy(mut self, policy: Arc<dyn TxAdmissionPolicy>) -> Self {
+        self.admission_policy = Some(policy);
+        self
+    }
+
     /// Launches the test.
     /// Provided `SealManager` is expected to be externally configured to adhere the written scenario logic.
     pub(crate) async fn run(self, sealer: SequencerSealer) {
+        self.launch(sealer).await.unwrap();
+    }
+
+    /// Launches the test expecting the state keeper to fail, and returns the returned error.
+    pub(crate) async fn run_expecting_error(self, sealer: SequencerSealer) -> anyhow::Error {
+        self.launch(sealer)
+            .await
+            .expect_err("State keeper unexpectedly succeeded")
+    }
+
+    async fn launch(self, sealer: SequencerSealer) -> anyhow::Result<()> {
         assert!(!self.actions.is_empty(), "Test scenario can't be empty");
 
         let batch_executor_base = TestBatchExecutorBuilder::new(&self);
+        let admission_policy = self.admission_policy.clone();
         let (stop_sender, stop_receiver) = watch::channel(false);
         let io = TestIO::new(stop_sender, self);
-        let sk = ZkSyncStateKeeper::new(
+        let mut sk = ZkSyncStateKeeper::new(
             stop_receiver,
             Box::new(io),
             Box::new(batch_executor_base),
             Arc::new(sealer),
         );
+        if let Some(admission_policy) = admission_policy {
+            sk = sk.with_admission_policy(admission_policy);
+        }
         let sk_thread = tokio::spawn(sk.run());
 
         // We must assume that *theoretically* state keeper may ignore the stop signal from IO once scenario is
@@ -212,11 +240,9 @@ impl TestScenario {
         let start = Instant::now();
         while start.elapsed() <= hard_timeout {
             if sk_thread.is_finished() {
-                sk_thread
+                return sk_thread
                     .await
-                    .unwrap_or_else(|_| panic!("State keeper thread panicked"))
-                    .unwrap();
-                return;
+                    .unwrap_or_else(|_| panic!("State keeper thread panicked"));
             }
             tokio::time::sleep(poll_interval).await;
         }
@@ -234,6 +260,22 @@ pub(crate) fn random_tx(tx_number: u64) -> Transaction {
     tx.into()
 }
 
+/// Creates a random L1 transaction. Provided tx number would be used as a transaction hash,
+/// so it's easier to understand which transaction caused test to fail.
+pub(crate) fn random_l1_tx(tx_number: u64) -> Transaction {
+    let mut tx = L1Tx {
+        execute: Default::default(),
+        common_data: L1TxCommonData {
+            sender: Address::random(),
+            serial_id: PriorityOpId(tx_number),
+            ..L1TxCommonData::default()
+        },
+        received_timestamp_ms: 0,
+    };
+    tx.common_data.canonical_tx_hash = H256::from_low_u64_be(tx_number);
+    tx.into()
+}
+
 /// Creates a random protocol upgrade transaction. Provided tx number would be used as a transaction hash,
 /// so it's easier to understand which transaction caused test to fail.
 pub(crate) fn random_upgrade_tx(tx_number: u64) -> ProtocolUpgradeTx {
@@ -3,6 +3,7 @@
 use std::fmt;
 
 use async_trait::async_trait;
+use zksync_health_check::{CheckHealth, Health, HealthStatus};
 use zksync_system_constants::ACCOUNT_CODE_STORAGE_ADDRESS;
 use zksync_types::{
     api::{self, en},
@@ -154,3 +155,31 @@ impl MainNodeClient for HttpClient {
             .await
     }
 }
+
+/// Main node health check.
+#[derive(Debug)]
+pub struct MainNodeHealthCheck(HttpClient);
+
+impl From<HttpClient> for MainNodeHealthCheck {
+    fn from(client: HttpClient) -> Self {
+        Self(client)
+    }
+}
+
+#[async_trait]
+impl CheckHealth for MainNodeHealthCheck {
+    fn name(&self) -> &'static str {
+        "main_node_http_rpc"
+    }
+
+    async fn check_health(&self) -> Health {
+        if let Err(err) = self.0.get_block_number().await {
+            tracing::warn!("Health-check call to main node HTTP RPC failed: {err}");
+            let details = serde_json::json!({
+                "error": err.to_string(),
+            });
+            return Health::from(HealthStatus::NotReady).with_details(details);
+        }
+        HealthStatus::Ready.into()
+    }
+}
@@ -10,6 +10,8 @@ mod sync_state;
 mod tests;
 
 pub use self::{
-    client::MainNodeClient, external_io::ExternalIO, sync_action::ActionQueue,
+    client::{MainNodeClient, MainNodeHealthCheck},
+    external_io::ExternalIO,
+    sync_action::{ActionQueue, ActionQueueSender},
     sync_state::SyncState,
 };
@@ -3,8 +3,8 @@ use zksync_config::{
     configs::{
         api::{HealthCheckConfig, MerkleTreeApiConfig, Web3JsonRpcConfig},
         chain::{
-            CircuitBreakerConfig, MempoolConfig, NetworkConfig, OperationsManagerConfig,
-            StateKeeperConfig,
+            CircuitBreakerConfig, L2CongestionConfig, MempoolConfig, NetworkConfig,
+            OperationsManagerConfig, StateKeeperConfig,
         },
         fri_prover_group::FriProverGroupConfig,
         house_keeper::HouseKeeperConfig,
@@ -72,6 +72,7 @@ pub struct TempConfigStore {
     pub gas_adjuster_config: Option<GasAdjusterConfig>,
     pub object_store_config: Option<ObjectStoreConfig>,
     pub consensus_config: Option<consensus::Config>,
+    pub l2_congestion_config: Option<L2CongestionConfig>,
 }
 
 impl ProtoFmt for TempConfigStore {
@@ -112,6 +113,7 @@ impl ProtoFmt for TempConfigStore {
             gas_adjuster_config: read_optional_repr(&r.gas_adjuster).context("gas_adjuster")?,
             object_store_config: read_optional_repr(&r.object_store).context("object_store")?,
             consensus_config: read_optional(&r.consensus).context("consensus")?,
+            l2_congestion_config: read_optional_repr(&r.l2_congestion).context("l2_congestion")?,
         })
     }
 
@@ -155,6 +157,7 @@ impl ProtoFmt for TempConfigStore {
             gas_adjuster: self.gas_adjuster_config.as_ref().map(ProtoRepr::build),
             object_store: self.object_store_config.as_ref().map(ProtoRepr::build),
             consensus: self.consensus_config.as_ref().map(ProtoFmt::build),
+            l2_congestion: self.l2_congestion_config.as_ref().map(ProtoRepr::build),
         }
     }
 }
@@ -0,0 +1,270 @@
+//! Transaction admission policies restricting which transactions can be included into the chain.
+//!
+//! Policies are enforced for L2 transactions by [`TxSender`](crate::api_server::tx_sender::TxSender) on submission,
+//! and for all transactions by the state keeper, so that restrictions cannot be bypassed via L1 priority transactions.
+//!
+//! Priority transactions cannot be skipped: L1 contracts expect priority operations to be executed in order, so a batch
+//! skipping a priority operation cannot be proven / executed on L1. Thus, a priority transaction denied by the policy
+//! stops the state keeper (and is reported via the `server_state_keeper_denied_l1_transactions` metric) until
+//! the policy is updated. Permissioned chains must restrict priority transactions on L1 as well; the state keeper
+//! policy only serves as the last line of defense.
+
+use std::{
+    collections::HashSet,
+    fmt,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use anyhow::Context as _;
+use tokio::sync::watch;
+use zksync_config::configs::chain::StateKeeperConfig;
+use zksync_dal::{
+    tx_admission_dal::{TxAdmissionRule, TxAdmissionRuleKind},
+    ConnectionPool,
+};
+use zksync_system_constants::CONTRACT_DEPLOYER_ADDRESS;
+use zksync_types::{l2::L2Tx, Address, Execute, Transaction};
+
+#[cfg(test)]
+mod tests;
+
+/// Transaction data checked by [`TxAdmissionPolicy`].
+#[derive(Debug, Clone, Copy)]
+pub struct TxAdmissionRequest<'a> {
+    pub initiator: Address,
+    pub execute: &'a Execute,
+}
+
+impl<'a> From<&'a L2Tx> for TxAdmissionRequest<'a> {
+    fn from(tx: &'a L2Tx) -> Self {
+        Self {
+            initiator: tx.initiator_account(),
+            execute: &tx.execute,
+        }
+    }
+}
+
+impl<'a> From<&'a Transaction> for TxAdmissionRequest<'a> {
+    fn from(tx: &'a Transaction) -> Self {
+        Self {
+            initiator: tx.initiator_account(),
+            execute: &tx.execute,
+        }
+    }
+}
+
+impl TxAdmissionRequest<'_> {
+    /// Checks whether the transaction deploys a contract. Deployments performed by the called contracts
+    /// are not detected.
+    fn is_deployment(&self) -> bool {
+        self.execute.contract_address == CONTRACT_DEPLOYER_ADDRESS
+    }
+}
+
+/// Reason for denying a transaction by [`TxAdmissionPolicy`].
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum TxAdmissionDenial {
+    #[error("account {0:?} is not allowed to send transactions")]
+    DeniedSender(Address),
+    #[error("account {0:?} is not allowed to deploy contracts")]
+    DeployerNotAllowed(Address),
+    #[error("contract {0:?} is not allowed to be called")]
+    ContractNotAllowed(Address),
+}
+
+/// Policy determining whether a transaction can be included into the chain.
+///
+/// Checks are performed synchronously for each transaction, so implementations are expected to be cheap
+/// (e.g., keep rules in memory and update them in the background).
+pub trait TxAdmissionPolicy: fmt::Debug + Send + Sync + 'static {
+    fn check(&self, tx: TxAdmissionRequest<'_>) -> Result<(), TxAdmissionDenial>;
+}
+
+/// Policy admitting all transactions. Used if no policy is explicitly configured.
+#[derive(Debug)]
+pub struct AllowAllPolicy;
+
+impl TxAdmissionPolicy for AllowAllPolicy {
+    fn check(&self, _tx: TxAdmissionRequest<'_>) -> Result<(), TxAdmissionDenial> {
+        Ok(())
+    }
+}
+
+/// Static set of admission rules. Allow-lists are only enforced if they are non-empty.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TxAdmissionRules {
+    pub denied_senders: HashSet<Address>,
+    pub allowed_deployers: HashSet<Address>,
+    pub allowed_contracts: HashSet<Address>,
+}
+
+impl TxAdmissionRules {
+    pub fn from_config(config: &StateKeeperConfig) -> Self {
+        Self {
+            denied_senders: config.tx_admission_denied_senders.iter().copied().collect(),
+            allowed_deployers: config
+                .tx_admission_allowed_deployers
+                .iter()
+                .copied()
+                .collect(),
+            allowed_contracts: config
+                .tx_admission_allowed_contracts
+                .iter()
+                .copied()
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.denied_senders.is_empty()
+            && self.allowed_deployers.is_empty()
+            && self.allowed_contracts.is_empty()
+    }
+
+    fn extend(&mut self, rules: impl IntoIterator<Item = TxAdmissionRule>) {
+        for rule in rules {
+            let set = match rule.kind {
+                TxAdmissionRuleKind::DeniedSender => &mut self.denied_senders,
+                TxAdmissionRuleKind::AllowedDeployer => &mut self.allowed_deployers,
+                TxAdmissionRuleKind::AllowedContract => &mut self.allowed_contracts,
+            };
+            set.insert(rule.address);
+        }
+    }
+}
+
+impl TxAdmissionPolicy for TxAdmissionRules {
+    fn check(&self, tx: TxAdmissionRequest<'_>) -> Result<(), TxAdmissionDenial> {
+        if self.denied_senders.contains(&tx.initiator) {
+            return Err(TxAdmissionDenial::DeniedSender(tx.initiator));
+        }
+
+        if tx.is_deployment() {
+            if !self.allowed_deployers.is_empty() && !self.allowed_deployers.contains(&tx.initiator)
+            {
+                return Err(TxAdmissionDenial::DeployerNotAllowed(tx.initiator));
+            }
+        } else if !tx.execute.calldata.is_empty() {
+            let contract_address = tx.execute.contract_address;
+            if !self.allowed_contracts.is_empty()
+                && !self.allowed_contracts.contains(&contract_address)
+            {
+                return Err(TxAdmissionDenial::ContractNotAllowed(contract_address));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Admission policy combining rules from the config with the rules stored in Postgres. Rules from Postgres
+/// are periodically reloaded by [`DbTxAdmissionRulesUpdater`].
+#[derive(Debug, Clone)]
+pub struct DbTxAdmissionPolicy {
+    rules: Arc<RwLock<TxAdmissionRules>>,
+}
+
+impl TxAdmissionPolicy for DbTxAdmissionPolicy {
+    fn check(&self, tx: TxAdmissionRequest<'_>) -> Result<(), TxAdmissionDenial> {
+        self.rules
+            .read()
+            .expect("admission rules are poisoned")
+            .check(tx)
+    }
+}
+
+impl DbTxAdmissionPolicy {
+    /// Creates a policy and loads the initial rules from Postgres.
+    pub async fn new(
+        config_rules: TxAdmissionRules,
+        pool: ConnectionPool,
+        reload_interval: Duration,
+    ) -> anyhow::Result<(Self, DbTxAdmissionRulesUpdater)> {
+        let this = Self {
+            rules: Arc::default(),
+        };
+        let updater = DbTxAdmissionRulesUpdater {
+            rules: this.rules.clone(),
+            config_rules,
+            pool,
+            reload_interval,
+        };
+        updater.update().await?;
+        Ok((this, updater))
+    }
+}
+
+/// Task periodically reloading rules for [`DbTxAdmissionPolicy`].
+#[derive(Debug)]
+pub struct DbTxAdmissionRulesUpdater {
+    rules: Arc<RwLock<TxAdmissionRules>>,
+    config_rules: TxAdmissionRules,
+    pool: ConnectionPool,
+    reload_interval: Duration,
+}
+
+impl DbTxAdmissionRulesUpdater {
+    async fn update(&self) -> anyhow::Result<()> {
+        let mut storage = self.pool.access_storage_tagged("tx_admission").await?;
+        let db_rules = storage
+            .tx_admission_dal()
+            .get_rules()
+            .await
+            .context("failed loading transaction admission rules")?;
+        drop(storage);
+
+        let mut rules = self.config_rules.clone();
+        rules.extend(db_rules);
+        let mut current_rules = self.rules.write().expect("admission rules are poisoned");
+        if *current_rules != rules {
+            tracing::info!(
+                "Updated transaction admission rules: {} denied senders, {} allowed deployers, {} allowed contracts",
+                rules.denied_senders.len(),
+                rules.allowed_deployers.len(),
+                rules.allowed_contracts.len()
+            );
+            *current_rules = rules;
+        }
+        Ok(())
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        loop {
+            if tokio::time::timeout(self.reload_interval, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+            if let Err(err) = self.update().await {
+                // Keep using the previously loaded rules.
+                tracing::warn!("Failed reloading transaction admission rules: {err:#}");
+            }
+        }
+        tracing::info!(
+            "Stop signal received, transaction admission rules updater is shutting down"
+        );
+        Ok(())
+    }
+}
+
+/// Creates the admission policy according to the config. If rules are loaded from Postgres, also returns
+/// the task reloading them, which should be run in the background.
+pub async fn create_tx_admission_policy(
+    config: &StateKeeperConfig,
+    pool: ConnectionPool,
+) -> anyhow::Result<(
+    Arc<dyn TxAdmissionPolicy>,
+    Option<DbTxAdmissionRulesUpdater>,
+)> {
+    let config_rules = TxAdmissionRules::from_config(config);
+    Ok(match config.tx_admission_reload_interval() {
+        Some(reload_interval) => {
+            let (policy, updater) =
+                DbTxAdmissionPolicy::new(config_rules, pool, reload_interval).await?;
+            (Arc::new(policy), Some(updater))
+        }
+        None if config_rules.is_empty() => (Arc::new(AllowAllPolicy), None),
+        None => (Arc::new(config_rules), None),
+    })
+}
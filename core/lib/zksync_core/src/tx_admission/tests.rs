@@ -0,0 +1,159 @@
+//! Tests for transaction admission policies.
+
+use zksync_dal::ConnectionPool;
+
+use super::*;
+
+fn call(contract_address: Address) -> Execute {
+    Execute {
+        contract_address,
+        calldata: vec![1, 2, 3, 4],
+        value: 0.into(),
+        factory_deps: None,
+    }
+}
+
+fn deployment() -> Execute {
+    Execute {
+        contract_address: CONTRACT_DEPLOYER_ADDRESS,
+        calldata: vec![1, 2, 3, 4],
+        value: 0.into(),
+        factory_deps: Some(vec![vec![0; 32]]),
+    }
+}
+
+fn request(initiator: Address, execute: &Execute) -> TxAdmissionRequest<'_> {
+    TxAdmissionRequest { initiator, execute }
+}
+
+#[test]
+fn empty_rules_admit_everything() {
+    let rules = TxAdmissionRules::default();
+    assert!(rules.is_empty());
+    let sender = Address::repeat_byte(1);
+    rules
+        .check(request(sender, &call(Address::repeat_byte(2))))
+        .unwrap();
+    rules.check(request(sender, &deployment())).unwrap();
+}
+
+#[test]
+fn denied_senders() {
+    let denied = Address::repeat_byte(1);
+    let rules = TxAdmissionRules {
+        denied_senders: HashSet::from([denied]),
+        ..TxAdmissionRules::default()
+    };
+    let execute = call(Address::repeat_byte(2));
+    assert_eq!(
+        rules.check(request(denied, &execute)),
+        Err(TxAdmissionDenial::DeniedSender(denied))
+    );
+    assert_eq!(
+        rules.check(request(denied, &deployment())),
+        Err(TxAdmissionDenial::DeniedSender(denied))
+    );
+    rules
+        .check(request(Address::repeat_byte(3), &execute))
+        .unwrap();
+}
+
+#[test]
+fn allowed_deployers() {
+    let deployer = Address::repeat_byte(1);
+    let other = Address::repeat_byte(2);
+    let rules = TxAdmissionRules {
+        allowed_deployers: HashSet::from([deployer]),
+        ..TxAdmissionRules::default()
+    };
+    rules.check(request(deployer, &deployment())).unwrap();
+    assert_eq!(
+        rules.check(request(other, &deployment())),
+        Err(TxAdmissionDenial::DeployerNotAllowed(other))
+    );
+    // Calls are not restricted by the deployer allow-list.
+    rules
+        .check(request(other, &call(Address::repeat_byte(3))))
+        .unwrap();
+}
+
+#[test]
+fn allowed_contracts() {
+    let sender = Address::repeat_byte(1);
+    let allowed = Address::repeat_byte(2);
+    let other = Address::repeat_byte(3);
+    let rules = TxAdmissionRules {
+        allowed_contracts: HashSet::from([allowed]),
+        ..TxAdmissionRules::default()
+    };
+    rules.check(request(sender, &call(allowed))).unwrap();
+    assert_eq!(
+        rules.check(request(sender, &call(other))),
+        Err(TxAdmissionDenial::ContractNotAllowed(other))
+    );
+
+    // Transfers and deployments are not restricted by the contract allow-list.
+    let transfer = Execute {
+        calldata: vec![],
+        ..call(other)
+    };
+    rules.check(request(sender, &transfer)).unwrap();
+    rules.check(request(sender, &deployment())).unwrap();
+}
+
+#[tokio::test]
+async fn policy_without_rules_admits_everything() {
+    let pool = ConnectionPool::test_pool().await;
+    let config = StateKeeperConfig::for_tests();
+    let (policy, updater) = create_tx_admission_policy(&config, pool).await.unwrap();
+    assert!(updater.is_none());
+    let sender = Address::repeat_byte(1);
+    policy.check(request(sender, &deployment())).unwrap();
+}
+
+#[tokio::test]
+async fn db_policy_reloads_rules() {
+    let pool = ConnectionPool::test_pool().await;
+    let config_denied = Address::repeat_byte(1);
+    let db_denied = Address::repeat_byte(2);
+    let mut config = StateKeeperConfig::for_tests();
+    config.tx_admission_denied_senders = vec![config_denied];
+    config.tx_admission_reload_interval_ms = Some(10);
+
+    let (policy, updater) = create_tx_admission_policy(&config, pool.clone())
+        .await
+        .unwrap();
+    let updater = updater.expect("no updater for DB-backed policy");
+    let execute = call(Address::repeat_byte(3));
+    assert_eq!(
+        policy.check(request(config_denied, &execute)),
+        Err(TxAdmissionDenial::DeniedSender(config_denied))
+    );
+    policy.check(request(db_denied, &execute)).unwrap();
+
+    let rule = TxAdmissionRule {
+        address: db_denied,
+        kind: TxAdmissionRuleKind::DeniedSender,
+    };
+    let mut storage = pool.access_storage().await.unwrap();
+    assert!(storage.tx_admission_dal().insert_rule(rule).await.unwrap());
+    drop(storage);
+
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let updater_task = tokio::spawn(updater.run(stop_receiver));
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while policy.check(request(db_denied, &execute)).is_ok() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("rules were not reloaded");
+    // Rules from the config must be retained.
+    assert_eq!(
+        policy.check(request(config_denied, &execute)),
+        Err(TxAdmissionDenial::DeniedSender(config_denied))
+    );
+
+    stop_sender.send_replace(true);
+    updater_task.await.unwrap().unwrap();
+}
@@ -40,6 +40,7 @@ pub(crate) fn create_miniblock(number: u32) -> MiniblockHeader {
         base_system_contracts_hashes: BaseSystemContractsHashes::default(),
         protocol_version: Some(ProtocolVersionId::latest()),
         virtual_blocks: 1,
+        logs_bloom: Default::default(),
     }
 }
 
@@ -5,7 +5,10 @@
 use anyhow::Context;
 use zksync_config::{
     configs::{
-        chain::{MempoolConfig, NetworkConfig, OperationsManagerConfig, StateKeeperConfig},
+        chain::{
+            CircuitBreakerConfig, L2CongestionConfig, MempoolConfig, NetworkConfig,
+            OperationsManagerConfig, StateKeeperConfig,
+        },
         fri_prover_group::FriProverGroupConfig,
         house_keeper::HouseKeeperConfig,
         FriProofCompressorConfig, FriProverConfig, FriWitnessGeneratorConfig, ObservabilityConfig,
@@ -19,25 +22,34 @@ use zksync_core::{
         tx_sender::{ApiContracts, TxSenderConfig},
         web3::{state::InternalApiConfig, Namespace},
     },
+    consensus,
     metadata_calculator::MetadataCalculatorConfig,
+    temp_config_store::decode_yaml,
 };
 use zksync_env_config::FromEnv;
 use zksync_node_framework::{
     implementations::layers::{
+        basic_witness_input_producer::BasicWitnessInputProducerLayer,
         commitment_generator::CommitmentGeneratorLayer,
+        consensus::ConsensusLayer,
+        contract_verification_api::ContractVerificationApiLayer,
+        eth_sender::{EthTxAggregatorLayer, EthTxManagerLayer},
         eth_watch::EthWatchLayer,
         fee_input::SequencerFeeInputLayer,
         healtcheck_server::HealthCheckLayer,
         house_keeper::HouseKeeperLayer,
         metadata_calculator::MetadataCalculatorLayer,
         object_store::ObjectStoreLayer,
+        pk_signing_eth_client::PKSigningEthClientLayer,
         pools_layer::PoolsLayerBuilder,
+        priority_ops_monitor::PendingPriorityOpsMonitorLayer,
         proof_data_handler::ProofDataHandlerLayer,
         query_eth_client::QueryEthClientLayer,
         state_keeper::{
             main_batch_executor::MainBatchExecutorLayer, mempool_io::MempoolIOLayer,
             StateKeeperLayer,
         },
+        tx_admission::TxAdmissionPolicyLayer,
         web3_api::{
             server::{Web3ServerLayer, Web3ServerOptionalConfig},
             tree_api_client::TreeApiClientLayer,
@@ -81,11 +93,14 @@ impl MainNodeBuilder {
         let gas_adjuster_config = GasAdjusterConfig::from_env()?;
         let state_keeper_config = StateKeeperConfig::from_env()?;
         let eth_sender_config = ETHSenderConfig::from_env()?;
-        let fee_input_layer = SequencerFeeInputLayer::new(
+        let mut fee_input_layer = SequencerFeeInputLayer::new(
             gas_adjuster_config,
             state_keeper_config,
             eth_sender_config.sender.pubdata_sending_mode,
         );
+        if let Ok(l2_congestion_config) = L2CongestionConfig::from_env() {
+            fee_input_layer = fee_input_layer.with_l2_congestion_config(l2_congestion_config);
+        }
         self.node.add_layer(fee_input_layer);
         Ok(self)
     }
@@ -98,14 +113,19 @@ impl MainNodeBuilder {
     }
 
     fn add_metadata_calculator_layer(mut self) -> anyhow::Result<Self> {
-        let merkle_tree_env_config = DBConfig::from_env()?.merkle_tree;
+        let db_config = DBConfig::from_env()?;
+        let merkle_tree_env_config = &db_config.merkle_tree;
         let operations_manager_env_config = OperationsManagerConfig::from_env()?;
         let metadata_calculator_config = MetadataCalculatorConfig::for_main_node(
-            &merkle_tree_env_config,
+            merkle_tree_env_config,
             &operations_manager_env_config,
         );
-        self.node
-            .add_layer(MetadataCalculatorLayer(metadata_calculator_config));
+        let tree_api_config = ApiConfig::from_env()?.merkle_tree;
+        self.node.add_layer(
+            MetadataCalculatorLayer::new(metadata_calculator_config)
+                .with_tree_api_config(tree_api_config)
+                .with_rocksdb_backups(&db_config),
+        );
         Ok(self)
     }
 
@@ -126,6 +146,15 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_tx_admission_policy_layer(mut self) -> anyhow::Result<Self> {
+        let state_keeper_config = StateKeeperConfig::from_env()?;
+        if state_keeper_config.tx_admission_enabled {
+            self.node
+                .add_layer(TxAdmissionPolicyLayer::new(&state_keeper_config));
+        }
+        Ok(self)
+    }
+
     fn add_eth_watch_layer(mut self) -> anyhow::Result<Self> {
         self.node.add_layer(EthWatchLayer::new(
             ETHWatchConfig::from_env()?,
@@ -232,6 +261,7 @@ impl MainNodeBuilder {
             websocket_requests_per_minute_limit: Some(
                 rpc_config.websocket_requests_per_minute_limit(),
             ),
+            polling_interval: Some(rpc_config.pubsub_interval()),
         };
         self.node.add_layer(Web3ServerLayer::ws(
             rpc_config.ws_port,
@@ -260,6 +290,69 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_priority_ops_monitor_layer(mut self) -> anyhow::Result<Self> {
+        let circuit_breaker_config = CircuitBreakerConfig::from_env()?;
+        self.node
+            .add_layer(PendingPriorityOpsMonitorLayer::new(circuit_breaker_config));
+        Ok(self)
+    }
+
+    fn add_pk_signing_client_layer(mut self) -> anyhow::Result<Self> {
+        self.node.add_layer(PKSigningEthClientLayer::new(
+            ETHSenderConfig::from_env()?,
+            ContractsConfig::from_env()?,
+            ETHClientConfig::from_env()?,
+        ));
+        Ok(self)
+    }
+
+    fn add_eth_sender_layers(mut self) -> anyhow::Result<Self> {
+        let eth_sender_config = ETHSenderConfig::from_env()?;
+        self.node
+            .add_layer(EthTxAggregatorLayer::new(
+                eth_sender_config.clone(),
+                ContractsConfig::from_env()?,
+                NetworkConfig::from_env()?,
+            ))
+            .add_layer(EthTxManagerLayer::new(eth_sender_config));
+        Ok(self)
+    }
+
+    fn add_contract_verification_api_layer(mut self) -> anyhow::Result<Self> {
+        let config = ApiConfig::from_env()?.contract_verification;
+        self.node
+            .add_layer(ContractVerificationApiLayer::new(config));
+        Ok(self)
+    }
+
+    fn add_consensus_layer(mut self) -> anyhow::Result<Self> {
+        // Consensus is optional on the main node; it's only enabled if both the config and secrets are provided.
+        let config = read_consensus_config().context("read_consensus_config()")?;
+        let secrets = read_consensus_secrets().context("read_consensus_secrets()")?;
+        match (config, secrets) {
+            (Some(config), Some(secrets)) => {
+                self.node
+                    .add_layer(ConsensusLayer::main_node(config, secrets));
+            }
+            (None, None) => { /* Consensus is disabled */ }
+            (Some(_), None) => {
+                anyhow::bail!("consensus config is provided, but secrets are missing")
+            }
+            (None, Some(_)) => {
+                anyhow::bail!("consensus secrets are provided, but config is missing")
+            }
+        }
+        Ok(self)
+    }
+
+    fn add_basic_witness_input_producer_layer(mut self) -> anyhow::Result<Self> {
+        let network_config = NetworkConfig::from_env()?;
+        self.node.add_layer(BasicWitnessInputProducerLayer::new(
+            network_config.zksync_network_id,
+        ));
+        Ok(self)
+    }
+
     fn add_commitment_generator_layer(mut self) -> anyhow::Result<Self> {
         self.node.add_layer(CommitmentGeneratorLayer);
 
@@ -271,6 +364,22 @@ impl MainNodeBuilder {
     }
 }
 
+fn read_consensus_secrets() -> anyhow::Result<Option<consensus::Secrets>> {
+    let Ok(path) = std::env::var("CONSENSUS_SECRETS_PATH") else {
+        return Ok(None);
+    };
+    let secrets = std::fs::read_to_string(&path).context(path)?;
+    Ok(Some(decode_yaml(&secrets).context("failed decoding YAML")?))
+}
+
+fn read_consensus_config() -> anyhow::Result<Option<consensus::Config>> {
+    let Ok(path) = std::env::var("CONSENSUS_CONFIG_PATH") else {
+        return Ok(None);
+    };
+    let config = std::fs::read_to_string(&path).context(path)?;
+    Ok(Some(decode_yaml(&config).context("failed decoding YAML")?))
+}
+
 fn main() -> anyhow::Result<()> {
     let observability_config =
         ObservabilityConfig::from_env().context("ObservabilityConfig::from_env()")?;
@@ -285,19 +394,26 @@ fn main() -> anyhow::Result<()> {
     MainNodeBuilder::new()
         .add_pools_layer()?
         .add_query_eth_client_layer()?
+        .add_pk_signing_client_layer()?
         .add_fee_input_layer()?
         .add_object_store_layer()?
         .add_metadata_calculator_layer()?
+        .add_tx_admission_policy_layer()?
         .add_state_keeper_layer()?
         .add_eth_watch_layer()?
+        .add_priority_ops_monitor_layer()?
+        .add_eth_sender_layers()?
         .add_proof_data_handler_layer()?
         .add_healthcheck_layer()?
         .add_tx_sender_layer()?
         .add_tree_api_client_layer()?
         .add_http_web3_api_layer()?
         .add_ws_web3_api_layer()?
+        .add_contract_verification_api_layer()?
+        .add_basic_witness_input_producer_layer()?
         .add_house_keeper_layer()?
         .add_commitment_generator_layer()?
+        .add_consensus_layer()?
         .build()
         .run()?;
 
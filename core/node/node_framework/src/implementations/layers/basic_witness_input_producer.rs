@@ -0,0 +1,71 @@
+use zksync_core::basic_witness_input_producer::BasicWitnessInputProducer;
+use zksync_queued_job_processor::JobProcessor;
+use zksync_types::L2ChainId;
+
+use crate::{
+    implementations::resources::{object_store::ObjectStoreResource, pools::MasterPoolResource},
+    service::{ServiceContext, StopReceiver},
+    task::Task,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Builder for the basic witness input producer.
+///
+/// ## Effects
+///
+/// - Resolves `MasterPoolResource`.
+/// - Resolves `ObjectStoreResource`.
+/// - Adds `basic_witness_input_producer` to the node (unless running in the local setup).
+#[derive(Debug)]
+pub struct BasicWitnessInputProducerLayer {
+    l2_chain_id: L2ChainId,
+}
+
+impl BasicWitnessInputProducerLayer {
+    pub fn new(l2_chain_id: L2ChainId) -> Self {
+        Self { l2_chain_id }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for BasicWitnessInputProducerLayer {
+    fn layer_name(&self) -> &'static str {
+        "basic_witness_input_producer_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        // Witness Generator won't be spawned with `ZKSYNC_LOCAL_SETUP` running.
+        // BasicWitnessInputProducer shouldn't be producing input for it locally either.
+        if std::env::var("ZKSYNC_LOCAL_SETUP") == Ok("true".to_owned()) {
+            tracing::info!("Running in the local setup; basic witness input producer is disabled");
+            return Ok(());
+        }
+
+        let pool = context
+            .get_resource::<MasterPoolResource>()
+            .await?
+            .get_singleton()
+            .await?;
+        let ObjectStoreResource(object_store) = context.get_resource().await?;
+
+        let producer = BasicWitnessInputProducer::new(pool, object_store, self.l2_chain_id);
+        context.add_task(Box::new(BasicWitnessInputProducerTask { producer }));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct BasicWitnessInputProducerTask {
+    producer: BasicWitnessInputProducer,
+}
+
+#[async_trait::async_trait]
+impl Task for BasicWitnessInputProducerTask {
+    fn name(&self) -> &'static str {
+        "basic_witness_input_producer"
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.producer.run(stop_receiver.0, None).await
+    }
+}
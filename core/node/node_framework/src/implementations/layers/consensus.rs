@@ -0,0 +1,195 @@
+use anyhow::Context as _;
+use zksync_concurrency::{ctx, limiter, scope, time};
+use zksync_core::{
+    consensus::{self, Fetcher, Store},
+    sync_layer::{ActionQueueSender, SyncState},
+};
+use zksync_dal::ConnectionPool;
+use zksync_web3_decl::jsonrpsee::http_client::HttpClient;
+
+use crate::{
+    implementations::resources::{
+        action_queue::ActionQueueSenderResource, main_node_client::MainNodeClientResource,
+        pools::MasterPoolResource, sync_state::SyncStateResource,
+    },
+    service::{ServiceContext, StopReceiver},
+    task::Task,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+#[derive(Debug)]
+enum Mode {
+    MainNode {
+        config: consensus::Config,
+        secrets: consensus::Secrets,
+    },
+    ExternalNode {
+        config: Option<(consensus::Config, consensus::Secrets)>,
+    },
+}
+
+/// Builder for the consensus component.
+///
+/// On the main node, the component generates consensus certificates for the L2 blocks produced by the state keeper
+/// and broadcasts them to the gossip network peers.
+///
+/// On the external node, the component fetches L2 blocks. If the consensus config is provided, L2 blocks are
+/// fetched using the peer-to-peer gossip network; otherwise, they are fetched from the main node JSON-RPC API.
+///
+/// ## Effects
+///
+/// - Resolves `MasterPoolResource`.
+/// - Resolves `MainNodeClientResource` (external node only).
+/// - Resolves `SyncStateResource` (external node only).
+/// - Takes `ActionQueueSenderResource` (external node only).
+/// - Adds `consensus` (main node) or `consensus_fetcher` (external node) to the node.
+#[derive(Debug)]
+pub struct ConsensusLayer {
+    mode: Mode,
+}
+
+impl ConsensusLayer {
+    pub fn main_node(config: consensus::Config, secrets: consensus::Secrets) -> Self {
+        Self {
+            mode: Mode::MainNode { config, secrets },
+        }
+    }
+
+    pub fn external_node(config: Option<(consensus::Config, consensus::Secrets)>) -> Self {
+        Self {
+            mode: Mode::ExternalNode { config },
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for ConsensusLayer {
+    fn layer_name(&self) -> &'static str {
+        "consensus_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let pool = context
+            .get_resource::<MasterPoolResource>()
+            .await?
+            .get()
+            .await?;
+
+        let config = match self.mode {
+            Mode::MainNode { config, secrets } => {
+                let config = config
+                    .main_node(&secrets)
+                    .context("invalid consensus config")?;
+                context.add_task(Box::new(MainNodeConsensusTask { config, pool }));
+                return Ok(());
+            }
+            Mode::ExternalNode { config } => config,
+        };
+
+        let MainNodeClientResource(main_node_client) = context.get_resource().await?;
+        let SyncStateResource(sync_state) = context.get_resource().await?;
+        let action_queue_sender = context
+            .get_resource::<ActionQueueSenderResource>()
+            .await?
+            .0
+            .take()
+            .context("ActionQueueSender was provided but taken by some other task")?;
+
+        let p2p_config = config
+            .map(|(config, secrets)| config.p2p(&secrets))
+            .transpose()
+            .context("invalid consensus config")?;
+
+        context.add_task(Box::new(FetcherTask {
+            p2p_config,
+            pool,
+            main_node_client,
+            sync_state,
+            action_queue_sender,
+        }));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct MainNodeConsensusTask {
+    config: consensus::MainNodeConfig,
+    pool: ConnectionPool,
+}
+
+#[async_trait::async_trait]
+impl Task for MainNodeConsensusTask {
+    fn name(&self) -> &'static str {
+        "consensus"
+    }
+
+    async fn run(self: Box<Self>, mut stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        let config = self.config;
+        let store = Store(self.pool);
+        scope::run!(&ctx::root(), |ctx, s| async {
+            s.spawn_bg(async {
+                // Consensus is a new component.
+                // For now in case of error we just log it and allow the server
+                // to continue running.
+                if let Err(err) = config.run(ctx, store).await {
+                    tracing::error!(%err, "Consensus actor failed");
+                } else {
+                    tracing::info!("Consensus actor stopped");
+                }
+                Ok(())
+            });
+            let _ = stop_receiver.0.wait_for(|stop| *stop).await?;
+            Ok(())
+        })
+        .await
+    }
+}
+
+#[derive(Debug)]
+struct FetcherTask {
+    p2p_config: Option<consensus::P2PConfig>,
+    pool: ConnectionPool,
+    main_node_client: HttpClient,
+    sync_state: SyncState,
+    action_queue_sender: ActionQueueSender,
+}
+
+#[async_trait::async_trait]
+impl Task for FetcherTask {
+    fn name(&self) -> &'static str {
+        "consensus_fetcher"
+    }
+
+    async fn run(self: Box<Self>, mut stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        let root_ctx = ctx::root();
+        let fetcher = Fetcher {
+            store: Store(self.pool),
+            sync_state: self.sync_state,
+            client: Box::new(self.main_node_client),
+            limiter: limiter::Limiter::new(
+                &root_ctx,
+                limiter::Rate {
+                    burst: 10,
+                    refresh: time::Duration::milliseconds(30),
+                },
+            ),
+        };
+        let actions = self.action_queue_sender;
+        let p2p_config = self.p2p_config;
+
+        scope::run!(&root_ctx, |ctx, s| async {
+            s.spawn_bg(async {
+                let res = match p2p_config {
+                    Some(p2p_config) => fetcher.run_p2p(ctx, actions, p2p_config).await,
+                    None => fetcher.run_centralized(ctx, actions).await,
+                };
+                tracing::info!("Consensus actor stopped");
+                res
+            });
+            ctx.wait(stop_receiver.0.wait_for(|stop| *stop)).await??;
+            Ok(())
+        })
+        .await
+        .context("consensus actor")
+    }
+}
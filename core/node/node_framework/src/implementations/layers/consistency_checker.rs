@@ -0,0 +1,83 @@
+use anyhow::Context as _;
+use zksync_core::consistency_checker::ConsistencyChecker;
+use zksync_types::Address;
+
+use crate::{
+    implementations::resources::{healthcheck::AppHealthCheckResource, pools::MasterPoolResource},
+    service::{ServiceContext, StopReceiver},
+    task::Task,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Builder for the consistency checker, which verifies that the L1 batches committed to L1
+/// match the locally stored ones.
+///
+/// ## Effects
+///
+/// - Resolves `MasterPoolResource`.
+/// - Adds `consistency_checker` health check to the `AppHealthCheckResource`.
+/// - Adds `consistency_checker` to the node.
+#[derive(Debug)]
+pub struct ConsistencyCheckerLayer {
+    l1_client_url: String,
+    diamond_proxy_addr: Address,
+    max_batches_to_recheck: u32,
+}
+
+impl ConsistencyCheckerLayer {
+    pub fn new(
+        l1_client_url: String,
+        diamond_proxy_addr: Address,
+        max_batches_to_recheck: u32,
+    ) -> Self {
+        Self {
+            l1_client_url,
+            diamond_proxy_addr,
+            max_batches_to_recheck,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for ConsistencyCheckerLayer {
+    fn layer_name(&self) -> &'static str {
+        "consistency_checker_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let pool = context
+            .get_resource::<MasterPoolResource>()
+            .await?
+            .get_singleton()
+            .await?;
+
+        let consistency_checker =
+            ConsistencyChecker::new(&self.l1_client_url, self.max_batches_to_recheck, pool)
+                .context("cannot initialize consistency checker")?
+                .with_diamond_proxy_addr(self.diamond_proxy_addr);
+
+        let AppHealthCheckResource(app_health) = context.get_resource_or_default().await;
+        app_health.insert_component(consistency_checker.health_check().clone());
+
+        context.add_task(Box::new(ConsistencyCheckerTask {
+            consistency_checker,
+        }));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct ConsistencyCheckerTask {
+    consistency_checker: ConsistencyChecker,
+}
+
+#[async_trait::async_trait]
+impl Task for ConsistencyCheckerTask {
+    fn name(&self) -> &'static str {
+        "consistency_checker"
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.consistency_checker.run(stop_receiver.0).await
+    }
+}
@@ -0,0 +1,104 @@
+use std::net::TcpListener;
+
+use anyhow::Context;
+use zksync_config::configs::api::ContractVerificationApiConfig;
+use zksync_core::api_server::contract_verification;
+use zksync_dal::ConnectionPool;
+
+use crate::{
+    implementations::resources::pools::{MasterPoolResource, ReplicaPoolResource},
+    service::{ServiceContext, StopReceiver},
+    task::Task,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Builder for the contract verification REST API.
+///
+/// ## Effects
+///
+/// - Resolves `MasterPoolResource`.
+/// - Resolves `ReplicaPoolResource`.
+/// - Adds `contract_verification_api` to the node.
+#[derive(Debug)]
+pub struct ContractVerificationApiLayer {
+    config: ContractVerificationApiConfig,
+    listener: Option<TcpListener>,
+}
+
+impl ContractVerificationApiLayer {
+    pub fn new(config: ContractVerificationApiConfig) -> Self {
+        Self {
+            config,
+            listener: None,
+        }
+    }
+
+    /// Makes the API server use the provided TCP listener instead of binding to the address from the config.
+    pub fn with_listener(mut self, listener: TcpListener) -> Self {
+        self.listener = Some(listener);
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for ContractVerificationApiLayer {
+    fn layer_name(&self) -> &'static str {
+        "contract_verification_api_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let master_pool = context
+            .get_resource::<MasterPoolResource>()
+            .await?
+            .get()
+            .await?;
+        let replica_pool = context
+            .get_resource::<ReplicaPoolResource>()
+            .await?
+            .get()
+            .await?;
+        context.add_task(Box::new(ContractVerificationApiTask {
+            master_pool,
+            replica_pool,
+            config: self.config,
+            listener: self.listener,
+        }));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct ContractVerificationApiTask {
+    master_pool: ConnectionPool,
+    replica_pool: ConnectionPool,
+    config: ContractVerificationApiConfig,
+    listener: Option<TcpListener>,
+}
+
+#[async_trait::async_trait]
+impl Task for ContractVerificationApiTask {
+    fn name(&self) -> &'static str {
+        "contract_verification_api"
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        let server_handle = if let Some(listener) = self.listener {
+            contract_verification::start_server_thread_detached_with_listener(
+                self.master_pool,
+                self.replica_pool,
+                listener,
+                stop_receiver.0,
+            )
+        } else {
+            contract_verification::start_server_thread_detached(
+                self.master_pool,
+                self.replica_pool,
+                self.config,
+                stop_receiver.0,
+            )
+        };
+        server_handle
+            .await
+            .context("contract verification API panicked")?
+    }
+}
@@ -0,0 +1,190 @@
+use anyhow::Context;
+use zksync_config::{configs::chain::NetworkConfig, ContractsConfig, ETHSenderConfig};
+use zksync_core::eth_sender::{Aggregator, EthTxAggregator, EthTxManager};
+use zksync_dal::ConnectionPool;
+
+use crate::{
+    implementations::resources::{
+        eth_interface::{BoundEthInterfaceForBlobsResource, BoundEthInterfaceResource},
+        l1_tx_params::L1TxParamsResource,
+        object_store::ObjectStoreResource,
+        pools::MasterPoolResource,
+    },
+    service::{ServiceContext, StopReceiver},
+    task::Task,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Builder for the component aggregating L1 batches into L1 transactions.
+///
+/// ## Effects
+///
+/// - Resolves `MasterPoolResource`.
+/// - Resolves `BoundEthInterfaceResource`.
+/// - Resolves `BoundEthInterfaceForBlobsResource` (optional).
+/// - Resolves `ObjectStoreResource`.
+/// - Adds `eth_tx_aggregator` to the node.
+#[derive(Debug)]
+pub struct EthTxAggregatorLayer {
+    eth_sender_config: ETHSenderConfig,
+    contracts_config: ContractsConfig,
+    network_config: NetworkConfig,
+}
+
+impl EthTxAggregatorLayer {
+    pub fn new(
+        eth_sender_config: ETHSenderConfig,
+        contracts_config: ContractsConfig,
+        network_config: NetworkConfig,
+    ) -> Self {
+        Self {
+            eth_sender_config,
+            contracts_config,
+            network_config,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for EthTxAggregatorLayer {
+    fn layer_name(&self) -> &'static str {
+        "eth_tx_aggregator_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let pool = context
+            .get_resource::<MasterPoolResource>()
+            .await?
+            .get_singleton()
+            .await
+            .context("failed to build eth_sender pool")?;
+        let BoundEthInterfaceResource(eth_client) = context.get_resource().await?;
+        let eth_client_blobs = match context
+            .get_resource::<BoundEthInterfaceForBlobsResource>()
+            .await
+        {
+            Ok(BoundEthInterfaceForBlobsResource(client)) => Some(client),
+            Err(WiringError::ResourceLacking(_)) => None,
+            Err(err) => return Err(err),
+        };
+        let ObjectStoreResource(object_store) = context.get_resource().await?;
+
+        let sender_config = self.eth_sender_config.sender;
+        let aggregator = Aggregator::new(
+            sender_config.clone(),
+            object_store,
+            eth_client_blobs.is_some(),
+            sender_config.pubdata_sending_mode.into(),
+        );
+        let eth_tx_aggregator = EthTxAggregator::new(
+            sender_config,
+            aggregator,
+            eth_client,
+            self.contracts_config.validator_timelock_addr,
+            self.contracts_config.l1_multicall3_addr,
+            self.contracts_config.diamond_proxy_addr,
+            self.network_config.zksync_network_id,
+            eth_client_blobs.map(|client| client.sender_account()),
+        )
+        .await;
+
+        context.add_task(Box::new(EthTxAggregatorTask {
+            eth_tx_aggregator,
+            pool,
+        }));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct EthTxAggregatorTask {
+    eth_tx_aggregator: EthTxAggregator,
+    pool: ConnectionPool,
+}
+
+#[async_trait::async_trait]
+impl Task for EthTxAggregatorTask {
+    fn name(&self) -> &'static str {
+        "eth_tx_aggregator"
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.eth_tx_aggregator.run(self.pool, stop_receiver.0).await
+    }
+}
+
+/// Builder for the component sending L1 transactions and monitoring their status.
+///
+/// ## Effects
+///
+/// - Resolves `MasterPoolResource`.
+/// - Resolves `BoundEthInterfaceResource`.
+/// - Resolves `BoundEthInterfaceForBlobsResource` (optional).
+/// - Resolves `L1TxParamsResource`.
+/// - Adds `eth_tx_manager` to the node.
+#[derive(Debug)]
+pub struct EthTxManagerLayer {
+    eth_sender_config: ETHSenderConfig,
+}
+
+impl EthTxManagerLayer {
+    pub fn new(eth_sender_config: ETHSenderConfig) -> Self {
+        Self { eth_sender_config }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for EthTxManagerLayer {
+    fn layer_name(&self) -> &'static str {
+        "eth_tx_manager_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let pool = context
+            .get_resource::<MasterPoolResource>()
+            .await?
+            .get_singleton()
+            .await
+            .context("failed to build eth_manager pool")?;
+        let BoundEthInterfaceResource(eth_client) = context.get_resource().await?;
+        let eth_client_blobs = match context
+            .get_resource::<BoundEthInterfaceForBlobsResource>()
+            .await
+        {
+            Ok(BoundEthInterfaceForBlobsResource(client)) => Some(client),
+            Err(WiringError::ResourceLacking(_)) => None,
+            Err(err) => return Err(err),
+        };
+        let L1TxParamsResource(gas_adjuster) = context.get_resource().await?;
+
+        let eth_tx_manager = EthTxManager::new(
+            self.eth_sender_config.sender,
+            gas_adjuster,
+            eth_client,
+            eth_client_blobs,
+        );
+
+        context.add_task(Box::new(EthTxManagerTask {
+            eth_tx_manager,
+            pool,
+        }));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct EthTxManagerTask {
+    eth_tx_manager: EthTxManager,
+    pool: ConnectionPool,
+}
+
+#[async_trait::async_trait]
+impl Task for EthTxManagerTask {
+    fn name(&self) -> &'static str {
+        "eth_tx_manager"
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.eth_tx_manager.run(self.pool, stop_receiver.0).await
+    }
+}
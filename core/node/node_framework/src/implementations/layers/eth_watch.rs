@@ -1,5 +1,6 @@
 use std::time::Duration;
 
+use anyhow::Context;
 use zksync_config::{ContractsConfig, ETHWatchConfig};
 use zksync_contracts::governance_contract;
 use zksync_core::eth_watch::{client::EthHttpQueryClient, EthWatch};
@@ -7,12 +8,25 @@ use zksync_dal::ConnectionPool;
 use zksync_types::{ethabi::Contract, Address};
 
 use crate::{
-    implementations::resources::{eth_interface::EthInterfaceResource, pools::MasterPoolResource},
+    implementations::resources::{
+        eth_interface::EthInterfaceResource, eth_watch::EventProcessorResource,
+        pools::MasterPoolResource,
+    },
+    resource::ResourceCollection,
     service::{ServiceContext, StopReceiver},
     task::Task,
     wiring_layer::{WiringError, WiringLayer},
 };
 
+/// Builder for the Ethereum watcher.
+///
+/// Besides the built-in event processors, the watcher runs all custom processors added by other layers
+/// to the `ResourceCollection<EventProcessorResource>`.
+///
+/// ## Effects
+///
+/// - Resolves `ResourceCollection<EventProcessorResource>`.
+/// - Adds `eth_watch` to the node.
 #[derive(Debug)]
 pub struct EthWatchLayer {
     eth_watch_config: ETHWatchConfig,
@@ -40,17 +54,18 @@ impl WiringLayer for EthWatchLayer {
 
         let client = context.get_resource::<EthInterfaceResource>().await?.0;
 
-        let eth_client = EthHttpQueryClient::new(
-            client,
-            self.contracts_config.diamond_proxy_addr,
-            Some(self.contracts_config.governance_addr),
-            self.eth_watch_config.confirmations_for_eth_event,
-        );
+        let event_processors = context
+            .get_resource_or_default::<ResourceCollection<EventProcessorResource>>()
+            .await;
+
+        let eth_client =
+            EthHttpQueryClient::new(client, self.eth_watch_config.l1_block_selection());
         context.add_task(Box::new(EthWatchTask {
             main_pool,
             client: eth_client,
-            governance_contract: Some(governance_contract()),
+            governance: Some((governance_contract(), self.contracts_config.governance_addr)),
             diamond_proxy_address: self.contracts_config.diamond_proxy_addr,
+            event_processors,
             poll_interval: self.eth_watch_config.poll_interval(),
         }));
 
@@ -62,8 +77,9 @@ impl WiringLayer for EthWatchLayer {
 struct EthWatchTask {
     main_pool: ConnectionPool,
     client: EthHttpQueryClient,
-    governance_contract: Option<Contract>,
+    governance: Option<(Contract, Address)>,
     diamond_proxy_address: Address,
+    event_processors: ResourceCollection<EventProcessorResource>,
     poll_interval: Duration,
 }
 
@@ -74,14 +90,24 @@ impl Task for EthWatchTask {
     }
 
     async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
-        let eth_watch = EthWatch::new(
+        let mut eth_watch = EthWatch::new(
             self.diamond_proxy_address,
-            self.governance_contract,
+            self.governance,
             Box::new(self.client),
             self.main_pool,
             self.poll_interval,
         )
         .await;
+        for EventProcessorResource(processor) in self.event_processors.resolve().await {
+            let processor = processor
+                .take()
+                .context("event processor was taken by another task")?;
+            // Processors are only available after the wiring is complete, so duplicate processors
+            // are detected here rather than in `wire()`.
+            eth_watch = eth_watch
+                .with_event_processor(processor)
+                .map_err(|err| WiringError::Configuration(format!("{err:#}")))?;
+        }
 
         eth_watch.run(stop_receiver.0).await
     }
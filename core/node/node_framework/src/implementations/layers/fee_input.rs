@@ -2,15 +2,23 @@ use std::sync::Arc;
 
 use anyhow::Context;
 use zksync_config::{
-    configs::{chain::StateKeeperConfig, eth_sender::PubdataSendingMode},
+    configs::{
+        chain::{L2CongestionConfig, StateKeeperConfig},
+        eth_sender::PubdataSendingMode,
+    },
     GasAdjusterConfig,
 };
-use zksync_core::{fee_model::MainNodeFeeInputProvider, l1_gas_price::GasAdjuster};
+use zksync_core::{
+    fee_model::{L2CongestionMonitor, MainNodeFeeInputProvider},
+    l1_gas_price::{GasAdjuster, MainNodeFeeParamsFetcher},
+};
 use zksync_types::fee_model::FeeModelConfig;
 
 use crate::{
     implementations::resources::{
         eth_interface::EthInterfaceResource, fee_input::FeeInputResource,
+        l1_tx_params::L1TxParamsResource, main_node_client::MainNodeClientResource,
+        pools::ReplicaPoolResource,
     },
     service::{ServiceContext, StopReceiver},
     task::Task,
@@ -22,6 +30,7 @@ pub struct SequencerFeeInputLayer {
     gas_adjuster_config: GasAdjusterConfig,
     state_keeper_config: StateKeeperConfig,
     pubdata_sending_mode: PubdataSendingMode,
+    l2_congestion_config: Option<L2CongestionConfig>,
 }
 
 impl SequencerFeeInputLayer {
@@ -34,8 +43,15 @@ impl SequencerFeeInputLayer {
             gas_adjuster_config,
             state_keeper_config,
             pubdata_sending_mode,
+            l2_congestion_config: None,
         }
     }
+
+    /// Enables the L2 congestion component of the fee model.
+    pub fn with_l2_congestion_config(mut self, config: L2CongestionConfig) -> Self {
+        self.l2_congestion_config = Some(config);
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -52,11 +68,23 @@ impl WiringLayer for SequencerFeeInputLayer {
                 .context("GasAdjuster::new()")?;
         let gas_adjuster = Arc::new(adjuster);
 
-        let batch_fee_input_provider = Arc::new(MainNodeFeeInputProvider::new(
+        let mut batch_fee_input_provider = MainNodeFeeInputProvider::new(
             gas_adjuster.clone(),
             FeeModelConfig::from_state_keeper_config(&self.state_keeper_config),
-        ));
-        context.insert_resource(FeeInputResource(batch_fee_input_provider))?;
+        );
+        if let Some(l2_congestion_config) = &self.l2_congestion_config {
+            let replica_pool = context.get_resource::<ReplicaPoolResource>().await?;
+            let pool = replica_pool.get().await?;
+            let monitor =
+                L2CongestionMonitor::new(pool, &self.state_keeper_config, l2_congestion_config)
+                    .context("L2CongestionMonitor::new()")?;
+            let monitor = Arc::new(monitor);
+            batch_fee_input_provider =
+                batch_fee_input_provider.with_l2_congestion_monitor(monitor.clone());
+            context.add_task(Box::new(L2CongestionMonitorTask { monitor }));
+        }
+        context.insert_resource(FeeInputResource(Arc::new(batch_fee_input_provider)))?;
+        context.insert_resource(L1TxParamsResource(gas_adjuster.clone()))?;
 
         context.add_task(Box::new(GasAdjusterTask { gas_adjuster }));
         Ok(())
@@ -78,3 +106,60 @@ impl Task for GasAdjusterTask {
         self.gas_adjuster.run(stop_receiver.0).await
     }
 }
+
+#[derive(Debug)]
+struct L2CongestionMonitorTask {
+    monitor: Arc<L2CongestionMonitor>,
+}
+
+#[async_trait::async_trait]
+impl Task for L2CongestionMonitorTask {
+    fn name(&self) -> &'static str {
+        "l2_congestion_monitor"
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.monitor.run(stop_receiver.0).await
+    }
+}
+
+/// Builder for the fee input provider of the external node, which fetches fee params from the main node.
+///
+/// ## Effects
+///
+/// - Resolves `MainNodeClientResource`.
+/// - Adds `FeeInputResource`.
+/// - Adds `main_node_fee_params_fetcher` to the node.
+#[derive(Debug)]
+pub struct MainNodeFeeParamsFetcherLayer;
+
+#[async_trait::async_trait]
+impl WiringLayer for MainNodeFeeParamsFetcherLayer {
+    fn layer_name(&self) -> &'static str {
+        "main_node_fee_params_fetcher_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let MainNodeClientResource(main_node_client) = context.get_resource().await?;
+        let fetcher = Arc::new(MainNodeFeeParamsFetcher::new(main_node_client));
+        context.insert_resource(FeeInputResource(fetcher.clone()))?;
+        context.add_task(Box::new(MainNodeFeeParamsFetcherTask { fetcher }));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct MainNodeFeeParamsFetcherTask {
+    fetcher: Arc<MainNodeFeeParamsFetcher>,
+}
+
+#[async_trait::async_trait]
+impl Task for MainNodeFeeParamsFetcherTask {
+    fn name(&self) -> &'static str {
+        "main_node_fee_params_fetcher"
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.fetcher.run(stop_receiver.0).await
+    }
+}
@@ -18,6 +18,9 @@ use crate::{
 ///
 /// This layer expects other tasks to add health checks to the `ResourceCollection<HealthCheckResource>`.
 ///
+/// Health check time limits from the config are only applied if this layer is the first one to access
+/// `AppHealthCheckResource`, so it should be added before the other layers.
+///
 /// ## Effects
 ///
 /// - Resolves `ResourceCollection<HealthCheckResource>`.
@@ -32,7 +35,14 @@ impl WiringLayer for HealthCheckLayer {
     }
 
     async fn wire(self: Box<Self>, mut node: ServiceContext<'_>) -> Result<(), WiringError> {
-        let AppHealthCheckResource(app_health_check) = node.get_resource_or_default().await;
+        let AppHealthCheckResource(app_health_check) = node
+            .get_resource_or_insert_with(|| {
+                AppHealthCheckResource(Arc::new(AppHealthCheck::new(
+                    self.0.slow_time_limit(),
+                    self.0.hard_time_limit(),
+                )))
+            })
+            .await;
 
         let task = HealthCheckTask {
             config: self.0,
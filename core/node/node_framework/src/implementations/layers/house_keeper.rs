@@ -21,7 +21,7 @@ use zksync_dal::ConnectionPool;
 use crate::{
     implementations::resources::pools::{ProverPoolResource, ReplicaPoolResource},
     service::{ServiceContext, StopReceiver},
-    task::Task,
+    task::{Backoff, RestartPolicy, Task},
     wiring_layer::{WiringError, WiringLayer},
 };
 
@@ -68,18 +68,24 @@ impl WiringLayer for HouseKeeperLayer {
         let prover_pool_resource = context.get_resource::<ProverPoolResource>().await?;
         let prover_pool = prover_pool_resource.get().await?;
 
+        // House keeper tasks are auxiliary, so failures are not fatal for the node.
+        let restart_policy = RestartPolicy::OnFailure(Backoff::default());
+
         // initialize and add tasks
         let pool_for_metrics = replica_pool.clone();
-        context.add_task(Box::new(PoolForMetricsTask { pool_for_metrics }));
+        context.add_restartable_task(PoolForMetricsTask { pool_for_metrics }, restart_policy);
 
         let l1_batch_metrics_reporter = L1BatchMetricsReporter::new(
             self.house_keeper_config
                 .l1_batch_metrics_reporting_interval_ms,
             replica_pool.clone(),
         );
-        context.add_task(Box::new(L1BatchMetricsReporterTask {
-            l1_batch_metrics_reporter,
-        }));
+        context.add_restartable_task(
+            L1BatchMetricsReporterTask {
+                l1_batch_metrics_reporter,
+            },
+            restart_policy,
+        );
 
         let fri_prover_job_retry_manager = FriProverJobRetryManager::new(
             self.fri_prover_config.max_attempts,
@@ -87,9 +93,12 @@ impl WiringLayer for HouseKeeperLayer {
             self.house_keeper_config.fri_prover_job_retrying_interval_ms,
             prover_pool.clone(),
         );
-        context.add_task(Box::new(FriProverJobRetryManagerTask {
-            fri_prover_job_retry_manager,
-        }));
+        context.add_restartable_task(
+            FriProverJobRetryManagerTask {
+                fri_prover_job_retry_manager,
+            },
+            restart_policy,
+        );
 
         let fri_witness_gen_job_retry_manager = FriWitnessGeneratorJobRetryManager::new(
             self.fri_witness_generator_config.max_attempts,
@@ -99,34 +108,46 @@ impl WiringLayer for HouseKeeperLayer {
                 .fri_witness_generator_job_retrying_interval_ms,
             prover_pool.clone(),
         );
-        context.add_task(Box::new(FriWitnessGeneratorJobRetryManagerTask {
-            fri_witness_gen_job_retry_manager,
-        }));
+        context.add_restartable_task(
+            FriWitnessGeneratorJobRetryManagerTask {
+                fri_witness_gen_job_retry_manager,
+            },
+            restart_policy,
+        );
 
         let waiting_to_queued_fri_witness_job_mover = WaitingToQueuedFriWitnessJobMover::new(
             self.house_keeper_config.fri_witness_job_moving_interval_ms,
             prover_pool.clone(),
         );
-        context.add_task(Box::new(WaitingToQueuedFriWitnessJobMoverTask {
-            waiting_to_queued_fri_witness_job_mover,
-        }));
+        context.add_restartable_task(
+            WaitingToQueuedFriWitnessJobMoverTask {
+                waiting_to_queued_fri_witness_job_mover,
+            },
+            restart_policy,
+        );
 
         let scheduler_circuit_queuer = SchedulerCircuitQueuer::new(
             self.house_keeper_config.fri_witness_job_moving_interval_ms,
             prover_pool.clone(),
         );
-        context.add_task(Box::new(SchedulerCircuitQueuerTask {
-            scheduler_circuit_queuer,
-        }));
+        context.add_restartable_task(
+            SchedulerCircuitQueuerTask {
+                scheduler_circuit_queuer,
+            },
+            restart_policy,
+        );
 
         let fri_witness_generator_stats_reporter = FriWitnessGeneratorStatsReporter::new(
             prover_pool.clone(),
             self.house_keeper_config
                 .witness_generator_stats_reporting_interval_ms,
         );
-        context.add_task(Box::new(FriWitnessGeneratorStatsReporterTask {
-            fri_witness_generator_stats_reporter,
-        }));
+        context.add_restartable_task(
+            FriWitnessGeneratorStatsReporterTask {
+                fri_witness_generator_stats_reporter,
+            },
+            restart_policy,
+        );
 
         let fri_prover_stats_reporter = FriProverStatsReporter::new(
             self.house_keeper_config
@@ -135,18 +156,24 @@ impl WiringLayer for HouseKeeperLayer {
             replica_pool.clone(),
             self.fri_prover_group_config,
         );
-        context.add_task(Box::new(FriProverStatsReporterTask {
-            fri_prover_stats_reporter,
-        }));
+        context.add_restartable_task(
+            FriProverStatsReporterTask {
+                fri_prover_stats_reporter,
+            },
+            restart_policy,
+        );
 
         let fri_proof_compressor_stats_reporter = FriProofCompressorStatsReporter::new(
             self.house_keeper_config
                 .fri_proof_compressor_stats_reporting_interval_ms,
             prover_pool.clone(),
         );
-        context.add_task(Box::new(FriProofCompressorStatsReporterTask {
-            fri_proof_compressor_stats_reporter,
-        }));
+        context.add_restartable_task(
+            FriProofCompressorStatsReporterTask {
+                fri_proof_compressor_stats_reporter,
+            },
+            restart_policy,
+        );
 
         let fri_proof_compressor_retry_manager = FriProofCompressorJobRetryManager::new(
             self.fri_proof_compressor_config.max_attempts,
@@ -155,9 +182,12 @@ impl WiringLayer for HouseKeeperLayer {
                 .fri_proof_compressor_job_retrying_interval_ms,
             prover_pool.clone(),
         );
-        context.add_task(Box::new(FriProofCompressorJobRetryManagerTask {
-            fri_proof_compressor_retry_manager,
-        }));
+        context.add_restartable_task(
+            FriProofCompressorJobRetryManagerTask {
+                fri_proof_compressor_retry_manager,
+            },
+            restart_policy,
+        );
 
         Ok(())
     }
@@ -165,7 +195,7 @@ impl WiringLayer for HouseKeeperLayer {
 
 // TODO (QIT-29): Support stop receivers for house keeper related tasks.
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct PoolForMetricsTask {
     pool_for_metrics: ConnectionPool,
 }
@@ -185,7 +215,7 @@ impl Task for PoolForMetricsTask {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct L1BatchMetricsReporterTask {
     l1_batch_metrics_reporter: L1BatchMetricsReporter,
 }
@@ -201,7 +231,7 @@ impl Task for L1BatchMetricsReporterTask {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct FriProverJobRetryManagerTask {
     fri_prover_job_retry_manager: FriProverJobRetryManager,
 }
@@ -217,7 +247,7 @@ impl Task for FriProverJobRetryManagerTask {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct FriWitnessGeneratorJobRetryManagerTask {
     fri_witness_gen_job_retry_manager: FriWitnessGeneratorJobRetryManager,
 }
@@ -233,7 +263,7 @@ impl Task for FriWitnessGeneratorJobRetryManagerTask {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct WaitingToQueuedFriWitnessJobMoverTask {
     waiting_to_queued_fri_witness_job_mover: WaitingToQueuedFriWitnessJobMover,
 }
@@ -249,7 +279,7 @@ impl Task for WaitingToQueuedFriWitnessJobMoverTask {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct SchedulerCircuitQueuerTask {
     scheduler_circuit_queuer: SchedulerCircuitQueuer,
 }
@@ -265,7 +295,7 @@ impl Task for SchedulerCircuitQueuerTask {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct FriWitnessGeneratorStatsReporterTask {
     fri_witness_generator_stats_reporter: FriWitnessGeneratorStatsReporter,
 }
@@ -281,7 +311,7 @@ impl Task for FriWitnessGeneratorStatsReporterTask {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct FriProverStatsReporterTask {
     fri_prover_stats_reporter: FriProverStatsReporter,
 }
@@ -297,7 +327,7 @@ impl Task for FriProverStatsReporterTask {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct FriProofCompressorStatsReporterTask {
     fri_proof_compressor_stats_reporter: FriProofCompressorStatsReporter,
 }
@@ -313,7 +343,7 @@ impl Task for FriProofCompressorStatsReporterTask {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct FriProofCompressorJobRetryManagerTask {
     fri_proof_compressor_retry_manager: FriProofCompressorJobRetryManager,
 }
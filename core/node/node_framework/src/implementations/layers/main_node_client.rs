@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use zksync_core::sync_layer::{MainNodeClient, MainNodeHealthCheck};
+
+use crate::{
+    implementations::resources::{
+        healthcheck::AppHealthCheckResource, main_node_client::MainNodeClientResource,
+    },
+    service::ServiceContext,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Builder for the JSON-RPC client of the main node.
+///
+/// ## Effects
+///
+/// - Adds `MainNodeClientResource`.
+/// - Adds main node health check to the `AppHealthCheckResource`.
+#[derive(Debug)]
+pub struct MainNodeClientLayer {
+    url: String,
+}
+
+impl MainNodeClientLayer {
+    pub fn new(url: String) -> Self {
+        Self { url }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for MainNodeClientLayer {
+    fn layer_name(&self) -> &'static str {
+        "main_node_client_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let client = <dyn MainNodeClient>::json_rpc(&self.url)
+            .context("failed creating JSON-RPC client for main node")?;
+
+        let AppHealthCheckResource(app_health) = context.get_resource_or_default().await;
+        app_health.insert_custom_component(Arc::new(MainNodeHealthCheck::from(client.clone())));
+
+        context.insert_resource(MainNodeClientResource(client))?;
+        Ok(())
+    }
+}
@@ -1,11 +1,25 @@
-use zksync_core::metadata_calculator::{MetadataCalculator, MetadataCalculatorConfig};
+use std::{
+    net::{Ipv4Addr, SocketAddr},
+    path::Path,
+    sync::Arc,
+    time::Duration,
+};
+
+use zksync_config::{configs::api::MerkleTreeApiConfig, DBConfig};
+use zksync_core::{
+    metadata_calculator::{LazyAsyncTreeReader, MetadataCalculator, MetadataCalculatorConfig},
+    rocksdb_backup::{RocksdbBackupUploader, RocksdbKind},
+};
 use zksync_dal::ConnectionPool;
 use zksync_storage::RocksDB;
 
 use crate::{
-    implementations::resources::{
-        healthcheck::AppHealthCheckResource, object_store::ObjectStoreResource,
-        pools::MasterPoolResource,
+    implementations::{
+        layers::rocksdb_backup::RocksdbBackupUploaderTask,
+        resources::{
+            healthcheck::AppHealthCheckResource, object_store::ObjectStoreResource,
+            pools::MasterPoolResource, web3_api::TreeApiClientResource,
+        },
     },
     service::{ServiceContext, StopReceiver},
     task::Task,
@@ -17,11 +31,52 @@ use crate::{
 /// ## Effects
 ///
 /// - Resolves `MasterPoolResource`.
-/// - Resolves `ObjectStoreResource` (optional).
+/// - Resolves `ObjectStoreResource` (optional; required if RocksDB backups or restoring from a backup are enabled).
 /// - Adds `tree_health_check` to the `ResourceCollection<HealthCheckResource>`.
+/// - Adds `TreeApiClientResource` backed by the local tree (if enabled).
 /// - Adds `metadata_calculator` to the node.
+/// - Adds `tree_api` to the node (if enabled).
+/// - Adds `merkle_tree_backup_uploader` to the node (if RocksDB backups are enabled).
 #[derive(Debug)]
-pub struct MetadataCalculatorLayer(pub MetadataCalculatorConfig);
+pub struct MetadataCalculatorLayer {
+    config: MetadataCalculatorConfig,
+    provide_tree_api_client: bool,
+    tree_api_config: Option<MerkleTreeApiConfig>,
+    rocksdb_backup_interval: Option<Duration>,
+    restore_rocksdb_from_backup: bool,
+}
+
+impl MetadataCalculatorLayer {
+    pub fn new(config: MetadataCalculatorConfig) -> Self {
+        Self {
+            config,
+            provide_tree_api_client: false,
+            tree_api_config: None,
+            rocksdb_backup_interval: None,
+            restore_rocksdb_from_backup: false,
+        }
+    }
+
+    /// Makes the layer provide a tree API client that reads from the local Merkle tree, e.g. for
+    /// the API servers co-located with the tree.
+    pub fn with_tree_api_client(mut self) -> Self {
+        self.provide_tree_api_client = true;
+        self
+    }
+
+    /// Makes the layer run the Merkle tree API server for the local tree.
+    pub fn with_tree_api_config(mut self, tree_api_config: MerkleTreeApiConfig) -> Self {
+        self.tree_api_config = Some(tree_api_config);
+        self
+    }
+
+    /// Configures RocksDB backups of the tree and restoring the tree from a backup according to `db_config`.
+    pub fn with_rocksdb_backups(mut self, db_config: &DBConfig) -> Self {
+        self.rocksdb_backup_interval = db_config.rocksdb_backup_interval();
+        self.restore_rocksdb_from_backup = db_config.restore_rocksdb_from_backup;
+        self
+    }
+}
 
 #[derive(Debug)]
 pub struct MetadataCalculatorTask {
@@ -46,12 +101,51 @@ impl WiringLayer for MetadataCalculatorLayer {
             );
         }
 
-        let metadata_calculator =
-            MetadataCalculator::new(self.0, object_store.map(|os| os.0)).await?;
+        let tree_db_path = self.config.db_path.clone();
+        let mut metadata_calculator =
+            MetadataCalculator::new(self.config, object_store.clone().map(|os| os.0)).await?;
+
+        if self.restore_rocksdb_from_backup || self.rocksdb_backup_interval.is_some() {
+            let Some(ObjectStoreResource(backup_store)) = object_store else {
+                return Err(WiringError::Configuration(
+                    "RocksDB backups require an object store".to_owned(),
+                ));
+            };
+            if self.restore_rocksdb_from_backup {
+                metadata_calculator =
+                    metadata_calculator.with_restore_from_backup(backup_store.clone());
+            }
+            if let Some(interval) = self.rocksdb_backup_interval {
+                let (uploader, handle) = RocksdbBackupUploader::new(
+                    RocksdbKind::MerkleTree,
+                    Path::new(&tree_db_path),
+                    interval,
+                    backup_store,
+                );
+                metadata_calculator = metadata_calculator.with_backups(handle);
+                context.add_task(Box::new(RocksdbBackupUploaderTask::new(
+                    RocksdbKind::MerkleTree,
+                    uploader,
+                )));
+            }
+        }
 
         let AppHealthCheckResource(app_health) = context.get_resource_or_default().await;
         app_health.insert_component(metadata_calculator.tree_health_check());
 
+        if self.provide_tree_api_client {
+            let tree_reader = Arc::new(metadata_calculator.tree_reader());
+            context.insert_resource(TreeApiClientResource(tree_reader))?;
+        }
+
+        if let Some(tree_api_config) = self.tree_api_config {
+            let bind_addr = (Ipv4Addr::UNSPECIFIED, tree_api_config.port).into();
+            context.add_task(Box::new(TreeApiTask {
+                bind_addr,
+                tree_reader: metadata_calculator.tree_reader(),
+            }));
+        }
+
         let task = Box::new(MetadataCalculatorTask {
             metadata_calculator,
             main_pool,
@@ -81,3 +175,22 @@ impl Task for MetadataCalculatorTask {
         result
     }
 }
+
+#[derive(Debug)]
+pub struct TreeApiTask {
+    bind_addr: SocketAddr,
+    tree_reader: LazyAsyncTreeReader,
+}
+
+#[async_trait::async_trait]
+impl Task for TreeApiTask {
+    fn name(&self) -> &'static str {
+        "tree_api"
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.tree_reader
+            .run_api_server(self.bind_addr, stop_receiver.0)
+            .await
+    }
+}
@@ -1,14 +1,27 @@
+pub mod basic_witness_input_producer;
+pub mod batch_status_updater;
 pub mod commitment_generator;
+pub mod consensus;
+pub mod consistency_checker;
+pub mod contract_verification_api;
+pub mod eth_sender;
 pub mod eth_watch;
 pub mod fee_input;
 pub mod healtcheck_server;
 pub mod house_keeper;
+pub mod main_node_client;
 pub mod metadata_calculator;
 pub mod object_store;
 pub mod pk_signing_eth_client;
 pub mod pools_layer;
+pub mod postgres_metrics;
+pub mod priority_ops_monitor;
 pub mod prometheus_exporter;
 pub mod proof_data_handler;
 pub mod query_eth_client;
+pub mod reorg_detector;
+pub mod rocksdb_backup;
+pub mod sigint;
 pub mod state_keeper;
+pub mod tx_admission;
 pub mod web3_api;
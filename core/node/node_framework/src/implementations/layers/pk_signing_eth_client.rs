@@ -4,11 +4,19 @@ use zksync_config::{ContractsConfig, ETHClientConfig, ETHSenderConfig};
 use zksync_eth_client::clients::PKSigningClient;
 
 use crate::{
-    implementations::resources::eth_interface::BoundEthInterfaceResource,
+    implementations::resources::eth_interface::{
+        BoundEthInterfaceForBlobsResource, BoundEthInterfaceResource,
+    },
     service::ServiceContext,
     wiring_layer::{WiringError, WiringLayer},
 };
 
+/// Builder for the clients used to sign and send transactions to L1.
+///
+/// ## Effects
+///
+/// - Adds `BoundEthInterfaceResource` to the resources.
+/// - Adds `BoundEthInterfaceForBlobsResource` to the resources if the blob operator is configured.
 #[derive(Debug)]
 pub struct PKSigningEthClientLayer {
     eth_sender_config: ETHSenderConfig,
@@ -49,6 +57,15 @@ impl WiringLayer for PKSigningEthClientLayer {
             &self.eth_client_config,
         );
         context.insert_resource(BoundEthInterfaceResource(Arc::new(signing_client)))?;
+
+        let signing_client_for_blobs = PKSigningClient::from_config_blobs(
+            &self.eth_sender_config,
+            &self.contracts_config,
+            &self.eth_client_config,
+        );
+        if let Some(client) = signing_client_for_blobs {
+            context.insert_resource(BoundEthInterfaceForBlobsResource(Arc::new(client)))?;
+        }
         Ok(())
     }
 }
@@ -1,3 +1,4 @@
+use prover_dal::ProverConnectionPool;
 use zksync_config::configs::PostgresConfig;
 use zksync_dal::ConnectionPool;
 
@@ -88,8 +89,10 @@ impl WiringLayer for PoolsLayer {
         }
 
         if self.with_prover {
-            let mut prover_pool =
-                ConnectionPool::builder(self.config.prover_url()?, self.config.max_connections()?);
+            let mut prover_pool = ProverConnectionPool::builder(
+                self.config.prover_url()?,
+                self.config.max_connections()?,
+            );
             prover_pool.set_statement_timeout(self.config.statement_timeout());
             context.insert_resource(ProverPoolResource::new(prover_pool))?;
         }
@@ -0,0 +1,62 @@
+use std::time::Duration;
+
+use zksync_dal::ConnectionPool;
+
+use crate::{
+    implementations::resources::pools::ReplicaPoolResource,
+    service::{ServiceContext, StopReceiver},
+    task::Task,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+const SCRAPE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Builder for the task periodically scraping Postgres metrics (e.g., table sizes).
+///
+/// ## Effects
+///
+/// - Resolves `ReplicaPoolResource`.
+/// - Adds `postgres_metrics_scraping` to the node.
+#[derive(Debug)]
+pub struct PostgresMetricsLayer;
+
+#[async_trait::async_trait]
+impl WiringLayer for PostgresMetricsLayer {
+    fn layer_name(&self) -> &'static str {
+        "postgres_metrics_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let pool = context
+            .get_resource::<ReplicaPoolResource>()
+            .await?
+            .get_singleton()
+            .await?;
+        context.add_task(Box::new(PostgresMetricsScrapingTask { pool }));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct PostgresMetricsScrapingTask {
+    pool: ConnectionPool,
+}
+
+#[async_trait::async_trait]
+impl Task for PostgresMetricsScrapingTask {
+    fn name(&self) -> &'static str {
+        "postgres_metrics_scraping"
+    }
+
+    async fn run(self: Box<Self>, mut stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        tokio::select! {
+            () = self.pool.run_postgres_metrics_scraping(SCRAPE_INTERVAL) => {
+                tracing::warn!("Postgres metrics scraping unexpectedly stopped");
+            }
+            _ = stop_receiver.0.changed() => {
+                tracing::info!("Stop signal received, Postgres metrics scraping is shutting down");
+            }
+        }
+        Ok(())
+    }
+}
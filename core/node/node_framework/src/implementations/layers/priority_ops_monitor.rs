@@ -0,0 +1,69 @@
+use futures::channel::oneshot;
+use zksync_circuit_breaker::{priority_ops::PendingPriorityOpsMonitor, CircuitBreakerChecker};
+use zksync_config::configs::chain::CircuitBreakerConfig;
+
+use crate::{
+    implementations::resources::pools::ReplicaPoolResource,
+    service::{ServiceContext, StopReceiver},
+    task::Task,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Builder for the monitor of pending priority operations. The monitor is run by a circuit breaker checker;
+/// unlike other circuit breakers, it only alerts on overdue priority operations and never stops the node.
+///
+/// ## Effects
+///
+/// - Resolves `ReplicaPoolResource`.
+/// - Adds `pending_priority_ops_monitor` to the node.
+#[derive(Debug)]
+pub struct PendingPriorityOpsMonitorLayer {
+    config: CircuitBreakerConfig,
+}
+
+impl PendingPriorityOpsMonitorLayer {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for PendingPriorityOpsMonitorLayer {
+    fn layer_name(&self) -> &'static str {
+        "pending_priority_ops_monitor_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let pool = context
+            .get_resource::<ReplicaPoolResource>()
+            .await?
+            .get_singleton()
+            .await?;
+        let monitor = PendingPriorityOpsMonitor::new(pool, &self.config);
+        let checker = CircuitBreakerChecker::new(vec![Box::new(monitor)], &self.config);
+        context.add_task(Box::new(PendingPriorityOpsMonitorTask { checker }));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct PendingPriorityOpsMonitorTask {
+    checker: CircuitBreakerChecker,
+}
+
+#[async_trait::async_trait]
+impl Task for PendingPriorityOpsMonitorTask {
+    fn name(&self) -> &'static str {
+        "pending_priority_ops_monitor"
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        let (cb_sender, mut cb_receiver) = oneshot::channel();
+        self.checker.run(cb_sender, stop_receiver.0).await?;
+        // The checker only returns after the stop signal or after sending a circuit breaker error.
+        if let Ok(Some(err)) = cb_receiver.try_recv() {
+            anyhow::bail!("Circuit breaker triggered: {err}");
+        }
+        Ok(())
+    }
+}
@@ -1,10 +1,12 @@
+use std::sync::Arc;
+
 use prometheus_exporter::PrometheusExporterConfig;
 use zksync_health_check::{HealthStatus, HealthUpdater, ReactiveHealthCheck};
 
 use crate::{
     implementations::resources::healthcheck::AppHealthCheckResource,
     service::{ServiceContext, StopReceiver},
-    task::Task,
+    task::{Backoff, RestartPolicy, Task},
     wiring_layer::{WiringError, WiringLayer},
 };
 
@@ -13,14 +15,15 @@ use crate::{
 /// ## Effects
 ///
 /// - Adds prometheus health check to the `ResourceCollection<HealthCheckResource>`.
-/// - Adds `prometheus_exporter` to the node.
+/// - Adds `prometheus_exporter` to the node. The task is restarted if it fails, since metrics are not critical
+///   for the node operation.
 #[derive(Debug)]
 pub struct PrometheusExporterLayer(pub PrometheusExporterConfig);
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct PrometheusExporterTask {
     config: PrometheusExporterConfig,
-    prometheus_health_updater: HealthUpdater,
+    prometheus_health_updater: Arc<HealthUpdater>,
 }
 
 #[async_trait::async_trait]
@@ -36,12 +39,12 @@ impl WiringLayer for PrometheusExporterLayer {
         let AppHealthCheckResource(app_health) = node.get_resource_or_default().await;
         app_health.insert_component(prometheus_health_check);
 
-        let task = Box::new(PrometheusExporterTask {
+        let task = PrometheusExporterTask {
             config: self.0,
-            prometheus_health_updater,
-        });
+            prometheus_health_updater: Arc::new(prometheus_health_updater),
+        };
 
-        node.add_task(task);
+        node.add_restartable_task(task, RestartPolicy::OnFailure(Backoff::default()));
         Ok(())
     }
 }
@@ -57,7 +60,13 @@ impl Task for PrometheusExporterTask {
         self.prometheus_health_updater
             .update(HealthStatus::Ready.into());
         let res = prometheus_task.await;
-        drop(self.prometheus_health_updater);
+        // The updater is shared among restarts of the task, so we cannot rely on it being dropped.
+        let health = if res.is_ok() {
+            HealthStatus::ShutDown
+        } else {
+            HealthStatus::NotReady
+        };
+        self.prometheus_health_updater.update(health.into());
         res
     }
 }
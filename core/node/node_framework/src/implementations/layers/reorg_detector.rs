@@ -0,0 +1,65 @@
+use anyhow::Context as _;
+use zksync_core::reorg_detector::ReorgDetector;
+
+use crate::{
+    implementations::resources::{
+        healthcheck::AppHealthCheckResource, main_node_client::MainNodeClientResource,
+        pools::MasterPoolResource,
+    },
+    service::{ServiceContext, StopReceiver},
+    task::Task,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Builder for the reorg detector of the external node.
+///
+/// ## Effects
+///
+/// - Resolves `MasterPoolResource`.
+/// - Resolves `MainNodeClientResource`.
+/// - Adds `reorg_detector` health check to the `AppHealthCheckResource`.
+/// - Adds `reorg_detector` to the node.
+#[derive(Debug)]
+pub struct ReorgDetectorLayer;
+
+#[async_trait::async_trait]
+impl WiringLayer for ReorgDetectorLayer {
+    fn layer_name(&self) -> &'static str {
+        "reorg_detector_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let pool = context
+            .get_resource::<MasterPoolResource>()
+            .await?
+            .get_singleton()
+            .await?;
+        let MainNodeClientResource(main_node_client) = context.get_resource().await?;
+
+        let reorg_detector = ReorgDetector::new(main_node_client, pool);
+        let AppHealthCheckResource(app_health) = context.get_resource_or_default().await;
+        app_health.insert_component(reorg_detector.health_check().clone());
+
+        context.add_task(Box::new(ReorgDetectorTask { reorg_detector }));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct ReorgDetectorTask {
+    reorg_detector: ReorgDetector,
+}
+
+#[async_trait::async_trait]
+impl Task for ReorgDetectorTask {
+    fn name(&self) -> &'static str {
+        "reorg_detector"
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.reorg_detector
+            .run(stop_receiver.0)
+            .await
+            .context("reorg_detector.run()")
+    }
+}
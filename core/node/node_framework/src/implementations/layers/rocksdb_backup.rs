@@ -0,0 +1,33 @@
+use zksync_core::rocksdb_backup::{RocksdbBackupUploader, RocksdbKind};
+
+use crate::{service::StopReceiver, task::Task};
+
+/// Task uploading RocksDB checkpoints to the object store. Added by the layers owning a RocksDB instance
+/// (see [`MetadataCalculatorLayer`](super::metadata_calculator::MetadataCalculatorLayer) and
+/// [`MainBatchExecutorLayer`](super::state_keeper::main_batch_executor::MainBatchExecutorLayer))
+/// if backups are enabled.
+#[derive(Debug)]
+pub struct RocksdbBackupUploaderTask {
+    kind: RocksdbKind,
+    uploader: RocksdbBackupUploader,
+}
+
+impl RocksdbBackupUploaderTask {
+    pub(crate) fn new(kind: RocksdbKind, uploader: RocksdbBackupUploader) -> Self {
+        Self { kind, uploader }
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for RocksdbBackupUploaderTask {
+    fn name(&self) -> &'static str {
+        match self.kind {
+            RocksdbKind::StateKeeperCache => "state_keeper_cache_backup_uploader",
+            RocksdbKind::MerkleTree => "merkle_tree_backup_uploader",
+        }
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.uploader.run(stop_receiver.0).await
+    }
+}
@@ -0,0 +1,53 @@
+use tokio::sync::oneshot;
+use zksync_core::setup_sigint_handler;
+
+use crate::{
+    service::{ServiceContext, StopReceiver},
+    task::Task,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Builder for the Ctrl+C (SIGINT) handler. Once the signal is received, the handler task exits,
+/// which makes the service stop all the other tasks.
+///
+/// ## Effects
+///
+/// - Adds `sigint_handler` to the node.
+#[derive(Debug)]
+pub struct SigintHandlerLayer;
+
+#[async_trait::async_trait]
+impl WiringLayer for SigintHandlerLayer {
+    fn layer_name(&self) -> &'static str {
+        "sigint_handler_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        context.add_task(Box::new(SigintHandlerTask {
+            sigint_receiver: setup_sigint_handler(),
+        }));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct SigintHandlerTask {
+    sigint_receiver: oneshot::Receiver<()>,
+}
+
+#[async_trait::async_trait]
+impl Task for SigintHandlerTask {
+    fn name(&self) -> &'static str {
+        "sigint_handler"
+    }
+
+    async fn run(self: Box<Self>, mut stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        tokio::select! {
+            _ = self.sigint_receiver => {
+                tracing::info!("Stop signal received, shutting down");
+            }
+            _ = stop_receiver.0.changed() => {}
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use zksync_core::{
+    state_keeper::{seal_criteria::NoopSealer, MiniblockSealer},
+    sync_layer::{ActionQueue, ExternalIO, SyncState},
+};
+use zksync_types::{Address, L2ChainId};
+
+use super::mempool_io::MiniblockSealerTask;
+use crate::{
+    implementations::resources::{
+        action_queue::ActionQueueSenderResource,
+        healthcheck::AppHealthCheckResource,
+        main_node_client::MainNodeClientResource,
+        pools::MasterPoolResource,
+        state_keeper::{ConditionalSealerResource, StateKeeperIOResource},
+        sync_state::SyncStateResource,
+    },
+    resource::Unique,
+    service::ServiceContext,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Builder for the state keeper I/O of the external node. The I/O executes L2 blocks fetched
+/// from the main node instead of producing them.
+///
+/// ## Effects
+///
+/// - Resolves `MasterPoolResource`.
+/// - Resolves `MainNodeClientResource`.
+/// - Adds `SyncStateResource` and the sync state health check to the `AppHealthCheckResource`.
+/// - Adds `ActionQueueSenderResource`; it is expected to be taken by the task fetching L2 blocks.
+/// - Adds `StateKeeperIOResource` and `ConditionalSealerResource`.
+/// - Adds `state_keeper/miniblock_sealer` to the node.
+#[derive(Debug)]
+pub struct ExternalIOLayer {
+    chain_id: L2ChainId,
+    l2_erc20_bridge_addr: Address,
+    miniblock_seal_queue_capacity: usize,
+}
+
+impl ExternalIOLayer {
+    pub fn new(
+        chain_id: L2ChainId,
+        l2_erc20_bridge_addr: Address,
+        miniblock_seal_queue_capacity: usize,
+    ) -> Self {
+        Self {
+            chain_id,
+            l2_erc20_bridge_addr,
+            miniblock_seal_queue_capacity,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for ExternalIOLayer {
+    fn layer_name(&self) -> &'static str {
+        "external_io_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        // Fetch required resources.
+        let master_pool = context.get_resource::<MasterPoolResource>().await?;
+        let MainNodeClientResource(main_node_client) = context.get_resource().await?;
+
+        // Create sync state.
+        let sync_state = SyncState::default();
+        let AppHealthCheckResource(app_health) = context.get_resource_or_default().await;
+        app_health.insert_custom_component(Arc::new(sync_state.clone()));
+        context.insert_resource(SyncStateResource(sync_state.clone()))?;
+
+        // Create action queue.
+        let (action_queue_sender, action_queue) = ActionQueue::new();
+        context.insert_resource(ActionQueueSenderResource(Unique::new(action_queue_sender)))?;
+
+        // Create miniblock sealer task.
+        let (miniblock_sealer, miniblock_sealer_handle) = MiniblockSealer::new(
+            master_pool
+                .get_singleton()
+                .await
+                .context("Get master pool")?,
+            self.miniblock_seal_queue_capacity,
+        );
+        context.add_task(Box::new(MiniblockSealerTask(miniblock_sealer)));
+
+        // Create external IO resource.
+        // The validation computational gas limit is used on the main node to *reject* transactions. The external
+        // node only mirrors what the main node has already executed, so we can safely set it to the maximum value.
+        let io_pool = master_pool
+            .get_singleton()
+            .await
+            .context("Get master pool")?;
+        let io = ExternalIO::new(
+            miniblock_sealer_handle,
+            io_pool,
+            action_queue,
+            sync_state,
+            Box::new(main_node_client),
+            self.l2_erc20_bridge_addr,
+            u32::MAX,
+            self.chain_id,
+        )
+        .await
+        .context("Failed initializing I/O for external node state keeper")?;
+        context.insert_resource(StateKeeperIOResource(Unique::new(Box::new(io))))?;
+
+        // Create sealer. The external node must seal batches exactly as the main node did, so it never seals
+        // them by itself.
+        context.insert_resource(ConditionalSealerResource(Arc::new(NoopSealer)))?;
+
+        Ok(())
+    }
+}
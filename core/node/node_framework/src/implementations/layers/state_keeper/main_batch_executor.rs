@@ -1,24 +1,79 @@
+use std::{path::Path, time::Duration};
+
+use anyhow::Context as _;
 use zksync_config::{configs::chain::StateKeeperConfig, DBConfig};
-use zksync_core::state_keeper::MainBatchExecutor;
+use zksync_core::{
+    rocksdb_backup::{RocksdbBackupUploader, RocksdbKind},
+    state_keeper::MainBatchExecutor,
+};
+use zksync_types::{VmVersion, U256};
 
 use crate::{
-    implementations::resources::{pools::MasterPoolResource, state_keeper::BatchExecutorResource},
+    implementations::{
+        layers::rocksdb_backup::RocksdbBackupUploaderTask,
+        resources::{
+            object_store::ObjectStoreResource, pools::MasterPoolResource,
+            state_keeper::BatchExecutorResource,
+        },
+    },
     resource::Unique,
     service::ServiceContext,
     wiring_layer::{WiringError, WiringLayer},
 };
 
+/// ## Effects
+///
+/// - Resolves `MasterPoolResource`.
+/// - Resolves `ObjectStoreResource` (only if RocksDB backups or restoring from a backup are enabled).
+/// - Adds `BatchExecutorResource`.
+/// - Adds `state_keeper_cache_backup_uploader` to the node (if RocksDB backups are enabled).
 #[derive(Debug)]
 pub struct MainBatchExecutorLayer {
-    db_config: DBConfig,
-    state_keeper_config: StateKeeperConfig,
+    state_keeper_db_path: String,
+    max_allowed_l2_tx_gas_limit: U256,
+    save_call_traces: bool,
+    upload_witness_inputs_to_gcs: bool,
+    enum_index_migration_chunk_size: usize,
+    optional_bytecode_compression: bool,
+    shadow_vm_version: Option<VmVersion>,
+    rocksdb_backup_interval: Option<Duration>,
+    restore_rocksdb_from_backup: bool,
 }
 
 impl MainBatchExecutorLayer {
     pub fn new(db_config: DBConfig, state_keeper_config: StateKeeperConfig) -> Self {
         Self {
-            db_config,
-            state_keeper_config,
+            rocksdb_backup_interval: db_config.rocksdb_backup_interval(),
+            restore_rocksdb_from_backup: db_config.restore_rocksdb_from_backup,
+            state_keeper_db_path: db_config.state_keeper_db_path,
+            max_allowed_l2_tx_gas_limit: state_keeper_config.max_allowed_l2_tx_gas_limit.into(),
+            save_call_traces: state_keeper_config.save_call_traces,
+            upload_witness_inputs_to_gcs: state_keeper_config.upload_witness_inputs_to_gcs,
+            enum_index_migration_chunk_size: state_keeper_config.enum_index_migration_chunk_size(),
+            optional_bytecode_compression: false,
+            shadow_vm_version: state_keeper_config.shadow_vm_version.map(Into::into),
+        }
+    }
+
+    /// Creates a batch executor layer for the external node.
+    ///
+    /// Transaction limits are set to the maximum possible values: the external node only re-executes transactions
+    /// that were already executed by the main node, so it must not reject any of them.
+    pub fn for_external_node(
+        state_keeper_db_path: String,
+        save_call_traces: bool,
+        enum_index_migration_chunk_size: usize,
+    ) -> Self {
+        Self {
+            state_keeper_db_path,
+            max_allowed_l2_tx_gas_limit: u32::MAX.into(),
+            save_call_traces,
+            upload_witness_inputs_to_gcs: false,
+            enum_index_migration_chunk_size,
+            optional_bytecode_compression: true,
+            shadow_vm_version: None,
+            rocksdb_backup_interval: None,
+            restore_rocksdb_from_backup: false,
         }
     }
 }
@@ -32,15 +87,41 @@ impl WiringLayer for MainBatchExecutorLayer {
     async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
         let master_pool = context.get_resource::<MasterPoolResource>().await?;
 
-        let builder = MainBatchExecutor::new(
-            self.db_config.state_keeper_db_path,
+        let mut builder = MainBatchExecutor::new(
+            self.state_keeper_db_path.clone(),
             master_pool.get_singleton().await?,
-            self.state_keeper_config.max_allowed_l2_tx_gas_limit.into(),
-            self.state_keeper_config.save_call_traces,
-            self.state_keeper_config.upload_witness_inputs_to_gcs,
-            self.state_keeper_config.enum_index_migration_chunk_size(),
-            false,
+            self.max_allowed_l2_tx_gas_limit,
+            self.save_call_traces,
+            self.upload_witness_inputs_to_gcs,
+            self.enum_index_migration_chunk_size,
+            self.optional_bytecode_compression,
         );
+        if let Some(shadow_vm_version) = self.shadow_vm_version {
+            builder.set_shadow_vm_version(shadow_vm_version);
+        }
+
+        if self.restore_rocksdb_from_backup || self.rocksdb_backup_interval.is_some() {
+            let object_store = context.get_resource::<ObjectStoreResource>().await?.0;
+            if self.restore_rocksdb_from_backup {
+                builder
+                    .restore_from_backup(object_store.as_ref())
+                    .await
+                    .context("failed restoring state keeper cache from backup")?;
+            }
+            if let Some(interval) = self.rocksdb_backup_interval {
+                let (uploader, handle) = RocksdbBackupUploader::new(
+                    RocksdbKind::StateKeeperCache,
+                    Path::new(&self.state_keeper_db_path),
+                    interval,
+                    object_store,
+                );
+                builder.set_backup_handle(handle);
+                context.add_task(Box::new(RocksdbBackupUploaderTask::new(
+                    RocksdbKind::StateKeeperCache,
+                    uploader,
+                )));
+            }
+        }
 
         context.insert_resource(BatchExecutorResource(Unique::new(Box::new(builder))))?;
         Ok(())
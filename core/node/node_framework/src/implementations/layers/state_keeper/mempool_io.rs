@@ -128,7 +128,7 @@ impl WiringLayer for MempoolIOLayer {
 }
 
 #[derive(Debug)]
-struct MiniblockSealerTask(MiniblockSealer);
+pub(super) struct MiniblockSealerTask(pub(super) MiniblockSealer);
 
 #[async_trait::async_trait]
 impl Task for MiniblockSealerTask {
@@ -1,17 +1,24 @@
 use std::sync::Arc;
 
 use anyhow::Context;
-use zksync_core::state_keeper::{
-    seal_criteria::ConditionalSealer, BatchExecutor, StateKeeperIO, ZkSyncStateKeeper,
+use zksync_core::{
+    state_keeper::{
+        seal_criteria::ConditionalSealer, BatchExecutor, StateKeeperIO, ZkSyncStateKeeper,
+    },
+    tx_admission::TxAdmissionPolicy,
 };
+use zksync_dal::ConnectionPool;
 use zksync_storage::RocksDB;
 
+pub mod external_io;
 pub mod main_batch_executor;
 pub mod mempool_io;
 
 use crate::{
-    implementations::resources::state_keeper::{
-        BatchExecutorResource, ConditionalSealerResource, StateKeeperIOResource,
+    implementations::resources::{
+        pools::MasterPoolResource,
+        state_keeper::{BatchExecutorResource, ConditionalSealerResource, StateKeeperIOResource},
+        tx_admission::TxAdmissionPolicyResource,
     },
     service::{ServiceContext, StopReceiver},
     task::Task,
@@ -22,6 +29,8 @@ use crate::{
 /// - `StateKeeperIOResource`
 /// - `BatchExecutorResource`
 /// - `ConditionalSealerResource`
+/// - `MasterPoolResource`
+/// - `TxAdmissionPolicyResource` (optional)
 ///
 #[derive(Debug)]
 pub struct StateKeeperLayer;
@@ -46,11 +55,20 @@ impl WiringLayer for StateKeeperLayer {
             .take()
             .context("L1BatchExecutorBuilder was provided but taken by some other task")?;
         let sealer = context.get_resource::<ConditionalSealerResource>().await?.0;
+        let master_pool = context.get_resource::<MasterPoolResource>().await?;
+        let migrations_pool = master_pool.get_custom(2).await.context("Get master pool")?;
+        let admission_policy = match context.get_resource::<TxAdmissionPolicyResource>().await {
+            Ok(policy) => Some(policy.0),
+            Err(WiringError::ResourceLacking(_)) => None,
+            Err(other) => return Err(other),
+        };
 
         context.add_task(Box::new(StateKeeperTask {
             io,
             batch_executor_base,
             sealer,
+            migrations_pool,
+            admission_policy,
         }));
         Ok(())
     }
@@ -61,6 +79,8 @@ struct StateKeeperTask {
     io: Box<dyn StateKeeperIO>,
     batch_executor_base: Box<dyn BatchExecutor>,
     sealer: Arc<dyn ConditionalSealer>,
+    migrations_pool: ConnectionPool,
+    admission_policy: Option<Arc<dyn TxAdmissionPolicy>>,
 }
 
 #[async_trait::async_trait]
@@ -70,13 +90,25 @@ impl Task for StateKeeperTask {
     }
 
     async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
-        let state_keeper = ZkSyncStateKeeper::new(
+        let mut state_keeper = ZkSyncStateKeeper::new(
             stop_receiver.0,
             self.io,
             self.batch_executor_base,
             self.sealer,
         );
-        let result = state_keeper.run().await;
+        if let Some(admission_policy) = self.admission_policy {
+            state_keeper = state_keeper.with_admission_policy(admission_policy);
+        }
+        // Data migrations are run alongside the state keeper; they never complete successfully,
+        // so that they don't stop the node.
+        let migrations = futures::future::try_join(
+            state_keeper.run_fee_address_migration(self.migrations_pool.clone()),
+            state_keeper.run_logs_bloom_backfill(self.migrations_pool),
+        );
+        let result = tokio::select! {
+            result = state_keeper.run() => result,
+            Err(err) = migrations => Err(err),
+        };
 
         // Wait for all the instances of RocksDB to be destroyed.
         tokio::task::spawn_blocking(RocksDB::await_rocksdb_termination)
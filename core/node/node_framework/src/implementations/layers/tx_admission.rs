@@ -0,0 +1,83 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Context;
+use zksync_config::configs::chain::StateKeeperConfig;
+use zksync_core::tx_admission::{
+    DbTxAdmissionPolicy, DbTxAdmissionRulesUpdater, TxAdmissionPolicy, TxAdmissionRules,
+};
+
+use crate::{
+    implementations::resources::{
+        pools::MasterPoolResource, tx_admission::TxAdmissionPolicyResource,
+    },
+    service::{ServiceContext, StopReceiver},
+    task::Task,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Builder for the transaction admission policy.
+///
+/// The policy is picked up by `TxSenderLayer` and `StateKeeperLayer` if it is present. Thus, this layer
+/// should only be added to the main node; the external node state keeper replays transactions
+/// already accepted by the main node and must not apply the policy.
+///
+/// ## Effects
+///
+/// - Resolves `MasterPoolResource` (only if rules are reloaded from Postgres).
+/// - Adds `TxAdmissionPolicyResource`.
+/// - Adds `tx_admission_rules_updater` to the node (only if rules are reloaded from Postgres).
+#[derive(Debug)]
+pub struct TxAdmissionPolicyLayer {
+    config_rules: TxAdmissionRules,
+    reload_interval: Option<Duration>,
+}
+
+impl TxAdmissionPolicyLayer {
+    pub fn new(state_keeper_config: &StateKeeperConfig) -> Self {
+        Self {
+            config_rules: TxAdmissionRules::from_config(state_keeper_config),
+            reload_interval: state_keeper_config.tx_admission_reload_interval(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for TxAdmissionPolicyLayer {
+    fn layer_name(&self) -> &'static str {
+        "tx_admission_policy_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let policy: Arc<dyn TxAdmissionPolicy> = if let Some(reload_interval) = self.reload_interval
+        {
+            let pool_resource = context.get_resource::<MasterPoolResource>().await?;
+            let pool = pool_resource.get_singleton().await?;
+            let (policy, updater) =
+                DbTxAdmissionPolicy::new(self.config_rules, pool, reload_interval)
+                    .await
+                    .context("DbTxAdmissionPolicy::new()")?;
+            context.add_task(Box::new(TxAdmissionRulesUpdaterTask { updater }));
+            Arc::new(policy)
+        } else {
+            Arc::new(self.config_rules)
+        };
+        context.insert_resource(TxAdmissionPolicyResource(policy))?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct TxAdmissionRulesUpdaterTask {
+    updater: DbTxAdmissionRulesUpdater,
+}
+
+#[async_trait::async_trait]
+impl Task for TxAdmissionRulesUpdaterTask {
+    fn name(&self) -> &'static str {
+        "tx_admission_rules_updater"
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.updater.run(stop_receiver.0).await
+    }
+}
@@ -1,4 +1,4 @@
-use std::num::NonZeroU32;
+use std::{num::NonZeroU32, time::Duration};
 
 use tokio::{sync::oneshot, task::JoinHandle};
 use zksync_core::api_server::web3::{state::InternalApiConfig, ApiBuilder, ApiServer, Namespace};
@@ -24,6 +24,7 @@ pub struct Web3ServerOptionalConfig {
     pub batch_request_size_limit: Option<usize>,
     pub response_body_size_limit: Option<usize>,
     pub websocket_requests_per_minute_limit: Option<NonZeroU32>,
+    pub polling_interval: Option<Duration>,
 }
 
 impl Web3ServerOptionalConfig {
@@ -48,6 +49,9 @@ impl Web3ServerOptionalConfig {
             api_builder = api_builder
                 .with_websocket_requests_per_minute_limit(websocket_requests_per_minute_limit);
         }
+        if let Some(polling_interval) = self.polling_interval {
+            api_builder = api_builder.with_polling_interval(polling_interval);
+        }
         api_builder
     }
 }
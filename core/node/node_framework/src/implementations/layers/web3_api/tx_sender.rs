@@ -1,8 +1,11 @@
 use std::{fmt, sync::Arc};
 
-use zksync_core::api_server::{
-    execution_sandbox::{VmConcurrencyBarrier, VmConcurrencyLimiter},
-    tx_sender::{ApiContracts, TxSenderBuilder, TxSenderConfig},
+use zksync_core::{
+    api_server::{
+        execution_sandbox::{VmConcurrencyBarrier, VmConcurrencyLimiter},
+        tx_sender::{ApiContracts, TxSenderBuilder, TxSenderConfig},
+    },
+    tx_admission::TxAdmissionPolicy,
 };
 use zksync_state::PostgresStorageCaches;
 
@@ -11,6 +14,7 @@ use crate::{
         fee_input::FeeInputResource,
         pools::ReplicaPoolResource,
         state_keeper::ConditionalSealerResource,
+        tx_admission::TxAdmissionPolicyResource,
         web3_api::{TxSenderResource, TxSinkResource},
     },
     service::{ServiceContext, StopReceiver},
@@ -31,6 +35,7 @@ pub struct TxSenderLayer {
     postgres_storage_caches_config: PostgresStorageCachesConfig,
     max_vm_concurrency: usize,
     api_contracts: ApiContracts,
+    admission_policy: Option<Arc<dyn TxAdmissionPolicy>>,
 }
 
 impl TxSenderLayer {
@@ -45,8 +50,17 @@ impl TxSenderLayer {
             postgres_storage_caches_config,
             max_vm_concurrency,
             api_contracts,
+            admission_policy: None,
         }
     }
+
+    /// Sets the transaction admission policy, which takes precedence over `TxAdmissionPolicyResource`.
+    /// This is used by the external node, which restricts transaction submission without sharing the policy
+    /// with its state keeper.
+    pub fn with_admission_policy(mut self, admission_policy: Arc<dyn TxAdmissionPolicy>) -> Self {
+        self.admission_policy = Some(admission_policy);
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -66,6 +80,14 @@ impl WiringLayer for TxSenderLayer {
             Err(other) => return Err(other),
         };
         let fee_input = context.get_resource::<FeeInputResource>().await?.0;
+        let admission_policy = match self.admission_policy {
+            Some(policy) => Some(policy),
+            None => match context.get_resource::<TxAdmissionPolicyResource>().await {
+                Ok(policy) => Some(policy.0),
+                Err(WiringError::ResourceLacking(_)) => None,
+                Err(other) => return Err(other),
+            },
+        };
 
         // Initialize Postgres caches.
         let factory_deps_capacity = self.postgres_storage_caches_config.factory_deps_cache_size;
@@ -99,6 +121,9 @@ impl WiringLayer for TxSenderLayer {
         if let Some(sealer) = sealer {
             tx_sender = tx_sender.with_sealer(sealer);
         }
+        if let Some(admission_policy) = admission_policy {
+            tx_sender = tx_sender.with_admission_policy(admission_policy);
+        }
         let tx_sender = tx_sender
             .build(
                 fee_input,
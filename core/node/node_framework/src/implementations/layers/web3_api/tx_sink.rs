@@ -1,19 +1,33 @@
 use std::sync::Arc;
 
 use zksync_core::api_server::tx_sender::{master_pool_sink::MasterPoolSink, proxy::TxProxy};
-use zksync_web3_decl::jsonrpsee::http_client::{transport::HttpBackend, HttpClient};
+use zksync_dal::ConnectionPool;
 
 use crate::{
-    implementations::resources::{pools::MasterPoolResource, web3_api::TxSinkResource},
-    service::ServiceContext,
+    implementations::resources::{
+        main_node_client::MainNodeClientResource, pools::MasterPoolResource,
+        web3_api::TxSinkResource,
+    },
+    service::{ServiceContext, StopReceiver},
+    task::Task,
     wiring_layer::{WiringError, WiringLayer},
 };
 
+/// Builder for the transaction sink used by the API server.
+///
+/// ## Effects
+///
+/// - Resolves `MasterPoolResource`.
+/// - Resolves `MainNodeClientResource` (for `ProxySink` only).
+/// - Adds `TxSinkResource`.
+/// - Adds `account_nonce_sweeper` to the node (for `ProxySink` only).
 #[derive(Debug)]
 #[non_exhaustive]
 pub enum TxSinkLayer {
+    /// Inserts transactions into the mempool.
     MasterPoolSink,
-    ProxySink { main_node_url: String },
+    /// Proxies transactions to the main node.
+    ProxySink,
 }
 
 #[async_trait::async_trait]
@@ -32,14 +46,45 @@ impl WiringLayer for TxSinkLayer {
                     .await?;
                 TxSinkResource(Arc::new(MasterPoolSink::new(pool)))
             }
-            TxSinkLayer::ProxySink { main_node_url } => {
-                let client = HttpClient::<HttpBackend>::builder()
-                    .build(main_node_url)
-                    .map_err(|err| WiringError::Internal(err.into()))?;
-                TxSinkResource(Arc::new(TxProxy::new(client)))
+            TxSinkLayer::ProxySink => {
+                let MainNodeClientResource(client) = context.get_resource().await?;
+                let proxy = TxProxy::new(client);
+
+                let pool = context
+                    .get_resource::<MasterPoolResource>()
+                    .await?
+                    .get_singleton()
+                    .await?;
+                let task = AccountNonceSweeperTask {
+                    proxy: Arc::new(proxy),
+                    pool,
+                };
+                let tx_sink = TxSinkResource(task.proxy.clone());
+                context.add_task(Box::new(task));
+                tx_sink
             }
         };
         context.insert_resource(tx_sink)?;
         Ok(())
     }
 }
+
+/// Task removing transactions proxied to the main node from the cache once they are synced back.
+#[derive(Debug)]
+struct AccountNonceSweeperTask {
+    proxy: Arc<TxProxy>,
+    pool: ConnectionPool,
+}
+
+#[async_trait::async_trait]
+impl Task for AccountNonceSweeperTask {
+    fn name(&self) -> &'static str {
+        "account_nonce_sweeper"
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.proxy
+            .run_account_nonce_sweeper(self.pool, stop_receiver.0)
+            .await
+    }
+}
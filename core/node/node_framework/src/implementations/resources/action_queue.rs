@@ -0,0 +1,14 @@
+use zksync_core::sync_layer::ActionQueueSender;
+
+use crate::resource::{Resource, ResourceId, Unique};
+
+/// Sending side of the action queue consumed by the external node state keeper.
+/// Must be taken by a single task fetching L2 blocks from the main node.
+#[derive(Debug, Clone)]
+pub struct ActionQueueSenderResource(pub Unique<ActionQueueSender>);
+
+impl Resource for ActionQueueSenderResource {
+    fn resource_id() -> ResourceId {
+        "external_node/action_queue_sender".into()
+    }
+}
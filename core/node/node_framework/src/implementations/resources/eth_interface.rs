@@ -21,3 +21,13 @@ impl Resource for BoundEthInterfaceResource {
         "common/bound_eth_interface".into()
     }
 }
+
+/// Client used to sign and send transactions with blobs. Only provided if the blob operator is configured.
+#[derive(Debug, Clone)]
+pub struct BoundEthInterfaceForBlobsResource(pub Arc<dyn BoundEthInterface>);
+
+impl Resource for BoundEthInterfaceForBlobsResource {
+    fn resource_id() -> ResourceId {
+        "common/bound_eth_interface_for_blobs".into()
+    }
+}
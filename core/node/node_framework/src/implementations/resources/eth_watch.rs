@@ -0,0 +1,14 @@
+use zksync_core::eth_watch::EventProcessor;
+
+use crate::resource::{Resource, ResourceId, Unique};
+
+/// Custom L1 event processor to be run by the Ethereum watcher.
+/// Processors should be added to the `ResourceCollection<EventProcessorResource>`.
+#[derive(Debug, Clone)]
+pub struct EventProcessorResource(pub Unique<Box<dyn EventProcessor>>);
+
+impl Resource for EventProcessorResource {
+    fn resource_id() -> ResourceId {
+        "eth_watch/event_processor".into()
+    }
+}
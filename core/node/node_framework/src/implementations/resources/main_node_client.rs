@@ -0,0 +1,13 @@
+use zksync_web3_decl::jsonrpsee::http_client::HttpClient;
+
+use crate::resource::{Resource, ResourceId};
+
+/// JSON-RPC client for the main node. Used by the external node components.
+#[derive(Debug, Clone)]
+pub struct MainNodeClientResource(pub HttpClient);
+
+impl Resource for MainNodeClientResource {
+    fn resource_id() -> ResourceId {
+        "external_node/main_node_client".into()
+    }
+}
@@ -1,8 +1,13 @@
+pub mod action_queue;
 pub mod eth_interface;
+pub mod eth_watch;
 pub mod fee_input;
 pub mod healthcheck;
+pub mod l1_tx_params;
+pub mod main_node_client;
 pub mod object_store;
 pub mod pools;
 pub mod state_keeper;
 pub mod sync_state;
+pub mod tx_admission;
 pub mod web3_api;
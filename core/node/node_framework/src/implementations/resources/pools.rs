@@ -3,6 +3,7 @@ use std::sync::{
     Arc,
 };
 
+use prover_dal::{ProverConnectionPool, ProverConnectionPoolBuilder};
 use zksync_dal::{connection::ConnectionPoolBuilder, ConnectionPool};
 
 use crate::resource::Resource;
@@ -121,7 +122,7 @@ impl ReplicaPoolResource {
 #[derive(Debug, Clone)]
 pub struct ProverPoolResource {
     connections_count: Arc<AtomicU32>,
-    builder: ConnectionPoolBuilder,
+    builder: ProverConnectionPoolBuilder,
 }
 
 impl Resource for ProverPoolResource {
@@ -131,14 +132,14 @@ impl Resource for ProverPoolResource {
 }
 
 impl ProverPoolResource {
-    pub fn new(builder: ConnectionPoolBuilder) -> Self {
+    pub fn new(builder: ProverConnectionPoolBuilder) -> Self {
         Self {
             connections_count: Arc::new(AtomicU32::new(0)),
             builder,
         }
     }
 
-    pub async fn get(&self) -> anyhow::Result<ConnectionPool> {
+    pub async fn get(&self) -> anyhow::Result<ProverConnectionPool> {
         let result = self.builder.build().await;
 
         if result.is_ok() {
@@ -146,18 +147,18 @@ impl ProverPoolResource {
                 .fetch_add(self.builder.max_size(), Ordering::Relaxed);
             let total_connections = self.connections_count.load(Ordering::Relaxed);
             tracing::info!(
-                "Created a new prover pool. Master pool total connections count: {total_connections}"
+                "Created a new prover pool. Prover pool total connections count: {total_connections}"
             );
         }
 
         result
     }
 
-    pub async fn get_singleton(&self) -> anyhow::Result<ConnectionPool> {
+    pub async fn get_singleton(&self) -> anyhow::Result<ProverConnectionPool> {
         self.get_custom(1).await
     }
 
-    pub async fn get_custom(&self, size: u32) -> anyhow::Result<ConnectionPool> {
+    pub async fn get_custom(&self, size: u32) -> anyhow::Result<ProverConnectionPool> {
         let result = self.builder.clone().set_max_size(size).build().await;
 
         if result.is_ok() {
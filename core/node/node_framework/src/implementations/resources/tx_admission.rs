@@ -0,0 +1,15 @@
+use std::sync::Arc;
+
+use zksync_core::tx_admission::TxAdmissionPolicy;
+
+use crate::resource::{Resource, ResourceId};
+
+/// Wrapper for the transaction admission policy shared by components accepting or executing transactions.
+#[derive(Debug, Clone)]
+pub struct TxAdmissionPolicyResource(pub Arc<dyn TxAdmissionPolicy>);
+
+impl Resource for TxAdmissionPolicyResource {
+    fn resource_id() -> ResourceId {
+        "common/tx_admission_policy".into()
+    }
+}
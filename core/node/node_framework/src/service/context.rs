@@ -1,7 +1,8 @@
+use super::supervisor::SupervisedTask;
 use crate::{
     resource::{Resource, StoredResource},
     service::ZkStackService,
-    task::Task,
+    task::{RestartPolicy, Task},
     wiring_layer::WiringError,
 };
 
@@ -36,7 +37,26 @@ impl<'a> ServiceContext<'a> {
     /// Added tasks will be launched after the wiring process will be finished.
     pub fn add_task(&mut self, task: Box<dyn Task>) -> &mut Self {
         tracing::info!("Layer {} has added a new task: {}", self.layer, task.name());
-        self.service.tasks.push(task);
+        self.service.tasks.push(SupervisedTask::new(task));
+        self
+    }
+
+    /// Adds a task to the service that will be restarted once it exits according to the provided `policy`,
+    /// instead of shutting down the node.
+    /// Each restart runs a fresh clone of the provided task.
+    pub fn add_restartable_task<T: Task + Clone>(
+        &mut self,
+        task: T,
+        policy: RestartPolicy,
+    ) -> &mut Self {
+        tracing::info!(
+            "Layer {} has added a new restartable task: {} ({policy:?})",
+            self.layer,
+            task.name()
+        );
+        self.service
+            .tasks
+            .push(SupervisedTask::restartable(task, policy));
         self
     }
 
@@ -1,17 +1,19 @@
-use std::{collections::HashMap, fmt, time::Duration};
+use std::{collections::HashMap, fmt, sync::Arc, time::Duration};
 
 use futures::{future::BoxFuture, FutureExt};
 use tokio::{runtime::Runtime, sync::watch};
 
+use self::supervisor::{SupervisedTask, TasksHealthCheck};
 pub use self::{context::ServiceContext, stop_receiver::StopReceiver};
 use crate::{
-    resource::{ResourceId, StoredResource},
-    task::Task,
+    implementations::resources::healthcheck::AppHealthCheckResource,
+    resource::{Resource, ResourceId, StoredResource},
     wiring_layer::{WiringError, WiringLayer},
 };
 
 mod context;
 mod stop_receiver;
+mod supervisor;
 #[cfg(test)]
 mod tests;
 
@@ -28,7 +30,10 @@ const TASK_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
 ///   - invokes a `wire` method on each added wiring layer. If any of the layers fails,
 ///     the service will return an error. If no layers have added a task, the service will
 ///     also return an error.
-///   - waits for any of the tasks to finish.
+///   - starts every task once the health checks it depends on (if any) report a healthy status.
+///   - restarts tasks that exit according to their restart policy (if any), reporting the state of the tasks
+///     via the `tasks` health check.
+///   - waits for any of the tasks to finish without being restarted.
 ///   - sends stop signal to all the tasks.
 ///   - waits for the remaining tasks to finish.
 ///   - calls `after_node_shutdown` hook for every task that has provided it.
@@ -39,7 +44,7 @@ pub struct ZkStackService {
     /// List of wiring layers.
     layers: Vec<Box<dyn WiringLayer>>,
     /// Tasks added to the service.
-    tasks: Vec<Box<dyn Task>>,
+    tasks: Vec<SupervisedTask>,
 
     /// Sender used to stop the tasks.
     stop_sender: watch::Sender<bool>,
@@ -114,11 +119,31 @@ impl ZkStackService {
             anyhow::bail!("One or more task weren't able to start");
         }
 
+        // Expose the state of the tasks via the app health check.
+        let app_health = self
+            .resources
+            .entry(AppHealthCheckResource::resource_id())
+            .or_insert_with(|| -> Box<dyn StoredResource> {
+                Box::new(AppHealthCheckResource::default())
+            })
+            .downcast_ref::<AppHealthCheckResource>()
+            .expect("Resource `common/app_health_check` has unexpected type")
+            .0
+            .clone();
+        let tasks_health = TasksHealthCheck::default();
+        app_health.insert_custom_component(Arc::new(tasks_health.clone()));
+
         let mut tasks = Vec::new();
         for task in std::mem::take(&mut self.tasks) {
-            let name = task.name().to_string();
-            let after_node_shutdown = task.after_node_shutdown();
-            let task_future = Box::pin(task.run(self.stop_receiver()));
+            let name = task.task().name().to_string();
+            // For restartable tasks, the hook of the initially added task instance is used.
+            let after_node_shutdown = task.task().after_node_shutdown();
+            tasks_health.add_task(task.task());
+            let task_future = Box::pin(task.run(
+                app_health.clone(),
+                tasks_health.clone(),
+                self.stop_receiver(),
+            ));
             let task_repr = TaskRepr {
                 name,
                 task: Some(task_future),
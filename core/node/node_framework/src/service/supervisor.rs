@@ -0,0 +1,282 @@
+//! Supervision of the tasks run by the service: dependency ordering, restarts and health reporting.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use anyhow::Context as _;
+use serde::Serialize;
+use tokio::task::JoinError;
+use zksync_health_check::{AppHealthCheck, CheckHealth, Health, HealthStatus};
+
+use crate::{
+    service::StopReceiver,
+    task::{RestartPolicy, Task},
+};
+
+/// Interval between checks of the task dependencies' health.
+const DEPENDENCY_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+type TaskFactory = Box<dyn Fn() -> Box<dyn Task> + Send>;
+
+/// Task added to the service together with the information required to restart it.
+pub(super) struct SupervisedTask {
+    task: Box<dyn Task>,
+    policy: RestartPolicy,
+    /// Creates a fresh instance of the task for each restart. Only set for restartable tasks.
+    factory: Option<TaskFactory>,
+}
+
+impl fmt::Debug for SupervisedTask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SupervisedTask")
+            .field("name", &self.task.name())
+            .field("policy", &self.policy)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SupervisedTask {
+    pub(super) fn new(task: Box<dyn Task>) -> Self {
+        Self {
+            task,
+            policy: RestartPolicy::Never,
+            factory: None,
+        }
+    }
+
+    pub(super) fn restartable<T: Task + Clone>(task: T, policy: RestartPolicy) -> Self {
+        let factory_task = task.clone();
+        Self {
+            task: Box::new(task),
+            policy,
+            factory: Some(Box::new(move || -> Box<dyn Task> {
+                Box::new(factory_task.clone())
+            })),
+        }
+    }
+
+    pub(super) fn task(&self) -> &dyn Task {
+        self.task.as_ref()
+    }
+
+    /// Runs the task until it exits and is not restarted any more, or until the node is stopped.
+    pub(super) async fn run(
+        self,
+        app_health: Arc<AppHealthCheck>,
+        tasks_health: TasksHealthCheck,
+        mut stop_receiver: StopReceiver,
+    ) -> anyhow::Result<()> {
+        let Self {
+            mut task,
+            policy,
+            factory,
+        } = self;
+        let name = task.name();
+
+        let dependencies = task.depends_on();
+        if !dependencies.is_empty() {
+            tasks_health.update(name, TaskState::WaitingForDependencies);
+            tracing::info!("Task {name} is waiting for its dependencies: {dependencies:?}");
+            let all_ready =
+                wait_for_dependencies(&app_health, &dependencies, &mut stop_receiver).await;
+            let all_ready = all_ready.with_context(|| format!("task {name} cannot be started"));
+            match all_ready {
+                Ok(true) => tracing::info!("Dependencies of task {name} are ready"),
+                Ok(false) => {
+                    tasks_health.update(name, TaskState::Finished);
+                    return Ok(());
+                }
+                Err(err) => {
+                    tasks_health.update(name, TaskState::Failed);
+                    tasks_health.set_last_error(name, format!("{err:#}"));
+                    return Err(err);
+                }
+            }
+        }
+
+        let mut restart_count = 0;
+        loop {
+            tasks_health.update(name, TaskState::Running);
+            let started_at = Instant::now();
+            let result = tokio::spawn(task.run(stop_receiver.clone())).await;
+            if policy.is_stable_run(started_at.elapsed()) {
+                // The task was running long enough; treat its exit as unrelated to the previous ones.
+                restart_count = 0;
+            }
+            let failure = describe_failure(&result);
+            if let Some(failure) = &failure {
+                tasks_health.set_last_error(name, failure.clone());
+            }
+
+            let is_stopped = *stop_receiver.0.borrow();
+            let restart_delay = if is_stopped {
+                None
+            } else {
+                policy.backoff(failure.is_some(), restart_count)
+            };
+            let Some(restart_delay) = restart_delay else {
+                let state = if failure.is_some() {
+                    TaskState::Failed
+                } else {
+                    TaskState::Finished
+                };
+                tasks_health.update(name, state);
+                return unwrap_task_result(result);
+            };
+
+            restart_count += 1;
+            let reason = failure.as_deref().unwrap_or("exited successfully");
+            tracing::warn!(
+                "Task {name} {reason}; restarting it in {restart_delay:?} (restart #{restart_count})"
+            );
+            tasks_health.update(name, TaskState::Restarting);
+            tasks_health.increment_restarts(name);
+
+            tokio::select! {
+                () = tokio::time::sleep(restart_delay) => {}
+                _ = stop_receiver.0.changed() => {
+                    tasks_health.update(name, TaskState::Finished);
+                    return Ok(());
+                }
+            }
+            let factory = factory
+                .as_ref()
+                .expect("restart policy can only be set for restartable tasks");
+            task = factory();
+        }
+    }
+}
+
+/// Waits until all `dependencies` report a healthy status. Returns `Ok(false)` if the node was stopped
+/// while waiting.
+async fn wait_for_dependencies(
+    app_health: &AppHealthCheck,
+    dependencies: &[&'static str],
+    stop_receiver: &mut StopReceiver,
+) -> anyhow::Result<bool> {
+    for &dependency in dependencies {
+        loop {
+            if *stop_receiver.0.borrow() {
+                return Ok(false);
+            }
+            let health = app_health
+                .check_component_health(dependency)
+                .await
+                .with_context(|| format!("health check `{dependency}` is not registered"))?;
+            if health.status().is_healthy() {
+                break;
+            }
+            tokio::time::timeout(DEPENDENCY_POLL_INTERVAL, stop_receiver.0.changed())
+                .await
+                .ok();
+        }
+    }
+    Ok(true)
+}
+
+fn describe_failure(result: &Result<anyhow::Result<()>, JoinError>) -> Option<String> {
+    match result {
+        Ok(Ok(())) => None,
+        Ok(Err(err)) => Some(format!("exited with an error: {err:#}")),
+        Err(err) if err.is_panic() => Some("panicked".to_owned()),
+        Err(_) => Some("was cancelled".to_owned()),
+    }
+}
+
+fn unwrap_task_result(result: Result<anyhow::Result<()>, JoinError>) -> anyhow::Result<()> {
+    match result {
+        Ok(result) => result,
+        // Propagate the panic so that it's reported by the service as such.
+        Err(err) if err.is_panic() => std::panic::resume_unwind(err.into_panic()),
+        Err(_) => Err(anyhow::anyhow!("task was cancelled")),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum TaskState {
+    NotStarted,
+    WaitingForDependencies,
+    Running,
+    Restarting,
+    Finished,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct TaskHealthDetails {
+    state: TaskState,
+    restarts: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_error: Option<String>,
+}
+
+/// Health check reporting the state of every task run by the service, including restarts and failures.
+///
+/// The component is not ready while any task is not started yet, waits for its dependencies or has failed,
+/// and is affected while any task is being restarted.
+#[derive(Debug, Clone, Default)]
+pub(super) struct TasksHealthCheck(Arc<Mutex<HashMap<&'static str, TaskHealthDetails>>>);
+
+impl TasksHealthCheck {
+    /// Registers a task in the not started state; the state is updated once the task is run.
+    pub(super) fn add_task(&self, task: &dyn Task) {
+        let details = TaskHealthDetails {
+            state: TaskState::NotStarted,
+            restarts: 0,
+            last_error: None,
+        };
+        self.lock().insert(task.name(), details);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<&'static str, TaskHealthDetails>> {
+        self.0.lock().expect("tasks health is poisoned")
+    }
+
+    fn update(&self, name: &'static str, state: TaskState) {
+        if let Some(details) = self.lock().get_mut(name) {
+            details.state = state;
+        }
+    }
+
+    fn increment_restarts(&self, name: &'static str) {
+        if let Some(details) = self.lock().get_mut(name) {
+            details.restarts += 1;
+        }
+    }
+
+    fn set_last_error(&self, name: &'static str, error: String) {
+        if let Some(details) = self.lock().get_mut(name) {
+            details.last_error = Some(error);
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl CheckHealth for TasksHealthCheck {
+    fn name(&self) -> &'static str {
+        "tasks"
+    }
+
+    async fn check_health(&self) -> Health {
+        let tasks = self.lock().clone();
+        let states = || tasks.values().map(|details| details.state);
+        let status = if states().any(|state| {
+            matches!(
+                state,
+                TaskState::NotStarted | TaskState::WaitingForDependencies | TaskState::Failed
+            )
+        }) {
+            HealthStatus::NotReady
+        } else if states().any(|state| state == TaskState::Restarting) {
+            HealthStatus::Affected
+        } else {
+            HealthStatus::Ready
+        };
+        Health::from(status).with_details(tasks)
+    }
+}
@@ -1,10 +1,19 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
 
 use anyhow::anyhow;
 use tokio::runtime::Runtime;
+use zksync_health_check::{AppHealthCheck, HealthStatus, HealthUpdater, ReactiveHealthCheck};
 
-use crate::service::{
-    ServiceContext, StopReceiver, Task, WiringError, WiringLayer, ZkStackService,
+use crate::{
+    implementations::resources::healthcheck::AppHealthCheckResource,
+    service::{ServiceContext, StopReceiver, WiringError, WiringLayer, ZkStackService},
+    task::{Backoff, RestartPolicy, Task},
 };
 
 // `ZkStack` Service's `new()` method has to have a check for nested runtime.
@@ -209,3 +218,269 @@ fn test_task_run() {
     let res2 = *remaining_task_was_run.lock().unwrap();
     assert!(res2, "Incorrect resource value");
 }
+
+const TEST_BACKOFF: Backoff = Backoff {
+    initial_delay: Duration::from_millis(1),
+    max_delay: Duration::from_millis(10),
+    max_restarts: Some(3),
+    reset_after: Duration::from_secs(60),
+};
+
+/// Task that fails the specified number of times, then checks the reported health and exits successfully.
+#[derive(Debug, Clone)]
+struct FlakyTask {
+    failures_left: Arc<AtomicUsize>,
+    app_health: Arc<AppHealthCheck>,
+}
+
+#[async_trait::async_trait]
+impl Task for FlakyTask {
+    fn name(&self) -> &'static str {
+        "flaky_task"
+    }
+
+    async fn run(self: Box<Self>, _stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        let failures_left = self.failures_left.load(Ordering::SeqCst);
+        if failures_left > 0 {
+            self.failures_left
+                .store(failures_left - 1, Ordering::SeqCst);
+            anyhow::bail!("flaky error");
+        }
+
+        let health = self
+            .app_health
+            .check_component_health("tasks")
+            .await
+            .expect("no tasks health check");
+        assert_eq!(health.status(), HealthStatus::Ready);
+        let health = serde_json::to_value(health).unwrap();
+        let details = &health["details"]["flaky_task"];
+        assert_eq!(details["state"], "running");
+        assert_eq!(details["restarts"], 2);
+        assert_eq!(details["last_error"], "exited with an error: flaky error");
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct FlakyTaskLayer {
+    failures: usize,
+    policy: RestartPolicy,
+    failures_left: Arc<AtomicUsize>,
+}
+
+impl FlakyTaskLayer {
+    fn new(failures: usize, policy: RestartPolicy) -> Self {
+        Self {
+            failures,
+            policy,
+            failures_left: Arc::default(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for FlakyTaskLayer {
+    fn layer_name(&self) -> &'static str {
+        "flaky_task_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut node: ServiceContext<'_>) -> Result<(), WiringError> {
+        self.failures_left.store(self.failures, Ordering::SeqCst);
+        let AppHealthCheckResource(app_health) = node.get_resource_or_default().await;
+        let task = FlakyTask {
+            failures_left: self.failures_left,
+            app_health,
+        };
+        node.add_restartable_task(task, self.policy);
+        Ok(())
+    }
+}
+
+#[test]
+fn restartable_task_is_restarted_on_failure() {
+    let layer = FlakyTaskLayer::new(2, RestartPolicy::OnFailure(TEST_BACKOFF));
+    let failures_left = layer.failures_left.clone();
+    let mut zk_stack_service = ZkStackService::new().unwrap();
+    zk_stack_service.add_layer(layer);
+    zk_stack_service.run().unwrap();
+    assert_eq!(failures_left.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn restartable_task_fails_after_max_restarts() {
+    let layer = FlakyTaskLayer::new(10, RestartPolicy::OnFailure(TEST_BACKOFF));
+    let failures_left = layer.failures_left.clone();
+    let mut zk_stack_service = ZkStackService::new().unwrap();
+    zk_stack_service.add_layer(layer);
+    let err = zk_stack_service.run().unwrap_err();
+    assert_eq!(err.to_string(), "Task flaky_task failed");
+    // The initial run + 3 restarts.
+    assert_eq!(failures_left.load(Ordering::SeqCst), 6);
+}
+
+#[test]
+fn task_without_restart_policy_is_not_restarted() {
+    let layer = FlakyTaskLayer::new(2, RestartPolicy::Never);
+    let failures_left = layer.failures_left.clone();
+    let mut zk_stack_service = ZkStackService::new().unwrap();
+    zk_stack_service.add_layer(layer);
+    let err = zk_stack_service.run().unwrap_err();
+    assert_eq!(err.to_string(), "Task flaky_task failed");
+    assert_eq!(failures_left.load(Ordering::SeqCst), 1);
+}
+
+/// Task that runs for the specified time, then fails the specified number of times before exiting successfully.
+#[derive(Debug, Clone)]
+struct LongRunningFlakyTask {
+    uptime: Duration,
+    failures_left: Arc<AtomicUsize>,
+}
+
+#[async_trait::async_trait]
+impl Task for LongRunningFlakyTask {
+    fn name(&self) -> &'static str {
+        "long_running_flaky_task"
+    }
+
+    async fn run(self: Box<Self>, _stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        tokio::time::sleep(self.uptime).await;
+        let failures_left = self.failures_left.load(Ordering::SeqCst);
+        if failures_left > 0 {
+            self.failures_left
+                .store(failures_left - 1, Ordering::SeqCst);
+            anyhow::bail!("flaky error");
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct LongRunningFlakyTaskLayer(LongRunningFlakyTask);
+
+#[async_trait::async_trait]
+impl WiringLayer for LongRunningFlakyTaskLayer {
+    fn layer_name(&self) -> &'static str {
+        "long_running_flaky_task_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut node: ServiceContext<'_>) -> Result<(), WiringError> {
+        let policy = RestartPolicy::OnFailure(Backoff {
+            max_restarts: Some(1),
+            reset_after: Duration::from_millis(20),
+            ..TEST_BACKOFF
+        });
+        node.add_restartable_task(self.0, policy);
+        Ok(())
+    }
+}
+
+#[test]
+fn restart_count_is_reset_after_stable_run() {
+    let failures_left = Arc::new(AtomicUsize::new(3));
+    let task = LongRunningFlakyTask {
+        uptime: Duration::from_millis(50),
+        failures_left: failures_left.clone(),
+    };
+    let mut zk_stack_service = ZkStackService::new().unwrap();
+    zk_stack_service.add_layer(LongRunningFlakyTaskLayer(task));
+    // Each run is stable, so the task is restarted despite exceeding `max_restarts` in total.
+    zk_stack_service.run().unwrap();
+    assert_eq!(failures_left.load(Ordering::SeqCst), 0);
+}
+
+/// Task that marks its health check as ready after a delay, and then waits for the stop signal.
+#[derive(Debug)]
+struct DependencyTask {
+    is_ready: Arc<AtomicBool>,
+    health_updater: HealthUpdater,
+}
+
+#[async_trait::async_trait]
+impl Task for DependencyTask {
+    fn name(&self) -> &'static str {
+        "dependency_task"
+    }
+
+    async fn run(self: Box<Self>, mut stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        self.is_ready.store(true, Ordering::SeqCst);
+        self.health_updater.update(HealthStatus::Ready.into());
+        stop_receiver.0.changed().await?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct DependentTask {
+    dependency: &'static str,
+    dependency_is_ready: Arc<AtomicBool>,
+}
+
+#[async_trait::async_trait]
+impl Task for DependentTask {
+    fn name(&self) -> &'static str {
+        "dependent_task"
+    }
+
+    fn depends_on(&self) -> Vec<&'static str> {
+        vec![self.dependency]
+    }
+
+    async fn run(self: Box<Self>, _stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.dependency_is_ready.load(Ordering::SeqCst),
+            "task was started before its dependency is ready"
+        );
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct DependentTasksLayer {
+    dependency: &'static str,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for DependentTasksLayer {
+    fn layer_name(&self) -> &'static str {
+        "dependent_tasks_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut node: ServiceContext<'_>) -> Result<(), WiringError> {
+        let (health_check, health_updater) = ReactiveHealthCheck::new("dependency");
+        let AppHealthCheckResource(app_health) = node.get_resource_or_default().await;
+        app_health.insert_component(health_check);
+
+        let is_ready = Arc::new(AtomicBool::new(false));
+        // The dependent task is added first to check that the order of adding tasks doesn't matter.
+        node.add_task(Box::new(DependentTask {
+            dependency: self.dependency,
+            dependency_is_ready: is_ready.clone(),
+        }))
+        .add_task(Box::new(DependencyTask {
+            is_ready,
+            health_updater,
+        }));
+        Ok(())
+    }
+}
+
+#[test]
+fn task_is_started_after_its_dependencies() {
+    let mut zk_stack_service = ZkStackService::new().unwrap();
+    zk_stack_service.add_layer(DependentTasksLayer {
+        dependency: "dependency",
+    });
+    zk_stack_service.run().unwrap();
+}
+
+#[test]
+fn task_with_unknown_dependency_fails() {
+    let mut zk_stack_service = ZkStackService::new().unwrap();
+    zk_stack_service.add_layer(DependentTasksLayer {
+        dependency: "unknown",
+    });
+    let err = zk_stack_service.run().unwrap_err();
+    assert_eq!(err.to_string(), "Task dependent_task failed");
+}
@@ -1,6 +1,8 @@
 //! Tasks define the "runnable" concept of the node, e.g. something that can be launched and runs until the node
 //! is stopped.
 
+use std::time::Duration;
+
 use futures::future::BoxFuture;
 
 use crate::service::StopReceiver;
@@ -13,7 +15,8 @@ pub trait Task: 'static + Send {
 
     /// Runs the task.
     ///
-    /// Once any of the task returns, the node will shutdown.
+    /// Once any of the task returns, the node will shutdown, unless the task was added with a [`RestartPolicy`]
+    /// that allows to restart it.
     /// If the task returns an error, the node will spawn an error-level log message and will return a non-zero
     /// exit code.
     ///
@@ -24,6 +27,15 @@ pub trait Task: 'static + Send {
     /// Each task is expected to perform the required cleanup after receiving the stop signal.
     async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()>;
 
+    /// Names of the health check components (as registered in `AppHealthCheckResource`) that must report
+    /// a healthy status before the task is started. Can be used to order tasks, e.g. to start a task only after
+    /// the task it relies on is ready.
+    ///
+    /// If any of the components is not registered once the wiring is complete, the task will fail.
+    fn depends_on(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+
     /// Asynchronous hook that will be called after *each task* has finished their cleanup.
     /// It is guaranteed that no other task is running at this point, e.g. `ZkStackService` will invoke
     /// this hook sequentially for each task.
@@ -38,3 +50,131 @@ pub trait Task: 'static + Send {
         None
     }
 }
+
+/// Policy defining whether the service should restart a task once it exits.
+///
+/// Restart policies are only applied to the tasks added via `ServiceContext::add_restartable_task()`.
+/// Tasks added via `ServiceContext::add_task()` are never restarted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RestartPolicy {
+    /// Task is never restarted; once it exits, the node will shutdown.
+    #[default]
+    Never,
+    /// Task is restarted if it returns an error or panics. If it returns successfully, the node will shutdown.
+    OnFailure(Backoff),
+    /// Task is restarted whenever it exits, unless the node is shutting down.
+    Always(Backoff),
+}
+
+impl RestartPolicy {
+    /// Returns the backoff to apply before the next restart, or `None` if the task should not be restarted.
+    pub(crate) fn backoff(&self, failed: bool, restart_count: u32) -> Option<Duration> {
+        let backoff = match self {
+            Self::Never => return None,
+            Self::OnFailure(backoff) if failed => backoff,
+            Self::OnFailure(_) => return None,
+            Self::Always(backoff) => backoff,
+        };
+        backoff.delay(restart_count)
+    }
+
+    /// Checks whether a task run with the specified uptime was stable, i.e., whether the restart count
+    /// should be reset after it.
+    pub(crate) fn is_stable_run(&self, uptime: Duration) -> bool {
+        match self {
+            Self::Never => false,
+            Self::OnFailure(backoff) | Self::Always(backoff) => uptime >= backoff.reset_after,
+        }
+    }
+}
+
+/// Exponential backoff used between task restarts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Backoff {
+    /// Delay before the first restart. Each subsequent delay is doubled.
+    pub initial_delay: Duration,
+    /// Upper bound for the delay between restarts.
+    pub max_delay: Duration,
+    /// Maximum number of consecutive restarts. Once it is reached, the next exit of the task will shutdown
+    /// the node. `None` means that the task may be restarted indefinitely.
+    pub max_restarts: Option<u32>,
+    /// Minimum uptime for a task run to be considered stable. Once a task that ran for at least this long exits,
+    /// the restart count is reset, so that the next restart uses `initial_delay` and occasional failures
+    /// of a long-running task don't exhaust `max_restarts`.
+    pub reset_after: Duration,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+            max_restarts: None,
+            reset_after: Duration::from_secs(300),
+        }
+    }
+}
+
+impl Backoff {
+    fn delay(&self, restart_count: u32) -> Option<Duration> {
+        if self.max_restarts.map_or(false, |max| restart_count >= max) {
+            return None;
+        }
+        let multiplier = 1_u32 << restart_count.min(31);
+        Some(
+            self.initial_delay
+                .saturating_mul(multiplier)
+                .min(self.max_delay),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_delays() {
+        let backoff = Backoff {
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+            max_restarts: Some(6),
+            reset_after: Duration::from_secs(60),
+        };
+        let delays: Vec<_> = (0..7).map(|count| backoff.delay(count)).collect();
+        let expected_delays: Vec<_> = vec![100, 200, 400, 800, 1_000, 1_000]
+            .into_iter()
+            .map(|ms| Some(Duration::from_millis(ms)))
+            .chain(std::iter::once(None))
+            .collect();
+        assert_eq!(delays, expected_delays);
+        assert_eq!(
+            Backoff::default().delay(u32::MAX),
+            Some(Duration::from_secs(60))
+        );
+    }
+
+    #[test]
+    fn restart_policy_backoff() {
+        let backoff = Backoff::default();
+        assert_eq!(RestartPolicy::Never.backoff(true, 0), None);
+        assert_eq!(RestartPolicy::OnFailure(backoff).backoff(false, 0), None);
+        assert_eq!(
+            RestartPolicy::OnFailure(backoff).backoff(true, 0),
+            Some(backoff.initial_delay)
+        );
+        assert_eq!(
+            RestartPolicy::Always(backoff).backoff(false, 0),
+            Some(backoff.initial_delay)
+        );
+    }
+
+    #[test]
+    fn restart_policy_stable_run() {
+        let backoff = Backoff::default();
+        assert!(!RestartPolicy::Never.is_stable_run(Duration::MAX));
+        assert!(!RestartPolicy::OnFailure(backoff).is_stable_run(Duration::from_secs(1)));
+        assert!(RestartPolicy::OnFailure(backoff).is_stable_run(backoff.reset_after));
+        assert!(RestartPolicy::Always(backoff).is_stable_run(Duration::from_secs(3_600)));
+    }
+}
@@ -0,0 +1,484 @@
+//! Integration test building a main node from the wiring layers.
+//! Requires a test Postgres instance (see `TEST_DATABASE_URL`); L1 is replaced with `MockEthereum`.
+
+use std::{
+    net::{Ipv4Addr, SocketAddr, TcpListener},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use rand::Rng;
+
+use zksync_config::{
+    configs::{
+        api::{ContractVerificationApiConfig, MerkleTreeApiConfig, Web3JsonRpcConfig},
+        chain::{
+            CircuitBreakerConfig, MempoolConfig, NetworkConfig, OperationsManagerConfig,
+            StateKeeperConfig,
+        },
+        database::MerkleTreeConfig,
+    },
+    ContractsConfig, DBConfig, ETHSenderConfig,
+};
+use zksync_consensus_roles::{node, validator};
+use zksync_contracts::BaseSystemContracts;
+use zksync_core::{
+    api_server::{
+        tx_sender::{ApiContracts, TxSenderConfig},
+        web3::state::InternalApiConfig,
+    },
+    consensus,
+    genesis::{ensure_genesis_state, GenesisParams},
+    metadata_calculator::MetadataCalculatorConfig,
+    rocksdb_backup::{RocksdbBackupManifest, RocksdbKind},
+};
+use zksync_dal::{
+    connection::{ConnectionPoolBuilder, TestTemplate},
+    ConnectionPool,
+};
+use zksync_eth_client::clients::MockEthereum;
+use zksync_health_check::AppHealthCheck;
+use zksync_node_framework::{
+    implementations::{
+        layers::{
+            basic_witness_input_producer::BasicWitnessInputProducerLayer,
+            commitment_generator::CommitmentGeneratorLayer,
+            consensus::ConsensusLayer,
+            contract_verification_api::ContractVerificationApiLayer,
+            eth_sender::{EthTxAggregatorLayer, EthTxManagerLayer},
+            fee_input::SequencerFeeInputLayer,
+            metadata_calculator::MetadataCalculatorLayer,
+            priority_ops_monitor::PendingPriorityOpsMonitorLayer,
+            state_keeper::{
+                main_batch_executor::MainBatchExecutorLayer, mempool_io::MempoolIOLayer,
+                StateKeeperLayer,
+            },
+            web3_api::{
+                server::{Web3ServerLayer, Web3ServerOptionalConfig},
+                tx_sender::{PostgresStorageCachesConfig, TxSenderLayer},
+                tx_sink::TxSinkLayer,
+            },
+        },
+        resources::{
+            eth_interface::{BoundEthInterfaceResource, EthInterfaceResource},
+            healthcheck::AppHealthCheckResource,
+            object_store::ObjectStoreResource,
+            pools::{MasterPoolResource, ReplicaPoolResource},
+        },
+    },
+    service::{ServiceContext, StopReceiver, ZkStackService},
+    task::Task,
+    wiring_layer::{WiringError, WiringLayer},
+};
+use zksync_object_store::{ObjectStore, ObjectStoreFactory};
+use zksync_types::{
+    ethabi::Token, protocol_version::L1VerifierConfig,
+    system_contracts::get_system_smart_contracts, Address, ProtocolVersionId, H256,
+};
+
+/// Number of L1 blocks available in the mock.
+const L1_BLOCK_COUNT: u64 = 20;
+/// Timeout for the components to start working after the node is ready.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(30);
+const PROBE_POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// Tasks that should still be running (without restarts) once the node components have made progress.
+const LONG_RUNNING_TASKS: [&str; 8] = [
+    "eth_tx_aggregator",
+    "eth_tx_manager",
+    "consensus",
+    "contract_verification_api",
+    "basic_witness_input_producer",
+    "pending_priority_ops_monitor",
+    "merkle_tree_backup_uploader",
+    "state_keeper_cache_backup_uploader",
+];
+
+/// Provides pools connected to the test database.
+#[derive(Debug)]
+struct TestPoolsLayer(ConnectionPoolBuilder);
+
+#[async_trait::async_trait]
+impl WiringLayer for TestPoolsLayer {
+    fn layer_name(&self) -> &'static str {
+        "test_pools_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        context.insert_resource(MasterPoolResource::new(self.0.clone()))?;
+        context.insert_resource(ReplicaPoolResource::new(self.0))?;
+        Ok(())
+    }
+}
+
+/// Provides `MockEthereum` as the L1 client and the mock object store.
+#[derive(Debug)]
+struct MockL1Layer {
+    /// Number of `eth_call`s to L1 (i.e., multicalls performed by `EthTxAggregator`).
+    call_count: Arc<AtomicUsize>,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for MockL1Layer {
+    fn layer_name(&self) -> &'static str {
+        "mock_l1_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let eth_client = MockEthereum::default()
+            .with_fee_history(vec![1_000_000_000; L1_BLOCK_COUNT as usize])
+            .with_excess_blob_gas_history(vec![0; L1_BLOCK_COUNT as usize])
+            .with_call_handler({
+                let call_count = self.call_count.clone();
+                move |_| {
+                    call_count.fetch_add(1, Ordering::SeqCst);
+                    mock_multicall_response()
+                }
+            });
+        eth_client.advance_block_number(L1_BLOCK_COUNT - 1);
+        let eth_client = Arc::new(eth_client);
+        context.insert_resource(EthInterfaceResource(eth_client.clone()))?;
+        context.insert_resource(BoundEthInterfaceResource(eth_client))?;
+
+        let object_store = ObjectStoreFactory::mock().create_store().await;
+        context.insert_resource(ObjectStoreResource(object_store))?;
+        Ok(())
+    }
+}
+
+/// Response to the multicall performed by `EthTxAggregator` on each iteration.
+fn mock_multicall_response() -> Token {
+    Token::Array(vec![
+        Token::Tuple(vec![Token::Bool(true), Token::Bytes(vec![1u8; 32])]),
+        Token::Tuple(vec![Token::Bool(true), Token::Bytes(vec![2u8; 32])]),
+        Token::Tuple(vec![Token::Bool(true), Token::Bytes(vec![3u8; 96])]),
+        Token::Tuple(vec![Token::Bool(true), Token::Bytes(vec![4u8; 32])]),
+        Token::Tuple(vec![
+            Token::Bool(true),
+            Token::Bytes(
+                H256::from_low_u64_be(ProtocolVersionId::default() as u64)
+                    .0
+                    .to_vec(),
+            ),
+        ]),
+    ])
+}
+
+/// Task that finishes (and thus stops the node) once the tree and both API servers are ready, and checks
+/// that the other components have started working.
+#[derive(Debug)]
+struct ReadinessProbeTask {
+    app_health: Arc<AppHealthCheck>,
+    pool: ConnectionPool,
+    object_store: Arc<dyn ObjectStore>,
+    l1_call_count: Arc<AtomicUsize>,
+    contract_verification_addr: SocketAddr,
+    validator_key: validator::PublicKey,
+}
+
+impl ReadinessProbeTask {
+    async fn assert_long_running_tasks_are_healthy(&self) {
+        let health = self
+            .app_health
+            .check_component_health("tasks")
+            .await
+            .expect("no tasks health check");
+        let health = serde_json::to_value(health).unwrap();
+        for name in LONG_RUNNING_TASKS {
+            let task_health = &health["details"][name];
+            assert_eq!(task_health["state"], "running", "{name}: {task_health}");
+            assert_eq!(task_health["restarts"], 0, "{name}: {task_health}");
+        }
+    }
+
+    async fn backup_manifest(&self, kind: RocksdbKind) -> Option<RocksdbBackupManifest> {
+        RocksdbBackupManifest::load_latest(&*self.object_store, kind)
+            .await
+            .unwrap()
+    }
+
+    async fn consensus_genesis(&self) -> Option<validator::Genesis> {
+        let mut storage = self.pool.access_storage().await.unwrap();
+        storage.consensus_dal().genesis().await.unwrap()
+    }
+
+    async fn zksolc_versions(&self) -> Option<Vec<String>> {
+        let url = format!(
+            "http://{}/contract_verification/zksolc_versions",
+            self.contract_verification_addr
+        );
+        let response = reqwest::get(url).await.ok()?;
+        assert!(response.status().is_success(), "{response:?}");
+        Some(response.json().await.unwrap())
+    }
+
+    async fn check(&self) {
+        // `EthTxAggregator` performs a multicall to L1 on each iteration.
+        while self.l1_call_count.load(Ordering::SeqCst) == 0 {
+            tokio::time::sleep(PROBE_POLL_INTERVAL).await;
+        }
+
+        // The main node consensus initializes the consensus genesis with its validator key.
+        let genesis = loop {
+            if let Some(genesis) = self.consensus_genesis().await {
+                break genesis;
+            }
+            tokio::time::sleep(PROBE_POLL_INTERVAL).await;
+        };
+        let expected_validators = validator::ValidatorSet::new([self.validator_key.clone()]);
+        assert_eq!(genesis.validators, expected_validators.unwrap());
+
+        let zksolc_versions = loop {
+            if let Some(versions) = self.zksolc_versions().await {
+                break versions;
+            }
+            tokio::time::sleep(PROBE_POLL_INTERVAL).await;
+        };
+        assert!(zksolc_versions.is_empty(), "{zksolc_versions:?}");
+
+        // Both the tree and the state keeper cache are backed up once they are initialized.
+        for kind in [RocksdbKind::MerkleTree, RocksdbKind::StateKeeperCache] {
+            while self.backup_manifest(kind).await.is_none() {
+                tokio::time::sleep(PROBE_POLL_INTERVAL).await;
+            }
+        }
+
+        // Some tasks (e.g., the priority ops monitor or the basic witness input producer) have no observable
+        // progress on an idle node, so we only check that they haven't failed by now.
+        self.assert_long_running_tasks_are_healthy().await;
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for ReadinessProbeTask {
+    fn name(&self) -> &'static str {
+        "readiness_probe"
+    }
+
+    fn depends_on(&self) -> Vec<&'static str> {
+        vec!["tree", "http_api", "ws_api"]
+    }
+
+    async fn run(self: Box<Self>, _stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        tokio::time::timeout(PROBE_TIMEOUT, self.check())
+            .await
+            .map_err(|_| anyhow::anyhow!("timed out waiting for node components to start"))
+    }
+}
+
+#[derive(Debug)]
+struct ReadinessProbeLayer {
+    l1_call_count: Arc<AtomicUsize>,
+    contract_verification_addr: SocketAddr,
+    validator_key: validator::PublicKey,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for ReadinessProbeLayer {
+    fn layer_name(&self) -> &'static str {
+        "readiness_probe_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let AppHealthCheckResource(app_health) = context.get_resource_or_default().await;
+        let pool = context
+            .get_resource::<MasterPoolResource>()
+            .await?
+            .get()
+            .await?;
+        let ObjectStoreResource(object_store) = context.get_resource().await?;
+        context.add_task(Box::new(ReadinessProbeTask {
+            app_health,
+            pool,
+            object_store,
+            l1_call_count: self.l1_call_count,
+            contract_verification_addr: self.contract_verification_addr,
+            validator_key: self.validator_key,
+        }));
+        Ok(())
+    }
+}
+
+/// Creates consensus config and secrets for a single-validator network.
+fn consensus_config() -> (consensus::Config, consensus::Secrets) {
+    let rng = &mut rand::thread_rng();
+    let validator_key: validator::SecretKey = rng.gen();
+    let node_key: node::SecretKey = rng.gen();
+    // The network consists of a single validator without gossip peers, so the bound port is never advertised.
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, 0));
+    let config = consensus::Config {
+        server_addr: addr,
+        public_addr: addr,
+        validators: validator::ValidatorSet::new([validator_key.public()]).unwrap(),
+        max_payload_size: 1_000_000,
+        gossip_dynamic_inbound_limit: 0,
+        gossip_static_inbound: Default::default(),
+        gossip_static_outbound: Default::default(),
+    };
+    let secrets = consensus::Secrets {
+        validator_key: Some(validator_key),
+        node_key: Some(node_key),
+    };
+    (config, secrets)
+}
+
+/// Creates a test database with the genesis state. Uses a separate runtime, since the service
+/// cannot be created within a Tokio runtime.
+fn prepare_database(network_config: &NetworkConfig) -> ConnectionPoolBuilder {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    runtime.block_on(async {
+        let pool_builder = TestTemplate::empty().unwrap().create_db(20).await.unwrap();
+        let pool = pool_builder.build().await.unwrap();
+        let genesis_params = GenesisParams {
+            first_validator: Address::repeat_byte(1),
+            protocol_version: ProtocolVersionId::latest(),
+            base_system_contracts: BaseSystemContracts::load_from_disk(),
+            system_contracts: get_system_smart_contracts(),
+            first_l1_verifier_config: L1VerifierConfig::default(),
+        };
+        let mut storage = pool.access_storage().await.unwrap();
+        ensure_genesis_state(
+            &mut storage,
+            network_config.zksync_network_id,
+            &genesis_params,
+        )
+        .await
+        .unwrap();
+        pool_builder
+    })
+}
+
+#[test]
+fn main_node_from_layers() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let network_config = NetworkConfig::for_tests();
+    let contracts_config = ContractsConfig::for_tests();
+    let state_keeper_config = StateKeeperConfig::for_tests();
+    let eth_sender_config = ETHSenderConfig::for_tests();
+    let rpc_config = Web3JsonRpcConfig::for_tests();
+    let mempool_config = MempoolConfig {
+        sync_interval_ms: 10,
+        sync_batch_size: 100,
+        capacity: 1_000,
+        stuck_tx_timeout: 10,
+        remove_stuck_txs: false,
+        delay_interval: 100,
+    };
+    let db_config = DBConfig {
+        state_keeper_db_path: temp_dir.path().join("state_keeper").display().to_string(),
+        merkle_tree: MerkleTreeConfig {
+            path: temp_dir.path().join("tree").display().to_string(),
+            ..MerkleTreeConfig::default()
+        },
+        rocksdb_backup_interval_sec: Some(1),
+        restore_rocksdb_from_backup: false,
+    };
+    let pool_builder = prepare_database(&network_config);
+    let l1_call_count = Arc::new(AtomicUsize::new(0));
+    let contract_verification_listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+    let contract_verification_addr = contract_verification_listener.local_addr().unwrap();
+    let (consensus_config, consensus_secrets) = consensus_config();
+    let validator_key = consensus_secrets.validator_key.as_ref().unwrap().public();
+
+    let metadata_calculator_config = MetadataCalculatorConfig::for_main_node(
+        &db_config.merkle_tree,
+        &OperationsManagerConfig {
+            delay_interval: 100,
+        },
+    );
+    let internal_api_config =
+        InternalApiConfig::new(&network_config, &rpc_config, &contracts_config);
+    let postgres_storage_caches_config = PostgresStorageCachesConfig {
+        factory_deps_cache_size: rpc_config.factory_deps_cache_size() as u64,
+        initial_writes_cache_size: rpc_config.initial_writes_cache_size() as u64,
+        latest_values_cache_size: rpc_config.latest_values_cache_size() as u64,
+    };
+
+    let mut node = ZkStackService::new().unwrap();
+    node.add_layer(TestPoolsLayer(pool_builder))
+        .add_layer(MockL1Layer {
+            call_count: l1_call_count.clone(),
+        })
+        .add_layer(SequencerFeeInputLayer::new(
+            eth_sender_config.gas_adjuster,
+            state_keeper_config.clone(),
+            eth_sender_config.sender.pubdata_sending_mode,
+        ))
+        .add_layer(
+            MetadataCalculatorLayer::new(metadata_calculator_config)
+                .with_tree_api_config(MerkleTreeApiConfig { port: 0 })
+                .with_rocksdb_backups(&db_config),
+        )
+        .add_layer(MempoolIOLayer::new(
+            network_config.clone(),
+            contracts_config.clone(),
+            state_keeper_config.clone(),
+            mempool_config,
+        ))
+        .add_layer(MainBatchExecutorLayer::new(
+            db_config,
+            state_keeper_config.clone(),
+        ))
+        .add_layer(StateKeeperLayer)
+        .add_layer(EthTxAggregatorLayer::new(
+            eth_sender_config.clone(),
+            contracts_config.clone(),
+            network_config.clone(),
+        ))
+        .add_layer(EthTxManagerLayer::new(eth_sender_config))
+        .add_layer(PendingPriorityOpsMonitorLayer::new(CircuitBreakerConfig {
+            sync_interval_ms: 100,
+            http_req_max_retry_number: 5,
+            http_req_retry_interval_sec: 1,
+            replication_lag_limit_sec: None,
+            priority_op_deadline_sec: Some(60),
+        }))
+        .add_layer(TxSinkLayer::MasterPoolSink)
+        .add_layer(TxSenderLayer::new(
+            TxSenderConfig::new(
+                &state_keeper_config,
+                &rpc_config,
+                network_config.zksync_network_id,
+            ),
+            postgres_storage_caches_config,
+            rpc_config.vm_concurrency_limit(),
+            ApiContracts::load_from_disk(),
+        ))
+        .add_layer(Web3ServerLayer::http(
+            0,
+            internal_api_config.clone(),
+            Web3ServerOptionalConfig::default(),
+        ))
+        .add_layer(Web3ServerLayer::ws(
+            0,
+            internal_api_config,
+            Web3ServerOptionalConfig::default(),
+        ))
+        .add_layer(
+            ContractVerificationApiLayer::new(ContractVerificationApiConfig {
+                port: contract_verification_addr.port(),
+                url: format!("http://{contract_verification_addr}"),
+            })
+            .with_listener(contract_verification_listener),
+        )
+        .add_layer(BasicWitnessInputProducerLayer::new(
+            network_config.zksync_network_id,
+        ))
+        .add_layer(CommitmentGeneratorLayer)
+        .add_layer(ConsensusLayer::main_node(
+            consensus_config,
+            consensus_secrets,
+        ))
+        .add_layer(ReadinessProbeLayer {
+            l1_call_count,
+            contract_verification_addr,
+            validator_key,
+        });
+
+    node.run().unwrap();
+}
@@ -1,14 +1,15 @@
 use std::{
     collections::VecDeque,
+    future,
     sync::Arc,
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime},
 };
 
 use futures::{channel::mpsc, SinkExt};
 use tokio::sync::RwLock;
 use zksync::{error::ClientError, operations::SyncTransactionHandle, HttpClient};
 use zksync_contracts::test_contracts::LoadnextContractExecutionParams;
-use zksync_types::{api::TransactionReceipt, Address, Nonce, H256, U256, U64};
+use zksync_types::{api::TransactionReceipt, Address, L1BatchNumber, Nonce, H256, U256, U64};
 use zksync_web3_decl::jsonrpsee::core::ClientError as CoreError;
 
 use crate::{
@@ -16,8 +17,10 @@ use crate::{
     account_pool::{AddressPool, TestWallet},
     command::{ExpectedOutcome, IncorrectnessModifier, TxCommand, TxType},
     config::{LoadtestConfig, RequestLimiters},
-    constants::{MAX_L1_TRANSACTIONS, POLLING_INTERVAL},
-    report::{Report, ReportBuilder, ReportLabel},
+    constants::{MAX_L1_TRANSACTIONS, OPEN_LOOP_DRAIN_TIMEOUT, POLLING_INTERVAL},
+    l1_commit_tracker::L1CommitTrackerHandle,
+    report::{LatencyStage, Report, ReportBuilder, ReportLabel},
+    scenario::ArrivalQueue,
     utils::format_gwei,
 };
 
@@ -41,6 +44,9 @@ struct InflightTx {
     tx_hash: H256,
     attempt: usize,
     start: Instant,
+    /// Moment the transaction was submitted; differs from `start` if the transaction was retried,
+    /// or waited for an available account in the open-loop mode.
+    submitted_at: Instant,
     command: TxCommand,
 }
 
@@ -72,6 +78,11 @@ pub struct AccountLifespan {
     inflight_txs: VecDeque<InflightTx>,
     /// Current account nonce, it is None at the beginning and will be set after the first transaction
     current_nonce: Option<Nonce>,
+    /// Queue of transactions to send in the open-loop mode. If not set, the account sends random transactions
+    /// as fast as they are processed.
+    arrivals: Option<ArrivalQueue>,
+    /// Tracker of L1 commitments for the included transactions.
+    l1_commit_tracker: Option<L1CommitTrackerHandle>,
 }
 
 impl AccountLifespan {
@@ -96,11 +107,31 @@ impl AccountLifespan {
             report_sink,
             inflight_txs: Default::default(),
             current_nonce: None,
+            arrivals: None,
+            l1_commit_tracker: None,
         }
     }
 
+    /// Switches the account to the open-loop mode, in which it sends transactions from the provided queue.
+    pub fn with_arrivals(mut self, arrivals: ArrivalQueue) -> Self {
+        self.arrivals = Some(arrivals);
+        self
+    }
+
+    pub fn with_l1_commit_tracker(mut self, tracker: L1CommitTrackerHandle) -> Self {
+        self.l1_commit_tracker = Some(tracker);
+        self
+    }
+
     pub async fn run(self, limiters: &RequestLimiters) {
-        let duration = self.config.duration();
+        // In the open-loop mode, the test ends once the scenario is finished.
+        let duration = self.arrivals.is_none().then(|| self.config.duration());
+        let test_timeout = async move {
+            match duration {
+                Some(duration) => tokio::time::sleep(duration).await,
+                None => future::pending().await,
+            }
+        };
         let tx_execution_task = self.clone().run_tx_execution();
         let api_requests_task = self.clone().run_api_requests_task(limiters);
 
@@ -114,7 +145,7 @@ impl AccountLifespan {
             result = self.run_pubsub_task(limiters) => {
                 tracing::trace!("PubSub task finished with {result:?}");
             },
-            () = tokio::time::sleep(duration) => {}
+            () = test_timeout => {}
         }
     }
 
@@ -129,12 +160,15 @@ impl AccountLifespan {
         self.execute_command(deploy_command.clone()).await?;
         self.wait_for_all_inflight_tx().await?;
 
+        if let Some(arrivals) = self.arrivals.take() {
+            return self.run_open_loop_tx_execution(arrivals).await;
+        }
+
         let mut timer = tokio::time::interval(POLLING_INTERVAL);
         let mut l1_tx_count = 0;
         loop {
             let command = self.generate_command();
-            let is_l1_transaction =
-                matches!(command.command_type, TxType::L1Execute | TxType::Deposit);
+            let is_l1_transaction = command.command_type.is_l1();
             if is_l1_transaction && l1_tx_count >= MAX_L1_TRANSACTIONS {
                 continue; // Skip command to not run out of Ethereum on L1
             }
@@ -153,6 +187,57 @@ impl AccountLifespan {
         }
     }
 
+    /// Sends transactions as they arrive according to the load scenario. Unlike in the closed-loop mode,
+    /// the rate of sent transactions doesn't depend on how fast the server processes them
+    /// (unless all accounts reach the `max_inflight_txs` limit).
+    async fn run_open_loop_tx_execution(mut self, arrivals: ArrivalQueue) -> Result<(), Aborted> {
+        let mut timer = tokio::time::interval(POLLING_INTERVAL);
+        let mut l1_tx_count = 0;
+        loop {
+            if self.inflight_txs.len() >= self.config.max_inflight_txs {
+                timer.tick().await;
+                self.check_inflight_txs().await?;
+                continue;
+            }
+
+            let arrival = tokio::select! {
+                arrival = arrivals.next() => arrival,
+                _ = timer.tick() => {
+                    self.check_inflight_txs().await?;
+                    continue;
+                }
+            };
+            let Some(arrival) = arrival else {
+                break; // The scenario is finished.
+            };
+
+            let command = self.generate_command_with_type(arrival.command_type);
+            let is_l1_transaction = command.command_type.is_l1();
+            if is_l1_transaction && l1_tx_count >= MAX_L1_TRANSACTIONS {
+                // Don't run out of Ethereum on L1.
+                let label = ReportLabel::skipped("L1 transactions limit is reached");
+                self.report(label, arrival.scheduled_at.elapsed(), 0, command)
+                    .await?;
+                continue;
+            }
+            self.contract_execution_params = arrival.contract_execution_params;
+            self.execute_command_submitted_at(command, arrival.scheduled_at)
+                .await?;
+            l1_tx_count += u64::from(is_l1_transaction);
+        }
+
+        let drain_result =
+            tokio::time::timeout(OPEN_LOOP_DRAIN_TIMEOUT, self.wait_for_all_inflight_tx()).await;
+        if drain_result.is_err() {
+            tracing::debug!(
+                "Account {:?}: {} transactions are not included after the scenario end",
+                self.wallet.wallet.address(),
+                self.inflight_txs.len()
+            );
+        }
+        Ok(())
+    }
+
     async fn wait_for_all_inflight_tx(&mut self) -> Result<(), Aborted> {
         let mut timer = tokio::time::interval(POLLING_INTERVAL);
         while !self.inflight_txs.is_empty() {
@@ -196,6 +281,8 @@ impl AccountLifespan {
                     );
                     self.report(label, tx.start.elapsed(), tx.attempt, tx.command)
                         .await?;
+                    self.report_inclusion(&transaction_receipt, tx.submitted_at)
+                        .await?;
                 }
                 other => {
                     tracing::trace!(
@@ -215,6 +302,28 @@ impl AccountLifespan {
         Ok(())
     }
 
+    /// Reports latency of the transaction inclusion and starts tracking its L1 commitment.
+    async fn report_inclusion(
+        &mut self,
+        receipt: &TransactionReceipt,
+        submitted_at: Instant,
+    ) -> Result<(), Aborted> {
+        let latency = submitted_at.elapsed();
+        if let (Some(tracker), Some(l1_batch_number)) =
+            (&self.l1_commit_tracker, receipt.l1_batch_number)
+        {
+            let submitted_at = SystemTime::now() - latency;
+            tracker.track(L1BatchNumber(l1_batch_number.as_u32()), submitted_at);
+        }
+
+        let report = ReportBuilder::default()
+            .action(LatencyStage::Inclusion)
+            .reporter(self.wallet.wallet.address())
+            .time(latency)
+            .finish();
+        self.send_report(report).await
+    }
+
     fn verify_receipt(
         &self,
         transaction_receipt: &TransactionReceipt,
@@ -252,6 +361,16 @@ impl AccountLifespan {
     /// before considering it completely failed. Such an approach makes us a bit more resilient to
     /// volatile errors such as random connection drop or insufficient fee error.
     async fn execute_command(&mut self, command: TxCommand) -> Result<(), Aborted> {
+        self.execute_command_submitted_at(command, Instant::now())
+            .await
+    }
+
+    /// Same as [`Self::execute_command()`], but the transaction latency is measured from `submitted_at`.
+    async fn execute_command_submitted_at(
+        &mut self,
+        command: TxCommand,
+        submitted_at: Instant,
+    ) -> Result<(), Aborted> {
         // We consider API errors to be somewhat likely, thus we will retry the operation if it fails
         // due to connection issues.
         const MAX_RETRIES: usize = 3;
@@ -286,6 +405,7 @@ impl AccountLifespan {
                     self.inflight_txs.push_back(InflightTx {
                         tx_hash,
                         start,
+                        submitted_at,
                         attempt,
                         command: command.clone(),
                     });
@@ -403,4 +523,13 @@ impl AccountLifespan {
             &self.addresses,
         )
     }
+
+    fn generate_command_with_type(&mut self, command_type: TxType) -> TxCommand {
+        TxCommand::new_with_type(
+            &mut self.wallet.rng,
+            self.wallet.wallet.address(),
+            &self.addresses,
+            command_type,
+        )
+    }
 }
@@ -84,6 +84,8 @@ pub struct AccountPool {
     pub accounts: VecDeque<TestWallet>,
     /// Pool of addresses of the test accounts.
     pub addresses: AddressPool,
+    /// Master RNG, which can be used by test components other than accounts.
+    pub rng: LoadtestRng,
 }
 
 impl AccountPool {
@@ -165,6 +167,7 @@ impl AccountPool {
             master_wallet,
             accounts,
             addresses: AddressPool::new(addresses),
+            rng,
         })
     }
 }
@@ -0,0 +1,100 @@
+//! Records transaction traces from a zkSync chain and replays them on another chain.
+//!
+//! The replay uses the same environment variables as the main loadtest (e.g., `MASTER_WALLET_PK`, `L2_RPC_ADDRESS`
+//! and `L2_CHAIN_ID`); see `README.md` for details.
+
+use std::{fs, path::PathBuf};
+
+use anyhow::Context as _;
+use clap::{Parser, Subcommand};
+use loadnext::{
+    config::{get_default_l2_rpc_address, LoadtestConfig},
+    trace::{Trace, TraceRecorder, TraceReplayer},
+};
+use zksync::HttpClientBuilder;
+use zksync_types::MiniblockNumber;
+
+#[derive(Debug, Parser)]
+#[command(author = "Matter Labs", version, about = "Loadnext transaction traces", long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Records transactions from a range of miniblocks into a trace file.
+    Record {
+        /// First miniblock to record (inclusive).
+        #[arg(long)]
+        from: u32,
+        /// Last miniblock to record (inclusive).
+        #[arg(long)]
+        to: u32,
+        /// Path to the output trace file.
+        #[arg(long)]
+        output: PathBuf,
+        /// JSON-RPC URL of the node to record transactions from.
+        #[arg(long, default_value_t = get_default_l2_rpc_address())]
+        rpc_url: String,
+    },
+    /// Replays transactions from a trace file on the chain configured via environment variables.
+    Replay {
+        /// Path to the trace file.
+        #[arg(long)]
+        trace: PathBuf,
+        /// Speed-up factor for the replay timing; e.g., 2.0 replays the trace twice as fast as it was recorded.
+        #[arg(long, default_value_t = 1.0)]
+        speed: f64,
+        /// Path to write the JSON replay report to.
+        #[arg(long)]
+        report: Option<PathBuf>,
+        /// Exit with an error if outcomes of any replayed transactions differ from the recorded ones.
+        #[arg(long)]
+        fail_on_mismatch: bool,
+    },
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let _guard = vlog::ObservabilityBuilder::new().build();
+
+    match Cli::parse().command {
+        Command::Record {
+            from,
+            to,
+            output,
+            rpc_url,
+        } => {
+            let client = HttpClientBuilder::default()
+                .build(&rpc_url)
+                .context("failed creating JSON-RPC client")?;
+            let trace = TraceRecorder::new(client)
+                .record(MiniblockNumber(from), MiniblockNumber(to))
+                .await?;
+            trace.save(&output)?;
+            tracing::info!("Saved trace to `{}`", output.display());
+        }
+        Command::Replay {
+            trace,
+            speed,
+            report,
+            fail_on_mismatch,
+        } => {
+            let trace = Trace::load(&trace)?;
+            let config = LoadtestConfig::from_env()
+                .context("Config parameters should be loaded from env or from default values")?;
+            let replay_report = TraceReplayer::new(config, speed)?.replay(&trace).await?;
+            replay_report.log_summary();
+            if let Some(path) = report {
+                let contents = serde_json::to_string_pretty(&replay_report)?;
+                fs::write(&path, contents)
+                    .with_context(|| format!("failed writing report to `{}`", path.display()))?;
+            }
+            if fail_on_mismatch && !replay_report.is_success() {
+                anyhow::bail!("Replayed transaction outcomes differ from the recorded ones");
+            }
+        }
+    }
+    Ok(())
+}
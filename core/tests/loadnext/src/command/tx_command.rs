@@ -26,18 +26,25 @@ pub enum TxType {
 
 impl TxType {
     pub fn initialize_weights(transaction_weights: &TransactionWeights) {
-        WEIGHTS
-            .set([
-                (TxType::Deposit, transaction_weights.deposit),
-                (TxType::L2Execute, transaction_weights.l2_transactions),
-                (TxType::L1Execute, transaction_weights.l1_transactions),
-                (TxType::WithdrawToSelf, transaction_weights.withdrawal / 2.0),
-                (
-                    TxType::WithdrawToOther,
-                    transaction_weights.withdrawal / 2.0,
-                ),
-            ])
-            .unwrap();
+        WEIGHTS.set(Self::weights(transaction_weights)).unwrap();
+    }
+
+    /// Returns weights of randomly generated transaction types corresponding to the provided config.
+    pub fn weights(transaction_weights: &TransactionWeights) -> [(TxType, f32); 5] {
+        [
+            (TxType::Deposit, transaction_weights.deposit),
+            (TxType::L2Execute, transaction_weights.l2_transactions),
+            (TxType::L1Execute, transaction_weights.l1_transactions),
+            (TxType::WithdrawToSelf, transaction_weights.withdrawal / 2.0),
+            (
+                TxType::WithdrawToOther,
+                transaction_weights.withdrawal / 2.0,
+            ),
+        ]
+    }
+
+    pub fn is_l1(self) -> bool {
+        matches!(self, Self::L1Execute | Self::Deposit)
     }
 }
 
@@ -161,7 +168,8 @@ impl TxCommand {
         Self::new_with_type(rng, own_address, addresses, command_type)
     }
 
-    fn new_with_type(
+    /// Generates a random transaction command of the specified type.
+    pub fn new_with_type(
         rng: &mut LoadtestRng,
         own_address: Address,
         addresses: &AddressPool,
@@ -181,7 +189,7 @@ impl TxCommand {
 
         // Fix incorrectness modifier:
         // L1 txs should always have `None` modifier.
-        if matches!(command.command_type, TxType::Deposit | TxType::L1Execute) {
+        if command.command_type.is_l1() {
             command.modifier = IncorrectnessModifier::None;
         }
 
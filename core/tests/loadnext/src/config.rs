@@ -136,6 +136,25 @@ pub struct LoadtestConfig {
     /// in an eventual test failure anyway (e.g., a failure processing transactions).
     #[serde(default)]
     pub fail_fast: bool,
+
+    /// Path to the JSON file with the load scenario. If set, the loadtest runs in the open-loop mode:
+    /// transactions are sent at the rate specified by the scenario phases rather than as fast as the server
+    /// processes them. The test duration is determined by the scenario, and `duration_sec` is ignored.
+    /// See `scenarios/` for examples.
+    #[serde(default)]
+    pub scenario_path: Option<PathBuf>,
+
+    /// Target arrival rate of transactions (per second) for the open-loop mode. Ignored if `scenario_path` is set;
+    /// otherwise, the loadtest sends transactions at this rate for `duration_sec` seconds.
+    /// If neither this value nor `scenario_path` are set, the loadtest runs in the closed-loop mode.
+    #[serde(default)]
+    pub target_tps: Option<f64>,
+
+    /// Time to keep waiting for L1 batches with transactions sent during the test to be committed on L1
+    /// after the test is finished. Transactions from batches not committed within this time are excluded
+    /// from the L1 commit latency statistics.
+    #[serde(default)]
+    pub l1_commit_wait_sec: u64,
 }
 
 fn default_max_inflight_txs() -> usize {
@@ -269,6 +288,10 @@ impl LoadtestConfig {
     pub fn duration(&self) -> Duration {
         Duration::from_secs(self.duration_sec)
     }
+
+    pub fn l1_commit_wait(&self) -> Duration {
+        Duration::from_secs(self.l1_commit_wait_sec)
+    }
 }
 
 /// Configuration for the weights of loadtest operations
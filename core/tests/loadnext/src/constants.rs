@@ -18,6 +18,10 @@ pub const POLLING_INTERVAL: Duration = Duration::from_secs(3);
 
 pub const MAX_OUTSTANDING_NONCE: usize = 20;
 
+/// In the open-loop mode, once the scenario is finished, accounts wait for this amount of time for their in-flight
+/// transactions to be included, so that latencies for these transactions are accounted for.
+pub const OPEN_LOOP_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
 /// Each account continuously sends API requests in addition to transactions. Such requests are considered failed
 /// after this amount of time elapsed without any server response.
 pub const API_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
@@ -8,7 +8,7 @@ use zksync::{
         get_approval_based_paymaster_input, get_approval_based_paymaster_input_for_estimation,
     },
     web3::types::TransactionReceipt,
-    EthNamespaceClient, EthereumProvider, ZksNamespaceClient,
+    EthNamespaceClient, EthereumProvider, HttpClientBuilder, ZksNamespaceClient,
 };
 use zksync_eth_client::{BoundEthInterface, EthInterface, Options};
 use zksync_eth_signer::PrivateKeySigner;
@@ -23,8 +23,10 @@ use crate::{
     account_pool::AccountPool,
     config::{ExecutionConfig, LoadtestConfig, RequestLimiters},
     constants::*,
+    l1_commit_tracker::L1CommitTracker,
     report::ReportBuilder,
     report_collector::{LoadtestResult, ReportCollector},
+    scenario::{Scenario, ScenarioDriver},
     utils::format_eth,
 };
 
@@ -37,11 +39,14 @@ use crate::{
 /// - Spawning the report collector.
 /// - Distributing the funds among the test wallets.
 /// - Spawning account lifespan futures.
+/// - Spawning the scenario driver in the open-loop mode.
 /// - Awaiting for all the account futures to complete.
 /// - Getting the final test resolution from the report collector.
 pub struct Executor {
     config: LoadtestConfig,
     execution_config: ExecutionConfig,
+    /// Load scenario; `None` if the test runs in the closed-loop mode.
+    scenario: Option<Scenario>,
     l2_main_token: Address,
     pool: AccountPool,
 }
@@ -49,9 +54,18 @@ pub struct Executor {
 impl Executor {
     /// Creates a new Executor entity.
     pub async fn new(
-        config: LoadtestConfig,
+        mut config: LoadtestConfig,
         execution_config: ExecutionConfig,
     ) -> anyhow::Result<Self> {
+        let scenario = Scenario::from_config(&config)?;
+        if let Some(scenario) = &scenario {
+            config.duration_sec = scenario.duration().as_secs();
+            tracing::info!(
+                "Running in open-loop mode with {} scenario phases; test duration: {}s",
+                scenario.phases.len(),
+                config.duration_sec
+            );
+        }
         let pool = AccountPool::new(&config).await?;
 
         // derive L2 main token address
@@ -67,6 +81,7 @@ impl Executor {
         Ok(Self {
             config,
             execution_config,
+            scenario,
             pool,
             l2_main_token,
         })
@@ -518,6 +533,25 @@ impl Executor {
         );
         let report_collector_future = tokio::spawn(report_collector.run());
 
+        let client = HttpClientBuilder::default().build(&self.config.l2_rpc_address)?;
+        let (l1_commit_tracker, l1_commit_tracker_handle) =
+            L1CommitTracker::new(client, report_sender.clone(), self.config.l1_commit_wait());
+        let l1_commit_tracker_future = tokio::spawn(l1_commit_tracker.run());
+
+        let backlog_capacity = self.config.accounts_amount * self.config.max_inflight_txs;
+        let (scenario_driver, arrivals) = match self.scenario.take() {
+            Some(scenario) => {
+                let (driver, arrivals) = ScenarioDriver::new(
+                    scenario,
+                    &self.execution_config,
+                    self.pool.rng.clone(),
+                    backlog_capacity,
+                );
+                (Some(driver), Some(arrivals))
+            }
+            None => (None, None),
+        };
+
         let config = &self.config;
         let accounts_amount = config.accounts_amount;
         let addresses = self.pool.addresses.clone();
@@ -568,7 +602,7 @@ impl Executor {
                     .accounts
                     .drain(..accounts_to_process)
                     .map(|wallet| {
-                        let account = AccountLifespan::new(
+                        let mut account = AccountLifespan::new(
                             config,
                             contract_execution_params.clone(),
                             addresses.clone(),
@@ -576,7 +610,11 @@ impl Executor {
                             report_sender.clone(),
                             main_token,
                             paymaster_address,
-                        );
+                        )
+                        .with_l1_commit_tracker(l1_commit_tracker_handle.clone());
+                        if let Some(arrivals) = &arrivals {
+                            account = account.with_arrivals(arrivals.clone());
+                        }
                         let limiters = Arc::clone(&limiters);
                         tokio::spawn(async move { account.run(&limiters).await })
                     });
@@ -589,6 +627,16 @@ impl Executor {
             .map_err(|_| anyhow!("test aborted; see reporter logs for details"))?;
         drop(report_sender);
         // ^ to terminate `report_collector_future` once all `account_futures` are finished
+        drop(l1_commit_tracker_handle);
+        // ^ to terminate `l1_commit_tracker_future` once all `account_futures` are finished
+        drop(arrivals);
+
+        if let Some(driver) = scenario_driver {
+            let config = self.config.clone();
+            account_tasks.push(tokio::spawn(async move {
+                driver.run(&config, &limiters).await;
+            }));
+        }
 
         assert!(
             self.pool.accounts.is_empty(),
@@ -599,6 +647,7 @@ impl Executor {
         tracing::info!("Waiting for the account futures to be completed...");
         future::try_join_all(account_tasks).await?;
         tracing::info!("All the spawned tasks are completed");
+        l1_commit_tracker_future.await?;
 
         Ok(report_collector_future.await?)
     }
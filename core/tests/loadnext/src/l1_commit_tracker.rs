@@ -0,0 +1,127 @@
+use std::{
+    collections::BTreeMap,
+    time::{Duration, SystemTime},
+};
+
+use futures::{channel::mpsc as futures_mpsc, SinkExt};
+use tokio::{sync::mpsc, time::Instant};
+use zksync::{HttpClient, ZksNamespaceClient};
+use zksync_types::L1BatchNumber;
+
+use crate::{
+    constants::ETH_POLLING_INTERVAL,
+    report::{LatencyStage, Report, ReportBuilder},
+};
+
+/// Handle used by the accounts to register transactions included into L1 batches.
+#[derive(Debug, Clone)]
+pub struct L1CommitTrackerHandle(mpsc::UnboundedSender<(L1BatchNumber, SystemTime)>);
+
+impl L1CommitTrackerHandle {
+    /// Registers a transaction submitted at `submitted_at` and included into the specified L1 batch.
+    pub fn track(&self, l1_batch_number: L1BatchNumber, submitted_at: SystemTime) {
+        // The tracker may be already stopped if the test is aborted; it's fine to ignore the error in this case.
+        self.0.send((l1_batch_number, submitted_at)).ok();
+    }
+}
+
+/// Tracks L1 batches with transactions sent by the test accounts, and reports latencies from transaction
+/// submission to the commitment of the corresponding L1 batch on L1.
+///
+/// The tracker stops once all handles are dropped and either all tracked batches are committed,
+/// or the configured wait time has elapsed.
+#[derive(Debug)]
+pub struct L1CommitTracker {
+    client: HttpClient,
+    receiver: mpsc::UnboundedReceiver<(L1BatchNumber, SystemTime)>,
+    report_sink: futures_mpsc::Sender<Report>,
+    wait_after_test: Duration,
+    /// Submission times of the included transactions that are not committed on L1 yet, grouped by L1 batch.
+    pending: BTreeMap<L1BatchNumber, Vec<SystemTime>>,
+}
+
+impl L1CommitTracker {
+    pub fn new(
+        client: HttpClient,
+        report_sink: futures_mpsc::Sender<Report>,
+        wait_after_test: Duration,
+    ) -> (Self, L1CommitTrackerHandle) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let this = Self {
+            client,
+            receiver,
+            report_sink,
+            wait_after_test,
+            pending: BTreeMap::new(),
+        };
+        (this, L1CommitTrackerHandle(sender))
+    }
+
+    pub async fn run(mut self) {
+        let mut timer = tokio::time::interval(ETH_POLLING_INTERVAL);
+        let mut deadline = None;
+        loop {
+            tokio::select! {
+                entry = self.receiver.recv(), if deadline.is_none() => {
+                    if let Some((l1_batch_number, submitted_at)) = entry {
+                        self.pending.entry(l1_batch_number).or_default().push(submitted_at);
+                    } else {
+                        // All accounts are finished.
+                        deadline = Some(Instant::now() + self.wait_after_test);
+                    }
+                }
+                _ = timer.tick() => {
+                    if !self.report_committed_batches().await {
+                        tracing::info!("Test aborted; stopping L1 commit tracking");
+                        return;
+                    }
+                    if deadline.map_or(false, |deadline| {
+                        self.pending.is_empty() || Instant::now() >= deadline
+                    }) {
+                        break;
+                    }
+                }
+            }
+        }
+
+        let uncommitted_count: usize = self.pending.values().map(Vec::len).sum();
+        if uncommitted_count > 0 {
+            tracing::info!(
+                "{uncommitted_count} included transactions were not committed on L1 before the end of the test; \
+                 they are excluded from L1 commit latency stats"
+            );
+        }
+    }
+
+    /// Reports latencies for transactions in committed batches. Returns `false` if the test is aborted.
+    async fn report_committed_batches(&mut self) -> bool {
+        while let Some((&l1_batch_number, _)) = self.pending.first_key_value() {
+            let details = match self.client.get_l1_batch_details(l1_batch_number).await {
+                Ok(details) => details,
+                Err(err) => {
+                    tracing::warn!("Failed getting details for L1 batch #{l1_batch_number}: {err}");
+                    break;
+                }
+            };
+            // Batches are committed in order, so there's no need to check the following batches.
+            let Some(committed_at) = details.and_then(|details| details.base.committed_at) else {
+                break;
+            };
+            let committed_at = SystemTime::from(committed_at);
+
+            for submitted_at in self.pending.remove(&l1_batch_number).unwrap() {
+                let latency = committed_at
+                    .duration_since(submitted_at)
+                    .unwrap_or_default();
+                let report = ReportBuilder::default()
+                    .action(LatencyStage::L1Commit)
+                    .time(latency)
+                    .finish();
+                if self.report_sink.send(report).await.is_err() {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
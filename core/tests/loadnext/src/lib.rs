@@ -9,7 +9,10 @@ pub mod constants;
 pub mod corrupted_tx;
 pub mod executor;
 pub mod fs_utils;
+pub mod l1_commit_tracker;
 pub mod report;
 pub mod report_collector;
 pub mod rng;
+pub mod scenario;
+pub mod trace;
 pub mod utils;
@@ -181,6 +181,21 @@ impl From<ApiRequest> for ApiActionType {
     }
 }
 
+/// Stage of the transaction lifecycle for which the latency since the transaction submission is measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum LatencyStage {
+    /// Transaction is included into a sealed L2 block.
+    Inclusion,
+    /// L1 batch containing the transaction is committed on L1.
+    L1Commit,
+}
+
+impl All for LatencyStage {
+    fn all() -> &'static [Self] {
+        &[Self::Inclusion, Self::L1Commit]
+    }
+}
+
 /// Generic wrapper of all the actions that can be done in loadtest.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ActionType {
@@ -188,6 +203,8 @@ pub enum ActionType {
     Tx(TxActionType),
     Api(ApiActionType),
     Subscription(SubscriptionType),
+    /// Not an action per se; reports the latency of a transaction reaching a certain stage.
+    Latency(LatencyStage),
 }
 
 impl From<TxActionType> for ActionType {
@@ -208,6 +225,12 @@ impl From<TxCommand> for ActionType {
     }
 }
 
+impl From<LatencyStage> for ActionType {
+    fn from(stage: LatencyStage) -> Self {
+        Self::Latency(stage)
+    }
+}
+
 impl From<SubscriptionType> for ActionType {
     fn from(subscription_type: SubscriptionType) -> Self {
         Self::Subscription(subscription_type)
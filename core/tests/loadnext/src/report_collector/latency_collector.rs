@@ -0,0 +1,106 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use crate::{all::All, report::LatencyStage};
+
+/// Percentiles reported for each latency stage.
+const REPORTED_PERCENTILES: [u64; 4] = [50, 90, 95, 99];
+
+/// Collector for the transaction latencies.
+///
+/// Unlike [`MetricsCollector`](super::metrics_collector::MetricsCollector), it stores all observed values, so that
+/// the reported percentiles are exact. This is feasible since there is at most one entry per stage for each
+/// transaction, and latencies to L1 commit are expected to be spread over minutes, which makes fixed histogram windows
+/// impractical.
+#[derive(Debug, Default)]
+pub struct LatencyCollector {
+    latencies: BTreeMap<LatencyStage, Vec<Duration>>,
+}
+
+impl LatencyCollector {
+    pub fn add_latency(&mut self, stage: LatencyStage, latency: Duration) {
+        self.latencies.entry(stage).or_default().push(latency);
+    }
+
+    /// Returns the latency for the requested percentile using the nearest-rank method,
+    /// or `None` if no latencies were observed for the stage.
+    pub fn percentile(&mut self, stage: LatencyStage, percentile: u64) -> Option<Duration> {
+        debug_assert!(percentile <= 100);
+        let latencies = self.latencies.get_mut(&stage)?;
+        if latencies.is_empty() {
+            return None;
+        }
+        latencies.sort_unstable();
+
+        let rank = (latencies.len() as u64 * percentile + 99) / 100;
+        let index = (rank as usize).saturating_sub(1);
+        Some(latencies[index])
+    }
+
+    pub fn report(&mut self, prometheus_label: &str) {
+        for &stage in LatencyStage::all() {
+            let count = self.latencies.get(&stage).map_or(0, Vec::len);
+            if count == 0 {
+                tracing::info!("{stage:?} latency: no transactions reached this stage");
+                continue;
+            }
+
+            let mut percentiles = Vec::with_capacity(REPORTED_PERCENTILES.len() + 1);
+            for percentile in REPORTED_PERCENTILES {
+                let latency = self.percentile(stage, percentile).unwrap();
+                metrics::gauge!(
+                    "loadtest.tx_latency",
+                    latency.as_secs_f64(),
+                    "label" => prometheus_label.to_owned(),
+                    "stage" => format!("{stage:?}"),
+                    "percentile" => percentile.to_string(),
+                );
+                percentiles.push(format!("p{percentile}={latency:?}"));
+            }
+            let max_latency = self.percentile(stage, 100).unwrap();
+            percentiles.push(format!("max={max_latency:?}"));
+
+            tracing::info!(
+                "{stage:?} latency ({count} transactions): {}",
+                percentiles.join(", ")
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latency_percentiles() {
+        let mut collector = LatencyCollector::default();
+        assert_eq!(collector.percentile(LatencyStage::Inclusion, 50), None);
+
+        // Add latencies 1..=100 ms in the reverse order, so that they need to be sorted.
+        for millis in (1..=100).rev() {
+            collector.add_latency(LatencyStage::Inclusion, Duration::from_millis(millis));
+        }
+        let expected = [(0, 1), (1, 1), (50, 50), (90, 90), (99, 99), (100, 100)];
+        for (percentile, expected_millis) in expected {
+            assert_eq!(
+                collector.percentile(LatencyStage::Inclusion, percentile),
+                Some(Duration::from_millis(expected_millis)),
+                "percentile {percentile}"
+            );
+        }
+        assert_eq!(collector.percentile(LatencyStage::L1Commit, 50), None);
+    }
+
+    #[test]
+    fn latency_percentiles_for_small_sample() {
+        let mut collector = LatencyCollector::default();
+        collector.add_latency(LatencyStage::L1Commit, Duration::from_secs(30));
+        collector.add_latency(LatencyStage::L1Commit, Duration::from_secs(10));
+        collector.add_latency(LatencyStage::L1Commit, Duration::from_secs(20));
+
+        let p50 = collector.percentile(LatencyStage::L1Commit, 50);
+        assert_eq!(p50, Some(Duration::from_secs(20)));
+        let p99 = collector.percentile(LatencyStage::L1Commit, 99);
+        assert_eq!(p99, Some(Duration::from_secs(30)));
+    }
+}
@@ -5,9 +5,10 @@ use operation_results_collector::OperationResultsCollector;
 
 use crate::{
     report::{ActionType, Report, ReportLabel},
-    report_collector::metrics_collector::MetricsCollector,
+    report_collector::{latency_collector::LatencyCollector, metrics_collector::MetricsCollector},
 };
 
+mod latency_collector;
 mod metrics_collector;
 mod operation_results_collector;
 
@@ -23,6 +24,7 @@ struct Collectors {
     start: Instant,
     is_aborted: bool,
     metrics: MetricsCollector,
+    latencies: LatencyCollector,
     operation_results: OperationResultsCollector,
 }
 
@@ -32,13 +34,15 @@ impl Collectors {
             start: Instant::now(),
             is_aborted: false,
             metrics: MetricsCollector::default(),
+            latencies: LatencyCollector::default(),
             operation_results: OperationResultsCollector::new(loadtest_duration),
         }
     }
 
-    fn report(&self, prometheus_label: String) {
+    fn report(&mut self, prometheus_label: String) {
         let actual_duration = self.start.elapsed();
         self.metrics.report();
+        self.latencies.report(&prometheus_label);
         if !self.is_aborted {
             metrics::gauge!(
                 "loadtest.tps",
@@ -80,6 +84,8 @@ impl Collectors {
 /// Currently, only the following collectors are used:
 ///
 /// - MetricsCollector, which builds time distribution histograms for each kind of performed action.
+/// - LatencyCollector, which computes percentiles of latencies from transaction submission to its inclusion
+///   and to the L1 commitment of its batch.
 /// - OperationResultsCollector, a primitive collector that counts the amount of failures and decides whether
 ///   test is passed.
 ///
@@ -138,6 +144,10 @@ impl ReportCollector {
             }
 
             if let Some(collectors) = &mut collectors {
+                if let ActionType::Latency(stage) = report.action {
+                    collectors.latencies.add_latency(stage, report.time);
+                    continue;
+                }
                 if matches!(&report.label, ReportLabel::ActionDone) {
                     // We only count successfully created statistics.
                     collectors.metrics.add_metric(report.action, report.time);
@@ -176,7 +186,7 @@ impl ReportCollector {
 
         // All the receivers are gone, it's likely the end of the test.
         // Now we can output the statistics.
-        if let Some(collectors) = collectors {
+        if let Some(mut collectors) = collectors {
             collectors.report(self.prometheus_label);
             collectors.final_resolution(self.expected_tx_count)
         } else {
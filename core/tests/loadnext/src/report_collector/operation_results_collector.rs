@@ -68,7 +68,7 @@ impl OperationResultsCollector {
             ActionType::Tx(_) => self.tx_results.add_status(status),
             ActionType::Api(_) => self.api_requests_results.add_status(status),
             ActionType::Subscription(_) => self.subscriptions_results.add_status(status),
-            ActionType::InitComplete => {}
+            ActionType::InitComplete | ActionType::Latency(_) => {}
         }
     }
 
@@ -0,0 +1,498 @@
+//! Open-loop traffic model.
+//!
+//! In the default (closed-loop) mode, each account sends transactions as fast as the server processes them,
+//! so the load depends on the server performance. In the open-loop mode, transactions arrive at the rate
+//! specified by a [`Scenario`] regardless of how fast they are processed; arrivals are distributed among
+//! the accounts that have capacity to send more transactions.
+
+use std::{
+    fs,
+    path::Path,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context as _;
+use rand::{seq::SliceRandom, Rng};
+use serde::Deserialize;
+use tokio::sync::{mpsc, Mutex};
+use zksync_contracts::test_contracts::LoadnextContractExecutionParams;
+
+use crate::{
+    command::TxType,
+    config::{ExecutionConfig, LoadtestConfig, RequestLimiters, TransactionWeights},
+    rng::LoadtestRng,
+};
+
+/// Interval after which the target arrival rate is re-evaluated if it's zero.
+const IDLE_RATE_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Declarative description of the load applied to the server in the open-loop mode.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Scenario {
+    /// Phases of the test executed one after another.
+    pub phases: Vec<Phase>,
+}
+
+/// A single phase of a [`Scenario`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct Phase {
+    /// Human-readable name of the phase used in logs.
+    pub name: String,
+    /// Duration of the phase.
+    pub duration_sec: u64,
+    /// Target arrival rate of transactions during the phase.
+    pub rate: RateProfile,
+    /// Mix of transaction types sent during the phase. If not specified, the mix from `ExecutionConfig` is used.
+    #[serde(default)]
+    pub transaction_weights: Option<TransactionWeights>,
+    /// Parameters of the `Execute` transactions sent during the phase. If not specified, the params
+    /// from `ExecutionConfig` are used.
+    #[serde(default)]
+    pub contract_execution_params: Option<LoadnextContractExecutionParams>,
+    /// Limit on the number of simultaneous API requests during the phase. Cannot exceed `sync_api_requests_limit`
+    /// from the main config, which is used if the limit is not specified.
+    #[serde(default)]
+    pub sync_api_requests_limit: Option<usize>,
+    /// Limit on the number of simultaneously active PubSub subscriptions during the phase. Cannot exceed
+    /// `sync_pubsub_subscriptions_limit` from the main config, which is used if the limit is not specified.
+    #[serde(default)]
+    pub sync_pubsub_subscriptions_limit: Option<usize>,
+}
+
+/// Profile of the target arrival rate within a [`Phase`].
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RateProfile {
+    /// Rate linearly changing from `from_tps` to `to_tps` over the phase duration. Used to ramp load up (or down).
+    Ramp { from_tps: f64, to_tps: f64 },
+    /// Constant rate.
+    Soak { tps: f64 },
+    /// Constant `base_tps` rate with a burst of `peak_tps` rate starting `spike_start_sec` after the phase start
+    /// and lasting for `spike_duration_sec`.
+    Spike {
+        base_tps: f64,
+        peak_tps: f64,
+        spike_start_sec: u64,
+        spike_duration_sec: u64,
+    },
+}
+
+impl RateProfile {
+    /// Returns the target arrival rate (transactions per second) after `elapsed` time since the phase start.
+    pub fn target_tps(&self, elapsed: Duration, phase_duration: Duration) -> f64 {
+        match *self {
+            Self::Ramp { from_tps, to_tps } => {
+                if phase_duration.is_zero() {
+                    return to_tps;
+                }
+                let progress = (elapsed.as_secs_f64() / phase_duration.as_secs_f64()).min(1.0);
+                from_tps + (to_tps - from_tps) * progress
+            }
+            Self::Soak { tps } => tps,
+            Self::Spike {
+                base_tps,
+                peak_tps,
+                spike_start_sec,
+                spike_duration_sec,
+            } => {
+                let spike_start = Duration::from_secs(spike_start_sec);
+                let spike_end = spike_start + Duration::from_secs(spike_duration_sec);
+                if (spike_start..spike_end).contains(&elapsed) {
+                    peak_tps
+                } else {
+                    base_tps
+                }
+            }
+        }
+    }
+
+    fn rates(&self) -> Vec<f64> {
+        match *self {
+            Self::Ramp { from_tps, to_tps } => vec![from_tps, to_tps],
+            Self::Soak { tps } => vec![tps],
+            Self::Spike {
+                base_tps, peak_tps, ..
+            } => vec![base_tps, peak_tps],
+        }
+    }
+}
+
+impl Phase {
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs(self.duration_sec)
+    }
+
+    fn validate(&self, config: &LoadtestConfig) -> anyhow::Result<()> {
+        anyhow::ensure!(self.duration_sec > 0, "phase duration must be positive");
+        for rate in self.rate.rates() {
+            anyhow::ensure!(
+                rate.is_finite() && rate >= 0.0,
+                "target rate must be a non-negative number, got {rate}"
+            );
+        }
+        if let RateProfile::Spike {
+            spike_start_sec,
+            spike_duration_sec,
+            ..
+        } = self.rate
+        {
+            anyhow::ensure!(
+                spike_start_sec + spike_duration_sec <= self.duration_sec,
+                "spike must end before the phase end"
+            );
+        }
+        if let Some(weights) = &self.transaction_weights {
+            let weights = TxType::weights(weights);
+            anyhow::ensure!(
+                weights.iter().all(|&(_, weight)| weight >= 0.0)
+                    && weights.iter().any(|&(_, weight)| weight > 0.0),
+                "transaction weights must be non-negative, with at least one positive weight"
+            );
+        }
+        if let Some(limit) = self.sync_api_requests_limit {
+            anyhow::ensure!(
+                limit <= config.sync_api_requests_limit,
+                "API requests limit ({limit}) exceeds the global limit ({})",
+                config.sync_api_requests_limit
+            );
+        }
+        if let Some(limit) = self.sync_pubsub_subscriptions_limit {
+            anyhow::ensure!(
+                limit <= config.sync_pubsub_subscriptions_limit,
+                "PubSub subscriptions limit ({limit}) exceeds the global limit ({})",
+                config.sync_pubsub_subscriptions_limit
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Scenario {
+    /// Loads the scenario as specified by the config: either from the scenario file, or as a single phase with
+    /// the constant `target_tps` rate. Returns `None` if the loadtest should run in the closed-loop mode.
+    pub fn from_config(config: &LoadtestConfig) -> anyhow::Result<Option<Self>> {
+        let scenario = if let Some(path) = &config.scenario_path {
+            Self::from_file(path)
+                .with_context(|| format!("failed loading scenario from `{}`", path.display()))?
+        } else if let Some(tps) = config.target_tps {
+            Self::constant(tps, config.duration())
+        } else {
+            return Ok(None);
+        };
+        scenario.validate(config)?;
+        Ok(Some(scenario))
+    }
+
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Creates a scenario with a single phase sending transactions at the constant rate.
+    pub fn constant(tps: f64, duration: Duration) -> Self {
+        Self {
+            phases: vec![Phase {
+                name: "soak".to_owned(),
+                duration_sec: duration.as_secs(),
+                rate: RateProfile::Soak { tps },
+                transaction_weights: None,
+                contract_execution_params: None,
+                sync_api_requests_limit: None,
+                sync_pubsub_subscriptions_limit: None,
+            }],
+        }
+    }
+
+    pub fn validate(&self, config: &LoadtestConfig) -> anyhow::Result<()> {
+        anyhow::ensure!(!self.phases.is_empty(), "scenario has no phases");
+        for phase in &self.phases {
+            phase
+                .validate(config)
+                .with_context(|| format!("invalid scenario phase `{}`", phase.name))?;
+        }
+        Ok(())
+    }
+
+    /// Returns the total duration of the scenario.
+    pub fn duration(&self) -> Duration {
+        self.phases.iter().map(Phase::duration).sum()
+    }
+}
+
+/// Transaction that should be sent by one of the accounts.
+#[derive(Debug, Clone)]
+pub struct TxArrival {
+    pub command_type: TxType,
+    pub contract_execution_params: LoadnextContractExecutionParams,
+    /// Moment at which the transaction is submitted according to the scenario. The transaction may actually be sent
+    /// later if all accounts are busy; latencies are measured from this moment.
+    pub scheduled_at: Instant,
+}
+
+/// Queue of arrived transactions shared among all the accounts.
+#[derive(Debug, Clone)]
+pub struct ArrivalQueue(Arc<Mutex<mpsc::Receiver<TxArrival>>>);
+
+impl ArrivalQueue {
+    /// Waits for the next transaction to send. Returns `None` if the scenario is finished.
+    pub async fn next(&self) -> Option<TxArrival> {
+        self.0.lock().await.recv().await
+    }
+}
+
+/// Statistics about a finished scenario phase.
+#[derive(Debug, Default)]
+struct PhaseStats {
+    scheduled: u64,
+    dropped: u64,
+}
+
+/// Generates transaction arrivals according to the scenario.
+#[derive(Debug)]
+pub struct ScenarioDriver {
+    scenario: Scenario,
+    default_weights: TransactionWeights,
+    default_execution_params: LoadnextContractExecutionParams,
+    rng: LoadtestRng,
+    sender: mpsc::Sender<TxArrival>,
+}
+
+impl ScenarioDriver {
+    /// Creates a driver and the queue of arrivals for it. Up to `backlog_capacity` arrivals can wait for
+    /// an available account; further arrivals are dropped.
+    pub fn new(
+        scenario: Scenario,
+        execution_config: &ExecutionConfig,
+        rng: LoadtestRng,
+        backlog_capacity: usize,
+    ) -> (Self, ArrivalQueue) {
+        let (sender, receiver) = mpsc::channel(backlog_capacity.max(1));
+        let this = Self {
+            scenario,
+            default_weights: execution_config.transaction_weights.clone(),
+            default_execution_params: execution_config.contract_execution_params.clone(),
+            rng,
+            sender,
+        };
+        (this, ArrivalQueue(Arc::new(Mutex::new(receiver))))
+    }
+
+    pub async fn run(mut self, config: &LoadtestConfig, limiters: &RequestLimiters) {
+        let phases = std::mem::take(&mut self.scenario.phases);
+        for phase in &phases {
+            let phase_start = Instant::now();
+            let phase_end = phase_start + phase.duration();
+            tracing::info!(
+                "Starting scenario phase `{}` ({:?}) for {:?}",
+                phase.name,
+                phase.rate,
+                phase.duration()
+            );
+
+            let restrict_api_load = restrict_api_load(config, limiters, phase, phase_end);
+            let ((), stats) = tokio::join!(
+                restrict_api_load,
+                self.generate_arrivals(phase, phase_start, phase_end)
+            );
+
+            let achieved_tps = stats.scheduled as f64 / phase.duration().as_secs_f64();
+            tracing::info!(
+                "Finished scenario phase `{}`: scheduled {} transactions ({achieved_tps:.2} TPS), \
+                 dropped {} transactions because all accounts were busy",
+                phase.name,
+                stats.scheduled,
+                stats.dropped
+            );
+            if self.sender.is_closed() {
+                tracing::warn!("All accounts have stopped; aborting the scenario");
+                break;
+            }
+        }
+    }
+
+    async fn generate_arrivals(
+        &mut self,
+        phase: &Phase,
+        phase_start: Instant,
+        phase_end: Instant,
+    ) -> PhaseStats {
+        let weights = TxType::weights(
+            phase
+                .transaction_weights
+                .as_ref()
+                .unwrap_or(&self.default_weights),
+        );
+        let execution_params = phase
+            .contract_execution_params
+            .as_ref()
+            .unwrap_or(&self.default_execution_params);
+
+        let mut stats = PhaseStats::default();
+        let mut next_arrival = phase_start;
+        loop {
+            let tps = phase
+                .rate
+                .target_tps(next_arrival - phase_start, phase.duration());
+            next_arrival += if tps > 0.0 {
+                // Arrivals form a Poisson process, so intervals between them are exponentially distributed.
+                // The interval is capped by the phase duration, since it would overflow `Duration` for a tiny `tps`.
+                let sample: f64 = self.rng.gen();
+                let interval_secs = -(1.0 - sample).ln() / tps;
+                Duration::from_secs_f64(interval_secs.min(phase.duration().as_secs_f64()))
+            } else {
+                IDLE_RATE_CHECK_INTERVAL
+            };
+            if next_arrival >= phase_end {
+                break;
+            }
+            if tps <= 0.0 {
+                continue;
+            }
+            tokio::time::sleep_until(next_arrival.into()).await;
+
+            let command_type = weights
+                .choose_weighted(&mut self.rng, |&(_, weight)| weight)
+                .expect("transaction weights are validated")
+                .0;
+            let arrival = TxArrival {
+                command_type,
+                contract_execution_params: execution_params.clone(),
+                scheduled_at: next_arrival,
+            };
+            match self.sender.try_send(arrival) {
+                Ok(()) => stats.scheduled += 1,
+                Err(mpsc::error::TrySendError::Full(_)) => stats.dropped += 1,
+                Err(mpsc::error::TrySendError::Closed(_)) => return stats,
+            }
+        }
+        tokio::time::sleep_until(phase_end.into()).await;
+        stats
+    }
+}
+
+/// Restricts the number of simultaneous API requests and subscriptions for the duration of the phase
+/// by reserving the corresponding number of permits from the global limiters.
+async fn restrict_api_load(
+    config: &LoadtestConfig,
+    limiters: &RequestLimiters,
+    phase: &Phase,
+    phase_end: Instant,
+) {
+    let reserved_api_requests = phase
+        .sync_api_requests_limit
+        .map_or(0, |limit| config.sync_api_requests_limit - limit);
+    let reserved_subscriptions = phase
+        .sync_pubsub_subscriptions_limit
+        .map_or(0, |limit| config.sync_pubsub_subscriptions_limit - limit);
+
+    // Permits may be held by the ongoing requests, so reserving them may take a while.
+    let reservation = tokio::time::timeout_at(phase_end.into(), async {
+        tokio::join!(
+            limiters
+                .api_requests
+                .acquire_many(reserved_api_requests as u32),
+            limiters
+                .subscriptions
+                .acquire_many(reserved_subscriptions as u32)
+        )
+    })
+    .await;
+    tokio::time::sleep_until(phase_end.into()).await;
+    drop(reservation);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_scenario() {
+        let scenario = r#"{
+            "phases": [
+                {
+                    "name": "ramp-up",
+                    "duration_sec": 60,
+                    "rate": { "kind": "ramp", "from_tps": 0, "to_tps": 100 }
+                },
+                {
+                    "name": "soak",
+                    "duration_sec": 600,
+                    "rate": { "kind": "soak", "tps": 100 },
+                    "transaction_weights": {
+                        "deposit": 0,
+                        "withdrawal": 0.1,
+                        "l1_transactions": 0,
+                        "l2_transactions": 1
+                    },
+                    "contract_execution_params": {
+                        "reads": 6,
+                        "writes": 2,
+                        "events": 2,
+                        "hashes": 10,
+                        "recursive_calls": 0,
+                        "deploys": 0
+                    },
+                    "sync_api_requests_limit": 10
+                },
+                {
+                    "name": "spike",
+                    "duration_sec": 120,
+                    "rate": {
+                        "kind": "spike",
+                        "base_tps": 100,
+                        "peak_tps": 500,
+                        "spike_start_sec": 30,
+                        "spike_duration_sec": 10
+                    }
+                }
+            ]
+        }"#;
+        let scenario: Scenario = serde_json::from_str(scenario).unwrap();
+
+        assert_eq!(scenario.phases.len(), 3);
+        assert_eq!(scenario.duration(), Duration::from_secs(780));
+        assert_eq!(
+            scenario.phases[0].rate,
+            RateProfile::Ramp {
+                from_tps: 0.0,
+                to_tps: 100.0
+            }
+        );
+        let soak = &scenario.phases[1];
+        assert_eq!(soak.rate, RateProfile::Soak { tps: 100.0 });
+        assert_eq!(soak.transaction_weights.as_ref().unwrap().deposit, 0.0);
+        assert_eq!(soak.contract_execution_params.as_ref().unwrap().hashes, 10);
+        assert_eq!(soak.sync_api_requests_limit, Some(10));
+        assert_eq!(soak.sync_pubsub_subscriptions_limit, None);
+        assert!(scenario.phases[2].transaction_weights.is_none());
+    }
+
+    #[test]
+    fn target_rate_for_ramp() {
+        let profile = RateProfile::Ramp {
+            from_tps: 10.0,
+            to_tps: 110.0,
+        };
+        let duration = Duration::from_secs(100);
+        assert_eq!(profile.target_tps(Duration::ZERO, duration), 10.0);
+        assert_eq!(profile.target_tps(Duration::from_secs(50), duration), 60.0);
+        assert_eq!(profile.target_tps(duration, duration), 110.0);
+        assert_eq!(profile.target_tps(duration * 2, duration), 110.0);
+    }
+
+    #[test]
+    fn target_rate_for_spike() {
+        let profile = RateProfile::Spike {
+            base_tps: 5.0,
+            peak_tps: 50.0,
+            spike_start_sec: 10,
+            spike_duration_sec: 5,
+        };
+        let duration = Duration::from_secs(30);
+        assert_eq!(profile.target_tps(Duration::from_secs(9), duration), 5.0);
+        assert_eq!(profile.target_tps(Duration::from_secs(10), duration), 50.0);
+        assert_eq!(profile.target_tps(Duration::from_secs(14), duration), 50.0);
+        assert_eq!(profile.target_tps(Duration::from_secs(15), duration), 5.0);
+    }
+}
@@ -0,0 +1,230 @@
+//! Recording and replaying of real transaction traces.
+//!
+//! Unlike the main loadtest flow, which sends synthetic transactions, traces allow to load the server with
+//! the transactions recorded from a real chain. [`TraceRecorder`] exports transactions from a range of miniblocks
+//! into a portable [`Trace`] file, and [`TraceReplayer`] re-signs and sends them to another (normally, local) chain
+//! comparing the outcome of each transaction with the recorded one.
+
+use std::{collections::HashMap, fs, path::Path};
+
+use anyhow::Context as _;
+use serde::{Deserialize, Serialize};
+use zksync_types::{api, Address, Bytes, MiniblockNumber, H256, U256};
+
+pub use self::{
+    recorder::TraceRecorder,
+    replayer::{ReplayReport, ReplayStatus, ReplayedTx, TraceReplayer},
+};
+
+mod recorder;
+mod replayer;
+
+/// Version of the trace file format. Should be incremented on incompatible changes.
+const TRACE_VERSION: u32 = 1;
+
+/// Portable trace of transactions from a range of miniblocks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Trace {
+    pub version: u32,
+    /// Chain ID of the chain the trace was recorded from.
+    pub l2_chain_id: u64,
+    /// First miniblock in the trace (inclusive).
+    pub first_miniblock: MiniblockNumber,
+    /// Last miniblock in the trace (inclusive).
+    pub last_miniblock: MiniblockNumber,
+    /// Contracts called by the traced transactions that were deployed before the first traced miniblock.
+    pub contracts: Vec<TracedContract>,
+    /// Transactions in the execution order.
+    pub transactions: Vec<TracedTx>,
+}
+
+impl Trace {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed reading trace from `{}`", path.display()))?;
+        let trace: Self = serde_json::from_str(&contents).context("failed parsing trace")?;
+        anyhow::ensure!(
+            trace.version == TRACE_VERSION,
+            "unsupported trace version {}; expected {TRACE_VERSION}",
+            trace.version
+        );
+        Ok(trace)
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let contents = serde_json::to_string_pretty(self)?;
+        fs::write(path, contents)
+            .with_context(|| format!("failed writing trace to `{}`", path.display()))
+    }
+}
+
+/// Contract deployed on the recorded chain before the traced range.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TracedContract {
+    pub address: Address,
+    pub bytecode: Bytes,
+}
+
+/// Kind of a traced transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TracedTxKind {
+    L2,
+    /// Priority operation from L1. Such transactions are recorded for completeness, but are not replayed.
+    L1,
+    /// Protocol upgrade transaction; not replayed.
+    ProtocolUpgrade,
+}
+
+/// Transaction recorded from the chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TracedTx {
+    pub hash: H256,
+    pub kind: TracedTxKind,
+    pub miniblock_number: MiniblockNumber,
+    /// Offset of the transaction from the start of the trace in milliseconds. Based on the time the transaction
+    /// was received by the server if available, and on the miniblock timestamp otherwise.
+    pub offset_ms: u64,
+    pub initiator: Address,
+    /// Nonce of the transaction; not set for L1 transactions.
+    pub nonce: Option<u32>,
+    pub contract_address: Address,
+    pub calldata: Bytes,
+    pub value: U256,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub factory_deps: Vec<Bytes>,
+    pub gas_limit: U256,
+    pub gas_per_pubdata_limit: U256,
+    /// Whether the transaction used a paymaster. Paymasters are not used during replay.
+    #[serde(default)]
+    pub used_paymaster: bool,
+    pub outcome: TxOutcome,
+}
+
+/// Observable outcome of a transaction used to compare the recorded and replayed executions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TxOutcome {
+    pub success: bool,
+    pub gas_used: U256,
+    pub logs_count: usize,
+    /// Address of the deployed contract, if any.
+    pub contract_address: Option<Address>,
+}
+
+impl From<&api::TransactionReceipt> for TxOutcome {
+    fn from(receipt: &api::TransactionReceipt) -> Self {
+        Self {
+            success: receipt.status.as_u64() == 1,
+            gas_used: receipt.gas_used.unwrap_or_default(),
+            logs_count: receipt.logs.len(),
+            contract_address: receipt.contract_address,
+        }
+    }
+}
+
+impl TxOutcome {
+    /// Returns human-readable differences between the expected and actual outcomes. Differences in gas usage
+    /// are not considered, since they may be caused by different chain state.
+    pub fn diff(&self, actual: &Self) -> Vec<String> {
+        let mut diff = vec![];
+        if self.success != actual.success {
+            diff.push(format!(
+                "success: expected {}, got {}",
+                self.success, actual.success
+            ));
+        }
+        if self.logs_count != actual.logs_count {
+            diff.push(format!(
+                "logs count: expected {}, got {}",
+                self.logs_count, actual.logs_count
+            ));
+        }
+        if self.contract_address.is_some() != actual.contract_address.is_some() {
+            diff.push(format!(
+                "contract deployment: expected {}, got {}",
+                self.contract_address.is_some(),
+                actual.contract_address.is_some()
+            ));
+        }
+        diff
+    }
+}
+
+/// Replaces ABI-encoded addresses in the calldata according to the provided mapping. Calldata is expected
+/// to consist of a 4-byte selector followed by 32-byte words; addresses are detected as words with 12 leading
+/// zero bytes, so the replacement is heuristic.
+pub fn remap_calldata_addresses(calldata: &mut [u8], mapping: &HashMap<Address, Address>) {
+    const SELECTOR_LEN: usize = 4;
+    const WORD_LEN: usize = 32;
+    const ADDRESS_OFFSET: usize = WORD_LEN - Address::len_bytes();
+
+    if mapping.is_empty() || calldata.len() < SELECTOR_LEN {
+        return;
+    }
+    for word in calldata[SELECTOR_LEN..].chunks_exact_mut(WORD_LEN) {
+        if word[..ADDRESS_OFFSET].iter().any(|&byte| byte != 0) {
+            continue;
+        }
+        let address = Address::from_slice(&word[ADDRESS_OFFSET..]);
+        if let Some(new_address) = mapping.get(&address) {
+            word[ADDRESS_OFFSET..].copy_from_slice(new_address.as_bytes());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_address(address: Address) -> [u8; 32] {
+        let mut word = [0_u8; 32];
+        word[12..].copy_from_slice(address.as_bytes());
+        word
+    }
+
+    #[test]
+    fn remapping_calldata_addresses() {
+        let original = Address::repeat_byte(0x11);
+        let remapped = Address::repeat_byte(0x22);
+        let unrelated = Address::repeat_byte(0x33);
+        let mapping = HashMap::from([(original, remapped)]);
+
+        let mut calldata = vec![0xa9, 0x05, 0x9c, 0xbb]; // `transfer(address,uint256)` selector
+        calldata.extend_from_slice(&encode_address(original));
+        calldata.extend_from_slice(&encode_address(unrelated));
+        let mut amount = [0xff_u8; 32];
+        amount[12..].copy_from_slice(original.as_bytes());
+        calldata.extend_from_slice(&amount);
+
+        remap_calldata_addresses(&mut calldata, &mapping);
+
+        let mut expected = vec![0xa9, 0x05, 0x9c, 0xbb];
+        expected.extend_from_slice(&encode_address(remapped));
+        expected.extend_from_slice(&encode_address(unrelated));
+        // The word has non-zero leading bytes, so it's not an address.
+        expected.extend_from_slice(&amount);
+        assert_eq!(calldata, expected);
+    }
+
+    #[test]
+    fn comparing_outcomes() {
+        let expected = TxOutcome {
+            success: true,
+            gas_used: 100_000.into(),
+            logs_count: 3,
+            contract_address: None,
+        };
+        let mut actual = TxOutcome {
+            gas_used: 120_000.into(),
+            ..expected.clone()
+        };
+        assert!(expected.diff(&actual).is_empty());
+
+        actual.success = false;
+        actual.logs_count = 1;
+        let diff = expected.diff(&actual);
+        assert_eq!(diff.len(), 2, "{diff:?}");
+        assert!(diff[0].starts_with("success"), "{diff:?}");
+        assert!(diff[1].starts_with("logs count"), "{diff:?}");
+    }
+}
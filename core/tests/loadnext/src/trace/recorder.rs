@@ -0,0 +1,204 @@
+use std::collections::{BTreeSet, HashSet};
+
+use anyhow::Context as _;
+use zksync::{EthNamespaceClient, HttpClient, ZksNamespaceClient};
+use zksync_types::{
+    api::{BlockIdVariant, BlockNumber},
+    Address, ExecuteTransactionCommon, MiniblockNumber, Transaction, H256,
+};
+
+use super::{Trace, TracedContract, TracedTx, TracedTxKind, TxOutcome, TRACE_VERSION};
+
+/// Exports transactions from a range of miniblocks into a [`Trace`] using the JSON-RPC API of the main node
+/// or an external node.
+#[derive(Debug)]
+pub struct TraceRecorder {
+    client: HttpClient,
+}
+
+impl TraceRecorder {
+    pub fn new(client: HttpClient) -> Self {
+        Self { client }
+    }
+
+    /// Records transactions from the specified range of miniblocks (both ends inclusive).
+    pub async fn record(
+        &self,
+        first_miniblock: MiniblockNumber,
+        last_miniblock: MiniblockNumber,
+    ) -> anyhow::Result<Trace> {
+        anyhow::ensure!(
+            first_miniblock <= last_miniblock,
+            "invalid miniblock range: {first_miniblock}..={last_miniblock}"
+        );
+        let l2_chain_id = self.client.chain_id().await?.as_u64();
+
+        let mut transactions = vec![];
+        let mut start_ms = None;
+        for number in first_miniblock.0..=last_miniblock.0 {
+            let number = MiniblockNumber(number);
+            let details = self
+                .client
+                .get_block_details(number)
+                .await?
+                .with_context(|| format!("miniblock #{number} is not sealed"))?;
+            let miniblock_timestamp_ms = details.base.timestamp * 1_000;
+            let start_ms = *start_ms.get_or_insert(miniblock_timestamp_ms);
+
+            let block_transactions = self.client.get_raw_block_transactions(number).await?;
+            tracing::debug!(
+                "Recording {} transactions from miniblock #{number}",
+                block_transactions.len()
+            );
+            for tx in block_transactions {
+                let outcome = self.tx_outcome(tx.hash()).await?;
+                let timestamp_ms = if tx.received_timestamp_ms > 0 {
+                    tx.received_timestamp_ms
+                } else {
+                    miniblock_timestamp_ms
+                };
+                let offset_ms = timestamp_ms.saturating_sub(start_ms);
+                transactions.push(TracedTx::new(tx, number, offset_ms, outcome));
+            }
+        }
+
+        let contracts = self
+            .record_contracts(first_miniblock, &transactions)
+            .await?;
+        tracing::info!(
+            "Recorded {} transactions and {} contracts from miniblocks {first_miniblock}..={last_miniblock}",
+            transactions.len(),
+            contracts.len()
+        );
+        Ok(Trace {
+            version: TRACE_VERSION,
+            l2_chain_id,
+            first_miniblock,
+            last_miniblock,
+            contracts,
+            transactions,
+        })
+    }
+
+    async fn tx_outcome(&self, tx_hash: H256) -> anyhow::Result<TxOutcome> {
+        let receipt = self
+            .client
+            .get_transaction_receipt(tx_hash)
+            .await?
+            .with_context(|| format!("no receipt for transaction {tx_hash:?}"))?;
+        Ok(TxOutcome::from(&receipt))
+    }
+
+    /// Records bytecodes of non-system contracts called by the traced transactions that were deployed
+    /// before the traced range.
+    async fn record_contracts(
+        &self,
+        first_miniblock: MiniblockNumber,
+        transactions: &[TracedTx],
+    ) -> anyhow::Result<Vec<TracedContract>> {
+        let deployed_in_trace: HashSet<_> = transactions
+            .iter()
+            .filter_map(|tx| tx.outcome.contract_address)
+            .collect();
+        let called_contracts: BTreeSet<_> = transactions
+            .iter()
+            .filter(|tx| tx.kind == TracedTxKind::L2)
+            .map(|tx| tx.contract_address)
+            .filter(|address| !is_system_address(*address) && !deployed_in_trace.contains(address))
+            .collect();
+
+        // Take the state before the first traced miniblock, so that contracts are recorded as they were
+        // at the start of the trace.
+        let state_block = first_miniblock.0.saturating_sub(1);
+        let block = BlockIdVariant::BlockNumber(BlockNumber::Number(state_block.into()));
+        let mut contracts = vec![];
+        for address in called_contracts {
+            let bytecode = self.client.get_code(address, Some(block)).await?;
+            // Accounts without code are EOAs.
+            if !bytecode.0.is_empty() {
+                contracts.push(TracedContract { address, bytecode });
+            }
+        }
+        Ok(contracts)
+    }
+}
+
+/// Addresses below 2^16 are reserved for system contracts, which exist on any chain and thus don't need to be recorded.
+fn is_system_address(address: Address) -> bool {
+    let bytes = address.as_bytes();
+    bytes[..Address::len_bytes() - 2]
+        .iter()
+        .all(|&byte| byte == 0)
+}
+
+impl TracedTx {
+    fn new(
+        tx: Transaction,
+        miniblock_number: MiniblockNumber,
+        offset_ms: u64,
+        outcome: TxOutcome,
+    ) -> Self {
+        let hash = tx.hash();
+        let nonce = tx.nonce().map(|nonce| nonce.0);
+        let (kind, initiator, gas_limit, gas_per_pubdata_limit, used_paymaster) =
+            match &tx.common_data {
+                ExecuteTransactionCommon::L2(data) => (
+                    TracedTxKind::L2,
+                    data.initiator_address,
+                    data.fee.gas_limit,
+                    data.fee.gas_per_pubdata_limit,
+                    data.paymaster_params.paymaster != Address::zero(),
+                ),
+                ExecuteTransactionCommon::L1(data) => (
+                    TracedTxKind::L1,
+                    data.sender,
+                    data.gas_limit,
+                    data.gas_per_pubdata_limit,
+                    false,
+                ),
+                ExecuteTransactionCommon::ProtocolUpgrade(data) => (
+                    TracedTxKind::ProtocolUpgrade,
+                    data.sender,
+                    data.gas_limit,
+                    data.gas_per_pubdata_limit,
+                    false,
+                ),
+            };
+        let execute = tx.execute;
+
+        Self {
+            hash,
+            kind,
+            miniblock_number,
+            offset_ms,
+            initiator,
+            nonce,
+            contract_address: execute.contract_address,
+            calldata: execute.calldata.into(),
+            value: execute.value,
+            factory_deps: execute
+                .factory_deps
+                .unwrap_or_default()
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            gas_limit,
+            gas_per_pubdata_limit,
+            used_paymaster,
+            outcome,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detecting_system_addresses() {
+        assert!(is_system_address(Address::zero()));
+        assert!(is_system_address(Address::from_low_u64_be(0x8006)));
+        assert!(!is_system_address(Address::from_low_u64_be(0x1_0000)));
+        assert!(!is_system_address(Address::repeat_byte(0x11)));
+    }
+}
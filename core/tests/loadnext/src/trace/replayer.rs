@@ -0,0 +1,492 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    convert::TryFrom,
+    str::FromStr,
+    time::Duration,
+};
+
+use anyhow::Context as _;
+use serde::Serialize;
+use tokio::time::Instant;
+use zksync::{
+    operations::SyncTransactionHandle, signer::Signer, EthNamespaceClient, HttpClient, Wallet,
+    ZksNamespaceClient,
+};
+use zksync_eth_signer::PrivateKeySigner;
+use zksync_types::{
+    fee::Fee, l2::L2Tx, tokens::ETHEREUM_ADDRESS, tx::primitives::PackedEthSignature, Address,
+    L2ChainId, Nonce, H256, U256,
+};
+
+use super::{remap_calldata_addresses, Trace, TracedTx, TracedTxKind, TxOutcome};
+use crate::{
+    account_pool::AccountCredentials,
+    config::LoadtestConfig,
+    constants::{COMMIT_TIMEOUT, POLLING_INTERVAL},
+    rng::{LoadtestRng, Random},
+};
+
+type ReplayWallet = Wallet<PrivateKeySigner, HttpClient>;
+
+/// Multiplier applied to the maximum fee of the traced transactions when funding replay accounts,
+/// so that accounts have enough funds even if fee estimates on the target chain are higher.
+const FEE_FUNDING_MULTIPLIER: u64 = 2;
+
+/// Outcome of replaying a single traced transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplayStatus {
+    /// Transaction was executed with the same observable outcome as the recorded one.
+    Matched,
+    /// Transaction was executed, but its outcome differs from the recorded one.
+    Mismatched,
+    /// Transaction was rejected by the server.
+    Rejected,
+    /// Transaction was accepted, but wasn't executed before the timeout.
+    NotExecuted,
+    /// Transaction wasn't replayed (e.g., because it's an L1 transaction).
+    Skipped,
+}
+
+/// Replay information for a single traced transaction.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayedTx {
+    pub original_hash: H256,
+    pub replayed_hash: Option<H256>,
+    pub status: ReplayStatus,
+    pub expected: TxOutcome,
+    pub actual: Option<TxOutcome>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub diff: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ReplayedTx {
+    fn new(tx: &TracedTx, status: ReplayStatus) -> Self {
+        Self {
+            original_hash: tx.hash,
+            replayed_hash: None,
+            status,
+            expected: tx.outcome.clone(),
+            actual: None,
+            diff: vec![],
+            error: None,
+        }
+    }
+
+    fn set_actual(&mut self, actual: TxOutcome) {
+        self.diff = self.expected.diff(&actual);
+        self.status = if self.diff.is_empty() {
+            ReplayStatus::Matched
+        } else {
+            ReplayStatus::Mismatched
+        };
+        self.actual = Some(actual);
+    }
+}
+
+/// Summary of a trace replay.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayReport {
+    pub matched: usize,
+    pub mismatched: usize,
+    pub rejected: usize,
+    pub not_executed: usize,
+    pub skipped: usize,
+    /// Total gas used by the executed transactions in the trace and during the replay, respectively.
+    /// Only transactions executed during the replay are accounted for.
+    pub expected_gas_used: U256,
+    pub actual_gas_used: U256,
+    pub transactions: Vec<ReplayedTx>,
+}
+
+impl ReplayReport {
+    fn new(transactions: Vec<ReplayedTx>) -> Self {
+        let count = |status| transactions.iter().filter(|tx| tx.status == status).count();
+        let (expected_gas_used, actual_gas_used) = transactions
+            .iter()
+            .filter_map(|tx| Some((tx.expected.gas_used, tx.actual.as_ref()?.gas_used)))
+            .fold((U256::zero(), U256::zero()), |(expected, actual), tx| {
+                (expected + tx.0, actual + tx.1)
+            });
+
+        Self {
+            matched: count(ReplayStatus::Matched),
+            mismatched: count(ReplayStatus::Mismatched),
+            rejected: count(ReplayStatus::Rejected),
+            not_executed: count(ReplayStatus::NotExecuted),
+            skipped: count(ReplayStatus::Skipped),
+            expected_gas_used,
+            actual_gas_used,
+            transactions,
+        }
+    }
+
+    /// Returns `true` if all replayed transactions have the same outcome as the recorded ones.
+    pub fn is_success(&self) -> bool {
+        self.mismatched == 0 && self.rejected == 0 && self.not_executed == 0
+    }
+
+    pub fn log_summary(&self) {
+        tracing::info!(
+            "Replayed {} transactions: {} matched, {} mismatched, {} rejected, {} not executed, {} skipped",
+            self.transactions.len(),
+            self.matched,
+            self.mismatched,
+            self.rejected,
+            self.not_executed,
+            self.skipped
+        );
+        tracing::info!(
+            "Gas used by executed transactions: {} recorded, {} replayed",
+            self.expected_gas_used,
+            self.actual_gas_used
+        );
+        for tx in &self.transactions {
+            match tx.status {
+                ReplayStatus::Mismatched => tracing::warn!(
+                    "Outcome mismatch for transaction {:?} (replayed as {:?}): {}",
+                    tx.original_hash,
+                    tx.replayed_hash.unwrap_or_default(),
+                    tx.diff.join("; ")
+                ),
+                ReplayStatus::Rejected => tracing::warn!(
+                    "Transaction {:?} was rejected: {}",
+                    tx.original_hash,
+                    tx.error.as_deref().unwrap_or("unknown error")
+                ),
+                _ => { /* do nothing */ }
+            }
+        }
+    }
+}
+
+/// Replays a [`Trace`] on a chain using generated test accounts.
+///
+/// Each initiator of an L2 transaction in the trace is mapped to a fresh account funded by the master wallet;
+/// contracts called by the trace are re-deployed from the recorded bytecodes. Addresses of initiators and contracts
+/// are remapped both in the transaction recipients and in the ABI-encoded calldata. Note that re-deployed contracts
+/// start with an empty storage, so transactions relying on the pre-existing contract state may have different outcomes.
+#[derive(Debug)]
+pub struct TraceReplayer {
+    config: LoadtestConfig,
+    /// Speed-up factor for the replay timing; e.g., 2.0 replays the trace twice as fast as it was recorded.
+    speed: f64,
+}
+
+impl TraceReplayer {
+    pub fn new(config: LoadtestConfig, speed: f64) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            speed.is_finite() && speed > 0.0,
+            "replay speed must be a positive number, got {speed}"
+        );
+        Ok(Self { config, speed })
+    }
+
+    pub async fn replay(&self, trace: &Trace) -> anyhow::Result<ReplayReport> {
+        let l2_chain_id = L2ChainId::try_from(self.config.l2_chain_id)
+            .map_err(|err| anyhow::anyhow!("invalid L2 chain ID: {err}"))?;
+        let master_wallet = {
+            let eth_pk = H256::from_str(&self.config.master_wallet_pk)
+                .context("cannot parse master wallet private key")?;
+            let address = PackedEthSignature::address_from_private_key(&eth_pk)
+                .context("cannot get address from the master wallet private key")?;
+            let signer = Signer::new(PrivateKeySigner::new(eth_pk), address, l2_chain_id);
+            Wallet::with_http_client(&self.config.l2_rpc_address, signer)?
+        };
+
+        let mut rng = LoadtestRng::new_generic(self.config.seed.clone());
+        tracing::info!("Using RNG with master seed: {}", rng.seed_hex());
+        let initiators: BTreeSet<_> = trace
+            .transactions
+            .iter()
+            .filter(|tx| tx.kind == TracedTxKind::L2)
+            .map(|tx| tx.initiator)
+            .collect();
+        let mut accounts = HashMap::with_capacity(initiators.len());
+        let mut address_mapping = HashMap::new();
+        for initiator in initiators {
+            let credentials = AccountCredentials::random(&mut rng);
+            let signer = Signer::new(
+                PrivateKeySigner::new(credentials.eth_pk),
+                credentials.address,
+                l2_chain_id,
+            );
+            let wallet = Wallet::with_http_client(&self.config.l2_rpc_address, signer)?;
+            address_mapping.insert(initiator, credentials.address);
+            accounts.insert(initiator, (wallet, Nonce(0)));
+        }
+
+        self.fund_accounts(&master_wallet, trace, &address_mapping)
+            .await?;
+        self.deploy_contracts(&master_wallet, trace, &mut address_mapping)
+            .await?;
+
+        tracing::info!(
+            "Replaying {} transactions from {} accounts with speed {}x",
+            trace.transactions.len(),
+            accounts.len(),
+            self.speed
+        );
+        let client = &master_wallet.provider;
+        let start = Instant::now();
+        let mut replayed = Vec::with_capacity(trace.transactions.len());
+        for tx in &trace.transactions {
+            if tx.kind != TracedTxKind::L2 {
+                replayed.push(ReplayedTx::new(tx, ReplayStatus::Skipped));
+                continue;
+            }
+            let offset = Duration::from_millis(tx.offset_ms).div_f64(self.speed);
+            tokio::time::sleep_until(start + offset).await;
+
+            let (wallet, nonce) = accounts.get_mut(&tx.initiator).unwrap();
+            let mut replayed_tx = ReplayedTx::new(tx, ReplayStatus::NotExecuted);
+            match Self::send_tx(wallet, *nonce, tx, &address_mapping).await {
+                Ok(hash) => {
+                    *nonce += 1;
+                    replayed_tx.replayed_hash = Some(hash);
+                }
+                Err(err) => {
+                    replayed_tx.status = ReplayStatus::Rejected;
+                    replayed_tx.error = Some(err.to_string());
+                }
+            }
+
+            // Subsequent transactions may call the deployed contract, so we need to know its address
+            // on the target chain before proceeding.
+            if let (Some(recorded_address), Some(hash)) =
+                (tx.outcome.contract_address, replayed_tx.replayed_hash)
+            {
+                let deadline = Instant::now() + COMMIT_TIMEOUT;
+                if let Some(outcome) = wait_for_outcome(client, hash, deadline).await? {
+                    if let Some(address) = outcome.contract_address {
+                        address_mapping.insert(recorded_address, address);
+                    }
+                    replayed_tx.set_actual(outcome);
+                }
+            }
+            replayed.push(replayed_tx);
+        }
+
+        tracing::info!("All transactions are sent; waiting for them to be executed");
+        let deadline = Instant::now() + COMMIT_TIMEOUT;
+        for replayed_tx in &mut replayed {
+            if replayed_tx.actual.is_some() {
+                continue;
+            }
+            if let Some(hash) = replayed_tx.replayed_hash {
+                if let Some(outcome) = wait_for_outcome(client, hash, deadline).await? {
+                    replayed_tx.set_actual(outcome);
+                }
+            }
+        }
+        Ok(ReplayReport::new(replayed))
+    }
+
+    /// Funds replay accounts with the values transferred by the traced transactions and fees for them.
+    async fn fund_accounts(
+        &self,
+        master_wallet: &ReplayWallet,
+        trace: &Trace,
+        address_mapping: &HashMap<Address, Address>,
+    ) -> anyhow::Result<()> {
+        let gas_price = master_wallet.provider.gas_price().await?;
+        let mut required_funds = HashMap::<_, U256>::new();
+        for tx in &trace.transactions {
+            if let Some(&account) = address_mapping.get(&tx.initiator) {
+                let max_fee = tx.gas_limit * gas_price * FEE_FUNDING_MULTIPLIER;
+                *required_funds.entry(account).or_default() += tx.value + max_fee;
+            }
+        }
+
+        tracing::info!("Funding {} replay accounts", required_funds.len());
+        let mut nonce = Nonce(master_wallet.get_nonce().await?);
+        let mut handles = Vec::with_capacity(required_funds.len());
+        for (account, amount) in required_funds {
+            let handle = master_wallet
+                .start_transfer()
+                .to(account)
+                .amount(amount)
+                .token(ETHEREUM_ADDRESS)
+                .nonce(nonce)
+                .send()
+                .await
+                .with_context(|| format!("failed funding replay account {account:?}"))?;
+            handles.push(handle);
+            nonce += 1;
+        }
+        for handle in handles {
+            check_committed(handle).await?;
+        }
+        Ok(())
+    }
+
+    /// Deploys contracts that existed before the trace start and adds their addresses to the mapping.
+    async fn deploy_contracts(
+        &self,
+        master_wallet: &ReplayWallet,
+        trace: &Trace,
+        address_mapping: &mut HashMap<Address, Address>,
+    ) -> anyhow::Result<()> {
+        tracing::info!("Deploying {} traced contracts", trace.contracts.len());
+        for contract in &trace.contracts {
+            let handle = master_wallet
+                .start_deploy_contract()
+                .bytecode(contract.bytecode.0.clone())
+                .constructor_calldata(vec![])
+                .send()
+                .await
+                .with_context(|| format!("failed deploying contract {:?}", contract.address))?;
+            let receipt = check_committed(handle).await?;
+            let address = receipt.contract_address.with_context(|| {
+                format!("no contract deployed for contract {:?}", contract.address)
+            })?;
+            address_mapping.insert(contract.address, address);
+        }
+        Ok(())
+    }
+
+    async fn send_tx(
+        wallet: &ReplayWallet,
+        nonce: Nonce,
+        tx: &TracedTx,
+        address_mapping: &HashMap<Address, Address>,
+    ) -> anyhow::Result<H256> {
+        let contract_address = address_mapping
+            .get(&tx.contract_address)
+            .copied()
+            .unwrap_or(tx.contract_address);
+        let mut calldata = tx.calldata.0.clone();
+        remap_calldata_addresses(&mut calldata, address_mapping);
+        let factory_deps = if tx.factory_deps.is_empty() {
+            None
+        } else {
+            Some(tx.factory_deps.iter().map(|dep| dep.0.clone()).collect())
+        };
+
+        let mut l2_tx = L2Tx::new(
+            contract_address,
+            calldata,
+            nonce,
+            Fee::default(),
+            wallet.address(),
+            tx.value,
+            factory_deps,
+            Default::default(),
+        );
+        l2_tx.common_data.fee = match wallet.provider.estimate_fee(l2_tx.clone().into()).await {
+            Ok(fee) => fee,
+            Err(err) => {
+                // Transactions that failed in the trace are expected to fail estimation as well,
+                // so we fall back to the recorded gas limit.
+                tracing::debug!(
+                    "Failed estimating fee for transaction {:?}: {err}; using the recorded gas limit",
+                    tx.hash
+                );
+                let gas_price = wallet.provider.gas_price().await?;
+                Fee {
+                    gas_limit: tx.gas_limit,
+                    max_fee_per_gas: gas_price,
+                    max_priority_fee_per_gas: gas_price,
+                    gas_per_pubdata_limit: tx.gas_per_pubdata_limit,
+                }
+            }
+        };
+        let handle = wallet.send_transaction(l2_tx).await?;
+        Ok(handle.hash())
+    }
+}
+
+async fn check_committed(
+    handle: SyncTransactionHandle<'_, HttpClient>,
+) -> anyhow::Result<zksync::web3::types::TransactionReceipt> {
+    let tx_hash = handle.hash();
+    let receipt = handle
+        .commit_timeout(COMMIT_TIMEOUT)
+        .wait_for_commit()
+        .await?;
+    anyhow::ensure!(
+        receipt.status == Some(1.into()),
+        "transaction {tx_hash:?} has failed"
+    );
+    Ok(receipt)
+}
+
+/// Waits for the transaction to be executed; returns `None` if it's not executed before the deadline.
+async fn wait_for_outcome(
+    client: &HttpClient,
+    tx_hash: H256,
+    deadline: Instant,
+) -> anyhow::Result<Option<TxOutcome>> {
+    loop {
+        if let Some(receipt) = client.get_transaction_receipt(tx_hash).await? {
+            return Ok(Some(TxOutcome::from(&receipt)));
+        }
+        if Instant::now() >= deadline {
+            tracing::warn!("Transaction {tx_hash:?} was not executed before the deadline");
+            return Ok(None);
+        }
+        tokio::time::sleep(POLLING_INTERVAL).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::MiniblockNumber;
+
+    use super::*;
+
+    fn traced_tx(kind: TracedTxKind, outcome: TxOutcome) -> TracedTx {
+        TracedTx {
+            hash: H256::repeat_byte(1),
+            kind,
+            miniblock_number: MiniblockNumber(1),
+            offset_ms: 0,
+            initiator: Address::repeat_byte(1),
+            nonce: None,
+            contract_address: Address::repeat_byte(2),
+            calldata: vec![].into(),
+            value: U256::zero(),
+            factory_deps: vec![],
+            gas_limit: 1_000_000.into(),
+            gas_per_pubdata_limit: 800.into(),
+            used_paymaster: false,
+            outcome,
+        }
+    }
+
+    #[test]
+    fn building_replay_report() {
+        let outcome = TxOutcome {
+            success: true,
+            gas_used: 100.into(),
+            logs_count: 2,
+            contract_address: None,
+        };
+        let tx = traced_tx(TracedTxKind::L2, outcome.clone());
+
+        let mut matched = ReplayedTx::new(&tx, ReplayStatus::NotExecuted);
+        matched.set_actual(TxOutcome {
+            gas_used: 120.into(),
+            ..outcome.clone()
+        });
+        let mut mismatched = ReplayedTx::new(&tx, ReplayStatus::NotExecuted);
+        mismatched.set_actual(TxOutcome {
+            success: false,
+            ..outcome.clone()
+        });
+        let not_executed = ReplayedTx::new(&tx, ReplayStatus::NotExecuted);
+        let skipped = ReplayedTx::new(&traced_tx(TracedTxKind::L1, outcome), ReplayStatus::Skipped);
+
+        let report = ReplayReport::new(vec![matched, mismatched, not_executed, skipped]);
+        assert_eq!(report.matched, 1);
+        assert_eq!(report.mismatched, 1);
+        assert_eq!(report.not_executed, 1);
+        assert_eq!(report.skipped, 1);
+        assert_eq!(report.rejected, 0);
+        assert_eq!(report.expected_gas_used, 200.into());
+        assert_eq!(report.actual_gas_used, 220.into());
+        assert!(!report.is_success());
+    }
+}
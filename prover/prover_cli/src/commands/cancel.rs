@@ -0,0 +1,77 @@
+use anyhow::Context as _;
+use clap::Args;
+use prover_dal::ProverConnectionPool;
+use serde::Serialize;
+use zksync_types::L1BatchNumber;
+
+use super::print_json;
+
+#[derive(Debug, Args)]
+pub(crate) struct CancelArgs {
+    /// L1 batch number to cancel proving for.
+    #[arg(short = 'n', long = "batch")]
+    batch: u32,
+}
+
+/// Number of jobs affected by cancellation.
+#[derive(Debug, Serialize)]
+struct CancelReport {
+    l1_batch_number: u32,
+    skipped_prover_jobs: u64,
+    skipped_witness_jobs: u64,
+    deleted_proof_compression_jobs: u64,
+}
+
+pub(crate) async fn run(
+    args: CancelArgs,
+    pool: &ProverConnectionPool,
+    json: bool,
+) -> anyhow::Result<()> {
+    let l1_batch_number = L1BatchNumber(args.batch);
+    let mut storage = pool.access_storage().await?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .context("failed to start transaction")?;
+
+    let skipped_prover_jobs = transaction
+        .fri_prover_jobs_dal()
+        .skip_jobs_for_l1_batch(l1_batch_number)
+        .await;
+    let skipped_witness_jobs = transaction
+        .fri_witness_generator_dal()
+        .skip_witness_jobs_for_l1_batch(l1_batch_number)
+        .await;
+    // Prevents the scheduler witness job from being queued once the remaining node proofs are ready.
+    transaction
+        .fri_scheduler_dependency_tracker_dal()
+        .skip_l1_batch(l1_batch_number)
+        .await;
+    let deleted_proof_compression_jobs = transaction
+        .fri_proof_compressor_dal()
+        .delete_proof_compression_job_for_l1_batch(l1_batch_number)
+        .await;
+    transaction
+        .commit()
+        .await
+        .context("failed to commit transaction")?;
+
+    let report = CancelReport {
+        l1_batch_number: l1_batch_number.0,
+        skipped_prover_jobs,
+        skipped_witness_jobs,
+        deleted_proof_compression_jobs,
+    };
+    if json {
+        print_json(&report)?;
+    } else {
+        println!("Cancelled proving of L1 batch {}", report.l1_batch_number);
+        println!("├── skipped prover jobs: {}", report.skipped_prover_jobs);
+        println!("├── skipped witness jobs: {}", report.skipped_witness_jobs);
+        println!(
+            "└── deleted proof compression jobs: {}",
+            report.deleted_proof_compression_jobs
+        );
+    }
+    Ok(())
+}
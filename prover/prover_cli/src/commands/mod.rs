@@ -0,0 +1,151 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use zksync_types::basic_fri_types::AggregationRound;
+
+pub(crate) mod cancel;
+pub(crate) mod restart;
+pub(crate) mod status;
+
+/// All aggregation rounds in the order they are processed.
+const ROUNDS: [AggregationRound; 4] = [
+    AggregationRound::BasicCircuits,
+    AggregationRound::LeafAggregation,
+    AggregationRound::NodeAggregation,
+    AggregationRound::Scheduler,
+];
+
+/// Aggregation round as specified on the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum Round {
+    BasicCircuits,
+    LeafAggregation,
+    NodeAggregation,
+    Scheduler,
+}
+
+impl From<AggregationRound> for Round {
+    fn from(round: AggregationRound) -> Self {
+        match round {
+            AggregationRound::BasicCircuits => Self::BasicCircuits,
+            AggregationRound::LeafAggregation => Self::LeafAggregation,
+            AggregationRound::NodeAggregation => Self::NodeAggregation,
+            AggregationRound::Scheduler => Self::Scheduler,
+        }
+    }
+}
+
+impl From<Round> for AggregationRound {
+    fn from(round: Round) -> Self {
+        match round {
+            Round::BasicCircuits => Self::BasicCircuits,
+            Round::LeafAggregation => Self::LeafAggregation,
+            Round::NodeAggregation => Self::NodeAggregation,
+            Round::Scheduler => Self::Scheduler,
+        }
+    }
+}
+
+impl Round {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::BasicCircuits => "basic_circuits",
+            Self::LeafAggregation => "leaf_aggregation",
+            Self::NodeAggregation => "node_aggregation",
+            Self::Scheduler => "scheduler",
+        }
+    }
+}
+
+/// Number of jobs per status.
+pub(crate) type StatusCounts = BTreeMap<String, usize>;
+
+fn count_statuses<'a>(statuses: impl Iterator<Item = &'a str>) -> StatusCounts {
+    let mut counts = StatusCounts::new();
+    for status in statuses {
+        *counts.entry(status.to_owned()).or_default() += 1;
+    }
+    counts
+}
+
+/// Summarizes job counts into a single status.
+fn summarize(counts: &StatusCounts) -> &'static str {
+    let total: usize = counts.values().sum();
+    let count = |status: &str| counts.get(status).copied().unwrap_or(0);
+    if total == 0 {
+        "not_started"
+    } else if count("successful") + count("skipped") == total {
+        if count("skipped") == total {
+            "skipped"
+        } else {
+            "successful"
+        }
+    } else if count("failed") > 0 {
+        "failed"
+    } else if count("in_progress") + count("in_gpu_proof") + count("successful") > 0 {
+        "in_progress"
+    } else if count("queued") > 0 {
+        "queued"
+    } else {
+        "waiting_for_proofs"
+    }
+}
+
+fn format_counts(counts: &StatusCounts) -> String {
+    let counts: Vec<_> = counts
+        .iter()
+        .map(|(status, count)| format!("{status}: {count}"))
+        .collect();
+    counts.join(", ")
+}
+
+fn print_json(value: &impl Serialize) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(entries: &[(&str, usize)]) -> StatusCounts {
+        entries
+            .iter()
+            .map(|&(status, count)| (status.to_owned(), count))
+            .collect()
+    }
+
+    #[test]
+    fn summarizing_job_statuses() {
+        assert_eq!(summarize(&counts(&[])), "not_started");
+        assert_eq!(summarize(&counts(&[("successful", 3)])), "successful");
+        assert_eq!(
+            summarize(&counts(&[("successful", 3), ("skipped", 1)])),
+            "successful"
+        );
+        assert_eq!(summarize(&counts(&[("skipped", 2)])), "skipped");
+        assert_eq!(
+            summarize(&counts(&[("successful", 3), ("failed", 1), ("queued", 2)])),
+            "failed"
+        );
+        assert_eq!(
+            summarize(&counts(&[("successful", 3), ("queued", 2)])),
+            "in_progress"
+        );
+        assert_eq!(summarize(&counts(&[("queued", 2)])), "queued");
+        assert_eq!(
+            summarize(&counts(&[("waiting_for_proofs", 2)])),
+            "waiting_for_proofs"
+        );
+    }
+
+    #[test]
+    fn counting_statuses() {
+        let statuses = ["queued", "successful", "queued"];
+        assert_eq!(
+            count_statuses(statuses.into_iter()),
+            counts(&[("queued", 2), ("successful", 1)])
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use anyhow::Context as _;
+use clap::Args;
+use prover_dal::{ProverConnectionPool, ProverStorageProcessor};
+use serde::Serialize;
+use zksync_types::L1BatchNumber;
+
+use super::{print_json, Round, ROUNDS};
+
+#[derive(Debug, Args)]
+pub(crate) struct RestartArgs {
+    /// L1 batch number to restart.
+    #[arg(short = 'n', long = "batch")]
+    batch: u32,
+    /// Also requeue jobs that are currently in progress (e.g., because the component processing them has crashed).
+    #[arg(long, conflicts_with = "from_round")]
+    stuck: bool,
+    /// Moves the batch back to the specified aggregation round. All prover jobs starting from this round are removed,
+    /// and witness generation for the round is started anew.
+    #[arg(long, value_enum)]
+    from_round: Option<Round>,
+}
+
+/// Number of jobs affected by a restart.
+#[derive(Debug, Default, Serialize)]
+struct RestartReport {
+    l1_batch_number: u32,
+    from_round: Option<Round>,
+    requeued_prover_jobs: u64,
+    deleted_prover_jobs: u64,
+    requeued_witness_jobs: u64,
+    reset_witness_jobs: u64,
+    requeued_proof_compression_jobs: u64,
+    deleted_proof_compression_jobs: u64,
+}
+
+pub(crate) async fn run(
+    args: RestartArgs,
+    pool: &ProverConnectionPool,
+    json: bool,
+) -> anyhow::Result<()> {
+    let l1_batch_number = L1BatchNumber(args.batch);
+    let mut storage = pool.access_storage().await?;
+    let mut transaction = storage
+        .start_transaction()
+        .await
+        .context("failed to start transaction")?;
+    let report = match args.from_round {
+        Some(from_round) => restart_from_round(&mut transaction, l1_batch_number, from_round).await,
+        None => requeue_jobs(&mut transaction, l1_batch_number, args.stuck).await,
+    };
+    transaction
+        .commit()
+        .await
+        .context("failed to commit transaction")?;
+
+    if json {
+        print_json(&report)?;
+    } else {
+        report.print();
+    }
+    Ok(())
+}
+
+/// Requeues failed (and optionally stuck) jobs for the batch in all pipeline stages.
+async fn requeue_jobs(
+    storage: &mut ProverStorageProcessor<'_>,
+    l1_batch_number: L1BatchNumber,
+    stuck: bool,
+) -> RestartReport {
+    let mut statuses = vec!["failed".to_owned()];
+    if stuck {
+        statuses.extend(["in_progress".to_owned(), "in_gpu_proof".to_owned()]);
+    }
+
+    let mut report = RestartReport {
+        l1_batch_number: l1_batch_number.0,
+        ..RestartReport::default()
+    };
+    report.requeued_prover_jobs = storage
+        .fri_prover_jobs_dal()
+        .requeue_jobs_for_l1_batch(l1_batch_number, &statuses)
+        .await;
+    for aggregation_round in ROUNDS {
+        report.requeued_witness_jobs += storage
+            .fri_witness_generator_dal()
+            .requeue_witness_jobs_for_l1_batch(l1_batch_number, aggregation_round, &statuses)
+            .await;
+    }
+    report.requeued_proof_compression_jobs = storage
+        .fri_proof_compressor_dal()
+        .requeue_proof_compression_job_for_l1_batch(l1_batch_number, &statuses)
+        .await;
+    report
+}
+
+/// Moves the batch back to `from_round`: witness generation for the round is queued again, and all later stages
+/// wait for the proofs produced by it.
+async fn restart_from_round(
+    storage: &mut ProverStorageProcessor<'_>,
+    l1_batch_number: L1BatchNumber,
+    from_round: Round,
+) -> RestartReport {
+    let mut report = RestartReport {
+        l1_batch_number: l1_batch_number.0,
+        from_round: Some(from_round),
+        ..RestartReport::default()
+    };
+    report.deleted_prover_jobs = storage
+        .fri_prover_jobs_dal()
+        .delete_jobs_for_l1_batch(l1_batch_number, from_round.into())
+        .await;
+
+    for aggregation_round in ROUNDS {
+        let round = Round::from(aggregation_round);
+        let status = if round == from_round {
+            "queued"
+        } else if round > from_round {
+            "waiting_for_proofs"
+        } else {
+            continue;
+        };
+        report.reset_witness_jobs += storage
+            .fri_witness_generator_dal()
+            .reset_witness_jobs_for_l1_batch(l1_batch_number, aggregation_round, status)
+            .await;
+    }
+    // The scheduler waits for the final node aggregation proofs, which are removed above. Otherwise, the scheduler
+    // job is queued directly, so its dependencies are considered processed. Both branches clear the skipped status
+    // of the batch set by `cancel`.
+    if from_round <= Round::NodeAggregation {
+        storage
+            .fri_scheduler_dependency_tracker_dal()
+            .reset_final_prover_job_ids_for_l1_batch(l1_batch_number)
+            .await;
+    } else {
+        storage
+            .fri_scheduler_dependency_tracker_dal()
+            .mark_l1_batches_queued(vec![l1_batch_number.0.into()])
+            .await;
+    }
+    report.deleted_proof_compression_jobs = storage
+        .fri_proof_compressor_dal()
+        .delete_proof_compression_job_for_l1_batch(l1_batch_number)
+        .await;
+    report
+}
+
+impl RestartReport {
+    fn print(&self) {
+        match self.from_round {
+            Some(round) => {
+                println!(
+                    "Restarted L1 batch {} from round {}",
+                    self.l1_batch_number,
+                    round.as_str()
+                );
+                println!("├── deleted prover jobs: {}", self.deleted_prover_jobs);
+                println!("├── reset witness jobs: {}", self.reset_witness_jobs);
+                println!(
+                    "└── deleted proof compression jobs: {}",
+                    self.deleted_proof_compression_jobs
+                );
+            }
+            None => {
+                println!("Requeued jobs for L1 batch {}", self.l1_batch_number);
+                println!("├── prover jobs: {}", self.requeued_prover_jobs);
+                println!("├── witness jobs: {}", self.requeued_witness_jobs);
+                println!(
+                    "└── proof compression jobs: {}",
+                    self.requeued_proof_compression_jobs
+                );
+            }
+        }
+    }
+}
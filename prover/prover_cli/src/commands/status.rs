@@ -0,0 +1,356 @@
+use std::collections::BTreeMap;
+
+use clap::{Args, Subcommand};
+use prover_dal::{
+    fri_prover_dal::types::{ProofCompressionJobFriInfo, ProverJobFriInfo, WitnessJobFriInfo},
+    ProverConnectionPool, ProverStorageProcessor,
+};
+use serde::Serialize;
+use zksync_types::L1BatchNumber;
+
+use super::{count_statuses, format_counts, print_json, summarize, Round, StatusCounts, ROUNDS};
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum StatusCommand {
+    /// Shows the status tree of L1 batches across all aggregation rounds.
+    Batch(BatchArgs),
+    /// Shows job attempts and errors per circuit for an L1 batch.
+    Circuits(CircuitsArgs),
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct BatchArgs {
+    /// L1 batch numbers to show the status for.
+    #[arg(short = 'n', long = "batch", required = true, num_args = 1..)]
+    batches: Vec<u32>,
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct CircuitsArgs {
+    /// L1 batch number to show circuits for.
+    #[arg(short = 'n', long = "batch")]
+    batch: u32,
+    /// Only show circuits that have failed jobs or jobs with errors.
+    #[arg(long)]
+    errors_only: bool,
+}
+
+pub(crate) async fn run(
+    command: StatusCommand,
+    pool: &ProverConnectionPool,
+    json: bool,
+) -> anyhow::Result<()> {
+    let mut storage = pool.access_storage().await?;
+    match command {
+        StatusCommand::Batch(args) => {
+            let mut statuses = Vec::with_capacity(args.batches.len());
+            for batch in args.batches {
+                statuses.push(BatchStatus::load(&mut storage, L1BatchNumber(batch)).await);
+            }
+            if json {
+                print_json(&statuses)?;
+            } else {
+                for status in &statuses {
+                    status.print();
+                }
+            }
+        }
+        StatusCommand::Circuits(args) => {
+            let l1_batch_number = L1BatchNumber(args.batch);
+            let mut circuits = CircuitStatus::load(&mut storage, l1_batch_number).await;
+            if args.errors_only {
+                circuits.retain(|circuit| !circuit.errors.is_empty() || circuit.failed() > 0);
+            }
+            if json {
+                print_json(&circuits)?;
+            } else {
+                println!("L1 batch {}", l1_batch_number.0);
+                for circuit in &circuits {
+                    circuit.print();
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Status of an L1 batch across all aggregation rounds.
+#[derive(Debug, Serialize)]
+struct BatchStatus {
+    l1_batch_number: u32,
+    status: &'static str,
+    rounds: Vec<RoundStatus>,
+    proof_compression: Option<JobStatus>,
+}
+
+#[derive(Debug, Serialize)]
+struct RoundStatus {
+    round: Round,
+    witness_generation: StageStatus,
+    proving: StageStatus,
+}
+
+#[derive(Debug, Serialize)]
+struct StageStatus {
+    status: &'static str,
+    jobs: StatusCounts,
+}
+
+impl StageStatus {
+    fn new(jobs: StatusCounts) -> Self {
+        Self {
+            status: summarize(&jobs),
+            jobs,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct JobStatus {
+    status: String,
+    attempts: u32,
+    error: Option<String>,
+}
+
+impl From<ProofCompressionJobFriInfo> for JobStatus {
+    fn from(job: ProofCompressionJobFriInfo) -> Self {
+        Self {
+            status: job.status,
+            attempts: job.attempts,
+            error: job.error,
+        }
+    }
+}
+
+impl BatchStatus {
+    async fn load(
+        storage: &mut ProverStorageProcessor<'_>,
+        l1_batch_number: L1BatchNumber,
+    ) -> Self {
+        let prover_jobs = storage
+            .fri_prover_jobs_dal()
+            .get_prover_jobs_for_l1_batch(l1_batch_number)
+            .await;
+        let mut rounds = Vec::with_capacity(ROUNDS.len());
+        for aggregation_round in ROUNDS {
+            let witness_jobs = storage
+                .fri_witness_generator_dal()
+                .get_witness_jobs_for_l1_batch(l1_batch_number, aggregation_round)
+                .await;
+            let proving_statuses = prover_jobs
+                .iter()
+                .filter(|job| job.aggregation_round == aggregation_round)
+                .map(|job| job.status.as_str());
+            rounds.push(RoundStatus {
+                round: aggregation_round.into(),
+                witness_generation: StageStatus::new(count_statuses(
+                    witness_jobs.iter().map(|job| job.status.as_str()),
+                )),
+                proving: StageStatus::new(count_statuses(proving_statuses)),
+            });
+        }
+        let proof_compression = storage
+            .fri_proof_compressor_dal()
+            .get_proof_compression_job_for_l1_batch(l1_batch_number)
+            .await
+            .map(JobStatus::from);
+
+        Self {
+            l1_batch_number: l1_batch_number.0,
+            status: Self::overall_status(&rounds, proof_compression.as_ref()),
+            rounds,
+            proof_compression,
+        }
+    }
+
+    /// Returns the status of the first round that isn't completed, or of the proof compression if all rounds are.
+    fn overall_status(
+        rounds: &[RoundStatus],
+        proof_compression: Option<&JobStatus>,
+    ) -> &'static str {
+        let incomplete_stage = rounds
+            .iter()
+            .flat_map(|round| [&round.witness_generation, &round.proving])
+            .find(|stage| stage.status != "successful");
+        if let Some(stage) = incomplete_stage {
+            return stage.status;
+        }
+        match proof_compression.map(|job| job.status.as_str()) {
+            None => "waiting_for_compression",
+            Some("successful") => "compressed",
+            Some("sent_to_server") => "sent_to_server",
+            Some("failed") => "failed",
+            Some(_) => "in_progress",
+        }
+    }
+
+    fn print(&self) {
+        println!("L1 batch {}: {}", self.l1_batch_number, self.status);
+        for round in &self.rounds {
+            println!("├── {}", round.round.as_str());
+            Self::print_stage("│   ├──", "witness generation", &round.witness_generation);
+            Self::print_stage("│   └──", "proving", &round.proving);
+        }
+        match &self.proof_compression {
+            Some(job) => {
+                print!(
+                    "└── proof compression: {} (attempts: {})",
+                    job.status, job.attempts
+                );
+                match &job.error {
+                    Some(error) => println!(", error: {error}"),
+                    None => println!(),
+                }
+            }
+            None => println!("└── proof compression: not_started"),
+        }
+    }
+
+    fn print_stage(prefix: &str, name: &str, stage: &StageStatus) {
+        if stage.jobs.is_empty() {
+            println!("{prefix} {name}: {}", stage.status);
+        } else {
+            println!(
+                "{prefix} {name}: {} ({})",
+                stage.status,
+                format_counts(&stage.jobs)
+            );
+        }
+    }
+}
+
+/// Status of all jobs for a single circuit in an aggregation round.
+#[derive(Debug, Serialize)]
+struct CircuitStatus {
+    round: Round,
+    circuit_id: u8,
+    jobs: StatusCounts,
+    max_attempts: u32,
+    errors: Vec<JobError>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum JobKind {
+    WitnessGenerator,
+    Prover,
+}
+
+#[derive(Debug, Serialize)]
+struct JobError {
+    kind: JobKind,
+    /// ID of the prover job; not set for witness generator jobs.
+    job_id: Option<u32>,
+    depth: Option<u16>,
+    status: String,
+    attempts: u32,
+    picked_by: Option<String>,
+    error: String,
+}
+
+impl From<&ProverJobFriInfo> for JobError {
+    fn from(job: &ProverJobFriInfo) -> Self {
+        Self {
+            kind: JobKind::Prover,
+            job_id: Some(job.id),
+            depth: Some(job.depth),
+            status: job.status.clone(),
+            attempts: job.attempts,
+            picked_by: job.picked_by.clone(),
+            error: job.error.clone().unwrap_or_default(),
+        }
+    }
+}
+
+impl From<&WitnessJobFriInfo> for JobError {
+    fn from(job: &WitnessJobFriInfo) -> Self {
+        Self {
+            kind: JobKind::WitnessGenerator,
+            job_id: None,
+            depth: job.depth,
+            status: job.status.clone(),
+            attempts: job.attempts,
+            picked_by: job.picked_by.clone(),
+            error: job.error.clone().unwrap_or_default(),
+        }
+    }
+}
+
+impl CircuitStatus {
+    async fn load(
+        storage: &mut ProverStorageProcessor<'_>,
+        l1_batch_number: L1BatchNumber,
+    ) -> Vec<Self> {
+        let prover_jobs = storage
+            .fri_prover_jobs_dal()
+            .get_prover_jobs_for_l1_batch(l1_batch_number)
+            .await;
+        let mut witness_jobs = vec![];
+        for aggregation_round in ROUNDS {
+            witness_jobs.extend(
+                storage
+                    .fri_witness_generator_dal()
+                    .get_witness_jobs_for_l1_batch(l1_batch_number, aggregation_round)
+                    .await,
+            );
+        }
+
+        let mut jobs_by_circuit = BTreeMap::<_, (Vec<&ProverJobFriInfo>, Vec<JobError>)>::new();
+        for job in &prover_jobs {
+            let (jobs, errors) = jobs_by_circuit
+                .entry((Round::from(job.aggregation_round), job.circuit_id))
+                .or_default();
+            jobs.push(job);
+            if job.error.is_some() {
+                errors.push(job.into());
+            }
+        }
+        // Witness generator jobs for basic circuits and the scheduler aren't tied to a specific circuit,
+        // so their errors are attributed to circuit 0.
+        for job in &witness_jobs {
+            if job.error.is_none() {
+                continue;
+            }
+            let circuit_id = job.circuit_id.unwrap_or(0);
+            let (_, errors) = jobs_by_circuit
+                .entry((Round::from(job.aggregation_round), circuit_id))
+                .or_default();
+            errors.push(job.into());
+        }
+
+        jobs_by_circuit
+            .into_iter()
+            .map(|((round, circuit_id), (jobs, errors))| Self {
+                round,
+                circuit_id,
+                jobs: count_statuses(jobs.iter().map(|job| job.status.as_str())),
+                max_attempts: jobs.iter().map(|job| job.attempts).max().unwrap_or(0),
+                errors,
+            })
+            .collect()
+    }
+
+    fn failed(&self) -> usize {
+        self.jobs.get("failed").copied().unwrap_or(0)
+    }
+
+    fn print(&self) {
+        println!(
+            "├── {} circuit {}: {} (max attempts: {})",
+            self.round.as_str(),
+            self.circuit_id,
+            format_counts(&self.jobs),
+            self.max_attempts
+        );
+        for error in &self.errors {
+            let job = match (&error.kind, error.job_id) {
+                (JobKind::Prover, Some(job_id)) => format!("prover job {job_id}"),
+                _ => "witness generator job".to_owned(),
+            };
+            println!(
+                "│   └── {job} [{}, attempts: {}]: {}",
+                error.status, error.attempts, error.error
+            );
+        }
+    }
+}
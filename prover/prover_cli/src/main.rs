@@ -0,0 +1,64 @@
+//! Admin CLI for the FRI proving pipeline. Allows to inspect the status of L1 batches across all aggregation rounds,
+//! as well as to restart or cancel proving of a batch.
+
+use anyhow::Context as _;
+use clap::{Parser, Subcommand};
+use prover_dal::ProverConnectionPool;
+use zksync_config::PostgresConfig;
+use zksync_env_config::FromEnv;
+
+use crate::commands::{cancel, restart, status};
+
+mod commands;
+
+#[derive(Debug, Parser)]
+#[command(
+    author = "Matter Labs",
+    version,
+    about = "Admin CLI for the FRI proving pipeline",
+    long_about = None
+)]
+struct Cli {
+    /// Prover database URL. If not specified, it is loaded from the environment.
+    #[arg(long, global = true)]
+    db_url: Option<String>,
+    /// Output results as JSON.
+    #[arg(long, global = true)]
+    json: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Shows the status of L1 batches.
+    #[command(subcommand)]
+    Status(status::StatusCommand),
+    /// Restarts failed or stuck jobs for an L1 batch, or moves the batch back to an earlier aggregation round.
+    Restart(restart::RestartArgs),
+    /// Cancels proving of an L1 batch.
+    Cancel(cancel::CancelArgs),
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let db_url = match cli.db_url {
+        Some(db_url) => db_url,
+        None => {
+            let postgres_config =
+                PostgresConfig::from_env().context("PostgresConfig::from_env()")?;
+            postgres_config.prover_url()?.to_owned()
+        }
+    };
+    let pool = ProverConnectionPool::singleton(&db_url)
+        .build()
+        .await
+        .context("failed to build a connection pool")?;
+
+    match cli.command {
+        Command::Status(command) => status::run(command, &pool, cli.json).await,
+        Command::Restart(args) => restart::run(args, &pool, cli.json).await,
+        Command::Cancel(args) => cancel::run(args, &pool, cli.json).await,
+    }
+}
@@ -4,16 +4,18 @@ use std::{future::Future, sync::Arc};
 use anyhow::Context as _;
 use local_ip_address::local_ip;
 use prometheus_exporter::PrometheusExporterConfig;
+use prover_dal::{
+    fri_prover_dal::types::{GpuProverInstanceStatus, SocketAddress},
+    ProverConnectionPool,
+};
 use tokio::{
     sync::{oneshot, watch::Receiver},
     task::JoinHandle,
 };
 use zksync_config::configs::{
-    fri_prover_group::FriProverGroupConfig, FriProverConfig, ObservabilityConfig, PostgresConfig,
-};
-use zksync_dal::{
-    fri_prover_dal::types::{GpuProverInstanceStatus, SocketAddress},
-    ConnectionPool,
+    fri_prover::KeystoreSource,
+    fri_prover_group::{FriProverGroupConfig, FriProverSchedulingConfig},
+    FriProverConfig, ObservabilityConfig, PostgresConfig,
 };
 use zksync_env_config::{
     object_store::{ProverObjectStoreConfig, PublicObjectStoreConfig},
@@ -24,6 +26,11 @@ use zksync_prover_fri_utils::{get_all_circuit_id_round_tuples_for, region_fetche
 use zksync_queued_job_processor::JobProcessor;
 use zksync_types::basic_fri_types::CircuitIdRoundTuple;
 use zksync_utils::wait_for_tasks::wait_for_tasks;
+use zksync_vk_setup_data_server_fri::{
+    keystore::Keystore, object_store_keystore::ObjectStoreKeystore,
+};
+
+use crate::utils::{get_setup_data_key, setup_metadata_to_setup_data_key};
 
 mod gpu_prover_job_processor;
 mod metrics;
@@ -33,7 +40,7 @@ mod utils;
 
 async fn graceful_shutdown(port: u16) -> anyhow::Result<impl Future<Output = ()>> {
     let postgres_config = PostgresConfig::from_env().context("PostgresConfig::from_env()")?;
-    let pool = ConnectionPool::singleton(postgres_config.prover_url()?)
+    let pool = ProverConnectionPool::singleton(postgres_config.prover_url()?)
         .build()
         .await
         .context("failed to build a connection pool")?;
@@ -106,8 +113,9 @@ async fn main() -> anyhow::Result<()> {
     };
     let specialized_group_id = prover_config.specialized_group_id;
 
-    let circuit_ids_for_round_to_be_proven = FriProverGroupConfig::from_env()
-        .context("FriProverGroupConfig::from_env()")?
+    let prover_group_config =
+        FriProverGroupConfig::from_env().context("FriProverGroupConfig::from_env()")?;
+    let circuit_ids_for_round_to_be_proven = prover_group_config
         .get_circuit_ids_for_group_id(specialized_group_id)
         .unwrap_or_default();
     let circuit_ids_for_round_to_be_proven =
@@ -118,6 +126,23 @@ async fn main() -> anyhow::Result<()> {
         specialized_group_id,
         circuit_ids_for_round_to_be_proven.clone()
     );
+    if prover_config.keystore_source == KeystoreSource::ObjectStore {
+        let setup_data_keys = if circuit_ids_for_round_to_be_proven.is_empty() {
+            ObjectStoreKeystore::all_setup_data_keys()
+        } else {
+            circuit_ids_for_round_to_be_proven
+                .iter()
+                .map(|tuple| get_setup_data_key(setup_metadata_to_setup_data_key(tuple)))
+                .collect()
+        };
+        ObjectStoreKeystore::new(
+            object_store_factory.create_store().await,
+            Keystore::default(),
+        )
+        .fetch_keys(&setup_data_keys)
+        .await
+        .context("failed fetching prover keys from object store")?;
+    }
     let postgres_config = PostgresConfig::from_env().context("PostgresConfig::from_env()")?;
 
     // There are 2 threads using the connection pool:
@@ -125,10 +150,11 @@ async fn main() -> anyhow::Result<()> {
     // 2. The socket listener thread, which is used to update the prover instance status.
     const MAX_POOL_SIZE_FOR_PROVER: u32 = 2;
 
-    let pool = ConnectionPool::builder(postgres_config.prover_url()?, MAX_POOL_SIZE_FOR_PROVER)
-        .build()
-        .await
-        .context("failed to build a connection pool")?;
+    let pool =
+        ProverConnectionPool::builder(postgres_config.prover_url()?, MAX_POOL_SIZE_FOR_PROVER)
+            .build()
+            .await
+            .context("failed to build a connection pool")?;
     let port = prover_config.witness_vector_receiver_port;
     let prover_tasks = get_prover_tasks(
         prover_config,
@@ -137,6 +163,7 @@ async fn main() -> anyhow::Result<()> {
         public_blob_store,
         pool,
         circuit_ids_for_round_to_be_proven,
+        prover_group_config.scheduling,
     )
     .await
     .context("get_prover_tasks()")?;
@@ -171,8 +198,9 @@ async fn get_prover_tasks(
     stop_receiver: Receiver<bool>,
     store_factory: ObjectStoreFactory,
     public_blob_store: Option<Arc<dyn ObjectStore>>,
-    pool: ConnectionPool,
+    pool: ProverConnectionPool,
     circuit_ids_for_round_to_be_proven: Vec<CircuitIdRoundTuple>,
+    scheduling_config: FriProverSchedulingConfig,
 ) -> anyhow::Result<Vec<JoinHandle<anyhow::Result<()>>>> {
     use zksync_vk_setup_data_server_fri::commitment_utils::get_cached_commitments;
 
@@ -194,6 +222,7 @@ async fn get_prover_tasks(
         pool,
         setup_load_mode,
         circuit_ids_for_round_to_be_proven,
+        scheduling_config,
         vk_commitments,
     );
     Ok(vec![tokio::spawn(prover.run(stop_receiver, None))])
@@ -205,8 +234,10 @@ async fn get_prover_tasks(
     stop_receiver: Receiver<bool>,
     store_factory: ObjectStoreFactory,
     public_blob_store: Option<Arc<dyn ObjectStore>>,
-    pool: ConnectionPool,
+    pool: ProverConnectionPool,
     circuit_ids_for_round_to_be_proven: Vec<CircuitIdRoundTuple>,
+    // GPU provers receive jobs from witness vector generators, which apply the scheduling policy.
+    _scheduling_config: FriProverSchedulingConfig,
 ) -> anyhow::Result<Vec<JoinHandle<anyhow::Result<()>>>> {
     use gpu_prover_job_processor::gpu_prover;
     use socket_listener::gpu_socket_listener;
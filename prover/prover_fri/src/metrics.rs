@@ -36,6 +36,10 @@ pub(crate) struct ProverFriMetrics {
     pub witness_vector_blob_time: LabeledFamily<u64, Histogram<Duration>>,
     #[metrics(buckets = Buckets::LATENCIES, labels = ["circuit_type"])]
     pub blob_save_time: LabeledFamily<String, Histogram<Duration>>,
+    /// Time from witness inputs for an L1 batch becoming available to the scheduler proof
+    /// for the batch being saved, i.e. end-to-end proving latency of a batch.
+    #[metrics(buckets = Buckets::exponential(60.0..=61_440.0, 2.0))]
+    pub l1_batch_proving_time: Histogram<Duration>,
 }
 
 #[vise::register]
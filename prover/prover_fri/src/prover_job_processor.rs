@@ -2,12 +2,15 @@ use std::{collections::HashMap, sync::Arc, time::Instant};
 
 use anyhow::Context as _;
 use circuit_definitions::{circuit_definitions::eip4844::EIP4844Circuit, eip4844_proof_config};
+use prover_dal::ProverConnectionPool;
 use tokio::task::JoinHandle;
 use zkevm_test_harness::prover_utils::{
     prove_base_layer_circuit, prove_eip4844_circuit, prove_recursion_layer_circuit,
 };
-use zksync_config::configs::{fri_prover_group::FriProverGroupConfig, FriProverConfig};
-use zksync_dal::ConnectionPool;
+use zksync_config::configs::{
+    fri_prover_group::{FriProverGroupConfig, FriProverSchedulingConfig},
+    FriProverConfig,
+};
 use zksync_env_config::FromEnv;
 use zksync_object_store::ObjectStore;
 use zksync_prover_fri_types::{
@@ -22,7 +25,7 @@ use zksync_prover_fri_types::{
     },
     CircuitWrapper, FriProofWrapper, ProverJob, ProverServiceDataKey,
 };
-use zksync_prover_fri_utils::fetch_next_circuit;
+use zksync_prover_fri_utils::{fetch_next_circuit, ProverJobScheduler};
 use zksync_queued_job_processor::{async_trait, JobProcessor};
 use zksync_types::{basic_fri_types::CircuitIdRoundTuple, protocol_version::L1VerifierConfig};
 use zksync_vk_setup_data_server_fri::{keystore::Keystore, GoldilocksProverSetupData};
@@ -44,11 +47,12 @@ pub struct Prover {
     blob_store: Arc<dyn ObjectStore>,
     public_blob_store: Option<Arc<dyn ObjectStore>>,
     config: Arc<FriProverConfig>,
-    prover_connection_pool: ConnectionPool,
+    prover_connection_pool: ProverConnectionPool,
     setup_load_mode: SetupLoadMode,
     // Only pick jobs for the configured circuit id and aggregation rounds.
     // Empty means all jobs are picked.
     circuit_ids_for_round_to_be_proven: Vec<CircuitIdRoundTuple>,
+    job_scheduler: ProverJobScheduler,
     vk_commitments: L1VerifierConfig,
 }
 
@@ -58,9 +62,10 @@ impl Prover {
         blob_store: Arc<dyn ObjectStore>,
         public_blob_store: Option<Arc<dyn ObjectStore>>,
         config: FriProverConfig,
-        prover_connection_pool: ConnectionPool,
+        prover_connection_pool: ProverConnectionPool,
         setup_load_mode: SetupLoadMode,
         circuit_ids_for_round_to_be_proven: Vec<CircuitIdRoundTuple>,
+        scheduling_config: FriProverSchedulingConfig,
         vk_commitments: L1VerifierConfig,
     ) -> Self {
         Prover {
@@ -70,6 +75,7 @@ impl Prover {
             prover_connection_pool,
             setup_load_mode,
             circuit_ids_for_round_to_be_proven,
+            job_scheduler: ProverJobScheduler::new(scheduling_config),
             vk_commitments,
         }
     }
@@ -237,6 +243,7 @@ impl JobProcessor for Prover {
             &*self.blob_store,
             &self.circuit_ids_for_round_to_be_proven,
             &self.vk_commitments,
+            &self.job_scheduler,
         )
         .await
         else {
@@ -3,15 +3,15 @@ pub mod gpu_socket_listener {
     use std::{net::SocketAddr, time::Instant};
 
     use anyhow::Context as _;
+    use prover_dal::{
+        fri_prover_dal::types::{GpuProverInstanceStatus, SocketAddress},
+        ProverConnectionPool,
+    };
     use tokio::{
         io::copy,
         net::{TcpListener, TcpStream},
         sync::watch,
     };
-    use zksync_dal::{
-        fri_prover_dal::types::{GpuProverInstanceStatus, SocketAddress},
-        ConnectionPool,
-    };
     use zksync_object_store::bincode;
     use zksync_prover_fri_types::WitnessVectorArtifacts;
 
@@ -23,7 +23,7 @@ pub mod gpu_socket_listener {
     pub(crate) struct SocketListener {
         address: SocketAddress,
         queue: SharedWitnessVectorQueue,
-        pool: ConnectionPool,
+        pool: ProverConnectionPool,
         specialized_prover_group_id: u8,
         zone: String,
     }
@@ -32,7 +32,7 @@ pub mod gpu_socket_listener {
         pub fn new(
             address: SocketAddress,
             queue: SharedWitnessVectorQueue,
-            pool: ConnectionPool,
+            pool: ProverConnectionPool,
             specialized_prover_group_id: u8,
             zone: String,
         ) -> Self {
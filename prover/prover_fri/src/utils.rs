@@ -2,11 +2,11 @@
 
 use std::{sync::Arc, time::Instant};
 
+use prover_dal::ProverStorageProcessor;
 use tokio::sync::Mutex;
 use zkevm_test_harness::prover_utils::{
     verify_base_layer_proof, verify_eip4844_proof, verify_recursion_layer_proof,
 };
-use zksync_dal::StorageProcessor;
 use zksync_object_store::ObjectStore;
 use zksync_prover_fri_types::{
     circuit_definitions::{
@@ -66,7 +66,7 @@ pub async fn save_proof(
     blob_store: &dyn ObjectStore,
     public_blob_store: Option<&dyn ObjectStore>,
     shall_save_to_public_bucket: bool,
-    storage_processor: &mut StorageProcessor<'_>,
+    storage_processor: &mut ProverStorageProcessor<'_>,
 ) {
     tracing::info!(
         "Successfully proven job: {}, total time taken: {:?}",
@@ -110,6 +110,13 @@ pub async fn save_proof(
             .fri_proof_compressor_dal()
             .insert_proof_compression_job(artifacts.block_number, &blob_url)
             .await;
+        let proving_time = transaction
+            .fri_witness_generator_dal()
+            .get_witness_inputs_age(artifacts.block_number)
+            .await;
+        if let Some(proving_time) = proving_time {
+            METRICS.l1_batch_proving_time.observe(proving_time);
+        }
     }
     if job_metadata.is_node_final_proof {
         let circuit_id = if job_metadata.circuit_id == EIP_4844_CIRCUIT_ID {
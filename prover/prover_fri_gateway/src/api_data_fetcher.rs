@@ -1,10 +1,10 @@
 use std::{sync::Arc, time::Duration};
 
 use async_trait::async_trait;
+use prover_dal::ProverConnectionPool;
 use reqwest::Client;
 use serde::{de::DeserializeOwned, Serialize};
 use tokio::{sync::watch, time::sleep};
-use zksync_dal::ConnectionPool;
 use zksync_object_store::ObjectStore;
 
 use crate::metrics::METRICS;
@@ -17,7 +17,7 @@ pub(crate) const SUBMIT_PROOF_PATH: &str = "/submit_proof";
 
 pub(crate) struct PeriodicApiStruct {
     pub(crate) blob_store: Arc<dyn ObjectStore>,
-    pub(crate) pool: ConnectionPool,
+    pub(crate) pool: ProverConnectionPool,
     pub(crate) api_url: String,
     pub(crate) poll_duration: Duration,
     pub(crate) client: Client,
@@ -1,9 +1,9 @@
 use anyhow::Context as _;
 use prometheus_exporter::PrometheusExporterConfig;
+use prover_dal::ProverConnectionPool;
 use reqwest::Client;
 use tokio::sync::{oneshot, watch};
 use zksync_config::configs::{FriProverGatewayConfig, ObservabilityConfig, PostgresConfig};
-use zksync_dal::ConnectionPool;
 use zksync_env_config::{object_store::ProverObjectStoreConfig, FromEnv};
 use zksync_object_store::ObjectStoreFactory;
 use zksync_prover_interface::api::{ProofGenerationDataRequest, SubmitProofRequest};
@@ -37,7 +37,7 @@ async fn main() -> anyhow::Result<()> {
     let config =
         FriProverGatewayConfig::from_env().context("FriProverGatewayConfig::from_env()")?;
     let postgres_config = PostgresConfig::from_env().context("PostgresConfig::from_env()")?;
-    let pool = ConnectionPool::builder(
+    let pool = ProverConnectionPool::builder(
         postgres_config.prover_url()?,
         postgres_config.max_connections()?,
     )
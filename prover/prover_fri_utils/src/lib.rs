@@ -1,6 +1,9 @@
 use std::time::Instant;
 
-use zksync_dal::StorageProcessor;
+use prover_dal::{fri_prover_dal::types::JobPriorityWeights, ProverStorageProcessor};
+use zksync_config::configs::fri_prover_group::{
+    FriProverSchedulingConfig, FriProverSchedulingPolicy,
+};
 use zksync_object_store::ObjectStore;
 use zksync_prover_fri_types::{
     circuit_definitions::{
@@ -24,17 +27,53 @@ pub mod metrics;
 pub mod region_fetcher;
 pub mod socket_utils;
 
+/// Applies [`FriProverSchedulingConfig`] when picking prover jobs.
+#[derive(Debug, Default)]
+pub struct ProverJobScheduler {
+    config: FriProverSchedulingConfig,
+}
+
+impl ProverJobScheduler {
+    pub fn new(config: FriProverSchedulingConfig) -> Self {
+        Self { config }
+    }
+
+    fn weights(&self, is_specialized: bool) -> JobPriorityWeights {
+        job_priority_weights(&self.config, is_specialized)
+    }
+
+    fn has_reserved_capacity(&self) -> bool {
+        self.config.reserved_aggregation_capacity_percent > 0
+    }
+
+    /// Checks whether the slot taken by the next pick of a generic prover is dedicated to aggregation jobs,
+    /// i.e. whether aggregation jobs would occupy less than `reserved_aggregation_capacity_percent` of busy slots
+    /// if the next pick wasn't an aggregation job. The arguments are the numbers of in-progress aggregation jobs
+    /// and of all in-progress jobs.
+    fn is_next_slot_reserved(
+        &self,
+        in_progress_aggregation: usize,
+        in_progress_total: usize,
+    ) -> bool {
+        let percent = usize::from(self.config.reserved_aggregation_capacity_percent);
+        in_progress_aggregation * 100 < percent * (in_progress_total + 1)
+    }
+}
+
 pub async fn fetch_next_circuit(
-    storage: &mut StorageProcessor<'_>,
+    storage: &mut ProverStorageProcessor<'_>,
     blob_store: &dyn ObjectStore,
     circuit_ids_for_round_to_be_proven: &Vec<CircuitIdRoundTuple>,
     vk_commitments: &L1VerifierConfig,
+    scheduler: &ProverJobScheduler,
 ) -> Option<ProverJob> {
     let protocol_versions = storage
         .fri_protocol_versions_dal()
         .protocol_version_for(vk_commitments)
         .await;
     let pod_name = get_current_pod_name();
+    let is_specialized = !circuit_ids_for_round_to_be_proven.is_empty();
+    let weights = scheduler.weights(is_specialized);
     let prover_job = match &circuit_ids_for_round_to_be_proven.is_empty() {
         false => {
             // Specialized prover: proving subset of configured circuits.
@@ -44,15 +83,44 @@ pub async fn fetch_next_circuit(
                     circuit_ids_for_round_to_be_proven,
                     &protocol_versions,
                     &pod_name,
+                    &weights,
                 )
                 .await
         }
         true => {
             // Generalized prover: proving all circuits.
-            storage
-                .fri_prover_jobs_dal()
-                .get_next_job(&protocol_versions, &pod_name)
-                .await
+            let is_reserved = if scheduler.has_reserved_capacity() {
+                let (in_progress_aggregation, in_progress_total) = storage
+                    .fri_prover_jobs_dal()
+                    .in_progress_job_counts(AggregationRound::NodeAggregation)
+                    .await;
+                scheduler.is_next_slot_reserved(in_progress_aggregation, in_progress_total)
+            } else {
+                false
+            };
+            let aggregation_job = if is_reserved {
+                storage
+                    .fri_prover_jobs_dal()
+                    .get_next_job_for_circuit_id_round(
+                        &get_aggregation_circuit_id_round_tuples(),
+                        &protocol_versions,
+                        &pod_name,
+                        &weights,
+                    )
+                    .await
+            } else {
+                None
+            };
+
+            match aggregation_job {
+                Some(job) => Some(job),
+                None => {
+                    storage
+                        .fri_prover_jobs_dal()
+                        .get_next_job(&protocol_versions, &pod_name, &weights)
+                        .await
+                }
+            }
         }
     }?;
     tracing::info!("Started processing prover job: {:?}", prover_job);
@@ -88,6 +156,43 @@ pub async fn fetch_next_circuit(
     ))
 }
 
+fn job_priority_weights(
+    config: &FriProverSchedulingConfig,
+    is_specialized: bool,
+) -> JobPriorityWeights {
+    match config.policy {
+        FriProverSchedulingPolicy::Default if is_specialized => {
+            JobPriorityWeights::OLDEST_BATCH_FIRST
+        }
+        FriProverSchedulingPolicy::Default => JobPriorityWeights::AGGREGATION_ROUND_FIRST,
+        FriProverSchedulingPolicy::AggregationRoundFirst => {
+            JobPriorityWeights::AGGREGATION_ROUND_FIRST
+        }
+        FriProverSchedulingPolicy::OldestBatchFirst => JobPriorityWeights::OLDEST_BATCH_FIRST,
+        FriProverSchedulingPolicy::RoundWeights => JobPriorityWeights::new(
+            config.round_weight(AggregationRound::BasicCircuits),
+            config.round_weight(AggregationRound::LeafAggregation),
+            config.round_weight(AggregationRound::NodeAggregation),
+            config.round_weight(AggregationRound::Scheduler),
+        ),
+    }
+}
+
+/// Returns circuits for node aggregation and scheduler jobs, which generic provers prioritize
+/// for the reserved share of prover capacity.
+fn get_aggregation_circuit_id_round_tuples() -> Vec<CircuitIdRoundTuple> {
+    get_all_circuit_id_round_tuples_for(vec![
+        CircuitIdRoundTuple::new(
+            ZkSyncRecursionLayerStorageType::NodeLayerCircuit as u8,
+            AggregationRound::NodeAggregation as u8,
+        ),
+        CircuitIdRoundTuple::new(
+            ZkSyncRecursionLayerStorageType::SchedulerCircuit as u8,
+            AggregationRound::Scheduler as u8,
+        ),
+    ])
+}
+
 pub fn get_recursive_layer_circuit_id_for_base_layer(base_layer_circuit_id: u8) -> u8 {
     let recursive_circuit_type = base_circuit_type_into_recursive_leaf_circuit_type(
         BaseLayerCircuitType::from_numeric_value(base_layer_circuit_id),
@@ -191,4 +296,78 @@ mod tests {
         let res = get_all_circuit_id_round_tuples_for(ids.clone());
         assert_eq!(ids, res);
     }
+
+    #[test]
+    fn job_priority_weights_for_policies() {
+        let mut config = FriProverSchedulingConfig {
+            basic_circuits_weight: 1,
+            leaf_aggregation_weight: 2,
+            node_aggregation_weight: 3,
+            scheduler_weight: 4,
+            ..FriProverSchedulingConfig::default()
+        };
+        // The default policy must preserve the ordering used before scheduling became configurable.
+        assert_eq!(
+            job_priority_weights(&config, false),
+            JobPriorityWeights::AGGREGATION_ROUND_FIRST
+        );
+        assert_eq!(
+            job_priority_weights(&config, true),
+            JobPriorityWeights::OLDEST_BATCH_FIRST
+        );
+
+        config.policy = FriProverSchedulingPolicy::AggregationRoundFirst;
+        for is_specialized in [false, true] {
+            assert_eq!(
+                job_priority_weights(&config, is_specialized),
+                JobPriorityWeights::AGGREGATION_ROUND_FIRST
+            );
+        }
+
+        config.policy = FriProverSchedulingPolicy::OldestBatchFirst;
+        for is_specialized in [false, true] {
+            assert_eq!(
+                job_priority_weights(&config, is_specialized),
+                JobPriorityWeights::OLDEST_BATCH_FIRST
+            );
+        }
+
+        config.policy = FriProverSchedulingPolicy::RoundWeights;
+        for is_specialized in [false, true] {
+            assert_eq!(
+                job_priority_weights(&config, is_specialized),
+                JobPriorityWeights::new(1, 2, 3, 4)
+            );
+        }
+    }
+
+    #[test]
+    fn reserved_slots_are_computed_from_in_progress_jobs() {
+        let scheduler = ProverJobScheduler::new(FriProverSchedulingConfig::default());
+        assert!(!scheduler.has_reserved_capacity());
+        assert!(!scheduler.is_next_slot_reserved(0, 0));
+        assert!(!scheduler.is_next_slot_reserved(0, 10));
+
+        let scheduler = ProverJobScheduler::new(FriProverSchedulingConfig {
+            reserved_aggregation_capacity_percent: 20,
+            ..FriProverSchedulingConfig::default()
+        });
+        assert!(scheduler.has_reserved_capacity());
+        // With 20% reserved capacity, every 5th busy slot is dedicated to aggregation jobs.
+        assert!(scheduler.is_next_slot_reserved(0, 0));
+        assert!(scheduler.is_next_slot_reserved(0, 4));
+        assert!(!scheduler.is_next_slot_reserved(1, 4));
+        assert!(!scheduler.is_next_slot_reserved(1, 3));
+        assert!(scheduler.is_next_slot_reserved(1, 9));
+        assert!(scheduler.is_next_slot_reserved(2, 19));
+        assert!(!scheduler.is_next_slot_reserved(4, 19));
+
+        let scheduler = ProverJobScheduler::new(FriProverSchedulingConfig {
+            reserved_aggregation_capacity_percent: 100,
+            ..FriProverSchedulingConfig::default()
+        });
+        for in_progress_total in [0, 1, 10, 100] {
+            assert!(scheduler.is_next_slot_reserved(in_progress_total, in_progress_total));
+        }
+    }
 }
@@ -36,6 +36,7 @@ pub enum ProverServiceDataType {
 /// There are 2 types:
 /// - small verification, finalization keys (used only during verification)
 /// - large setup keys, used during proving.
+#[derive(Debug, Clone)]
 pub struct Keystore {
     /// Directory to store all the small keys.
     basedir: String,
@@ -84,7 +85,7 @@ impl Keystore {
         &self.basedir
     }
 
-    fn get_file_path(
+    pub(crate) fn get_file_path(
         &self,
         key: ProverServiceDataKey,
         service_data_type: ProverServiceDataType,
@@ -112,6 +113,21 @@ impl Keystore {
         }
     }
 
+    /// Returns the path to finalization hints for the specified key.
+    pub(crate) fn get_finalization_hints_path(&self, key: ProverServiceDataKey) -> String {
+        let mut key = key;
+        // For `NodeAggregation` round we have only 1 finalization hints for all circuit type.
+        // TODO: is this needed??
+        if key.round == AggregationRound::NodeAggregation {
+            key.circuit_id = ZkSyncRecursionLayerStorageType::NodeLayerCircuit as u8;
+        }
+        self.get_file_path(key, ProverServiceDataType::FinalizationHints)
+    }
+
+    pub(crate) fn get_commitments_path(&self) -> String {
+        format!("{}/commitments.json", self.get_base_path())
+    }
+
     fn load_json_from_file<T: for<'a> Deserialize<'a>>(filepath: String) -> anyhow::Result<T> {
         let text = std::fs::read_to_string(&filepath)
             .with_context(|| format!("Failed reading verification key from path: {filepath}"))?;
@@ -219,15 +235,7 @@ impl Keystore {
         &self,
         key: ProverServiceDataKey,
     ) -> anyhow::Result<FinalizationHintsForProver> {
-        let mut key = key;
-        // For `NodeAggregation` round we have only 1 finalization hints for all circuit type.
-        // TODO: is this needed??
-        if key.round == AggregationRound::NodeAggregation {
-            key.circuit_id = ZkSyncRecursionLayerStorageType::NodeLayerCircuit as u8;
-        }
-        Self::load_bincode_from_file(
-            self.get_file_path(key, ProverServiceDataType::FinalizationHints),
-        )
+        Self::load_bincode_from_file(self.get_finalization_hints_path(key))
     }
 
     ///
@@ -456,12 +464,9 @@ impl Keystore {
     }
 
     pub fn load_commitments(&self) -> anyhow::Result<VkCommitments> {
-        Self::load_json_from_file(format!("{}/commitments.json", self.get_base_path()))
+        Self::load_json_from_file(self.get_commitments_path())
     }
     pub fn save_commitments(&self, commitments: &VkCommitments) -> anyhow::Result<()> {
-        Self::save_json_pretty(
-            format!("{}/commitments.json", self.get_base_path()),
-            &commitments,
-        )
+        Self::save_json_pretty(self.get_commitments_path(), &commitments)
     }
 }
@@ -24,6 +24,7 @@ use {shivini::cs::GpuSetup, std::alloc::Global};
 
 pub mod commitment_utils;
 pub mod keystore;
+pub mod object_store_keystore;
 pub mod setup_data_generator;
 pub mod utils;
 pub mod vk_commitment_helper;
@@ -0,0 +1,594 @@
+//! Keystore backed by an object store.
+
+use std::{ffi::OsStr, io, path::Path, sync::Arc};
+
+use anyhow::Context as _;
+use circuit_definitions::{
+    boojum::cs::implementations::setup::FinalizationHintsForProver,
+    circuit_definitions::{
+        aux_layer::{EIP4844VerificationKey, ZkSyncSnarkWrapperVK},
+        base_layer::ZkSyncBaseLayerVerificationKey,
+        recursion_layer::{ZkSyncRecursionLayerStorageType, ZkSyncRecursionLayerVerificationKey},
+    },
+    zkevm_circuits::scheduler::aux::BaseLayerCircuitType,
+};
+use tokio::{fs, sync::OnceCell};
+use toml_edit::Item;
+use zksync_object_store::{Bucket, ObjectStore};
+use zksync_prover_fri_types::ProverServiceDataKey;
+use zksync_types::basic_fri_types::AggregationRound;
+
+#[cfg(feature = "gpu")]
+use crate::GoldilocksGpuProverSetupData;
+use crate::{
+    commitment_utils::generate_commitments,
+    keystore::{Keystore, ProverServiceDataType},
+    vk_commitment_helper::read_contract_toml,
+    GoldilocksProverSetupData, VkCommitments,
+};
+
+/// Keystore that fetches prover keys from the [`Bucket::ProverKeys`] bucket of an object store.
+///
+/// Fetched keys are cached on the local disk using the layout of the wrapped [`Keystore`]. Thus, after the keys
+/// are fetched (e.g., using [`Self::fetch_keys()`]), they can be loaded with the local [`Keystore`] as well.
+///
+/// Fetched verification keys are validated against the commitments in `contracts.toml`
+/// (see [`vk_commitment_helper`](crate::vk_commitment_helper)); setup data and finalization hints are validated
+/// against the verification keys. Keys failing validation are evicted from the cache.
+///
+/// Methods mirror the ones of [`Keystore`] and take the same arguments, but are async since they may access
+/// the object store. Methods working with all keys at once (e.g., [`Keystore::load_keys_to_data_source()`]) are not
+/// mirrored; instead, fetch the keys with [`Self::fetch_keys()`] and use the local keystore returned by [`Self::cache()`].
+#[derive(Debug, Clone)]
+pub struct ObjectStoreKeystore {
+    cache: Keystore,
+    store: Arc<dyn ObjectStore>,
+    verification_keys_fetched: Arc<OnceCell<()>>,
+}
+
+impl ObjectStoreKeystore {
+    pub fn new(store: Arc<dyn ObjectStore>, cache: Keystore) -> Self {
+        Self {
+            cache,
+            store,
+            verification_keys_fetched: Arc::default(),
+        }
+    }
+
+    /// Returns the local keystore used as a cache.
+    pub fn cache(&self) -> &Keystore {
+        &self.cache
+    }
+
+    /// Returns keys for all circuits, i.e. for all setup data used by provers.
+    pub fn all_setup_data_keys() -> Vec<ProverServiceDataKey> {
+        let base_keys = (BaseLayerCircuitType::VM as u8
+            ..=BaseLayerCircuitType::L1MessagesHasher as u8)
+            .map(|circuit_type| {
+                ProverServiceDataKey::new(circuit_type, AggregationRound::BasicCircuits)
+            });
+        let recursive_keys = (ZkSyncRecursionLayerStorageType::SchedulerCircuit as u8
+            ..=ZkSyncRecursionLayerStorageType::LeafLayerCircuitForL1MessagesHasher as u8)
+            .map(ProverServiceDataKey::new_recursive);
+        base_keys
+            .chain(recursive_keys)
+            .chain([ProverServiceDataKey::eip4844()])
+            .collect()
+    }
+
+    /// Fetches all verification keys and finalization hints, and setup data for the specified keys.
+    pub async fn fetch_keys(&self, setup_data_keys: &[ProverServiceDataKey]) -> anyhow::Result<()> {
+        self.fetch_verification_keys().await?;
+        self.fetch_finalization_hints().await?;
+        for key in setup_data_keys {
+            self.fetch_setup_data(key.clone())
+                .await
+                .with_context(|| format!("failed fetching setup data for {key:?}"))?;
+        }
+        Ok(())
+    }
+
+    /// Fetches all verification keys and validates them against the commitments in `contracts.toml`.
+    ///
+    /// Commitments are computed from all verification keys together, so a validation failure cannot be attributed
+    /// to a specific key. If validation fails for keys that were (partially) cached before, all verification keys
+    /// are evicted and re-fetched from the object store. If validation still fails, all keys are evicted again,
+    /// so that they are re-fetched on the next attempt.
+    pub async fn fetch_verification_keys(&self) -> anyhow::Result<()> {
+        self.verification_keys_fetched
+            .get_or_try_init(|| async {
+                let paths = self.verification_key_paths();
+                let mut fetched_count = 0;
+                for path in &paths {
+                    if self.fetch(path).await? {
+                        fetched_count += 1;
+                    }
+                }
+                let mut result = self.validate_commitments().await;
+                if let Err(err) = &result {
+                    if fetched_count < paths.len() {
+                        tracing::warn!(
+                            "Cached verification keys are invalid ({err:#}); re-fetching all of them from object store"
+                        );
+                        self.evict_all(&paths).await?;
+                        for path in &paths {
+                            self.fetch(path).await?;
+                        }
+                        result = self.validate_commitments().await;
+                    }
+                }
+                if result.is_err() {
+                    self.evict_all(&paths).await?;
+                }
+                result
+            })
+            .await?;
+        Ok(())
+    }
+
+    async fn validate_commitments(&self) -> anyhow::Result<()> {
+        let cache = self.cache.clone();
+        tokio::task::spawn_blocking(move || validate_commitments(&cache))
+            .await
+            .context("verification key validation panicked")?
+    }
+
+    async fn evict_all(&self, paths: &[String]) -> anyhow::Result<()> {
+        for path in paths {
+            evict(path).await?;
+        }
+        Ok(())
+    }
+
+    /// Fetches finalization hints for all circuits. Hints are checked to be consistent with the verification keys
+    /// when the corresponding setup data is fetched.
+    async fn fetch_finalization_hints(&self) -> anyhow::Result<()> {
+        for key in Self::all_setup_data_keys() {
+            self.fetch(&self.cache.get_finalization_hints_path(key))
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Fetches setup data for the specified key and checks that its verification key and finalization hints
+    /// match the ones in the keystore. If validation fails, both setup data and finalization hints are evicted,
+    /// since either of them may be invalid.
+    async fn fetch_setup_data(&self, key: ProverServiceDataKey) -> anyhow::Result<()> {
+        self.fetch_verification_keys().await?;
+        let hints_path = self.cache.get_finalization_hints_path(key.clone());
+        self.fetch(&hints_path).await?;
+
+        let path = self
+            .cache
+            .get_file_path(key.clone(), ProverServiceDataType::SetupData);
+        if !self.fetch(&path).await? {
+            return Ok(());
+        }
+        let cache = self.cache.clone();
+        let validation_result =
+            tokio::task::spawn_blocking(move || validate_setup_data(&cache, key))
+                .await
+                .context("setup data validation panicked")?;
+        if validation_result.is_err() {
+            self.evict_all(&[path, hints_path]).await?;
+        }
+        validation_result
+    }
+
+    fn verification_key_paths(&self) -> Vec<String> {
+        let mut paths: Vec<_> = Self::all_setup_data_keys()
+            .into_iter()
+            .map(|key| {
+                self.cache
+                    .get_file_path(key, ProverServiceDataType::VerificationKey)
+            })
+            .collect();
+        paths.push(self.cache.get_file_path(
+            ProverServiceDataKey::snark(),
+            ProverServiceDataType::SnarkVerificationKey,
+        ));
+        paths
+    }
+
+    /// Fetches the file at the specified path from the object store unless it's already cached.
+    /// Returns `true` if the file was fetched.
+    async fn fetch(&self, path: &str) -> anyhow::Result<bool> {
+        if fs::try_exists(path)
+            .await
+            .with_context(|| format!("failed accessing `{path}`"))?
+        {
+            return Ok(false);
+        }
+
+        let object_key = object_key(path)?;
+        tracing::info!("Fetching prover key `{object_key}` from object store to `{path}`");
+        let data = self
+            .store
+            .get_raw(Bucket::ProverKeys, object_key)
+            .await
+            .with_context(|| format!("failed fetching prover key `{object_key}`"))?;
+
+        if let Some(parent) = Path::new(path).parent() {
+            fs::create_dir_all(parent)
+                .await
+                .with_context(|| format!("failed creating directory {parent:?}"))?;
+        }
+        // Write to a temporary file first, so that an interrupted write doesn't leave a truncated key in the cache.
+        let tmp_path = format!("{path}.tmp");
+        fs::write(&tmp_path, data)
+            .await
+            .with_context(|| format!("failed writing `{tmp_path}`"))?;
+        fs::rename(&tmp_path, path)
+            .await
+            .with_context(|| format!("failed renaming `{tmp_path}` to `{path}`"))?;
+        Ok(true)
+    }
+
+    /// Uploads the file at the specified path to the object store.
+    async fn upload(&self, path: String) -> anyhow::Result<()> {
+        let object_key = object_key(&path)?;
+        let data = fs::read(&path)
+            .await
+            .with_context(|| format!("failed reading `{path}`"))?;
+        tracing::info!("Uploading prover key `{object_key}` from `{path}` to object store");
+        self.store
+            .put_raw(Bucket::ProverKeys, object_key, data)
+            .await
+            .with_context(|| format!("failed uploading prover key `{object_key}`"))
+    }
+
+    ///
+    ///   Verification keys
+    ///
+
+    pub async fn load_base_layer_verification_key(
+        &self,
+        circuit_type: u8,
+    ) -> anyhow::Result<ZkSyncBaseLayerVerificationKey> {
+        self.fetch_verification_keys().await?;
+        self.cache.load_base_layer_verification_key(circuit_type)
+    }
+
+    pub async fn load_recursive_layer_verification_key(
+        &self,
+        circuit_type: u8,
+    ) -> anyhow::Result<ZkSyncRecursionLayerVerificationKey> {
+        self.fetch_verification_keys().await?;
+        self.cache
+            .load_recursive_layer_verification_key(circuit_type)
+    }
+
+    pub async fn load_4844_verification_key(&self) -> anyhow::Result<EIP4844VerificationKey> {
+        self.fetch_verification_keys().await?;
+        self.cache.load_4844_verification_key()
+    }
+
+    pub async fn save_base_layer_verification_key(
+        &self,
+        vk: ZkSyncBaseLayerVerificationKey,
+    ) -> anyhow::Result<()> {
+        let key =
+            ProverServiceDataKey::new(vk.numeric_circuit_type(), AggregationRound::BasicCircuits);
+        self.cache.save_base_layer_verification_key(vk)?;
+        self.upload(
+            self.cache
+                .get_file_path(key, ProverServiceDataType::VerificationKey),
+        )
+        .await
+    }
+
+    pub async fn save_recursive_layer_verification_key(
+        &self,
+        vk: ZkSyncRecursionLayerVerificationKey,
+    ) -> anyhow::Result<()> {
+        let key = ProverServiceDataKey::new_recursive(vk.numeric_circuit_type());
+        self.cache.save_recursive_layer_verification_key(vk)?;
+        self.upload(
+            self.cache
+                .get_file_path(key, ProverServiceDataType::VerificationKey),
+        )
+        .await
+    }
+
+    pub async fn save_4844_verification_key(
+        &self,
+        vk: EIP4844VerificationKey,
+    ) -> anyhow::Result<()> {
+        self.cache.save_4844_verification_key(vk)?;
+        self.upload(self.cache.get_file_path(
+            ProverServiceDataKey::eip4844(),
+            ProverServiceDataType::VerificationKey,
+        ))
+        .await
+    }
+
+    ///
+    /// Finalization hints
+    ///
+
+    pub async fn save_finalization_hints(
+        &self,
+        key: ProverServiceDataKey,
+        hint: &FinalizationHintsForProver,
+    ) -> anyhow::Result<()> {
+        self.cache.save_finalization_hints(key.clone(), hint)?;
+        self.upload(
+            self.cache
+                .get_file_path(key, ProverServiceDataType::FinalizationHints),
+        )
+        .await
+    }
+
+    pub async fn load_finalization_hints(
+        &self,
+        key: ProverServiceDataKey,
+    ) -> anyhow::Result<FinalizationHintsForProver> {
+        self.fetch(&self.cache.get_finalization_hints_path(key.clone()))
+            .await?;
+        self.cache.load_finalization_hints(key)
+    }
+
+    ///
+    ///   Snark wrapper
+    ///
+
+    pub async fn load_snark_verification_key(&self) -> anyhow::Result<String> {
+        self.fetch_verification_keys().await?;
+        self.cache.load_snark_verification_key()
+    }
+
+    pub async fn save_snark_verification_key(
+        &self,
+        vk: ZkSyncSnarkWrapperVK,
+    ) -> anyhow::Result<()> {
+        self.cache.save_snark_verification_key(vk)?;
+        self.upload(self.cache.get_file_path(
+            ProverServiceDataKey::snark(),
+            ProverServiceDataType::SnarkVerificationKey,
+        ))
+        .await
+    }
+
+    ///
+    /// Setup keys
+    ///
+
+    pub async fn load_cpu_setup_data_for_circuit_type(
+        &self,
+        key: ProverServiceDataKey,
+    ) -> anyhow::Result<GoldilocksProverSetupData> {
+        self.fetch_setup_data(key.clone()).await?;
+        let cache = self.cache.clone();
+        tokio::task::spawn_blocking(move || cache.load_cpu_setup_data_for_circuit_type(key))
+            .await
+            .context("loading setup data panicked")?
+    }
+
+    #[cfg(feature = "gpu")]
+    pub async fn load_gpu_setup_data_for_circuit_type(
+        &self,
+        key: ProverServiceDataKey,
+    ) -> anyhow::Result<GoldilocksGpuProverSetupData> {
+        self.fetch_setup_data(key.clone()).await?;
+        let cache = self.cache.clone();
+        tokio::task::spawn_blocking(move || cache.load_gpu_setup_data_for_circuit_type(key))
+            .await
+            .context("loading setup data panicked")?
+    }
+
+    pub async fn save_setup_data_for_circuit_type(
+        &self,
+        key: ProverServiceDataKey,
+        serialized_setup_data: &Vec<u8>,
+    ) -> anyhow::Result<()> {
+        self.cache
+            .save_setup_data_for_circuit_type(key.clone(), serialized_setup_data)?;
+        self.upload(
+            self.cache
+                .get_file_path(key, ProverServiceDataType::SetupData),
+        )
+        .await
+    }
+
+    pub async fn load_commitments(&self) -> anyhow::Result<VkCommitments> {
+        self.fetch(&self.cache.get_commitments_path()).await?;
+        self.cache.load_commitments()
+    }
+
+    pub async fn save_commitments(&self, commitments: &VkCommitments) -> anyhow::Result<()> {
+        self.cache.save_commitments(commitments)?;
+        self.upload(self.cache.get_commitments_path()).await
+    }
+}
+
+/// Returns the object store key for a cached file, which is just the file name.
+fn object_key(path: &str) -> anyhow::Result<&str> {
+    Path::new(path)
+        .file_name()
+        .and_then(OsStr::to_str)
+        .with_context(|| format!("invalid prover key path `{path}`"))
+}
+
+async fn evict(path: &str) -> anyhow::Result<()> {
+    tracing::warn!("Evicting invalid prover key `{path}` from cache");
+    match fs::remove_file(path).await {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err).with_context(|| format!("failed removing `{path}`")),
+    }
+}
+
+/// Checks that commitments computed from the verification keys in `keystore` match the ones in `contracts.toml`.
+fn validate_commitments(keystore: &Keystore) -> anyhow::Result<()> {
+    let commitments = generate_commitments(keystore).context("generate_commitments()")?;
+    let contract_doc = read_contract_toml().context("read_contract_toml()")?;
+    let contracts = contract_doc
+        .as_table()
+        .get("contracts")
+        .context("`contracts` section is missing in contracts.toml")?;
+
+    let expected_commitments = [
+        ("FRI_RECURSION_LEAF_LEVEL_VK_HASH", &commitments.leaf),
+        ("FRI_RECURSION_NODE_LEVEL_VK_HASH", &commitments.node),
+        (
+            "FRI_RECURSION_SCHEDULER_LEVEL_VK_HASH",
+            &commitments.scheduler,
+        ),
+        ("SNARK_WRAPPER_VK_HASH", &commitments.snark_wrapper),
+    ];
+    for (name, actual) in expected_commitments {
+        let expected = contracts
+            .get(name)
+            .and_then(Item::as_str)
+            .with_context(|| format!("`{name}` is missing in contracts.toml"))?;
+        anyhow::ensure!(
+            expected.eq_ignore_ascii_case(actual),
+            "verification keys don't match `{name}` in contracts.toml: expected {expected}, got {actual}"
+        );
+    }
+    Ok(())
+}
+
+/// Checks that the verification key and finalization hints embedded in the setup data match
+/// the ones in `keystore`.
+fn validate_setup_data(keystore: &Keystore, key: ProverServiceDataKey) -> anyhow::Result<()> {
+    #[cfg(not(feature = "gpu"))]
+    let setup_data = keystore.load_cpu_setup_data_for_circuit_type(key.clone())?;
+    #[cfg(feature = "gpu")]
+    let setup_data = keystore.load_gpu_setup_data_for_circuit_type(key.clone())?;
+
+    let expected_vk = if key.is_eip4844() {
+        bincode::serialize(&keystore.load_4844_verification_key()?)
+    } else if key.is_base_layer() {
+        let vk = keystore.load_base_layer_verification_key(key.circuit_id)?;
+        bincode::serialize(&vk.into_inner())
+    } else {
+        let vk = keystore.load_recursive_layer_verification_key(key.circuit_id)?;
+        bincode::serialize(&vk.into_inner())
+    };
+    let expected_vk = expected_vk.context("failed serializing verification key")?;
+    let actual_vk =
+        bincode::serialize(&setup_data.vk).context("failed serializing verification key")?;
+    anyhow::ensure!(
+        actual_vk == expected_vk,
+        "verification key in setup data for {key:?} doesn't match the verification key in keystore"
+    );
+
+    let expected_hints = bincode::serialize(&keystore.load_finalization_hints(key.clone())?)
+        .context("failed serializing finalization hints")?;
+    let actual_hints = bincode::serialize(&setup_data.finalization_hint)
+        .context("failed serializing finalization hints")?;
+    anyhow::ensure!(
+        actual_hints == expected_hints,
+        "finalization hints in setup data for {key:?} don't match the hints in keystore"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_object_store::ObjectStoreFactory;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn keys_are_uploaded_and_fetched() {
+        let store = ObjectStoreFactory::mock().create_store().await;
+        let source_dir = tempfile::TempDir::new().unwrap();
+        let source = ObjectStoreKeystore::new(
+            store.clone(),
+            Keystore::new_with_optional_setup_path(
+                source_dir.path().to_str().unwrap().to_owned(),
+                None,
+            ),
+        );
+        let commitments = VkCommitments {
+            leaf: "0x01".to_owned(),
+            node: "0x02".to_owned(),
+            scheduler: "0x03".to_owned(),
+            snark_wrapper: "0x04".to_owned(),
+        };
+        source.save_commitments(&commitments).await.unwrap();
+
+        let cache_dir = tempfile::TempDir::new().unwrap();
+        let cache_path = cache_dir.path().join("keys");
+        let keystore = ObjectStoreKeystore::new(
+            store,
+            Keystore::new_with_optional_setup_path(cache_path.to_str().unwrap().to_owned(), None),
+        );
+        let fetched = keystore.load_commitments().await.unwrap();
+        assert_eq!(fetched.leaf, commitments.leaf);
+        assert_eq!(fetched.snark_wrapper, commitments.snark_wrapper);
+        // The fetched file should be cached and readable via the local keystore.
+        assert!(cache_path.join("commitments.json").exists());
+        let cached = keystore.cache().load_commitments().unwrap();
+        assert_eq!(cached.node, commitments.node);
+    }
+
+    #[tokio::test]
+    async fn invalid_cached_verification_key_is_evicted_and_refetched() {
+        let store = ObjectStoreFactory::mock().create_store().await;
+        let source = ObjectStoreKeystore::new(store.clone(), Keystore::default());
+        let source_paths = source.verification_key_paths();
+        for path in &source_paths {
+            source.upload(path.clone()).await.unwrap();
+        }
+
+        let cache_dir = tempfile::TempDir::new().unwrap();
+        let keystore = ObjectStoreKeystore::new(
+            store,
+            Keystore::new_with_optional_setup_path(
+                cache_dir.path().to_str().unwrap().to_owned(),
+                None,
+            ),
+        );
+        // Populate the cache with valid keys, except for the scheduler key, which is replaced with the node key.
+        let cache_paths = keystore.verification_key_paths();
+        for (source_path, cache_path) in source_paths.iter().zip(&cache_paths) {
+            std::fs::create_dir_all(Path::new(cache_path).parent().unwrap()).unwrap();
+            std::fs::copy(source_path, cache_path).unwrap();
+        }
+        let vk_path = |keystore: &ObjectStoreKeystore,
+                       circuit_type: ZkSyncRecursionLayerStorageType| {
+            keystore.cache().get_file_path(
+                ProverServiceDataKey::new_recursive(circuit_type as u8),
+                ProverServiceDataType::VerificationKey,
+            )
+        };
+        let node_vk_path = vk_path(&keystore, ZkSyncRecursionLayerStorageType::NodeLayerCircuit);
+        let scheduler_vk_path =
+            vk_path(&keystore, ZkSyncRecursionLayerStorageType::SchedulerCircuit);
+        std::fs::copy(&node_vk_path, &scheduler_vk_path).unwrap();
+
+        keystore.fetch_verification_keys().await.unwrap();
+
+        let expected_scheduler_vk = std::fs::read(vk_path(
+            &source,
+            ZkSyncRecursionLayerStorageType::SchedulerCircuit,
+        ))
+        .unwrap();
+        assert_eq!(
+            std::fs::read(&scheduler_vk_path).unwrap(),
+            expected_scheduler_vk
+        );
+        let vk = keystore
+            .load_recursive_layer_verification_key(
+                ZkSyncRecursionLayerStorageType::SchedulerCircuit as u8,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            vk.numeric_circuit_type(),
+            ZkSyncRecursionLayerStorageType::SchedulerCircuit as u8
+        );
+    }
+
+    #[test]
+    fn object_key_is_file_name() {
+        assert_eq!(
+            object_key("/data/keys/verification_basic_1_key.json").unwrap(),
+            "verification_basic_1_key.json"
+        );
+        assert!(object_key("/").is_err());
+    }
+}
@@ -18,6 +18,7 @@ use circuit_definitions::{
 use multivm::vm_latest::{
     constants::MAX_CYCLES_FOR_TX, HistoryDisabled, StorageOracle as VmStorageOracle,
 };
+use prover_dal::{fri_witness_generator_dal::FriWitnessJobStatus, ProverConnectionPool};
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 use zkevm_test_harness::{
@@ -26,7 +27,7 @@ use zkevm_test_harness::{
     zkevm_circuits::eip_4844::input::EIP4844OutputDataWitness,
 };
 use zksync_config::configs::FriWitnessGeneratorConfig;
-use zksync_dal::{fri_witness_generator_dal::FriWitnessJobStatus, ConnectionPool};
+use zksync_dal::ConnectionPool;
 use zksync_object_store::{Bucket, ObjectStore, ObjectStoreFactory, StoredObject};
 use zksync_prover_fri_types::{
     circuit_definitions::{
@@ -103,7 +104,7 @@ pub struct BasicWitnessGenerator {
     object_store: Arc<dyn ObjectStore>,
     public_blob_store: Option<Arc<dyn ObjectStore>>,
     connection_pool: ConnectionPool,
-    prover_connection_pool: ConnectionPool,
+    prover_connection_pool: ProverConnectionPool,
     protocol_versions: Vec<FriProtocolVersionId>,
 }
 
@@ -113,7 +114,7 @@ impl BasicWitnessGenerator {
         store_factory: &ObjectStoreFactory,
         public_blob_store: Option<Arc<dyn ObjectStore>>,
         connection_pool: ConnectionPool,
-        prover_connection_pool: ConnectionPool,
+        prover_connection_pool: ProverConnectionPool,
         protocol_versions: Vec<FriProtocolVersionId>,
     ) -> Self {
         Self {
@@ -129,7 +130,7 @@ impl BasicWitnessGenerator {
     async fn process_job_impl(
         object_store: Arc<dyn ObjectStore>,
         connection_pool: ConnectionPool,
-        prover_connection_pool: ConnectionPool,
+        prover_connection_pool: ProverConnectionPool,
         basic_job: BasicWitnessGeneratorJob,
         started_at: Instant,
         config: Arc<FriWitnessGeneratorConfig>,
@@ -364,7 +365,7 @@ async fn process_basic_circuits_job(
 }
 
 async fn update_database(
-    prover_connection_pool: &ConnectionPool,
+    prover_connection_pool: &ProverConnectionPool,
     started_at: Instant,
     block_number: L1BatchNumber,
     blob_urls: BlobUrls,
@@ -5,6 +5,7 @@ use std::time::Instant;
 use anyhow::{anyhow, Context as _};
 use futures::{channel::mpsc, executor::block_on, SinkExt};
 use prometheus_exporter::PrometheusExporterConfig;
+use prover_dal::ProverConnectionPool;
 use structopt::StructOpt;
 use tokio::sync::watch;
 use zksync_config::{
@@ -103,7 +104,7 @@ async fn main() -> anyhow::Result<()> {
     .build()
     .await
     .context("failed to build a connection_pool")?;
-    let prover_connection_pool = ConnectionPool::singleton(postgres_config.prover_url()?)
+    let prover_connection_pool = ProverConnectionPool::singleton(postgres_config.prover_url()?)
         .build()
         .await
         .context("failed to build a prover_connection_pool")?;
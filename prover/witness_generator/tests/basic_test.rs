@@ -1,8 +1,8 @@
 use std::time::Instant;
 
+use prover_dal::fri_prover_dal::types::{LeafAggregationJobMetadata, NodeAggregationJobMetadata};
 use serde::Serialize;
 use zksync_config::{configs::object_store::ObjectStoreMode, ObjectStoreConfig};
-use zksync_dal::fri_prover_dal::types::{LeafAggregationJobMetadata, NodeAggregationJobMetadata};
 use zksync_object_store::ObjectStoreFactory;
 use zksync_prover_fri_types::{
     keys::{AggregationsKey, FriCircuitKey},
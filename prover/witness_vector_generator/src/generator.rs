@@ -6,9 +6,11 @@ use std::{
 
 use anyhow::Context as _;
 use async_trait::async_trait;
+use prover_dal::{fri_prover_dal::types::GpuProverInstanceStatus, ProverConnectionPool};
 use tokio::{task::JoinHandle, time::sleep};
-use zksync_config::configs::FriWitnessVectorGeneratorConfig;
-use zksync_dal::{fri_prover_dal::types::GpuProverInstanceStatus, ConnectionPool};
+use zksync_config::configs::{
+    fri_prover_group::FriProverSchedulingConfig, FriWitnessVectorGeneratorConfig,
+};
 use zksync_object_store::ObjectStore;
 use zksync_prover_fri_types::{
     circuit_definitions::{
@@ -22,7 +24,7 @@ use zksync_prover_fri_types::{
     CircuitWrapper, ProverJob, WitnessVectorArtifacts,
 };
 use zksync_prover_fri_utils::{
-    fetch_next_circuit, get_numeric_circuit_id, socket_utils::send_assembly,
+    fetch_next_circuit, get_numeric_circuit_id, socket_utils::send_assembly, ProverJobScheduler,
 };
 use zksync_queued_job_processor::JobProcessor;
 use zksync_types::{basic_fri_types::CircuitIdRoundTuple, protocol_version::L1VerifierConfig};
@@ -32,8 +34,9 @@ use crate::metrics::METRICS;
 
 pub struct WitnessVectorGenerator {
     blob_store: Arc<dyn ObjectStore>,
-    pool: ConnectionPool,
+    pool: ProverConnectionPool,
     circuit_ids_for_round_to_be_proven: Vec<CircuitIdRoundTuple>,
+    job_scheduler: ProverJobScheduler,
     zone: String,
     config: FriWitnessVectorGeneratorConfig,
     vk_commitments: L1VerifierConfig,
@@ -43,8 +46,9 @@ pub struct WitnessVectorGenerator {
 impl WitnessVectorGenerator {
     pub fn new(
         blob_store: Arc<dyn ObjectStore>,
-        prover_connection_pool: ConnectionPool,
+        prover_connection_pool: ProverConnectionPool,
         circuit_ids_for_round_to_be_proven: Vec<CircuitIdRoundTuple>,
+        scheduling_config: FriProverSchedulingConfig,
         zone: String,
         config: FriWitnessVectorGeneratorConfig,
         vk_commitments: L1VerifierConfig,
@@ -54,6 +58,7 @@ impl WitnessVectorGenerator {
             blob_store,
             pool: prover_connection_pool,
             circuit_ids_for_round_to_be_proven,
+            job_scheduler: ProverJobScheduler::new(scheduling_config),
             zone,
             config,
             vk_commitments,
@@ -100,6 +105,7 @@ impl JobProcessor for WitnessVectorGenerator {
             &*self.blob_store,
             &self.circuit_ids_for_round_to_be_proven,
             &self.vk_commitments,
+            &self.job_scheduler,
         )
         .await
         else {
@@ -229,7 +235,7 @@ async fn handle_send_result(
     result: &Result<(Duration, u64), String>,
     job_id: u32,
     address: &SocketAddr,
-    pool: &ConnectionPool,
+    pool: &ProverConnectionPool,
     zone: String,
 ) {
     match result {
@@ -2,19 +2,22 @@
 
 use anyhow::Context as _;
 use prometheus_exporter::PrometheusExporterConfig;
+use prover_dal::ProverConnectionPool;
 use structopt::StructOpt;
 use tokio::sync::{oneshot, watch};
 use zksync_config::configs::{
-    fri_prover_group::FriProverGroupConfig, FriProverConfig, FriWitnessVectorGeneratorConfig,
-    ObservabilityConfig, PostgresConfig,
+    fri_prover::KeystoreSource, fri_prover_group::FriProverGroupConfig, FriProverConfig,
+    FriWitnessVectorGeneratorConfig, ObservabilityConfig, PostgresConfig,
 };
-use zksync_dal::ConnectionPool;
 use zksync_env_config::{object_store::ProverObjectStoreConfig, FromEnv};
 use zksync_object_store::ObjectStoreFactory;
 use zksync_prover_fri_utils::{get_all_circuit_id_round_tuples_for, region_fetcher::get_zone};
 use zksync_queued_job_processor::JobProcessor;
 use zksync_utils::wait_for_tasks::wait_for_tasks;
-use zksync_vk_setup_data_server_fri::commitment_utils::get_cached_commitments;
+use zksync_vk_setup_data_server_fri::{
+    commitment_utils::get_cached_commitments, keystore::Keystore,
+    object_store_keystore::ObjectStoreKeystore,
+};
 
 use crate::generator::WitnessVectorGenerator;
 
@@ -57,7 +60,7 @@ async fn main() -> anyhow::Result<()> {
     let exporter_config = PrometheusExporterConfig::pull(config.prometheus_listener_port);
 
     let postgres_config = PostgresConfig::from_env().context("PostgresConfig::from_env()")?;
-    let pool = ConnectionPool::singleton(postgres_config.prover_url()?)
+    let pool = ProverConnectionPool::singleton(postgres_config.prover_url()?)
         .build()
         .await
         .context("failed to build a connection pool")?;
@@ -66,13 +69,21 @@ async fn main() -> anyhow::Result<()> {
     let blob_store = ObjectStoreFactory::new(object_store_config.0)
         .create_store()
         .await;
-    let circuit_ids_for_round_to_be_proven = FriProverGroupConfig::from_env()
-        .context("FriProverGroupConfig::from_env()")?
+    let prover_group_config =
+        FriProverGroupConfig::from_env().context("FriProverGroupConfig::from_env()")?;
+    let circuit_ids_for_round_to_be_proven = prover_group_config
         .get_circuit_ids_for_group_id(specialized_group_id)
         .unwrap_or_default();
     let circuit_ids_for_round_to_be_proven =
         get_all_circuit_id_round_tuples_for(circuit_ids_for_round_to_be_proven);
     let fri_prover_config = FriProverConfig::from_env().context("FriProverConfig::from_env()")?;
+    if fri_prover_config.keystore_source == KeystoreSource::ObjectStore {
+        // Witness vector generation doesn't need setup data, only verification keys and finalization hints.
+        ObjectStoreKeystore::new(blob_store.clone(), Keystore::default())
+            .fetch_keys(&[])
+            .await
+            .context("failed fetching prover keys from object store")?;
+    }
     let zone_url = &fri_prover_config.zone_read_url;
     let zone = get_zone(zone_url).await.context("get_zone()")?;
     let vk_commitments = get_cached_commitments();
@@ -80,6 +91,7 @@ async fn main() -> anyhow::Result<()> {
         blob_store,
         pool,
         circuit_ids_for_round_to_be_proven.clone(),
+        prover_group_config.scheduling,
         zone.clone(),
         config,
         vk_commitments,
@@ -19,6 +19,8 @@ pub enum ClientError {
 
     #[error("Operation timeout")]
     OperationTimeout,
+    #[error("Withdrawal cannot be finalized yet: {0}")]
+    WithdrawalNotReady(String),
     #[error("Polling interval is too small")]
     PollingIntervalIsTooSmall,
 
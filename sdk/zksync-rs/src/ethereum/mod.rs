@@ -24,6 +24,7 @@ use zksync_types::{
 };
 use zksync_web3_decl::namespaces::{EthNamespaceClient, ZksNamespaceClient};
 
+pub use self::withdrawal::WithdrawalFinalizationParams;
 use crate::{
     error::ClientError,
     operations::SyncTransactionHandle,
@@ -31,6 +32,8 @@ use crate::{
     web3::ethabi::Bytes,
 };
 
+mod withdrawal;
+
 const IERC20_INTERFACE: &str = include_str!("../abi/IERC20.json");
 const ZKSYNC_INTERFACE: &str = include_str!("../abi/IZkSync.json");
 const L1_DEFAULT_BRIDGE_INTERFACE: &str = include_str!("../abi/IL1Bridge.json");
@@ -66,6 +69,7 @@ pub struct EthereumProvider<S: EthereumSigner> {
     erc20_abi: ethabi::Contract,
     l1_bridge_abi: ethabi::Contract,
     confirmation_timeout: Duration,
+    batch_execution_timeout: Duration,
     polling_interval: Duration,
 }
 
@@ -118,6 +122,7 @@ impl<S: EthereumSigner> EthereumProvider<S> {
             erc20_abi,
             l1_bridge_abi,
             confirmation_timeout: Duration::from_secs(10),
+            batch_execution_timeout: Duration::from_secs(3_600),
             polling_interval: Duration::from_secs(1),
         })
     }
@@ -561,6 +566,12 @@ impl<S: EthereumSigner> EthereumProvider<S> {
         self.confirmation_timeout = timeout;
     }
 
+    /// Sets the timeout to wait for L1 batches with withdrawals to be executed on L1.
+    /// By default it is set to 1 hour.
+    pub fn set_batch_execution_timeout(&mut self, timeout: Duration) {
+        self.batch_execution_timeout = timeout;
+    }
+
     pub fn set_polling_interval(&mut self, polling_interval: Duration) {
         self.polling_interval = polling_interval;
     }
@@ -0,0 +1,407 @@
+//! Finalization of withdrawals on L1.
+
+use std::{convert::TryFrom, time::Instant};
+
+use zksync_eth_client::{BoundEthInterface, CallFunctionArgs, EthInterface, Options};
+use zksync_eth_signer::EthereumSigner;
+use zksync_types::{
+    api,
+    web3::{contract::tokens::Detokenize, ethabi},
+    Address, L1BatchNumber, H256, L1_MESSENGER_ADDRESS, L2_ETH_TOKEN_ADDRESS, U256,
+};
+use zksync_web3_decl::namespaces::{EthNamespaceClient, ZksNamespaceClient};
+
+use super::EthereumProvider;
+use crate::error::ClientError;
+
+/// Gas limit used for withdrawal finalization transactions if it's not specified explicitly.
+const FINALIZE_WITHDRAWAL_GAS_LIMIT: u64 = 500_000;
+
+/// Data necessary to finalize a withdrawal on L1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WithdrawalFinalizationParams {
+    /// L1 batch containing the withdrawal transaction.
+    pub l1_batch_number: L1BatchNumber,
+    /// Index of the withdrawal message in the Merkle tree of L2-to-L1 logs of the batch.
+    pub l2_message_index: u32,
+    /// Index of the withdrawal transaction in the batch.
+    pub l2_tx_number_in_batch: u16,
+    /// Message sent to L1 by the withdrawal transaction.
+    pub message: Vec<u8>,
+    /// L2 sender of the message: the L2 ETH token contract for ETH withdrawals, or the L2 bridge for ERC20 withdrawals.
+    pub sender: Address,
+    /// Merkle proof of the message inclusion.
+    pub proof: Vec<H256>,
+}
+
+impl WithdrawalFinalizationParams {
+    /// Checks whether these params correspond to an ETH withdrawal.
+    pub fn is_eth_withdrawal(&self) -> bool {
+        self.sender == L2_ETH_TOKEN_ADDRESS
+    }
+
+    fn finalization_tokens(&self) -> Vec<ethabi::Token> {
+        let proof = self
+            .proof
+            .iter()
+            .map(|hash| ethabi::Token::FixedBytes(hash.as_bytes().to_vec()))
+            .collect();
+        vec![
+            ethabi::Token::Uint(self.l1_batch_number.0.into()),
+            ethabi::Token::Uint(self.l2_message_index.into()),
+            ethabi::Token::Uint(self.l2_tx_number_in_batch.into()),
+            ethabi::Token::Bytes(self.message.clone()),
+            ethabi::Token::Array(proof),
+        ]
+    }
+}
+
+/// Withdrawal data extracted from the receipt of a withdrawal transaction.
+#[derive(Debug, PartialEq)]
+struct WithdrawalLog {
+    l1_batch_number: L1BatchNumber,
+    l2_tx_number_in_batch: u16,
+    sender: Address,
+    message: Vec<u8>,
+    /// Index of the corresponding L2-to-L1 log among logs produced by the transaction.
+    l2_to_l1_log_index: usize,
+}
+
+impl WithdrawalLog {
+    fn new(receipt: &api::TransactionReceipt) -> Result<Self, ClientError> {
+        let l1_batch_number = receipt.l1_batch_number.ok_or_else(|| {
+            ClientError::WithdrawalNotReady("transaction is not included into an L1 batch".into())
+        })?;
+        let l2_tx_number_in_batch = receipt.l1_batch_tx_index.ok_or_else(|| {
+            ClientError::WithdrawalNotReady("transaction is not included into an L1 batch".into())
+        })?;
+        let l2_tx_number_in_batch = u16::try_from(l2_tx_number_in_batch.as_u64())
+            .map_err(|_| ClientError::MalformedResponse("L1 batch tx index overflow".into()))?;
+
+        let message_sent_signature = ethabi::long_signature(
+            "L1MessageSent",
+            &[
+                ethabi::ParamType::Address,
+                ethabi::ParamType::FixedBytes(32),
+                ethabi::ParamType::Bytes,
+            ],
+        );
+        // Withdrawals are performed by sending a message to L1 via the L1 messenger system contract.
+        let message_log = receipt
+            .logs
+            .iter()
+            .find(|log| {
+                log.address == L1_MESSENGER_ADDRESS
+                    && log.topics.first() == Some(&message_sent_signature)
+            })
+            .ok_or(ClientError::IncorrectInput)?;
+        let sender = message_log.topics.get(1).ok_or_else(|| {
+            ClientError::MalformedResponse("L1 message log has no sender topic".into())
+        })?;
+        let sender = Address::from_slice(&sender.as_bytes()[12..]);
+        let message = ethabi::decode(&[ethabi::ParamType::Bytes], &message_log.data.0)
+            .ok()
+            .and_then(|tokens| tokens.into_iter().next()?.into_bytes())
+            .ok_or_else(|| {
+                ClientError::MalformedResponse("cannot decode message from L1 message log".into())
+            })?;
+
+        let l2_to_l1_log_index = receipt
+            .l2_to_l1_logs
+            .iter()
+            .position(|log| log.sender == L1_MESSENGER_ADDRESS)
+            .ok_or_else(|| {
+                ClientError::MalformedResponse(
+                    "no L2-to-L1 log corresponding to the L1 message".into(),
+                )
+            })?;
+
+        Ok(Self {
+            l1_batch_number: L1BatchNumber(l1_batch_number.as_u32()),
+            l2_tx_number_in_batch,
+            sender,
+            message,
+            l2_to_l1_log_index,
+        })
+    }
+}
+
+impl<S: EthereumSigner> EthereumProvider<S> {
+    /// Returns the data necessary to finalize a withdrawal initiated by the specified L2 transaction.
+    /// The transaction must be included into a sealed L1 batch.
+    pub async fn withdrawal_finalization_params<P>(
+        &self,
+        provider: &P,
+        withdrawal_hash: H256,
+    ) -> Result<WithdrawalFinalizationParams, ClientError>
+    where
+        P: EthNamespaceClient + ZksNamespaceClient + Sync,
+    {
+        let receipt = provider
+            .get_transaction_receipt(withdrawal_hash)
+            .await?
+            .ok_or_else(|| ClientError::WithdrawalNotReady("transaction is not executed".into()))?;
+        let log = WithdrawalLog::new(&receipt)?;
+        let proof = provider
+            .get_l2_to_l1_log_proof(withdrawal_hash, Some(log.l2_to_l1_log_index))
+            .await?
+            .ok_or_else(|| ClientError::WithdrawalNotReady("L1 batch is not sealed".into()))?;
+
+        Ok(WithdrawalFinalizationParams {
+            l1_batch_number: log.l1_batch_number,
+            l2_message_index: proof.id,
+            l2_tx_number_in_batch: log.l2_tx_number_in_batch,
+            message: log.message,
+            sender: log.sender,
+            proof: proof.proof,
+        })
+    }
+
+    /// Waits until the L1 batch containing the specified withdrawal transaction is executed on L1,
+    /// which is necessary to finalize the withdrawal. Returns the number of this batch.
+    ///
+    /// The wait time is limited by the timeout set via [`Self::set_batch_execution_timeout()`].
+    pub async fn wait_for_withdrawal_execution<P>(
+        &self,
+        provider: &P,
+        withdrawal_hash: H256,
+    ) -> Result<L1BatchNumber, ClientError>
+    where
+        P: EthNamespaceClient + ZksNamespaceClient + Sync,
+    {
+        let mut poller = tokio::time::interval(self.polling_interval);
+
+        let start = Instant::now();
+        loop {
+            let l1_batch_number = provider
+                .get_transaction_receipt(withdrawal_hash)
+                .await?
+                .and_then(|receipt| receipt.l1_batch_number);
+            if let Some(l1_batch_number) = l1_batch_number {
+                let l1_batch_number = L1BatchNumber(l1_batch_number.as_u32());
+                let details = provider.get_l1_batch_details(l1_batch_number).await?;
+                if details.map_or(false, |details| details.base.executed_at.is_some()) {
+                    return Ok(l1_batch_number);
+                }
+            }
+
+            if start.elapsed() > self.batch_execution_timeout {
+                return Err(ClientError::OperationTimeout);
+            }
+            poller.tick().await;
+        }
+    }
+
+    /// Finalizes a withdrawal initiated by the specified L2 transaction by sending a transaction to
+    /// the zkSync contract (for ETH withdrawals) or the L1 bridge (for ERC20 withdrawals).
+    /// Waits until the L1 batch with the withdrawal transaction is executed on L1 before that.
+    ///
+    /// `l1_bridge` must be specified for ERC20 withdrawals via custom bridges; otherwise, the default bridge is used.
+    pub async fn finalize_withdrawal<P>(
+        &self,
+        provider: &P,
+        withdrawal_hash: H256,
+        l1_bridge: Option<Address>,
+        eth_options: Option<Options>,
+    ) -> Result<H256, ClientError>
+    where
+        P: EthNamespaceClient + ZksNamespaceClient + Sync,
+    {
+        self.wait_for_withdrawal_execution(provider, withdrawal_hash)
+            .await?;
+        let params = self
+            .withdrawal_finalization_params(provider, withdrawal_hash)
+            .await?;
+
+        let mut options = eth_options.unwrap_or_default();
+        options
+            .gas
+            .get_or_insert(FINALIZE_WITHDRAWAL_GAS_LIMIT.into());
+        let tokens = params.finalization_tokens();
+        let signed_tx = if params.is_eth_withdrawal() {
+            let data = self
+                .eth_client
+                .encode_tx_data("finalizeEthWithdrawal", tokens);
+            self.eth_client
+                .sign_prepared_tx(data, options, "provider")
+                .await
+        } else {
+            let bridge = self.l1_bridge_for_withdrawal(params.sender, l1_bridge)?;
+            let data = self
+                .l1_bridge_abi
+                .function("finalizeWithdrawal")
+                .expect("failed to get function parameters")
+                .encode_input(&tokens)
+                .expect("failed to encode parameters");
+            self.eth_client
+                .sign_prepared_tx_for_addr(data, bridge, options, "provider")
+                .await
+        };
+        let signed_tx = signed_tx.map_err(|_| ClientError::IncorrectCredentials)?;
+
+        let transaction_hash = self
+            .eth_client
+            .send_raw_tx(signed_tx.raw_tx)
+            .await
+            .map_err(|err| ClientError::NetworkError(err.to_string()))?;
+        Ok(transaction_hash)
+    }
+
+    /// Checks whether a withdrawal initiated by the specified L2 transaction is finalized on L1.
+    ///
+    /// `l1_bridge` must be specified for ERC20 withdrawals via custom bridges; otherwise, the default bridge is used.
+    pub async fn is_withdrawal_finalized<P>(
+        &self,
+        provider: &P,
+        withdrawal_hash: H256,
+        l1_bridge: Option<Address>,
+    ) -> Result<bool, ClientError>
+    where
+        P: EthNamespaceClient + ZksNamespaceClient + Sync,
+    {
+        let params = self
+            .withdrawal_finalization_params(provider, withdrawal_hash)
+            .await?;
+        let args = (
+            U256::from(params.l1_batch_number.0),
+            U256::from(params.l2_message_index),
+        );
+
+        let res = if params.is_eth_withdrawal() {
+            let args = CallFunctionArgs::new("isEthWithdrawalFinalized", args);
+            self.eth_client.call_main_contract_function(args).await
+        } else {
+            let bridge = self.l1_bridge_for_withdrawal(params.sender, l1_bridge)?;
+            let args = CallFunctionArgs::new("isWithdrawalFinalized", args)
+                .for_contract(bridge, self.l1_bridge_abi.clone());
+            self.eth_client.call_contract_function(args).await
+        };
+        let res = res.map_err(|err| ClientError::NetworkError(err.to_string()))?;
+        bool::from_tokens(res).map_err(|err| ClientError::MalformedResponse(err.to_string()))
+    }
+
+    /// Returns the L1 bridge corresponding to the L2 bridge that has sent a withdrawal message.
+    fn l1_bridge_for_withdrawal(
+        &self,
+        l2_sender: Address,
+        l1_bridge: Option<Address>,
+    ) -> Result<Address, ClientError> {
+        if let Some(l1_bridge) = l1_bridge {
+            return Ok(l1_bridge);
+        }
+
+        let bridges = &self.default_bridges;
+        match (bridges.l2_weth_bridge, bridges.l1_weth_bridge) {
+            _ if l2_sender == bridges.l2_erc20_default_bridge => {
+                Ok(bridges.l1_erc20_default_bridge)
+            }
+            (Some(l2_weth_bridge), Some(l1_weth_bridge)) if l2_sender == l2_weth_bridge => {
+                Ok(l1_weth_bridge)
+            }
+            // The withdrawal was performed via a custom bridge, so the corresponding L1 bridge is unknown.
+            _ => Err(ClientError::IncorrectAddress),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{api::Log, web3::types::Bytes, U64};
+
+    use super::*;
+
+    fn l2_to_l1_log(sender: Address) -> api::L2ToL1Log {
+        api::L2ToL1Log {
+            block_hash: None,
+            block_number: U64::from(1),
+            l1_batch_number: Some(U64::from(3)),
+            log_index: U256::zero(),
+            transaction_index: U64::from(0),
+            transaction_hash: H256::zero(),
+            transaction_log_index: U256::zero(),
+            tx_index_in_l1_batch: Some(U64::from(5)),
+            shard_id: U64::zero(),
+            is_service: false,
+            sender,
+            key: H256::zero(),
+            value: H256::zero(),
+        }
+    }
+
+    fn log(address: Address, topics: Vec<H256>, data: Vec<u8>) -> Log {
+        Log {
+            address,
+            topics,
+            data: Bytes(data),
+            block_hash: None,
+            block_number: None,
+            l1_batch_number: None,
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            transaction_log_index: None,
+            log_type: None,
+            removed: None,
+        }
+    }
+
+    #[test]
+    fn parsing_withdrawal_log() {
+        let message_sent_signature = ethabi::long_signature(
+            "L1MessageSent",
+            &[
+                ethabi::ParamType::Address,
+                ethabi::ParamType::FixedBytes(32),
+                ethabi::ParamType::Bytes,
+            ],
+        );
+        let message = b"withdrawal message".to_vec();
+        let message_log = log(
+            L1_MESSENGER_ADDRESS,
+            vec![
+                message_sent_signature,
+                H256::from(L2_ETH_TOKEN_ADDRESS),
+                H256::repeat_byte(1),
+            ],
+            ethabi::encode(&[ethabi::Token::Bytes(message.clone())]),
+        );
+        let transfer_log = log(L2_ETH_TOKEN_ADDRESS, vec![H256::repeat_byte(2)], vec![]);
+        let receipt = api::TransactionReceipt {
+            l1_batch_number: Some(U64::from(3)),
+            l1_batch_tx_index: Some(U64::from(5)),
+            logs: vec![transfer_log, message_log],
+            l2_to_l1_logs: vec![
+                l2_to_l1_log(Address::repeat_byte(0x11)),
+                l2_to_l1_log(L1_MESSENGER_ADDRESS),
+            ],
+            ..api::TransactionReceipt::default()
+        };
+
+        let withdrawal_log = WithdrawalLog::new(&receipt).unwrap();
+        assert_eq!(
+            withdrawal_log,
+            WithdrawalLog {
+                l1_batch_number: L1BatchNumber(3),
+                l2_tx_number_in_batch: 5,
+                sender: L2_ETH_TOKEN_ADDRESS,
+                message,
+                l2_to_l1_log_index: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn parsing_non_withdrawal_receipt() {
+        let receipt = api::TransactionReceipt {
+            l1_batch_number: Some(U64::from(3)),
+            l1_batch_tx_index: Some(U64::from(5)),
+            ..api::TransactionReceipt::default()
+        };
+        let err = WithdrawalLog::new(&receipt).unwrap_err();
+        assert!(matches!(err, ClientError::IncorrectInput), "{err:?}");
+
+        let receipt = api::TransactionReceipt::default();
+        let err = WithdrawalLog::new(&receipt).unwrap_err();
+        assert!(matches!(err, ClientError::WithdrawalNotReady(_)), "{err:?}");
+    }
+}
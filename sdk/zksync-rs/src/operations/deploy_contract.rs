@@ -5,7 +5,10 @@ use zksync_types::{
 use zksync_utils::bytecode::hash_bytecode;
 
 use crate::{
-    error::ClientError, operations::SyncTransactionHandle, wallet::Wallet, zksync_types::fee::Fee,
+    error::ClientError,
+    operations::{set_signature_placeholder, SyncTransactionHandle},
+    wallet::Wallet,
+    zksync_types::fee::Fee,
     EthNamespaceClient, ZksNamespaceClient,
 };
 
@@ -143,7 +146,7 @@ where
         let main_contract_hash = hash_bytecode(&bytecode);
         let mut factory_deps = self.factory_deps.clone().unwrap_or_default();
         factory_deps.push(bytecode);
-        let l2_tx = L2Tx::new(
+        let mut l2_tx = L2Tx::new(
             CONTRACT_DEPLOYER_ADDRESS,
             Execute::encode_deploy_params_create(Default::default(), main_contract_hash, calldata),
             Nonce(0),
@@ -153,6 +156,7 @@ where
             Some(factory_deps),
             paymaster_params,
         );
+        set_signature_placeholder(&mut l2_tx, &self.wallet.signer);
         self.wallet
             .provider
             .estimate_fee(l2_tx.into())
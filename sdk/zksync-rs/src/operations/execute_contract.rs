@@ -4,8 +4,10 @@ use zksync_types::{
 };
 
 use crate::{
-    error::ClientError, operations::SyncTransactionHandle, wallet::Wallet, EthNamespaceClient,
-    ZksNamespaceClient,
+    error::ClientError,
+    operations::{set_signature_placeholder, SyncTransactionHandle},
+    wallet::Wallet,
+    EthNamespaceClient, ZksNamespaceClient,
 };
 
 pub struct ExecuteContractBuilder<'a, S: EthereumSigner, P> {
@@ -143,7 +145,7 @@ where
             .or_else(|| self.paymaster_params.clone())
             .unwrap_or_default();
 
-        let execute = L2Tx::new(
+        let mut execute = L2Tx::new(
             contract_address,
             calldata,
             Nonce(0),
@@ -153,6 +155,7 @@ where
             self.factory_deps.clone(),
             paymaster_params,
         );
+        set_signature_placeholder(&mut execute, &self.wallet.signer);
         self.wallet
             .provider
             .estimate_fee(execute.into())
@@ -2,6 +2,7 @@
 
 use std::time::{Duration, Instant};
 
+use zksync_eth_signer::EthereumSigner;
 use zksync_types::{
     api::{BlockNumber, TransactionReceipt},
     l2::L2Tx,
@@ -14,7 +15,7 @@ pub use self::{
     transfer::{create_transfer_calldata, TransferBuilder},
     withdraw::WithdrawBuilder,
 };
-use crate::{error::ClientError, EthNamespaceClient};
+use crate::{error::ClientError, signer::Signer, EthNamespaceClient};
 
 mod deploy_contract;
 mod execute_contract;
@@ -29,6 +30,12 @@ pub fn encode_transaction(tx: &L2Tx, chain_id: L2ChainId) -> Result<Bytes, Clien
     Ok(tx.get_rlp_bytes(chain_id))
 }
 
+/// Sets a signature placeholder for a transaction used in fee estimation, so that the estimate
+/// covers signature validation by the account.
+pub(crate) fn set_signature_placeholder<S: EthereumSigner>(tx: &mut L2Tx, signer: &Signer<S>) {
+    tx.set_raw_signature(signer.dummy_signature());
+}
+
 /// Handle for transaction, providing an interface to control its execution.
 /// For obtained handle it's possible to set the polling interval, commit timeout
 /// and verify timeout values.
@@ -4,7 +4,7 @@ use zksync_types::{fee::Fee, l2::L2Tx, Address, Nonce, L2_ETH_TOKEN_ADDRESS, U25
 use crate::{
     error::ClientError,
     ethereum::ierc20_contract,
-    operations::SyncTransactionHandle,
+    operations::{set_signature_placeholder, SyncTransactionHandle},
     wallet::Wallet,
     web3::contract::tokens::Tokenize,
     zksync_types::{transaction_request::PaymasterParams, Execute, L2TxCommonData},
@@ -173,12 +173,13 @@ where
             paymaster_params,
             ..Default::default()
         };
-        let l2_tx = L2Tx {
+        let mut l2_tx = L2Tx {
             common_data,
             execute: tx,
             received_timestamp_ms: 0,
             raw_bytes: None,
         };
+        set_signature_placeholder(&mut l2_tx, &self.wallet.signer);
         self.wallet
             .provider
             .estimate_fee(l2_tx.into())
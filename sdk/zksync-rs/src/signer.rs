@@ -1,5 +1,6 @@
-use std::fmt::Debug;
+use std::fmt;
 
+use async_trait::async_trait;
 use zksync_eth_signer::{error::SignerError, EthereumSigner};
 use zksync_types::{
     fee::Fee, l2::L2Tx, transaction_request::PaymasterParams, Address, Eip712Domain, L2ChainId,
@@ -12,26 +13,197 @@ fn signing_failed_error(err: impl ToString) -> SignerError {
     SignerError::SigningFailed(err.to_string())
 }
 
+/// Length of an ECDSA signature in the packed `(r, s, v)` format.
+const ECDSA_SIGNATURE_LEN: usize = 65;
+
+/// Returns an ECDSA signature placeholder with the minimal valid `r` and `s` values and `v = 27`,
+/// so that it passes format checks performed by account contracts.
+fn dummy_ecdsa_signature() -> [u8; ECDSA_SIGNATURE_LEN] {
+    let mut signature = [0_u8; ECDSA_SIGNATURE_LEN];
+    signature[31] = 1;
+    signature[63] = 1;
+    signature[64] = 27;
+    signature
+}
+
+/// Signer of L2 transactions on behalf of an account.
+///
+/// Unlike [`EthereumSigner`], which always produces an ECDSA signature of an externally owned account (EOA),
+/// an account signer can produce signatures in an arbitrary format expected by a custom account contract
+/// (e.g., a multisig or a session key account). The signature is passed to the account as `customSignature`.
+#[async_trait]
+pub trait AccountSigner: 'static + fmt::Debug + Send + Sync {
+    /// Returns the address of the account.
+    fn address(&self) -> Address;
+
+    /// Signs the transaction for the specified EIP-712 domain.
+    async fn sign_transaction(
+        &self,
+        domain: &Eip712Domain,
+        transaction: &TransactionRequest,
+    ) -> Result<Vec<u8>, SignerError>;
+
+    /// Returns a signature placeholder used for fee estimation. The placeholder must have the same length
+    /// as the actual signatures, so that the estimated gas covers the signature processing by the account.
+    fn dummy_signature(&self) -> Vec<u8>;
+}
+
+/// [`AccountSigner`] for externally owned accounts (EOAs).
+#[derive(Clone)]
+pub struct EoaSigner<S: EthereumSigner> {
+    eth_signer: S,
+    address: Address,
+}
+
+impl<S: EthereumSigner> fmt::Debug for EoaSigner<S> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("EoaSigner")
+            .field("address", &self.address)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: EthereumSigner> EoaSigner<S> {
+    pub fn new(eth_signer: S, address: Address) -> Self {
+        Self {
+            eth_signer,
+            address,
+        }
+    }
+}
+
+#[async_trait]
+impl<S: EthereumSigner> AccountSigner for EoaSigner<S> {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    async fn sign_transaction(
+        &self,
+        domain: &Eip712Domain,
+        transaction: &TransactionRequest,
+    ) -> Result<Vec<u8>, SignerError> {
+        let signature = self.eth_signer.sign_typed_data(domain, transaction).await?;
+        Ok(signature.serialize_packed().to_vec())
+    }
+
+    fn dummy_signature(&self) -> Vec<u8> {
+        dummy_ecdsa_signature().to_vec()
+    }
+}
+
+/// [`AccountSigner`] for multisig accounts controlled by several ECDSA keys. The signature is a concatenation
+/// of the owners' ECDSA signatures of the EIP-712 transaction hash in the order the owners are provided.
+#[derive(Clone)]
+pub struct MultisigEcdsaSigner<S: EthereumSigner> {
+    address: Address,
+    owners: Vec<S>,
+}
+
+impl<S: EthereumSigner> fmt::Debug for MultisigEcdsaSigner<S> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("MultisigEcdsaSigner")
+            .field("address", &self.address)
+            .field("owners_count", &self.owners.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: EthereumSigner> MultisigEcdsaSigner<S> {
+    /// Creates a signer for the account deployed at `address`. Returns an error if `owners` is empty.
+    pub fn new(address: Address, owners: Vec<S>) -> Result<Self, SignerError> {
+        if owners.is_empty() {
+            return Err(SignerError::CustomError(
+                "multisig account must have at least one owner".to_owned(),
+            ));
+        }
+        Ok(Self { address, owners })
+    }
+}
+
+#[async_trait]
+impl<S: EthereumSigner> AccountSigner for MultisigEcdsaSigner<S> {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    async fn sign_transaction(
+        &self,
+        domain: &Eip712Domain,
+        transaction: &TransactionRequest,
+    ) -> Result<Vec<u8>, SignerError> {
+        let mut signature = Vec::with_capacity(ECDSA_SIGNATURE_LEN * self.owners.len());
+        for owner in &self.owners {
+            let owner_signature = owner.sign_typed_data(domain, transaction).await?;
+            signature.extend_from_slice(&owner_signature.serialize_packed());
+        }
+        Ok(signature)
+    }
+
+    fn dummy_signature(&self) -> Vec<u8> {
+        dummy_ecdsa_signature().repeat(self.owners.len())
+    }
+}
+
 #[derive(Debug)]
 pub struct Signer<S: EthereumSigner> {
+    /// Signer used for L1 operations.
     pub(crate) eth_signer: S,
+    /// Signer used for L2 transactions.
+    pub(crate) account_signer: Box<dyn AccountSigner>,
     pub(crate) address: Address,
     pub(crate) chain_id: L2ChainId,
 }
 
 impl<S: EthereumSigner> Signer<S> {
+    /// Creates a signer for an externally owned account (EOA).
     pub fn new(eth_signer: S, address: Address, chain_id: L2ChainId) -> Self {
+        let account_signer = EoaSigner::new(eth_signer.clone(), address);
+        Self::with_account_signer(eth_signer, account_signer, chain_id)
+    }
+
+    /// Creates a signer for a custom account. L2 transactions are signed with `account_signer`, while `eth_signer`
+    /// is only used for L1 operations (e.g., deposits).
+    pub fn with_account_signer(
+        eth_signer: S,
+        account_signer: impl AccountSigner,
+        chain_id: L2ChainId,
+    ) -> Self {
         Self {
             eth_signer,
-            address,
+            address: account_signer.address(),
+            account_signer: Box::new(account_signer),
             chain_id,
         }
     }
 
+    /// Returns the signer used for L2 transactions.
+    pub fn account_signer(&self) -> &dyn AccountSigner {
+        self.account_signer.as_ref()
+    }
+
+    /// Returns a signature placeholder for fee estimation.
+    pub fn dummy_signature(&self) -> Vec<u8> {
+        self.account_signer.dummy_signature()
+    }
+
+    /// Signs the transaction with the ECDSA key of `eth_signer`. Returns an error for custom accounts,
+    /// which are not controlled by this key; use [`Self::sign_transaction_raw()`] for them instead.
     pub async fn sign_transaction(
         &self,
         transaction: &L2Tx,
     ) -> Result<PackedEthSignature, SignerError> {
+        let eth_signer_address = self.eth_signer.get_address().await?;
+        if eth_signer_address != self.address {
+            return Err(SignerError::CustomError(format!(
+                "account {:?} is not an externally owned account controlled by the Ethereum signer; \
+                 use `sign_transaction_raw()` to sign transactions for custom accounts",
+                self.address
+            )));
+        }
+
         let domain = Eip712Domain::new(self.chain_id);
         let transaction_request: TransactionRequest = transaction.clone().into();
         self.eth_signer
@@ -39,6 +211,23 @@ impl<S: EthereumSigner> Signer<S> {
             .await
     }
 
+    /// Signs the transaction with the account signer. The returned signature has the format
+    /// expected by the account (e.g., a concatenation of owners' signatures for a multisig).
+    pub async fn sign_transaction_raw(&self, transaction: &L2Tx) -> Result<Vec<u8>, SignerError> {
+        let transaction_request: TransactionRequest = transaction.clone().into();
+        self.sign_transaction_request(&transaction_request).await
+    }
+
+    pub(crate) async fn sign_transaction_request(
+        &self,
+        transaction_request: &TransactionRequest,
+    ) -> Result<Vec<u8>, SignerError> {
+        let domain = Eip712Domain::new(self.chain_id);
+        self.account_signer
+            .sign_transaction(&domain, transaction_request)
+            .await
+    }
+
     pub async fn sign_transfer(
         &self,
         to: Address,
@@ -55,17 +244,17 @@ impl<S: EthereumSigner> Signer<S> {
                 Default::default(),
                 nonce,
                 fee,
-                self.eth_signer.get_address().await?,
+                self.address,
                 amount,
                 None,
                 Default::default(),
             );
 
             let signature = self
-                .sign_transaction(&transfer)
+                .sign_transaction_raw(&transfer)
                 .await
                 .map_err(signing_failed_error)?;
-            transfer.set_signature(signature);
+            transfer.set_raw_signature(signature);
 
             return Ok(transfer);
         }
@@ -77,17 +266,17 @@ impl<S: EthereumSigner> Signer<S> {
             data,
             nonce,
             fee,
-            self.eth_signer.get_address().await?,
+            self.address,
             U256::zero(),
             None,
             paymaster_params,
         );
 
         let signature = self
-            .sign_transaction(&transfer)
+            .sign_transaction_raw(&transfer)
             .await
             .map_err(signing_failed_error)?;
-        transfer.set_signature(signature);
+        transfer.set_raw_signature(signature);
 
         Ok(transfer)
     }
@@ -126,18 +315,104 @@ impl<S: EthereumSigner> Signer<S> {
             calldata,
             nonce,
             fee,
-            self.eth_signer.get_address().await?,
+            self.address,
             U256::zero(),
             factory_deps,
             paymaster_params,
         );
 
         let signature = self
-            .sign_transaction(&execute_contract)
+            .sign_transaction_raw(&execute_contract)
             .await
             .map_err(signing_failed_error)?;
-        execute_contract.set_signature(signature);
+        execute_contract.set_raw_signature(signature);
 
         Ok(execute_contract)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use zksync_eth_signer::PrivateKeySigner;
+    use zksync_types::H256;
+
+    use super::*;
+
+    fn create_eth_signer() -> (PrivateKeySigner, Address) {
+        let private_key = H256::random();
+        let address = PackedEthSignature::address_from_private_key(&private_key).unwrap();
+        (PrivateKeySigner::new(private_key), address)
+    }
+
+    async fn sign_test_transfer(signer: &Signer<PrivateKeySigner>) -> L2Tx {
+        signer
+            .sign_transfer(
+                Address::repeat_byte(0x7e),
+                L2_ETH_TOKEN_ADDRESS,
+                100.into(),
+                Fee::default(),
+                Nonce(1),
+                PaymasterParams::default(),
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn signing_transaction_for_eoa() {
+        let (eth_signer, address) = create_eth_signer();
+        let signer = Signer::new(eth_signer, address, L2ChainId::default());
+        assert_eq!(signer.dummy_signature().len(), ECDSA_SIGNATURE_LEN);
+
+        let transfer = sign_test_transfer(&signer).await;
+        assert_eq!(transfer.initiator_account(), address);
+        let signature = &transfer.common_data.signature;
+        assert_eq!(signature.len(), ECDSA_SIGNATURE_LEN);
+        PackedEthSignature::deserialize_packed(signature).unwrap();
+
+        let eth_signature = signer.sign_transaction(&transfer).await.unwrap();
+        assert_eq!(eth_signature.serialize_packed().len(), ECDSA_SIGNATURE_LEN);
+    }
+
+    #[test]
+    fn multisig_signer_requires_owners() {
+        let err = MultisigEcdsaSigner::<PrivateKeySigner>::new(Address::repeat_byte(0x11), vec![])
+            .unwrap_err();
+        assert!(matches!(err, SignerError::CustomError(_)), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn signing_transaction_for_multisig_account() {
+        let account_address = Address::repeat_byte(0x11);
+        let owners: Vec<_> = (0..3).map(|_| create_eth_signer()).collect();
+        let account_signer = MultisigEcdsaSigner::new(
+            account_address,
+            owners.iter().map(|(signer, _)| signer.clone()).collect(),
+        )
+        .unwrap();
+        let (eth_signer, _) = create_eth_signer();
+        let signer = Signer::with_account_signer(eth_signer, account_signer, L2ChainId::default());
+        assert_eq!(signer.address, account_address);
+        assert_eq!(signer.dummy_signature().len(), ECDSA_SIGNATURE_LEN * 3);
+
+        let transfer = sign_test_transfer(&signer).await;
+        assert_eq!(transfer.initiator_account(), account_address);
+        let signature = &transfer.common_data.signature;
+        assert_eq!(signature.len(), ECDSA_SIGNATURE_LEN * 3);
+
+        // Each chunk of the signature must be the signature of the corresponding owner.
+        let domain = Eip712Domain::new(L2ChainId::default());
+        let transaction_request: TransactionRequest = transfer.clone().into();
+        for ((owner, _), chunk) in owners.iter().zip(signature.chunks(ECDSA_SIGNATURE_LEN)) {
+            let owner_signature = EoaSigner::new(owner.clone(), Address::zero())
+                .sign_transaction(&domain, &transaction_request)
+                .await
+                .unwrap();
+            assert_eq!(chunk, owner_signature.as_slice());
+        }
+
+        // The ECDSA key of the Ethereum signer doesn't control the account, so ECDSA signing must fail.
+        let err = signer.sign_transaction(&transfer).await.unwrap_err();
+        assert!(matches!(err, SignerError::CustomError(_)), "{err:?}");
+    }
+}
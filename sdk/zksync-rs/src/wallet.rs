@@ -4,7 +4,7 @@ use zksync_types::{
     l2::L2Tx,
     tokens::ETHEREUM_ADDRESS,
     transaction_request::CallRequest,
-    Address, Bytes, Eip712Domain, U256,
+    Address, Bytes, PackedEthSignature, U256,
 };
 use zksync_web3_decl::{
     jsonrpsee::http_client::{HttpClient, HttpClientBuilder},
@@ -140,9 +140,9 @@ where
         &self,
         tx: L2Tx,
     ) -> Result<SyncTransactionHandle<'_, P>, ClientError> {
-        // Since we sign the transaction with the Ethereum signature later on,
+        // Since we sign the transaction with the account signer later on,
         // we might want to get rid of the signature and the initiator left from `L2Tx`.
-        let transaction_request: TransactionRequest = {
+        let mut transaction_request: TransactionRequest = {
             let mut req: TransactionRequest = tx.into();
             if let Some(meta) = req.eip712_meta.as_mut() {
                 meta.custom_signature = None;
@@ -150,14 +150,18 @@ where
             req.from = Some(self.address());
             req
         };
-        let domain = Eip712Domain::new(self.signer.chain_id);
         let signature = self
             .signer
-            .eth_signer
-            .sign_typed_data(&domain, &transaction_request)
+            .sign_transaction_request(&transaction_request)
             .await?;
 
-        let encoded_tx = transaction_request.get_signed_bytes(&signature, self.signer.chain_id);
+        // ECDSA signatures of EOAs are additionally encoded as `v`, `r` and `s`, while signatures
+        // of custom accounts are only passed as the custom signature.
+        let eth_signature = PackedEthSignature::deserialize_packed(&signature).unwrap_or_default();
+        if let Some(meta) = transaction_request.eip712_meta.as_mut() {
+            meta.custom_signature = Some(signature);
+        }
+        let encoded_tx = transaction_request.get_signed_bytes(&eth_signature, self.signer.chain_id);
         let bytes = Bytes(encoded_tx);
 
         let tx_hash = self.provider.send_raw_transaction(bytes).await?;